@@ -0,0 +1,24 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use ethereum_ibc::cache::{validate_sync_committee, NoCache, RecentSyncCommittees};
+use ethereum_ibc::MAINNET_SYNC_COMMITTEE_SIZE;
+use ethereum_light_client_verifier::consensus::test_utils::MockSyncCommitteeManager;
+
+/// Compares the validation of a mainnet-sized sync committee without a cache and with a warm cache
+fn bench_validate_sync_committee(c: &mut Criterion) {
+    let scm = MockSyncCommitteeManager::<MAINNET_SYNC_COMMITTEE_SIZE>::new(1, 1);
+    let sync_committee = scm.get_committee(1).to_committee().clone();
+
+    let mut group = c.benchmark_group("validate_sync_committee");
+    group.bench_function("no_cache", |b| {
+        b.iter(|| validate_sync_committee(&mut NoCache, &sync_committee).unwrap())
+    });
+    let mut cache = RecentSyncCommittees::new(2);
+    validate_sync_committee(&mut cache, &sync_committee).unwrap();
+    group.bench_function("warm_cache", |b| {
+        b.iter(|| validate_sync_committee(&mut cache, &sync_committee).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_validate_sync_committee);
+criterion_main!(benches);
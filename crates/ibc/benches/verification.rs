@@ -0,0 +1,363 @@
+//! Benchmarks of the verification of headers, misbehaviours and commitments with a mainnet-sized sync committee
+//!
+//! The headers and misbehaviours are generated from the deterministic keys of `MockSyncCommitteeManager` at fixed slots,
+//! so every run verifies the same bytes. The commitment proofs are the checked-in proofs of the unit tests.
+//!
+//! `header` benchmarks `verify::verify_header`, which `ClientState::check_header_and_update_state` delegates to after reading the consensus states from the host.
+//! Hot spots to measure follow-up optimizations against:
+//! - the trusted sync committee is validated twice per header, by `Header::validate` and `TrustedConsensusState::new` (see `cache_hit`)
+//! - the branch accessors of `ConsensusUpdate` and `ExecutionUpdate` allocate a new `Vec` per call
+//! - `commitment::verify_membership` copies the proof once since `CommitmentProofBytes` does not lend its bytes, while the trie nodes are walked in place
+use criterion::{criterion_group, criterion_main, Criterion};
+use ethereum_ibc::address::Address;
+use ethereum_ibc::cache::RecentSyncCommittees;
+use ethereum_ibc::client_state::ClientState;
+use ethereum_ibc::commitment::{self, CommitmentValueCodec};
+use ethereum_ibc::consensus::beacon::{Slot, Version};
+use ethereum_ibc::consensus::compute::compute_timestamp_at_slot;
+use ethereum_ibc::consensus::context::ChainContext;
+use ethereum_ibc::consensus::fork::{
+    altair::ALTAIR_FORK_SPEC, bellatrix::BELLATRIX_FORK_SPEC, capella::CAPELLA_FORK_SPEC,
+    deneb::DENEB_FORK_SPEC, ForkParameter, ForkParameters,
+};
+use ethereum_ibc::consensus::preset::mainnet::PRESET;
+use ethereum_ibc::consensus::types::{H256, U64};
+use ethereum_ibc::consensus_state::ConsensusState;
+use ethereum_ibc::header::Header;
+use ethereum_ibc::light_client_verifier::consensus::test_utils::{
+    gen_light_client_update_with_params, MockSyncCommitteeManager,
+};
+use ethereum_ibc::light_client_verifier::context::Fraction;
+use ethereum_ibc::light_client_verifier::misbehaviour::{
+    FinalizedHeaderMisbehaviour, Misbehaviour as MisbehaviourData,
+};
+use ethereum_ibc::light_client_verifier::updates::{
+    ConsensusUpdateInfo as EthConsensusUpdateInfo, ExecutionUpdate,
+};
+use ethereum_ibc::misbehaviour::Misbehaviour;
+use ethereum_ibc::misc::height_from_block_number;
+use ethereum_ibc::types::{
+    AccountUpdateInfo, ConsensusUpdateInfo, ExecutionUpdateInfo, TrustedSyncCommittee,
+};
+use ethereum_ibc::verify::{verify_header, verify_header_with_cache, verify_misbehaviour};
+use ethereum_ibc::{eth_client_type, MAINNET_SYNC_COMMITTEE_SIZE};
+use hex_literal::hex;
+use ibc::core::ics23_commitment::commitment::{CommitmentProofBytes, CommitmentRoot};
+use ibc::core::ics24_host::identifier::ClientId;
+use ibc::core::ics24_host::Path;
+use ibc::timestamp::Timestamp;
+use ibc_proto::google::protobuf::Any as IBCAny;
+use prost::Message;
+use std::str::FromStr;
+use std::time::Duration;
+use tiny_keccak::{Hasher, Keccak};
+
+const N: usize = MAINNET_SYNC_COMMITTEE_SIZE;
+
+// the account proof of 8 nodes of `test_verify_account_storage_non_existence` of `client_state`
+const ACCOUNT_PROOF: &str = include_str!("../testdata/account_proof_non_existence.hex");
+
+struct Scenario {
+    client_state: ClientState<N>,
+    trusted_consensus_state: ConsensusState,
+    header: Header<N>,
+    now: Timestamp,
+}
+
+fn new_client_state() -> ClientState<N> {
+    ClientState {
+        genesis_validators_root: H256([1u8; 32]),
+        min_sync_committee_participants: 1.into(),
+        genesis_time: 1.into(),
+        fork_parameters: ForkParameters::new(
+            Version([0, 0, 0, 1]),
+            vec![
+                ForkParameter::new(Version([1, 0, 0, 1]), U64(0), ALTAIR_FORK_SPEC),
+                ForkParameter::new(Version([2, 0, 0, 1]), U64(0), BELLATRIX_FORK_SPEC),
+                ForkParameter::new(Version([3, 0, 0, 1]), U64(0), CAPELLA_FORK_SPEC),
+                ForkParameter::new(Version([4, 0, 0, 1]), U64(0), DENEB_FORK_SPEC),
+            ],
+        )
+        .unwrap(),
+        seconds_per_slot: PRESET.SECONDS_PER_SLOT,
+        slots_per_epoch: PRESET.SLOTS_PER_EPOCH,
+        epochs_per_sync_committee_period: PRESET.EPOCHS_PER_SYNC_COMMITTEE_PERIOD,
+        ibc_address: Address(hex!("a7f733a4fEA1071f58114b203F57444969b86524")),
+        ibc_commitments_slot: H256(hex!(
+            "1ee222554989dda120e26ecacf756fe1235cd8d726706b57517715dde4f0c900"
+        )),
+        commitment_value_codec: CommitmentValueCodec::Keccak256,
+        commitment_prefix: Default::default(),
+        trust_level: Fraction::new(2, 3).unwrap(),
+        trusting_period: Duration::from_secs(60 * 60 * 27),
+        max_clock_drift: Duration::from_secs(60),
+        max_intermediate_consensus_updates: U64(0),
+        latest_execution_block_number: 1.into(),
+        frozen_height: None,
+        misbehaviour_evidence_digest: None,
+        consensus_verifier: Default::default(),
+        execution_verifier: Default::default(),
+    }
+}
+
+fn to_consensus_update_info(update: EthConsensusUpdateInfo<N>) -> ConsensusUpdateInfo<N> {
+    ConsensusUpdateInfo {
+        attested_header: update.light_client_update.attested_header,
+        next_sync_committee: update.light_client_update.next_sync_committee,
+        finalized_header: update.light_client_update.finalized_header,
+        sync_aggregate: update.light_client_update.sync_aggregate,
+        signature_slot: update.light_client_update.signature_slot,
+        finalized_execution_root: update.finalized_execution_root,
+        finalized_execution_branch: update.finalized_execution_branch,
+    }
+}
+
+fn keccak_256(input: &[u8]) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    let mut k = Keccak::v256();
+    k.update(input);
+    k.finalize(&mut out);
+    out
+}
+
+/// Returns the state root of a state trie that only contains the account of `address` with `storage_root`, and the account update proving it
+fn single_account_update(address: &Address, storage_root: H256) -> (H256, AccountUpdateInfo) {
+    let mut account = rlp::RlpStream::new_list(4);
+    account.append(&1u64);
+    account.append_empty_data();
+    account.append(&storage_root.as_bytes().to_vec());
+    account.append(&keccak_256(&[]).to_vec());
+    // the leaf node holds the whole key, which is hex-prefixed with the even leaf flag
+    let mut key = vec![0x20];
+    key.extend_from_slice(address.trie_key().as_bytes());
+    let mut leaf = rlp::RlpStream::new_list(2);
+    leaf.append(&key);
+    leaf.append(&account.out().to_vec());
+    let leaf = leaf.out().to_vec();
+    (
+        H256(keccak_256(&leaf)),
+        AccountUpdateInfo {
+            account_proof: vec![leaf],
+            account_storage_root: storage_root,
+        },
+    )
+}
+
+/// Returns a header signed in the period after `trusted_slot` by the next sync committee if `rotation` is `true`,
+/// otherwise in the period of `trusted_slot` by the current sync committee
+fn header_scenario(scm: &MockSyncCommitteeManager<N>, rotation: bool) -> Scenario {
+    let client_state = new_client_state();
+    let slots_per_period =
+        client_state.slots_per_epoch * client_state.epochs_per_sync_committee_period;
+    let timestamp_at = |slot: Slot| {
+        compute_timestamp_at_slot(
+            client_state.genesis_time,
+            client_state.seconds_per_slot,
+            slot,
+        )
+        .unwrap()
+    };
+    // the trusted consensus state is close to the end of period 1, so that both updates are within the trusting period
+    let trusted_slot = U64(2) * slots_per_period - client_state.slots_per_epoch * U64(4);
+    let storage_root = H256(hex!(
+        "27cd08827e6bf1e435832f4b2660107beb562314287b3fa534f3b189574c0cca"
+    ));
+    let (state_root, account_update) =
+        single_account_update(&client_state.ibc_address, storage_root);
+    let trusted_consensus_state = ConsensusState {
+        slot: trusted_slot,
+        storage_root: storage_root.as_bytes().to_vec().into(),
+        timestamp: timestamp_at(trusted_slot),
+        current_sync_committee: scm.get_committee(1).to_committee().aggregate_pubkey.clone(),
+        next_sync_committee: scm.get_committee(2).to_committee().aggregate_pubkey.clone(),
+        current_block_hash: None,
+    };
+    let (signature_slot, signing_period) = if rotation {
+        (U64(2) * slots_per_period + client_state.slots_per_epoch, 2)
+    } else {
+        (trusted_slot + client_state.slots_per_epoch * U64(3), 1)
+    };
+    let now = timestamp_at(signature_slot + 1);
+    let cc = client_state.build_context_at(now);
+    let attested_slot = signature_slot - 1;
+    let (update, execution_update) = gen_light_client_update_with_params::<N, _>(
+        &cc,
+        signature_slot,
+        attested_slot,
+        attested_slot / cc.slots_per_epoch(),
+        state_root,
+        2.into(),
+        scm.get_committee(signing_period),
+        scm.get_committee(signing_period + 1),
+        true,
+        N,
+    );
+    let consensus_update = to_consensus_update_info(update);
+    let header = Header {
+        trusted_sync_committee: TrustedSyncCommittee {
+            height: height_from_block_number(U64(1)).unwrap(),
+            sync_committee: scm.get_committee(signing_period).to_committee().clone(),
+            is_next: rotation,
+        },
+        intermediate_consensus_updates: vec![],
+        timestamp: timestamp_at(consensus_update.finalized_header.0.slot),
+        consensus_update,
+        execution_update: ExecutionUpdateInfo {
+            state_root: execution_update.state_root(),
+            state_root_branch: execution_update.state_root_branch(),
+            block_number: execution_update.block_number(),
+            block_number_branch: execution_update.block_number_branch(),
+            block_hash: None,
+            block_hash_branch: vec![],
+        },
+        account_update,
+    };
+    Scenario {
+        client_state,
+        trusted_consensus_state,
+        header,
+        now,
+    }
+}
+
+fn misbehaviour_scenario(scm: &MockSyncCommitteeManager<N>) -> (Scenario, Misbehaviour<N>) {
+    let scenario = header_scenario(scm, false);
+    let cc = scenario.client_state.build_context_at(scenario.now);
+    let signature_slot = scenario.header.consensus_update.signature_slot;
+    let gen_update = |execution_state_root: [u8; 32]| {
+        let attested_slot = signature_slot - 1;
+        let (update, _) = gen_light_client_update_with_params::<N, _>(
+            &cc,
+            signature_slot,
+            attested_slot,
+            attested_slot / cc.slots_per_epoch(),
+            execution_state_root.into(),
+            1.into(),
+            scm.get_committee(1),
+            scm.get_committee(2),
+            true,
+            N,
+        );
+        to_consensus_update_info(update)
+    };
+    // the sync committee signs two different finalized headers at the same slot
+    let misbehaviour = Misbehaviour {
+        client_id: ClientId::new(eth_client_type(), 0).unwrap(),
+        trusted_sync_committee_1: scenario.header.trusted_sync_committee.clone(),
+        trusted_sync_committee_2: scenario.header.trusted_sync_committee.clone(),
+        data: MisbehaviourData::FinalizedHeader(FinalizedHeaderMisbehaviour {
+            consensus_update_1: gen_update([1u8; 32]),
+            consensus_update_2: gen_update([2u8; 32]),
+        }),
+    };
+    (scenario, misbehaviour)
+}
+
+fn bench_header(c: &mut Criterion) {
+    let scm = MockSyncCommitteeManager::<N>::new(1, 3);
+    let mut group = c.benchmark_group("header");
+    for (name, rotation) in [("same_period", false), ("rotation", true)] {
+        let s = header_scenario(&scm, rotation);
+        // check the scenario once so that the benchmark does not measure an early error
+        verify_header(
+            &s.client_state,
+            &s.trusted_consensus_state,
+            s.header.clone(),
+            s.now,
+        )
+        .unwrap();
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                verify_header(
+                    &s.client_state,
+                    &s.trusted_consensus_state,
+                    s.header.clone(),
+                    s.now,
+                )
+                .unwrap()
+            })
+        });
+    }
+
+    let s = header_scenario(&scm, false);
+    let mut cache = RecentSyncCommittees::new(2);
+    group.bench_function("cache_hit", |b| {
+        b.iter(|| {
+            verify_header_with_cache(
+                &s.client_state,
+                &s.trusted_consensus_state,
+                s.header.clone(),
+                s.now,
+                &mut cache,
+            )
+            .unwrap()
+        })
+    });
+
+    let bz = IBCAny::from(s.header.clone()).encode_to_vec();
+    group.bench_function("decode", |b| {
+        b.iter(|| Header::<N>::try_from(IBCAny::decode(bz.as_slice()).unwrap()).unwrap())
+    });
+    group.finish();
+}
+
+fn bench_misbehaviour(c: &mut Criterion) {
+    let scm = MockSyncCommitteeManager::<N>::new(1, 3);
+    let (s, misbehaviour) = misbehaviour_scenario(&scm);
+    let trusted = (&s.trusted_consensus_state, &s.trusted_consensus_state);
+    verify_misbehaviour(&s.client_state, trusted, &misbehaviour, s.now).unwrap();
+    c.bench_function("misbehaviour", |b| {
+        b.iter(|| verify_misbehaviour(&s.client_state, trusted, &misbehaviour, s.now).unwrap())
+    });
+}
+
+fn bench_commitment(c: &mut Criterion) {
+    let client_state = new_client_state();
+    let mut group = c.benchmark_group("commitment");
+
+    // the account proof is verified once per header with an account update
+    let account_proof =
+        commitment::decode_eip1186_rlp_proof(hex::decode(ACCOUNT_PROOF.trim()).unwrap()).unwrap();
+    let state_root = H256(hex!(
+        "568a51c3253bbd2d46e3923b35df0489712df11453fd04dd71341120356952c0"
+    ));
+    group.bench_function("account", |b| {
+        b.iter(|| {
+            commitment::verify_account_storage_root(
+                state_root,
+                &client_state.ibc_address,
+                &account_proof,
+                H256::default(),
+            )
+            .unwrap()
+        })
+    });
+
+    // the storage proof of `get_membership_proof` of `client_state`
+    let root = CommitmentRoot::from_bytes(&hex!(
+        "27cd08827e6bf1e435832f4b2660107beb562314287b3fa534f3b189574c0cca"
+    ));
+    let path = Path::from_str("clients/lcp-client-0/clientState").unwrap();
+    let proof: CommitmentProofBytes = hex!("f90159f901118080a0143145e818eeff83817419a6632ea193fd1acaa4f791eb17282f623f38117f56a0e6ee0a993a7254ee9253d766ea005aec74eb1e11656961f0fb11323f4f91075580808080a01efae04adc2e970b4af3517581f41ce2ba4ff60492d33696c1e2a5ab70cb55bba03bac3f5124774e41fb6efdd7219530846f9f6441045c4666d2855c6598cfca00a020d7122ffc86cb37228940b5a9441e9fd272a3450245c9130ca3ab00bc1cd6ef80a0047f255205a0f2b0e7d29d490abf02bfb62c3ed201c338bc7f0088fa9c5d77eda069fecc766fcb2df04eb3a834b1f4ba134df2be114479e251d9cc9b6ba493077b80a094c3ed6a7ef63a6a67e46cc9876b9b1882eeba3d28e6d61bb15cdfb207d077e180f843a03e077f3dfd0489e70c68282ced0126c62fcef50acdcb7f57aa4552b87b456b11a1a05dc044e92e82db28c96fd98edd502949612b06e8da6dd74664a43a5ed857b298")
+        .to_vec()
+        .try_into()
+        .unwrap();
+    let value = hex!("0a242f6962632e6c69676874636c69656e74732e6c63702e76312e436c69656e74537461746512ed010a208083673c69fe3f098ea79a799d9dbb99c39b4b4f17a1a79ef58bdf8ae86299951080f524220310fb012a1353575f48415244454e494e475f4e45454445442a1147524f55505f4f55545f4f465f44415445320e494e54454c2d53412d3030323139320e494e54454c2d53412d3030323839320e494e54454c2d53412d3030333334320e494e54454c2d53412d3030343737320e494e54454c2d53412d3030363134320e494e54454c2d53412d3030363135320e494e54454c2d53412d3030363137320e494e54454c2d53412d30303832383a14cb96f8d6c2d543102184d679d7829b39434e4eec48015001");
+    group.bench_function("membership", |b| {
+        b.iter(|| {
+            commitment::verify_membership(
+                &root,
+                &client_state.ibc_commitments_slot,
+                client_state.commitment_value_codec,
+                &proof,
+                path.clone(),
+                &value,
+            )
+            .unwrap()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_header, bench_misbehaviour, bench_commitment);
+criterion_main!(benches);
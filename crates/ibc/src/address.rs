@@ -0,0 +1,191 @@
+//! The address of an account on the execution layer
+use crate::commitment::keccak_256;
+use crate::errors::Error;
+use crate::internal_prelude::*;
+use core::fmt;
+use core::str::FromStr;
+use ethereum_consensus::types::H256;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// The 20-byte address of an account on the execution layer, e.g. the IBC contract
+///
+/// Human-readable formats encode the address as a 0x-prefixed EIP-55 checksummed hex string and accept the hex string in any case.
+/// Non-human-readable formats encode the 20 bytes as is.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Address(pub [u8; 20]);
+
+impl Address {
+    /// The length of an address in bytes
+    pub const LEN: usize = 20;
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Returns the key of the account in the state trie, i.e. `keccak256(address)`
+    pub fn trie_key(&self) -> H256 {
+        H256(keccak_256(&self.0))
+    }
+
+    /// Returns the 0x-prefixed hex string of the address with the EIP-55 checksum
+    ///
+    /// A letter of the lowercase hex string is uppercased if the corresponding nibble of the keccak256 hash of the lowercase hex string is 8 or greater.
+    pub fn to_checksum_string(&self) -> String {
+        let lower = hex::encode(self.0);
+        let hash = keccak_256(lower.as_bytes());
+        let mut s = String::with_capacity(2 + lower.len());
+        s.push_str("0x");
+        for (i, c) in lower.chars().enumerate() {
+            let nibble = if i % 2 == 0 {
+                hash[i / 2] >> 4
+            } else {
+                hash[i / 2] & 0x0f
+            };
+            s.push(if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            });
+        }
+        s
+    }
+}
+
+impl From<[u8; 20]> for Address {
+    fn from(value: [u8; 20]) -> Self {
+        Self(value)
+    }
+}
+
+impl TryFrom<&[u8]> for Address {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Error> {
+        Ok(Self(value.try_into().map_err(|_| {
+            Error::InvalidBytesLength {
+                expected: Self::LEN,
+                actual: value.len(),
+            }
+        })?))
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_checksum_string())
+    }
+}
+
+/// Parses a 0x-prefixed hex string of 20 bytes in any case; the checksum is not verified
+impl FromStr for Address {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        let hex_str = s
+            .strip_prefix("0x")
+            .ok_or_else(|| format!("address must start with `0x`: `{}`", s))?;
+        let bz = hex::decode(hex_str).map_err(|e| format!("invalid hex address `{}`: {}", s, e))?;
+        Address::try_from(bz.as_slice()).map_err(|e| format!("invalid address `{}`: {}", s, e))
+    }
+}
+
+impl Serialize for Address {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&self.to_checksum_string())
+        } else {
+            self.0.serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Address {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            String::deserialize(deserializer)?
+                .parse()
+                .map_err(D::Error::custom)
+        } else {
+            <[u8; 20]>::deserialize(deserializer).map(Self)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn test_checksum_round_trip() {
+        // the test vectors of EIP-55
+        for s in [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ] {
+            let address = Address::from_str(s).unwrap();
+            assert_eq!(address.to_string(), s);
+            // the input is case-insensitive
+            assert_eq!(Address::from_str(&s.to_lowercase()).unwrap(), address);
+            assert_eq!(
+                Address::from_str(&format!("0x{}", s[2..].to_uppercase())).unwrap(),
+                address
+            );
+
+            let json = serde_json::to_string(&address).unwrap();
+            assert_eq!(json, format!("\"{}\"", s));
+            assert_eq!(serde_json::from_str::<Address>(&json).unwrap(), address);
+            let lower = serde_json::to_string(&s.to_lowercase()).unwrap();
+            assert_eq!(serde_json::from_str::<Address>(&lower).unwrap(), address);
+        }
+
+        for s in [
+            "5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeA",
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAedee",
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeg",
+        ] {
+            assert!(Address::from_str(s).is_err(), "{}", s);
+        }
+        assert!(
+            serde_json::from_str::<Address>("\"0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeA\"")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_try_from_slice() {
+        let bz = hex!("ff77D90D6aA12db33d3Ba50A34fB25401f6e4c4F");
+        assert_eq!(Address::try_from(bz.as_slice()).unwrap(), Address(bz));
+        for len in [0, 19, 21, 32] {
+            let res = Address::try_from(vec![1u8; len].as_slice());
+            assert!(
+                matches!(
+                    res,
+                    Err(Error::InvalidBytesLength { expected: 20, actual }) if actual == len
+                ),
+                "{:?}",
+                res
+            );
+        }
+    }
+
+    #[test]
+    fn test_trie_key() {
+        // WETH on mainnet
+        let address = Address(hex!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"));
+        assert_eq!(
+            address.trie_key(),
+            H256(hex!(
+                "8679e8eda65bd257638cf8cf09b8238888947cc3c0bea2aa2cc3f1c4ac7a3002"
+            ))
+        );
+        assert_eq!(
+            address.to_string(),
+            "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"
+        );
+    }
+}
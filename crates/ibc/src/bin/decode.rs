@@ -0,0 +1,60 @@
+//! Decode a proto-encoded message of the client
+//!
+//! Usage: `ethereum-ibc-decode [--preset mainnet|minimal] [<input>]`
+//!
+//! The input is the hex or base64 encoding of an `Any`, which is read from stdin if no file is given.
+//! The summary and the JSON of the message are written to stdout, and the decoding error is written to stderr with the exit code 1.
+use ethereum_ibc::inspect::{decode_message, DecodedMessage};
+use ethereum_ibc::{MAINNET_SYNC_COMMITTEE_SIZE, MINIMAL_SYNC_COMMITTEE_SIZE};
+use std::io::Read;
+use std::process::exit;
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (preset, path) = match args.as_slice() {
+        [] => ("mainnet", None),
+        [path] => ("mainnet", Some(path)),
+        [flag, preset] if flag == "--preset" => (preset.as_str(), None),
+        [flag, preset, path] if flag == "--preset" => (preset.as_str(), Some(path)),
+        _ => usage(),
+    };
+    let input = match path {
+        Some(path) => std::fs::read_to_string(path).unwrap_or_else(|e| {
+            eprintln!("failed to read {}: {}", path, e);
+            exit(1)
+        }),
+        None => {
+            let mut input = String::new();
+            std::io::stdin()
+                .read_to_string(&mut input)
+                .unwrap_or_else(|e| {
+                    eprintln!("failed to read stdin: {}", e);
+                    exit(1)
+                });
+            input
+        }
+    };
+    let res = match preset {
+        "mainnet" => run::<MAINNET_SYNC_COMMITTEE_SIZE>(&input),
+        "minimal" => run::<MINIMAL_SYNC_COMMITTEE_SIZE>(&input),
+        _ => usage(),
+    };
+    match res {
+        Ok(output) => println!("{}", output),
+        Err(e) => {
+            eprintln!("{}", e);
+            exit(1)
+        }
+    }
+}
+
+fn run<const SYNC_COMMITTEE_SIZE: usize>(input: &str) -> Result<String, String> {
+    let message: DecodedMessage<SYNC_COMMITTEE_SIZE> = decode_message(input)?;
+    let json = serde_json::to_string_pretty(&message.to_json()).map_err(|e| e.to_string())?;
+    Ok(format!("{}\n\n{}", message, json))
+}
+
+fn usage() -> ! {
+    eprintln!("usage: ethereum-ibc-decode [--preset mainnet|minimal] [<input>]");
+    exit(2)
+}
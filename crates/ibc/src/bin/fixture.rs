@@ -0,0 +1,45 @@
+//! Build a header fixture from a captured light client update
+//!
+//! Usage: `ethereum-ibc-fixture [--preset mainnet|minimal] <input.json>`
+//!
+//! The input is a `fixture::FixtureInput` and the fixture is written to stdout as JSON.
+use ethereum_ibc::fixture::{build_fixture, FixtureInput};
+use ethereum_ibc::{MAINNET_SYNC_COMMITTEE_SIZE, MINIMAL_SYNC_COMMITTEE_SIZE};
+use std::process::exit;
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (preset, path) = match args.as_slice() {
+        [path] => ("mainnet", path),
+        [flag, preset, path] if flag == "--preset" => (preset.as_str(), path),
+        _ => usage(),
+    };
+    let input = std::fs::read_to_string(path).unwrap_or_else(|e| {
+        eprintln!("failed to read {}: {}", path, e);
+        exit(1)
+    });
+    let res = match preset {
+        "mainnet" => run::<MAINNET_SYNC_COMMITTEE_SIZE>(&input),
+        "minimal" => run::<MINIMAL_SYNC_COMMITTEE_SIZE>(&input),
+        _ => usage(),
+    };
+    match res {
+        Ok(output) => println!("{}", output),
+        Err(e) => {
+            eprintln!("{}", e);
+            exit(1)
+        }
+    }
+}
+
+fn run<const SYNC_COMMITTEE_SIZE: usize>(input: &str) -> Result<String, String> {
+    let input: FixtureInput<SYNC_COMMITTEE_SIZE> =
+        serde_json::from_str(input).map_err(|e| format!("invalid input: {}", e))?;
+    let fixture = build_fixture(input)?;
+    serde_json::to_string_pretty(&fixture).map_err(|e| e.to_string())
+}
+
+fn usage() -> ! {
+    eprintln!("usage: ethereum-ibc-fixture [--preset mainnet|minimal] <input.json>");
+    exit(2)
+}
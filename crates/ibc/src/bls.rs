@@ -0,0 +1,375 @@
+//! Validation of the encodings of the BLS12-381 public keys and signatures on decoding
+//!
+//! The public keys are G1 points of 48 bytes and the signatures are G2 points of 96 bytes in the compressed form of the consensus layer:
+//! the three most significant bits of the first byte are the compression flag, the infinity flag and the sort flag,
+//! followed by the big-endian x coordinate. The x coordinate `x_0 + x_1 * u` of a G2 point is encoded as `x_1 || x_0`.
+//!
+//! A public key is rejected if it is not compressed, if it is the point at infinity, or if its x coordinate is not a field element.
+//! The signature is additionally rejected if no point on the curve has its x coordinate, i.e. if its decompression fails.
+//! The signature at infinity is accepted, since it is the signature of a sync aggregate without participants, which is rejected by the participation thresholds.
+//! The subgroup checks and the curve check of the public keys are left to the BLS verification.
+use displaydoc::Display;
+use ethereum_consensus::bls::{PublicKey, Signature};
+
+/// The reason why the encoding of a point is rejected
+#[derive(Clone, Copy, Debug, Display, PartialEq, Eq)]
+pub enum PointError {
+    /// the compression flag is not set
+    NotCompressed,
+    /// the point at infinity
+    Infinity,
+    /// the point at infinity is not encoded canonically
+    NonCanonicalInfinity,
+    /// the x coordinate is not less than the field modulus
+    NotInField,
+    /// no point on the curve has the x coordinate
+    NotOnCurve,
+}
+
+/// Validate the encoding of the public key, which must not be the point at infinity
+pub fn validate_public_key(key: &PublicKey) -> Result<(), PointError> {
+    let bz = &key.0[..];
+    if decode_flags(bz)? {
+        return Err(PointError::Infinity);
+    }
+    Fp::from_be_bytes(&masked(bz)).ok_or(PointError::NotInField)?;
+    Ok(())
+}
+
+/// Validate that the signature is decompressed into a point on the curve or the point at infinity
+pub fn validate_signature(signature: &Signature) -> Result<(), PointError> {
+    let bz = &signature.0[..];
+    if decode_flags(bz)? {
+        return Ok(());
+    }
+    let (c1, c0) = bz.split_at(48);
+    let x = (
+        Fp::from_be_bytes(c0).ok_or(PointError::NotInField)?,
+        Fp::from_be_bytes(&masked(c1)).ok_or(PointError::NotInField)?,
+    );
+    // y^2 = x^3 + 4 * (1 + u)
+    let x3 = fp2_mul(fp2_mul(x, x), x);
+    let four = Fp::from_u64(4);
+    if !fp2_is_square((x3.0.add(&four), x3.1.add(&four))) {
+        return Err(PointError::NotOnCurve);
+    }
+    Ok(())
+}
+
+/// Returns `true` if the encoding is the canonical point at infinity
+fn decode_flags(bz: &[u8]) -> Result<bool, PointError> {
+    if bz[0] & 0x80 == 0 {
+        return Err(PointError::NotCompressed);
+    }
+    if bz[0] & 0x40 == 0 {
+        return Ok(false);
+    }
+    if bz[0] & 0x3f != 0 || bz[1..].iter().any(|b| *b != 0) {
+        return Err(PointError::NonCanonicalInfinity);
+    }
+    Ok(true)
+}
+
+/// Returns the first 48 bytes of the encoding without the flags
+fn masked(bz: &[u8]) -> [u8; 48] {
+    let mut x = [0u8; 48];
+    x.copy_from_slice(&bz[..48]);
+    x[0] &= 0x1f;
+    x
+}
+
+/// The field modulus of BLS12-381 in little-endian limbs
+const MODULUS: [u64; 6] = [
+    0xb9feffffffffaaab,
+    0x1eabfffeb153ffff,
+    0x6730d2a0f6b0f624,
+    0x64774b84f38512bf,
+    0x4b1ba7b6434bacd7,
+    0x1a0111ea397fe69a,
+];
+
+/// `-MODULUS^-1 mod 2^64`
+const INV: u64 = 0x89f3fffcfffcfffd;
+
+/// `2^384 mod MODULUS`, i.e. one in the Montgomery form
+const R: [u64; 6] = [
+    0x760900000002fffd,
+    0xebf4000bc40c0002,
+    0x5f48985753c758ba,
+    0x77ce585370525745,
+    0x5c071a97a256ec6d,
+    0x15f65ec3fa80e493,
+];
+
+/// `2^768 mod MODULUS`
+const R2: [u64; 6] = [
+    0xf4df1f341c341746,
+    0x0a76e6a609d104f1,
+    0x8de5476c4c95b6d5,
+    0x67eb88a9939d83c0,
+    0x9a793e85b519952d,
+    0x11988fe592cae3aa,
+];
+
+/// `(MODULUS - 1) / 2`
+const HALF_MODULUS: [u64; 6] = [
+    0xdcff7fffffffd555,
+    0x0f55ffff58a9ffff,
+    0xb39869507b587b12,
+    0xb23ba5c279c2895f,
+    0x258dd3db21a5d66b,
+    0x0d0088f51cbff34d,
+];
+
+/// An element of the base field in the Montgomery form
+///
+/// The arithmetic is not constant-time, which is fine for the public inputs validated here.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Fp([u64; 6]);
+
+impl Fp {
+    /// Returns `None` if the big-endian integer is not less than the modulus
+    fn from_be_bytes(bz: &[u8]) -> Option<Self> {
+        let mut limbs = [0u64; 6];
+        for (i, chunk) in bz.chunks_exact(8).enumerate() {
+            limbs[5 - i] = u64::from_be_bytes(chunk.try_into().unwrap());
+        }
+        if !lt(&limbs, &MODULUS) {
+            return None;
+        }
+        Some(Fp(limbs).mul(&Fp(R2)))
+    }
+
+    fn from_u64(n: u64) -> Self {
+        Fp([n, 0, 0, 0, 0, 0]).mul(&Fp(R2))
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == [0; 6]
+    }
+
+    fn add(&self, rhs: &Self) -> Self {
+        let mut limbs = [0u64; 6];
+        let mut carry = 0u128;
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let v = self.0[i] as u128 + rhs.0[i] as u128 + carry;
+            *limb = v as u64;
+            carry = v >> 64;
+        }
+        // the sum fits in 6 limbs since the modulus is less than 2^383
+        Fp(reduce(limbs))
+    }
+
+    fn sub(&self, rhs: &Self) -> Self {
+        let (limbs, borrow) = sub_limbs(&self.0, &rhs.0);
+        if borrow {
+            let (limbs, _) = sub_limbs(&limbs, &neg_modulus());
+            Fp(limbs)
+        } else {
+            Fp(limbs)
+        }
+    }
+
+    /// Montgomery multiplication
+    fn mul(&self, rhs: &Self) -> Self {
+        let mut t = [0u64; 7];
+        for i in 0..6 {
+            let mut carry = 0u128;
+            for j in 0..6 {
+                let v = t[j] as u128 + (self.0[j] as u128) * (rhs.0[i] as u128) + carry;
+                t[j] = v as u64;
+                carry = v >> 64;
+            }
+            let top = t[6] as u128 + carry;
+            let m = t[0].wrapping_mul(INV);
+            let mut carry = (t[0] as u128 + (m as u128) * (MODULUS[0] as u128)) >> 64;
+            for j in 1..6 {
+                let v = t[j] as u128 + (m as u128) * (MODULUS[j] as u128) + carry;
+                t[j - 1] = v as u64;
+                carry = v >> 64;
+            }
+            let v = (top as u64) as u128 + carry;
+            t[5] = v as u64;
+            t[6] = ((top >> 64) + (v >> 64)) as u64;
+        }
+        // the result is less than twice the modulus, so `t[6]` is zero
+        let mut limbs = [0u64; 6];
+        limbs.copy_from_slice(&t[..6]);
+        Fp(reduce(limbs))
+    }
+
+    fn pow(&self, exp: &[u64; 6]) -> Self {
+        let mut res = Fp(R);
+        for limb in exp.iter().rev() {
+            for i in (0..64).rev() {
+                res = res.mul(&res);
+                if (limb >> i) & 1 == 1 {
+                    res = res.mul(self);
+                }
+            }
+        }
+        res
+    }
+
+    /// Euler's criterion
+    fn is_square(&self) -> bool {
+        self.is_zero() || self.pow(&HALF_MODULUS) == Fp(R)
+    }
+}
+
+/// An element `c0 + c1 * u` of the quadratic extension with `u^2 = -1`
+type Fp2 = (Fp, Fp);
+
+fn fp2_mul(a: Fp2, b: Fp2) -> Fp2 {
+    (
+        a.0.mul(&b.0).sub(&a.1.mul(&b.1)),
+        a.0.mul(&b.1).add(&a.1.mul(&b.0)),
+    )
+}
+
+/// An element of the extension is a square if and only if its norm `c0^2 + c1^2` is a square in the base field
+fn fp2_is_square(a: Fp2) -> bool {
+    a.0.mul(&a.0).add(&a.1.mul(&a.1)).is_square()
+}
+
+fn lt(a: &[u64; 6], b: &[u64; 6]) -> bool {
+    for i in (0..6).rev() {
+        if a[i] != b[i] {
+            return a[i] < b[i];
+        }
+    }
+    false
+}
+
+/// Subtracts the modulus if `limbs` is not less than it
+fn reduce(limbs: [u64; 6]) -> [u64; 6] {
+    if lt(&limbs, &MODULUS) {
+        limbs
+    } else {
+        sub_limbs(&limbs, &MODULUS).0
+    }
+}
+
+fn sub_limbs(a: &[u64; 6], b: &[u64; 6]) -> ([u64; 6], bool) {
+    let mut limbs = [0u64; 6];
+    let mut borrow = false;
+    for i in 0..6 {
+        let (v, b1) = a[i].overflowing_sub(b[i]);
+        let (v, b2) = v.overflowing_sub(borrow as u64);
+        limbs[i] = v;
+        borrow = b1 || b2;
+    }
+    (limbs, borrow)
+}
+
+/// `2^384 - MODULUS`, whose subtraction adds the modulus modulo `2^384`
+fn neg_modulus() -> [u64; 6] {
+    sub_limbs(&[0; 6], &MODULUS).0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+
+    fn pubkey(bz: &[u8]) -> PublicKey {
+        PublicKey::try_from(bz.to_vec()).unwrap()
+    }
+
+    fn signature(bz: &[u8]) -> Signature {
+        Signature::try_from(bz.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn test_validate_public_key() {
+        // a public key of the sync committee of the fixtures and the generator of G1
+        let valid = hex!("a145063e1b5eda80fa55960296f2c4b2c021f75767318ea2572a9f7abb649010b746754ca7fc2ba57c1156881516a357");
+        assert_eq!(validate_public_key(&pubkey(&valid)), Ok(()));
+        let generator = hex!("97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb");
+        assert_eq!(validate_public_key(&pubkey(&generator)), Ok(()));
+
+        let mut infinity = [0u8; 48];
+        infinity[0] = 0xc0;
+        assert_eq!(
+            validate_public_key(&pubkey(&infinity)),
+            Err(PointError::Infinity)
+        );
+        infinity[47] = 1;
+        assert_eq!(
+            validate_public_key(&pubkey(&infinity)),
+            Err(PointError::NonCanonicalInfinity)
+        );
+        // the default value of a public key has no flags
+        assert_eq!(
+            validate_public_key(&PublicKey::default()),
+            Err(PointError::NotCompressed)
+        );
+        let mut modulus = hex!("1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab");
+        modulus[0] |= 0x80;
+        assert_eq!(
+            validate_public_key(&pubkey(&modulus)),
+            Err(PointError::NotInField)
+        );
+    }
+
+    #[test]
+    fn test_validate_signature() {
+        // the generator of G2
+        let generator = hex!("93e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8");
+        assert_eq!(validate_signature(&signature(&generator)), Ok(()));
+
+        let mut infinity = [0u8; 96];
+        infinity[0] = 0xc0;
+        assert_eq!(validate_signature(&signature(&infinity)), Ok(()));
+        infinity[95] = 1;
+        assert_eq!(
+            validate_signature(&signature(&infinity)),
+            Err(PointError::NonCanonicalInfinity)
+        );
+        assert_eq!(
+            validate_signature(&Signature::default()),
+            Err(PointError::NotCompressed)
+        );
+
+        // no point on the curve has the x coordinate 0 or 1
+        let mut off_curve = [0u8; 96];
+        off_curve[0] = 0x80;
+        assert_eq!(
+            validate_signature(&signature(&off_curve)),
+            Err(PointError::NotOnCurve)
+        );
+        off_curve[95] = 1;
+        assert_eq!(
+            validate_signature(&signature(&off_curve)),
+            Err(PointError::NotOnCurve)
+        );
+
+        // each half of the x coordinate must be a field element
+        let modulus = hex!("1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab");
+        let mut bz = [0u8; 96];
+        bz[..48].copy_from_slice(&modulus);
+        bz[0] |= 0x80;
+        assert_eq!(
+            validate_signature(&signature(&bz)),
+            Err(PointError::NotInField)
+        );
+        let mut bz = [0u8; 96];
+        bz[48..].copy_from_slice(&modulus);
+        bz[0] = 0x80;
+        assert_eq!(
+            validate_signature(&signature(&bz)),
+            Err(PointError::NotInField)
+        );
+    }
+
+    #[test]
+    fn test_field_arithmetic() {
+        let (a, b) = (Fp::from_u64(3), Fp::from_u64(5));
+        assert_eq!(a.mul(&b), Fp::from_u64(15));
+        assert_eq!(a.sub(&b).add(&b), a);
+        assert_eq!(Fp::from_u64(1), Fp(R));
+        assert!(Fp::from_u64(4).is_square());
+        // -1 is not a square since the modulus is 3 mod 4
+        assert!(!Fp::from_u64(0).sub(&Fp::from_u64(1)).is_square());
+    }
+}
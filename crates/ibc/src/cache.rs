@@ -0,0 +1,148 @@
+//! Caches of the sync committees whose aggregate pubkey has been validated
+//!
+//! Validating a sync committee aggregates all of its public keys, which dominates the cost of a header verification,
+//! while the same sync committee is trusted by the headers of a whole period (~27 hours).
+//! `verify::verify_header_with_cache` consults a `SyncCommitteeCache` before validating the trusted sync committee of the header.
+//! `verify::verify_header` and the ibc-rs `ClientState` implementation use `NoCache`, so deterministic environments are not affected.
+use crate::errors::Error;
+use crate::internal_prelude::*;
+use ethereum_consensus::bls::PublicKey;
+use ethereum_consensus::sync_protocol::SyncCommittee;
+
+/// A store of the sync committees whose aggregate pubkey has been validated, keyed by the aggregate pubkey
+pub trait SyncCommitteeCache<const SYNC_COMMITTEE_SIZE: usize> {
+    /// Returns the validated sync committee whose aggregate pubkey is `aggregate_pubkey` if any
+    fn get(&self, aggregate_pubkey: &PublicKey) -> Option<&SyncCommittee<SYNC_COMMITTEE_SIZE>>;
+
+    /// Stores `sync_committee`, which has been validated
+    fn insert(&mut self, sync_committee: &SyncCommittee<SYNC_COMMITTEE_SIZE>);
+}
+
+/// Validate `sync_committee` unless it is in `cache`, and store it in `cache` after the validation
+///
+/// The cached sync committee must be equal to `sync_committee` including all public keys,
+/// so a sync committee that claims the aggregate pubkey of a cached one with other public keys is still validated.
+pub fn validate_sync_committee<C, const SYNC_COMMITTEE_SIZE: usize>(
+    cache: &mut C,
+    sync_committee: &SyncCommittee<SYNC_COMMITTEE_SIZE>,
+) -> Result<(), Error>
+where
+    C: SyncCommitteeCache<SYNC_COMMITTEE_SIZE> + ?Sized,
+{
+    if cache.get(&sync_committee.aggregate_pubkey) == Some(sync_committee) {
+        return Ok(());
+    }
+    sync_committee.validate()?;
+    cache.insert(sync_committee);
+    Ok(())
+}
+
+/// A cache that stores nothing, i.e. every sync committee is validated
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NoCache;
+
+impl<const SYNC_COMMITTEE_SIZE: usize> SyncCommitteeCache<SYNC_COMMITTEE_SIZE> for NoCache {
+    fn get(&self, _aggregate_pubkey: &PublicKey) -> Option<&SyncCommittee<SYNC_COMMITTEE_SIZE>> {
+        None
+    }
+
+    fn insert(&mut self, _sync_committee: &SyncCommittee<SYNC_COMMITTEE_SIZE>) {}
+}
+
+/// An in-memory cache of the `capacity` most recently validated sync committees
+///
+/// A capacity of 2 keeps the current and next sync committees of the latest consensus state.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecentSyncCommittees<const SYNC_COMMITTEE_SIZE: usize> {
+    capacity: usize,
+    // the least recently inserted first
+    sync_committees: Vec<SyncCommittee<SYNC_COMMITTEE_SIZE>>,
+}
+
+impl<const SYNC_COMMITTEE_SIZE: usize> RecentSyncCommittees<SYNC_COMMITTEE_SIZE> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            sync_committees: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the number of the cached sync committees
+    pub fn len(&self) -> usize {
+        self.sync_committees.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sync_committees.is_empty()
+    }
+}
+
+impl<const SYNC_COMMITTEE_SIZE: usize> SyncCommitteeCache<SYNC_COMMITTEE_SIZE>
+    for RecentSyncCommittees<SYNC_COMMITTEE_SIZE>
+{
+    fn get(&self, aggregate_pubkey: &PublicKey) -> Option<&SyncCommittee<SYNC_COMMITTEE_SIZE>> {
+        self.sync_committees
+            .iter()
+            .find(|sync_committee| &sync_committee.aggregate_pubkey == aggregate_pubkey)
+    }
+
+    fn insert(&mut self, sync_committee: &SyncCommittee<SYNC_COMMITTEE_SIZE>) {
+        if self.capacity == 0 {
+            return;
+        }
+        // a sync committee of the same aggregate pubkey is replaced
+        self.sync_committees
+            .retain(|cached| cached.aggregate_pubkey != sync_committee.aggregate_pubkey);
+        if self.sync_committees.len() == self.capacity {
+            self.sync_committees.remove(0);
+        }
+        self.sync_committees.push(sync_committee.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethereum_light_client_verifier::consensus::test_utils::MockSyncCommitteeManager;
+
+    #[test]
+    fn test_validate_sync_committee() {
+        let scm = MockSyncCommitteeManager::<32>::new(1, 3);
+        let sync_committees: Vec<SyncCommittee<32>> = (1..=3)
+            .map(|period| scm.get_committee(period).to_committee().clone())
+            .collect();
+
+        let mut cache = RecentSyncCommittees::<32>::new(2);
+        assert!(cache.is_empty());
+        for sync_committee in sync_committees.iter() {
+            validate_sync_committee(&mut cache, sync_committee).unwrap();
+            assert_eq!(
+                cache.get(&sync_committee.aggregate_pubkey),
+                Some(sync_committee)
+            );
+        }
+        // the least recently inserted sync committee is evicted
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&sync_committees[0].aggregate_pubkey).is_none());
+        // a cached sync committee is not inserted twice
+        validate_sync_committee(&mut cache, &sync_committees[2]).unwrap();
+        assert_eq!(cache.len(), 2);
+
+        // the public keys do not match the aggregate pubkey of a cached sync committee
+        let mut forged = sync_committees[1].clone();
+        forged.pubkeys[0] = sync_committees[0].pubkeys[0].clone();
+        let res = validate_sync_committee(&mut cache, &forged);
+        assert!(res.is_err(), "{:?}", res);
+        assert_eq!(
+            cache.get(&forged.aggregate_pubkey),
+            Some(&sync_committees[1])
+        );
+
+        // nothing is cached with a capacity of zero
+        let mut cache = RecentSyncCommittees::<32>::new(0);
+        validate_sync_committee(&mut cache, &sync_committees[0]).unwrap();
+        assert!(cache.is_empty());
+        let res = validate_sync_committee(&mut NoCache, &forged);
+        assert!(res.is_err(), "{:?}", res);
+    }
+}
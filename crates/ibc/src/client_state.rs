@@ -1,15 +1,23 @@
-use crate::commitment::{calculate_ibc_commitment_storage_location, decode_eip1184_rlp_proof};
+use crate::address::Address;
+use crate::commitment::{self, CommitmentValueCodec, ExpectedCommitmentPrefix};
 use crate::consensus_state::{ConsensusState, TrustedConsensusState};
 use crate::errors::Error;
 use crate::header::Header;
-use crate::misbehaviour::Misbehaviour;
-use crate::types::AccountUpdateInfo;
-use crate::update::apply_updates;
+use crate::merkle::is_valid_merkle_branch;
+use crate::misbehaviour::{Misbehaviour, MisbehaviourEvidence};
+use crate::misc::{compute_timestamp_at_slot, height_from_block_number};
+use crate::paths;
+use crate::types::{decode_h256, AccountUpdateInfo, ConsensusUpdateInfo, ExecutionUpdateInfo};
+use crate::update::{apply_updates, UpdateRoute};
+use crate::verify;
 use crate::{eth_client_type, internal_prelude::*};
 use core::time::Duration;
-use ethereum_consensus::beacon::{Epoch, Root, Slot, Version};
+use ethereum_consensus::beacon::{Domain, Epoch, Root, Slot, Version, DOMAIN_SYNC_COMMITTEE};
+use ethereum_consensus::compute::compute_domain;
 use ethereum_consensus::fork::{ForkParameter, ForkParameters, ForkSpec, BELLATRIX_INDEX};
-use ethereum_consensus::types::{Address, H256, U64};
+use ethereum_consensus::sync_protocol::SyncCommitteePeriod;
+use ethereum_consensus::types::{H256, U64};
+use ethereum_ibc_proto::any::AnyMessage;
 use ethereum_ibc_proto::ibc::lightclients::ethereum::v1::{
     ClientState as RawClientState, Fork as RawFork, ForkSpec as RawForkSpec,
 };
@@ -18,10 +26,13 @@ use ethereum_light_client_verifier::context::{
     ChainConsensusVerificationContext, Fraction, LightClientContext,
 };
 use ethereum_light_client_verifier::execution::ExecutionVerifier;
+use ethereum_light_client_verifier::misbehaviour::Misbehaviour as MisbehaviourData;
+use ethereum_light_client_verifier::state::LightClientStoreReader;
 use ibc::core::ics02_client::client_state::{ClientState as Ics2ClientState, UpdatedState};
 use ibc::core::ics02_client::client_type::ClientType;
 use ibc::core::ics02_client::consensus_state::ConsensusState as Ics02ConsensusState;
 use ibc::core::ics02_client::error::ClientError;
+use ibc::core::ics02_client::misbehaviour::Misbehaviour as Ics02Misbehaviour;
 use ibc::core::ics03_connection::connection::ConnectionEnd;
 use ibc::core::ics24_host::identifier::{ChainId, ClientId};
 use ibc::core::ics24_host::path::ClientConsensusStatePath;
@@ -32,53 +43,91 @@ use ibc::Height;
 use ibc_proto::google::protobuf::Any;
 use ibc_proto::protobuf::Protobuf;
 use prost::Message;
-use serde::{Deserialize, Serialize};
-use tiny_keccak::{Hasher, Keccak};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
 
 /// The revision number for the Ethereum light client is always 0.
 ///
 /// Therefore, in ethereum, the revision number is not used to determine the hard fork.
 /// The current fork is determined by the client state's fork parameters.
 pub const ETHEREUM_CLIENT_REVISION_NUMBER: u64 = 0;
-pub const ETHEREUM_CLIENT_STATE_TYPE_URL: &str = "/ibc.lightclients.ethereum.v1.ClientState";
+pub const ETHEREUM_CLIENT_STATE_TYPE_URL: &str = RawClientState::TYPE_URL;
 pub const ETHEREUM_ACCOUNT_STORAGE_ROOT_INDEX: usize = 2;
+/// The index of `state_root` among the fields of the execution payload header
+const EXECUTION_PAYLOAD_STATE_ROOT_INDEX: u64 = 2;
+/// The index of `block_hash` among the fields of the execution payload header
+const EXECUTION_PAYLOAD_BLOCK_HASH_INDEX: u64 = 12;
 
+/// The client state of the Ethereum light client
+///
+/// The serde representation rejects unknown fields so that a typo in a configuration is not silently ignored.
+/// The fields added since the initial schema may be omitted and default to the values that keep its behaviour.
+/// Use `deserialize_validated` to also reject misconfigured values when loading an initial client state.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
 pub struct ClientState<const SYNC_COMMITTEE_SIZE: usize> {
     // Verification parameters
     /// `genesis_validators_root` of the target beacon chain's BeaconState
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::H256"))]
     pub genesis_validators_root: Root,
     /// https://github.com/ethereum/consensus-specs/blob/a09d0c321550c5411557674a981e2b444a1178c0/specs/altair/light-client/sync-protocol.md#misc
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::U64"))]
     pub min_sync_committee_participants: U64,
     /// `genesis_time` of the target beacon chain's BeaconState
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::U64"))]
     pub genesis_time: U64,
     /// fork parameters of the target beacon chain
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::ForkParameters"))]
     pub fork_parameters: ForkParameters,
     /// https://github.com/ethereum/consensus-specs/blob/a09d0c321550c5411557674a981e2b444a1178c0/configs/mainnet.yaml#L69
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::U64"))]
     pub seconds_per_slot: U64,
     /// https://github.com/ethereum/consensus-specs/blob/a09d0c321550c5411557674a981e2b444a1178c0/presets/mainnet/phase0.yaml#L36
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::U64"))]
     pub slots_per_epoch: Slot,
     /// https://github.com/ethereum/consensus-specs/blob/a09d0c321550c5411557674a981e2b444a1178c0/presets/mainnet/altair.yaml#L18
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::U64"))]
     pub epochs_per_sync_committee_period: Epoch,
 
     /// An address of IBC contract on execution layer
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Address"))]
     pub ibc_address: Address,
     /// The IBC contract's base storage location for storing commitments
     /// https://github.com/hyperledger-labs/yui-ibc-solidity/blob/0e83dc7aadf71380dae6e346492e148685510663/docs/architecture.md#L46
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::H256"))]
     pub ibc_commitments_slot: H256,
+    /// `commitment_value_codec` defines how the storage value proven for a path is compared with the expected commitment value
+    #[serde(default)]
+    pub commitment_value_codec: CommitmentValueCodec,
+    /// `commitment_prefix` is the commitment prefix that the counterparty must use for this client, which is `ibc` for ibc-solidity deployments
+    #[serde(default)]
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Bytes"))]
+    pub commitment_prefix: ExpectedCommitmentPrefix,
 
     /// `trust_level` is threshold of sync committee participants to consider the attestation as valid. Highly recommended to be 2/3.
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Fraction"))]
     pub trust_level: Fraction,
     /// `trusting_period` is the period in which the consensus state is considered trusted
     pub trusting_period: Duration,
     /// `max_clock_drift` defines how much new finalized header's time can drift into the future
     pub max_clock_drift: Duration,
+    /// `max_intermediate_consensus_updates` is the maximum number of intermediate consensus updates in a header. If 0, headers with intermediate consensus updates are rejected.
+    #[serde(default)]
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::U64"))]
+    pub max_intermediate_consensus_updates: U64,
 
     // State
     /// The latest block number of the stored consensus state
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::U64"))]
     pub latest_execution_block_number: U64,
     /// `frozen_height` is the height at which the client is considered frozen. If `None`, the client is unfrozen.
+    #[cfg_attr(feature = "schema", schemars(with = "Option<crate::schema::Height>"))]
     pub frozen_height: Option<Height>,
+    /// `misbehaviour_evidence_digest` is the digest of the evidence of the misbehaviour that froze the client, see `MisbehaviourEvidence::digest`.
+    /// It is `None` if the client is unfrozen or was frozen by a conflicting consensus state.
+    #[serde(default)]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<crate::schema::H256>"))]
+    pub misbehaviour_evidence_digest: Option<H256>,
 
     // Verifiers
     #[serde(skip)]
@@ -89,6 +138,9 @@ pub struct ClientState<const SYNC_COMMITTEE_SIZE: usize> {
 }
 
 impl<const SYNC_COMMITTEE_SIZE: usize> ClientState<SYNC_COMMITTEE_SIZE> {
+    /// Returns the client state frozen at `h`
+    ///
+    /// `h` should be the height at which the misbehaviour was detected.
     pub fn with_frozen_height(self, h: Height) -> Self {
         Self {
             frozen_height: Some(h),
@@ -96,17 +148,52 @@ impl<const SYNC_COMMITTEE_SIZE: usize> ClientState<SYNC_COMMITTEE_SIZE> {
         }
     }
 
+    /// Returns the client state recording `digest` of the evidence of the misbehaviour that froze it, see `MisbehaviourEvidence::digest`
+    pub fn with_misbehaviour_evidence_digest(self, digest: H256) -> Self {
+        Self {
+            misbehaviour_evidence_digest: Some(digest),
+            ..self
+        }
+    }
+
+    /// Deserialize a client state and `validate` it
+    ///
+    /// The derived `Deserialize` does not validate the client state since it also decodes the states stored by the host,
+    /// e.g. frozen ones. This is intended for loading an initial client state from a configuration in any serde format,
+    /// either directly or with `#[serde(deserialize_with = "ClientState::deserialize_validated")]`.
+    pub fn deserialize_validated<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let client_state = Self::deserialize(deserializer)?;
+        client_state.validate().map_err(D::Error::custom)?;
+        Ok(client_state)
+    }
+
+    /// Encode the client state into `Any` deterministically
+    pub fn into_any(self) -> Any {
+        self.into()
+    }
+
+    /// Returns the length of the proto-encoded client state without encoding it
+    ///
+    /// Unlike the consensus state, the length is not bounded since it grows by about 25 bytes per fork in the fork parameters,
+    /// so the length should be rechecked whenever a fork is added by an upgrade.
+    pub fn encoded_len(&self) -> usize {
+        RawClientState::from(self.clone()).encoded_len()
+    }
+
     pub fn build_context(
         &self,
         vctx: &dyn ValidationContext,
     ) -> impl ChainConsensusVerificationContext {
-        let current_timestamp = U64::from(
-            vctx.host_timestamp()
-                .unwrap()
-                .into_tm_time()
-                .unwrap()
-                .unix_timestamp() as u64,
-        );
+        self.build_context_at(vctx.host_timestamp().unwrap())
+    }
+
+    /// Build the verification context with `now` as the current time, without an ibc-rs host context
+    ///
+    /// The verifier takes the current time in seconds, so the sub-second part of `now` is truncated.
+    pub fn build_context_at(&self, now: Timestamp) -> impl ChainConsensusVerificationContext {
+        let current_timestamp = U64::from(now.nanoseconds() / 1_000_000_000);
         LightClientContext::new(
             self.fork_parameters.clone(),
             self.seconds_per_slot,
@@ -120,156 +207,489 @@ impl<const SYNC_COMMITTEE_SIZE: usize> ClientState<SYNC_COMMITTEE_SIZE> {
         )
     }
 
+    /// Verify that the storage root of the IBC contract is `account_update.account_storage_root` in the state trie of `state_root`
+    ///
+    /// See `commitment::verify_account_storage_root` for the verification of the account proof.
     pub fn verify_account_storage(
         &self,
         state_root: H256,
         account_update: &AccountUpdateInfo,
     ) -> Result<(), Error> {
-        match self
-            .execution_verifier
-            .verify_account(
-                state_root,
-                &self.ibc_address,
-                account_update.account_proof.clone(),
-            )
-            .map_err(|e| {
-                Error::MPTVerificationError(
-                    e,
-                    state_root,
-                    hex::encode(self.ibc_address.0),
-                    account_update
-                        .account_proof
-                        .iter()
-                        .map(hex::encode)
-                        .collect(),
-                )
-            })? {
-            Some(account) => {
-                if account_update.account_storage_root == account.storage_root {
-                    Ok(())
-                } else {
-                    Err(Error::AccountStorageRootMismatch(
-                        account_update.account_storage_root,
-                        account.storage_root,
-                        state_root,
-                        hex::encode(self.ibc_address.0),
-                        account_update
-                            .account_proof
-                            .iter()
-                            .map(hex::encode)
-                            .collect(),
-                    ))
-                }
-            }
-            None => {
-                if account_update.account_storage_root.is_zero() {
-                    Ok(())
-                } else {
-                    Err(Error::AccountStorageRootMismatch(
-                        account_update.account_storage_root,
-                        H256::default(),
-                        state_root,
-                        hex::encode(self.ibc_address.0),
-                        account_update
-                            .account_proof
-                            .iter()
-                            .map(hex::encode)
-                            .collect(),
-                    ))
-                }
-            }
-        }
+        commitment::verify_account_storage_root(
+            state_root,
+            &self.ibc_address,
+            &account_update.account_proof,
+            account_update.account_storage_root,
+        )
+    }
+
+    /// Returns the commitment prefix of the IBC contract, which the counterparty must use for this client
+    ///
+    /// This is the `commitment_prefix` field, which defaults to `commitment::IBC_SOLIDITY_COMMITMENT_PREFIX`, i.e. `ibc`, for ibc-solidity deployments.
+    pub fn commitment_prefix(&self) -> ibc::core::ics23_commitment::commitment::CommitmentPrefix {
+        self.commitment_prefix
+            .as_bytes()
+            .to_vec()
+            .try_into()
+            .expect("the commitment prefix is never empty")
     }
 
+    /// Verify that the commitment of `value` is stored at `path` in the IBC contract at `proof_height`
+    ///
+    /// `counterparty_prefix` must be `commitment_prefix()`. See `commitment::verify_membership` for the verification of the storage proof.
     pub fn verify_membership(
         &self,
         proof_height: ibc::Height,
-        _counterparty_prefix: &ibc::core::ics23_commitment::commitment::CommitmentPrefix,
+        counterparty_prefix: &ibc::core::ics23_commitment::commitment::CommitmentPrefix,
         proof: &ibc::core::ics23_commitment::commitment::CommitmentProofBytes,
         root: &ibc::core::ics23_commitment::commitment::CommitmentRoot,
         path: impl Into<Path>,
         value: Vec<u8>,
     ) -> Result<(), ClientError> {
         self.verify_height(proof_height)?;
-        let proof = decode_eip1184_rlp_proof(proof.clone().into())?;
-        let path = path.into();
-        let root = H256::from_slice(root.as_bytes());
-        // if root is zero, the IBC contract has not been initialized yet
-        if root.is_zero() {
-            return Err(ClientError::ClientSpecific {
-                description: format!(
-                    "failed to verify membership: root is zero: path={} value={:?}",
-                    path, value
-                ),
-            });
-        }
-        let key =
-            calculate_ibc_commitment_storage_location(&self.ibc_commitments_slot, path.clone());
-        let commitment = Self::keccak256(&value);
-        self.execution_verifier
-            .verify_membership(
-                root,
-                key.as_bytes(),
-                rlp::encode(&trim_left_zero(commitment.as_bytes())).as_ref(),
-                proof.clone(),
-            )
-            .map_err(|e| ClientError::ClientSpecific {
-                description: format!(
-                    "failed to verify membership: path={} root={} value={:?} proof={:?} error={}",
-                    path, root, value, proof, e
-                ),
-            })?;
+        self.commitment_prefix.verify(counterparty_prefix)?;
+        commitment::verify_membership(
+            root,
+            &self.ibc_commitments_slot,
+            self.commitment_value_codec,
+            proof,
+            path.into(),
+            &value,
+        )?;
+        Ok(())
+    }
+
+    /// Verify that the commitments of all `items` are stored in the IBC contract at `proof_height` with one batch proof
+    ///
+    /// The account proof of the IBC contract is verified once when the consensus state is created, so `proof`
+    /// only contains the storage trie nodes. See `commitment::verify_membership_batch` for the format of `proof`.
+    pub fn verify_membership_batch(
+        &self,
+        proof_height: ibc::Height,
+        counterparty_prefix: &ibc::core::ics23_commitment::commitment::CommitmentPrefix,
+        proof: &ibc::core::ics23_commitment::commitment::CommitmentProofBytes,
+        root: &ibc::core::ics23_commitment::commitment::CommitmentRoot,
+        items: &[(Path, Vec<u8>)],
+    ) -> Result<(), ClientError> {
+        self.verify_height(proof_height)?;
+        self.commitment_prefix.verify(counterparty_prefix)?;
+        commitment::verify_membership_batch(
+            root,
+            &self.ibc_commitments_slot,
+            self.commitment_value_codec,
+            proof,
+            items,
+        )?;
         Ok(())
     }
 
+    /// Verify that no commitment is stored at `path` in the IBC contract at `proof_height`
+    ///
+    /// `counterparty_prefix` must be `commitment_prefix()`. See `commitment::verify_non_membership` for the verification of the storage proof.
     pub fn verify_non_membership(
         &self,
         proof_height: ibc::Height,
-        _counterparty_prefix: &ibc::core::ics23_commitment::commitment::CommitmentPrefix,
+        counterparty_prefix: &ibc::core::ics23_commitment::commitment::CommitmentPrefix,
         proof: &ibc::core::ics23_commitment::commitment::CommitmentProofBytes,
         root: &ibc::core::ics23_commitment::commitment::CommitmentRoot,
         path: impl Into<Path>,
     ) -> Result<(), ibc::core::ics02_client::error::ClientError> {
         self.verify_height(proof_height)?;
-        let proof = decode_eip1184_rlp_proof(proof.clone().into())?;
-        let path = path.into();
-        let root = H256::from_slice(root.as_bytes());
-        // if root is zero, the IBC contract has not been initialized yet
-        if root.is_zero() {
-            return Err(ClientError::ClientSpecific {
-                description: format!(
-                    "failed to verify non-membership: root is zero: path={}",
-                    path
-                ),
+        self.commitment_prefix.verify(counterparty_prefix)?;
+        commitment::verify_non_membership(root, &self.ibc_commitments_slot, proof, path.into())?;
+        Ok(())
+    }
+
+    /// Verify that the sync aggregate of the update has at least `min_sync_committee_participants` participants
+    /// and that the participation satisfies the `trust_level`
+    ///
+    /// This check only counts the participation bits, so it should be performed before the BLS signature verification to reject such updates cheaply.
+    /// An aggregate without participants is rejected with `Error::ZeroSyncCommitteeParticipants` regardless of the configured minimum,
+    /// which usually means that the relayer fetched the update before the beacon node received any sync committee message for the slot.
+    pub fn verify_sync_committee_participants(
+        &self,
+        consensus_update: &ConsensusUpdateInfo<SYNC_COMMITTEE_SIZE>,
+    ) -> Result<(), Error> {
+        let participants = consensus_update
+            .sync_aggregate
+            .sync_committee_bits
+            .count_ones();
+        if participants == 0 {
+            return Err(Error::ZeroSyncCommitteeParticipants {
+                signature_slot: consensus_update.signature_slot,
             });
         }
-        let key =
-            calculate_ibc_commitment_storage_location(&self.ibc_commitments_slot, path.clone());
-        self.execution_verifier
-            .verify_non_membership(root, key.as_bytes(), proof.clone())
-            .map_err(|e| ClientError::ClientSpecific {
-                description: format!(
-                    "failed to verify non-membership: path={} root={} proof={:?} error={}",
-                    path, root, proof, e
-                ),
+        let min_sync_committee_participants = self.min_sync_committee_participants.0 as usize;
+        if participants < min_sync_committee_participants {
+            return Err(Error::InsufficientSyncCommitteeParticipants {
+                min_sync_committee_participants,
+                participants,
+            });
+        }
+        verify_trust_level_participation(&self.trust_level, participants, SYNC_COMMITTEE_SIZE)
+    }
+
+    /// Verify the signature and merkle branches of the consensus update against the trusted consensus state
+    ///
+    /// The trusted sync committee must be the one for the signature period of the update, see `TrustedConsensusState::verify_signature_period`.
+    /// `cc` must be built from the client state, e.g. with `build_context`, so that the signature is verified with the domain of `compute_sync_committee_domain_at`.
+    /// If the verification of the signature or branches fails, the error carries the signature slot, the sync committee periods of the signature and the store, the attested and finalized slots of the update,
+    /// and the fork version and domain selected for the signature.
+    pub fn verify_consensus_update<CC: ChainConsensusVerificationContext>(
+        &self,
+        cc: &CC,
+        trusted_consensus_state: &TrustedConsensusState<SYNC_COMMITTEE_SIZE>,
+        consensus_update: &ConsensusUpdateInfo<SYNC_COMMITTEE_SIZE>,
+    ) -> Result<(), Error> {
+        trusted_consensus_state.verify_signature_period(cc, consensus_update.signature_slot)?;
+        let (fork_version, signature_domain) =
+            self.sync_committee_fork_version_and_domain(consensus_update.signature_slot)?;
+        self.consensus_verifier
+            .validate_consensus_update(cc, trusted_consensus_state, consensus_update)
+            .map_err(|err| {
+                self.with_fork_schedule_hint(
+                    consensus_update.signature_slot,
+                    Error::ConsensusUpdateVerificationError {
+                        signature_slot: consensus_update.signature_slot,
+                        signature_period: consensus_update.signature_period(self),
+                        store_period: trusted_consensus_state.current_period(cc),
+                        attested_slot: consensus_update.attested_header.slot,
+                        finalized_slot: consensus_update.finalized_header.0.slot,
+                        fork_version,
+                        signature_domain,
+                        err,
+                    },
+                )
+            })
+    }
+
+    /// Wraps `err` of an update signed at `signature_slot` into `Error::SlotBeyondLastFork` if the slot is after the epoch of the last configured fork
+    ///
+    /// The signing domain of such an update is computed with the version of the last fork and `genesis_validators_root`,
+    /// so a signature mismatch may be caused by a fork of the network that is missing from the fork schedule, or by a client state configured for another network.
+    fn with_fork_schedule_hint(&self, signature_slot: Slot, err: Error) -> Error {
+        let epoch = signature_slot / self.slots_per_epoch;
+        match self.fork_parameters.forks().last() {
+            Some(last_fork) if last_fork.epoch < epoch => Error::SlotBeyondLastFork {
+                slot: signature_slot,
+                epoch,
+                last_fork_epoch: last_fork.epoch,
+                fork_version: last_fork.version.clone(),
+                genesis_validators_root: self.genesis_validators_root,
+                err: Box::new(err),
+            },
+            _ => err,
+        }
+    }
+
+    /// Verify the state root and block number branches of the execution update against the finalized execution root of the consensus update
+    ///
+    /// `verify_consensus_update` must be called beforehand so that the finalized execution root is trusted.
+    /// The block hash branch is verified too if the execution update has the block hash, see `verify_execution_block_hash`.
+    pub fn verify_execution_update(
+        &self,
+        consensus_update: &ConsensusUpdateInfo<SYNC_COMMITTEE_SIZE>,
+        execution_update: &ExecutionUpdateInfo,
+    ) -> Result<(), Error> {
+        self.consensus_verifier
+            .validate_execution_update(consensus_update.finalized_execution_root, execution_update)
+            .map_err(|err| Error::ExecutionUpdateVerificationError {
+                finalized_execution_root: consensus_update.finalized_execution_root,
+                state_root: execution_update.state_root,
+                block_number: execution_update.block_number,
+                err,
             })?;
+        self.verify_execution_block_hash(consensus_update, execution_update)
+    }
+
+    /// Verify the block hash branch of the execution update against the finalized execution root of the consensus update, if the execution update has the block hash
+    ///
+    /// The upstream verifier does not know the block hash, so the branch is verified here at the generalized index of `block_hash` in the execution payload header
+    /// of the fork at the finalized slot. `block_hash` is the 13th field and `state_root` is the 3rd field of the header in every fork since Bellatrix,
+    /// so the generalized index is derived from `ForkSpec::execution_payload_state_root_gindex`.
+    pub fn verify_execution_block_hash(
+        &self,
+        consensus_update: &ConsensusUpdateInfo<SYNC_COMMITTEE_SIZE>,
+        execution_update: &ExecutionUpdateInfo,
+    ) -> Result<(), Error> {
+        let block_hash = match execution_update.block_hash {
+            Some(block_hash) => block_hash,
+            None => return Ok(()),
+        };
+        let spec = self.fork_spec_at(consensus_update.finalized_header.0.slot)?;
+        let gindex = spec.execution_payload_state_root_gindex as u64
+            + (EXECUTION_PAYLOAD_BLOCK_HASH_INDEX - EXECUTION_PAYLOAD_STATE_ROOT_INDEX);
+        if is_valid_merkle_branch(
+            block_hash,
+            &execution_update.block_hash_branch,
+            gindex,
+            consensus_update.finalized_execution_root,
+        ) {
+            Ok(())
+        } else {
+            Err(Error::InvalidExecutionBlockHashBranch {
+                finalized_execution_root: consensus_update.finalized_execution_root,
+                block_hash,
+                gindex,
+            })
+        }
+    }
+
+    /// Returns the sync committee period of `slot` with `slots_per_epoch` and `epochs_per_sync_committee_period` of the client state
+    pub fn compute_period_at_slot(&self, slot: Slot) -> SyncCommitteePeriod {
+        slot / self.slots_per_epoch / self.epochs_per_sync_committee_period
+    }
+
+    /// Returns the domain of the sync committee signature of an update signed at `signature_slot`
+    ///
+    /// Following the sync protocol, the sync committee signs the block at `signature_slot - 1`, so the fork version is the one active at the epoch of that slot
+    /// according to the fork schedule of the client state; an update signed at the first slot of a fork epoch is signed with the version of the previous fork.
+    /// Relayers can compare the result with the domain of the network to debug a signature mismatch around a fork transition.
+    pub fn compute_sync_committee_domain_at(&self, signature_slot: Slot) -> Result<Domain, Error> {
+        self.sync_committee_fork_version_and_domain(signature_slot)
+            .map(|(_, domain)| domain)
+    }
+
+    fn sync_committee_fork_version_and_domain(
+        &self,
+        signature_slot: Slot,
+    ) -> Result<(Version, Domain), Error> {
+        let fork_version = self.fork_version_at(U64(signature_slot.0.max(1) - 1))?;
+        let domain = compute_domain(
+            &self.build_context_at(Timestamp::none()),
+            DOMAIN_SYNC_COMMITTEE,
+            Some(fork_version.clone()),
+            Some(self.genesis_validators_root),
+        )
+        .map_err(Error::EthereumConsensusError)?;
+        Ok((fork_version, domain))
+    }
+
+    /// Verify that the slot is covered by the fork schedule, i.e. it is at or after the first fork epoch
+    pub fn verify_slot_in_fork_schedule(&self, slot: Slot) -> Result<(), Error> {
+        self.fork_version_at(slot).map(|_| ())
+    }
+
+    /// Returns the version of the fork that is active at the epoch of `slot` according to the fork schedule
+    ///
+    /// The client rejects the updates signed before the first fork, so this returns `Error::SlotBeforeFirstFork` for such a slot instead of the genesis fork version.
+    /// Relayers can compare the result with the fork version of the network at the signature slot of an update to detect a misconfigured client state before submitting it.
+    pub fn fork_version_at(&self, slot: Slot) -> Result<Version, Error> {
+        self.fork_at(slot).map(|fork| fork.version.clone())
+    }
+
+    /// Returns the spec of the fork that is active at the epoch of `slot` according to the fork schedule, see `fork_version_at`
+    pub fn fork_spec_at(&self, slot: Slot) -> Result<ForkSpec, Error> {
+        self.fork_at(slot).map(|fork| fork.spec.clone())
+    }
+
+    fn fork_at(&self, slot: Slot) -> Result<&ForkParameter, Error> {
+        let epoch = slot / self.slots_per_epoch;
+        let forks = self.fork_parameters.forks();
+        if let Some(fork) = forks.iter().rev().find(|fork| fork.epoch <= epoch) {
+            return Ok(fork);
+        }
+        match forks.first() {
+            Some(first_fork) => Err(Error::SlotBeforeFirstFork {
+                slot,
+                epoch,
+                first_fork_epoch: first_fork.epoch,
+            }),
+            None => Err(Error::UninitializedClientStateField("fork_parameters")),
+        }
+    }
+
+    /// Verify the intermediate consensus updates of a header in order and return the trusted consensus state for the final consensus update
+    ///
+    /// Each update is verified with the trusted consensus state derived from the previous update,
+    /// and its next sync committee is trusted to verify the following update. Therefore, each update's signature period must be the period of the previous update's next sync committee.
+    /// If any update fails, the whole header is rejected.
+    ///
+    /// The derived consensus states are not stored because `UpdatedState` can hold only one consensus state.
+    pub fn verify_intermediate_consensus_updates<CC: ChainConsensusVerificationContext>(
+        &self,
+        cc: &CC,
+        trusted_consensus_state: TrustedConsensusState<SYNC_COMMITTEE_SIZE>,
+        updates: &[ConsensusUpdateInfo<SYNC_COMMITTEE_SIZE>],
+    ) -> Result<TrustedConsensusState<SYNC_COMMITTEE_SIZE>, Error> {
+        if updates.len() as u64 > self.max_intermediate_consensus_updates.0 {
+            return Err(Error::TooManyIntermediateConsensusUpdates {
+                max: self.max_intermediate_consensus_updates,
+                actual: updates.len(),
+            });
+        }
+        updates
+            .iter()
+            .enumerate()
+            .try_fold(trusted_consensus_state, |trusted, (index, update)| {
+                self.verify_slot_in_fork_schedule(update.signature_slot)?;
+                self.verify_sync_committee_participants(update)?;
+                // an intermediate update must advance the trusted consensus state to the next period
+                let trusted_slot = trusted.slot();
+                if update.finalized_header.0.slot <= trusted_slot {
+                    return Err(Error::IntermediateConsensusUpdateError {
+                        index,
+                        err: Box::new(Error::FinalizedSlotNotAdvanced {
+                            finalized_slot: update.finalized_header.0.slot,
+                            trusted_slot,
+                        }),
+                    });
+                }
+                self.verify_consensus_update(cc, &trusted, update)
+                    .map_err(|err| Error::IntermediateConsensusUpdateError {
+                        index,
+                        err: Box::new(err),
+                    })?;
+                let next_sync_committee = match &update.next_sync_committee {
+                    Some((next_sync_committee, _)) => next_sync_committee.clone(),
+                    None => {
+                        return Err(Error::MissingNextSyncCommitteeInIntermediateUpdate { index })
+                    }
+                };
+                let consensus_state = ConsensusState::from(trusted);
+                let (_, consensus_state) = apply_updates(
+                    cc,
+                    self,
+                    &consensus_state,
+                    update.clone(),
+                    self.latest_execution_block_number,
+                    None,
+                    H256::from_slice(consensus_state.storage_root.as_bytes()),
+                    compute_timestamp_at_slot(
+                        self.genesis_time,
+                        self.seconds_per_slot,
+                        update.finalized_header.0.slot,
+                    )?,
+                )?;
+                TrustedConsensusState::new(cc, consensus_state, next_sync_committee, true)
+            })
+    }
+
+    /// Verify that each update of the misbehaviour is valid against its own trusted consensus state
+    ///
+    /// `trusted_consensus_states` corresponds to `Misbehaviour::trusted_sync_committees`, so the two updates may be signed in different sync committee periods.
+    /// Each update's signature and merkle branches are verified independently. `Misbehaviour::validate` should be called beforehand.
+    /// For `NextSyncCommittee`, the attested headers of both updates must be in the same sync committee period.
+    pub fn verify_misbehaviour<CC: ChainConsensusVerificationContext>(
+        &self,
+        cc: &CC,
+        trusted_consensus_states: (
+            &TrustedConsensusState<SYNC_COMMITTEE_SIZE>,
+            &TrustedConsensusState<SYNC_COMMITTEE_SIZE>,
+        ),
+        misbehaviour: &Misbehaviour<SYNC_COMMITTEE_SIZE>,
+    ) -> Result<(), Error> {
+        if let MisbehaviourData::NextSyncCommittee(data) = &misbehaviour.data {
+            let period_1 =
+                self.compute_period_at_slot(data.consensus_update_1.attested_header.slot);
+            let period_2 =
+                self.compute_period_at_slot(data.consensus_update_2.attested_header.slot);
+            if period_1 != period_2 {
+                return Err(Error::NextSyncCommitteeMisbehaviourPeriodMismatch {
+                    period_1,
+                    period_2,
+                });
+            }
+        }
+        let (update_1, update_2) = misbehaviour.consensus_updates();
+        for (index, (trusted_consensus_state, update)) in [
+            (trusted_consensus_states.0, update_1),
+            (trusted_consensus_states.1, update_2),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            self.verify_slot_in_fork_schedule(update.signature_slot)?;
+            self.verify_sync_committee_participants(update)?;
+            self.verify_consensus_update(cc, trusted_consensus_state, update)
+                .map_err(|err| Error::MisbehaviourConsensusUpdateError {
+                    index,
+                    err: Box::new(err),
+                })?;
+        }
         Ok(())
     }
 
-    /// Verify that the client is at a sufficient height and unfrozen
+    /// Returns how the update from the trusted consensus state at `trusted_height` to `new_consensus_state` at `height` is applied
+    ///
+    /// The finalized slot of the update, i.e. the slot of `new_consensus_state`, is compared with the slot of the trusted consensus state:
+    /// - a later slot is `UpdateRoute::Advance`
+    /// - the same slot is `UpdateRoute::NoOp` if the update is for the same execution block and does not conflict with the trusted consensus state by `check_for_misbehaviour`, otherwise `UpdateRoute::Misbehaviour`
+    /// - an earlier slot is `UpdateRoute::Historical`, which `verify::simulate_update` rejects unless the consensus state at `height` is already stored
+    pub fn route_update(
+        &self,
+        trusted_height: Height,
+        trusted_consensus_state: &ConsensusState,
+        height: Height,
+        new_consensus_state: &ConsensusState,
+    ) -> UpdateRoute {
+        match new_consensus_state.slot.cmp(&trusted_consensus_state.slot) {
+            core::cmp::Ordering::Greater => UpdateRoute::Advance,
+            core::cmp::Ordering::Equal
+                if height == trusted_height
+                    && !self.check_for_misbehaviour(
+                        new_consensus_state,
+                        Some(trusted_consensus_state),
+                    ) =>
+            {
+                UpdateRoute::NoOp
+            }
+            core::cmp::Ordering::Equal => UpdateRoute::Misbehaviour,
+            core::cmp::Ordering::Less => UpdateRoute::Historical,
+        }
+    }
+
+    /// Returns `true` if `new_consensus_state` conflicts with `stored_consensus_state` stored at the same height, in which case the client should be frozen
+    ///
+    /// The consensus states conflict if their storage roots, timestamps or current sync committees differ,
+    /// or if both have the next sync committee or the block hash and they differ. An identical re-submission is not a conflict.
+    pub fn check_for_misbehaviour(
+        &self,
+        new_consensus_state: &ConsensusState,
+        stored_consensus_state: Option<&ConsensusState>,
+    ) -> bool {
+        let stored = match stored_consensus_state {
+            Some(stored) => stored,
+            None => return false,
+        };
+        let next_sync_committee_conflicts = match (
+            new_consensus_state.sync_committee_aggregate(true),
+            stored.sync_committee_aggregate(true),
+        ) {
+            (Some(new), Some(stored)) => new != stored,
+            _ => false,
+        };
+        let block_hash_conflicts = match (
+            new_consensus_state.current_block_hash,
+            stored.current_block_hash,
+        ) {
+            (Some(new), Some(stored)) => new != stored,
+            _ => false,
+        };
+        new_consensus_state.storage_root != stored.storage_root
+            || new_consensus_state.timestamp != stored.timestamp
+            || new_consensus_state.current_sync_committee != stored.current_sync_committee
+            || next_sync_committee_conflicts
+            || block_hash_conflicts
+    }
+
+    /// Verify that the client is not frozen
+    pub fn verify_not_frozen(&self) -> Result<(), Error> {
+        match self.frozen_height {
+            Some(frozen_height) => Err(Error::ClientFrozen { frozen_height }),
+            None => Ok(()),
+        }
+    }
+
+    /// Verify that the client is unfrozen and at a sufficient height
     pub fn verify_height(&self, height: Height) -> Result<(), Error> {
+        self.verify_not_frozen()?;
         if height.revision_number() != ETHEREUM_CLIENT_REVISION_NUMBER {
             return Err(Error::UnexpectedHeightRevisionNumber {
                 expected: ETHEREUM_CLIENT_REVISION_NUMBER,
                 got: height.revision_number(),
             });
         }
-        if self.is_frozen() {
-            return Err(Error::ClientFrozen {
-                frozen_height: self.frozen_height.unwrap(),
-                target_height: height,
-            });
-        }
         if self.latest_height() < height {
             return Err(Error::InsufficientHeight {
                 latest_height: self.latest_height(),
@@ -294,6 +714,8 @@ impl<const SYNC_COMMITTEE_SIZE: usize> ClientState<SYNC_COMMITTEE_SIZE> {
             Err(Error::UninitializedClientStateField("fork_parameters"))
         } else if self.fork_parameters.forks().len() <= BELLATRIX_INDEX {
             Err(Error::MissingBellatrixFork)
+        } else if let Err(e) = validate_fork_parameters(&self.fork_parameters) {
+            Err(e)
         } else if self.seconds_per_slot == U64::default() {
             Err(Error::UninitializedClientStateField("seconds_per_slot"))
         } else if self.slots_per_epoch == Slot::default() {
@@ -306,6 +728,11 @@ impl<const SYNC_COMMITTEE_SIZE: usize> ClientState<SYNC_COMMITTEE_SIZE> {
             Err(Error::UninitializedClientStateField("ibc_address"))
         } else if self.trust_level == Fraction::default() {
             Err(Error::UninitializedClientStateField("trust_level"))
+        } else if !is_valid_trust_level(&self.trust_level) {
+            Err(Error::InvalidTrustLevel {
+                numerator: self.trust_level.numerator(),
+                denominator: self.trust_level.denominator(),
+            })
         } else if self.trusting_period == Duration::default() {
             Err(Error::UninitializedClientStateField("trusting_period"))
         } else if self.latest_execution_block_number == U64::default() {
@@ -317,212 +744,402 @@ impl<const SYNC_COMMITTEE_SIZE: usize> ClientState<SYNC_COMMITTEE_SIZE> {
         }
     }
 
-    fn keccak256(bz: &[u8]) -> H256 {
-        let mut hasher = Keccak::v256();
-        let mut output = [0u8; 32];
-        hasher.update(bz);
-        hasher.finalize(&mut output);
-        H256::from_slice(&output)
-    }
-}
-
-impl<const SYNC_COMMITTEE_SIZE: usize> Ics2ClientState for ClientState<SYNC_COMMITTEE_SIZE> {
-    fn client_type(&self) -> ClientType {
-        eth_client_type()
-    }
+    /// Validate that the initial consensus state is consistent with the client state
+    ///
+    /// The following conditions are checked:
+    /// - the timestamp of the consensus state matches the one computed from `genesis_time`, `seconds_per_slot` and the slot
+    /// - the slot of the consensus state is at or after the bellatrix fork epoch
+    pub fn validate_initial_consensus_state(
+        &self,
+        consensus_state: &ConsensusState,
+    ) -> Result<(), Error> {
+        let slot = consensus_state.slot;
+        let expected = compute_timestamp_at_slot(self.genesis_time, self.seconds_per_slot, slot)?;
+        if consensus_state.timestamp != expected {
+            return Err(Error::InitialConsensusStateTimestampMismatch {
+                slot,
+                expected,
+                actual: consensus_state.timestamp,
+            });
+        }
 
-    fn latest_height(&self) -> Height {
-        Height::new(
-            ETHEREUM_CLIENT_REVISION_NUMBER,
-            self.latest_execution_block_number.into(),
-        )
-        .unwrap()
+        let epoch = slot / self.slots_per_epoch;
+        let bellatrix_epoch = self.fork_parameters.forks()[BELLATRIX_INDEX].epoch;
+        if epoch < bellatrix_epoch {
+            return Err(Error::InitialConsensusStateBeforeBellatrixFork {
+                slot,
+                epoch,
+                bellatrix_epoch,
+            });
+        }
+        Ok(())
     }
 
-    fn frozen_height(&self) -> Option<Height> {
-        self.frozen_height
+    /// Returns the heights of the given consensus states that can be safely pruned at `host_timestamp`
+    ///
+    /// A consensus state is prunable if its trusting period has expired. However, the following consensus states are always retained even if expired:
+    /// - the consensus states in the latest sync committee period among the given ones, because they hold the current and next sync committees
+    ///   required to verify subsequent updates. Pruning them would make the client unable to be updated.
+    /// - the consensus state at the latest height of the client
+    pub fn prunable_heights(
+        &self,
+        consensus_states: &[(Height, ConsensusState)],
+        host_timestamp: Timestamp,
+    ) -> Vec<Height> {
+        let latest_period = match consensus_states.iter().map(|(_, cs)| cs.slot).max() {
+            Some(slot) => self.compute_period_at_slot(slot),
+            None => return Vec::new(),
+        };
+        let latest_height = self.latest_height();
+        consensus_states
+            .iter()
+            .filter(|(height, cs)| {
+                *height != latest_height
+                    && self.compute_period_at_slot(cs.slot) != latest_period
+                    && validate_state_timestamp_within_trusting_period(
+                        host_timestamp,
+                        self.trusting_period,
+                        cs.timestamp,
+                    )
+                    .is_err()
+            })
+            .map(|(height, _)| *height)
+            .collect()
     }
 
-    fn initialise(
+    /// Returns the heights of the given consensus states that expired at `header_timestamp`, in ascending order
+    ///
+    /// A consensus state expires if its timestamp is older than `trusting_period + max_clock_drift` before `header_timestamp`.
+    /// The consensus state at `trusted_height`, which backs the sync committee trusted by the update, and the consensus state at the latest height are never pruned.
+    /// The result depends only on the arguments, not on the host's clock or the order of `consensus_states`, so that all validators of the host prune identically.
+    pub fn expired_heights(
         &self,
-        consensus_state: Any,
-    ) -> Result<Box<dyn Ics02ConsensusState>, ClientError> {
-        self.validate()?;
-        if self.is_frozen() {
-            return Err(Error::CannotInitializeFrozenClient.into());
-        }
-        let consensus_state = ConsensusState::try_from(consensus_state)?;
-        consensus_state.validate()?;
-        Ok(ConsensusState::into_box(consensus_state))
+        consensus_states: &[(Height, ConsensusState)],
+        header_timestamp: Timestamp,
+        trusted_height: Height,
+    ) -> Vec<Height> {
+        let expiry = self.trusting_period + self.max_clock_drift;
+        let latest_height = self.latest_height();
+        let mut heights: Vec<Height> = consensus_states
+            .iter()
+            .filter(|(height, cs)| {
+                *height != trusted_height
+                    && *height != latest_height
+                    // a consensus state whose expiry overflows never expires
+                    && (cs.timestamp + expiry).is_ok_and(|end| header_timestamp.after(&end))
+            })
+            .map(|(height, _)| *height)
+            .collect();
+        heights.sort();
+        heights.dedup();
+        heights
     }
 
-    fn check_header_and_update_state(
+    /// Verify the header and return the result of the update
+    ///
+    /// This is the implementation of `check_header_and_update_state`, which returns the details of the update for the host to emit events and prune consensus states.
+    /// The update is computed by `verify::simulate_update` with the consensus states read from `ctx`, so the dry run and the update never diverge.
+    /// `stored_heights` are the heights of the consensus states stored for the client, which the host passes because `ValidationContext` cannot enumerate them.
+    /// The consensus states at these heights are read from `ctx`, and `UpdateResult::prunable_heights` is computed from them by `expired_heights` at the header timestamp.
+    pub fn update_state(
         &self,
         ctx: &dyn ValidationContext,
         client_id: ClientId,
         header: Any,
-    ) -> Result<UpdatedState, ClientError> {
-        if self.is_frozen() {
-            return Err(ClientError::ClientFrozen { client_id });
-        }
+        stored_heights: &[Height],
+    ) -> Result<UpdateResult<SYNC_COMMITTEE_SIZE>, ClientError> {
+        self.verify_not_frozen()?;
         let cc = self.build_context(ctx);
         let header = Header::<SYNC_COMMITTEE_SIZE>::try_from(header)?;
+        // `Header::height` requires a valid header
+        header.validate_basic()?;
         header.validate(&cc)?;
 
-        let trusted_sync_committee = header.trusted_sync_committee;
+        let trusted_height = header.trusted_height();
         let consensus_state = match maybe_consensus_state(
             ctx,
-            &ClientConsensusStatePath::new(&client_id, &trusted_sync_committee.height),
+            &ClientConsensusStatePath::new(&client_id, &trusted_height),
         )? {
             Some(cs) => cs,
             None => {
                 return Err(ClientError::ConsensusStateNotFound {
                     client_id,
-                    height: trusted_sync_committee.height,
+                    height: trusted_height,
                 })
             }
         };
 
-        let trusted_consensus_state = TrustedConsensusState::new(
-            consensus_state.clone(),
-            trusted_sync_committee.sync_committee,
-            trusted_sync_committee.is_next,
+        // the consensus state already stored at the height of the header, if any
+        let header_height = header.height();
+        let stored_consensus_state = maybe_consensus_state(
+            ctx,
+            &ClientConsensusStatePath::new(&client_id, &header_height),
         )?;
 
-        let consensus_update = header.consensus_update;
-        let execution_update = header.execution_update;
-        let account_update = header.account_update;
-        let header_timestamp = header.timestamp;
-
-        self.consensus_verifier
-            .validate_updates(
-                &cc,
-                &trusted_consensus_state,
-                &consensus_update,
-                &execution_update,
-            )
-            .map_err(Error::VerificationError)?;
-
-        self.verify_account_storage(execution_update.state_root, &account_update)?;
+        let mut consensus_states = Vec::new();
+        for height in stored_heights.iter() {
+            if let Some(cs) =
+                maybe_consensus_state(ctx, &ClientConsensusStatePath::new(&client_id, height))?
+            {
+                consensus_states.push((*height, cs));
+            }
+        }
 
         let host_timestamp = ctx
             .host_timestamp()
             .map_err(|e| ClientError::ClientSpecific {
                 description: e.to_string(),
             })?;
-        // check if the current timestamp is within the trusting period
-        validate_state_timestamp_within_trusting_period(
-            host_timestamp,
-            self.trusting_period,
-            consensus_state.timestamp,
-        )?;
-        // check if the header timestamp does not indicate a future time
-        validate_header_timestamp_not_future(
-            host_timestamp,
-            self.max_clock_drift,
-            header_timestamp,
-        )?;
-
-        let (new_client_state, new_consensus_state) = apply_updates(
-            &cc,
+        Ok(verify::simulate_update(
             self,
             &consensus_state,
-            consensus_update,
-            execution_update.block_number,
-            account_update.account_storage_root,
-            header_timestamp,
-        )?;
-
-        Ok(UpdatedState {
-            client_state: new_client_state.into_box(),
-            consensus_state: new_consensus_state.into_box(),
-        })
+            stored_consensus_state.as_ref(),
+            &consensus_states,
+            header,
+            host_timestamp,
+        )?)
     }
 
-    fn check_misbehaviour_and_update_state(
+    /// Verify the misbehaviour and return the client state frozen by it with the evidence
+    ///
+    /// This is the implementation of `check_misbehaviour_and_update_state`. The frozen client state records the digest of `MisbehaviourResult::evidence`,
+    /// which the host can keep or emit to justify the freeze.
+    pub fn update_state_on_misbehaviour(
         &self,
         ctx: &dyn ValidationContext,
         client_id: ClientId,
         misbehaviour: Any,
-    ) -> Result<alloc::boxed::Box<dyn Ics2ClientState>, ibc::core::ContextError> {
-        if self.is_frozen() {
-            return Err(ClientError::ClientFrozen { client_id }.into());
-        }
+    ) -> Result<MisbehaviourResult<SYNC_COMMITTEE_SIZE>, ClientError> {
+        self.verify_not_frozen()?;
         let misbehaviour = Misbehaviour::<SYNC_COMMITTEE_SIZE>::try_from(misbehaviour)?;
-        misbehaviour.validate()?;
         if misbehaviour.client_id != client_id {
             return Err(
                 Error::UnexpectedClientIdInMisbehaviour(client_id, misbehaviour.client_id).into(),
             );
         }
 
-        let consensus_state = match maybe_consensus_state(
-            ctx,
-            &ClientConsensusStatePath::new(&client_id, &misbehaviour.trusted_sync_committee.height),
-        )? {
-            Some(cs) => cs,
-            None => {
-                return Err(ClientError::ConsensusStateNotFound {
-                    client_id,
-                    height: misbehaviour.trusted_sync_committee.height,
+        let (trusted_sync_committee_1, trusted_sync_committee_2) =
+            misbehaviour.trusted_sync_committees();
+        let mut trusted_consensus_states = Vec::new();
+        for trusted_sync_committee in [trusted_sync_committee_1, trusted_sync_committee_2] {
+            let consensus_state = match maybe_consensus_state(
+                ctx,
+                &ClientConsensusStatePath::new(&client_id, &trusted_sync_committee.height),
+            )? {
+                Some(cs) => cs,
+                None => {
+                    return Err(ClientError::ConsensusStateNotFound {
+                        client_id,
+                        height: trusted_sync_committee.height,
+                    })
                 }
-                .into())
-            }
-        };
-
-        let cc = self.build_context(ctx);
-        let trusted_consensus_state = TrustedConsensusState::new(
-            consensus_state.clone(),
-            misbehaviour.trusted_sync_committee.sync_committee,
-            misbehaviour.trusted_sync_committee.is_next,
-        )?;
-
-        self.consensus_verifier
-            .validate_misbehaviour(&cc, &trusted_consensus_state, misbehaviour.data)
-            .map_err(Error::VerificationError)?;
+            };
+            trusted_consensus_states.push(consensus_state);
+        }
 
         let host_timestamp = ctx
             .host_timestamp()
             .map_err(|e| ClientError::ClientSpecific {
                 description: e.to_string(),
             })?;
-        validate_state_timestamp_within_trusting_period(
+        verify::verify_misbehaviour(
+            self,
+            (&trusted_consensus_states[0], &trusted_consensus_states[1]),
+            &misbehaviour,
             host_timestamp,
-            self.trusting_period,
-            consensus_state.timestamp,
         )?;
 
         // found misbehaviour
-        Ok(self
-            .clone()
-            .with_frozen_height(misbehaviour.trusted_sync_committee.height)
-            .into_box())
+        let evidence = misbehaviour.evidence(self)?;
+        Ok(MisbehaviourResult {
+            client_state: self
+                .clone()
+                .with_frozen_height(misbehaviour.height())
+                .with_misbehaviour_evidence_digest(evidence.digest()),
+            evidence,
+        })
     }
 
-    fn verify_client_consensus_state(
+    pub(crate) fn update_result(
         &self,
-        proof_height: ibc::Height,
-        counterparty_prefix: &ibc::core::ics23_commitment::commitment::CommitmentPrefix,
-        proof: &ibc::core::ics23_commitment::commitment::CommitmentProofBytes,
-        root: &ibc::core::ics23_commitment::commitment::CommitmentRoot,
-        client_cons_state_path: &ibc::core::ics24_host::path::ClientConsensusStatePath,
-        expected_consensus_state: &dyn ibc::core::ics02_client::consensus_state::ConsensusState,
-    ) -> Result<(), ClientError> {
-        let value = expected_consensus_state
-            .encode_vec()
-            .map_err(ClientError::InvalidAnyConsensusState)?;
-        self.verify_membership(
-            proof_height,
-            counterparty_prefix,
-            proof,
-            root,
-            client_cons_state_path.clone(),
-            value,
-        )
+        new_client_state: Self,
+        height: Height,
+        consensus_state: ConsensusState,
+        committee_rotated: bool,
+        prunable_heights: Vec<Height>,
+    ) -> UpdateResult<SYNC_COMMITTEE_SIZE> {
+        UpdateResult {
+            height,
+            consensus_state,
+            client_state: if new_client_state == *self {
+                None
+            } else {
+                Some(new_client_state)
+            },
+            committee_rotated,
+            prunable_heights,
+        }
     }
+}
 
-    fn verify_connection_state(
-        &self,
+/// The result of `ClientState::update_state`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UpdateResult<const SYNC_COMMITTEE_SIZE: usize> {
+    /// The height of the consensus state written by the update
+    pub height: Height,
+    /// The consensus state at `height`. If a consensus state is already stored at `height`, it is returned as is because it is never overwritten.
+    pub consensus_state: ConsensusState,
+    /// The new client state, or `None` if the update does not change the client state
+    ///
+    /// The client state changes if the latest height advances or the client is frozen due to a conflicting consensus state at `height`.
+    pub client_state: Option<ClientState<SYNC_COMMITTEE_SIZE>>,
+    /// `true` if `consensus_state` is in a later sync committee period than the trusted consensus state, i.e. the sync committees rotated
+    pub committee_rotated: bool,
+    /// The heights of the consensus states to be deleted by the host after the update, in ascending order, see `ClientState::expired_heights`
+    pub prunable_heights: Vec<Height>,
+}
+
+impl<const SYNC_COMMITTEE_SIZE: usize> UpdateResult<SYNC_COMMITTEE_SIZE> {
+    /// Converts the result into the `UpdatedState` of ibc-rs, where `client_state` is the client state of `client_id` before the update
+    ///
+    /// The update handler of ibc-rs writes `UpdatedState::consensus_state` at the latest height of `UpdatedState::client_state`,
+    /// and records the current host time and height as its processed time and height, which the delay periods of the proofs count from.
+    /// So an update that writes nothing, i.e. `UpdateRoute::NoOp` or a header for a consensus state already stored at `height`, fails with `Error::ConsensusStateAlreadyStored`
+    /// instead of resetting the processed time of the latest height.
+    /// If the update freezes the client and `height` is not the latest height, the consensus state stored at the latest height is returned unchanged instead of `consensus_state`.
+    /// An update that stores a new consensus state below the latest height is rejected by `update_state` already, and fails with `Error::HistoricalConsensusStateNotSupported` here too.
+    /// `UpdatedState` has no room for `prunable_heights`, so a host that prunes deletes them from the result before the conversion.
+    pub fn into_updated_state(
+        self,
+        ctx: &dyn ValidationContext,
+        client_id: &ClientId,
+        client_state: &ClientState<SYNC_COMMITTEE_SIZE>,
+    ) -> Result<UpdatedState, ClientError> {
+        let new_client_state = self.client_state.unwrap_or_else(|| client_state.clone());
+        let stored_consensus_state =
+            maybe_consensus_state(ctx, &ClientConsensusStatePath::new(client_id, &self.height))?;
+        // the update of a frozen client is rejected by `update_state`, so only a freezing update is written over a stored consensus state
+        if stored_consensus_state.is_some() && new_client_state.frozen_height.is_none() {
+            return Err(Error::ConsensusStateAlreadyStored {
+                height: self.height,
+            }
+            .into());
+        }
+        let latest_height = new_client_state.latest_height();
+        if self.height == latest_height {
+            return Ok(UpdatedState {
+                client_state: new_client_state.into_box(),
+                consensus_state: self.consensus_state.into_box(),
+            });
+        }
+        if stored_consensus_state.as_ref() != Some(&self.consensus_state) {
+            return Err(Error::HistoricalConsensusStateNotSupported {
+                height: self.height,
+                latest_height,
+            }
+            .into());
+        }
+        let latest_consensus_state = maybe_consensus_state(
+            ctx,
+            &ClientConsensusStatePath::new(client_id, &latest_height),
+        )?
+        .ok_or_else(|| ClientError::ConsensusStateNotFound {
+            client_id: client_id.clone(),
+            height: latest_height,
+        })?;
+        Ok(UpdatedState {
+            client_state: new_client_state.into_box(),
+            consensus_state: latest_consensus_state.into_box(),
+        })
+    }
+}
+
+/// The result of `ClientState::update_state_on_misbehaviour`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MisbehaviourResult<const SYNC_COMMITTEE_SIZE: usize> {
+    /// The client state frozen at the height of the misbehaviour, which records the digest of `evidence`
+    pub client_state: ClientState<SYNC_COMMITTEE_SIZE>,
+    /// The summary of the verified misbehaviour
+    pub evidence: MisbehaviourEvidence,
+}
+
+impl<const SYNC_COMMITTEE_SIZE: usize> Ics2ClientState for ClientState<SYNC_COMMITTEE_SIZE> {
+    fn client_type(&self) -> ClientType {
+        eth_client_type()
+    }
+
+    fn latest_height(&self) -> Height {
+        height_from_block_number(self.latest_execution_block_number).unwrap()
+    }
+
+    fn frozen_height(&self) -> Option<Height> {
+        self.frozen_height
+    }
+
+    fn initialise(
+        &self,
+        consensus_state: Any,
+    ) -> Result<Box<dyn Ics02ConsensusState>, ClientError> {
+        self.validate()?;
+        if self.is_frozen() {
+            return Err(Error::CannotInitializeFrozenClient.into());
+        }
+        let consensus_state = ConsensusState::try_from(consensus_state)?;
+        consensus_state.validate()?;
+        self.validate_initial_consensus_state(&consensus_state)?;
+        Ok(ConsensusState::into_box(consensus_state))
+    }
+
+    /// Verify the header with `update_state` and return the states for the update handler of ibc-rs, see `UpdateResult::into_updated_state`
+    ///
+    /// The handler can neither enumerate nor delete the stored consensus states, so nothing is pruned here.
+    /// A host that prunes calls `update_state` with the heights of the stored consensus states and deletes `UpdateResult::prunable_heights` itself.
+    fn check_header_and_update_state(
+        &self,
+        ctx: &dyn ValidationContext,
+        client_id: ClientId,
+        header: Any,
+    ) -> Result<UpdatedState, ClientError> {
+        self.update_state(ctx, client_id.clone(), header, &[])?
+            .into_updated_state(ctx, &client_id, self)
+    }
+
+    fn check_misbehaviour_and_update_state(
+        &self,
+        ctx: &dyn ValidationContext,
+        client_id: ClientId,
+        misbehaviour: Any,
+    ) -> Result<alloc::boxed::Box<dyn Ics2ClientState>, ibc::core::ContextError> {
+        Ok(self
+            .update_state_on_misbehaviour(ctx, client_id, misbehaviour)?
+            .client_state
+            .into_box())
+    }
+
+    fn verify_client_consensus_state(
+        &self,
+        proof_height: ibc::Height,
+        counterparty_prefix: &ibc::core::ics23_commitment::commitment::CommitmentPrefix,
+        proof: &ibc::core::ics23_commitment::commitment::CommitmentProofBytes,
+        root: &ibc::core::ics23_commitment::commitment::CommitmentRoot,
+        client_cons_state_path: &ibc::core::ics24_host::path::ClientConsensusStatePath,
+        expected_consensus_state: &dyn ibc::core::ics02_client::consensus_state::ConsensusState,
+    ) -> Result<(), ClientError> {
+        let value = expected_consensus_state
+            .encode_vec()
+            .map_err(ClientError::InvalidAnyConsensusState)?;
+        self.verify_membership(
+            proof_height,
+            counterparty_prefix,
+            proof,
+            root,
+            client_cons_state_path.clone(),
+            value,
+        )
+    }
+
+    fn verify_connection_state(
+        &self,
         proof_height: ibc::Height,
         counterparty_prefix: &ibc::core::ics23_commitment::commitment::CommitmentPrefix,
         proof: &ibc::core::ics23_commitment::commitment::CommitmentProofBytes,
@@ -530,16 +1147,13 @@ impl<const SYNC_COMMITTEE_SIZE: usize> Ics2ClientState for ClientState<SYNC_COMM
         counterparty_conn_path: &ibc::core::ics24_host::path::ConnectionPath,
         expected_counterparty_connection_end: &ibc::core::ics03_connection::connection::ConnectionEnd,
     ) -> Result<(), ClientError> {
-        let value = expected_counterparty_connection_end
-            .encode_vec()
-            .map_err(ClientError::InvalidConnectionEnd)?;
         self.verify_membership(
             proof_height,
             counterparty_prefix,
             proof,
             root,
             counterparty_conn_path.clone(),
-            value,
+            paths::connection_state_value(expected_counterparty_connection_end),
         )
     }
 
@@ -552,17 +1166,13 @@ impl<const SYNC_COMMITTEE_SIZE: usize> Ics2ClientState for ClientState<SYNC_COMM
         counterparty_chan_end_path: &ibc::core::ics24_host::path::ChannelEndPath,
         expected_counterparty_channel_end: &ibc::core::ics04_channel::channel::ChannelEnd,
     ) -> Result<(), ClientError> {
-        let value = expected_counterparty_channel_end
-            .encode_vec()
-            .map_err(ClientError::InvalidChannelEnd)?;
-
         self.verify_membership(
             proof_height,
             counterparty_prefix,
             proof,
             root,
             counterparty_chan_end_path.clone(),
-            value,
+            paths::channel_state_value(expected_counterparty_channel_end),
         )
     }
 
@@ -640,18 +1250,13 @@ impl<const SYNC_COMMITTEE_SIZE: usize> Ics2ClientState for ClientState<SYNC_COMM
         sequence: ibc::core::ics04_channel::packet::Sequence,
     ) -> Result<(), ClientError> {
         verify_delay_passed(ctx, proof_height, connection_end)?;
-        let mut seq_bytes = Vec::new();
-        u64::from(sequence)
-            .encode(&mut seq_bytes)
-            .expect("buffer size too small");
-
         self.verify_membership(
             proof_height,
             connection_end.counterparty().prefix(),
             proof,
             root,
             seq_recv_path.clone(),
-            seq_bytes,
+            paths::next_sequence_recv_value(sequence),
         )
     }
 
@@ -674,14 +1279,16 @@ impl<const SYNC_COMMITTEE_SIZE: usize> Ics2ClientState for ClientState<SYNC_COMM
         )
     }
 
-    // `chain_id`, `expired`, `zero_custom_fields`, `verify_upgrade_client`, `update_state_with_upgrade_client` are not supported for Ethereum client
-
-    fn chain_id(&self) -> ChainId {
-        unimplemented!()
+    /// Returns `true` if the consensus state `elapsed` old is out of the trusting period, as `validate_state_timestamp_within_trusting_period` rejects it
+    ///
+    /// The update handler of ibc-rs checks the consensus state at the latest height with this before `check_header_and_update_state`.
+    fn expired(&self, elapsed: Duration) -> bool {
+        elapsed >= self.trusting_period
     }
 
-    #[allow(unused_variables)]
-    fn expired(&self, elapsed: Duration) -> bool {
+    // `chain_id`, `zero_custom_fields`, `verify_upgrade_client`, `update_state_with_upgrade_client` are not supported for Ethereum client
+
+    fn chain_id(&self) -> ChainId {
         unimplemented!()
     }
 
@@ -711,7 +1318,76 @@ impl<const SYNC_COMMITTEE_SIZE: usize> Ics2ClientState for ClientState<SYNC_COMM
     }
 }
 
-fn validate_state_timestamp_within_trusting_period(
+/// Validate that the fork epochs are in ascending order and each fork version is unique
+///
+/// Note that consecutive forks may share the same epoch (e.g. devnets that activate several forks at genesis); in that case the later fork takes effect.
+fn validate_fork_parameters(fork_parameters: &ForkParameters) -> Result<(), Error> {
+    let forks = fork_parameters.forks();
+    for (i, fork) in forks.iter().enumerate() {
+        if fork.version == *fork_parameters.genesis_version()
+            || forks[..i].iter().any(|f| f.version == fork.version)
+        {
+            return Err(Error::DuplicatedForkVersion {
+                index: i,
+                version: fork.version.clone(),
+            });
+        }
+        if i > 0 && forks[i - 1].epoch > fork.epoch {
+            return Err(Error::UnorderedForkEpochs {
+                index: i,
+                prev_epoch: forks[i - 1].epoch,
+                epoch: fork.epoch,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Returns true if the trust level is in the range [1/3, 1]
+fn is_valid_trust_level(trust_level: &Fraction) -> bool {
+    let numerator = trust_level.numerator() as u128;
+    let denominator = trust_level.denominator() as u128;
+    denominator != 0 && numerator <= denominator && numerator * 3 >= denominator
+}
+
+/// Verify that `participants / sync_committee_size >= trust_level`
+fn verify_trust_level_participation(
+    trust_level: &Fraction,
+    participants: usize,
+    sync_committee_size: usize,
+) -> Result<(), Error> {
+    let numerator = trust_level.numerator();
+    let denominator = trust_level.denominator();
+    if (participants as u128) * (denominator as u128)
+        < (numerator as u128) * (sync_committee_size as u128)
+    {
+        return Err(Error::InsufficientTrustLevelParticipation {
+            participants,
+            sync_committee_size,
+            numerator,
+            denominator,
+        });
+    }
+    Ok(())
+}
+
+/// Returns the least number of participants that satisfies `verify_trust_level_participation`, or `usize::MAX` if no number does
+pub(crate) fn required_trust_level_participants(
+    trust_level: &Fraction,
+    sync_committee_size: usize,
+) -> usize {
+    let required = (trust_level.numerator() as u128) * (sync_committee_size as u128);
+    if required == 0 {
+        return 0;
+    }
+    // ceil(required / denominator)
+    (required - 1)
+        .checked_div(trust_level.denominator() as u128)
+        .and_then(|n| usize::try_from(n + 1).ok())
+        .unwrap_or(usize::MAX)
+}
+
+pub(crate) fn validate_state_timestamp_within_trusting_period(
     current_timestamp: Timestamp,
     trusting_period: Duration,
     trusted_consensus_state_timestamp: Timestamp,
@@ -726,6 +1402,25 @@ fn validate_state_timestamp_within_trusting_period(
     Ok(())
 }
 
+/// Validate that the header timestamp is within `[current_timestamp - trusting_period, current_timestamp + clock_drift)`
+pub(crate) fn validate_header_timestamp(
+    current_timestamp: Timestamp,
+    trusting_period: Duration,
+    clock_drift: Duration,
+    untrusted_header_timestamp: Timestamp,
+) -> Result<(), Error> {
+    validate_header_timestamp_not_future(
+        current_timestamp,
+        clock_drift,
+        untrusted_header_timestamp,
+    )?;
+    validate_state_timestamp_within_trusting_period(
+        current_timestamp,
+        trusting_period,
+        untrusted_header_timestamp,
+    )
+}
+
 fn validate_header_timestamp_not_future(
     current_timestamp: Timestamp,
     clock_drift: Duration,
@@ -753,11 +1448,16 @@ impl<const SYNC_COMMITTEE_SIZE: usize> TryFrom<RawClientState>
     type Error = Error;
 
     fn try_from(value: RawClientState) -> Result<Self, Self::Error> {
-        fn bytes_to_version(bz: Vec<u8>) -> Version {
-            assert_eq!(bz.len(), 4);
+        fn bytes_to_version(fork: &str, bz: Vec<u8>) -> Result<Version, Error> {
+            if bz.len() != 4 {
+                return Err(Error::InvalidForkVersionLength {
+                    fork: fork.to_string(),
+                    length: bz.len(),
+                });
+            }
             let mut version = Version::default();
             version.0.copy_from_slice(&bz);
-            version
+            Ok(version)
         }
 
         fn convert_fork_spec(idx: usize, spec: Option<RawForkSpec>) -> Result<ForkSpec, Error> {
@@ -772,22 +1472,25 @@ impl<const SYNC_COMMITTEE_SIZE: usize> TryFrom<RawClientState>
                         .execution_payload_block_number_gindex,
                 })
             } else {
-                Err(Error::proto_missing(&format!("forks[{}].spec", idx)))
+                Err(Error::decode_error(
+                    &format!("forks[{}]", idx),
+                    Error::proto_missing("Fork", "spec"),
+                ))
             }
         }
 
         let raw_fork_parameters = value
             .fork_parameters
-            .ok_or(Error::proto_missing("fork_parameters"))?;
+            .ok_or(Error::proto_missing("ClientState", "fork_parameters"))?;
         let fork_parameters: ForkParameters = ForkParameters::new(
-            bytes_to_version(raw_fork_parameters.genesis_fork_version),
+            bytes_to_version("genesis", raw_fork_parameters.genesis_fork_version)?,
             raw_fork_parameters
                 .forks
                 .into_iter()
                 .enumerate()
                 .map(|(i, f)| -> Result<_, Error> {
                     Ok(ForkParameter::new(
-                        bytes_to_version(f.version),
+                        bytes_to_version(&format!("forks[{}]", i), f.version)?,
                         f.epoch.into(),
                         convert_fork_spec(i, f.spec)?,
                     ))
@@ -795,24 +1498,52 @@ impl<const SYNC_COMMITTEE_SIZE: usize> TryFrom<RawClientState>
                 .collect::<Result<Vec<_>, _>>()?,
         )
         .map_err(Error::EthereumConsensusError)?;
+        validate_fork_parameters(&fork_parameters)?;
         let trust_level = value
             .trust_level
-            .ok_or(Error::proto_missing("trust_level"))?;
-        let frozen_height = if let Some(h) = value.frozen_height {
-            Some(Height::new(h.revision_number, h.revision_height)?)
-        } else {
-            None
+            .ok_or(Error::proto_missing("ClientState", "trust_level"))?;
+        // encoders of non-nullable heights, e.g. gogoproto, emit the zero height for an unfrozen client.
+        // It is accepted here for `ClientState::try_from_any_lenient`, while `TryFrom<Any>` rejects it as non-canonical.
+        let frozen_height = match value.frozen_height {
+            Some(h) if h.revision_number == 0 && h.revision_height == 0 => None,
+            Some(h) => Some(Height::new(h.revision_number, h.revision_height)?),
+            None => None,
+        };
+        // the digest is empty for the client states encoded before the field was added
+        let misbehaviour_evidence_digest = match value.misbehaviour_evidence_digest.len() {
+            0 => None,
+            32 => Some(H256::from_slice(&value.misbehaviour_evidence_digest)),
+            actual => {
+                return Err(Error::decode_error(
+                    "misbehaviour_evidence_digest",
+                    Error::InvalidBytesLength {
+                        expected: 32,
+                        actual,
+                    },
+                ))
+            }
         };
         Ok(Self {
-            genesis_validators_root: H256::from_slice(&value.genesis_validators_root),
+            genesis_validators_root: decode_h256(
+                "genesis_validators_root",
+                &value.genesis_validators_root,
+            )?,
             min_sync_committee_participants: value.min_sync_committee_participants.into(),
             genesis_time: value.genesis_time.into(),
             fork_parameters,
             seconds_per_slot: value.seconds_per_slot.into(),
             slots_per_epoch: value.slots_per_epoch.into(),
             epochs_per_sync_committee_period: value.epochs_per_sync_committee_period.into(),
-            ibc_address: value.ibc_address.as_slice().try_into()?,
-            ibc_commitments_slot: H256::from_slice(&value.ibc_commitments_slot),
+            ibc_address: Address::try_from(value.ibc_address.as_slice())
+                .map_err(|e| Error::decode_error("ibc_address", e))?,
+            ibc_commitments_slot: decode_h256("ibc_commitments_slot", &value.ibc_commitments_slot)?,
+            commitment_value_codec: value.commitment_value_codec.try_into()?,
+            // the prefix is empty for the client states encoded before the field was added
+            commitment_prefix: if value.commitment_prefix.is_empty() {
+                Default::default()
+            } else {
+                ExpectedCommitmentPrefix::new(value.commitment_prefix)?
+            },
             trust_level: Fraction::new(trust_level.numerator, trust_level.denominator)
                 .map_err(Error::VerificationError)?,
             trusting_period: value
@@ -825,8 +1556,10 @@ impl<const SYNC_COMMITTEE_SIZE: usize> TryFrom<RawClientState>
                 .ok_or(Error::NegativeMaxClockDrift)?
                 .try_into()
                 .map_err(|_| Error::NegativeMaxClockDrift)?,
+            max_intermediate_consensus_updates: value.max_intermediate_consensus_updates.into(),
             latest_execution_block_number: value.latest_execution_block_number.into(),
             frozen_height,
+            misbehaviour_evidence_digest,
             consensus_verifier: Default::default(),
             execution_verifier: Default::default(),
         })
@@ -885,11 +1618,22 @@ impl<const SYNC_COMMITTEE_SIZE: usize> From<ClientState<SYNC_COMMITTEE_SIZE>> fo
             }),
             trusting_period: Some(value.trusting_period.into()),
             max_clock_drift: Some(value.max_clock_drift.into()),
+            max_intermediate_consensus_updates: value.max_intermediate_consensus_updates.into(),
+            commitment_value_codec: value.commitment_value_codec.into(),
             latest_execution_block_number: value.latest_execution_block_number.into(),
             frozen_height: value.frozen_height.map(|h| ProtoHeight {
                 revision_number: h.revision_number(),
                 revision_height: h.revision_height(),
             }),
+            misbehaviour_evidence_digest: value
+                .misbehaviour_evidence_digest
+                .map_or(Vec::new(), |d| d.as_bytes().to_vec()),
+            // the default prefix is omitted so that the encodings of the client states before the field was added are unchanged
+            commitment_prefix: if value.commitment_prefix.is_default() {
+                Vec::new()
+            } else {
+                value.commitment_prefix.as_bytes().to_vec()
+            },
         }
     }
 }
@@ -899,27 +1643,63 @@ impl<const SYNC_COMMITTEE_SIZE: usize> Protobuf<Any> for ClientState<SYNC_COMMIT
 impl<const SYNC_COMMITTEE_SIZE: usize> TryFrom<Any> for ClientState<SYNC_COMMITTEE_SIZE> {
     type Error = ClientError;
 
+    /// Decode the client state from `Any`
+    ///
+    /// The payload must be the canonical encoding of the client state, i.e. it must not contain unknown fields or trailing bytes,
+    /// and it must be the encoding of the decoded client state, so a zero `frozen_height` and an explicit default `commitment_prefix` are rejected too.
+    /// This makes the commitment of the client state not malleable. See `ClientState::try_from_any_lenient` for the encodings of the counterpart implementations.
     fn try_from(raw: Any) -> Result<Self, Self::Error> {
-        use bytes::Buf;
-        use core::ops::Deref;
-
-        fn decode_client_state<const SYNC_COMMITTEE_SIZE: usize, B: Buf>(
-            buf: B,
-        ) -> Result<ClientState<SYNC_COMMITTEE_SIZE>, Error> {
-            RawClientState::decode(buf)
-                .map_err(Error::Decode)?
-                .try_into()
+        decode_client_state_any(raw, false)
+    }
+}
+
+impl<const SYNC_COMMITTEE_SIZE: usize> ClientState<SYNC_COMMITTEE_SIZE> {
+    /// Decode the client state from `Any` as `TryFrom<Any>` does, except that a zero `frozen_height` is accepted as not frozen and `ibc` is accepted as `commitment_prefix`
+    ///
+    /// Encoders of non-nullable heights, e.g. gogoproto of the counterpart implementations, emit the zero height for an unfrozen client,
+    /// and the counterpart implementations may write the default commitment prefix explicitly.
+    /// The decoded client state is re-encoded without `frozen_height` and the default prefix, so this must only be used to read client states from such implementations,
+    /// never where the bytes of the client state are committed to.
+    pub fn try_from_any_lenient(raw: Any) -> Result<Self, ClientError> {
+        decode_client_state_any(raw, true)
+    }
+}
+
+fn decode_client_state_any<const SYNC_COMMITTEE_SIZE: usize>(
+    raw: Any,
+    lenient: bool,
+) -> Result<ClientState<SYNC_COMMITTEE_SIZE>, ClientError> {
+    fn decode_client_state<const SYNC_COMMITTEE_SIZE: usize>(
+        bz: &[u8],
+        lenient: bool,
+    ) -> Result<ClientState<SYNC_COMMITTEE_SIZE>, Error> {
+        let raw_client_state = RawClientState::decode(bz).map_err(Error::Decode)?;
+        let zero_frozen_height = raw_client_state
+            .frozen_height
+            .as_ref()
+            .is_some_and(|h| h.revision_number == 0 && h.revision_height == 0);
+        // the default commitment prefix is encoded as empty bytes
+        let explicit_default_prefix =
+            raw_client_state.commitment_prefix == commitment::IBC_SOLIDITY_COMMITMENT_PREFIX;
+        if raw_client_state.encode_to_vec() != bz
+            || ((zero_frozen_height || explicit_default_prefix) && !lenient)
+        {
+            return Err(Error::UnexpectedAnyPayload {
+                type_url: ETHEREUM_CLIENT_STATE_TYPE_URL,
+            });
         }
+        raw_client_state.try_into()
+    }
 
-        match raw.type_url.as_str() {
-            ETHEREUM_CLIENT_STATE_TYPE_URL => {
-                decode_client_state::<SYNC_COMMITTEE_SIZE, &[u8]>(raw.value.deref())
-                    .map_err(Into::into)
-            }
-            _ => Err(ClientError::UnknownClientStateType {
-                client_state_type: raw.type_url,
-            }),
+    match raw.type_url.as_str() {
+        ETHEREUM_CLIENT_STATE_TYPE_URL => {
+            decode_client_state::<SYNC_COMMITTEE_SIZE>(&raw.value, lenient).map_err(Into::into)
+        }
+        _ => Err(Error::UnexpectedTypeUrl {
+            expected: &[ETHEREUM_CLIENT_STATE_TYPE_URL],
+            got: raw.type_url,
         }
+        .into()),
     }
 }
 
@@ -963,31 +1743,33 @@ fn maybe_consensus_state(
     }
 }
 
-fn trim_left_zero(value: &[u8]) -> &[u8] {
-    let mut pos = 0;
-    for v in value {
-        if *v != 0 {
-            break;
-        }
-        pos += 1;
-    }
-    &value[pos..]
-}
-
-// A copy from https://github.com/cosmos/ibc-rs/blob/eea4f0e7a1887f2f1cb18a550d08bb805a08240a/crates/ibc/src/clients/ics07_tendermint/client_state.rs#L1031
 fn verify_delay_passed(
     ctx: &dyn ValidationContext,
     height: Height,
     connection_end: &ConnectionEnd,
 ) -> Result<(), ClientError> {
-    let current_timestamp = ctx.host_timestamp().map_err(|e| ClientError::Other {
-        description: e.to_string(),
-    })?;
-    let current_height = ctx.host_height().map_err(|e| ClientError::Other {
-        description: e.to_string(),
-    })?;
+    let delay_period_time = connection_end.delay_period();
+    Ok(verify_delay_periods_passed(
+        ctx,
+        connection_end.client_id(),
+        height,
+        delay_period_time,
+        ctx.block_delay(&delay_period_time),
+    )?)
+}
+
+/// Verify that `delay_period_time` and `delay_period_blocks` have passed on the host since the consensus state of `client_id` at `height` was processed
+// A copy from https://github.com/cosmos/ibc-rs/blob/eea4f0e7a1887f2f1cb18a550d08bb805a08240a/crates/ibc/src/clients/ics07_tendermint/client_state.rs#L1031
+pub(crate) fn verify_delay_periods_passed(
+    ctx: &dyn ValidationContext,
+    client_id: &ClientId,
+    height: Height,
+    delay_period_time: Duration,
+    delay_period_blocks: u64,
+) -> Result<(), Error> {
+    let current_timestamp = ctx.host_timestamp().map_err(Error::ContextError)?;
+    let current_height = ctx.host_height().map_err(Error::ContextError)?;
 
-    let client_id = connection_end.client_id();
     let processed_time =
         ctx.client_update_time(client_id, &height)
             .map_err(|_| Error::ProcessedTimeNotFound {
@@ -1001,93 +1783,2249 @@ fn verify_delay_passed(
         }
     })?;
 
-    let delay_period_time = connection_end.delay_period();
-    let delay_period_height = ctx.block_delay(&delay_period_time);
+    let earliest_time =
+        (processed_time + delay_period_time).map_err(Error::TimestampOverflowError)?;
+    if !(current_timestamp == earliest_time || current_timestamp.after(&earliest_time)) {
+        return Err(Error::NotEnoughTimeElapsed {
+            current_timestamp,
+            earliest_time,
+        });
+    }
+
+    let earliest_height = processed_height.add(delay_period_blocks);
+    if current_height < earliest_height {
+        return Err(Error::NotEnoughBlocksElapsed {
+            current_height,
+            earliest_height,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::commitment::decode_eip1186_rlp_proof;
+    use crate::types::tests::{arb_h256, arb_height};
+    use crate::types::TrustedSyncCommittee;
+    use crate::MinimalClientState;
+    use core::str::FromStr;
+    use ethereum_consensus::beacon::BeaconBlockHeader;
+    use ethereum_consensus::bls::PublicKey;
+    use ethereum_consensus::compute::{compute_epoch_at_slot, compute_fork_version};
+    use ethereum_consensus::context::ChainContext;
+    use ethereum_consensus::fork::{
+        altair::ALTAIR_FORK_SPEC, bellatrix::BELLATRIX_FORK_SPEC, capella::CAPELLA_FORK_SPEC,
+        deneb::DENEB_FORK_SPEC,
+    };
+    use ethereum_consensus::preset::minimal::PRESET;
+    use ethereum_consensus::{config, sync_protocol::SyncCommittee};
+    use ethereum_light_client_verifier::{
+        consensus::test_utils::{gen_light_client_update_with_params, MockSyncCommitteeManager},
+        misbehaviour::{FinalizedHeaderMisbehaviour, NextSyncCommitteeMisbehaviour},
+        updates::ConsensusUpdateInfo as EthConsensusUpdateInfo,
+    };
+    use hex_literal::hex;
+    use ibc::core::ics23_commitment::commitment::CommitmentPrefix;
+    use proptest::prelude::*;
+    use std::time::SystemTime;
+    use time::{macros::datetime, OffsetDateTime};
+
+    #[test]
+    fn test_client_state_conversion() {
+        let client_state =
+            ClientState::<{ ethereum_consensus::preset::minimal::PRESET.SYNC_COMMITTEE_SIZE }> {
+                genesis_validators_root: keccak256("genesis_validators_root"),
+                min_sync_committee_participants: 1.into(),
+                genesis_time: 1.into(),
+                fork_parameters: ForkParameters::new(
+                    Version([0, 0, 0, 1]),
+                    vec![
+                        ForkParameter::new(Version([1, 0, 0, 1]), U64(0), ALTAIR_FORK_SPEC),
+                        ForkParameter::new(Version([2, 0, 0, 1]), U64(0), BELLATRIX_FORK_SPEC),
+                        ForkParameter::new(Version([3, 0, 0, 1]), U64(0), CAPELLA_FORK_SPEC),
+                        ForkParameter::new(Version([4, 0, 0, 1]), U64(0), DENEB_FORK_SPEC),
+                    ],
+                )
+                .unwrap(),
+                seconds_per_slot: PRESET.SECONDS_PER_SLOT,
+                slots_per_epoch: PRESET.SLOTS_PER_EPOCH,
+                epochs_per_sync_committee_period: PRESET.EPOCHS_PER_SYNC_COMMITTEE_PERIOD,
+                ibc_address: Address(hex!("ff77D90D6aA12db33d3Ba50A34fB25401f6e4c4F")),
+                ibc_commitments_slot: keccak256("ibc_commitments_slot"),
+                commitment_value_codec: CommitmentValueCodec::Keccak256,
+                commitment_prefix: Default::default(),
+                trust_level: Fraction::new(2, 3).unwrap(),
+                trusting_period: Duration::from_secs(60 * 60 * 27),
+                max_clock_drift: Duration::from_secs(60),
+                max_intermediate_consensus_updates: U64(0),
+                latest_execution_block_number: 1.into(),
+                frozen_height: None,
+                misbehaviour_evidence_digest: None,
+                consensus_verifier: Default::default(),
+                execution_verifier: Default::default(),
+            };
+        let res = client_state.validate();
+        assert!(res.is_ok(), "{:?}", res);
+
+        let any_client_state: Any = client_state.clone().into();
+        let client_state2 = ClientState::try_from(any_client_state).unwrap();
+        assert_eq!(client_state, client_state2);
+
+        // the IBC contract address must be 20 bytes
+        for len in [0, 19, 21] {
+            let raw = RawClientState {
+                ibc_address: vec![1u8; len],
+                ..client_state.clone().into()
+            };
+            let res = MinimalClientState::try_from(raw);
+            assert!(
+                matches!(
+                    &res,
+                    Err(Error::DecodeError { field, source })
+                        if field == "ibc_address"
+                            && matches!(**source, Error::InvalidBytesLength { expected: 20, actual } if actual == len)
+                ),
+                "{:?}",
+                res
+            );
+        }
+        // the 32-byte fields of other lengths used to make `H256::from_slice` panic
+        for len in [0, 31, 33] {
+            let raws = [
+                (
+                    "genesis_validators_root",
+                    RawClientState {
+                        genesis_validators_root: vec![1u8; len],
+                        ..client_state.clone().into()
+                    },
+                ),
+                (
+                    "ibc_commitments_slot",
+                    RawClientState {
+                        ibc_commitments_slot: vec![1u8; len],
+                        ..client_state.clone().into()
+                    },
+                ),
+            ];
+            for (expected_field, raw) in raws {
+                let res = MinimalClientState::try_from(raw);
+                assert!(
+                    matches!(
+                        &res,
+                        Err(Error::DecodeError { field, source })
+                            if field == expected_field
+                                && matches!(**source, Error::InvalidBytesLength { expected: 32, actual } if actual == len)
+                    ),
+                    "{:?}",
+                    res
+                );
+            }
+        }
+        // the JSON representation of the address is checksummed
+        let json = serde_json::to_value(&client_state).unwrap();
+        assert_eq!(
+            json["ibc_address"],
+            "0xff77D90D6aA12db33d3Ba50A34fB25401f6e4c4F"
+        );
+
+        // Unexpected fork parameters
+        let mut client_state = client_state.clone();
+        client_state.fork_parameters = ForkParameters::new(
+            Version([0, 0, 0, 1]),
+            vec![ForkParameter::new(
+                Version([1, 0, 0, 1]),
+                U64(0),
+                ALTAIR_FORK_SPEC,
+            )],
+        )
+        .unwrap();
+        let res = client_state.validate();
+        assert!(res.is_err(), "{:?}", res);
+    }
+
+    /// The forks follow the order of the fork specs with distinct versions and non-decreasing epochs
+    fn arb_fork_parameters() -> impl Strategy<Value = ForkParameters> {
+        (any::<[u8; 3]>(), prop::collection::vec(any::<u64>(), 1..=4)).prop_map(
+            |(version_suffix, mut epochs)| {
+                let version = |prefix: u8| {
+                    Version([
+                        prefix,
+                        version_suffix[0],
+                        version_suffix[1],
+                        version_suffix[2],
+                    ])
+                };
+                epochs.sort();
+                let specs = [
+                    ALTAIR_FORK_SPEC,
+                    BELLATRIX_FORK_SPEC,
+                    CAPELLA_FORK_SPEC,
+                    DENEB_FORK_SPEC,
+                ];
+                ForkParameters::new(
+                    version(0),
+                    epochs
+                        .into_iter()
+                        .zip(specs)
+                        .enumerate()
+                        .map(|(i, (epoch, spec))| {
+                            ForkParameter::new(version(i as u8 + 1), U64(epoch), spec)
+                        })
+                        .collect(),
+                )
+                .unwrap()
+            },
+        )
+    }
+
+    fn arb_duration() -> impl Strategy<Value = Duration> {
+        (0..=u32::MAX as u64, 0..1_000_000_000u32)
+            .prop_map(|(secs, nanos)| Duration::new(secs, nanos))
+    }
+
+    /// The trust level is in the valid range [1/3, 1]
+    pub(crate) fn arb_client_state<const SYNC_COMMITTEE_SIZE: usize>(
+    ) -> impl Strategy<Value = ClientState<SYNC_COMMITTEE_SIZE>> {
+        let trust_level = (1..=1000u64).prop_flat_map(|denominator| {
+            ((denominator + 2) / 3..=denominator)
+                .prop_map(move |numerator| Fraction::new(numerator, denominator).unwrap())
+        });
+        (
+            (
+                arb_h256(),
+                any::<u64>(),
+                any::<u64>(),
+                arb_fork_parameters(),
+            ),
+            (any::<u64>(), any::<u64>(), any::<u64>()),
+            (
+                any::<[u8; 20]>(),
+                arb_h256(),
+                prop_oneof![
+                    Just(CommitmentValueCodec::Keccak256),
+                    Just(CommitmentValueCodec::Raw)
+                ],
+            ),
+            (trust_level, arb_duration(), arb_duration(), any::<u64>()),
+            (any::<u64>(), prop::option::of(arb_height())),
+        )
+            .prop_map(
+                |(
+                    (
+                        genesis_validators_root,
+                        min_sync_committee_participants,
+                        genesis_time,
+                        fork_parameters,
+                    ),
+                    (seconds_per_slot, slots_per_epoch, epochs_per_sync_committee_period),
+                    (ibc_address, ibc_commitments_slot, commitment_value_codec),
+                    (
+                        trust_level,
+                        trusting_period,
+                        max_clock_drift,
+                        max_intermediate_consensus_updates,
+                    ),
+                    (latest_execution_block_number, frozen_height),
+                )| ClientState {
+                    genesis_validators_root,
+                    min_sync_committee_participants: min_sync_committee_participants.into(),
+                    genesis_time: genesis_time.into(),
+                    fork_parameters,
+                    seconds_per_slot: seconds_per_slot.into(),
+                    slots_per_epoch: slots_per_epoch.into(),
+                    epochs_per_sync_committee_period: epochs_per_sync_committee_period.into(),
+                    ibc_address: Address(ibc_address),
+                    ibc_commitments_slot,
+                    commitment_value_codec,
+                    commitment_prefix: Default::default(),
+                    trust_level,
+                    trusting_period,
+                    max_clock_drift,
+                    max_intermediate_consensus_updates: max_intermediate_consensus_updates.into(),
+                    latest_execution_block_number: latest_execution_block_number.into(),
+                    frozen_height,
+                    misbehaviour_evidence_digest: None,
+                    consensus_verifier: Default::default(),
+                    execution_verifier: Default::default(),
+                },
+            )
+    }
+
+    fn client_state_round_trip<const SYNC_COMMITTEE_SIZE: usize>(
+        client_state: ClientState<SYNC_COMMITTEE_SIZE>,
+    ) -> Result<(), TestCaseError> {
+        let bz = RawClientState::from(client_state.clone()).encode_to_vec();
+        let raw = RawClientState::decode(bz.as_slice()).unwrap();
+        prop_assert_eq!(
+            ClientState::<SYNC_COMMITTEE_SIZE>::try_from(raw).unwrap(),
+            client_state.clone()
+        );
+        let any = Any::from(client_state.clone());
+        prop_assert_eq!(
+            ClientState::<SYNC_COMMITTEE_SIZE>::try_from(any).unwrap(),
+            client_state
+        );
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn prop_minimal_client_state_round_trip(
+            client_state in arb_client_state::<{ ethereum_consensus::preset::minimal::PRESET.SYNC_COMMITTEE_SIZE }>()
+        ) {
+            client_state_round_trip(client_state)?;
+        }
+
+        #[test]
+        fn prop_mainnet_client_state_round_trip(
+            client_state in arb_client_state::<{ ethereum_consensus::preset::mainnet::PRESET.SYNC_COMMITTEE_SIZE }>()
+        ) {
+            client_state_round_trip(client_state)?;
+        }
+    }
+
+    #[test]
+    fn test_client_state_encoded_len() {
+        let mut client_state = new_client_state();
+        let genesis_version = client_state.fork_parameters.genesis_version().clone();
+        let mut forks = client_state.fork_parameters.forks().to_vec();
+        let mut prev = client_state.encoded_len();
+        assert_eq!(
+            prev,
+            RawClientState::from(client_state.clone())
+                .encode_to_vec()
+                .len()
+        );
+        // the length prefix of the fork parameters grows to 2 bytes once they exceed 127 bytes
+        for epoch in 1..=8 {
+            forks.push(ForkParameter::new(
+                Version([4 + epoch as u8, 0, 0, 1]),
+                U64(epoch),
+                DENEB_FORK_SPEC,
+            ));
+            client_state.fork_parameters =
+                ForkParameters::new(genesis_version.clone(), forks.clone()).unwrap();
+            let len = client_state.encoded_len();
+            let raw = RawClientState::from(client_state.clone());
+            assert_eq!(len, raw.encode_to_vec().len());
+            assert!(len > prev, "{} <= {}", len, prev);
+            prev = len;
+        }
+        assert!(
+            RawClientState::from(client_state)
+                .fork_parameters
+                .unwrap()
+                .encoded_len()
+                > 127
+        );
+    }
+
+    #[test]
+    fn test_deserialize_validated_client_state() {
+        let client_state = new_client_state();
+        let json = serde_json::to_value(&client_state).unwrap();
+        let decoded =
+            MinimalClientState::deserialize_validated(json.clone()).expect("valid client state");
+        assert_eq!(decoded, client_state);
+
+        // a typo in a field name
+        let mut typo = json.clone();
+        let obj = typo.as_object_mut().unwrap();
+        let value = obj.remove("trusting_period").unwrap();
+        obj.insert("trustng_period".into(), value);
+        let err = MinimalClientState::deserialize_validated(typo.clone()).unwrap_err();
+        assert!(
+            err.to_string().contains("unknown field `trustng_period`"),
+            "{}",
+            err
+        );
+        // the derived `Deserialize` rejects unknown fields too
+        assert!(serde_json::from_value::<MinimalClientState>(typo).is_err());
+
+        // a trust level below 1/3
+        let out_of_range = serde_json::to_value(ClientState {
+            trust_level: Fraction::new(1, 4).unwrap(),
+            ..client_state.clone()
+        })
+        .unwrap();
+        let err = MinimalClientState::deserialize_validated(out_of_range.clone()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            Error::InvalidTrustLevel {
+                numerator: 1,
+                denominator: 4
+            }
+            .to_string()
+        );
+        // the derived `Deserialize` does not validate the client state
+        assert!(serde_json::from_value::<MinimalClientState>(out_of_range).is_ok());
+    }
+
+    #[test]
+    fn test_validate_initial_consensus_state() {
+        let client_state = new_client_state();
+        let slot = U64(64);
+        let consensus_state = ConsensusState {
+            slot,
+            storage_root: keccak256("storage_root").as_bytes().to_vec().into(),
+            timestamp: Timestamp::from_nanoseconds(
+                (client_state.genesis_time.0 + slot.0 * client_state.seconds_per_slot.0)
+                    * 1_000_000_000,
+            )
+            .unwrap(),
+            current_sync_committee: Default::default(),
+            next_sync_committee: Default::default(),
+            current_block_hash: None,
+        };
+        let res = client_state.validate_initial_consensus_state(&consensus_state);
+        assert!(res.is_ok(), "{:?}", res);
+
+        // off-by-one genesis time
+        let mut client_state2 = client_state.clone();
+        client_state2.genesis_time = client_state.genesis_time + 1;
+        let res = client_state2.validate_initial_consensus_state(&consensus_state);
+        assert!(
+            matches!(
+                res,
+                Err(Error::InitialConsensusStateTimestampMismatch { .. })
+            ),
+            "{:?}",
+            res
+        );
+
+        // the bellatrix fork is not active at the slot
+        let mut client_state3 = client_state.clone();
+        client_state3.fork_parameters = ForkParameters::new(
+            Version([0, 0, 0, 1]),
+            vec![
+                ForkParameter::new(Version([1, 0, 0, 1]), U64(0), ALTAIR_FORK_SPEC),
+                ForkParameter::new(Version([2, 0, 0, 1]), U64(100), BELLATRIX_FORK_SPEC),
+            ],
+        )
+        .unwrap();
+        let res = client_state3.validate_initial_consensus_state(&consensus_state);
+        assert!(
+            matches!(
+                res,
+                Err(Error::InitialConsensusStateBeforeBellatrixFork { .. })
+            ),
+            "{:?}",
+            res
+        );
+    }
+
+    #[test]
+    fn test_type_urls() {
+        assert_eq!(
+            crate::ETHEREUM_CLIENT_STATE_TYPE_URL,
+            "/ibc.lightclients.ethereum.v1.ClientState"
+        );
+        assert_eq!(
+            crate::ETHEREUM_CONSENSUS_STATE_TYPE_URL,
+            "/ibc.lightclients.ethereum.v1.ConsensusState"
+        );
+        assert_eq!(
+            crate::ETHEREUM_HEADER_TYPE_URL,
+            "/ibc.lightclients.ethereum.v1.Header"
+        );
+        assert_eq!(
+            crate::ETHEREUM_FINALIZED_HEADER_MISBEHAVIOUR_TYPE_URL,
+            "/ibc.lightclients.ethereum.v1.FinalizedHeaderMisbehaviour"
+        );
+        assert_eq!(
+            crate::ETHEREUM_NEXT_SYNC_COMMITTEE_MISBEHAVIOUR_TYPE_URL,
+            "/ibc.lightclients.ethereum.v1.NextSyncCommitteeMisbehaviour"
+        );
+
+        let any = Any {
+            type_url: crate::ETHEREUM_CONSENSUS_STATE_TYPE_URL.to_string(),
+            value: Any::from(new_client_state()).value,
+        };
+        let expected = Error::UnexpectedTypeUrl {
+            expected: &[ETHEREUM_CLIENT_STATE_TYPE_URL],
+            got: crate::ETHEREUM_CONSENSUS_STATE_TYPE_URL.to_string(),
+        }
+        .to_string();
+        match ClientState::<32>::try_from(any) {
+            Err(ClientError::ClientSpecific { description }) => {
+                assert_eq!(description, expected)
+            }
+            res => panic!("unexpected result: {:?}", res),
+        }
+
+        // `Any` of the client state is the packed message of the proto crate
+        let packed = RawClientState::from(new_client_state()).pack();
+        let any = Any::from(new_client_state());
+        assert_eq!(any.type_url, packed.type_url);
+        assert_eq!(any.value, packed.value);
+        assert!(RawClientState::unpack(&packed).is_ok());
+    }
+
+    #[test]
+    fn test_decode_initial_schema_client_state() {
+        // a client state encoded with the initial schema, i.e. without the fields added since then,
+        // which is how the client states created before the upgrade are stored on-chain
+        let bz = hex::decode(include_str!("../testdata/client_state_initial_schema.hex").trim())
+            .unwrap();
+        let client_state = crate::MainnetClientState::try_from(Any {
+            type_url: ETHEREUM_CLIENT_STATE_TYPE_URL.to_string(),
+            value: bz.clone(),
+        })
+        .unwrap();
+        // the added fields take the values that keep the behaviour of the initial schema
+        assert_eq!(client_state.max_intermediate_consensus_updates, U64(0));
+        assert_eq!(
+            client_state.commitment_value_codec,
+            CommitmentValueCodec::Keccak256
+        );
+        assert_eq!(client_state.misbehaviour_evidence_digest, None);
+
+        assert_eq!(client_state.genesis_time, U64(1606824023));
+        assert_eq!(client_state.fork_parameters.forks().len(), 4);
+        assert_eq!(client_state.trust_level, Fraction::new(2, 3).unwrap());
+        assert_eq!(client_state.trusting_period, Duration::from_secs(1209600));
+        assert_eq!(client_state.max_clock_drift, Duration::from_secs(10));
+        assert_eq!(client_state.latest_execution_block_number, U64(20000000));
+        assert!(client_state.frozen_height.is_none());
+        // the added fields are omitted from the encoding while they have the default values,
+        // so the client state is encoded into the same bytes and its commitment does not change
+        assert_eq!(Any::from(client_state).value, bz);
+    }
+
+    #[test]
+    fn test_deserialize_initial_schema_client_state() {
+        // the JSON representation of the client state in `client_state_initial_schema.hex`,
+        // i.e. without the fields added since the initial schema, as existing configurations are written
+        let json: serde_json::Value =
+            serde_json::from_str(include_str!("../testdata/client_state_initial_schema.json"))
+                .unwrap();
+        let client_state = crate::MainnetClientState::deserialize_validated(json.clone())
+            .expect("valid client state");
+        // the added fields take the values that keep the behaviour of the initial schema
+        assert_eq!(client_state.max_intermediate_consensus_updates, U64(0));
+        assert_eq!(
+            client_state.commitment_value_codec,
+            CommitmentValueCodec::Keccak256
+        );
+        assert_eq!(client_state.misbehaviour_evidence_digest, None);
+        assert_eq!(
+            serde_json::from_value::<crate::MainnetClientState>(json).unwrap(),
+            client_state
+        );
+
+        let bz = hex::decode(include_str!("../testdata/client_state_initial_schema.hex").trim())
+            .unwrap();
+        let decoded = crate::MainnetClientState::try_from(Any {
+            type_url: ETHEREUM_CLIENT_STATE_TYPE_URL.to_string(),
+            value: bz,
+        })
+        .unwrap();
+        assert_eq!(client_state, decoded);
+    }
+
+    #[test]
+    fn test_fork_parameters_validation() {
+        let raw_client_state = RawClientState::from(new_client_state());
+        let set_epochs = |raw: &mut RawClientState, epochs: &[u64]| {
+            for (fork, epoch) in raw
+                .fork_parameters
+                .as_mut()
+                .unwrap()
+                .forks
+                .iter_mut()
+                .zip(epochs)
+            {
+                fork.epoch = *epoch;
+            }
+        };
+
+        let mut raw = raw_client_state.clone();
+        set_epochs(&mut raw, &[0, 1, 2, 3]);
+        let res = ClientState::<32>::try_from(raw.clone());
+        assert!(res.is_ok(), "{:?}", res);
+
+        // shuffled
+        raw.fork_parameters.as_mut().unwrap().forks.swap(1, 3);
+        let res = ClientState::<32>::try_from(raw);
+        assert!(res.is_err(), "{:?}", res);
+
+        // duplicated fork version
+        let mut raw = raw_client_state.clone();
+        raw.fork_parameters.as_mut().unwrap().forks[1].version = vec![1, 0, 0, 1];
+        let res = ClientState::<32>::try_from(raw);
+        assert!(res.is_err(), "{:?}", res);
+
+        // truncated fork version
+        let mut raw = raw_client_state.clone();
+        raw.fork_parameters.as_mut().unwrap().forks[2].version = vec![3, 0, 0];
+        let res = ClientState::<32>::try_from(raw);
+        assert!(
+            matches!(res, Err(Error::InvalidForkVersionLength { length: 3, .. })),
+            "{:?}",
+            res
+        );
+
+        // missing fork spec
+        let mut raw = raw_client_state.clone();
+        raw.fork_parameters.as_mut().unwrap().forks[2].spec = None;
+        let res = ClientState::<32>::try_from(raw);
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "failed to decode `forks[2]`: proto missing field error: `Fork.spec`"
+        );
+
+        // truncated genesis fork version
+        let mut raw = raw_client_state;
+        raw.fork_parameters
+            .as_mut()
+            .unwrap()
+            .genesis_fork_version
+            .clear();
+        let res = ClientState::<32>::try_from(raw);
+        assert!(
+            matches!(res, Err(Error::InvalidForkVersionLength { length: 0, .. })),
+            "{:?}",
+            res
+        );
+    }
+
+    #[test]
+    fn test_commitment_prefix_conversion() {
+        let client_state = ClientState {
+            commitment_prefix: ExpectedCommitmentPrefix::new(b"ibc-1".to_vec()).unwrap(),
+            ..new_client_state()
+        };
+        let raw = RawClientState::from(client_state.clone());
+        assert_eq!(raw.commitment_prefix, b"ibc-1");
+        let res = ClientState::<32>::try_from(raw.clone());
+        assert_eq!(res.unwrap(), client_state);
+        assert_eq!(client_state.commitment_prefix().as_bytes(), b"ibc-1");
+
+        // the default prefix is encoded as empty bytes, which the client states encoded before the field was added have
+        let default = RawClientState::from(new_client_state());
+        assert!(default.commitment_prefix.is_empty());
+        assert_eq!(
+            ClientState::<32>::try_from(default.clone())
+                .unwrap()
+                .commitment_prefix,
+            ExpectedCommitmentPrefix::default()
+        );
+
+        // an explicit default prefix is only accepted by the lenient decoding
+        let explicit = Any {
+            type_url: ETHEREUM_CLIENT_STATE_TYPE_URL.to_string(),
+            value: RawClientState {
+                commitment_prefix: b"ibc".to_vec(),
+                ..default
+            }
+            .encode_to_vec(),
+        };
+        let res = ClientState::<32>::try_from(explicit.clone());
+        assert!(res.is_err(), "{:?}", res);
+        assert_eq!(
+            ClientState::<32>::try_from_any_lenient(explicit).unwrap(),
+            new_client_state()
+        );
+    }
+
+    #[test]
+    fn test_commitment_value_codec_conversion() {
+        let client_state = ClientState {
+            commitment_value_codec: CommitmentValueCodec::Raw,
+            ..new_client_state()
+        };
+        let raw = RawClientState::from(client_state.clone());
+        assert_eq!(raw.commitment_value_codec, 1);
+        let res = ClientState::<32>::try_from(raw.clone());
+        assert_eq!(res.unwrap(), client_state);
+
+        // the default is keccak256 for the client states encoded before the field was added
+        let res = ClientState::<32>::try_from(RawClientState {
+            commitment_value_codec: 0,
+            ..raw.clone()
+        });
+        assert_eq!(
+            res.unwrap().commitment_value_codec,
+            CommitmentValueCodec::Keccak256
+        );
+
+        let res = ClientState::<32>::try_from(RawClientState {
+            commitment_value_codec: 2,
+            ..raw
+        });
+        assert!(
+            matches!(res, Err(Error::UnknownCommitmentValueCodec(2))),
+            "{:?}",
+            res
+        );
+    }
+    #[test]
+    fn test_misbehaviour_evidence_digest_conversion() {
+        let client_state = new_client_state()
+            .with_frozen_height(height_from_block_number(U64(1)).unwrap())
+            .with_misbehaviour_evidence_digest(keccak256("evidence"));
+        let raw = RawClientState::from(client_state.clone());
+        assert_eq!(
+            raw.misbehaviour_evidence_digest,
+            keccak256("evidence").as_bytes()
+        );
+        let res = ClientState::<32>::try_from(raw.clone());
+        assert_eq!(res.unwrap(), client_state);
+
+        // the client states encoded before the field was added have no digest
+        let res = ClientState::<32>::try_from(RawClientState {
+            misbehaviour_evidence_digest: vec![],
+            ..raw.clone()
+        });
+        assert_eq!(res.unwrap().misbehaviour_evidence_digest, None);
+
+        let res = ClientState::<32>::try_from(RawClientState {
+            misbehaviour_evidence_digest: vec![0; 31],
+            ..raw
+        });
+        assert!(
+            matches!(&res, Err(Error::DecodeError { field, .. }) if field == "misbehaviour_evidence_digest"),
+            "{:?}",
+            res
+        );
+    }
+
+    #[test]
+    fn test_verify_slot_in_fork_schedule() {
+        let mut client_state = new_client_state();
+        client_state.fork_parameters = ForkParameters::new(
+            Version([0, 0, 0, 1]),
+            vec![
+                ForkParameter::new(Version([1, 0, 0, 1]), U64(2), ALTAIR_FORK_SPEC),
+                ForkParameter::new(Version([2, 0, 0, 1]), U64(3), BELLATRIX_FORK_SPEC),
+            ],
+        )
+        .unwrap();
+        let first_fork_slot = U64(2) * client_state.slots_per_epoch;
+        assert!(client_state
+            .verify_slot_in_fork_schedule(first_fork_slot)
+            .is_ok());
+        let res = client_state.verify_slot_in_fork_schedule(first_fork_slot - 1);
+        assert!(
+            matches!(res, Err(Error::SlotBeforeFirstFork { .. })),
+            "{:?}",
+            res
+        );
+    }
+
+    #[test]
+    fn test_compute_period_at_slot() {
+        // the minimal preset has 8 slots per epoch and 8 epochs per period, unlike 32 and 256 of the mainnet preset
+        let client_state = new_client_state();
+        assert_eq!(
+            client_state.slots_per_epoch * client_state.epochs_per_sync_committee_period,
+            U64(64)
+        );
+        for (slot, period) in [(0, 0), (63, 0), (64, 1), (8191, 127), (8192, 128)] {
+            assert_eq!(client_state.compute_period_at_slot(U64(slot)), U64(period));
+        }
+    }
+
+    #[test]
+    fn test_fork_version_at() {
+        let mut client_state = new_client_state();
+        client_state.fork_parameters = ForkParameters::new(
+            Version([0, 0, 0, 1]),
+            vec![
+                ForkParameter::new(Version([1, 0, 0, 1]), U64(2), ALTAIR_FORK_SPEC),
+                ForkParameter::new(Version([2, 0, 0, 1]), U64(3), BELLATRIX_FORK_SPEC),
+            ],
+        )
+        .unwrap();
+        let slots_per_epoch = client_state.slots_per_epoch;
+
+        // a slot before the first fork is not covered by the fork schedule, even though the genesis fork is configured
+        let res = client_state.fork_version_at(U64(2) * slots_per_epoch - 1);
+        match res {
+            Err(Error::SlotBeforeFirstFork {
+                slot,
+                epoch,
+                first_fork_epoch,
+            }) => {
+                assert_eq!(slot, U64(2) * slots_per_epoch - 1);
+                assert_eq!(epoch, U64(1));
+                assert_eq!(first_fork_epoch, U64(2));
+            }
+            res => panic!("unexpected result: {:?}", res),
+        }
+        assert!(client_state.fork_version_at(U64(0)).is_err());
+
+        for (slot, version) in [
+            (U64(2) * slots_per_epoch, Version([1, 0, 0, 1])),
+            (U64(3) * slots_per_epoch - 1, Version([1, 0, 0, 1])),
+            (U64(3) * slots_per_epoch, Version([2, 0, 0, 1])),
+            // the last fork stays active after its epoch
+            (U64(1000) * slots_per_epoch, Version([2, 0, 0, 1])),
+        ] {
+            assert_eq!(client_state.fork_version_at(slot).unwrap(), version);
+        }
+
+        // the error of an update signed after the last configured epoch is wrapped with the fork schedule
+        let err = Error::ZeroTimestampError;
+        let slot = U64(4) * slots_per_epoch;
+        match client_state.with_fork_schedule_hint(slot, err) {
+            Error::SlotBeyondLastFork {
+                slot: got_slot,
+                epoch,
+                last_fork_epoch,
+                fork_version,
+                genesis_validators_root,
+                err,
+            } => {
+                assert_eq!(got_slot, slot);
+                assert_eq!(epoch, U64(4));
+                assert_eq!(last_fork_epoch, U64(3));
+                assert_eq!(fork_version, Version([2, 0, 0, 1]));
+                assert_eq!(
+                    genesis_validators_root,
+                    client_state.genesis_validators_root
+                );
+                assert!(matches!(*err, Error::ZeroTimestampError), "{:?}", err);
+            }
+            err => panic!("unexpected error: {:?}", err),
+        }
+        // the error of an update signed within the epochs of the configured forks is returned as is
+        for slot in [U64(3) * slots_per_epoch, U64(4) * slots_per_epoch - 1] {
+            let err = client_state.with_fork_schedule_hint(slot, Error::ZeroTimestampError);
+            assert!(matches!(err, Error::ZeroTimestampError), "{:?}", err);
+        }
+    }
+
+    /// `new_client_state` with the deneb fork activated at `deneb_epoch`
+    fn new_client_state_with_deneb_at(deneb_epoch: Epoch) -> ClientState<32> {
+        ClientState {
+            fork_parameters: ForkParameters::new(
+                Version([0, 0, 0, 1]),
+                vec![
+                    ForkParameter::new(Version([1, 0, 0, 1]), U64(0), ALTAIR_FORK_SPEC),
+                    ForkParameter::new(Version([2, 0, 0, 1]), U64(0), BELLATRIX_FORK_SPEC),
+                    ForkParameter::new(Version([3, 0, 0, 1]), U64(0), CAPELLA_FORK_SPEC),
+                    ForkParameter::new(Version([4, 0, 0, 1]), deneb_epoch, DENEB_FORK_SPEC),
+                ],
+            )
+            .unwrap(),
+            ..new_client_state()
+        }
+    }
+
+    #[test]
+    fn test_compute_sync_committee_domain_at() {
+        let client_state = new_client_state_with_deneb_at(U64(20));
+        let slots_per_epoch = client_state.slots_per_epoch;
+        // `DOMAIN_SYNC_COMMITTEE` followed by the first 28 bytes of the fork data root of the version and `genesis_validators_root`
+        let capella = hex!("07000000a294dd5ff2a841fb303295d0fa3cb049c6a82155063f0ef2c440a229");
+        let deneb = hex!("070000006f362ae6c6c6c021285cc6523b6acf7fe56aebc7ee8281d4425bdf1c");
+        for (slot, expected) in [
+            // one epoch before the transition
+            (U64(19) * slots_per_epoch + 3, capella),
+            // the block at the last slot before the transition is signed at the first slot of the fork epoch
+            (U64(20) * slots_per_epoch, capella),
+            (U64(20) * slots_per_epoch + 1, deneb),
+            // one epoch after the transition
+            (U64(21) * slots_per_epoch + 3, deneb),
+        ] {
+            let domain = client_state.compute_sync_committee_domain_at(slot).unwrap();
+            assert_eq!(domain.0, expected, "slot={}", slot);
+        }
+
+        // the block signed at the first slot of the first fork is before the fork schedule
+        let client_state = MinimalClientState {
+            fork_parameters: ForkParameters::new(
+                Version([0, 0, 0, 1]),
+                vec![ForkParameter::new(
+                    Version([1, 0, 0, 1]),
+                    U64(1),
+                    ALTAIR_FORK_SPEC,
+                )],
+            )
+            .unwrap(),
+            ..new_client_state()
+        };
+        assert!(client_state
+            .compute_sync_committee_domain_at(slots_per_epoch + 1)
+            .is_ok());
+        let res = client_state.compute_sync_committee_domain_at(slots_per_epoch);
+        assert!(
+            matches!(res, Err(Error::SlotBeforeFirstFork { .. })),
+            "{:?}",
+            res
+        );
+    }
+
+    #[test]
+    fn test_verify_execution_block_hash() {
+        let client_state = new_client_state_with_deneb_at(U64(20));
+        let slots_per_epoch = client_state.slots_per_epoch;
+        // the depth of the execution payload header differs between capella and deneb
+        for slot in [U64(20) * slots_per_epoch - 1, U64(20) * slots_per_epoch] {
+            let spec = client_state.fork_spec_at(slot).unwrap();
+            let depth = 63 - (spec.execution_payload_state_root_gindex as u64).leading_zeros();
+            let leaves: Vec<[u8; 32]> = (0..1usize << depth).map(|i| [i as u8 + 1; 32]).collect();
+            let (root, branches) = crate::merkle::merkle_root_and_branches(
+                &leaves,
+                &[EXECUTION_PAYLOAD_BLOCK_HASH_INDEX as usize],
+            );
+            let consensus_update = ConsensusUpdateInfo::<32> {
+                finalized_header: (
+                    BeaconBlockHeader {
+                        slot,
+                        ..Default::default()
+                    },
+                    vec![],
+                ),
+                finalized_execution_root: H256(root),
+                ..Default::default()
+            };
+            let block_hash = H256(leaves[EXECUTION_PAYLOAD_BLOCK_HASH_INDEX as usize]);
+            let execution_update = ExecutionUpdateInfo {
+                block_hash: Some(block_hash),
+                block_hash_branch: branches[0].clone(),
+                ..Default::default()
+            };
+            assert!(client_state
+                .verify_execution_block_hash(&consensus_update, &execution_update)
+                .is_ok());
+
+            // the block hash is not proven
+            let res = client_state.verify_execution_block_hash(
+                &consensus_update,
+                &ExecutionUpdateInfo {
+                    block_hash: None,
+                    block_hash_branch: vec![],
+                    ..execution_update.clone()
+                },
+            );
+            assert!(res.is_ok(), "{:?}", res);
+
+            // another block hash, or the state root proven as the block hash
+            for wrong in [
+                H256([0xff; 32]),
+                H256(leaves[EXECUTION_PAYLOAD_STATE_ROOT_INDEX as usize]),
+            ] {
+                let res = client_state.verify_execution_block_hash(
+                    &consensus_update,
+                    &ExecutionUpdateInfo {
+                        block_hash: Some(wrong),
+                        ..execution_update.clone()
+                    },
+                );
+                match res {
+                    Err(Error::InvalidExecutionBlockHashBranch {
+                        finalized_execution_root,
+                        block_hash,
+                        gindex,
+                    }) => {
+                        assert_eq!(finalized_execution_root, H256(root));
+                        assert_eq!(block_hash, wrong);
+                        assert_eq!(gindex, (1 << depth) + EXECUTION_PAYLOAD_BLOCK_HASH_INDEX);
+                    }
+                    res => panic!("unexpected result: {:?}", res),
+                }
+            }
+
+            // the branch of a tree of another depth
+            let res = client_state.verify_execution_block_hash(
+                &consensus_update,
+                &ExecutionUpdateInfo {
+                    block_hash_branch: branches[0][1..].to_vec(),
+                    ..execution_update.clone()
+                },
+            );
+            assert!(
+                matches!(res, Err(Error::InvalidExecutionBlockHashBranch { .. })),
+                "{:?}",
+                res
+            );
+        }
+    }
+
+    #[test]
+    fn test_fork_schedule_with_future_fork() {
+        // a fork after deneb is appended to the schedule as is; it reuses the latest fork spec until the spec changes
+        let client_state = MinimalClientState {
+            fork_parameters: ForkParameters::new(
+                Version([0, 0, 0, 1]),
+                vec![
+                    ForkParameter::new(Version([1, 0, 0, 1]), U64(1), ALTAIR_FORK_SPEC),
+                    ForkParameter::new(Version([2, 0, 0, 1]), U64(2), BELLATRIX_FORK_SPEC),
+                    ForkParameter::new(Version([3, 0, 0, 1]), U64(3), CAPELLA_FORK_SPEC),
+                    ForkParameter::new(Version([4, 0, 0, 1]), U64(4), DENEB_FORK_SPEC),
+                    ForkParameter::new(Version([5, 0, 0, 1]), U64(5), DENEB_FORK_SPEC),
+                ],
+            )
+            .unwrap(),
+            ..new_client_state()
+        };
+        let raw = RawClientState::from(client_state.clone());
+        assert_eq!(raw.fork_parameters.as_ref().unwrap().forks.len(), 5);
+        let client_state = MinimalClientState::try_from(raw.clone()).unwrap();
+        client_state.validate().unwrap();
+        let slots_per_epoch = client_state.slots_per_epoch;
+
+        let domains = [
+            hex!("0700000077253f0e5eb6f430d4852b80986887b9041329f9b7b1ce80f6783a9b"),
+            hex!("0700000019875730be84a7550e7a71425e0d280b17396dd4dd67f1360dde1f97"),
+            hex!("07000000a294dd5ff2a841fb303295d0fa3cb049c6a82155063f0ef2c440a229"),
+            hex!("070000006f362ae6c6c6c021285cc6523b6acf7fe56aebc7ee8281d4425bdf1c"),
+            hex!("070000004886daddc13d1ca724b85b813e74229376c9360dacff72ec1dbe4a2d"),
+        ];
+        for (i, domain) in domains.iter().enumerate() {
+            let epoch = U64(i as u64 + 1);
+            let version = Version([i as u8 + 1, 0, 0, 1]);
+            let first_slot = epoch * slots_per_epoch;
+            assert_eq!(client_state.fork_version_at(first_slot).unwrap(), version);
+            if i > 0 {
+                assert_eq!(
+                    client_state.fork_version_at(first_slot - 1).unwrap(),
+                    Version([i as u8, 0, 0, 1])
+                );
+                // the block at the last slot of the previous fork is signed at the first slot of the fork epoch
+                assert_eq!(
+                    client_state
+                        .compute_sync_committee_domain_at(first_slot)
+                        .unwrap()
+                        .0,
+                    domains[i - 1]
+                );
+            }
+            assert_eq!(
+                client_state
+                    .compute_sync_committee_domain_at(first_slot + 1)
+                    .unwrap()
+                    .0,
+                *domain,
+                "epoch={}",
+                epoch
+            );
+        }
+        // the last fork stays active after its epoch
+        assert_eq!(
+            client_state
+                .compute_sync_committee_domain_at(U64(1000) * slots_per_epoch)
+                .unwrap()
+                .0,
+            domains[4]
+        );
+
+        // the appended fork must still be scheduled after the previous one
+        let mut unordered = raw;
+        unordered.fork_parameters.as_mut().unwrap().forks[4].epoch = 3;
+        let res = MinimalClientState::try_from(unordered);
+        assert!(
+            matches!(res, Err(Error::UnorderedForkEpochs { index: 4, .. })),
+            "{:?}",
+            res
+        );
+    }
+
+    #[test]
+    fn test_sync_committee_domain_matches_upstream_at_fork_boundaries() {
+        let client_state = MinimalClientState {
+            fork_parameters: ForkParameters::new(
+                Version([0, 0, 0, 1]),
+                vec![
+                    ForkParameter::new(Version([1, 0, 0, 1]), U64(1), ALTAIR_FORK_SPEC),
+                    ForkParameter::new(Version([2, 0, 0, 1]), U64(2), BELLATRIX_FORK_SPEC),
+                    ForkParameter::new(Version([3, 0, 0, 1]), U64(4), CAPELLA_FORK_SPEC),
+                    ForkParameter::new(Version([4, 0, 0, 1]), U64(7), DENEB_FORK_SPEC),
+                ],
+            )
+            .unwrap(),
+            ..new_client_state()
+        };
+        let cc = client_state.build_context_at(Timestamp::none());
+        // the domain the verifier of ethereum-light-client-rs computes for the signature of a consensus update
+        let upstream_domain = |signature_slot: Slot| {
+            let fork_version = compute_fork_version(
+                &cc,
+                compute_epoch_at_slot(&cc, U64(signature_slot.0.max(1) - 1)),
+            )
+            .unwrap();
+            compute_domain(
+                &cc,
+                DOMAIN_SYNC_COMMITTEE,
+                Some(fork_version),
+                Some(client_state.genesis_validators_root),
+            )
+            .unwrap()
+        };
+        let slots_per_epoch = client_state.slots_per_epoch;
+        for (i, epoch) in [U64(1), U64(2), U64(4), U64(7)].into_iter().enumerate() {
+            let first_slot = epoch * slots_per_epoch;
+            // just before, at and just after the first slot of the fork epoch
+            for slot in [first_slot - 1, first_slot, first_slot + 1] {
+                let res = client_state.sync_committee_fork_version_and_domain(slot);
+                if i == 0 && slot <= first_slot {
+                    // the updates signed before the first fork are rejected instead of using the genesis fork version
+                    assert!(
+                        matches!(res, Err(Error::SlotBeforeFirstFork { .. })),
+                        "slot={} {:?}",
+                        slot,
+                        res
+                    );
+                    continue;
+                }
+                let (fork_version, domain) = res.unwrap();
+                assert_eq!(domain, upstream_domain(slot), "slot={}", slot);
+                assert_eq!(
+                    domain,
+                    client_state.compute_sync_committee_domain_at(slot).unwrap(),
+                    "slot={}",
+                    slot
+                );
+                // the signature at the first slot of the fork epoch is for the block at the last slot of the previous fork
+                let expected = if slot > first_slot { i + 1 } else { i };
+                assert_eq!(
+                    fork_version,
+                    Version([expected as u8, 0, 0, 1]),
+                    "slot={}",
+                    slot
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_verify_consensus_update_around_fork_transition() {
+        let scm = MockSyncCommitteeManager::<32>::new(1, 3);
+        let now = Timestamp::from_nanoseconds(10_000 * 1_000_000_000).unwrap();
+        // the network has activated deneb since genesis, but the client state schedules it at epoch 20
+        let network = new_client_state();
+        let network_ctx = network.build_context_at(now);
+        let client_state = new_client_state_with_deneb_at(U64(20));
+        let ctx = client_state.build_context_at(now);
+        let slots_per_epoch = client_state.slots_per_epoch;
+        let committee =
+            |period: u64| -> SyncCommittee<32> { scm.get_committee(period).to_committee().clone() };
+        let gen_update = |signature_slot: Slot| {
+            let attested_slot = signature_slot - 1;
+            let (update, _) = gen_light_client_update_with_params::<32, _>(
+                &network_ctx,
+                signature_slot,
+                attested_slot,
+                attested_slot / slots_per_epoch,
+                [1u8; 32].into(),
+                1.into(),
+                scm.get_committee(2),
+                scm.get_committee(3),
+                true,
+                32,
+            );
+            to_consensus_update_info(update)
+        };
+        let consensus_state = ConsensusState {
+            slot: U64(72),
+            storage_root: [2u8; 32].to_vec().into(),
+            timestamp: Timestamp::from_nanoseconds(1_000_000_000).unwrap(),
+            current_sync_committee: committee(1).aggregate_pubkey,
+            next_sync_committee: committee(2).aggregate_pubkey,
+            current_block_hash: None,
+        };
+        let trusted_consensus_state =
+            TrustedConsensusState::new(&ctx, consensus_state, committee(2), true).unwrap();
+
+        // both select the deneb domain one epoch after the transition
+        let update = gen_update(U64(21) * slots_per_epoch + 3);
+        let res = client_state.verify_consensus_update(&ctx, &trusted_consensus_state, &update);
+        assert!(res.is_ok(), "{:?}", res);
+
+        // one epoch before the transition, the client selects the capella domain while the update is signed with the deneb domain
+        let signature_slot = U64(19) * slots_per_epoch + 3;
+        let update = gen_update(signature_slot);
+        let res = network.verify_consensus_update(&network_ctx, &trusted_consensus_state, &update);
+        assert!(res.is_ok(), "{:?}", res);
+        let res = client_state.verify_consensus_update(&ctx, &trusted_consensus_state, &update);
+        match res {
+            Err(Error::ConsensusUpdateVerificationError {
+                signature_slot: slot,
+                fork_version,
+                signature_domain,
+                ..
+            }) => {
+                assert_eq!(slot, signature_slot);
+                assert_eq!(fork_version, Version([3, 0, 0, 1]));
+                assert_eq!(
+                    signature_domain,
+                    client_state
+                        .compute_sync_committee_domain_at(signature_slot)
+                        .unwrap()
+                );
+                assert_ne!(
+                    signature_domain,
+                    network
+                        .compute_sync_committee_domain_at(signature_slot)
+                        .unwrap()
+                );
+            }
+            res => panic!("unexpected result: {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_verify_intermediate_consensus_updates() {
+        let scm = MockSyncCommitteeManager::<32>::new(1, 5);
+        let ctx = LightClientContext::new_with_config(
+            config::minimal::get_config(),
+            Default::default(),
+            Default::default(),
+            Fraction::new(2, 3).unwrap(),
+            SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap()
+                .as_secs()
+                .into(),
+        );
+        let slots_per_period = ctx.slots_per_epoch() * ctx.epochs_per_sync_committee_period();
+        let committee =
+            |period: u64| -> SyncCommittee<32> { scm.get_committee(period).to_committee().clone() };
+        // an update signed by `signer` in `period`, which contains the sync committee of `period + 1` as the next sync committee
+        let gen_update = |period: u64, signer: u64| {
+            let signature_slot = U64(period) * slots_per_period + 11;
+            let attested_slot = signature_slot - 1;
+            let (update, _) = gen_light_client_update_with_params::<32, _>(
+                &ctx,
+                signature_slot,
+                attested_slot,
+                attested_slot / ctx.slots_per_epoch(),
+                [1u8; 32].into(),
+                1.into(),
+                scm.get_committee(signer),
+                scm.get_committee(period + 1),
+                true,
+                32,
+            );
+            to_consensus_update_info(update)
+        };
+
+        let mut client_state = new_client_state();
+        client_state.max_intermediate_consensus_updates = U64(2);
+        let consensus_state = ConsensusState {
+            slot: U64(1) * slots_per_period + 8,
+            storage_root: [2u8; 32].to_vec().into(),
+            timestamp: Timestamp::from_nanoseconds(1_000_000_000).unwrap(),
+            current_sync_committee: committee(1).aggregate_pubkey,
+            next_sync_committee: committee(2).aggregate_pubkey,
+            current_block_hash: None,
+        };
+        let trusted_consensus_state =
+            TrustedConsensusState::new(&ctx, consensus_state.clone(), committee(2), true).unwrap();
+
+        // catch up from period 1 to period 4 with a single header
+        let intermediate_updates = vec![gen_update(2, 2), gen_update(3, 3)];
+        let final_update = gen_update(4, 4);
+        let trusted = client_state
+            .verify_intermediate_consensus_updates(
+                &ctx,
+                trusted_consensus_state.clone(),
+                &intermediate_updates,
+            )
+            .unwrap();
+        let res = client_state.consensus_verifier.validate_consensus_update(
+            &ctx,
+            &trusted,
+            &final_update,
+        );
+        assert!(res.is_ok(), "{:?}", res);
+        let (_, new_consensus_state) = apply_updates(
+            &ctx,
+            &client_state,
+            &ConsensusState::from(trusted),
+            final_update.clone(),
+            2.into(),
+            None,
+            H256([3u8; 32]),
+            Timestamp::from_nanoseconds(2_000_000_000).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            new_consensus_state.slot,
+            final_update.finalized_header.0.slot
+        );
+        assert_eq!(
+            new_consensus_state.current_sync_committee,
+            committee(4).aggregate_pubkey
+        );
+        assert_eq!(
+            new_consensus_state.next_sync_committee,
+            committee(5).aggregate_pubkey
+        );
+
+        // the final update cannot be verified without the intermediate updates
+        let res = client_state.consensus_verifier.validate_consensus_update(
+            &ctx,
+            &trusted_consensus_state,
+            &final_update,
+        );
+        assert!(res.is_err());
+        // and the skipped periods are detected before the signature verification
+        let res =
+            client_state.verify_consensus_update(&ctx, &trusted_consensus_state, &final_update);
+        assert!(
+            matches!(
+                res,
+                Err(Error::SyncCommitteePeriodSkipped {
+                    trusted_period: U64(1),
+                    signature_period: U64(4),
+                })
+            ),
+            "{:?}",
+            res
+        );
+
+        // the middle update is signed by a wrong sync committee
+        let res = client_state.verify_intermediate_consensus_updates(
+            &ctx,
+            trusted_consensus_state.clone(),
+            &[gen_update(2, 2), gen_update(3, 4)],
+        );
+        // all forks of `new_client_state` are at epoch 0, so the error is wrapped with the hint of the fork schedule
+        match &res {
+            Err(Error::IntermediateConsensusUpdateError { index: 1, err }) => match err.as_ref() {
+                Error::SlotBeyondLastFork { err, .. } => match err.as_ref() {
+                    Error::ConsensusUpdateVerificationError {
+                        signature_slot,
+                        signature_period,
+                        store_period,
+                        attested_slot,
+                        ..
+                    } => {
+                        assert_eq!(*signature_slot, U64(3) * slots_per_period + 11);
+                        assert_eq!(*signature_period, U64(3));
+                        assert_eq!(*store_period, U64(2));
+                        assert_eq!(*attested_slot, U64(3) * slots_per_period + 10);
+                    }
+                    err => panic!("unexpected error: {:?}", err),
+                },
+                err => panic!("unexpected error: {:?}", err),
+            },
+            res => panic!("unexpected result: {:?}", res),
+        }
+        let msg = res.unwrap_err().to_string();
+        assert!(
+            msg.starts_with(&format!(
+                "intermediate consensus update verification error: index=1 slot {} is beyond the last configured fork; ",
+                U64(3) * slots_per_period + 11,
+            )),
+            "{}",
+            msg
+        );
+        assert!(
+            msg.contains(&format!(
+                " consensus update verification error: signature_slot={} signature_period=3 store_period=2 attested_slot={} ",
+                U64(3) * slots_per_period + 11,
+                U64(3) * slots_per_period + 10,
+            )),
+            "{}",
+            msg
+        );
+
+        // the updates are out of order
+        let res = client_state.verify_intermediate_consensus_updates(
+            &ctx,
+            trusted_consensus_state.clone(),
+            &[gen_update(3, 3), gen_update(2, 2)],
+        );
+        match &res {
+            Err(Error::IntermediateConsensusUpdateError { index: 0, err }) => assert!(
+                matches!(
+                    err.as_ref(),
+                    Error::SyncCommitteePeriodSkipped {
+                        trusted_period: U64(1),
+                        signature_period: U64(3),
+                    }
+                ),
+                "{:?}",
+                err
+            ),
+            res => panic!("unexpected result: {:?}", res),
+        }
+
+        // the update signed in period 2 finalizes the slot of the trusted consensus state
+        let (update, _) = gen_light_client_update_with_params::<32, _>(
+            &ctx,
+            U64(2) * slots_per_period + 11,
+            U64(2) * slots_per_period + 10,
+            consensus_state.slot / ctx.slots_per_epoch(),
+            [1u8; 32].into(),
+            1.into(),
+            scm.get_committee(2),
+            scm.get_committee(3),
+            true,
+            32,
+        );
+        let res = client_state.verify_intermediate_consensus_updates(
+            &ctx,
+            trusted_consensus_state.clone(),
+            &[to_consensus_update_info(update)],
+        );
+        match &res {
+            Err(Error::IntermediateConsensusUpdateError { index: 0, err }) => assert!(
+                matches!(
+                    err.as_ref(),
+                    Error::FinalizedSlotNotAdvanced { finalized_slot, trusted_slot }
+                        if *finalized_slot == consensus_state.slot && *trusted_slot == consensus_state.slot
+                ),
+                "{:?}",
+                err
+            ),
+            res => panic!("unexpected result: {:?}", res),
+        }
+
+        // the number of intermediate updates exceeds the limit
+        client_state.max_intermediate_consensus_updates = U64(1);
+        let res = client_state.verify_intermediate_consensus_updates(
+            &ctx,
+            trusted_consensus_state.clone(),
+            &intermediate_updates,
+        );
+        assert!(
+            matches!(
+                res,
+                Err(Error::TooManyIntermediateConsensusUpdates { actual: 2, .. })
+            ),
+            "{:?}",
+            res
+        );
+
+        // intermediate updates are disabled by default
+        client_state.max_intermediate_consensus_updates = U64(0);
+        let res = client_state.verify_intermediate_consensus_updates(
+            &ctx,
+            trusted_consensus_state.clone(),
+            &intermediate_updates[..1],
+        );
+        assert!(
+            matches!(
+                res,
+                Err(Error::TooManyIntermediateConsensusUpdates { actual: 1, .. })
+            ),
+            "{:?}",
+            res
+        );
+        let res =
+            client_state.verify_intermediate_consensus_updates(&ctx, trusted_consensus_state, &[]);
+        assert!(res.is_ok(), "{:?}", res);
+    }
+
+    #[test]
+    fn test_verify_finalized_header_misbehaviour() {
+        let scm = MockSyncCommitteeManager::<32>::new(1, 2);
+        let ctx = LightClientContext::new_with_config(
+            config::minimal::get_config(),
+            Default::default(),
+            Default::default(),
+            Fraction::new(2, 3).unwrap(),
+            SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap()
+                .as_secs()
+                .into(),
+        );
+        let period_1 = U64(1) * ctx.slots_per_epoch() * ctx.epochs_per_sync_committee_period();
+        let gen_update = |signature_slot: Slot, execution_state_root: [u8; 32]| {
+            let attested_slot = signature_slot - 1;
+            let (update, _) = gen_light_client_update_with_params::<32, _>(
+                &ctx,
+                signature_slot,
+                attested_slot,
+                attested_slot / ctx.slots_per_epoch(),
+                execution_state_root.into(),
+                1.into(),
+                scm.get_committee(1),
+                scm.get_committee(2),
+                true,
+                32,
+            );
+            to_consensus_update_info(update)
+        };
+        let client_id = ClientId::new(eth_client_type(), 0).unwrap();
+        let trusted_sync_committee = TrustedSyncCommittee {
+            height: height_from_block_number(U64(1)).unwrap(),
+            sync_committee: scm.get_committee(1).to_committee().clone(),
+            is_next: false,
+        };
+        let new_misbehaviour =
+            |consensus_update_1: ConsensusUpdateInfo<32>,
+             consensus_update_2: ConsensusUpdateInfo<32>| Misbehaviour {
+                client_id: client_id.clone(),
+                trusted_sync_committee_1: trusted_sync_committee.clone(),
+                trusted_sync_committee_2: trusted_sync_committee.clone(),
+                data: MisbehaviourData::FinalizedHeader(FinalizedHeaderMisbehaviour {
+                    consensus_update_1,
+                    consensus_update_2,
+                }),
+            };
+
+        let client_state = new_client_state();
+        let consensus_state = ConsensusState {
+            slot: period_1 + 1,
+            storage_root: [1u8; 32].to_vec().into(),
+            timestamp: Timestamp::from_nanoseconds(1_000_000_000).unwrap(),
+            current_sync_committee: scm.get_committee(1).to_committee().aggregate_pubkey.clone(),
+            next_sync_committee: scm.get_committee(2).to_committee().aggregate_pubkey.clone(),
+            current_block_hash: None,
+        };
+        let trusted_consensus_state = TrustedConsensusState::new(
+            &ctx,
+            consensus_state,
+            scm.get_committee(1).to_committee().clone(),
+            false,
+        )
+        .unwrap();
+
+        // the sync committee signs two different finalized headers at the same slot
+        let update_1 = gen_update(period_1 + 11, [1u8; 32]);
+        let update_2 = gen_update(period_1 + 11, [2u8; 32]);
+        assert_eq!(
+            update_1.finalized_header.0.slot,
+            update_2.finalized_header.0.slot
+        );
+        let misbehaviour = new_misbehaviour(update_1.clone(), update_2);
+        let res = misbehaviour.validate();
+        assert!(res.is_ok(), "{:?}", res);
+        let res = client_state.verify_misbehaviour(
+            &ctx,
+            (&trusted_consensus_state, &trusted_consensus_state),
+            &misbehaviour,
+        );
+        assert!(res.is_ok(), "{:?}", res);
+        let frozen_client_state = client_state
+            .clone()
+            .with_frozen_height(misbehaviour.height());
+        assert!(frozen_client_state.is_frozen());
+        assert!(frozen_client_state.verify_not_frozen().is_err());
+
+        // identical updates are not misbehaviour
+        let misbehaviour = new_misbehaviour(update_1.clone(), update_1.clone());
+        let res = misbehaviour.validate();
+        assert!(
+            matches!(res, Err(Error::IdenticalConsensusUpdatesInMisbehaviour)),
+            "{:?}",
+            res
+        );
+
+        // updates for different slots are not misbehaviour
+        let misbehaviour = new_misbehaviour(update_1, gen_update(period_1 + 21, [2u8; 32]));
+        let res = misbehaviour.validate();
+        assert!(
+            matches!(
+                res,
+                Err(Error::FinalizedHeaderMisbehaviourSlotMismatch { .. })
+            ),
+            "{:?}",
+            res
+        );
+
+        // an update which is not signed by the trusted sync committee is rejected
+        let (update_3, _) = gen_light_client_update_with_params::<32, _>(
+            &ctx,
+            period_1 + 11,
+            period_1 + 10,
+            (period_1 + 10) / ctx.slots_per_epoch(),
+            [2u8; 32].into(),
+            1.into(),
+            scm.get_committee(2),
+            scm.get_committee(2),
+            true,
+            32,
+        );
+        let misbehaviour = new_misbehaviour(
+            gen_update(period_1 + 11, [1u8; 32]),
+            to_consensus_update_info(update_3),
+        );
+        assert!(misbehaviour.validate().is_ok());
+        let res = client_state.verify_misbehaviour(
+            &ctx,
+            (&trusted_consensus_state, &trusted_consensus_state),
+            &misbehaviour,
+        );
+        assert!(
+            matches!(
+                res,
+                Err(Error::MisbehaviourConsensusUpdateError { index: 1, .. })
+            ),
+            "{:?}",
+            res
+        );
+
+        // the updates are signed in different periods and verified with different trusted consensus states
+        let period_2 = period_1 * 2;
+        let (update_4, _) = gen_light_client_update_with_params::<32, _>(
+            &ctx,
+            period_2 + 1,
+            period_1 + 10,
+            (period_1 + 10) / ctx.slots_per_epoch(),
+            [2u8; 32].into(),
+            1.into(),
+            scm.get_committee(2),
+            scm.get_committee(2),
+            true,
+            32,
+        );
+        let next_trusted_consensus_state = TrustedConsensusState::new(
+            &ctx,
+            ConsensusState {
+                slot: period_1 + 2,
+                storage_root: [2u8; 32].to_vec().into(),
+                timestamp: Timestamp::from_nanoseconds(2_000_000_000).unwrap(),
+                current_sync_committee: scm
+                    .get_committee(1)
+                    .to_committee()
+                    .aggregate_pubkey
+                    .clone(),
+                next_sync_committee: scm.get_committee(2).to_committee().aggregate_pubkey.clone(),
+                current_block_hash: None,
+            },
+            scm.get_committee(2).to_committee().clone(),
+            true,
+        )
+        .unwrap();
+        let misbehaviour = Misbehaviour {
+            trusted_sync_committee_2: TrustedSyncCommittee {
+                height: height_from_block_number(U64(2)).unwrap(),
+                sync_committee: scm.get_committee(2).to_committee().clone(),
+                is_next: true,
+            },
+            ..new_misbehaviour(
+                gen_update(period_1 + 11, [1u8; 32]),
+                to_consensus_update_info(update_4),
+            )
+        };
+        assert!(misbehaviour.validate().is_ok());
+        let res = client_state.verify_misbehaviour(
+            &ctx,
+            (&trusted_consensus_state, &next_trusted_consensus_state),
+            &misbehaviour,
+        );
+        assert!(res.is_ok(), "{:?}", res);
+        assert_eq!(
+            misbehaviour.height(),
+            height_from_block_number(U64(1)).unwrap()
+        );
+        // the second update cannot be verified with the first trusted consensus state,
+        // whose current sync committee does not sign in the next period
+        let res = client_state.verify_misbehaviour(
+            &ctx,
+            (&trusted_consensus_state, &trusted_consensus_state),
+            &misbehaviour,
+        );
+        match &res {
+            Err(Error::MisbehaviourConsensusUpdateError { index: 1, err }) => assert!(
+                matches!(
+                    err.as_ref(),
+                    Error::UnexpectedTrustedSyncCommittee { is_next: false, .. }
+                ),
+                "{:?}",
+                err
+            ),
+            res => panic!("unexpected result: {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_verify_next_sync_committee_misbehaviour() {
+        let scm = MockSyncCommitteeManager::<32>::new(1, 3);
+        let ctx = LightClientContext::new_with_config(
+            config::minimal::get_config(),
+            Default::default(),
+            Default::default(),
+            Fraction::new(2, 3).unwrap(),
+            SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap()
+                .as_secs()
+                .into(),
+        );
+        let slots_per_period = ctx.slots_per_epoch() * ctx.epochs_per_sync_committee_period();
+        let period_1 = U64(1) * slots_per_period;
+        let gen_update = |signature_slot: Slot, next_sync_committee: u64| {
+            let attested_slot = signature_slot - 1;
+            let (update, _) = gen_light_client_update_with_params::<32, _>(
+                &ctx,
+                signature_slot,
+                attested_slot,
+                attested_slot / ctx.slots_per_epoch(),
+                [1u8; 32].into(),
+                1.into(),
+                scm.get_committee(1),
+                scm.get_committee(next_sync_committee),
+                true,
+                32,
+            );
+            to_consensus_update_info(update)
+        };
+        let trusted_sync_committee = TrustedSyncCommittee {
+            height: height_from_block_number(U64(1)).unwrap(),
+            sync_committee: scm.get_committee(1).to_committee().clone(),
+            is_next: false,
+        };
+        let new_misbehaviour =
+            |consensus_update_1: ConsensusUpdateInfo<32>,
+             consensus_update_2: ConsensusUpdateInfo<32>| {
+                Misbehaviour {
+                    client_id: ClientId::new(eth_client_type(), 0).unwrap(),
+                    trusted_sync_committee_1: trusted_sync_committee.clone(),
+                    trusted_sync_committee_2: trusted_sync_committee.clone(),
+                    data: MisbehaviourData::NextSyncCommittee(NextSyncCommitteeMisbehaviour {
+                        consensus_update_1,
+                        consensus_update_2,
+                    }),
+                }
+            };
+
+        let client_state = new_client_state();
+        let consensus_state = ConsensusState {
+            slot: period_1 + 1,
+            storage_root: [1u8; 32].to_vec().into(),
+            timestamp: Timestamp::from_nanoseconds(1_000_000_000).unwrap(),
+            current_sync_committee: scm.get_committee(1).to_committee().aggregate_pubkey.clone(),
+            next_sync_committee: scm.get_committee(2).to_committee().aggregate_pubkey.clone(),
+            current_block_hash: None,
+        };
+        let trusted_consensus_state = TrustedConsensusState::new(
+            &ctx,
+            consensus_state,
+            scm.get_committee(1).to_committee().clone(),
+            false,
+        )
+        .unwrap();
+
+        // the sync committee signs two different next sync committees in the same period
+        let update_1 = gen_update(period_1 + 11, 2);
+        let misbehaviour = new_misbehaviour(update_1.clone(), gen_update(period_1 + 21, 3));
+        let res = misbehaviour.validate();
+        assert!(res.is_ok(), "{:?}", res);
+        let res = client_state.verify_misbehaviour(
+            &ctx,
+            (&trusted_consensus_state, &trusted_consensus_state),
+            &misbehaviour,
+        );
+        assert!(res.is_ok(), "{:?}", res);
+        assert!(client_state
+            .clone()
+            .with_frozen_height(misbehaviour.height())
+            .is_frozen());
+
+        // the next sync committees are equal
+        let misbehaviour = new_misbehaviour(update_1.clone(), gen_update(period_1 + 21, 2));
+        let res = misbehaviour.validate();
+        assert!(
+            matches!(
+                res,
+                Err(Error::IdenticalNextSyncCommitteesInMisbehaviour(_))
+            ),
+            "{:?}",
+            res
+        );
+
+        // an update does not contain the next sync committee
+        let mut update_2 = gen_update(period_1 + 21, 3);
+        update_2.next_sync_committee = None;
+        let misbehaviour = new_misbehaviour(update_1.clone(), update_2);
+        let res = misbehaviour.validate();
+        assert!(
+            matches!(
+                res,
+                Err(Error::NoNextSyncCommitteeInMisbehaviour { index: 1 })
+            ),
+            "{:?}",
+            res
+        );
+
+        // the attested headers are in different periods
+        let misbehaviour =
+            new_misbehaviour(update_1, gen_update(period_1 + slots_per_period + 11, 3));
+        assert!(misbehaviour.validate().is_ok());
+        let res = client_state.verify_misbehaviour(
+            &ctx,
+            (&trusted_consensus_state, &trusted_consensus_state),
+            &misbehaviour,
+        );
+        assert!(
+            matches!(
+                res,
+                Err(Error::NextSyncCommitteeMisbehaviourPeriodMismatch { .. })
+            ),
+            "{:?}",
+            res
+        );
+    }
+
+    #[test]
+    fn test_check_for_misbehaviour() {
+        let client_state = new_client_state();
+        let consensus_state = ConsensusState {
+            slot: 10.into(),
+            storage_root: [1u8; 32].to_vec().into(),
+            timestamp: Timestamp::from_nanoseconds(1_000_000_000).unwrap(),
+            current_sync_committee: PublicKey::try_from(hex!("a145063e1b5eda80fa55960296f2c4b2c021f75767318ea2572a9f7abb649010b746754ca7fc2ba57c1156881516a357").to_vec()).unwrap(),
+            next_sync_committee: PublicKey::try_from(hex!("91a8e5d0ad0d5ed9e8ef1a8cd8ed6ee0ebd7b5a1a3d8a8b164a4e8c06759e1d2c9b0d0c3f1f0f4b83f3a5fdf1a0c2c8c").to_vec()).unwrap(),
+            current_block_hash: None,
+        };
+
+        // no consensus state is stored at the height
+        assert!(!client_state.check_for_misbehaviour(&consensus_state, None));
+        // an identical re-submission
+        assert!(
+            !client_state.check_for_misbehaviour(&consensus_state, Some(&consensus_state.clone()))
+        );
+
+        // a different storage root at the same height
+        let conflicting = ConsensusState {
+            storage_root: [2u8; 32].to_vec().into(),
+            ..consensus_state.clone()
+        };
+        assert!(client_state.check_for_misbehaviour(&conflicting, Some(&consensus_state)));
+
+        // a different timestamp at the same height
+        let conflicting = ConsensusState {
+            timestamp: Timestamp::from_nanoseconds(2_000_000_000).unwrap(),
+            ..consensus_state.clone()
+        };
+        assert!(client_state.check_for_misbehaviour(&conflicting, Some(&consensus_state)));
+
+        // a different current sync committee at the same height
+        let conflicting = ConsensusState {
+            current_sync_committee: PublicKey::default(),
+            ..consensus_state.clone()
+        };
+        assert!(client_state.check_for_misbehaviour(&conflicting, Some(&consensus_state)));
+
+        // the next sync committee is compared only if both consensus states have it
+        let without_next = ConsensusState {
+            next_sync_committee: PublicKey::default(),
+            ..consensus_state.clone()
+        };
+        assert!(!client_state.check_for_misbehaviour(&without_next, Some(&consensus_state)));
+        assert!(!client_state.check_for_misbehaviour(&consensus_state, Some(&without_next)));
+        let conflicting = ConsensusState {
+            next_sync_committee: PublicKey::try_from(hex!("a42dffb90d85cec7acfcb53be0e8792155d8f18c0dc9efc2a5587d5a0ba3e578df366fc3e2b743de6ecd3b53e345c266").to_vec()).unwrap(),
+            ..consensus_state.clone()
+        };
+        assert!(client_state.check_for_misbehaviour(&conflicting, Some(&consensus_state)));
+
+        // the block hash is compared only if both consensus states have it
+        let with_block_hash = ConsensusState {
+            current_block_hash: Some(H256([3u8; 32])),
+            ..consensus_state.clone()
+        };
+        assert!(!client_state.check_for_misbehaviour(&with_block_hash, Some(&consensus_state)));
+        assert!(!client_state.check_for_misbehaviour(&consensus_state, Some(&with_block_hash)));
+        let conflicting = ConsensusState {
+            current_block_hash: Some(H256([4u8; 32])),
+            ..consensus_state.clone()
+        };
+        assert!(client_state.check_for_misbehaviour(&conflicting, Some(&with_block_hash)));
+    }
+
+    #[test]
+    fn test_prunable_heights() {
+        let mut client_state = new_client_state();
+        client_state.trusting_period = Duration::from_secs(200);
+        let (genesis_time, seconds_per_slot) =
+            (client_state.genesis_time.0, client_state.seconds_per_slot.0);
+        let timestamp_at = |slot: u64| {
+            Timestamp::from_nanoseconds((genesis_time + slot * seconds_per_slot) * 1_000_000_000)
+                .unwrap()
+        };
+        let new_consensus_state = |slot: u64| ConsensusState {
+            slot: slot.into(),
+            timestamp: timestamp_at(slot),
+            ..Default::default()
+        };
+        let height = |n: u64| height_from_block_number(n.into()).unwrap();
+        assert!(client_state
+            .prunable_heights(&[], timestamp_at(1000))
+            .is_empty());
+
+        // period = slot / 64 in the minimal preset
+        let mut consensus_states = vec![];
+        for (i, slot) in [10, 70, 100].into_iter().enumerate() {
+            consensus_states.push((height(i as u64 + 1), new_consensus_state(slot)));
+        }
+        client_state.latest_execution_block_number = 3.into();
+        // the state at slot 70 is expired, but it is in the latest period(1)
+        let host_timestamp = (timestamp_at(100) + Duration::from_secs(199)).unwrap();
+        assert_eq!(
+            client_state.prunable_heights(&consensus_states, host_timestamp),
+            vec![height(1)]
+        );
+
+        for (i, slot) in [130, 140].into_iter().enumerate() {
+            consensus_states.push((height(i as u64 + 4), new_consensus_state(slot)));
+        }
+        client_state.latest_execution_block_number = 5.into();
+        assert_eq!(
+            client_state.prunable_heights(&consensus_states, host_timestamp),
+            vec![height(1), height(2)]
+        );
+
+        // all states are expired, but the states in the latest period(2) are retained
+        let host_timestamp = (timestamp_at(140) + Duration::from_secs(200)).unwrap();
+        assert_eq!(
+            client_state.prunable_heights(&consensus_states, host_timestamp),
+            vec![height(1), height(2), height(3)]
+        );
+
+        // the state at the latest height is retained even if it is not in the latest period
+        client_state.latest_execution_block_number = 2.into();
+        assert_eq!(
+            client_state.prunable_heights(&consensus_states, host_timestamp),
+            vec![height(1), height(3)]
+        );
+    }
+
+    #[test]
+    fn test_update_result() {
+        let mut client_state = new_client_state();
+        client_state.latest_execution_block_number = 2.into();
+        let height = |n: u64| height_from_block_number(n.into()).unwrap();
+        let new_consensus_state = |slot: u64| ConsensusState {
+            slot: slot.into(),
+            ..Default::default()
+        };
+        let client_id = ClientId::new(eth_client_type(), 0).unwrap();
+        let mut ctx = crate::testing::MockContext::<32>::new(
+            Timestamp::from_nanoseconds(1).unwrap(),
+            Height::new(0, 1).unwrap(),
+        );
+
+        // the update advances the latest height
+        let mut new_client_state = client_state.clone();
+        new_client_state.latest_execution_block_number = 3.into();
+        let res = client_state.update_result(
+            new_client_state.clone(),
+            height(3),
+            new_consensus_state(100),
+            true,
+            vec![height(1)],
+        );
+        assert_eq!(res.height, height(3));
+        assert_eq!(res.consensus_state, new_consensus_state(100));
+        assert_eq!(res.client_state, Some(new_client_state.clone()));
+        assert!(res.committee_rotated);
+        assert_eq!(res.prunable_heights, vec![height(1)]);
+        let updated_state = res
+            .into_updated_state(&ctx, &client_id, &client_state)
+            .unwrap();
+        assert_eq!(
+            updated_state.client_state.latest_height(),
+            new_client_state.latest_height()
+        );
+        assert_eq!(
+            downcast_eth_consensus_state(updated_state.consensus_state.as_ref()).unwrap(),
+            new_consensus_state(100)
+        );
+
+        // the update is stored at an older height, so the client state does not change
+        let res = client_state.update_result(
+            client_state.clone(),
+            height(2),
+            new_consensus_state(71),
+            false,
+            vec![],
+        );
+        assert_eq!(res.client_state, None);
+        assert!(!res.committee_rotated);
+        let updated_state = res
+            .into_updated_state(&ctx, &client_id, &client_state)
+            .unwrap();
+        assert_eq!(
+            updated_state.client_state.latest_height(),
+            client_state.latest_height()
+        );
+
+        // a new consensus state below the latest height cannot be written by the update handler of ibc-rs
+        let res = client_state.update_result(
+            client_state.clone(),
+            height(1),
+            new_consensus_state(10),
+            false,
+            vec![],
+        );
+        let expected = ClientError::from(Error::HistoricalConsensusStateNotSupported {
+            height: height(1),
+            latest_height: height(2),
+        })
+        .to_string();
+        let err = res
+            .clone()
+            .into_updated_state(&ctx, &client_id, &client_state)
+            .err()
+            .unwrap();
+        assert_eq!(err.to_string(), expected);
+
+        // the update for a consensus state already stored changes nothing,
+        // so it is rejected rather than written with a new processed time
+        ctx.store_consensus_state(client_id.clone(), height(1), new_consensus_state(10));
+        ctx.store_consensus_state(client_id.clone(), height(2), new_consensus_state(71));
+        let expected =
+            ClientError::from(Error::ConsensusStateAlreadyStored { height: height(1) }).to_string();
+        let err = res
+            .into_updated_state(&ctx, &client_id, &client_state)
+            .err()
+            .unwrap();
+        assert_eq!(err.to_string(), expected);
+        let res = client_state.update_result(
+            client_state.clone(),
+            height(2),
+            new_consensus_state(71),
+            false,
+            vec![],
+        );
+        let expected =
+            ClientError::from(Error::ConsensusStateAlreadyStored { height: height(2) }).to_string();
+        let err = res
+            .into_updated_state(&ctx, &client_id, &client_state)
+            .err()
+            .unwrap();
+        assert_eq!(err.to_string(), expected);
 
-    let earliest_time =
-        (processed_time + delay_period_time).map_err(Error::TimestampOverflowError)?;
-    if !(current_timestamp == earliest_time || current_timestamp.after(&earliest_time)) {
-        return Err(Error::NotEnoughTimeElapsed {
-            current_timestamp,
-            earliest_time,
-        }
-        .into());
+        // the update freezing the client below the latest height returns the consensus state at the latest height unchanged
+        let res = client_state.update_result(
+            client_state.clone().with_frozen_height(height(1)),
+            height(1),
+            new_consensus_state(10),
+            false,
+            vec![],
+        );
+        let updated_state = res
+            .into_updated_state(&ctx, &client_id, &client_state)
+            .unwrap();
+        assert_eq!(updated_state.client_state.frozen_height(), Some(height(1)));
+        assert_eq!(
+            downcast_eth_consensus_state(updated_state.consensus_state.as_ref()).unwrap(),
+            new_consensus_state(71)
+        );
     }
 
-    let earliest_height = processed_height.add(delay_period_height);
-    if current_height < earliest_height {
-        return Err(Error::NotEnoughBlocksElapsed {
-            current_height,
-            earliest_height,
-        }
-        .into());
-    }
+    #[test]
+    fn test_expired_heights() {
+        let mut client_state = new_client_state();
+        client_state.trusting_period = Duration::from_secs(50);
+        client_state.max_clock_drift = Duration::from_secs(10);
+        let timestamp_at = |secs: u64| Timestamp::from_nanoseconds(secs * 1_000_000_000).unwrap();
+        let height = |n: u64| height_from_block_number(n.into()).unwrap();
+        let new_consensus_state = |secs: u64| ConsensusState {
+            timestamp: timestamp_at(secs),
+            ..Default::default()
+        };
+        assert!(client_state
+            .expired_heights(&[], timestamp_at(1000), height(1))
+            .is_empty());
 
-    Ok(())
-}
+        // a header every 20 seconds, each trusting the consensus state at height 1, is applied to the stored states
+        let mut consensus_states = vec![(height(1), new_consensus_state(0))];
+        for n in 2..=20 {
+            let header_timestamp = timestamp_at((n - 1) * 20);
+            client_state.latest_execution_block_number = n.into();
+            let expired =
+                client_state.expired_heights(&consensus_states, header_timestamp, height(1));
+            consensus_states.retain(|(h, _)| !expired.contains(h));
+            consensus_states.push((height(n), new_consensus_state((n - 1) * 20)));
+        }
+        // the states older than 60 seconds before the last header at 380 seconds are pruned, except for the trusted one
+        let surviving: Vec<Height> = consensus_states.iter().map(|(h, _)| *h).collect();
+        assert_eq!(
+            surviving,
+            vec![height(1), height(17), height(18), height(19), height(20)]
+        );
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use core::str::FromStr;
-    use ethereum_consensus::fork::{
-        altair::ALTAIR_FORK_SPEC, bellatrix::BELLATRIX_FORK_SPEC, capella::CAPELLA_FORK_SPEC,
-        deneb::DENEB_FORK_SPEC,
-    };
-    use ethereum_consensus::preset::minimal::PRESET;
-    use hex_literal::hex;
-    use time::{macros::datetime, OffsetDateTime};
+        // a state exactly at the expiry is retained
+        let consensus_states = vec![
+            (height(3), new_consensus_state(40)),
+            (height(2), new_consensus_state(39)),
+            (height(4), new_consensus_state(100)),
+        ];
+        client_state.latest_execution_block_number = 4.into();
+        assert_eq!(
+            client_state.expired_heights(&consensus_states, timestamp_at(100), height(1)),
+            vec![height(2)]
+        );
+        // the result is sorted regardless of the order of the given states
+        assert_eq!(
+            client_state.expired_heights(&consensus_states, timestamp_at(101), height(1)),
+            vec![height(2), height(3)]
+        );
+        // the trusted state and the state at the latest height are retained
+        assert_eq!(
+            client_state.expired_heights(&consensus_states, timestamp_at(1000), height(2)),
+            vec![height(3)]
+        );
+    }
 
     #[test]
-    fn test_client_state_conversion() {
-        let client_state =
-            ClientState::<{ ethereum_consensus::preset::minimal::PRESET.SYNC_COMMITTEE_SIZE }> {
-                genesis_validators_root: keccak256("genesis_validators_root"),
-                min_sync_committee_participants: 1.into(),
-                genesis_time: 1.into(),
-                fork_parameters: ForkParameters::new(
-                    Version([0, 0, 0, 1]),
-                    vec![
-                        ForkParameter::new(Version([1, 0, 0, 1]), U64(0), ALTAIR_FORK_SPEC),
-                        ForkParameter::new(Version([2, 0, 0, 1]), U64(0), BELLATRIX_FORK_SPEC),
-                        ForkParameter::new(Version([3, 0, 0, 1]), U64(0), CAPELLA_FORK_SPEC),
-                        ForkParameter::new(Version([4, 0, 0, 1]), U64(0), DENEB_FORK_SPEC),
-                    ],
-                )
-                .unwrap(),
-                seconds_per_slot: PRESET.SECONDS_PER_SLOT,
-                slots_per_epoch: PRESET.SLOTS_PER_EPOCH,
-                epochs_per_sync_committee_period: PRESET.EPOCHS_PER_SYNC_COMMITTEE_PERIOD,
-                ibc_address: Address(hex!("ff77D90D6aA12db33d3Ba50A34fB25401f6e4c4F")),
-                ibc_commitments_slot: keccak256("ibc_commitments_slot"),
-                trust_level: Fraction::new(2, 3).unwrap(),
-                trusting_period: Duration::from_secs(60 * 60 * 27),
-                max_clock_drift: Duration::from_secs(60),
-                latest_execution_block_number: 1.into(),
-                frozen_height: None,
-                consensus_verifier: Default::default(),
-                execution_verifier: Default::default(),
-            };
-        let res = client_state.validate();
-        assert!(res.is_ok(), "{:?}", res);
+    fn test_frozen_client_state() {
+        let client_state = new_client_state();
+        assert!(!client_state.is_frozen());
+        assert!(client_state.verify_not_frozen().is_ok());
+        let any_client_state: Any = client_state.clone().into();
+        assert_eq!(
+            client_state,
+            ClientState::try_from(any_client_state).unwrap()
+        );
 
+        let frozen_height = height_from_block_number(U64(100)).unwrap();
+        let client_state = client_state.with_frozen_height(frozen_height);
+        assert!(client_state.is_frozen());
+        assert_eq!(client_state.frozen_height(), Some(frozen_height));
+        let res = client_state.verify_not_frozen();
+        assert!(
+            matches!(res, Err(Error::ClientFrozen { frozen_height: h }) if h == frozen_height),
+            "{:?}",
+            res
+        );
         let any_client_state: Any = client_state.clone().into();
-        let client_state2 = ClientState::try_from(any_client_state).unwrap();
-        assert_eq!(client_state, client_state2);
+        assert_eq!(
+            client_state,
+            ClientState::try_from(any_client_state).unwrap()
+        );
 
-        // Unexpected fork parameters
-        let mut client_state = client_state.clone();
-        client_state.fork_parameters = ForkParameters::new(
-            Version([0, 0, 0, 1]),
-            vec![ForkParameter::new(
-                Version([1, 0, 0, 1]),
-                U64(0),
-                ALTAIR_FORK_SPEC,
-            )],
-        )
-        .unwrap();
-        let res = client_state.validate();
+        // the frozen check takes precedence over the other checks
+        let res = client_state.verify_height(Height::new(1, 1).unwrap());
+        assert!(matches!(res, Err(Error::ClientFrozen { .. })), "{:?}", res);
+
+        let root = hex!("27cd08827e6bf1e435832f4b2660107beb562314287b3fa534f3b189574c0cca")
+            .to_vec()
+            .into();
+        let proof_height = height_from_block_number(U64(1)).unwrap();
+        let expected = ClientError::from(Error::ClientFrozen { frozen_height }).to_string();
+        let (path, proof, value) = get_membership_proof();
+        let res = client_state.verify_membership(
+            proof_height,
+            &Default::default(),
+            &proof.try_into().unwrap(),
+            &root,
+            Path::from_str(&path).unwrap(),
+            value,
+        );
+        assert_eq!(res.unwrap_err().to_string(), expected);
+        let (path, proof) = get_non_membership_proof();
+        let res = client_state.verify_non_membership(
+            proof_height,
+            &Default::default(),
+            &proof.try_into().unwrap(),
+            &root,
+            Path::from_str(&path).unwrap(),
+        );
+        assert_eq!(res.unwrap_err().to_string(), expected);
+    }
+
+    #[test]
+    fn test_client_state_encoding_snapshot() {
+        let client_state = ClientState::<{ PRESET.SYNC_COMMITTEE_SIZE }> {
+            genesis_validators_root: H256(hex!(
+                "4b363db94e286120d76eb905340fdd4e54bfe9f06bf33ff6cf5ad27f511bfe95"
+            )),
+            min_sync_committee_participants: 1.into(),
+            genesis_time: 1606824023.into(),
+            fork_parameters: ForkParameters::new(
+                Version([0, 0, 0, 0]),
+                vec![
+                    ForkParameter::new(
+                        Version([1, 0, 0, 0]),
+                        U64(74240),
+                        ForkSpec {
+                            finalized_root_gindex: 105,
+                            current_sync_committee_gindex: 54,
+                            next_sync_committee_gindex: 55,
+                            execution_payload_gindex: 0,
+                            execution_payload_state_root_gindex: 0,
+                            execution_payload_block_number_gindex: 0,
+                        },
+                    ),
+                    ForkParameter::new(
+                        Version([2, 0, 0, 0]),
+                        U64(144896),
+                        ForkSpec {
+                            finalized_root_gindex: 105,
+                            current_sync_committee_gindex: 54,
+                            next_sync_committee_gindex: 55,
+                            execution_payload_gindex: 25,
+                            execution_payload_state_root_gindex: 18,
+                            execution_payload_block_number_gindex: 22,
+                        },
+                    ),
+                ],
+            )
+            .unwrap(),
+            seconds_per_slot: 12.into(),
+            slots_per_epoch: 32.into(),
+            epochs_per_sync_committee_period: 256.into(),
+            ibc_address: Address(hex!("ff77D90D6aA12db33d3Ba50A34fB25401f6e4c4F")),
+            ibc_commitments_slot: H256(hex!(
+                "1ee222554989dda120e26ecacf756fe1235cd8d726706b57517715dde4f0c900"
+            )),
+            commitment_value_codec: CommitmentValueCodec::Keccak256,
+            commitment_prefix: Default::default(),
+            trust_level: Fraction::new(2, 3).unwrap(),
+            trusting_period: Duration::from_secs(60 * 60 * 27),
+            max_clock_drift: Duration::from_secs(60),
+            max_intermediate_consensus_updates: U64(0),
+            latest_execution_block_number: 1.into(),
+            frozen_height: None,
+            misbehaviour_evidence_digest: None,
+            consensus_verifier: Default::default(),
+            execution_verifier: Default::default(),
+        };
+        // changing these bytes breaks the commitments of the existing client states
+        let expected = hex!("0a204b363db94e286120d76eb905340fdd4e54bfe9f06bf33ff6cf5ad27f511bfe95100118d7e098fe0522340a040000000012120a04010000001080c4041a0608691036183712180a04020000001080ec081a0c086910361837201928123016280c30203880024214ff77d90d6aa12db33d3ba50a34fb25401f6e4c4f4a201ee222554989dda120e26ecacf756fe1235cd8d726706b57517715dde4f0c9005204080210035a0408b0f7056202083c6801").to_vec();
+        let any_client_state = client_state.clone().into_any();
+        assert_eq!(any_client_state.type_url, ETHEREUM_CLIENT_STATE_TYPE_URL);
+        assert_eq!(any_client_state.value, expected);
+        assert_eq!(
+            ClientState::try_from(any_client_state.clone()).unwrap(),
+            client_state
+        );
+
+        // unknown field
+        let mut any = any_client_state.clone();
+        any.value.extend_from_slice(&[0x78, 0x01]);
+        let res = ClientState::<{ PRESET.SYNC_COMMITTEE_SIZE }>::try_from(any);
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            ClientError::from(Error::UnexpectedAnyPayload {
+                type_url: ETHEREUM_CLIENT_STATE_TYPE_URL
+            })
+            .to_string()
+        );
+        // duplicated field
+        let mut any = any_client_state.clone();
+        any.value.extend_from_slice(&[0x68, 0x01]);
+        let res = ClientState::<{ PRESET.SYNC_COMMITTEE_SIZE }>::try_from(any);
         assert!(res.is_err(), "{:?}", res);
+        // trailing garbage
+        let mut any = any_client_state.clone();
+        any.value.push(0);
+        let res = ClientState::<{ PRESET.SYNC_COMMITTEE_SIZE }>::try_from(any);
+        assert!(res.is_err(), "{:?}", res);
+
+        // a zero frozen height is re-encoded as absent, so only the lenient decoding accepts it
+        let mut raw = RawClientState::decode(any_client_state.value.as_slice()).unwrap();
+        raw.frozen_height = Some(Default::default());
+        let any = Any {
+            type_url: ETHEREUM_CLIENT_STATE_TYPE_URL.to_string(),
+            value: raw.encode_to_vec(),
+        };
+        let res = ClientState::<{ PRESET.SYNC_COMMITTEE_SIZE }>::try_from(any.clone());
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            ClientError::from(Error::UnexpectedAnyPayload {
+                type_url: ETHEREUM_CLIENT_STATE_TYPE_URL
+            })
+            .to_string()
+        );
+        let decoded =
+            ClientState::<{ PRESET.SYNC_COMMITTEE_SIZE }>::try_from_any_lenient(any).unwrap();
+        assert_eq!(decoded, client_state);
+        assert_eq!(decoded.into_any().value, expected);
     }
 
     #[test]
@@ -1097,21 +4035,73 @@ mod tests {
                 ibc_address: Address(hex!("ff77D90D6aA12db33d3Ba50A34fB25401f6e4c4F")),
                 ..Default::default()
             };
-        let account_proof = decode_eip1184_rlp_proof(
+        let account_proof = decode_eip1186_rlp_proof(
             hex!("f901fff90191a05844e303fa8db3fa31c729db25d9b593367f853b4cbcb1a91fc85eda11e16617a09bb111cd80eee4c6ae6af0d01422ae82fccfa80d0267c4c8d525bc7f2b6233afa0323230228b1ba9b7eb88084b6d1ed9b75813a2da2d5ff0df9067335f5f55444ca0bfca1461a76f96944aa00afff03dc8de770275fbbe360f6ee03b0fe0ce902fd8a04c7579812e09de2b1aa746b0a047d357e898e9d634ac185d7e9d25b3d2336ab3808080a0c7de43d788c5228ebde29b62cb0f9b9eb10c0cb9b1078d6a51f768e0cdf296d6a0b8ad2523a3d1fdf33b627f598622775508297710e3623de115f2174c7f5727dfa023910890abfb016861bb7916cb555add80e552f118c0f1b93ec7d26798976f1da077153f3a45bebfb8b6709bd52e71d0993e9ecfd4e425204e258e5e5ac775ee73a01b42efb18b5af3defc59ba21f68965c5a28c716e109df937d216a2041fee4770a06b4b8f8ad0ae7588581c191bf177d5020fcc0f9152123cd26b3acf4e3469744280a0b4ec201ec80c64cefbe351f2febea48eb21c0d65d3e1c868178ece65e3d63ff480f869a0346090ccaa6fa9fa12360268a84aaba21af051a53bfdc84493350c840f61b79eb846f8440180a0d70e9391a3dd508a60195d2a5e12fb2f7e49582f9ce2c12477299377ccfadaada073092abb9be4a3fa206fd43699af07ff9d4278c27693f013fceb7780f3654c09").to_vec()
         ).unwrap();
+        let state_root = H256(hex!(
+            "48b7747ba1094684d9197bbaa5dcb134587d23e493fb53a29e400c50e50f5147"
+        ));
+        let storage_root = H256(hex!(
+            "d70e9391a3dd508a60195d2a5e12fb2f7e49582f9ce2c12477299377ccfadaad"
+        ));
         let res = client_state.verify_account_storage(
-            H256(hex!(
-                "48b7747ba1094684d9197bbaa5dcb134587d23e493fb53a29e400c50e50f5147"
-            )),
+            state_root,
             &AccountUpdateInfo {
-                account_proof,
-                account_storage_root: H256(hex!(
-                    "d70e9391a3dd508a60195d2a5e12fb2f7e49582f9ce2c12477299377ccfadaad"
-                )),
+                account_proof: account_proof.clone(),
+                account_storage_root: storage_root,
             },
         );
         assert!(res.is_ok(), "{:?}", res);
+
+        let account =
+            commitment::verify_account(state_root, &client_state.ibc_address, &account_proof);
+        assert_eq!(
+            account.unwrap(),
+            Some(commitment::Account {
+                nonce: 1,
+                balance: H256::default(),
+                storage_root,
+                code_hash: H256(hex!(
+                    "73092abb9be4a3fa206fd43699af07ff9d4278c27693f013fceb7780f3654c09"
+                )),
+            })
+        );
+
+        // the public API returns the same result as the verification of `AccountUpdateInfo`
+        let mut corrupted = account_proof.clone();
+        corrupted[1][10] ^= 0xff;
+        for (account_proof, account_storage_root) in [
+            (account_proof.clone(), storage_root),
+            (account_proof.clone(), H256::default()),
+            (account_proof[..1].to_vec(), storage_root),
+            (corrupted, storage_root),
+        ] {
+            let expected = commitment::verify_account_storage_root(
+                state_root,
+                &client_state.ibc_address,
+                &account_proof,
+                account_storage_root,
+            );
+            let res = client_state.verify_account_storage(
+                state_root,
+                &AccountUpdateInfo {
+                    account_proof,
+                    account_storage_root,
+                },
+            );
+            assert_eq!(format!("{:?}", res), format!("{:?}", expected));
+        }
+        let res = commitment::verify_account_storage_root(
+            state_root,
+            &client_state.ibc_address,
+            &account_proof,
+            H256::default(),
+        );
+        assert!(
+            matches!(res, Err(Error::AccountStorageRootMismatch(..))),
+            "{:?}",
+            res
+        );
     }
 
     #[test]
@@ -1121,7 +4111,7 @@ mod tests {
                 ibc_address: Address(hex!("a7f733a4fEA1071f58114b203F57444969b86524")),
                 ..Default::default()
             };
-        let account_proof = decode_eip1184_rlp_proof(
+        let account_proof = decode_eip1186_rlp_proof(
             hex!("f90c8bf90211a0735c089329da81ce7b2d42666be5a1937cba65e1e018ca007d2d89097643093da050ac94d9d3f41b5affc198a6fe058bbf0b5c325da0bc89914e94cd792c835ec7a0dd6fafef2a8250c254db15fa44c2d53dc543eddb86800bcd892bd98c0adb2fafa0603d4baa1bf91623b90e4d7760123036058fabdc5895f1efab03d9bc9b92da9ea0688c8a3f57cf3579a8b19011d9a811e5bcbcd467216935fdfde60884e6af7991a0812a0308609cc2b529630ab727abd3b1aa974896e4cd0a02bbe517f77b142a2ba02904a77c6e9c680d4d302e5a51b847fca63a6532b21b07dd41a3ac49f4151349a0e179454a28109b5aab7b880693b5786b568781d9aa45986f74be7d6a7a818d72a07f3363c3908198f0aaa22c64185be10461a51524bc2b53f5d622caef25c672a0a0e65b893234155029bcf2f893bfdf4498509062c0949eb0ffc027c075267b999fa01d6a1e0b72b63a7a00083a40572d4041116a286fa3a2d747b25fa0cefe07642ca0d8b29fe0f182ddf0482f5b3ab6b3a0640e63177a78eadf75f5773ed534214e4aa0de1d7bd5c5dbc04a2f734621a7225856c722cce92fe201cab1348282b05a1d97a0f9e111c8222ff2bc2fe565ff7f626c9748239c2038e288380081eb2a3af0c908a07dd132ff024cf49ed3f49149f8bae764e9a173f499ccfcd9f408dd2000bb531da0b954951fd86e5b275c759fbd29eaa292f3c84fdbb6bcc5b1e714a1d838582aae80f90211a04d152a07d2c9c1547501953755563814d7cb184a7756551eaf482b759dae5769a07316575d385637101fdb5f52e676e10f25ae0aa08eee125a7e5bedf4aad6c122a011e2f69401bab09a648aff06e1893c93e2e58a9635d0bcbeb56ae3f585a04b85a086e49bb50598013f14675177ae79c1da54e6c2bbaf91c3b1341142c29271c409a0ceff5f5bd8e1824b24651f15e4ba56fee26cb0b9173f7d4ebab3e0293c7269e7a0fecedf695e9918f9c3397accb5416c4696999fa64fa2e842ec79be8cb93a8651a0854ca3709cc3460ada39aea9619d7f571c117b6054ecb2c4370584d63cf03641a0826eff5d12e4895656e42b47bb3ae6d82027b210c09c85ea702c117193beab3da009fe1cc705eb0d3ac788027f618c076ef1510aadcee6cd736ac931c763f9ef67a0cafc24169ce2d0bc3a14471e1d74470cf692df45e672a4b5281be6634d0f06f4a06dd0a6ae12c583185056c34a27b4df3e66b7da685d90b05e05f73e4904dd502aa0a43f00480c0a7219aca6e9681573abe0206a0ac744fddbd721a00d9342bbc418a0f97f13efdb911e697b75663c41dca7e5b31ad93a6d460bac224e30c1188fb7e3a07fc2f215ae82e3c19b48ec6f1a28427f17a04668308f573c20b68626bc85955ca04896f54096f0489f5d0649b4d7fc796caccfbb275dfa1a90b8b9be2116a7e273a0cdbca11f9ed5ea1d347dd6f40c8bdb8505f21deb53161ea57ebd08fe0becfa8f80f90211a03bf4c6b5933c499c155a6edb44b781dc408992c2c4c16ca1d75ad19e23fafeefa0b55c56b13f5d19dde3d7d96cc156a92d2feb78d29125f9bd2925202f466313a7a0244e4254c915f0209716c58e02b1e59702b3b9a28c5c59d361dd06cd91ec70f9a06ea7d219827e2a71031d36d892fffb6aca878ec90f0e1086cbf6a5dd1ff8763ba0751e39cd8c27f3607a42d11897cd190d0984be5847cbe6100751d06d04c637bba002871c10a84f539119d02559afcc35d6751864529189ce5c7038a2c118601fe3a0306ff9c515871b0080b9334022351644dbe44901c2b2f267311675829c1307fca0a963d8f5da27142226b9607bd09d1aa7d64f3615236bc22cb7439045b0d93abea0e91fb125186252297f099d76d4f40ee948d5a0fb2b469ee63c685db204be7a4ca0ccd4667345dd458431de1d9fab425f36553d444e9b059d847d72b4a193971f1aa0d3b2c96703267e6040450109d4226f4b05cd4274c5f4ed99aa97b83b70044bf1a0543e2abdea1838ca8408ac52fb1d7fb170b2e822aa6989558d4fbf47a0f8b851a016b137139bdb068d710119ec01df37f40e6c6db0c31346bf083893a73e3402e0a070ef51e27ac486580d225c4f7bb73f6456f2fbc69ea5e945471d9c86268d3da4a0838f376bf27fbe43dcede5396b787b4047ab9ab13c3d54b71ea7ded3aeb44ef1a02c207350ede911a939db2cbde3644c5508592fe281d97b7f798753779ae6651a80f90211a04081815e12cf03a52f183def5e687076094b0bc6387363c051b3e4b1be4d1d19a06c73c48b7349672f346b03a3df4838129ed5f92eddddd7e2d1e2efad591602cba0b78231ff87a3f6b239661cde00ea1f605e78a70886467d2fba5ec455327182a4a00e844f2238c3a9a7401aacd5382fd8e9ade8c5d9a2ed18936f18b7a4a0ce0159a0f0cf87ad75cbe0d422d501ceecd3eeb2322e987dd06b948719296214d2b1bd3da0b972c5937a9fc152bc6fa930e84060e2bef4a63d83978a4ec6bc14b60bf452efa0db85e78720006b5ea6e7b258522837815cfb16a3c634e74913394239fb83397ea0f79388ee57552bb0f2cd6fb7fcb716156607be0f95acdab409b64f590c7d7f72a0140623eb8411a98ac14271b6838908e6a27803bfea3073aef8c8825eaed50fe1a09400f4a94faf9a7a80d58f889ca08a665c82e1cac2039a7fcc0ae9698621f1e0a0ebb02920f9288829afb95192e7466da33e45f996d9cd29d7fc06cad275663d47a0dfd361a59760d8542085eb26b3108ccda0111407637599dfd6ee1db3f8d7829ea07bfd10a32a6183ace7f87df01d5dc24bf0e9d09f65e7954096ab6386ac72bf3fa056a0b81e9ddedb42072c3b9100cf911c1933d16e231dde74647d33b7e8de95b7a0ed1a49b7ed1daea815c0cb7400776af17dbc4b27300cffc5c5d61ce01305f6c6a0b98bf6e4aadc8c9f754d04107972e868325c1c0b65338b14e80a8754952c533780f90211a06c58a57085037dfc8db7fcc57396521078f8fcfb9e76eeb2b45d3408c1fdd191a056dcc694badf8487675150d3f90d7452e1d008da178140a84cab3708e94eb4eda06ee4844656a92e04a04a5469280c9d69b4f12ab8f29896b2645fcb92603bea80a0807c0b0f7cdfa86c4390d62202ad4eacfe0a6182189b34bad027db16336c5a78a02b1b7221d0a98161170a5008a702030f8ec84d02f939b59755952ce3437bba0aa01c5e574e042f54cf46caec858db5ca34901a000195d1f044e03dcbb66f904e36a0d0f3ab90005c9f49578bbba90bca526e4034d86d49fde2c8269be58a30f540e3a0145539ee81a9fb21e1af7b5851d57a497765dd5c4351904c8a725ec755e0ce52a0e90a6fd2863da09c2bf1f1a4f0d8f04a095bdc76816000aeaef5bb20e4f717d9a033d95a81d8b601f9dbdbea947a7052eb849dd91875e734da645c9f240418c72fa053dc0376d2c1309c1bbe7755d7ede7bfa4af13c2c933538a539c75bdecbd9e53a0707665486d4924ae7eecfcf41f7f804906140baecf43fa72ec8419bcdfd48f1ba0ccf9006732611705137f5562d6b59442af44c13ea97df11c09df468dbe0a7323a0385c7d72bf637b580a2ddec76007d12d1048a2553ec662d96ed25ccedd97053fa0939aa354a3598e3248baf25e27953c3812b2c555d5e43db59d330ab6f9e8c3c3a0c941eff5b200af34d12362bf73856fa0164d788274804245239e66c55091135080f901b18080a0a3c8aca2eeb300dc458c7fc99aadba23e3bbd0f88e9def4c89c8c58c5e4b468aa02440a82db7c8b7044dd2fba70e5204e481032a630173d8d9752851e7df7e4240a0a60accaa78c07a21cfb1414797e9d8dd1236cf96bb1bb56235ac899906c25bc7a047e97e35f2cd979e6b93001f40002e22a0e754923d6e093c0f7cf2578d460e82a017e89cef18477aa7708df31998c60d1acd734e0266e70d8388914ab6f53d7ce9a000031279827d634f41754800f88bc0e9e134cb2d1d66065493c6ef32f22ed1c6a03bdc555af1c06c2c53d99b142ef0fc72c4b58bbfe63975c1aeda24a3eb2ea66ca06408ef004b300be9baccefd12ee648e24adc85841af0d0037c56c3992a0a5e2d80a0b4c8ba93a4166cfc2a051400b8afcdb41d5c74be9d105fb39613f8d9ab064a4da073f59f57bc2e6297832cb37f6618ba99279e2f32d2661110c1682ed2e8694c7ca056ec3e008e136bdf75ee10371aad81c93e6592dc5224b0b3018fea73bae89f7da01cf891d6aa49256fbe12d75e60adfbc612826164564eb89c19371a2625fb8633a04b147c2c876e2578176a1789f8c80d2c4674fbc6ea1cdd319e4b89085594fc4880f87180808080a0898c7859ae8ec9411296d9568545abbe3395dcab69264d16de52cf2489bea53e808080808080a0fdbf45b5370653412069c67c697029941cc4c34a563d265b2b34f95656cb2a38a06a47e9ff626afe400b0591b2632976db76ff66355e000d89b39fa40c3148934f80808080").to_vec()
         ).unwrap();
         let res = client_state.verify_account_storage(
@@ -1129,11 +4119,63 @@ mod tests {
                 "568a51c3253bbd2d46e3923b35df0489712df11453fd04dd71341120356952c0"
             )),
             &AccountUpdateInfo {
-                account_proof,
+                account_proof: account_proof.clone(),
                 account_storage_root: H256::default(), // non-existence
             },
         );
         assert!(res.is_ok(), "{:?}", res);
+
+        let res = commitment::verify_account(
+            H256(hex!(
+                "568a51c3253bbd2d46e3923b35df0489712df11453fd04dd71341120356952c0"
+            )),
+            &client_state.ibc_address,
+            &account_proof,
+        );
+        assert_eq!(res.unwrap(), None);
+        // the account does not exist, so the storage root must be zero
+        let res = commitment::verify_account_storage_root(
+            H256(hex!(
+                "568a51c3253bbd2d46e3923b35df0489712df11453fd04dd71341120356952c0"
+            )),
+            &client_state.ibc_address,
+            &account_proof,
+            H256([1u8; 32]),
+        );
+        assert!(
+            matches!(res, Err(Error::AccountStorageRootMismatch(..))),
+            "{:?}",
+            res
+        );
+
+        // a corrupted node in the middle of the proof is reported
+        let mut corrupted = account_proof.clone();
+        corrupted[2][10] ^= 0xff;
+        let res = client_state.verify_account_storage(
+            H256(hex!(
+                "568a51c3253bbd2d46e3923b35df0489712df11453fd04dd71341120356952c0"
+            )),
+            &AccountUpdateInfo {
+                account_proof: corrupted.clone(),
+                account_storage_root: H256::default(),
+            },
+        );
+        match res {
+            Err(Error::AccountProofWalkError { err, .. }) => {
+                assert_eq!(err.node_index, 2);
+                assert_eq!(
+                    err.expected_hash,
+                    H256(commitment::keccak_256(&account_proof[2]))
+                );
+                assert_eq!(
+                    err.got_hash,
+                    Some(H256(commitment::keccak_256(&corrupted[2])))
+                );
+                // each branch node above the corrupted node consumes a nibble
+                assert_eq!(err.consumed_nibbles, 2);
+            }
+            res => panic!("unexpected result: {:?}", res),
+        }
     }
 
     #[test]
@@ -1151,16 +4193,37 @@ mod tests {
             .to_vec()
             .into();
         let (path, proof, value) = get_membership_proof();
-        let proof_height = Height::new(ETHEREUM_CLIENT_REVISION_NUMBER, 1).unwrap();
+        let proof_height = height_from_block_number(U64(1)).unwrap();
         let res = client_state.verify_membership(
             proof_height,
-            &Default::default(),
-            &proof.try_into().unwrap(),
+            &client_state.commitment_prefix(),
+            &proof.clone().try_into().unwrap(),
             &root,
             Path::from_str(&path).unwrap(),
-            value,
+            value.clone(),
         );
         assert!(res.is_ok(), "{:?}", res);
+        assert_eq!(client_state.commitment_prefix().as_bytes(), b"ibc");
+
+        // the prefix is not part of the storage key, so any other prefix is a misconfiguration of the counterparty
+        for prefix in [
+            CommitmentPrefix::default(),
+            CommitmentPrefix::try_from(b"ibc/".to_vec()).unwrap(),
+        ] {
+            let res = client_state.verify_membership(
+                proof_height,
+                &prefix,
+                &proof.clone().try_into().unwrap(),
+                &root,
+                Path::from_str(&path).unwrap(),
+                value.clone(),
+            );
+            let expected = ClientError::from(Error::UnexpectedCommitmentPrefix {
+                expected: b"ibc".to_vec(),
+                got: prefix.as_bytes().to_vec(),
+            });
+            assert_eq!(res.unwrap_err().to_string(), expected.to_string());
+        }
     }
 
     #[test]
@@ -1178,10 +4241,10 @@ mod tests {
             .to_vec()
             .into();
         let (path, proof) = get_non_membership_proof();
-        let proof_height = Height::new(ETHEREUM_CLIENT_REVISION_NUMBER, 1).unwrap();
+        let proof_height = height_from_block_number(U64(1)).unwrap();
         let res = client_state.verify_non_membership(
             proof_height,
-            &Default::default(),
+            &client_state.commitment_prefix(),
             &proof.try_into().unwrap(),
             &root,
             Path::from_str(&path).unwrap(),
@@ -1189,8 +4252,143 @@ mod tests {
         assert!(res.is_ok(), "{:?}", res);
     }
 
+    #[test]
+    fn test_consensus_state_root() {
+        let client_state =
+            ClientState::<{ ethereum_consensus::preset::minimal::PRESET.SYNC_COMMITTEE_SIZE }> {
+                ibc_address: Address(hex!("a7f733a4fEA1071f58114b203F57444969b86524")),
+                ibc_commitments_slot: H256(hex!(
+                    "1ee222554989dda120e26ecacf756fe1235cd8d726706b57517715dde4f0c900"
+                )),
+                latest_execution_block_number: 1.into(),
+                ..Default::default()
+            };
+        // the storage root of the IBC contract deployed with ibc-solidity
+        let storage_root = hex!("27cd08827e6bf1e435832f4b2660107beb562314287b3fa534f3b189574c0cca");
+        let consensus_state = ConsensusState {
+            storage_root: storage_root.to_vec().into(),
+            ..Default::default()
+        };
+        // the root is the storage root itself
+        assert_eq!(consensus_state.root().as_bytes(), storage_root);
+        assert!(consensus_state.matches_commitment(&storage_root));
+        assert!(!consensus_state.matches_commitment(&storage_root[1..]));
+        assert!(!consensus_state.matches_commitment(&[0u8; 32]));
+
+        // the commitment committed by the counterpart can be verified against the root
+        let (path, proof, value) = get_membership_proof();
+        let res = client_state.verify_membership(
+            height_from_block_number(U64(1)).unwrap(),
+            &client_state.commitment_prefix(),
+            &proof.try_into().unwrap(),
+            consensus_state.root(),
+            Path::from_str(&path).unwrap(),
+            value,
+        );
+        assert!(res.is_ok(), "{:?}", res);
+    }
+
+    /// Returns the state root of a state trie that only contains the account of `address` with `storage_root`, and the account update proving it
+    pub(crate) fn single_account_update(
+        address: &Address,
+        storage_root: H256,
+    ) -> (H256, AccountUpdateInfo) {
+        let mut account = rlp::RlpStream::new_list(4);
+        account.append(&1u64);
+        account.append_empty_data();
+        account.append(&storage_root.as_bytes().to_vec());
+        account.append(&commitment::keccak_256(&[]).to_vec());
+        // the leaf node holds the whole key, which is hex-prefixed with the even leaf flag
+        let mut key = vec![0x20];
+        key.extend_from_slice(address.trie_key().as_bytes());
+        let mut leaf = rlp::RlpStream::new_list(2);
+        leaf.append(&key);
+        leaf.append(&account.out().to_vec());
+        let leaf = leaf.out().to_vec();
+        (
+            H256(commitment::keccak_256(&leaf)),
+            AccountUpdateInfo {
+                account_proof: vec![leaf],
+                account_storage_root: storage_root,
+            },
+        )
+    }
+
+    #[test]
+    fn test_single_account_update() {
+        let address = Address(hex!("a7f733a4fEA1071f58114b203F57444969b86524"));
+        let client_state = ClientState::<{ PRESET.SYNC_COMMITTEE_SIZE }> {
+            ibc_address: address,
+            ..Default::default()
+        };
+        let storage_root = H256([1u8; 32]);
+        let (state_root, account_update) = single_account_update(&address, storage_root);
+        let res = client_state.verify_account_storage(state_root, &account_update);
+        assert!(res.is_ok(), "{:?}", res);
+
+        // the proof does not prove another storage root
+        let res = client_state.verify_account_storage(
+            state_root,
+            &AccountUpdateInfo {
+                account_storage_root: H256([2u8; 32]),
+                ..account_update.clone()
+            },
+        );
+        assert!(
+            matches!(res, Err(Error::AccountStorageRootMismatch(..))),
+            "{:?}",
+            res
+        );
+        // nor the storage root in another state trie
+        let (other_state_root, _) = single_account_update(&address, H256([2u8; 32]));
+        let res = client_state.verify_account_storage(other_state_root, &account_update);
+        assert!(res.is_err(), "{:?}", res);
+    }
+
+    #[test]
+    fn test_verify_membership_with_different_commitments_slot() {
+        let root = hex!("27cd08827e6bf1e435832f4b2660107beb562314287b3fa534f3b189574c0cca")
+            .to_vec()
+            .into();
+        let (path, proof, value) = get_membership_proof();
+        let proof_height = height_from_block_number(U64(1)).unwrap();
+        for (ibc_commitments_slot, expected_ok) in [
+            (
+                H256(hex!(
+                    "1ee222554989dda120e26ecacf756fe1235cd8d726706b57517715dde4f0c900"
+                )),
+                true,
+            ),
+            (
+                H256(hex!(
+                    "0000000000000000000000000000000000000000000000000000000000000003"
+                )),
+                false,
+            ),
+            (H256::default(), false),
+        ] {
+            let client_state = ClientState::<
+                { ethereum_consensus::preset::minimal::PRESET.SYNC_COMMITTEE_SIZE },
+            > {
+                ibc_address: Address(hex!("a7f733a4fEA1071f58114b203F57444969b86524")),
+                ibc_commitments_slot,
+                latest_execution_block_number: 1.into(),
+                ..Default::default()
+            };
+            let res = client_state.verify_membership(
+                proof_height,
+                &client_state.commitment_prefix(),
+                &proof.clone().try_into().unwrap(),
+                &root,
+                Path::from_str(&path).unwrap(),
+                value.clone(),
+            );
+            assert_eq!(res.is_ok(), expected_ok, "{:?}", res);
+        }
+    }
+
     // returns: (path, proof, value)
-    fn get_membership_proof() -> (String, Vec<u8>, Vec<u8>) {
+    pub(crate) fn get_membership_proof() -> (String, Vec<u8>, Vec<u8>) {
         (
             "clients/lcp-client-0/clientState".to_string(),
             hex!("f90159f901118080a0143145e818eeff83817419a6632ea193fd1acaa4f791eb17282f623f38117f56a0e6ee0a993a7254ee9253d766ea005aec74eb1e11656961f0fb11323f4f91075580808080a01efae04adc2e970b4af3517581f41ce2ba4ff60492d33696c1e2a5ab70cb55bba03bac3f5124774e41fb6efdd7219530846f9f6441045c4666d2855c6598cfca00a020d7122ffc86cb37228940b5a9441e9fd272a3450245c9130ca3ab00bc1cd6ef80a0047f255205a0f2b0e7d29d490abf02bfb62c3ed201c338bc7f0088fa9c5d77eda069fecc766fcb2df04eb3a834b1f4ba134df2be114479e251d9cc9b6ba493077b80a094c3ed6a7ef63a6a67e46cc9876b9b1882eeba3d28e6d61bb15cdfb207d077e180f843a03e077f3dfd0489e70c68282ced0126c62fcef50acdcb7f57aa4552b87b456b11a1a05dc044e92e82db28c96fd98edd502949612b06e8da6dd74664a43a5ed857b298").to_vec(),
@@ -1254,6 +4452,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_header_timestamp_validation() {
+        let to_timestamp = |t: OffsetDateTime| {
+            Timestamp::from_nanoseconds(t.unix_timestamp_nanos() as u64).unwrap()
+        };
+        let current_timestamp = datetime!(2023-08-20 0:00 UTC);
+        let trusting_period = Duration::from_secs(100);
+        let clock_drift = Duration::from_secs(10);
+        let validate = |header_timestamp: OffsetDateTime| {
+            validate_header_timestamp(
+                to_timestamp(current_timestamp),
+                trusting_period,
+                clock_drift,
+                to_timestamp(header_timestamp),
+            )
+        };
+
+        assert!(validate(current_timestamp).is_ok());
+        // future boundary
+        assert!(validate(current_timestamp + clock_drift - Duration::new(0, 1)).is_ok());
+        assert!(matches!(
+            validate(current_timestamp + clock_drift),
+            Err(Error::HeaderFromFuture { .. })
+        ));
+        // past boundary
+        assert!(validate(current_timestamp - trusting_period + Duration::new(0, 1)).is_ok());
+        assert!(matches!(
+            validate(current_timestamp - trusting_period),
+            Err(Error::OutOfTrustingPeriod { .. })
+        ));
+    }
+
     fn validate_and_assert_trusting_period_no_error(
         current_timestamp: OffsetDateTime,
         trusting_period: u64,
@@ -1332,14 +4562,146 @@ mod tests {
     }
 
     #[test]
-    fn test_trim_left_zero() {
-        assert_eq!(trim_left_zero(&[1, 2, 3, 4]), [1, 2, 3, 4]);
-        assert_eq!(trim_left_zero(&[1, 2, 3, 0]), [1, 2, 3, 0]);
-        assert_eq!(trim_left_zero(&[0, 2, 3, 0]), [2, 3, 0]);
-        assert_eq!(trim_left_zero(&[0, 0, 3, 0]), [3, 0]);
-        assert_eq!(trim_left_zero(&[0, 0, 0, 4]), [4]);
-        assert!(trim_left_zero(&[0, 0, 0, 0]).is_empty());
-        assert!(trim_left_zero(&[]).is_empty());
+    fn test_verify_sync_committee_participants() {
+        let client_state = ClientState::<32> {
+            min_sync_committee_participants: 10.into(),
+            trust_level: Fraction::new(1, 32).unwrap(),
+            ..Default::default()
+        };
+        let update_with_participants = |participants: usize| {
+            let mut update = ConsensusUpdateInfo::<32>::default();
+            for i in 0..participants {
+                update.sync_aggregate.sync_committee_bits.set(i, true);
+            }
+            update
+        };
+
+        for participants in [10, 11, 32] {
+            let res = client_state
+                .verify_sync_committee_participants(&update_with_participants(participants));
+            assert!(res.is_ok(), "{:?}", res);
+        }
+        for participants in [1, 9] {
+            let res = client_state
+                .verify_sync_committee_participants(&update_with_participants(participants));
+            match res {
+                Err(Error::InsufficientSyncCommitteeParticipants {
+                    min_sync_committee_participants,
+                    participants: actual,
+                }) => {
+                    assert_eq!(min_sync_committee_participants, 10);
+                    assert_eq!(actual, participants);
+                }
+                _ => panic!("unexpected result: {:?}", res),
+            }
+        }
+
+        // no participants is rejected before the minimum is consulted
+        let mut update = update_with_participants(0);
+        update.signature_slot = 100.into();
+        let res = ClientState::<32> {
+            min_sync_committee_participants: 0.into(),
+            ..client_state
+        }
+        .verify_sync_committee_participants(&update);
+        assert!(
+            matches!(
+                res,
+                Err(Error::ZeroSyncCommitteeParticipants { signature_slot }) if signature_slot == U64(100)
+            ),
+            "{:?}",
+            res
+        );
+    }
+
+    #[test]
+    fn test_trust_level_participation() {
+        let trust_level = Fraction::new(2, 3).unwrap();
+        // 512 * 2 / 3 = 341.33...
+        assert!(verify_trust_level_participation(&trust_level, 342, 512).is_ok());
+        assert!(verify_trust_level_participation(&trust_level, 512, 512).is_ok());
+        let res = verify_trust_level_participation(&trust_level, 341, 512);
+        assert!(
+            matches!(
+                res,
+                Err(Error::InsufficientTrustLevelParticipation {
+                    participants: 341,
+                    sync_committee_size: 512,
+                    numerator: 2,
+                    denominator: 3,
+                })
+            ),
+            "{:?}",
+            res
+        );
+        // 32 * 2 / 3 = 21.33...
+        assert!(verify_trust_level_participation(&trust_level, 22, 32).is_ok());
+        assert!(verify_trust_level_participation(&trust_level, 21, 32).is_err());
+        assert!(verify_trust_level_participation(&trust_level, 0, 32).is_err());
+        assert_eq!(required_trust_level_participants(&trust_level, 512), 342);
+        assert_eq!(required_trust_level_participants(&trust_level, 32), 22);
+        assert_eq!(
+            required_trust_level_participants(&Fraction::new(1, 1).unwrap(), 32),
+            32
+        );
+        assert_eq!(
+            required_trust_level_participants(&Fraction::new(1, 32).unwrap(), 32),
+            1
+        );
+
+        assert!(is_valid_trust_level(&Fraction::new(1, 3).unwrap()));
+        assert!(is_valid_trust_level(&Fraction::new(2, 3).unwrap()));
+        assert!(is_valid_trust_level(&Fraction::new(1, 1).unwrap()));
+        assert!(!is_valid_trust_level(&Fraction::new(1, 4).unwrap()));
+    }
+
+    pub(crate) fn to_consensus_update_info<const SYNC_COMMITTEE_SIZE: usize>(
+        consensus_update: EthConsensusUpdateInfo<SYNC_COMMITTEE_SIZE>,
+    ) -> ConsensusUpdateInfo<SYNC_COMMITTEE_SIZE> {
+        ConsensusUpdateInfo {
+            attested_header: consensus_update.light_client_update.attested_header,
+            next_sync_committee: consensus_update.light_client_update.next_sync_committee,
+            finalized_header: consensus_update.light_client_update.finalized_header,
+            sync_aggregate: consensus_update.light_client_update.sync_aggregate,
+            signature_slot: consensus_update.light_client_update.signature_slot,
+            finalized_execution_root: consensus_update.finalized_execution_root,
+            finalized_execution_branch: consensus_update.finalized_execution_branch,
+        }
+    }
+
+    pub(crate) fn new_client_state(
+    ) -> ClientState<{ ethereum_consensus::preset::minimal::PRESET.SYNC_COMMITTEE_SIZE }> {
+        ClientState {
+            genesis_validators_root: keccak256("genesis_validators_root"),
+            min_sync_committee_participants: 1.into(),
+            genesis_time: 1.into(),
+            fork_parameters: ForkParameters::new(
+                Version([0, 0, 0, 1]),
+                vec![
+                    ForkParameter::new(Version([1, 0, 0, 1]), U64(0), ALTAIR_FORK_SPEC),
+                    ForkParameter::new(Version([2, 0, 0, 1]), U64(0), BELLATRIX_FORK_SPEC),
+                    ForkParameter::new(Version([3, 0, 0, 1]), U64(0), CAPELLA_FORK_SPEC),
+                    ForkParameter::new(Version([4, 0, 0, 1]), U64(0), DENEB_FORK_SPEC),
+                ],
+            )
+            .unwrap(),
+            seconds_per_slot: PRESET.SECONDS_PER_SLOT,
+            slots_per_epoch: PRESET.SLOTS_PER_EPOCH,
+            epochs_per_sync_committee_period: PRESET.EPOCHS_PER_SYNC_COMMITTEE_PERIOD,
+            ibc_address: Address(hex!("ff77D90D6aA12db33d3Ba50A34fB25401f6e4c4F")),
+            ibc_commitments_slot: keccak256("ibc_commitments_slot"),
+            commitment_value_codec: CommitmentValueCodec::Keccak256,
+            commitment_prefix: Default::default(),
+            trust_level: Fraction::new(2, 3).unwrap(),
+            trusting_period: Duration::from_secs(60 * 60 * 27),
+            max_clock_drift: Duration::from_secs(60),
+            max_intermediate_consensus_updates: U64(0),
+            latest_execution_block_number: 1.into(),
+            frozen_height: None,
+            misbehaviour_evidence_digest: None,
+            consensus_verifier: Default::default(),
+            execution_verifier: Default::default(),
+        }
     }
 
     fn keccak256(s: &str) -> H256 {
@@ -1,45 +1,2126 @@
+use crate::address::Address;
 use crate::errors::Error;
+use crate::evm_layout::mapping_slot;
 use crate::internal_prelude::*;
+use alloc::collections::{BTreeMap, BTreeSet};
 use ethereum_consensus::types::H256;
+use ethereum_ibc_proto::ibc::lightclients::ethereum::v1::CommitmentValueCodec as RawCommitmentValueCodec;
+use ibc::core::ics23_commitment::commitment::{
+    CommitmentPrefix, CommitmentProofBytes, CommitmentRoot,
+};
 use ibc::core::ics24_host::Path;
 use rlp::Rlp;
+use serde::{Deserialize, Serialize};
 use tiny_keccak::{Hasher, Keccak};
 
-/// Calculate the storage location for the commitment stored in the IBC contract
+/// The root of an empty trie, i.e. `keccak256(rlp(""))`
+pub const EMPTY_TRIE_ROOT: H256 = H256([
+    0x56, 0xe8, 0x1f, 0x17, 0x1b, 0xcc, 0x55, 0xa6, 0xff, 0x83, 0x45, 0xe6, 0x92, 0xc0, 0xf8, 0x6e,
+    0x5b, 0x48, 0xe0, 0x1b, 0x99, 0x6c, 0xad, 0xc0, 0x01, 0x62, 0x2f, 0xb5, 0xe3, 0x63, 0xb4, 0x21,
+]);
+
+/// The commitment prefix of ibc-solidity, which is returned by `getCommitmentPrefix()` of the IBC contract
+///
+/// ibc-solidity does not namespace the paths with the prefix, i.e. the prefix is not part of the storage key of a commitment,
+/// so the prefix is only checked to be the expected one to detect a misconfigured counterparty.
+pub const IBC_SOLIDITY_COMMITMENT_PREFIX: &[u8] = b"ibc";
+
+/// The commitment prefix that the counterparty must use for the client, see `ClientState::commitment_prefix`
+///
+/// It is never empty and defaults to `IBC_SOLIDITY_COMMITMENT_PREFIX`. Human-readable formats represent it as a 0x-prefixed hex string.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExpectedCommitmentPrefix(Vec<u8>);
+
+impl ExpectedCommitmentPrefix {
+    /// Returns the prefix of `bytes`, which must not be empty
+    pub fn new(bytes: Vec<u8>) -> Result<Self, Error> {
+        if bytes.is_empty() {
+            Err(Error::UninitializedClientStateField("commitment_prefix"))
+        } else {
+            Ok(Self(bytes))
+        }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Returns true if this is `IBC_SOLIDITY_COMMITMENT_PREFIX`, which is encoded as empty bytes in the proto client state
+    pub fn is_default(&self) -> bool {
+        self.0 == IBC_SOLIDITY_COMMITMENT_PREFIX
+    }
+
+    /// Verify that `prefix` is this prefix
+    pub fn verify(&self, prefix: &CommitmentPrefix) -> Result<(), Error> {
+        if prefix.as_bytes() == self.as_bytes() {
+            Ok(())
+        } else {
+            Err(Error::UnexpectedCommitmentPrefix {
+                expected: self.0.clone(),
+                got: prefix.as_bytes().to_vec(),
+            })
+        }
+    }
+}
+
+impl Default for ExpectedCommitmentPrefix {
+    fn default() -> Self {
+        Self(IBC_SOLIDITY_COMMITMENT_PREFIX.to_vec())
+    }
+}
+
+impl Serialize for ExpectedCommitmentPrefix {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&format!("0x{}", hex::encode(&self.0)))
+        } else {
+            self.0.serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ExpectedCommitmentPrefix {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error as _;
+        let bytes = if deserializer.is_human_readable() {
+            let s = String::deserialize(deserializer)?;
+            let s = s
+                .strip_prefix("0x")
+                .ok_or_else(|| D::Error::custom("the commitment prefix must be 0x-prefixed hex"))?;
+            hex::decode(s).map_err(D::Error::custom)?
+        } else {
+            Vec::<u8>::deserialize(deserializer)?
+        };
+        Self::new(bytes).map_err(D::Error::custom)
+    }
+}
+
+/// How the storage value proven for a path is compared with the expected commitment value
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum CommitmentValueCodec {
+    /// The storage value is `keccak256(value)`, as ibc-solidity stores the commitments
+    #[default]
+    Keccak256,
+    /// The storage value is `value` itself as a 32-byte word
+    ///
+    /// Following the EVM storage semantics, a value shorter than 32 bytes is left-padded with zeros, i.e. it is a big-endian word,
+    /// and a value longer than 32 bytes is rejected. Note that a zero word cannot be proven to be stored since the EVM deletes zero-valued slots.
+    Raw,
+}
+
+impl CommitmentValueCodec {
+    /// Returns the storage value expected to be stored for `value` at `path`
+    pub fn encode(&self, path: &Path, value: &[u8]) -> Result<H256, Error> {
+        match self {
+            Self::Keccak256 => Ok(H256(keccak_256(value))),
+            Self::Raw if value.len() > 32 => Err(Error::InvalidRawCommitmentValueLength {
+                path: path.to_string(),
+                length: value.len(),
+            }),
+            Self::Raw => {
+                let mut word = [0u8; 32];
+                word[32 - value.len()..].copy_from_slice(value);
+                Ok(H256(word))
+            }
+        }
+    }
+}
+
+impl TryFrom<i32> for CommitmentValueCodec {
+    type Error = Error;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match RawCommitmentValueCodec::from_i32(value) {
+            Some(RawCommitmentValueCodec::Keccak256) => Ok(Self::Keccak256),
+            Some(RawCommitmentValueCodec::Raw) => Ok(Self::Raw),
+            None => Err(Error::UnknownCommitmentValueCodec(value)),
+        }
+    }
+}
+
+impl From<CommitmentValueCodec> for i32 {
+    fn from(value: CommitmentValueCodec) -> Self {
+        match value {
+            CommitmentValueCodec::Keccak256 => RawCommitmentValueCodec::Keccak256 as i32,
+            CommitmentValueCodec::Raw => RawCommitmentValueCodec::Raw as i32,
+        }
+    }
+}
+
+/// An account in the state trie of the execution layer
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Account {
+    /// The number of transactions sent from the account
+    pub nonce: u64,
+    /// The balance of the account in wei as a big-endian 256-bit integer
+    pub balance: H256,
+    /// The root of the storage trie of the account
+    pub storage_root: H256,
+    /// The hash of the code of the account
+    pub code_hash: H256,
+}
+
+/// Verify the EIP-1186 account proof of `address` against `state_root` and return the account
+///
+/// `proof` is the `accountProof` of `eth_getProof`. Returns `None` if `proof` shows that the account does not exist.
+pub fn verify_account(
+    state_root: H256,
+    address: &Address,
+    proof: &[Vec<u8>],
+) -> Result<Option<Account>, Error> {
+    let malformed = |description: String| Error::MalformedAccountProof {
+        address: address.to_string(),
+        description,
+    };
+    let mut nodes = ProofNodes(proof.iter());
+    let value = walk_trie(state_root, &address.trie_key().0, &mut nodes).map_err(|err| {
+        Error::AccountProofWalkError {
+            address: address.to_string(),
+            err,
+        }
+    })?;
+    if nodes.0.next().is_some() {
+        return Err(malformed(
+            "proof contains nodes that are not on the path".into(),
+        ));
+    }
+    let value = match value {
+        Some(value) => value,
+        None => return Ok(None),
+    };
+    let account = Rlp::new(value);
+    if account.item_count().map_err(|e| malformed(e.to_string()))? != 4
+        || account
+            .payload_info()
+            .map_err(|e| malformed(e.to_string()))?
+            .total()
+            != value.len()
+    {
+        return Err(malformed(
+            "account must be a rlp list of nonce, balance, storage root and code hash".into(),
+        ));
+    }
+    let word = |i: usize, name: &str, exact: bool| -> Result<H256, Error> {
+        let bz = account
+            .at(i)
+            .and_then(|item| item.data())
+            .map_err(|e| malformed(format!("{}: {}", name, e)))?;
+        if bz.len() > 32 || (exact && bz.len() != 32) {
+            return Err(malformed(format!(
+                "{} has an invalid length: length={}",
+                name,
+                bz.len()
+            )));
+        }
+        let mut word = [0u8; 32];
+        word[32 - bz.len()..].copy_from_slice(bz);
+        Ok(H256(word))
+    };
+    Ok(Some(Account {
+        nonce: account
+            .val_at(0)
+            .map_err(|e| malformed(format!("nonce: {}", e)))?,
+        balance: word(1, "balance", false)?,
+        storage_root: word(2, "storage root", true)?,
+        code_hash: word(3, "code hash", true)?,
+    }))
+}
+
+/// Verify that the storage root of the account of `address` in the state trie of `state_root` is `expected_storage_root`
+///
+/// `proof` is the `accountProof` of `eth_getProof`. If `proof` shows that the account does not exist, `expected_storage_root` must be zero.
+/// The client verifies `AccountUpdateInfo` of headers with this function, so relayers can check their proofs before submitting them.
+pub fn verify_account_storage_root(
+    state_root: H256,
+    address: &Address,
+    proof: &[Vec<u8>],
+    expected_storage_root: H256,
+) -> Result<(), Error> {
+    let storage_root = match verify_account(state_root, address, proof)? {
+        Some(account) => account.storage_root,
+        None => H256::default(),
+    };
+    if storage_root == expected_storage_root {
+        Ok(())
+    } else {
+        Err(Error::AccountStorageRootMismatch(
+            expected_storage_root,
+            storage_root,
+            state_root,
+            address.to_string(),
+            proof.iter().map(hex::encode).collect(),
+        ))
+    }
+}
+
+/// Calculate the storage key of the commitment for `path` in the IBC contract
+///
+/// The IBC contract stores the commitments in a `mapping(bytes32 => bytes32)` at `commitments_slot` keyed by `keccak256(path)`,
+/// so following the storage layout of Solidity mappings (see `evm_layout::mapping_slot`) the key is `keccak256(keccak256(path) ++ commitments_slot)`,
+/// where `path` is the UTF-8 bytes of the ICS-24 path and `++` is the concatenation of the two 32-byte words.
+/// Relayers should pass this key to `eth_getProof` to get the storage proof verified by the client.
 ///
 /// The spec is here: https://github.com/hyperledger-labs/yui-ibc-solidity/blob/0e83dc7aadf71380dae6e346492e148685510663/docs/architecture.md#L46
+pub fn calculate_ibc_commitment_storage_key(commitments_slot: &H256, path: &str) -> H256 {
+    mapping_slot(&keccak_256(path.as_bytes()), commitments_slot)
+}
+
+/// Calculate the storage location for the commitment stored in the IBC contract
+///
+/// This is `calculate_ibc_commitment_storage_key` for an ICS-24 path.
 pub fn calculate_ibc_commitment_storage_location(ibc_commitments_slot: &H256, path: Path) -> H256 {
-    keccak_256(
-        &[
-            &keccak_256(&path.into_bytes()),
-            ibc_commitments_slot.as_bytes(),
-        ]
-        .concat(),
-    )
-    .into()
+    calculate_ibc_commitment_storage_key(ibc_commitments_slot, &path.to_string())
 }
 
-/// decode rlp format `List<List>` to `Vec<List>`
-pub fn decode_eip1184_rlp_proof(proof: Vec<u8>) -> Result<Vec<Vec<u8>>, Error> {
-    let r = Rlp::new(&proof);
-    if r.is_list() {
-        Ok(r.into_iter()
-            .map(|r| {
-                let proof: Vec<Vec<u8>> = r.as_list().unwrap();
-                rlp::encode_list::<Vec<u8>, Vec<u8>>(&proof).into()
+/// Verify that the commitment of `value` is stored at `path` in the storage of the IBC contract
+///
+/// `root` is the storage root of the IBC contract and `proof` is the EIP-1186 storage proof of the storage location derived from `path` and `ibc_commitments_slot`.
+/// `codec` determines the commitment of `value`, e.g. ibc-solidity stores `keccak256(value)`, and the stored value is the commitment without leading zero bytes.
+pub fn verify_membership(
+    root: &CommitmentRoot,
+    ibc_commitments_slot: &H256,
+    codec: CommitmentValueCodec,
+    proof: &CommitmentProofBytes,
+    path: Path,
+    value: &[u8],
+) -> Result<(), Error> {
+    let expected = codec.encode(&path, value)?;
+    let got = verify_storage_proof(root, ibc_commitments_slot, proof, &path)?;
+    check_commitment(ibc_commitments_slot, &path, expected, got)
+}
+
+/// Verify that no commitment is stored at `path` in the storage of the IBC contract
+///
+/// See `verify_membership` for the arguments. `proof` must be an exclusion proof; a proof that shows a non-zero value at the
+/// storage location is rejected with `Error::UnexpectedCommitment`.
+pub fn verify_non_membership(
+    root: &CommitmentRoot,
+    ibc_commitments_slot: &H256,
+    proof: &CommitmentProofBytes,
+    path: Path,
+) -> Result<(), Error> {
+    match verify_storage_proof(root, ibc_commitments_slot, proof, &path)? {
+        Some(got) => Err(Error::UnexpectedCommitment {
+            path: path.to_string(),
+            got,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Verify that the commitment of each `(path, value)` of `items` is stored in the storage of the IBC contract
+///
+/// `proof` is the RLP list of the trie nodes needed to walk from `root` to every storage location of `items`, in any order.
+/// This is the union of the EIP-1186 storage proofs of the items with each node included only once,
+/// so the nodes shared by the proofs, e.g. the root node, are sent and decoded once.
+/// Every node of `proof` must be used by at least one item.
+/// If an item fails, `Error::BatchMembershipVerificationError` reports the index of the first failing item.
+pub fn verify_membership_batch(
+    root: &CommitmentRoot,
+    ibc_commitments_slot: &H256,
+    codec: CommitmentValueCodec,
+    proof: &CommitmentProofBytes,
+    items: &[(Path, Vec<u8>)],
+) -> Result<(), Error> {
+    let proof: Vec<u8> = proof.clone().into();
+    let proof = decode_proof_nodes(&proof)?;
+    let mut nodes = NodeSet::new(&proof)?;
+    for (index, (path, value)) in items.iter().enumerate() {
+        codec
+            .encode(path, value)
+            .and_then(|expected| {
+                let got = lookup_commitment(root, ibc_commitments_slot, &mut nodes, path)?;
+                check_commitment(ibc_commitments_slot, path, expected, got)
             })
-            .collect())
+            .map_err(|err| Error::BatchMembershipVerificationError {
+                index,
+                err: Box::new(err),
+            })?;
+    }
+    if nodes.used.len() != nodes.nodes.len() {
+        return Err(Error::InvalidProofFormatError(
+            "batch proof contains nodes that are not used by any item".into(),
+        ));
+    }
+    Ok(())
+}
+
+fn check_commitment(
+    ibc_commitments_slot: &H256,
+    path: &Path,
+    expected: H256,
+    got: Option<H256>,
+) -> Result<(), Error> {
+    match got {
+        Some(got) if got == expected => Ok(()),
+        Some(got) => Err(Error::CommitmentValueMismatch {
+            path: path.to_string(),
+            expected,
+            got,
+        }),
+        None => Err(Error::CommitmentNotFound {
+            path: path.to_string(),
+            key: calculate_ibc_commitment_storage_key(ibc_commitments_slot, &path.to_string()),
+        }),
+    }
+}
+
+/// Returns the commitment stored at `path` or `None` if the storage location is empty
+fn verify_storage_proof(
+    root: &CommitmentRoot,
+    ibc_commitments_slot: &H256,
+    proof: &CommitmentProofBytes,
+    path: &Path,
+) -> Result<Option<H256>, Error> {
+    let proof: Vec<u8> = proof.clone().into();
+    let proof = decode_proof_nodes(&proof).map_err(|e| Error::MalformedCommitmentProof {
+        path: path.to_string(),
+        description: e.to_string(),
+    })?;
+    let mut nodes = ProofNodes(proof.iter());
+    let commitment = lookup_commitment(root, ibc_commitments_slot, &mut nodes, path)?;
+    if nodes.0.next().is_some() {
+        return Err(Error::MalformedCommitmentProof {
+            path: path.to_string(),
+            description: "proof contains nodes that are not on the path".into(),
+        });
+    }
+    Ok(commitment)
+}
+
+/// Returns the commitment stored at `path` in the trie of `nodes`, or `None` if the storage location is empty
+///
+/// A storage location holding zero is considered empty since the EVM deletes zero-valued slots from the trie.
+fn lookup_commitment<'a, N: TrieNodes<'a>>(
+    root: &CommitmentRoot,
+    ibc_commitments_slot: &H256,
+    nodes: &mut N,
+    path: &Path,
+) -> Result<Option<H256>, Error> {
+    let malformed = |description: String| Error::MalformedCommitmentProof {
+        path: path.to_string(),
+        description,
+    };
+    let root = root.as_bytes();
+    if root.len() != 32 {
+        return Err(Error::InvalidStorageRootLength(root.len()));
+    }
+    let root = H256::from_slice(root);
+    // if root is zero, the IBC contract has not been initialized yet
+    if root.is_zero() {
+        return Err(Error::ZeroStorageRoot {
+            path: path.to_string(),
+        });
+    }
+    let key = calculate_ibc_commitment_storage_key(ibc_commitments_slot, &path.to_string());
+    let value = match lookup_storage_value(root, &key, nodes).map_err(|e| match e {
+        StorageLookupError::Walk(err) => Error::StorageProofWalkError {
+            path: path.to_string(),
+            key,
+            err,
+        },
+        StorageLookupError::Decode(e) => malformed(e.to_string()),
+    })? {
+        Some(value) => value,
+        None => return Ok(None),
+    };
+    if value.len() > 32 || trim_left_zero(&value).len() != value.len() {
+        return Err(malformed(format!(
+            "stored value must be a 32-byte word without leading zero bytes: value=0x{}",
+            hex::encode(&value)
+        )));
+    }
+    let mut commitment = [0u8; 32];
+    commitment[32 - value.len()..].copy_from_slice(&value);
+    Ok(Some(H256(commitment)))
+}
+
+/// Extract the value stored at `key` from the EIP-1186 storage proof against `storage_root`
+///
+/// `key` is the storage key passed to `eth_getProof`, e.g. the result of `calculate_ibc_commitment_storage_key`, and `proof` is its `proof`.
+/// Returns the rlp-decoded stored value, i.e. the big-endian word without leading zero bytes, or `None` if `proof` shows that `key` is absent.
+/// Unlike `verify_membership`, the value is returned as is without checking it against a commitment, so tools can inspect what a proof shows.
+pub fn extract_storage_value_from_proof(
+    storage_root: H256,
+    key: H256,
+    proof: &[Vec<u8>],
+) -> Result<Option<Vec<u8>>, Error> {
+    let malformed = |description: String| Error::MalformedStorageValueProof { key, description };
+    let mut nodes = ProofNodes(proof.iter());
+    let value = lookup_storage_value(storage_root, &key, &mut nodes).map_err(|e| match e {
+        StorageLookupError::Walk(err) => Error::StorageValueProofWalkError { key, err },
+        StorageLookupError::Decode(e) => malformed(e.to_string()),
+    })?;
+    if nodes.0.next().is_some() {
+        return Err(malformed(
+            "proof contains nodes that are not on the path".into(),
+        ));
+    }
+    Ok(value)
+}
+
+/// The reason why the value stored at a key cannot be looked up
+enum StorageLookupError {
+    /// The walk from the storage root failed
+    Walk(TrieWalkError),
+    /// The stored value is not a rlp byte string
+    Decode(rlp::DecoderError),
+}
+
+/// Returns the rlp-decoded value stored at `key` in the storage trie of `root`, or `None` if it is absent or empty
+fn lookup_storage_value<'a, N: TrieNodes<'a>>(
+    root: H256,
+    key: &H256,
+    nodes: &mut N,
+) -> Result<Option<Vec<u8>>, StorageLookupError> {
+    let value = match walk_trie(root, &keccak_256(key.as_bytes()), nodes)
+        .map_err(StorageLookupError::Walk)?
+    {
+        Some(value) => value,
+        None => return Ok(None),
+    };
+    let value: Vec<u8> = rlp::decode(value).map_err(StorageLookupError::Decode)?;
+    if value.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(value))
+}
+
+/// The context of a merkle patricia trie walk at the node where it failed
+#[derive(Clone, Debug)]
+pub struct TrieWalkError {
+    /// The root the walk started from
+    pub root: H256,
+    /// The index of the failed node among the nodes referenced by hash on the walk, counting the root node as 0
+    ///
+    /// For a single EIP-1186 proof, this is the index of the node in the proof.
+    pub node_index: usize,
+    /// The hash of the node at `node_index` referenced by its parent, or `root` for the root node
+    pub expected_hash: H256,
+    /// The hash of the provided node at `node_index`, or `None` if the proof does not contain it
+    pub got_hash: Option<H256>,
+    /// The number of nibbles of the trie key consumed before reaching the failed node
+    pub consumed_nibbles: usize,
+    /// The reason of the failure
+    pub description: String,
+}
+
+impl core::fmt::Display for TrieWalkError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{}: root={} node_index={} consumed_nibbles={} expected_node_hash={} got_node_hash=",
+            self.description, self.root, self.node_index, self.consumed_nibbles, self.expected_hash
+        )?;
+        match self.got_hash {
+            Some(got) => write!(f, "{}", got),
+            None => write!(f, "none"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TrieWalkError {}
+
+/// The reason why the node referenced by a hash cannot be resolved
+enum TrieNodeError {
+    /// The proof does not contain the node
+    Missing,
+    /// The provided node has a different hash
+    HashMismatch(H256),
+}
+
+/// The trie nodes referenced by hash while walking the trie
+trait TrieNodes<'a> {
+    /// Returns the rlp-encoded node whose hash is `hash`
+    fn get(&mut self, hash: &H256) -> Result<&'a [u8], TrieNodeError>;
+}
+
+/// The nodes of a single proof, which must be consumed in order from the root
+struct ProofNodes<'a, T>(core::slice::Iter<'a, T>);
+
+impl<'a, T: AsRef<[u8]>> TrieNodes<'a> for ProofNodes<'a, T> {
+    fn get(&mut self, hash: &H256) -> Result<&'a [u8], TrieNodeError> {
+        let node = self.0.next().ok_or(TrieNodeError::Missing)?.as_ref();
+        let got = H256(keccak_256(node));
+        if &got == hash {
+            Ok(node)
+        } else {
+            Err(TrieNodeError::HashMismatch(got))
+        }
+    }
+}
+
+/// The nodes shared by multiple proofs, which can be looked up in any order
+struct NodeSet<'a> {
+    nodes: BTreeMap<[u8; 32], &'a [u8]>,
+    used: BTreeSet<[u8; 32]>,
+}
+
+impl<'a> NodeSet<'a> {
+    fn new<T: AsRef<[u8]>>(nodes: &'a [T]) -> Result<Self, Error> {
+        let mut set = BTreeMap::new();
+        for node in nodes {
+            let node = node.as_ref();
+            if set.insert(keccak_256(node), node).is_some() {
+                return Err(Error::InvalidProofFormatError(
+                    "batch proof contains duplicate nodes".into(),
+                ));
+            }
+        }
+        Ok(Self {
+            nodes: set,
+            used: BTreeSet::new(),
+        })
+    }
+}
+
+impl<'a> TrieNodes<'a> for NodeSet<'a> {
+    fn get(&mut self, hash: &H256) -> Result<&'a [u8], TrieNodeError> {
+        let node = self
+            .nodes
+            .get(&hash.0)
+            .copied()
+            .ok_or(TrieNodeError::Missing)?;
+        self.used.insert(hash.0);
+        Ok(node)
+    }
+}
+
+/// Walk the merkle patricia trie from `root` along `key`, resolving the referenced nodes from `nodes`
+///
+/// Returns the value stored at `key`, or `None` if `key` is absent, i.e. the walk terminates at
+/// an empty trie, at a branch with an empty child slot, or at a leaf or extension whose path diverges from `key`.
+/// On failure, the returned error reports the node where the walk failed and how far the walk got along `key`.
+/// The returned value borrows from the nodes, which are walked in place without copying.
+fn walk_trie<'a, N: TrieNodes<'a>>(
+    root: H256,
+    key: &[u8; 32],
+    nodes: &mut N,
+) -> Result<Option<&'a [u8]>, TrieWalkError> {
+    if root == EMPTY_TRIE_ROOT {
+        return Ok(None);
+    }
+    let mut key_nibbles = [0u8; 64];
+    for (i, b) in key.iter().enumerate() {
+        key_nibbles[2 * i] = b >> 4;
+        key_nibbles[2 * i + 1] = b & 0x0f;
+    }
+    let mut node_index = 0;
+    let mut consumed_nibbles = 0;
+    let mut hash = root;
+    loop {
+        let fail =
+            |got_hash: Option<H256>, consumed_nibbles: usize, description: String| TrieWalkError {
+                root,
+                node_index,
+                expected_hash: hash,
+                got_hash,
+                consumed_nibbles,
+                description,
+            };
+        let mut node = Rlp::new(nodes.get(&hash).map_err(|e| match e {
+            TrieNodeError::Missing => {
+                fail(None, consumed_nibbles, "proof is missing a node".into())
+            }
+            TrieNodeError::HashMismatch(got) => {
+                fail(Some(got), consumed_nibbles, "node hash mismatch".into())
+            }
+        })?);
+        // walk the node and the nodes embedded in it
+        let next = loop {
+            let (step, n) = step_trie_node(&node, &key_nibbles[consumed_nibbles..])
+                .map_err(|e| fail(Some(hash), consumed_nibbles, e.to_string()))?;
+            consumed_nibbles += n;
+            match step {
+                TrieStep::Value(value) if value.is_empty() => return Ok(None),
+                TrieStep::Value(value) => return Ok(Some(value)),
+                TrieStep::Absent => return Ok(None),
+                TrieStep::Embedded(child) => node = child,
+                TrieStep::Hash(child) => break child,
+            }
+        };
+        node_index += 1;
+        hash = next;
+    }
+}
+
+/// The result of walking a single node
+enum TrieStep<'a> {
+    /// The value stored at the key
+    Value(&'a [u8]),
+    /// The key is absent from the trie
+    Absent,
+    /// The next node, which is smaller than 32 bytes and embedded in its parent instead of being referenced by hash
+    Embedded(Rlp<'a>),
+    /// The hash of the next node
+    Hash(H256),
+}
+
+/// Walk `node` along `nibbles`, returning the next step and the number of nibbles consumed by `node`
+fn step_trie_node<'a>(
+    node: &Rlp<'a>,
+    nibbles: &[u8],
+) -> Result<(TrieStep<'a>, usize), rlp::DecoderError> {
+    use rlp::DecoderError::Custom;
+
+    let (child, consumed) = match node.item_count()? {
+        17 => match nibbles.first() {
+            Some(nibble) => (node.at(*nibble as usize)?, 1),
+            None => return Ok((TrieStep::Value(node.at(16)?.data()?), 0)),
+        },
+        2 => {
+            let (path, is_leaf) = decode_hex_prefix(node.at(0)?.data()?)?;
+            if !path.is_prefix_of(nibbles) || (is_leaf && nibbles.len() != path.len()) {
+                // the path diverges from the key
+                return Ok((TrieStep::Absent, 0));
+            }
+            if is_leaf {
+                return Ok((TrieStep::Value(node.at(1)?.data()?), path.len()));
+            }
+            (node.at(1)?, path.len())
+        }
+        _ => return Err(Custom("node must be a branch, an extension or a leaf")),
+    };
+    let step = if child.is_list() {
+        TrieStep::Embedded(child)
     } else {
-        Err(Error::InvalidProofFormatError(
+        let bz = child.data()?;
+        match bz.len() {
+            // the branch has an empty child slot
+            0 => TrieStep::Absent,
+            32 => TrieStep::Hash(H256::from_slice(bz)),
+            // some encoders put the rlp of an embedded node into a byte string instead of inlining it
+            len if len < 32 => {
+                let node = Rlp::new(bz);
+                if !node.is_list() || node.payload_info()?.total() != len {
+                    return Err(Custom("embedded node must be a rlp list"));
+                }
+                TrieStep::Embedded(node)
+            }
+            _ => return Err(Custom("child reference must be a 32-byte hash")),
+        }
+    };
+    Ok((step, consumed))
+}
+
+/// The nibbles of a hex-prefix encoded path, read from the encoded bytes without expanding them
+struct HexPrefixPath<'a> {
+    /// The encoded path including the flag nibble
+    bz: &'a [u8],
+    /// The number of nibbles to skip at the start of `bz`, i.e. 1 for the flag nibble of an odd-length path and 2 otherwise
+    offset: usize,
+}
+
+impl HexPrefixPath<'_> {
+    fn len(&self) -> usize {
+        self.bz.len() * 2 - self.offset
+    }
+
+    fn nibble(&self, i: usize) -> u8 {
+        let i = i + self.offset;
+        let b = self.bz[i / 2];
+        if i % 2 == 0 {
+            b >> 4
+        } else {
+            b & 0x0f
+        }
+    }
+
+    fn is_prefix_of(&self, nibbles: &[u8]) -> bool {
+        self.len() <= nibbles.len() && (0..self.len()).all(|i| self.nibble(i) == nibbles[i])
+    }
+}
+
+/// Decode the hex-prefix encoded path of a leaf or extension node into its nibbles and whether the node is a leaf
+fn decode_hex_prefix(bz: &[u8]) -> Result<(HexPrefixPath<'_>, bool), rlp::DecoderError> {
+    let first = bz
+        .first()
+        .ok_or(rlp::DecoderError::Custom("empty hex-prefix path"))?;
+    let flag = first >> 4;
+    if flag > 3 {
+        return Err(rlp::DecoderError::Custom("invalid hex-prefix flag"));
+    }
+    let offset = if flag & 1 == 1 { 1 } else { 2 };
+    Ok((HexPrefixPath { bz, offset }, flag & 2 == 2))
+}
+
+/// The maximum length of an rlp-encoded proof accepted by `decode_eip1186_rlp_proof`
+pub const MAX_PROOF_SIZE: usize = 16 * 1024;
+
+/// The maximum number of nodes in a proof accepted by `decode_eip1186_rlp_proof`
+///
+/// The tries are balanced by the hashed keys, so the proofs of the state trie and the storage tries have around 10 nodes.
+pub const MAX_PROOF_NODES: usize = 64;
+
+/// The maximum length of a rlp-encoded node in a proof accepted by `decode_eip1186_rlp_proof`
+///
+/// The largest node is a branch node with 16 hashes, which is 532 bytes.
+pub const MAX_PROOF_NODE_SIZE: usize = 1024;
+
+/// Decode an EIP-1186 proof into the rlp-encoded trie nodes
+///
+/// Two encodings of the outer rlp list are accepted:
+/// - a list of the nodes themselves, i.e. `List<List>`
+/// - a list of byte strings, each of which is an rlp-encoded node
+///
+/// All nodes must use the same encoding, and each node must be exactly one rlp list.
+/// The proof is rejected with `Error::DecodeLimitExceeded` before the nodes are decoded if it exceeds `MAX_PROOF_SIZE` or `MAX_PROOF_NODES`,
+/// or if a node exceeds `MAX_PROOF_NODE_SIZE`.
+pub fn decode_eip1186_rlp_proof(proof: Vec<u8>) -> Result<Vec<Vec<u8>>, Error> {
+    Ok(decode_proof_nodes(&proof)?
+        .into_iter()
+        .map(|node| node.to_vec())
+        .collect())
+}
+
+/// `decode_eip1186_rlp_proof` returning the nodes borrowed from `proof`
+fn decode_proof_nodes(proof: &[u8]) -> Result<Vec<&[u8]>, Error> {
+    let invalid = Error::RlpDecodeError;
+    check_limit("MAX_PROOF_SIZE", MAX_PROOF_SIZE, proof.len())?;
+    let r = Rlp::new(proof);
+    if !r.is_list() {
+        return Err(Error::InvalidProofFormatError(
             "proof must be rlp list".into(),
-        ))
+        ));
+    }
+    // the proof must be exactly one rlp list
+    if r.payload_info().map_err(invalid)?.total() != proof.len() {
+        return Err(Error::InvalidProofFormatError(
+            "proof must not contain trailing bytes".into(),
+        ));
+    }
+    let item_count = r.item_count().map_err(invalid)?;
+    check_limit("MAX_PROOF_NODES", MAX_PROOF_NODES, item_count)?;
+    let mut nested = None;
+    (0..item_count)
+        .map(|i| {
+            let item = r.at(i).map_err(invalid)?;
+            let is_nested = item.is_data();
+            if *nested.get_or_insert(is_nested) != is_nested {
+                return Err(Error::InvalidProofFormatError(
+                    "proof nodes must not mix rlp lists and byte strings".into(),
+                ));
+            }
+            let node = if is_nested {
+                let bz = item.data().map_err(invalid)?;
+                let node = Rlp::new(bz);
+                if !node.is_list() || node.payload_info().map_err(invalid)?.total() != bz.len() {
+                    return Err(Error::InvalidProofFormatError(format!(
+                        "proof node must be a rlp-encoded list: index={}",
+                        i
+                    )));
+                }
+                bz
+            } else if item.is_list() {
+                item.as_raw()
+            } else {
+                return Err(Error::InvalidProofFormatError(
+                    "proof node must be rlp list".into(),
+                ));
+            };
+            check_limit("MAX_PROOF_NODE_SIZE", MAX_PROOF_NODE_SIZE, node.len())?;
+            Ok(node)
+        })
+        .collect()
+}
+
+/// Returns `Error::DecodeLimitExceeded` if `actual` exceeds `max` of `limit`
+pub(crate) fn check_limit(limit: &'static str, max: usize, actual: usize) -> Result<(), Error> {
+    if actual > max {
+        Err(Error::DecodeLimitExceeded { limit, max, actual })
+    } else {
+        Ok(())
+    }
+}
+
+/// decode rlp format `List<List>` to `Vec<List>`
+#[deprecated(note = "use `decode_eip1186_rlp_proof` instead")]
+pub fn decode_eip1184_rlp_proof(proof: Vec<u8>) -> Result<Vec<Vec<u8>>, Error> {
+    decode_eip1186_rlp_proof(proof)
+}
+
+fn trim_left_zero(value: &[u8]) -> &[u8] {
+    let mut pos = 0;
+    for v in value {
+        if *v != 0 {
+            break;
+        }
+        pos += 1;
     }
+    &value[pos..]
 }
 
-fn keccak_256(input: &[u8]) -> [u8; 32] {
+pub(crate) fn keccak_256(input: &[u8]) -> [u8; 32] {
     let mut out = [0u8; 32];
     let mut k = Keccak::v256();
     k.update(input);
     k.finalize(&mut out);
     out
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::str::FromStr;
+    use hex_literal::hex;
+    use ibc::core::ics02_client::error::ClientError;
+    use ibc::core::ics23_commitment::error::CommitmentError;
+
+    // A storage trie of the IBC contract with the commitments derived from `COMMITMENTS_SLOT` and the following paths:
+    // - packet commitment: `commitments/ports/transfer/channels/channel-0/sequences/1`
+    // - acknowledgement: `acks/ports/transfer/channels/channel-0/sequences/1` (the commitment has a leading zero byte)
+    // - connection state: `connections/connection-0`
+    const STORAGE_ROOT: [u8; 32] =
+        hex!("641c295339ba7d872b2f5c43e6fcab574e29a488b776cf1960931cee9afa3de9");
+    const COMMITMENTS_SLOT: [u8; 32] =
+        hex!("1ee222554989dda120e26ecacf756fe1235cd8d726706b57517715dde4f0c900");
+    const PACKET_COMMITMENT_PATH: &str =
+        "commitments/ports/transfer/channels/channel-0/sequences/1";
+    const PACKET_COMMITMENT: [u8; 32] =
+        hex!("8b88bb234ea8979220b694579cdb87636236809fed2a3e903e704ed09de7b0af");
+    const PACKET_COMMITMENT_PROOF: &[u8] = &hex!("f8ebf8518080a099fa7bf1f51f328439c3ad3e4d2dc8a9cc1fd54d4df16838ac431416bc78b5d980a03e4d2ff7e7cad67ad87e921188e0124f2101c24782030c7cc876734f6419e20c808080808080808080808080f851808080a0f75a02897e6d80308da3fb3d4e7ec5a25cdcc84d24b93f5cf85ab6ee30e9fe1580808080808080808080a0f78c262bf8184a538ede5fe47e9ce1d4e0f6aa52c36b74b05dd19eb98280ddd08080f843a020b3de8757b4bf718d39ca342c95e0d0aca0de8ca6e87556eb6536960a3526f4a1a0c405e4cc87f5538f551977b631a3a919239c09d332348da206ffd54697cff24d");
+    const ACK_PATH: &str = "acks/ports/transfer/channels/channel-0/sequences/1";
+    const ACK_COMMITMENT: [u8; 32] =
+        hex!("a45b1b0e2fe55b93432c8a4005f4a71345130b6aa7547b96bb38997eb24de136");
+    const ACK_PROOF: &[u8] = &hex!("f897f8518080a099fa7bf1f51f328439c3ad3e4d2dc8a9cc1fd54d4df16838ac431416bc78b5d980a03e4d2ff7e7cad67ad87e921188e0124f2101c24782030c7cc876734f6419e20c808080808080808080808080f842a0398e274c0e3a9f944959cf78218411db895873cf3d55641b6d4e84ce2879a5afa09fd1fae32e08a84559cd3ac36708019d83bad944117fd38595a47b75a690b3ba");
+    const CONNECTION_PATH: &str = "connections/connection-0";
+    const CONNECTION_END: &[u8] = &hex!("0a0f30372d74656e6465726d696e742d3012230a0131120d4f524445525f4f524445524544120f4f524445525f554e4f524445524544180122130a0f30372d74656e6465726d696e742d301a00");
+    const CONNECTION_PROOF: &[u8] = &hex!("f8ebf8518080a099fa7bf1f51f328439c3ad3e4d2dc8a9cc1fd54d4df16838ac431416bc78b5d980a03e4d2ff7e7cad67ad87e921188e0124f2101c24782030c7cc876734f6419e20c808080808080808080808080f851808080a0f75a02897e6d80308da3fb3d4e7ec5a25cdcc84d24b93f5cf85ab6ee30e9fe1580808080808080808080a0f78c262bf8184a538ede5fe47e9ce1d4e0f6aa52c36b74b05dd19eb98280ddd08080f843a020dc93aa2071d8fee619b0413af2f932685da696e8852d2c3c8dd087a6f0ffa6a1a039eec8d447861526f43d7309b0d243729a4e5fb977544759ab519fd591a778f6");
+    // the proof of absence of `receipts/ports/transfer/channels/channel-0/sequences/1`
+    const RECEIPT_PATH: &str = "receipts/ports/transfer/channels/channel-0/sequences/1";
+    const RECEIPT_ABSENCE_PROOF: &[u8] = &hex!("f897f8518080a099fa7bf1f51f328439c3ad3e4d2dc8a9cc1fd54d4df16838ac431416bc78b5d980a03e4d2ff7e7cad67ad87e921188e0124f2101c24782030c7cc876734f6419e20c808080808080808080808080f842a0398e274c0e3a9f944959cf78218411db895873cf3d55641b6d4e84ce2879a5afa09fd1fae32e08a84559cd3ac36708019d83bad944117fd38595a47b75a690b3ba");
+
+    // the proof of absence of `receipts/ports/transfer/channels/channel-0/sequences/2`, which terminates at an empty child slot of the root
+    const EMPTY_SLOT_RECEIPT_PATH: &str = "receipts/ports/transfer/channels/channel-0/sequences/2";
+    const EMPTY_SLOT_ABSENCE_PROOF: &[u8] = &hex!("f853f8518080a099fa7bf1f51f328439c3ad3e4d2dc8a9cc1fd54d4df16838ac431416bc78b5d980a03e4d2ff7e7cad67ad87e921188e0124f2101c24782030c7cc876734f6419e20c808080808080808080808080");
+
+    // A storage trie whose root is an extension node, containing the packet commitments of sequences 1 and 2
+    const EXTENSION_STORAGE_ROOT: [u8; 32] =
+        hex!("ffcd6022539ba7d67162a1f2bfe89f962844df2bfd226330e688a8d7d37592b9");
+    const EXTENSION_PACKET_COMMITMENT: [u8; 32] =
+        hex!("c48e8d2fd69d694f90962ad5c6da898c6e83f070c32804601d4c8b4b02854127");
+    const EXTENSION_PACKET_COMMITMENT_PROOF: &[u8] = &hex!("f8bbe214a0e53cb928fbc0dfcb02875a7d505655d4c1b6d12294fd128231405abffb0ebe2af85180808080808080808080a04fac64923510a4d41133ae4d2f7769959ecba9669aac34a5ce55410bf068bb61808080a0b360fd700c14137897afd900f37a0126da5f28b08d44344856dabc8dfc32e5438080f843a020b3de8757b4bf718d39ca342c95e0d0aca0de8ca6e87556eb6536960a3526f4a1a08e93f5580968577009d4b04441840a3ba1bf48b720239ec52a87197f4723cc4c");
+    // the proof of absence of `RECEIPT_PATH`, which terminates at the extension node
+    const EXTENSION_ABSENCE_PROOF: &[u8] =
+        &hex!("e3e214a0e53cb928fbc0dfcb02875a7d505655d4c1b6d12294fd128231405abffb0ebe2a");
+
+    // A storage trie with only the connection state of `CONNECTION_PATH`, so the root is a leaf node
+    const SINGLE_NODE_STORAGE_ROOT: [u8; 32] =
+        hex!("c8823c4ca2b297c0d52e8b5f5f28aebec20b26fc3a6434224aefc003852e94ca");
+    const SINGLE_NODE_PROOF: &[u8] = &hex!("f846f844a12043dc93aa2071d8fee619b0413af2f932685da696e8852d2c3c8dd087a6f0ffa6a1a039eec8d447861526f43d7309b0d243729a4e5fb977544759ab519fd591a778f6");
+
+    // A storage trie with zero stored at `RECEIPT_PATH`
+    const ZERO_VALUE_STORAGE_ROOT: [u8; 32] =
+        hex!("83bf0965a6b340988ffda218cf370ecfa91aa08f6f43cdc6d624447e71b38888");
+    const ZERO_VALUE_PROOF: &[u8] =
+        &hex!("e5e4a12025349d518babd687f049f03a05ddad0221d0bed523876210c9fccc462deef50b8180");
+
+    // A storage trie with the raw packet receipt `0x01` stored at `RECEIPT_PATH`, as ibc-solidity stores the receipts
+    const RAW_RECEIPT_STORAGE_ROOT: [u8; 32] =
+        hex!("3df95467eea917c96b80aa2f1c4601fd9581444c3cdf05e7e2ff7b6e6e2640c1");
+    const RAW_RECEIPT_PROOF: &[u8] =
+        &hex!("e4e3a12025349d518babd687f049f03a05ddad0221d0bed523876210c9fccc462deef50b01");
+
+    // A storage trie with the raw packet receipts of `EMBEDDED_RECEIPT_PATH_1` and `EMBEDDED_RECEIPT_PATH_2`, whose storage keys share the first 8 nibbles,
+    // so the root is an extension node to a branch node with the two leaf nodes embedded since each of them is shorter than 32 bytes
+    const EMBEDDED_STORAGE_ROOT: [u8; 32] =
+        hex!("3ebcd2fc484dcc2c79d8f266f30abf8c43cffebdc88c3e0d8e49daca419eefc9");
+    const EMBEDDED_RECEIPT_PATH_1: &str =
+        "receipts/ports/transfer/channels/channel-0/sequences/6159905";
+    const EMBEDDED_RECEIPT_PATH_2: &str =
+        "receipts/ports/transfer/channels/channel-0/sequences/11837682";
+    // the storage key of this path shares the first 9 nibbles with `EMBEDDED_RECEIPT_PATH_1`, so the walk diverges in the embedded leaf node
+    const EMBEDDED_ABSENT_RECEIPT_PATH: &str =
+        "receipts/ports/transfer/channels/channel-0/sequences/10904255";
+    // the proof of all the three paths
+    const EMBEDDED_PROOF: &[u8] = &hex!("f877e7850049695933a09aad876bb336a22fc856656be0c0ce2ea5d577d86a3c7ed5364e9152e8f906e7f84d808080de9c31e14745be74319ec5b87807295b9ebf61105f3bdfe8a139f6b145260180808080808080de9c3a7c7bbc5f0015b0e46f16fbc5799e3bff64475648ca4b055b5a40c2018080808080");
+    // the same trie as `EMBEDDED_STORAGE_ROOT` except that the embedded nodes are encoded as byte strings
+    const EMBEDDED_BYTES_STORAGE_ROOT: [u8; 32] =
+        hex!("fe0d188789da3095ef3e47aaefd45234cb3ea0e8161bececcff9d4a5ef6c9136");
+    const EMBEDDED_BYTES_PROOF: &[u8] = &hex!("f879e7850049695933a03b2472f472cca8a4f9524a42fca962c6b98065d46eecf45e3a95e8169bc842b3f84f8080809fde9c31e14745be74319ec5b87807295b9ebf61105f3bdfe8a139f6b1452601808080808080809fde9c3a7c7bbc5f0015b0e46f16fbc5799e3bff64475648ca4b055b5a40c2018080808080");
+
+    // The union of the proofs of `PACKET_COMMITMENT_PATH`, `ACK_PATH` and `CONNECTION_PATH` in the trie of `STORAGE_ROOT`
+    const BATCH_PROOF: &[u8] = &hex!("f90174f843a020dc93aa2071d8fee619b0413af2f932685da696e8852d2c3c8dd087a6f0ffa6a1a039eec8d447861526f43d7309b0d243729a4e5fb977544759ab519fd591a778f6f842a0398e274c0e3a9f944959cf78218411db895873cf3d55641b6d4e84ce2879a5afa09fd1fae32e08a84559cd3ac36708019d83bad944117fd38595a47b75a690b3baf843a020b3de8757b4bf718d39ca342c95e0d0aca0de8ca6e87556eb6536960a3526f4a1a0c405e4cc87f5538f551977b631a3a919239c09d332348da206ffd54697cff24df851808080a0f75a02897e6d80308da3fb3d4e7ec5a25cdcc84d24b93f5cf85ab6ee30e9fe1580808080808080808080a0f78c262bf8184a538ede5fe47e9ce1d4e0f6aa52c36b74b05dd19eb98280ddd08080f8518080a099fa7bf1f51f328439c3ad3e4d2dc8a9cc1fd54d4df16838ac431416bc78b5d980a03e4d2ff7e7cad67ad87e921188e0124f2101c24782030c7cc876734f6419e20c808080808080808080808080");
+
+    #[test]
+    fn test_ibc_commitment_storage_locations() {
+        let slot = H256(COMMITMENTS_SLOT);
+        for (path, location) in [
+            // the location of the client state in the storage trie of an ibc-solidity deployment
+            (
+                "clients/lcp-client-0/clientState",
+                hex!("35c1d86194f8ce30f3fa3b209e77f0af2cbcb385bcf2b13bf0f641d4cf40f711"),
+            ),
+            (
+                PACKET_COMMITMENT_PATH,
+                hex!("869c4679a7b434720517279db15e30ed4ab0839a358369a1e28c4612f314889a"),
+            ),
+            (
+                ACK_PATH,
+                hex!("3934e5caa953617e97c8f675a200cb35c79887e0048221df623c0628066e0045"),
+            ),
+            (
+                CONNECTION_PATH,
+                hex!("30d2611f7782ee1a081b58bfb075d03452cf1978bfe897cdfec3fcb283b4ffc4"),
+            ),
+            (
+                RECEIPT_PATH,
+                hex!("58a4da3cf106c03276d0b67d78af39e37108ed4d384002cc4656a7b5acd3b217"),
+            ),
+        ] {
+            assert_eq!(
+                calculate_ibc_commitment_storage_key(&slot, path),
+                H256(location),
+                "{}",
+                path
+            );
+            assert_eq!(
+                calculate_ibc_commitment_storage_location(&slot, Path::from_str(path).unwrap()),
+                H256(location),
+                "{}",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn test_calculate_ibc_commitment_storage_key() {
+        // keccak256(path)
+        let path_hash = hex!("1c75f1e3a9b72d75450490debf8961c6528e080e31e30ebf2fd0f7ec8194584b");
+        assert_eq!(keccak_256(PACKET_COMMITMENT_PATH.as_bytes()), path_hash);
+        // keccak256(keccak256(path) ++ commitments_slot)
+        for (slot, key) in [
+            (
+                COMMITMENTS_SLOT,
+                hex!("869c4679a7b434720517279db15e30ed4ab0839a358369a1e28c4612f314889a"),
+            ),
+            (
+                [0u8; 32],
+                hex!("b4cef5b0b42f300c2f8aaa5aa4d36cffdda5fabd6e89ce519cf9c36bbb46d2eb"),
+            ),
+        ] {
+            assert_eq!(
+                calculate_ibc_commitment_storage_key(&H256(slot), PACKET_COMMITMENT_PATH),
+                H256(key)
+            );
+            assert_eq!(H256(key), H256(keccak_256(&[path_hash, slot].concat())));
+        }
+    }
+
+    #[test]
+    fn test_verify_membership() {
+        let root = CommitmentRoot::from_bytes(&STORAGE_ROOT);
+        let slot = H256(COMMITMENTS_SLOT);
+        for (path, value, proof) in [
+            (
+                PACKET_COMMITMENT_PATH,
+                PACKET_COMMITMENT.as_slice(),
+                PACKET_COMMITMENT_PROOF,
+            ),
+            (ACK_PATH, ACK_COMMITMENT.as_slice(), ACK_PROOF),
+            (CONNECTION_PATH, CONNECTION_END, CONNECTION_PROOF),
+        ] {
+            let res = verify_membership(
+                &root,
+                &slot,
+                CommitmentValueCodec::Keccak256,
+                &proof.to_vec().try_into().unwrap(),
+                Path::from_str(path).unwrap(),
+                value,
+            );
+            assert!(res.is_ok(), "{}: {:?}", path, res);
+            let res = verify_non_membership(
+                &root,
+                &slot,
+                &proof.to_vec().try_into().unwrap(),
+                Path::from_str(path).unwrap(),
+            );
+            assert!(
+                matches!(res, Err(Error::UnexpectedCommitment { .. })),
+                "{}: {:?}",
+                path,
+                res
+            );
+        }
+
+        // the stored commitment differs from the expected one
+        let res = verify_membership(
+            &root,
+            &slot,
+            CommitmentValueCodec::Keccak256,
+            &PACKET_COMMITMENT_PROOF.to_vec().try_into().unwrap(),
+            Path::from_str(PACKET_COMMITMENT_PATH).unwrap(),
+            &ACK_COMMITMENT,
+        );
+        match res {
+            Err(Error::CommitmentValueMismatch {
+                path,
+                expected,
+                got,
+            }) => {
+                assert_eq!(path, PACKET_COMMITMENT_PATH);
+                assert_eq!(expected, H256(keccak_256(&ACK_COMMITMENT)));
+                assert_eq!(
+                    got,
+                    H256(hex!(
+                        "c405e4cc87f5538f551977b631a3a919239c09d332348da206ffd54697cff24d"
+                    ))
+                );
+            }
+            res => panic!("unexpected result: {:?}", res),
+        }
+
+        // the key is absent from the storage trie
+        let res = verify_membership(
+            &root,
+            &slot,
+            CommitmentValueCodec::Keccak256,
+            &RECEIPT_ABSENCE_PROOF.to_vec().try_into().unwrap(),
+            Path::from_str(RECEIPT_PATH).unwrap(),
+            &PACKET_COMMITMENT,
+        );
+        match res {
+            Err(Error::CommitmentNotFound { path, key }) => {
+                assert_eq!(path, RECEIPT_PATH);
+                assert_eq!(
+                    key,
+                    calculate_ibc_commitment_storage_location(
+                        &slot,
+                        Path::from_str(RECEIPT_PATH).unwrap()
+                    )
+                );
+            }
+            res => panic!("unexpected result: {:?}", res),
+        }
+        let res = verify_non_membership(
+            &root,
+            &slot,
+            &RECEIPT_ABSENCE_PROOF.to_vec().try_into().unwrap(),
+            Path::from_str(RECEIPT_PATH).unwrap(),
+        );
+        assert!(res.is_ok(), "{:?}", res);
+
+        // malformed proofs
+        for proof in [
+            // not a rlp list
+            hex!("80").to_vec(),
+            // a node is not a rlp list
+            hex!("c180").to_vec(),
+            // the proof does not start from the root
+            hex!("c3c2c180").to_vec(),
+            // truncated
+            PACKET_COMMITMENT_PROOF[..3].to_vec(),
+        ] {
+            let res = verify_membership(
+                &root,
+                &slot,
+                CommitmentValueCodec::Keccak256,
+                &proof.try_into().unwrap(),
+                Path::from_str(ACK_PATH).unwrap(),
+                &ACK_COMMITMENT,
+            );
+            assert!(
+                matches!(res, Err(Error::MalformedCommitmentProof { .. })),
+                "{:?}",
+                res
+            );
+        }
+        for (proof, node_index, got_hash, consumed_nibbles) in [
+            // the proof does not start from the root
+            (
+                hex!("c3c2c180").to_vec(),
+                0,
+                Some(H256(keccak_256(&hex!("c2c180")))),
+                0,
+            ),
+            // no nodes
+            (
+                rlp::encode_list::<Vec<u8>, Vec<u8>>(&[]).to_vec(),
+                0,
+                None,
+                0,
+            ),
+            // a node on the path is missing
+            (EMPTY_SLOT_ABSENCE_PROOF.to_vec(), 1, None, 1),
+        ] {
+            let res = verify_membership(
+                &root,
+                &slot,
+                CommitmentValueCodec::Keccak256,
+                &proof.try_into().unwrap(),
+                Path::from_str(ACK_PATH).unwrap(),
+                &ACK_COMMITMENT,
+            );
+            match res {
+                Err(Error::StorageProofWalkError { key, err, .. }) => {
+                    assert_eq!(key, calculate_ibc_commitment_storage_key(&slot, ACK_PATH));
+                    assert_eq!(err.root, H256(STORAGE_ROOT));
+                    assert_eq!(err.node_index, node_index);
+                    assert_eq!(err.got_hash, got_hash);
+                    assert_eq!(err.consumed_nibbles, consumed_nibbles);
+                }
+                res => panic!("unexpected result: {:?}", res),
+            }
+        }
+
+        // the IBC contract has not been initialized
+        let res = verify_membership(
+            &CommitmentRoot::from_bytes(&[0u8; 32]),
+            &slot,
+            CommitmentValueCodec::Keccak256,
+            &PACKET_COMMITMENT_PROOF.to_vec().try_into().unwrap(),
+            Path::from_str(PACKET_COMMITMENT_PATH).unwrap(),
+            &PACKET_COMMITMENT,
+        );
+        assert!(
+            matches!(res, Err(Error::ZeroStorageRoot { .. })),
+            "{:?}",
+            res
+        );
+        let res = verify_membership(
+            &CommitmentRoot::from_bytes(&STORAGE_ROOT[1..]),
+            &slot,
+            CommitmentValueCodec::Keccak256,
+            &PACKET_COMMITMENT_PROOF.to_vec().try_into().unwrap(),
+            Path::from_str(PACKET_COMMITMENT_PATH).unwrap(),
+            &PACKET_COMMITMENT,
+        );
+        assert!(
+            matches!(res, Err(Error::InvalidStorageRootLength(31))),
+            "{:?}",
+            res
+        );
+    }
+
+    #[test]
+    fn test_verify_non_membership() {
+        let slot = H256(COMMITMENTS_SLOT);
+        for (name, root, path, proof) in [
+            (
+                "empty child slot of a branch",
+                STORAGE_ROOT,
+                EMPTY_SLOT_RECEIPT_PATH,
+                EMPTY_SLOT_ABSENCE_PROOF,
+            ),
+            (
+                "diverging leaf",
+                STORAGE_ROOT,
+                RECEIPT_PATH,
+                RECEIPT_ABSENCE_PROOF,
+            ),
+            (
+                "diverging extension",
+                EXTENSION_STORAGE_ROOT,
+                RECEIPT_PATH,
+                EXTENSION_ABSENCE_PROOF,
+            ),
+            (
+                "empty trie",
+                EMPTY_TRIE_ROOT.0,
+                RECEIPT_PATH,
+                hex!("c0").as_slice(),
+            ),
+            (
+                "single-node trie",
+                SINGLE_NODE_STORAGE_ROOT,
+                "connections/connection-1",
+                SINGLE_NODE_PROOF,
+            ),
+            (
+                "zero-valued slot",
+                ZERO_VALUE_STORAGE_ROOT,
+                RECEIPT_PATH,
+                ZERO_VALUE_PROOF,
+            ),
+        ] {
+            let root = CommitmentRoot::from_bytes(&root);
+            let res = verify_non_membership(
+                &root,
+                &slot,
+                &proof.to_vec().try_into().unwrap(),
+                Path::from_str(path).unwrap(),
+            );
+            assert!(res.is_ok(), "{}: {:?}", name, res);
+            // an exclusion proof is not a membership proof
+            let res = verify_membership(
+                &root,
+                &slot,
+                CommitmentValueCodec::Keccak256,
+                &proof.to_vec().try_into().unwrap(),
+                Path::from_str(path).unwrap(),
+                &PACKET_COMMITMENT,
+            );
+            assert!(
+                matches!(res, Err(Error::CommitmentNotFound { .. })),
+                "{}: {:?}",
+                name,
+                res
+            );
+        }
+
+        // membership proofs are rejected
+        for (root, path, value, proof) in [
+            (
+                EXTENSION_STORAGE_ROOT,
+                PACKET_COMMITMENT_PATH,
+                EXTENSION_PACKET_COMMITMENT.as_slice(),
+                EXTENSION_PACKET_COMMITMENT_PROOF,
+            ),
+            (
+                SINGLE_NODE_STORAGE_ROOT,
+                CONNECTION_PATH,
+                CONNECTION_END,
+                SINGLE_NODE_PROOF,
+            ),
+        ] {
+            let root = CommitmentRoot::from_bytes(&root);
+            let res = verify_membership(
+                &root,
+                &slot,
+                CommitmentValueCodec::Keccak256,
+                &proof.to_vec().try_into().unwrap(),
+                Path::from_str(path).unwrap(),
+                value,
+            );
+            assert!(res.is_ok(), "{}: {:?}", path, res);
+            let res = verify_non_membership(
+                &root,
+                &slot,
+                &proof.to_vec().try_into().unwrap(),
+                Path::from_str(path).unwrap(),
+            );
+            assert!(
+                matches!(res, Err(Error::UnexpectedCommitment { .. })),
+                "{}: {:?}",
+                path,
+                res
+            );
+        }
+
+        // the proof must contain exactly the nodes on the path
+        let mut proof = rlp::RlpStream::new_list(2);
+        proof.append_raw(&EMPTY_SLOT_ABSENCE_PROOF[2..], 1);
+        proof.append_raw(&RECEIPT_ABSENCE_PROOF[0x55..], 1);
+        let res = verify_non_membership(
+            &CommitmentRoot::from_bytes(&STORAGE_ROOT),
+            &slot,
+            &proof.out().to_vec().try_into().unwrap(),
+            Path::from_str(EMPTY_SLOT_RECEIPT_PATH).unwrap(),
+        );
+        assert!(
+            matches!(res, Err(Error::MalformedCommitmentProof { .. })),
+            "{:?}",
+            res
+        );
+        // the proof of an empty trie must not contain any nodes
+        let res = verify_non_membership(
+            &CommitmentRoot::from_bytes(&EMPTY_TRIE_ROOT.0),
+            &slot,
+            &EMPTY_SLOT_ABSENCE_PROOF.to_vec().try_into().unwrap(),
+            Path::from_str(RECEIPT_PATH).unwrap(),
+        );
+        assert!(
+            matches!(res, Err(Error::MalformedCommitmentProof { .. })),
+            "{:?}",
+            res
+        );
+        assert_eq!(
+            EMPTY_TRIE_ROOT,
+            H256(keccak_256(&rlp::encode(&Vec::<u8>::new())))
+        );
+    }
+
+    #[test]
+    fn test_verify_membership_batch() {
+        let root = CommitmentRoot::from_bytes(&STORAGE_ROOT);
+        let slot = H256(COMMITMENTS_SLOT);
+        let items = [
+            (
+                Path::from_str(PACKET_COMMITMENT_PATH).unwrap(),
+                PACKET_COMMITMENT.to_vec(),
+            ),
+            (Path::from_str(ACK_PATH).unwrap(), ACK_COMMITMENT.to_vec()),
+            (
+                Path::from_str(CONNECTION_PATH).unwrap(),
+                CONNECTION_END.to_vec(),
+            ),
+        ];
+        let proof: CommitmentProofBytes = BATCH_PROOF.to_vec().try_into().unwrap();
+        let res = verify_membership_batch(
+            &root,
+            &slot,
+            CommitmentValueCodec::Keccak256,
+            &proof,
+            &items,
+        );
+        assert!(res.is_ok(), "{:?}", res);
+        // the shared nodes are included only once
+        assert!(
+            BATCH_PROOF.len()
+                < PACKET_COMMITMENT_PROOF.len() + ACK_PROOF.len() + CONNECTION_PROOF.len()
+        );
+
+        // the index of the first failing item is reported
+        let mut invalid_items = items.clone();
+        invalid_items[1].1 = PACKET_COMMITMENT.to_vec();
+        invalid_items[2].1 = PACKET_COMMITMENT.to_vec();
+        let res = verify_membership_batch(
+            &root,
+            &slot,
+            CommitmentValueCodec::Keccak256,
+            &proof,
+            &invalid_items,
+        );
+        match res {
+            Err(Error::BatchMembershipVerificationError { index, err }) => {
+                assert_eq!(index, 1);
+                assert!(
+                    matches!(*err, Error::CommitmentValueMismatch { .. }),
+                    "{:?}",
+                    err
+                );
+            }
+            res => panic!("unexpected result: {:?}", res),
+        }
+        let mut invalid_items = items.to_vec();
+        invalid_items.push((
+            Path::from_str(RECEIPT_PATH).unwrap(),
+            PACKET_COMMITMENT.to_vec(),
+        ));
+        let res = verify_membership_batch(
+            &root,
+            &slot,
+            CommitmentValueCodec::Keccak256,
+            &proof,
+            &invalid_items,
+        );
+        match res {
+            Err(Error::BatchMembershipVerificationError { index, err }) => {
+                assert_eq!(index, 3);
+                assert!(
+                    matches!(*err, Error::CommitmentNotFound { .. }),
+                    "{:?}",
+                    err
+                );
+            }
+            res => panic!("unexpected result: {:?}", res),
+        }
+
+        // every node must be used by an item
+        let res = verify_membership_batch(
+            &root,
+            &slot,
+            CommitmentValueCodec::Keccak256,
+            &proof,
+            &items[..2],
+        );
+        assert!(
+            matches!(res, Err(Error::InvalidProofFormatError(_))),
+            "{:?}",
+            res
+        );
+        // a node must not be included twice
+        let nodes = decode_eip1186_rlp_proof(BATCH_PROOF.to_vec()).unwrap();
+        let mut duplicated = rlp::RlpStream::new_list(nodes.len() + 1);
+        for node in nodes.iter().chain(nodes.first()) {
+            duplicated.append_raw(node, 1);
+        }
+        let res = verify_membership_batch(
+            &root,
+            &slot,
+            CommitmentValueCodec::Keccak256,
+            &duplicated.out().to_vec().try_into().unwrap(),
+            &items,
+        );
+        assert!(
+            matches!(res, Err(Error::InvalidProofFormatError(_))),
+            "{:?}",
+            res
+        );
+        // a single proof is a batch proof of one item
+        let res = verify_membership_batch(
+            &root,
+            &slot,
+            CommitmentValueCodec::Keccak256,
+            &ACK_PROOF.to_vec().try_into().unwrap(),
+            &items[1..2],
+        );
+        assert!(res.is_ok(), "{:?}", res);
+    }
+
+    #[test]
+    fn test_embedded_nodes() {
+        let slot = H256(COMMITMENTS_SLOT);
+        for (root, proof) in [
+            (EMBEDDED_STORAGE_ROOT, EMBEDDED_PROOF),
+            (EMBEDDED_BYTES_STORAGE_ROOT, EMBEDDED_BYTES_PROOF),
+        ] {
+            let root = CommitmentRoot::from_bytes(&root);
+            let proof: CommitmentProofBytes = proof.to_vec().try_into().unwrap();
+            for path in [EMBEDDED_RECEIPT_PATH_1, EMBEDDED_RECEIPT_PATH_2] {
+                let res = verify_membership(
+                    &root,
+                    &slot,
+                    CommitmentValueCodec::Raw,
+                    &proof,
+                    Path::from_str(path).unwrap(),
+                    &[1],
+                );
+                assert!(res.is_ok(), "{}: {:?}", path, res);
+                let res =
+                    verify_non_membership(&root, &slot, &proof, Path::from_str(path).unwrap());
+                assert!(
+                    matches!(res, Err(Error::UnexpectedCommitment { .. })),
+                    "{}: {:?}",
+                    path,
+                    res
+                );
+            }
+            let res = verify_non_membership(
+                &root,
+                &slot,
+                &proof,
+                Path::from_str(EMBEDDED_ABSENT_RECEIPT_PATH).unwrap(),
+            );
+            assert!(res.is_ok(), "{:?}", res);
+            let res = verify_membership(
+                &root,
+                &slot,
+                CommitmentValueCodec::Raw,
+                &proof,
+                Path::from_str(EMBEDDED_ABSENT_RECEIPT_PATH).unwrap(),
+                &[1],
+            );
+            assert!(
+                matches!(res, Err(Error::CommitmentNotFound { .. })),
+                "{:?}",
+                res
+            );
+        }
+        // the both paths are proven by the same nodes
+        let res = verify_membership_batch(
+            &CommitmentRoot::from_bytes(&EMBEDDED_STORAGE_ROOT),
+            &slot,
+            CommitmentValueCodec::Raw,
+            &EMBEDDED_PROOF.to_vec().try_into().unwrap(),
+            &[
+                (Path::from_str(EMBEDDED_RECEIPT_PATH_1).unwrap(), vec![1]),
+                (Path::from_str(EMBEDDED_RECEIPT_PATH_2).unwrap(), vec![1]),
+            ],
+        );
+        assert!(res.is_ok(), "{:?}", res);
+
+        // an embedded node in a byte string must be a rlp list
+        let nodes = decode_eip1186_rlp_proof(EMBEDDED_BYTES_PROOF.to_vec()).unwrap();
+        let branch = Rlp::new(&nodes[1]);
+        let mut corrupted = rlp::RlpStream::new_list(17);
+        for i in 0..17 {
+            let item = branch.at(i).unwrap();
+            if i == 3 {
+                corrupted.append(&item.data().unwrap()[1..].to_vec());
+            } else {
+                corrupted.append_raw(item.as_raw(), 1);
+            }
+        }
+        let corrupted = corrupted.out().to_vec();
+        let mut extension = rlp::RlpStream::new_list(2);
+        extension.append_raw(Rlp::new(&nodes[0]).at(0).unwrap().as_raw(), 1);
+        extension.append(&keccak_256(&corrupted).to_vec());
+        let extension = extension.out().to_vec();
+        let mut proof = rlp::RlpStream::new_list(2);
+        proof.append_raw(&extension, 1);
+        proof.append_raw(&corrupted, 1);
+        let res = verify_membership(
+            &CommitmentRoot::from_bytes(&keccak_256(&extension)),
+            &slot,
+            CommitmentValueCodec::Raw,
+            &proof.out().to_vec().try_into().unwrap(),
+            Path::from_str(EMBEDDED_RECEIPT_PATH_1).unwrap(),
+            &[1],
+        );
+        match res {
+            Err(Error::StorageProofWalkError { err, .. }) => {
+                assert_eq!(err.node_index, 1);
+                assert_eq!(err.consumed_nibbles, 8);
+            }
+            res => panic!("unexpected result: {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_storage_proof_walk_error() {
+        let root = CommitmentRoot::from_bytes(&EXTENSION_STORAGE_ROOT);
+        let slot = H256(COMMITMENTS_SLOT);
+        // the proof consists of the root extension node, a branch node and a leaf node
+        let nodes = decode_eip1186_rlp_proof(EXTENSION_PACKET_COMMITMENT_PROOF.to_vec()).unwrap();
+        assert_eq!(nodes.len(), 3);
+        // corrupt a child reference of the branch node
+        let mut corrupted = nodes[1].clone();
+        let last = corrupted.len() - 2;
+        corrupted[last] ^= 0xff;
+        let mut proof = rlp::RlpStream::new_list(3);
+        for node in [&nodes[0], &corrupted, &nodes[2]] {
+            proof.append_raw(node, 1);
+        }
+        let res = verify_membership(
+            &root,
+            &slot,
+            CommitmentValueCodec::Keccak256,
+            &proof.out().to_vec().try_into().unwrap(),
+            Path::from_str(PACKET_COMMITMENT_PATH).unwrap(),
+            &EXTENSION_PACKET_COMMITMENT,
+        );
+        match res {
+            Err(Error::StorageProofWalkError { path, key, err }) => {
+                assert_eq!(path, PACKET_COMMITMENT_PATH);
+                assert_eq!(
+                    key,
+                    calculate_ibc_commitment_storage_key(&slot, PACKET_COMMITMENT_PATH)
+                );
+                assert_eq!(err.root, H256(EXTENSION_STORAGE_ROOT));
+                assert_eq!(err.node_index, 1);
+                assert_eq!(err.expected_hash, H256(keccak_256(&nodes[1])));
+                assert_eq!(err.got_hash, Some(H256(keccak_256(&corrupted))));
+                // the root extension node consumes a nibble
+                assert_eq!(err.consumed_nibbles, 1);
+            }
+            res => panic!("unexpected result: {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_extract_storage_value_from_proof() {
+        let root = H256(STORAGE_ROOT);
+        let slot = H256(COMMITMENTS_SLOT);
+        let key = |path: &str| calculate_ibc_commitment_storage_key(&slot, path);
+        let nodes = |proof: &[u8]| decode_eip1186_rlp_proof(proof.to_vec()).unwrap();
+
+        // the stored values are the commitments without leading zero bytes
+        let value = extract_storage_value_from_proof(
+            root,
+            key(PACKET_COMMITMENT_PATH),
+            &nodes(PACKET_COMMITMENT_PROOF),
+        )
+        .unwrap();
+        assert_eq!(
+            value,
+            Some(hex!("c405e4cc87f5538f551977b631a3a919239c09d332348da206ffd54697cff24d").to_vec())
+        );
+        assert_eq!(value, Some(keccak_256(&PACKET_COMMITMENT).to_vec()));
+        let ack = keccak_256(&ACK_COMMITMENT);
+        assert_eq!(ack[0], 0);
+        let value =
+            extract_storage_value_from_proof(root, key(ACK_PATH), &nodes(ACK_PROOF)).unwrap();
+        assert_eq!(value, Some(ack[1..].to_vec()));
+
+        // the key is absent from the storage trie
+        for (path, proof) in [
+            (RECEIPT_PATH, RECEIPT_ABSENCE_PROOF),
+            (EMPTY_SLOT_RECEIPT_PATH, EMPTY_SLOT_ABSENCE_PROOF),
+        ] {
+            let res = extract_storage_value_from_proof(root, key(path), &nodes(proof));
+            assert!(matches!(res, Ok(None)), "{}: {:?}", path, res);
+        }
+        let res = extract_storage_value_from_proof(EMPTY_TRIE_ROOT, key(ACK_PATH), &[]);
+        assert!(matches!(res, Ok(None)), "{:?}", res);
+
+        // the proof agrees with `verify_membership`
+        assert!(verify_membership(
+            &CommitmentRoot::from_bytes(&STORAGE_ROOT),
+            &slot,
+            CommitmentValueCodec::Keccak256,
+            &ACK_PROOF.to_vec().try_into().unwrap(),
+            Path::from_str(ACK_PATH).unwrap(),
+            &ACK_COMMITMENT,
+        )
+        .is_ok());
+
+        // a node on the path is missing
+        let res =
+            extract_storage_value_from_proof(root, key(ACK_PATH), &nodes(EMPTY_SLOT_ABSENCE_PROOF));
+        match res {
+            Err(Error::StorageValueProofWalkError { key: got, err }) => {
+                assert_eq!(got, key(ACK_PATH));
+                assert_eq!(err.root, root);
+                assert_eq!(err.node_index, 1);
+                assert_eq!(err.got_hash, None);
+                assert_eq!(err.consumed_nibbles, 1);
+            }
+            res => panic!("unexpected result: {:?}", res),
+        }
+        // the proof does not start from the root
+        let res = extract_storage_value_from_proof(
+            H256(EXTENSION_STORAGE_ROOT),
+            key(ACK_PATH),
+            &nodes(ACK_PROOF),
+        );
+        match res {
+            Err(Error::StorageValueProofWalkError { err, .. }) => {
+                assert_eq!(err.node_index, 0);
+                assert_eq!(err.expected_hash, H256(EXTENSION_STORAGE_ROOT));
+            }
+            res => panic!("unexpected result: {:?}", res),
+        }
+        // the proof contains a node that is not on the path
+        let mut proof = nodes(ACK_PROOF);
+        proof.push(nodes(PACKET_COMMITMENT_PROOF)[1].clone());
+        let res = extract_storage_value_from_proof(root, key(ACK_PATH), &proof);
+        assert!(
+            matches!(res, Err(Error::MalformedStorageValueProof { .. })),
+            "{:?}",
+            res
+        );
+        // the stored value is not a rlp byte string
+        let mut leaf = rlp::RlpStream::new_list(2);
+        leaf.append(
+            &[
+                [0x20u8].as_slice(),
+                keccak_256(key(ACK_PATH).as_bytes()).as_slice(),
+            ]
+            .concat(),
+        );
+        leaf.append(&hex!("c0").to_vec());
+        let leaf = leaf.out().to_vec();
+        let res = extract_storage_value_from_proof(H256(keccak_256(&leaf)), key(ACK_PATH), &[leaf]);
+        match res {
+            Err(Error::MalformedStorageValueProof { key: got, .. }) => {
+                assert_eq!(got, key(ACK_PATH));
+            }
+            res => panic!("unexpected result: {:?}", res),
+        }
+    }
+
+    #[test]
+    fn test_verify_commitment_prefix() {
+        let prefix = |bz: &[u8]| CommitmentPrefix::try_from(bz.to_vec()).unwrap();
+        let expected = ExpectedCommitmentPrefix::default();
+        assert!(expected.is_default());
+        let res = expected.verify(&prefix(b"ibc"));
+        assert!(res.is_ok(), "{:?}", res);
+        for got in [CommitmentPrefix::default(), prefix(b"ibc/"), prefix(b"IBC")] {
+            match expected.verify(&got) {
+                Err(Error::UnexpectedCommitmentPrefix {
+                    expected,
+                    got: actual,
+                }) => {
+                    assert_eq!(expected, b"ibc");
+                    assert_eq!(actual, got.as_bytes());
+                }
+                res => panic!("unexpected result: {:?}", res),
+            }
+        }
+
+        // a client state may expect another prefix, e.g. of a deployment namespacing its host
+        let custom = ExpectedCommitmentPrefix::new(b"ibc-1".to_vec()).unwrap();
+        assert!(!custom.is_default());
+        assert!(custom.verify(&prefix(b"ibc-1")).is_ok());
+        assert!(matches!(
+            custom.verify(&prefix(b"ibc")),
+            Err(Error::UnexpectedCommitmentPrefix { .. })
+        ));
+        assert!(matches!(
+            ExpectedCommitmentPrefix::new(Vec::new()),
+            Err(Error::UninitializedClientStateField("commitment_prefix"))
+        ));
+
+        // the serde representation is a 0x-prefixed hex string
+        let json = serde_json::to_value(&custom).unwrap();
+        assert_eq!(json, serde_json::json!("0x6962632d31"));
+        assert_eq!(
+            serde_json::from_value::<ExpectedCommitmentPrefix>(json).unwrap(),
+            custom
+        );
+        for invalid in ["0x", "6962632d31", "0xzz"] {
+            assert!(
+                serde_json::from_value::<ExpectedCommitmentPrefix>(serde_json::json!(invalid))
+                    .is_err(),
+                "{}",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn test_commitment_value_codec() {
+        let path = Path::from_str(RECEIPT_PATH).unwrap();
+        let mut word = [0u8; 32];
+        word[31] = 1;
+        // a value shorter than 32 bytes is left-padded, so leading zero bytes do not matter
+        for value in [
+            hex!("01").as_slice(),
+            hex!("0001").as_slice(),
+            word.as_slice(),
+        ] {
+            assert_eq!(
+                CommitmentValueCodec::Raw.encode(&path, value).unwrap(),
+                H256(word)
+            );
+        }
+        assert_eq!(
+            CommitmentValueCodec::Raw.encode(&path, &[]).unwrap(),
+            H256::default()
+        );
+        assert_eq!(
+            CommitmentValueCodec::Keccak256
+                .encode(&path, &hex!("01"))
+                .unwrap(),
+            H256(keccak_256(&hex!("01")))
+        );
+        // a value longer than 32 bytes does not fit in a storage slot
+        let res = CommitmentValueCodec::Raw.encode(&path, &[1u8; 33]);
+        assert!(
+            matches!(
+                res,
+                Err(Error::InvalidRawCommitmentValueLength { length: 33, .. })
+            ),
+            "{:?}",
+            res
+        );
+        let res = CommitmentValueCodec::Keccak256.encode(&path, &[1u8; 33]);
+        assert!(res.is_ok(), "{:?}", res);
+
+        let root = CommitmentRoot::from_bytes(&RAW_RECEIPT_STORAGE_ROOT);
+        let slot = H256(COMMITMENTS_SLOT);
+        let proof: CommitmentProofBytes = RAW_RECEIPT_PROOF.to_vec().try_into().unwrap();
+        for value in [hex!("01").as_slice(), hex!("0001").as_slice(), &word] {
+            let res = verify_membership(
+                &root,
+                &slot,
+                CommitmentValueCodec::Raw,
+                &proof,
+                path.clone(),
+                value,
+            );
+            assert!(res.is_ok(), "{:?}", res);
+        }
+        // the raw value is not the keccak256 of the value
+        let res = verify_membership(
+            &root,
+            &slot,
+            CommitmentValueCodec::Keccak256,
+            &proof,
+            path.clone(),
+            &hex!("01"),
+        );
+        assert!(
+            matches!(res, Err(Error::CommitmentValueMismatch { .. })),
+            "{:?}",
+            res
+        );
+        for (value, expected) in [
+            // a trailing zero byte is not truncated
+            (
+                hex!("0100").as_slice(),
+                H256::from_slice(&[[0u8; 30].as_slice(), &[1, 0]].concat()),
+            ),
+            (
+                hex!("02").as_slice(),
+                H256::from_slice(&[[0u8; 31].as_slice(), &[2]].concat()),
+            ),
+        ] {
+            match verify_membership(
+                &root,
+                &slot,
+                CommitmentValueCodec::Raw,
+                &proof,
+                path.clone(),
+                value,
+            ) {
+                Err(Error::CommitmentValueMismatch {
+                    expected: e, got, ..
+                }) => {
+                    assert_eq!(e, expected);
+                    assert_eq!(got, H256(word));
+                }
+                res => panic!("unexpected result: {:?}", res),
+            }
+        }
+        let res = verify_membership(
+            &root,
+            &slot,
+            CommitmentValueCodec::Raw,
+            &proof,
+            path.clone(),
+            &[1u8; 33],
+        );
+        assert!(
+            matches!(res, Err(Error::InvalidRawCommitmentValueLength { .. })),
+            "{:?}",
+            res
+        );
+        // a zero word cannot be proven to be stored
+        let res = verify_membership(
+            &CommitmentRoot::from_bytes(&ZERO_VALUE_STORAGE_ROOT),
+            &slot,
+            CommitmentValueCodec::Raw,
+            &ZERO_VALUE_PROOF.to_vec().try_into().unwrap(),
+            path.clone(),
+            &[0u8; 32],
+        );
+        assert!(
+            matches!(res, Err(Error::CommitmentNotFound { .. })),
+            "{:?}",
+            res
+        );
+
+        // the raw codec in a batch
+        let res = verify_membership_batch(
+            &root,
+            &slot,
+            CommitmentValueCodec::Raw,
+            &proof,
+            &[(path.clone(), vec![1u8; 33])],
+        );
+        assert!(
+            matches!(
+                res,
+                Err(Error::BatchMembershipVerificationError { index: 0, .. })
+            ),
+            "{:?}",
+            res
+        );
+        let res = verify_membership_batch(
+            &root,
+            &slot,
+            CommitmentValueCodec::Raw,
+            &proof,
+            &[(path, vec![1u8])],
+        );
+        assert!(res.is_ok(), "{:?}", res);
+
+        for codec in [CommitmentValueCodec::Keccak256, CommitmentValueCodec::Raw] {
+            assert_eq!(
+                CommitmentValueCodec::try_from(i32::from(codec)).unwrap(),
+                codec
+            );
+        }
+        assert!(matches!(
+            CommitmentValueCodec::try_from(2),
+            Err(Error::UnknownCommitmentValueCodec(2))
+        ));
+    }
+
+    #[test]
+    fn test_decode_eip1186_rlp_proof() {
+        for proof in [ACK_PROOF, BATCH_PROOF] {
+            let nodes = decode_eip1186_rlp_proof(proof.to_vec()).unwrap();
+            // encode each node as a byte string instead of a nested list
+            let mut stream = rlp::RlpStream::new_list(nodes.len());
+            for node in nodes.iter() {
+                stream.append(node);
+            }
+            let encoded = stream.out().to_vec();
+            assert_ne!(encoded, proof);
+            assert_eq!(decode_eip1186_rlp_proof(encoded).unwrap(), nodes);
+        }
+
+        // the both encodings can be used for membership verification
+        let nodes = decode_eip1186_rlp_proof(ACK_PROOF.to_vec()).unwrap();
+        let mut stream = rlp::RlpStream::new_list(nodes.len());
+        for node in nodes.iter() {
+            stream.append(node);
+        }
+        let res = verify_membership(
+            &CommitmentRoot::from_bytes(&STORAGE_ROOT),
+            &H256(COMMITMENTS_SLOT),
+            CommitmentValueCodec::Keccak256,
+            &stream.out().to_vec().try_into().unwrap(),
+            Path::from_str(ACK_PATH).unwrap(),
+            ACK_COMMITMENT.as_slice(),
+        );
+        assert!(res.is_ok(), "{:?}", res);
+
+        // a proof must not mix the two encodings
+        let mut stream = rlp::RlpStream::new_list(nodes.len());
+        stream.append_raw(&nodes[0], 1);
+        stream.append(&nodes[1]);
+        let res = decode_eip1186_rlp_proof(stream.out().to_vec());
+        assert!(
+            matches!(res, Err(Error::InvalidProofFormatError(_))),
+            "{:?}",
+            res
+        );
+
+        // a byte string must be a rlp-encoded node
+        for bz in [
+            hex!("8180").to_vec(),
+            [nodes[0].as_slice(), &[0x80]].concat(),
+            nodes[0][..nodes[0].len() - 1].to_vec(),
+            vec![],
+        ] {
+            let mut stream = rlp::RlpStream::new_list(2);
+            stream.append(&nodes[1]);
+            stream.append(&bz);
+            let res = decode_eip1186_rlp_proof(stream.out().to_vec());
+            // a truncated node is rejected by the rlp decoder itself
+            assert!(
+                matches!(
+                    res,
+                    Err(Error::InvalidProofFormatError(_) | Error::RlpDecodeError(_))
+                ),
+                "{:?}",
+                res
+            );
+        }
+
+        // the deprecated name is kept for compatibility
+        #[allow(deprecated)]
+        let res = decode_eip1184_rlp_proof(ACK_PROOF.to_vec()).unwrap();
+        assert_eq!(res, nodes);
+    }
+
+    #[test]
+    fn test_decode_eip1186_rlp_proof_limits() {
+        // a rlp list of a byte string, which is `len` bytes in total
+        let node = |len: usize| {
+            let mut stream = rlp::RlpStream::new_list(1);
+            stream.append(&vec![0u8; len - 6]);
+            let node = stream.out().to_vec();
+            assert_eq!(node.len(), len);
+            node
+        };
+        let proof = |nodes: &[Vec<u8>]| {
+            let mut stream = rlp::RlpStream::new_list(nodes.len());
+            for node in nodes {
+                stream.append_raw(node, 1);
+            }
+            stream.out().to_vec()
+        };
+        let assert_limit_exceeded = |proof: Vec<u8>, expected_limit: &str, expected: usize| {
+            let res = decode_eip1186_rlp_proof(proof);
+            assert!(
+                matches!(
+                    res,
+                    Err(Error::DecodeLimitExceeded { limit, actual, .. })
+                        if limit == expected_limit && actual == expected
+                ),
+                "{:?}",
+                res
+            );
+        };
+
+        // the outer list header is 3 bytes
+        let mut nodes = vec![node(MAX_PROOF_NODE_SIZE); 15];
+        nodes.push(node(MAX_PROOF_SIZE - 3 - 15 * MAX_PROOF_NODE_SIZE));
+        let at_limit = proof(&nodes);
+        assert_eq!(at_limit.len(), MAX_PROOF_SIZE);
+        assert_eq!(decode_eip1186_rlp_proof(at_limit).unwrap(), nodes);
+        nodes[15] = node(MAX_PROOF_SIZE - 2 - 15 * MAX_PROOF_NODE_SIZE);
+        assert_limit_exceeded(proof(&nodes), "MAX_PROOF_SIZE", MAX_PROOF_SIZE + 1);
+
+        let nodes = vec![vec![0xc0]; MAX_PROOF_NODES];
+        assert_eq!(decode_eip1186_rlp_proof(proof(&nodes)).unwrap(), nodes);
+        let nodes = vec![vec![0xc0]; MAX_PROOF_NODES + 1];
+        assert_limit_exceeded(proof(&nodes), "MAX_PROOF_NODES", MAX_PROOF_NODES + 1);
+
+        let nodes = vec![node(MAX_PROOF_NODE_SIZE)];
+        assert_eq!(decode_eip1186_rlp_proof(proof(&nodes)).unwrap(), nodes);
+        let nodes = vec![node(MAX_PROOF_NODE_SIZE + 1)];
+        assert_limit_exceeded(
+            proof(&nodes),
+            "MAX_PROOF_NODE_SIZE",
+            MAX_PROOF_NODE_SIZE + 1,
+        );
+        // the same limit applies to a node encoded as a byte string
+        let mut stream = rlp::RlpStream::new_list(1);
+        stream.append(&nodes[0]);
+        assert_limit_exceeded(
+            stream.out().to_vec(),
+            "MAX_PROOF_NODE_SIZE",
+            MAX_PROOF_NODE_SIZE + 1,
+        );
+
+        // a commitment proof exceeding the limits is an invalid merkle proof
+        let res = verify_membership(
+            &CommitmentRoot::from_bytes(&STORAGE_ROOT),
+            &H256(COMMITMENTS_SLOT),
+            CommitmentValueCodec::Keccak256,
+            &proof(&nodes).try_into().unwrap(),
+            Path::from_str(ACK_PATH).unwrap(),
+            ACK_COMMITMENT.as_slice(),
+        )
+        .map_err(ClientError::from);
+        assert!(
+            matches!(
+                res,
+                Err(ClientError::InvalidCommitmentProof(
+                    CommitmentError::InvalidMerkleProof
+                ))
+            ),
+            "{:?}",
+            res
+        );
+    }
+
+    #[test]
+    fn test_trim_left_zero() {
+        assert_eq!(trim_left_zero(&[1, 2, 3, 4]), [1, 2, 3, 4]);
+        assert_eq!(trim_left_zero(&[1, 2, 3, 0]), [1, 2, 3, 0]);
+        assert_eq!(trim_left_zero(&[0, 2, 3, 0]), [2, 3, 0]);
+        assert_eq!(trim_left_zero(&[0, 0, 3, 0]), [3, 0]);
+        assert_eq!(trim_left_zero(&[0, 0, 0, 4]), [4]);
+        assert!(trim_left_zero(&[0, 0, 0, 0]).is_empty());
+        assert!(trim_left_zero(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_decode_hex_prefix() {
+        let nibbles = |bz: &[u8]| {
+            let (path, is_leaf) = decode_hex_prefix(bz).unwrap();
+            let nibbles: Vec<u8> = (0..path.len()).map(|i| path.nibble(i)).collect();
+            (nibbles, is_leaf)
+        };
+        // the examples of the hex-prefix encoding in the yellow paper
+        assert_eq!(nibbles(&[0x00, 0x01, 0x23]), (vec![0, 1, 2, 3], false));
+        assert_eq!(nibbles(&[0x11, 0x23]), (vec![1, 2, 3], false));
+        assert_eq!(
+            nibbles(&[0x20, 0x0f, 0x1c, 0xb8]),
+            (vec![0, 0xf, 1, 0xc, 0xb, 8], true)
+        );
+        assert_eq!(
+            nibbles(&[0x3f, 0x1c, 0xb8]),
+            (vec![0xf, 1, 0xc, 0xb, 8], true)
+        );
+        assert_eq!(nibbles(&[0x20]), (vec![], true));
+
+        let (path, _) = decode_hex_prefix(&[0x11, 0x23]).unwrap();
+        assert!(path.is_prefix_of(&[1, 2, 3]));
+        assert!(path.is_prefix_of(&[1, 2, 3, 4]));
+        assert!(!path.is_prefix_of(&[1, 2]));
+        assert!(!path.is_prefix_of(&[1, 2, 4]));
+
+        assert!(decode_hex_prefix(&[]).is_err());
+        assert!(decode_hex_prefix(&[0x40, 0x12]).is_err());
+    }
+
+    #[test]
+    fn test_calculate_ibc_commitment_storage_location() {
+        let path = Path::from_str("clients/lcp-client-0/clientState").unwrap();
+        let slot = H256(hex!(
+            "1ee222554989dda120e26ecacf756fe1235cd8d726706b57517715dde4f0c900"
+        ));
+        let location = calculate_ibc_commitment_storage_location(&slot, path.clone());
+        assert_eq!(
+            location,
+            H256(keccak_256(
+                &[
+                    keccak_256(path.clone().into_bytes().as_slice()).as_slice(),
+                    slot.as_bytes()
+                ]
+                .concat()
+            ))
+        );
+        // the location must depend on the commitments slot
+        assert_ne!(
+            location,
+            calculate_ibc_commitment_storage_location(
+                &H256(hex!(
+                    "0000000000000000000000000000000000000000000000000000000000000003"
+                )),
+                path
+            )
+        );
+    }
+}
@@ -1,14 +1,18 @@
+use crate::cache::{validate_sync_committee, NoCache, SyncCommitteeCache};
+use crate::commitment::keccak_256;
 use crate::errors::Error;
 use crate::internal_prelude::*;
+use crate::types::TrustedSyncCommittee;
 use ethereum_consensus::{
     beacon::Slot,
     bls::PublicKey,
     compute::compute_sync_committee_period_at_slot,
     context::ChainContext,
     sync_protocol::{SyncCommittee, SyncCommitteePeriod},
+    types::H256,
 };
 use ethereum_ibc_proto::{
-    google::protobuf::Timestamp as ProtoTimestamp,
+    any::AnyMessage, google::protobuf::Timestamp as ProtoTimestamp,
     ibc::lightclients::ethereum::v1::ConsensusState as RawConsensusState,
 };
 use ethereum_light_client_verifier::{state::LightClientStoreReader, updates::ConsensusUpdate};
@@ -23,48 +27,203 @@ use ibc::{
 };
 use ibc_proto::{google::protobuf::Any as IBCAny, protobuf::Protobuf};
 
-pub const ETHEREUM_CONSENSUS_STATE_TYPE_URL: &str = "/ibc.lightclients.ethereum.v1.ConsensusState";
+pub const ETHEREUM_CONSENSUS_STATE_TYPE_URL: &str = RawConsensusState::TYPE_URL;
+
+/// The maximum length of the proto-encoded consensus state, see `ConsensusState::encoded_len`
+///
+/// It is reached with a `u64::MAX` slot and timestamp and with the current block hash:
+/// slot (11) + storage root (34) + timestamp (14) + sync committees (2 * 50) + current block hash (34).
+/// Note that the `Any` wrapping adds the type URL and the length prefix of the value.
+pub const MAX_CONSENSUS_STATE_ENCODED_LEN: usize = 193;
+
+/// The length of `ConsensusState::abi_encode`
+///
+/// slot (8) + storage root (32) + timestamp (8) + sync committees (2 * 48).
+pub const ABI_ENCODED_CONSENSUS_STATE_LEN: usize = 144;
 
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(deny_unknown_fields)]
 pub struct ConsensusState {
     /// finalized header's slot
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::U64"))]
     pub slot: Slot,
     /// the storage root of the IBC contract
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::CommitmentRoot"))]
     pub storage_root: CommitmentRoot,
     /// timestamp from execution payload
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Timestamp"))]
     pub timestamp: Timestamp,
     /// aggregate public key of current sync committee
     /// "current" indicates a period corresponding to the `slot`
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::PublicKey"))]
     pub current_sync_committee: PublicKey,
     /// aggregate public key of next sync committee
     /// "next" indicates `current + 1` period
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::PublicKey"))]
     pub next_sync_committee: PublicKey,
+    /// hash of the finalized execution block if known
+    /// It is the block hash proven by `ExecutionUpdateInfo::block_hash` of the header that created this consensus state.
+    /// This field is not a part of the commitment root, and it is `None` for consensus states created from a header without the block hash or before this field was introduced
+    #[serde(default)]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<crate::schema::H256>"))]
+    pub current_block_hash: Option<H256>,
 }
 
+/// The compressed encoding of the BLS12-381 G1 point at infinity
+const INFINITY_PUBKEY: [u8; 48] = {
+    let mut bz = [0u8; 48];
+    bz[0] = 0xc0;
+    bz
+};
+
 impl ConsensusState {
+    /// Deserialize a consensus state and `validate` it, see `ClientState::deserialize_validated`
+    pub fn deserialize_validated<'de, D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        use serde::{de::Error as _, Deserialize};
+        let consensus_state = Self::deserialize(deserializer)?;
+        consensus_state.validate().map_err(D::Error::custom)?;
+        Ok(consensus_state)
+    }
+
+    /// Validate that each field of the consensus state is initialized
+    ///
+    /// A zero `storage_root` is rejected because the update only accepts the storage root of an existing IBC contract, see `AccountUpdateInfo::validate`.
     pub fn validate(&self) -> Result<(), Error> {
         if self.slot == Default::default() {
             Err(Error::UninitializedConsensusStateField("slot"))
         } else if self.storage_root.as_bytes().is_empty() {
             Err(Error::UninitializedConsensusStateField("storage_root"))
+        } else if self.storage_root.as_bytes().len() != 32 {
+            Err(Error::InvalidStorageRootLength(
+                self.storage_root.as_bytes().len(),
+            ))
+        } else if self.storage_root.as_bytes() == H256::default().as_bytes() {
+            Err(Error::ZeroConsensusStateStorageRoot)
         } else if self.timestamp == Timestamp::default() {
             Err(Error::UninitializedConsensusStateField("timestamp"))
         } else if self.current_sync_committee == PublicKey::default() {
             Err(Error::UninitializedConsensusStateField(
                 "current_sync_committee",
             ))
+        } else if self.current_sync_committee.to_vec() == INFINITY_PUBKEY {
+            Err(Error::InfinitySyncCommitteeAggregatePubkey(
+                "current_sync_committee",
+            ))
         } else if self.next_sync_committee == PublicKey::default() {
             Err(Error::UninitializedConsensusStateField(
                 "next_sync_committee",
             ))
+        } else if self.next_sync_committee.to_vec() == INFINITY_PUBKEY {
+            Err(Error::InfinitySyncCommitteeAggregatePubkey(
+                "next_sync_committee",
+            ))
         } else {
             Ok(())
         }
     }
 
+    /// Returns true if `root_bytes` equals the commitment root of the consensus state, i.e. the storage root of the IBC contract
+    pub fn matches_commitment(&self, root_bytes: &[u8]) -> bool {
+        self.storage_root.as_bytes() == root_bytes
+    }
+
+    /// Returns the hash of the finalized execution block if known
+    pub fn current_block_hash(&self) -> Option<H256> {
+        self.current_block_hash
+    }
+
+    /// Encode the consensus state into `Any` deterministically
+    pub fn into_any(self) -> IBCAny {
+        self.into()
+    }
+
+    /// Returns the length of the proto-encoded consensus state without encoding it
+    ///
+    /// The length is bounded by `MAX_CONSENSUS_STATE_ENCODED_LEN` since all fields have a fixed length except the varints.
+    pub fn encoded_len(&self) -> usize {
+        use prost::Message;
+        RawConsensusState::from(self.clone()).encoded_len()
+    }
+
+    /// Encode the consensus state in the layout of Solidity's `abi.encodePacked`
+    ///
+    /// The layout is `uint64 slot || bytes32 storageRoot || uint64 timestamp || bytes48 currentSyncCommittee || bytes48 nextSyncCommittee`,
+    /// where the integers are big-endian and the timestamp is in nanoseconds, so the encoding is always `ABI_ENCODED_CONSENSUS_STATE_LEN` bytes.
+    /// `current_block_hash` is not encoded since it is not a part of the commitment root.
+    /// Returns `Error::InvalidStorageRootLength` if the storage root is not 32 bytes.
+    pub fn abi_encode(&self) -> Result<[u8; ABI_ENCODED_CONSENSUS_STATE_LEN], Error> {
+        let storage_root = self.storage_root.as_bytes();
+        if storage_root.len() != 32 {
+            return Err(Error::InvalidStorageRootLength(storage_root.len()));
+        }
+        let mut bz = [0u8; ABI_ENCODED_CONSENSUS_STATE_LEN];
+        bz[..8].copy_from_slice(&self.slot.0.to_be_bytes());
+        bz[8..40].copy_from_slice(storage_root);
+        bz[40..48].copy_from_slice(&self.timestamp.nanoseconds().to_be_bytes());
+        bz[48..96].copy_from_slice(&self.current_sync_committee.to_vec());
+        bz[96..].copy_from_slice(&self.next_sync_committee.to_vec());
+        Ok(bz)
+    }
+
+    /// Returns `keccak256(abi_encode())`, which is the commitment of the consensus state on the Solidity side
+    pub fn keccak_commitment(&self) -> Result<H256, Error> {
+        Ok(H256(keccak_256(&self.abi_encode()?)))
+    }
+
     pub fn current_period<C: ChainContext>(&self, ctx: &C) -> SyncCommitteePeriod {
         compute_sync_committee_period_at_slot(ctx, self.slot)
     }
+
+    /// Returns the aggregate public key of the next sync committee if `is_next` is true, otherwise the current one
+    ///
+    /// Returns `None` if `is_next` is true and the consensus state doesn't have the next sync committee yet.
+    pub fn sync_committee_aggregate(&self, is_next: bool) -> Option<&PublicKey> {
+        if !is_next {
+            Some(&self.current_sync_committee)
+        } else if self.next_sync_committee == PublicKey::default() {
+            None
+        } else {
+            Some(&self.next_sync_committee)
+        }
+    }
+
+    /// Validate that the trusted sync committee matches the current or next sync committee of the consensus state
+    pub fn validate_trusted_sync_committee<C: ChainContext, const SYNC_COMMITTEE_SIZE: usize>(
+        &self,
+        ctx: &C,
+        trusted_sync_committee: &TrustedSyncCommittee<SYNC_COMMITTEE_SIZE>,
+    ) -> Result<(), Error> {
+        self.validate_sync_committee_aggregate(
+            ctx,
+            &trusted_sync_committee.sync_committee.aggregate_pubkey,
+            trusted_sync_committee.is_next,
+        )
+    }
+
+    fn validate_sync_committee_aggregate<C: ChainContext>(
+        &self,
+        ctx: &C,
+        aggregate_pubkey: &PublicKey,
+        is_next: bool,
+    ) -> Result<(), Error> {
+        match self.sync_committee_aggregate(is_next) {
+            Some(stored) if stored == aggregate_pubkey => Ok(()),
+            Some(stored) if is_next => Err(Error::InvalidNextSyncCommitteeKeys {
+                period: self.current_period(ctx) + 1,
+                stored: stored.clone(),
+                provided: aggregate_pubkey.clone(),
+            }),
+            Some(stored) => Err(Error::InvalidCurrentSyncCommitteeKeys {
+                period: self.current_period(ctx),
+                stored: stored.clone(),
+                provided: aggregate_pubkey.clone(),
+            }),
+            None => Err(Error::NoNextSyncCommitteeInConsensusState),
+        }
+    }
 }
 
 impl Default for ConsensusState {
@@ -75,11 +234,16 @@ impl Default for ConsensusState {
             timestamp: Default::default(),
             current_sync_committee: Default::default(),
             next_sync_committee: Default::default(),
+            current_block_hash: None,
         }
     }
 }
 
 impl Ics02ConsensusState for ConsensusState {
+    /// Returns the storage root of the IBC contract as is
+    ///
+    /// No prefix or hashing is applied, so the root is the 32-byte storage root of the account at `ClientState::ibc_address`,
+    /// which is the root of the MPT that the membership proofs are verified against.
     fn root(&self) -> &CommitmentRoot {
         &self.storage_root
     }
@@ -98,6 +262,12 @@ fn proto_timestamp_to_ibc_timestamp(timestamp: ProtoTimestamp) -> Result<Timesta
             reason: "timestamp seconds or nanos is negative".to_string(),
         });
     }
+    // a timestamp has a single encoding only if the nanos are less than a second, which `ibc_timestamp_to_proto_timestamp` always satisfies
+    if timestamp.nanos > 999_999_999 {
+        return Err(Error::InvalidRawConsensusState {
+            reason: format!("timestamp nanos is out of range: nanos={}", timestamp.nanos),
+        });
+    }
     let nanos = (timestamp.seconds as u64)
         .checked_mul(1_000_000_000)
         .ok_or_else(|| Error::TimestampOverflowError(TimestampOverflow))?
@@ -125,17 +295,31 @@ impl TryFrom<RawConsensusState> for ConsensusState {
         } else {
             PublicKey::try_from(value.next_sync_committee)?
         };
-        Ok(Self {
+        let consensus_state = Self {
             slot: value.slot.into(),
             storage_root: value.storage_root.into(),
-            timestamp: proto_timestamp_to_ibc_timestamp(value.timestamp.ok_or_else(|| {
-                Self::Error::InvalidRawConsensusState {
-                    reason: "timestamp is none".to_string(),
-                }
-            })?)?,
+            timestamp: proto_timestamp_to_ibc_timestamp(
+                value
+                    .timestamp
+                    .ok_or(Error::proto_missing("ConsensusState", "timestamp"))?,
+            )?,
             current_sync_committee: PublicKey::try_from(value.current_sync_committee)?,
             next_sync_committee,
-        })
+            current_block_hash: if value.current_block_hash.is_empty() {
+                None
+            } else if value.current_block_hash.len() == 32 {
+                Some(H256::from_slice(&value.current_block_hash))
+            } else {
+                return Err(Self::Error::InvalidRawConsensusState {
+                    reason: format!(
+                        "invalid current_block_hash length: {}",
+                        value.current_block_hash.len()
+                    ),
+                });
+            },
+        };
+        consensus_state.validate()?;
+        Ok(consensus_state)
     }
 }
 
@@ -147,6 +331,9 @@ impl From<ConsensusState> for RawConsensusState {
             timestamp: Some(ibc_timestamp_to_proto_timestamp(value.timestamp)),
             current_sync_committee: value.current_sync_committee.to_vec(),
             next_sync_committee: value.next_sync_committee.to_vec(),
+            current_block_hash: value
+                .current_block_hash
+                .map_or(Vec::new(), |h| h.as_bytes().to_vec()),
         }
     }
 }
@@ -156,24 +343,32 @@ impl Protobuf<IBCAny> for ConsensusState {}
 impl TryFrom<IBCAny> for ConsensusState {
     type Error = ClientError;
 
+    /// Decode the consensus state from `Any`
+    ///
+    /// The payload must be the canonical encoding of the consensus state, i.e. it must not contain unknown fields or trailing bytes,
+    /// so that the commitment of the consensus state is not malleable.
     fn try_from(raw: IBCAny) -> Result<Self, Self::Error> {
-        use bytes::Buf;
-        use core::ops::Deref;
         use prost::Message;
 
-        fn decode_consensus_state<B: Buf>(buf: B) -> Result<ConsensusState, Error> {
-            RawConsensusState::decode(buf)
-                .map_err(Error::Decode)?
-                .try_into()
+        fn decode_consensus_state(bz: &[u8]) -> Result<ConsensusState, Error> {
+            let raw_consensus_state = RawConsensusState::decode(bz).map_err(Error::Decode)?;
+            if raw_consensus_state.encode_to_vec() != bz {
+                return Err(Error::UnexpectedAnyPayload {
+                    type_url: ETHEREUM_CONSENSUS_STATE_TYPE_URL,
+                });
+            }
+            raw_consensus_state.try_into()
         }
 
         match raw.type_url.as_str() {
             ETHEREUM_CONSENSUS_STATE_TYPE_URL => {
-                decode_consensus_state(raw.value.deref()).map_err(Into::into)
+                decode_consensus_state(&raw.value).map_err(Into::into)
             }
-            _ => Err(ClientError::UnknownConsensusStateType {
-                consensus_state_type: raw.type_url,
-            }),
+            _ => Err(Error::UnexpectedTypeUrl {
+                expected: &[ETHEREUM_CONSENSUS_STATE_TYPE_URL],
+                got: raw.type_url,
+            }
+            .into()),
         }
     }
 }
@@ -196,39 +391,87 @@ pub struct TrustedConsensusState<const SYNC_COMMITTEE_SIZE: usize> {
 }
 
 impl<const SYNC_COMMITTEE_SIZE: usize> TrustedConsensusState<SYNC_COMMITTEE_SIZE> {
-    pub fn new(
+    pub fn new<C: ChainContext>(
+        ctx: &C,
         consensus_state: ConsensusState,
         sync_committee: SyncCommittee<SYNC_COMMITTEE_SIZE>,
         is_next: bool,
     ) -> Result<Self, Error> {
-        sync_committee.validate()?;
-        if !is_next {
-            return if sync_committee.aggregate_pubkey == consensus_state.current_sync_committee {
-                Ok(Self {
-                    state: consensus_state,
-                    current_sync_committee: Some(sync_committee),
-                    next_sync_committee: None,
-                })
-            } else {
-                Err(Error::InvalidCurrentSyncCommitteeKeys(
-                    sync_committee.aggregate_pubkey,
-                    consensus_state.current_sync_committee,
-                ))
-            };
-        }
+        Self::new_with_cache(ctx, consensus_state, sync_committee, is_next, &mut NoCache)
+    }
 
-        if sync_committee.aggregate_pubkey == consensus_state.next_sync_committee {
-            Ok(Self {
-                state: consensus_state,
-                current_sync_committee: None,
-                next_sync_committee: Some(sync_committee),
-            })
+    /// `new` that skips the validation of `sync_committee` if it is in `cache`
+    pub fn new_with_cache<C: ChainContext, SC: SyncCommitteeCache<SYNC_COMMITTEE_SIZE> + ?Sized>(
+        ctx: &C,
+        consensus_state: ConsensusState,
+        sync_committee: SyncCommittee<SYNC_COMMITTEE_SIZE>,
+        is_next: bool,
+        cache: &mut SC,
+    ) -> Result<Self, Error> {
+        validate_sync_committee(cache, &sync_committee)?;
+        consensus_state.validate_sync_committee_aggregate(
+            ctx,
+            &sync_committee.aggregate_pubkey,
+            is_next,
+        )?;
+        let (current_sync_committee, next_sync_committee) = if is_next {
+            (None, Some(sync_committee))
         } else {
-            Err(Error::InvalidNextSyncCommitteeKeys(
-                sync_committee.aggregate_pubkey,
-                consensus_state.next_sync_committee,
-            ))
+            (Some(sync_committee), None)
+        };
+        Ok(Self {
+            state: consensus_state,
+            current_sync_committee,
+            next_sync_committee,
+        })
+    }
+
+    /// Returns `true` if the trusted sync committee is the next sync committee of the consensus state
+    pub fn is_next(&self) -> bool {
+        self.next_sync_committee.is_some()
+    }
+
+    /// Returns the slot of the consensus state
+    pub fn slot(&self) -> Slot {
+        self.state.slot
+    }
+
+    /// Verify that the trusted sync committee is the one that signs an update at `signature_slot`
+    ///
+    /// The signature period must be the period of the consensus state's slot or the next period.
+    /// If the update is signed in the next period, it crosses the period boundary and must be verified with the next sync committee, so `is_next` must be `true`.
+    /// Otherwise, it must be verified with the current sync committee, so `is_next` must be `false`.
+    ///
+    /// If the update is signed more than one period after the trusted period, the consensus state does not know the signing sync committee,
+    /// and `SyncCommitteePeriodSkipped` is returned so that the relayer submits intermediate consensus updates for the skipped periods.
+    pub fn verify_signature_period<C: ChainContext>(
+        &self,
+        ctx: &C,
+        signature_slot: Slot,
+    ) -> Result<(), Error> {
+        let trusted_period = self.state.current_period(ctx);
+        let signature_period = compute_sync_committee_period_at_slot(ctx, signature_slot);
+        if signature_period < trusted_period {
+            return Err(Error::SignaturePeriodOutOfRange {
+                trusted_period,
+                signature_period,
+            });
+        }
+        if signature_period > trusted_period + 1 {
+            return Err(Error::SyncCommitteePeriodSkipped {
+                trusted_period,
+                signature_period,
+            });
+        }
+        let is_next = self.is_next();
+        if is_next != (signature_period == trusted_period + 1) {
+            return Err(Error::UnexpectedTrustedSyncCommittee {
+                is_next,
+                trusted_period,
+                signature_period,
+            });
         }
+        Ok(())
     }
 }
 
@@ -247,20 +490,14 @@ impl<const SYNC_COMMITTEE_SIZE: usize> LightClientStoreReader<SYNC_COMMITTEE_SIZ
         self.next_sync_committee.clone()
     }
 
+    /// Any finalized slot is relevant: an update that does not finalize a later slot than the consensus state is not rejected by the verifier
+    /// but routed by `ClientState::route_update`, so that it never advances the client.
     fn ensure_relevant_update<CC: ChainContext, C: ConsensusUpdate<SYNC_COMMITTEE_SIZE>>(
         &self,
         _ctx: &CC,
-        update: &C,
+        _update: &C,
     ) -> Result<(), ethereum_light_client_verifier::errors::Error> {
-        if self.state.slot >= update.finalized_beacon_header().slot {
-            Err(
-                ethereum_light_client_verifier::errors::Error::IrrelevantConsensusUpdates(
-                    "finalized header slot is not greater than current slot".to_string(),
-                ),
-            )
-        } else {
-            Ok(())
-        }
+        Ok(())
     }
 }
 
@@ -275,11 +512,123 @@ impl<const SYNC_COMMITTEE_SIZE: usize> From<TrustedConsensusState<SYNC_COMMITTEE
 #[cfg(test)]
 mod tests {
     use super::*;
-    use ethereum_consensus::types::H256;
+    use crate::types::tests::{arb_h256, pubkey_pool};
+    use ethereum_consensus::{config, types::U64};
     use ethereum_light_client_verifier::consensus::test_utils::MockSyncCommitteeManager;
+    use ethereum_light_client_verifier::context::{Fraction, LightClientContext};
     use hex_literal::hex;
+    use proptest::prelude::*;
+    use prost::Message;
     use time::macros::datetime;
 
+    /// The sync committees are valid public keys, and the slot and the timestamp are not zero as `validate` requires
+    pub(crate) fn arb_consensus_state() -> impl Strategy<Value = ConsensusState> {
+        let pubkeys = pubkey_pool();
+        (
+            1..=u64::MAX,
+            arb_h256(),
+            1..=u32::MAX as u64 * 1_000_000_000,
+            prop::sample::select(pubkeys.clone()),
+            prop::sample::select(pubkeys),
+            prop::option::of(arb_h256()),
+        )
+            .prop_map(
+                |(
+                    slot,
+                    storage_root,
+                    timestamp,
+                    current_sync_committee,
+                    next_sync_committee,
+                    current_block_hash,
+                )| ConsensusState {
+                    slot: slot.into(),
+                    storage_root: CommitmentRoot::from_bytes(storage_root.as_bytes()),
+                    timestamp: Timestamp::from_nanoseconds(timestamp).unwrap(),
+                    current_sync_committee,
+                    next_sync_committee,
+                    current_block_hash,
+                },
+            )
+    }
+
+    proptest! {
+        #[test]
+        fn prop_consensus_state_round_trip(consensus_state in arb_consensus_state()) {
+            let bz = RawConsensusState::from(consensus_state.clone()).encode_to_vec();
+            let raw = RawConsensusState::decode(bz.as_slice()).unwrap();
+            prop_assert_eq!(ConsensusState::try_from(raw).unwrap(), consensus_state.clone());
+            let any = IBCAny::from(consensus_state.clone());
+            prop_assert_eq!(ConsensusState::try_from(any).unwrap(), consensus_state);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn prop_consensus_state_encoded_len(consensus_state in arb_consensus_state()) {
+            let len = consensus_state.encoded_len();
+            prop_assert_eq!(len, RawConsensusState::from(consensus_state).encode_to_vec().len());
+            prop_assert!(len <= MAX_CONSENSUS_STATE_ENCODED_LEN);
+        }
+    }
+
+    #[test]
+    fn test_decode_initial_schema_consensus_state() {
+        // a consensus state encoded with the initial schema, i.e. without `current_block_hash`
+        let bz = hex::decode(include_str!("../testdata/consensus_state_initial_schema.hex").trim())
+            .unwrap();
+        let consensus_state = ConsensusState::try_from(IBCAny {
+            type_url: ETHEREUM_CONSENSUS_STATE_TYPE_URL.to_string(),
+            value: bz.clone(),
+        })
+        .unwrap();
+        assert_eq!(consensus_state.current_block_hash, None);
+        assert_eq!(consensus_state.slot, U64(9000000));
+        assert_eq!(
+            consensus_state.storage_root.as_bytes(),
+            hex!("27cf5d2ad7e2c8d3d8726d41ac4a098137e3ff1b3e48c6d35754c85bfc43e9b4")
+        );
+        assert_eq!(
+            consensus_state.timestamp,
+            Timestamp::from_nanoseconds(1714000000 * 1_000_000_000).unwrap()
+        );
+        assert_eq!(
+            consensus_state.next_sync_committee.to_vec(),
+            hex!("a42dffb90d85cec7acfcb53be0e8792155d8f18c0dc9efc2a5587d5a0ba3e578df366fc3e2b743de6ecd3b53e345c266").to_vec()
+        );
+        assert_eq!(IBCAny::from(consensus_state).value, bz);
+    }
+
+    #[test]
+    fn test_consensus_state_encoded_len() {
+        let pubkeys = pubkey_pool();
+        let mut consensus_state = ConsensusState {
+            slot: 1_000_000.into(),
+            storage_root: CommitmentRoot::from_bytes(keccak256("storage").as_bytes()),
+            timestamp: Timestamp::from_nanoseconds(1_700_000_000 * 1_000_000_000).unwrap(),
+            current_sync_committee: pubkeys[0].clone(),
+            next_sync_committee: pubkeys[1].clone(),
+            current_block_hash: Some(keccak256("block")),
+        };
+        // slot (4) + storage root (34) + timestamp without nanos (8) + sync committees (100) + current block hash (34)
+        assert_eq!(consensus_state.encoded_len(), 180);
+        consensus_state.current_block_hash = None;
+        assert_eq!(consensus_state.encoded_len(), 146);
+
+        consensus_state.slot = u64::MAX.into();
+        consensus_state.timestamp = Timestamp::from_nanoseconds(u64::MAX).unwrap();
+        consensus_state.current_block_hash = Some(keccak256("block"));
+        assert_eq!(
+            consensus_state.encoded_len(),
+            MAX_CONSENSUS_STATE_ENCODED_LEN
+        );
+        assert_eq!(
+            RawConsensusState::from(consensus_state)
+                .encode_to_vec()
+                .len(),
+            MAX_CONSENSUS_STATE_ENCODED_LEN
+        );
+    }
+
     #[test]
     fn test_consensus_state_conversion() {
         let consensus_state = ConsensusState {
@@ -291,16 +640,364 @@ mod tests {
             .unwrap(),
             current_sync_committee: PublicKey::try_from(hex!("a145063e1b5eda80fa55960296f2c4b2c021f75767318ea2572a9f7abb649010b746754ca7fc2ba57c1156881516a357").to_vec()).unwrap(),
             next_sync_committee: PublicKey::try_from(hex!("a42dffb90d85cec7acfcb53be0e8792155d8f18c0dc9efc2a5587d5a0ba3e578df366fc3e2b743de6ecd3b53e345c266").to_vec()).unwrap(),
+            current_block_hash: None,
         };
         let res = consensus_state.validate();
         assert!(res.is_ok(), "{:?}", res);
         let any_consensus_state = IBCAny::from(consensus_state.clone());
         let consensus_state2 = ConsensusState::try_from(any_consensus_state).unwrap();
         assert_eq!(consensus_state, consensus_state2);
+
+        let raw = RawConsensusState {
+            timestamp: None,
+            ..RawConsensusState::from(consensus_state)
+        };
+        assert_eq!(
+            ConsensusState::try_from(raw).unwrap_err().to_string(),
+            "proto missing field error: `ConsensusState.timestamp`"
+        );
+    }
+
+    #[test]
+    fn test_deserialize_validated_consensus_state() {
+        let consensus_state = ConsensusState {
+            slot: 1.into(),
+            storage_root: CommitmentRoot::from_bytes(keccak256("storage").as_bytes()),
+            timestamp: Timestamp::from_nanoseconds(
+                datetime!(2023-08-20 0:00 UTC).unix_timestamp_nanos() as u64,
+            )
+            .unwrap(),
+            current_sync_committee: PublicKey::try_from(hex!("a145063e1b5eda80fa55960296f2c4b2c021f75767318ea2572a9f7abb649010b746754ca7fc2ba57c1156881516a357").to_vec()).unwrap(),
+            next_sync_committee: PublicKey::try_from(hex!("a42dffb90d85cec7acfcb53be0e8792155d8f18c0dc9efc2a5587d5a0ba3e578df366fc3e2b743de6ecd3b53e345c266").to_vec()).unwrap(),
+            current_block_hash: None,
+        };
+        let json = serde_json::to_value(&consensus_state).unwrap();
+        assert_eq!(
+            ConsensusState::deserialize_validated(json.clone()).unwrap(),
+            consensus_state
+        );
+
+        let mut typo = json;
+        let obj = typo.as_object_mut().unwrap();
+        let value = obj.remove("storage_root").unwrap();
+        obj.insert("storageRoot".into(), value);
+        let err = ConsensusState::deserialize_validated(typo).unwrap_err();
+        assert!(
+            err.to_string().contains("unknown field `storageRoot`"),
+            "{}",
+            err
+        );
+
+        let uninitialized = serde_json::to_value(ConsensusState {
+            slot: 0.into(),
+            ..consensus_state
+        })
+        .unwrap();
+        let err = ConsensusState::deserialize_validated(uninitialized).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            Error::UninitializedConsensusStateField("slot").to_string()
+        );
+    }
+
+    #[test]
+    fn test_deserialize_initial_schema_consensus_state() {
+        let consensus_state = ConsensusState {
+            slot: 1.into(),
+            storage_root: CommitmentRoot::from_bytes(keccak256("storage").as_bytes()),
+            timestamp: Timestamp::from_nanoseconds(
+                datetime!(2023-08-20 0:00 UTC).unix_timestamp_nanos() as u64,
+            )
+            .unwrap(),
+            current_sync_committee: PublicKey::try_from(hex!("a145063e1b5eda80fa55960296f2c4b2c021f75767318ea2572a9f7abb649010b746754ca7fc2ba57c1156881516a357").to_vec()).unwrap(),
+            next_sync_committee: PublicKey::try_from(hex!("a42dffb90d85cec7acfcb53be0e8792155d8f18c0dc9efc2a5587d5a0ba3e578df366fc3e2b743de6ecd3b53e345c266").to_vec()).unwrap(),
+            current_block_hash: Some(keccak256("block")),
+        };
+        // the JSON representation of the initial schema, i.e. without `current_block_hash`
+        let mut json = serde_json::to_value(&consensus_state).unwrap();
+        json.as_object_mut()
+            .unwrap()
+            .remove("current_block_hash")
+            .unwrap();
+        let expected = ConsensusState {
+            current_block_hash: None,
+            ..consensus_state
+        };
+        assert_eq!(
+            ConsensusState::deserialize_validated(json.clone()).unwrap(),
+            expected
+        );
+        assert_eq!(
+            serde_json::from_value::<ConsensusState>(json).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_consensus_state_encoding_snapshot() {
+        let consensus_state = ConsensusState {
+            slot: 1.into(),
+            storage_root: CommitmentRoot::from_bytes(&hex!(
+                "27cd08827e6bf1e435832f4b2660107beb562314287b3fa534f3b189574c0cca"
+            )),
+            timestamp: Timestamp::from_nanoseconds(
+                datetime!(2023-08-20 0:00 UTC).unix_timestamp_nanos() as u64,
+            )
+            .unwrap(),
+            current_sync_committee: PublicKey::try_from(hex!("a145063e1b5eda80fa55960296f2c4b2c021f75767318ea2572a9f7abb649010b746754ca7fc2ba57c1156881516a357").to_vec()).unwrap(),
+            next_sync_committee: PublicKey::try_from(hex!("a42dffb90d85cec7acfcb53be0e8792155d8f18c0dc9efc2a5587d5a0ba3e578df366fc3e2b743de6ecd3b53e345c266").to_vec()).unwrap(),
+            current_block_hash: None,
+        };
+        // changing these bytes breaks the commitments of the existing consensus states
+        let expected = hex!("0801122027cd08827e6bf1e435832f4b2660107beb562314287b3fa534f3b189574c0cca1a060880af85a7062230a145063e1b5eda80fa55960296f2c4b2c021f75767318ea2572a9f7abb649010b746754ca7fc2ba57c1156881516a3572a30a42dffb90d85cec7acfcb53be0e8792155d8f18c0dc9efc2a5587d5a0ba3e578df366fc3e2b743de6ecd3b53e345c266").to_vec();
+        let any_consensus_state = consensus_state.clone().into_any();
+        assert_eq!(
+            any_consensus_state.type_url,
+            ETHEREUM_CONSENSUS_STATE_TYPE_URL
+        );
+        assert_eq!(any_consensus_state.value, expected);
+        assert_eq!(
+            ConsensusState::try_from(any_consensus_state.clone()).unwrap(),
+            consensus_state
+        );
+
+        // unknown field
+        let mut any = any_consensus_state.clone();
+        any.value.extend_from_slice(&[0x30, 0x01]);
+        let res = ConsensusState::try_from(any);
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            ClientError::from(Error::UnexpectedAnyPayload {
+                type_url: ETHEREUM_CONSENSUS_STATE_TYPE_URL
+            })
+            .to_string()
+        );
+        // trailing garbage
+        let mut any = any_consensus_state.clone();
+        any.value.push(0);
+        let res = ConsensusState::try_from(any);
+        assert!(res.is_err(), "{:?}", res);
+
+        // the same timestamp with a second carried into the nanos is a canonical proto encoding, but not of this consensus state
+        let mut raw = RawConsensusState::decode(any_consensus_state.value.as_slice()).unwrap();
+        let timestamp = raw.timestamp.as_mut().unwrap();
+        timestamp.seconds -= 1;
+        timestamp.nanos += 1_000_000_000;
+        let any = IBCAny {
+            type_url: ETHEREUM_CONSENSUS_STATE_TYPE_URL.to_string(),
+            value: raw.encode_to_vec(),
+        };
+        let res = ConsensusState::try_from(any);
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            ClientError::from(Error::InvalidRawConsensusState {
+                reason: "timestamp nanos is out of range: nanos=1000000000".to_string()
+            })
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_consensus_state_abi_encoding() {
+        let consensus_state = ConsensusState {
+            slot: 1.into(),
+            storage_root: CommitmentRoot::from_bytes(&hex!(
+                "27cd08827e6bf1e435832f4b2660107beb562314287b3fa534f3b189574c0cca"
+            )),
+            timestamp: Timestamp::from_nanoseconds(
+                datetime!(2023-08-20 0:00 UTC).unix_timestamp_nanos() as u64,
+            )
+            .unwrap(),
+            current_sync_committee: PublicKey::try_from(hex!("a145063e1b5eda80fa55960296f2c4b2c021f75767318ea2572a9f7abb649010b746754ca7fc2ba57c1156881516a357").to_vec()).unwrap(),
+            next_sync_committee: PublicKey::try_from(hex!("a42dffb90d85cec7acfcb53be0e8792155d8f18c0dc9efc2a5587d5a0ba3e578df366fc3e2b743de6ecd3b53e345c266").to_vec()).unwrap(),
+            current_block_hash: None,
+        };
+        // `abi.encodePacked(uint64(1), storageRoot, uint64(1692489600000000000), currentSyncCommittee, nextSyncCommittee)` on the Solidity side;
+        // changing these bytes breaks the commitments verified by the counterparty
+        let expected = hex!("000000000000000127cd08827e6bf1e435832f4b2660107beb562314287b3fa534f3b189574c0cca177cee52f20b0000a145063e1b5eda80fa55960296f2c4b2c021f75767318ea2572a9f7abb649010b746754ca7fc2ba57c1156881516a357a42dffb90d85cec7acfcb53be0e8792155d8f18c0dc9efc2a5587d5a0ba3e578df366fc3e2b743de6ecd3b53e345c266");
+        assert_eq!(consensus_state.abi_encode().unwrap(), expected);
+        assert_eq!(
+            consensus_state.keccak_commitment().unwrap(),
+            H256(hex!(
+                "2a66c3ff3e5f67181eabea2dbe3e31811708371e563393a285638ac9c216d327"
+            ))
+        );
+
+        // the block hash is not committed
+        let with_block_hash = ConsensusState {
+            current_block_hash: Some(H256([1u8; 32])),
+            ..consensus_state.clone()
+        };
+        assert_eq!(with_block_hash.abi_encode().unwrap(), expected);
+
+        // the integers have a fixed width regardless of their values
+        let max = ConsensusState {
+            slot: u64::MAX.into(),
+            timestamp: Timestamp::from_nanoseconds(u64::MAX).unwrap(),
+            ..consensus_state.clone()
+        };
+        let bz = max.abi_encode().unwrap();
+        assert_eq!(bz[..8], [0xff; 8]);
+        assert_eq!(bz[8..40], expected[8..40]);
+        assert_eq!(bz[40..48], [0xff; 8]);
+        assert_eq!(bz[48..], expected[48..]);
+        assert_ne!(
+            max.keccak_commitment().unwrap(),
+            consensus_state.keccak_commitment().unwrap()
+        );
+
+        let invalid = ConsensusState {
+            storage_root: CommitmentRoot::from_bytes(&[1u8; 31]),
+            ..consensus_state
+        };
+        let res = invalid.keccak_commitment();
+        assert!(
+            matches!(res, Err(Error::InvalidStorageRootLength(31))),
+            "{:?}",
+            res
+        );
+    }
+
+    #[test]
+    fn test_consensus_state_block_hash_compatibility() {
+        // encoded before `current_block_hash` was introduced
+        let old_bytes = hex!("0801122027cd08827e6bf1e435832f4b2660107beb562314287b3fa534f3b189574c0cca1a060880af85a7062230a145063e1b5eda80fa55960296f2c4b2c021f75767318ea2572a9f7abb649010b746754ca7fc2ba57c1156881516a3572a30a42dffb90d85cec7acfcb53be0e8792155d8f18c0dc9efc2a5587d5a0ba3e578df366fc3e2b743de6ecd3b53e345c266").to_vec();
+        let consensus_state = ConsensusState::try_from(IBCAny {
+            type_url: ETHEREUM_CONSENSUS_STATE_TYPE_URL.to_string(),
+            value: old_bytes.clone(),
+        })
+        .unwrap();
+        assert_eq!(consensus_state.current_block_hash(), None);
+        assert_eq!(consensus_state.clone().into_any().value, old_bytes);
+
+        let block_hash = H256(hex!(
+            "9b1c6c5b4a1e5a8b0f8f5ff1d1d0f0b5a2b0e4d7c3f6b2a1e0d9c8b7a6f5e4d3"
+        ));
+        let consensus_state = ConsensusState {
+            current_block_hash: Some(block_hash),
+            ..consensus_state
+        };
+        let any_consensus_state = consensus_state.clone().into_any();
+        // the new field is appended to the old encoding
+        let mut expected = old_bytes;
+        expected.extend_from_slice(&[0x32, 0x20]);
+        expected.extend_from_slice(block_hash.as_bytes());
+        assert_eq!(any_consensus_state.value, expected);
+        let consensus_state2 = ConsensusState::try_from(any_consensus_state).unwrap();
+        assert_eq!(consensus_state2.current_block_hash(), Some(block_hash));
+        assert_eq!(consensus_state, consensus_state2);
+        // the commitment root is not affected
+        assert_eq!(consensus_state.root(), consensus_state2.root());
+
+        let mut raw = RawConsensusState::from(consensus_state);
+        raw.current_block_hash.pop();
+        assert!(ConsensusState::try_from(raw).is_err());
+    }
+
+    #[test]
+    fn test_consensus_state_validation() {
+        let consensus_state = ConsensusState {
+            slot: 1.into(),
+            storage_root: CommitmentRoot::from_bytes(keccak256("storage").as_bytes()),
+            timestamp: Timestamp::from_nanoseconds(
+                datetime!(2023-08-20 0:00 UTC).unix_timestamp_nanos() as u64,
+            )
+            .unwrap(),
+            current_sync_committee: PublicKey::try_from(hex!("a145063e1b5eda80fa55960296f2c4b2c021f75767318ea2572a9f7abb649010b746754ca7fc2ba57c1156881516a357").to_vec()).unwrap(),
+            next_sync_committee: PublicKey::try_from(hex!("a42dffb90d85cec7acfcb53be0e8792155d8f18c0dc9efc2a5587d5a0ba3e578df366fc3e2b743de6ecd3b53e345c266").to_vec()).unwrap(),
+            current_block_hash: None,
+        };
+        assert!(consensus_state.validate().is_ok());
+
+        let infinity = PublicKey::try_from(INFINITY_PUBKEY.to_vec()).unwrap();
+        let cases = [
+            (
+                ConsensusState {
+                    slot: 0.into(),
+                    ..consensus_state.clone()
+                },
+                Error::UninitializedConsensusStateField("slot"),
+            ),
+            (
+                ConsensusState {
+                    storage_root: CommitmentRoot::from_bytes(&[]),
+                    ..consensus_state.clone()
+                },
+                Error::UninitializedConsensusStateField("storage_root"),
+            ),
+            (
+                ConsensusState {
+                    storage_root: CommitmentRoot::from_bytes(&[1u8; 31]),
+                    ..consensus_state.clone()
+                },
+                Error::InvalidStorageRootLength(31),
+            ),
+            (
+                ConsensusState {
+                    storage_root: CommitmentRoot::from_bytes(H256::default().as_bytes()),
+                    ..consensus_state.clone()
+                },
+                Error::ZeroConsensusStateStorageRoot,
+            ),
+            (
+                ConsensusState {
+                    timestamp: Timestamp::default(),
+                    ..consensus_state.clone()
+                },
+                Error::UninitializedConsensusStateField("timestamp"),
+            ),
+            (
+                ConsensusState {
+                    current_sync_committee: PublicKey::default(),
+                    ..consensus_state.clone()
+                },
+                Error::UninitializedConsensusStateField("current_sync_committee"),
+            ),
+            (
+                ConsensusState {
+                    current_sync_committee: infinity.clone(),
+                    ..consensus_state.clone()
+                },
+                Error::InfinitySyncCommitteeAggregatePubkey("current_sync_committee"),
+            ),
+            (
+                ConsensusState {
+                    next_sync_committee: PublicKey::default(),
+                    ..consensus_state.clone()
+                },
+                Error::UninitializedConsensusStateField("next_sync_committee"),
+            ),
+            (
+                ConsensusState {
+                    next_sync_committee: infinity,
+                    ..consensus_state.clone()
+                },
+                Error::InfinitySyncCommitteeAggregatePubkey("next_sync_committee"),
+            ),
+        ];
+        for (cs, expected) in cases {
+            let res = cs.validate();
+            assert_eq!(
+                res.as_ref().map_err(|e| e.to_string()),
+                Err(expected.to_string()),
+                "{:?}",
+                res
+            );
+            // decoding also rejects the invalid state
+            let res = ConsensusState::try_from(RawConsensusState::from(cs));
+            assert!(res.is_err(), "{:?}", res);
+        }
     }
 
     #[test]
     fn test_trusted_consensus_state() {
+        let ctx = LightClientContext::new_with_config(
+            config::minimal::get_config(),
+            Default::default(),
+            Default::default(),
+            Fraction::new(2, 3).unwrap(),
+            Default::default(),
+        );
         let scm = MockSyncCommitteeManager::<32>::new(1, 2);
         let current_sync_committee = scm.get_committee(1);
         let next_sync_committee = scm.get_committee(2);
@@ -314,9 +1011,11 @@ mod tests {
             .unwrap(),
             current_sync_committee: current_sync_committee.to_committee().aggregate_pubkey,
             next_sync_committee: next_sync_committee.to_committee().aggregate_pubkey,
+            current_block_hash: None,
         };
 
         let res = TrustedConsensusState::new(
+            &ctx,
             consensus_state.clone(),
             current_sync_committee.to_committee(),
             false,
@@ -326,6 +1025,7 @@ mod tests {
         assert!(state.current_sync_committee.is_some());
         assert!(state.next_sync_committee.is_none());
         let res = TrustedConsensusState::new(
+            &ctx,
             consensus_state.clone(),
             current_sync_committee.to_committee(),
             true,
@@ -333,6 +1033,7 @@ mod tests {
         assert!(res.is_err(), "{:?}", res);
 
         let res = TrustedConsensusState::new(
+            &ctx,
             consensus_state.clone(),
             next_sync_committee.to_committee(),
             true,
@@ -342,6 +1043,7 @@ mod tests {
         assert!(state.current_sync_committee.is_none());
         assert!(state.next_sync_committee.is_some());
         let res = TrustedConsensusState::new(
+            &ctx,
             consensus_state.clone(),
             next_sync_committee.to_committee(),
             false,
@@ -349,6 +1051,232 @@ mod tests {
         assert!(res.is_err(), "{:?}", res);
     }
 
+    #[test]
+    fn test_verify_signature_period() {
+        let ctx = LightClientContext::new_with_config(
+            config::minimal::get_config(),
+            Default::default(),
+            Default::default(),
+            Fraction::new(2, 3).unwrap(),
+            Default::default(),
+        );
+        let scm = MockSyncCommitteeManager::<32>::new(1, 2);
+        // 64 slots per period in the minimal preset
+        let first_slot = |period: u64| U64(period * 64);
+        let last_slot = |period: u64| U64(period * 64 + 63);
+        let trusted = |slot: Slot, is_next: bool| {
+            let consensus_state = ConsensusState {
+                slot,
+                storage_root: CommitmentRoot::from_bytes(keccak256("storage").as_bytes()),
+                timestamp: Timestamp::from_nanoseconds(
+                    datetime!(2023-08-20 0:00 UTC).unix_timestamp_nanos() as u64,
+                )
+                .unwrap(),
+                current_sync_committee: scm.get_committee(1).to_committee().aggregate_pubkey,
+                next_sync_committee: scm.get_committee(2).to_committee().aggregate_pubkey,
+                current_block_hash: None,
+            };
+            let sync_committee = scm.get_committee(if is_next { 2 } else { 1 });
+            TrustedConsensusState::new(
+                &ctx,
+                consensus_state,
+                sync_committee.to_committee(),
+                is_next,
+            )
+            .unwrap()
+        };
+
+        for slot in [first_slot(1), last_slot(1)] {
+            // the current sync committee signs the updates in the same period
+            let state = trusted(slot, false);
+            assert!(!state.is_next());
+            for signature_slot in [first_slot(1), last_slot(1)] {
+                let res = state.verify_signature_period(&ctx, signature_slot);
+                assert!(res.is_ok(), "{:?}", res);
+            }
+            let res = state.verify_signature_period(&ctx, first_slot(2));
+            match res {
+                Err(Error::UnexpectedTrustedSyncCommittee {
+                    is_next: false,
+                    trusted_period,
+                    signature_period,
+                }) => {
+                    assert_eq!(trusted_period, U64(1));
+                    assert_eq!(signature_period, U64(2));
+                }
+                res => panic!("unexpected result: {:?}", res),
+            }
+
+            // the next sync committee signs the updates in the next period
+            let state = trusted(slot, true);
+            assert!(state.is_next());
+            for signature_slot in [first_slot(2), last_slot(2)] {
+                let res = state.verify_signature_period(&ctx, signature_slot);
+                assert!(res.is_ok(), "{:?}", res);
+            }
+            let res = state.verify_signature_period(&ctx, last_slot(1));
+            assert!(
+                matches!(
+                    res,
+                    Err(Error::UnexpectedTrustedSyncCommittee { is_next: true, .. })
+                ),
+                "{:?}",
+                res
+            );
+
+            for is_next in [false, true] {
+                let state = trusted(slot, is_next);
+                // the update is signed before the trusted period
+                let res = state.verify_signature_period(&ctx, last_slot(0));
+                assert!(
+                    matches!(
+                        res,
+                        Err(Error::SignaturePeriodOutOfRange {
+                            trusted_period: U64(1),
+                            signature_period: U64(0),
+                        })
+                    ),
+                    "{:?}",
+                    res
+                );
+                // the update skips the next period
+                for signature_slot in [first_slot(3), last_slot(3)] {
+                    let res = state.verify_signature_period(&ctx, signature_slot);
+                    assert!(
+                        matches!(
+                            res,
+                            Err(Error::SyncCommitteePeriodSkipped {
+                                trusted_period: U64(1),
+                                signature_period: U64(3),
+                            })
+                        ),
+                        "{:?}",
+                        res
+                    );
+                }
+            }
+        }
+
+        let err = trusted(first_slot(1), true)
+            .verify_signature_period(&ctx, first_slot(4))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the update skips a sync committee period, so the trusted consensus state does not know the sync committee that signed it: trusted_period=1 signature_period=4. Submit the header with intermediate consensus updates for the skipped periods"
+        );
+    }
+
+    #[test]
+    fn test_validate_trusted_sync_committee() {
+        let ctx = LightClientContext::new_with_config(
+            config::minimal::get_config(),
+            Default::default(),
+            Default::default(),
+            Fraction::new(2, 3).unwrap(),
+            Default::default(),
+        );
+        let scm = MockSyncCommitteeManager::<32>::new(1, 3);
+        let current_sync_committee = scm.get_committee(1).to_committee();
+        let next_sync_committee = scm.get_committee(2).to_committee();
+        let trusted_sync_committee =
+            |sync_committee: &SyncCommittee<32>, is_next: bool| TrustedSyncCommittee {
+                height: ibc::Height::new(0, 1).unwrap(),
+                sync_committee: sync_committee.clone(),
+                is_next,
+            };
+
+        let consensus_state = ConsensusState {
+            slot: 64.into(),
+            current_sync_committee: current_sync_committee.aggregate_pubkey.clone(),
+            next_sync_committee: next_sync_committee.aggregate_pubkey.clone(),
+            ..Default::default()
+        };
+        assert_eq!(
+            consensus_state.sync_committee_aggregate(false),
+            Some(&current_sync_committee.aggregate_pubkey)
+        );
+        assert_eq!(
+            consensus_state.sync_committee_aggregate(true),
+            Some(&next_sync_committee.aggregate_pubkey)
+        );
+
+        // matching current
+        let res = consensus_state.validate_trusted_sync_committee(
+            &ctx,
+            &trusted_sync_committee(&current_sync_committee, false),
+        );
+        assert!(res.is_ok(), "{:?}", res);
+        // matching next
+        let res = consensus_state.validate_trusted_sync_committee(
+            &ctx,
+            &trusted_sync_committee(&next_sync_committee, true),
+        );
+        assert!(res.is_ok(), "{:?}", res);
+        // mismatching
+        let res = consensus_state.validate_trusted_sync_committee(
+            &ctx,
+            &trusted_sync_committee(&next_sync_committee, false),
+        );
+        match &res {
+            Err(Error::InvalidCurrentSyncCommitteeKeys {
+                period,
+                stored,
+                provided,
+            }) => {
+                // slot 64 is the first slot of period 1 in the minimal preset
+                assert_eq!(*period, 1.into());
+                assert_eq!(stored, &current_sync_committee.aggregate_pubkey);
+                assert_eq!(provided, &next_sync_committee.aggregate_pubkey);
+            }
+            res => panic!("unexpected result: {:?}", res),
+        }
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .starts_with("invalid current sync committee keys: period=1 stored="));
+        let res = consensus_state.validate_trusted_sync_committee(
+            &ctx,
+            &trusted_sync_committee(&current_sync_committee, true),
+        );
+        match &res {
+            Err(Error::InvalidNextSyncCommitteeKeys {
+                period,
+                stored,
+                provided,
+            }) => {
+                assert_eq!(*period, 2.into());
+                assert_eq!(stored, &next_sync_committee.aggregate_pubkey);
+                assert_eq!(provided, &current_sync_committee.aggregate_pubkey);
+            }
+            res => panic!("unexpected result: {:?}", res),
+        }
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .starts_with("invalid next sync committee keys: period=2 stored="));
+
+        // no next sync committee stored yet
+        let consensus_state = ConsensusState {
+            next_sync_committee: PublicKey::default(),
+            ..consensus_state
+        };
+        assert_eq!(consensus_state.sync_committee_aggregate(true), None);
+        let res = consensus_state.validate_trusted_sync_committee(
+            &ctx,
+            &trusted_sync_committee(&next_sync_committee, true),
+        );
+        assert!(
+            matches!(res, Err(Error::NoNextSyncCommitteeInConsensusState)),
+            "{:?}",
+            res
+        );
+        let res = consensus_state.validate_trusted_sync_committee(
+            &ctx,
+            &trusted_sync_committee(&current_sync_committee, false),
+        );
+        assert!(res.is_ok(), "{:?}", res);
+    }
+
     #[test]
     fn test_timestamp() {
         {
@@ -372,6 +1300,21 @@ mod tests {
             let it2 = proto_timestamp_to_ibc_timestamp(pt1).unwrap();
             assert_eq!(it1, it2);
         }
+
+        {
+            // nanos is less than a second
+            let pt = ProtoTimestamp {
+                seconds: 1,
+                nanos: 999_999_999,
+            };
+            let it = proto_timestamp_to_ibc_timestamp(pt).unwrap();
+            assert_eq!(it.nanoseconds(), 1_999_999_999);
+            let res = proto_timestamp_to_ibc_timestamp(ProtoTimestamp {
+                seconds: 1,
+                nanos: 1_000_000_000,
+            });
+            assert!(res.is_err(), "{:?}", res);
+        }
     }
 
     fn keccak256(s: &str) -> H256 {
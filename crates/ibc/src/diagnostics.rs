@@ -0,0 +1,382 @@
+use crate::client_state::{required_trust_level_participants, ClientState};
+use crate::consensus_state::ConsensusState;
+use crate::internal_prelude::*;
+use crate::misc::HostTime;
+use crate::types::ConsensusUpdateInfo;
+use core::fmt;
+use core::time::Duration;
+use ethereum_consensus::beacon::{Epoch, Slot, Version};
+use ethereum_consensus::bls::PublicKey;
+use ethereum_consensus::sync_protocol::SyncCommitteePeriod;
+use ethereum_consensus::types::U64;
+use ibc::Height;
+use serde::{Deserialize, Serialize};
+
+/// The number of leading bytes of an aggregate public key used as its fingerprint
+pub const FINGERPRINT_BYTES: usize = 8;
+
+/// A snapshot of the client's view of the chain for debugging
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostics {
+    /// The latest block number of the stored consensus state
+    pub latest_execution_block_number: U64,
+    /// The finalized slot of the consensus state
+    pub slot: Slot,
+    /// The sync committee period corresponding to `slot`
+    pub current_sync_committee_period: SyncCommitteePeriod,
+    /// `current_sync_committee_period + 1`
+    pub next_sync_committee_period: SyncCommitteePeriod,
+    /// The fingerprint of the current sync committee's aggregate public key
+    pub current_sync_committee_fingerprint: String,
+    /// The fingerprint of the next sync committee's aggregate public key. `None` if the consensus state has no next sync committee.
+    pub next_sync_committee_fingerprint: Option<String>,
+    /// The remaining trusting period of the consensus state. `None` if the trusting period has expired.
+    pub trusting_period_remaining: Option<Duration>,
+    /// The height at which the client is frozen. `None` if the client is unfrozen.
+    pub frozen_height: Option<Height>,
+    /// The epoch of the fork in effect at `slot`. `None` if `slot` is before the first fork.
+    pub fork_epoch: Option<Epoch>,
+    /// The version of the fork in effect at `slot`
+    pub fork_version: Version,
+}
+
+/// Whether a consensus update would pass the thresholds of the client, see `ClientState::check_update_viability`
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpdateViability {
+    /// The number of sync committee members that signed the update
+    pub participants: usize,
+    /// The least number of participants satisfying both `min_sync_committee_participants` and `trust_level`
+    pub required_participants: usize,
+    /// The sync committee period of the trusted consensus state
+    pub trusted_period: SyncCommitteePeriod,
+    /// The sync committee period of the signature slot
+    pub signature_period: SyncCommitteePeriod,
+    /// `true` if the update is signed in the period after `trusted_period`, so `TrustedSyncCommittee::is_next` of the header must be `true`
+    pub is_next: bool,
+    /// `true` if the update is signed more than one period after `trusted_period`, so the intermediate updates of the skipped periods are required
+    pub skips_period: bool,
+    /// `true` if the finalized header is in the period after `trusted_period`, so the update must include the next sync committee to rotate the sync committees
+    pub rotation_required: bool,
+    /// `true` if the update includes the next sync committee
+    pub has_next_sync_committee: bool,
+    /// The finalized slot of the update, which is the slot of the new consensus state
+    ///
+    /// The new consensus state is stored at the height of the execution block number of the header, which the consensus update does not contain.
+    pub finalized_slot: Slot,
+    /// `true` if `finalized_slot` is after the slot of the trusted consensus state
+    pub advances: bool,
+}
+
+impl UpdateViability {
+    /// Returns `true` if the update would pass all the checks of `UpdateViability`
+    ///
+    /// The update may still be rejected by the verification of its signature and merkle branches, which `check_update_viability` does not perform.
+    pub fn is_viable(&self) -> bool {
+        self.participants >= self.required_participants
+            && self.signature_period >= self.trusted_period
+            && !self.skips_period
+            && (!self.rotation_required || self.has_next_sync_committee)
+            && self.advances
+    }
+}
+
+impl<const SYNC_COMMITTEE_SIZE: usize> ClientState<SYNC_COMMITTEE_SIZE> {
+    /// Returns whether `update` would pass the thresholds of the client against `trusted_consensus_state`, and which trusted sync committee the header must attach
+    ///
+    /// Only the participation bits and the slots of the update are inspected, without the BLS signature or the merkle branches,
+    /// so a relayer can check a candidate update cheaply and without a host context before submitting it.
+    pub fn check_update_viability(
+        &self,
+        trusted_consensus_state: &ConsensusState,
+        update: &ConsensusUpdateInfo<SYNC_COMMITTEE_SIZE>,
+    ) -> UpdateViability {
+        let participants = update.sync_aggregate.sync_committee_bits.count_ones();
+        let required_participants = (self.min_sync_committee_participants.0 as usize).max(
+            required_trust_level_participants(&self.trust_level, SYNC_COMMITTEE_SIZE),
+        );
+        let trusted_period = self.compute_period_at_slot(trusted_consensus_state.slot);
+        let signature_period = update.signature_period(self);
+        let finalized_slot = update.finalized_header.0.slot;
+        UpdateViability {
+            participants,
+            required_participants,
+            trusted_period,
+            signature_period,
+            is_next: signature_period == trusted_period + 1,
+            skips_period: signature_period > trusted_period + 1,
+            rotation_required: self.compute_period_at_slot(finalized_slot) == trusted_period + 1,
+            has_next_sync_committee: update.next_sync_committee.is_some(),
+            finalized_slot,
+            advances: finalized_slot > trusted_consensus_state.slot,
+        }
+    }
+
+    /// Returns the diagnostics of the client with the given consensus state at the current time of `host`
+    ///
+    /// The trusting period is reported as expired if its end overflows, since the verification rejects the consensus state in that case too.
+    pub fn diagnostics(
+        &self,
+        consensus_state: &ConsensusState,
+        host: impl HostTime,
+    ) -> Diagnostics {
+        let slot = consensus_state.slot;
+        let epoch = slot / self.slots_per_epoch;
+        let current_sync_committee_period = self.compute_period_at_slot(slot);
+        let trusting_period_remaining = (consensus_state.timestamp + self.trusting_period)
+            .ok()
+            .and_then(|end| end.duration_since(&host.now()))
+            .filter(|remaining| !remaining.is_zero());
+        let fork = self
+            .fork_parameters
+            .forks()
+            .iter()
+            .rev()
+            .find(|f| f.epoch <= epoch);
+        Diagnostics {
+            latest_execution_block_number: self.latest_execution_block_number,
+            slot,
+            current_sync_committee_period,
+            next_sync_committee_period: current_sync_committee_period + 1,
+            current_sync_committee_fingerprint: fingerprint(
+                &consensus_state.current_sync_committee,
+            ),
+            next_sync_committee_fingerprint: if consensus_state.next_sync_committee
+                == PublicKey::default()
+            {
+                None
+            } else {
+                Some(fingerprint(&consensus_state.next_sync_committee))
+            },
+            trusting_period_remaining,
+            frozen_height: self.frozen_height,
+            fork_epoch: fork.map(|f| f.epoch),
+            fork_version: fork.map_or(self.fork_parameters.genesis_version().clone(), |f| {
+                f.version.clone()
+            }),
+        }
+    }
+}
+
+impl fmt::Display for Diagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "latest_execution_block_number: {}",
+            self.latest_execution_block_number
+        )?;
+        writeln!(f, "slot: {}", self.slot)?;
+        writeln!(
+            f,
+            "current_sync_committee: period={} fingerprint={}",
+            self.current_sync_committee_period, self.current_sync_committee_fingerprint
+        )?;
+        match &self.next_sync_committee_fingerprint {
+            Some(fp) => writeln!(
+                f,
+                "next_sync_committee: period={} fingerprint={}",
+                self.next_sync_committee_period, fp
+            )?,
+            None => writeln!(f, "next_sync_committee: none")?,
+        }
+        match self.trusting_period_remaining {
+            Some(remaining) => writeln!(f, "trusting_period_remaining: {}s", remaining.as_secs())?,
+            None => writeln!(f, "trusting_period_remaining: expired")?,
+        }
+        match self.frozen_height {
+            Some(h) => writeln!(f, "frozen_height: {}", h)?,
+            None => writeln!(f, "frozen_height: none")?,
+        }
+        match self.fork_epoch {
+            Some(epoch) => write!(
+                f,
+                "fork: version=0x{} epoch={}",
+                to_hex(&self.fork_version.0),
+                epoch
+            ),
+            None => write!(
+                f,
+                "fork: version=0x{} genesis",
+                to_hex(&self.fork_version.0)
+            ),
+        }
+    }
+}
+
+/// Returns the first `FINGERPRINT_BYTES` bytes of `key` as a 0x-prefixed hex string
+pub(crate) fn fingerprint(key: &PublicKey) -> String {
+    let bz = key.to_vec();
+    format!("0x{}", to_hex(&bz[..FINGERPRINT_BYTES.min(bz.len())]))
+}
+
+fn to_hex(bz: &[u8]) -> String {
+    bz.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::misc::height_from_block_number;
+    use crate::preset::mainnet_fork_parameters;
+    use crate::testing::MockContext;
+    use crate::MinimalClientState;
+    use ethereum_consensus::preset::minimal::PRESET;
+    use ethereum_consensus::sync_protocol::SyncCommittee;
+    use ethereum_light_client_verifier::context::Fraction;
+    use hex_literal::hex;
+    use ibc::timestamp::Timestamp;
+
+    #[test]
+    fn test_diagnostics() {
+        let client_state = MinimalClientState {
+            slots_per_epoch: PRESET.SLOTS_PER_EPOCH,
+            epochs_per_sync_committee_period: PRESET.EPOCHS_PER_SYNC_COMMITTEE_PERIOD,
+            fork_parameters: mainnet_fork_parameters(),
+            trusting_period: Duration::from_secs(100),
+            latest_execution_block_number: 10.into(),
+            ..Default::default()
+        };
+        // 8 slots per epoch and 8 epochs per period in the minimal preset
+        let consensus_state = ConsensusState {
+            slot: U64(8 * 8 * 144896 + 3),
+            timestamp: Timestamp::from_nanoseconds(1_000 * 1_000_000_000).unwrap(),
+            current_sync_committee: PublicKey::try_from(hex!("a145063e1b5eda80fa55960296f2c4b2c021f75767318ea2572a9f7abb649010b746754ca7fc2ba57c1156881516a357").to_vec()).unwrap(),
+            ..Default::default()
+        };
+        let now = Timestamp::from_nanoseconds(1_030 * 1_000_000_000).unwrap();
+        let diagnostics = client_state.diagnostics(&consensus_state, now);
+        assert_eq!(diagnostics.latest_execution_block_number, U64(10));
+        assert_eq!(diagnostics.current_sync_committee_period, U64(144896));
+        assert_eq!(diagnostics.next_sync_committee_period, U64(144897));
+        assert_eq!(
+            diagnostics.current_sync_committee_fingerprint,
+            "0xa145063e1b5eda80"
+        );
+        assert_eq!(diagnostics.next_sync_committee_fingerprint, None);
+        assert_eq!(
+            diagnostics.trusting_period_remaining,
+            Some(Duration::from_secs(70))
+        );
+        assert_eq!(diagnostics.frozen_height, None);
+        // epoch 8 * 144896 is after the deneb fork of mainnet
+        assert_eq!(diagnostics.fork_epoch, Some(U64(269568)));
+        assert_eq!(diagnostics.fork_version, Version([4, 0, 0, 0]));
+        assert!(diagnostics.to_string().contains("period=144896"));
+        // the host context is the time source
+        let ctx = MockContext::<32>::new(now, height_from_block_number(U64(1)).unwrap());
+        assert_eq!(
+            client_state.diagnostics(&consensus_state, &ctx),
+            diagnostics
+        );
+
+        // the trusting period has expired
+        let now = Timestamp::from_nanoseconds(1_100 * 1_000_000_000).unwrap();
+        let diagnostics = client_state
+            .clone()
+            .with_frozen_height(height_from_block_number(U64(5)).unwrap())
+            .diagnostics(&consensus_state, now);
+        assert_eq!(diagnostics.trusting_period_remaining, None);
+        assert!(diagnostics.frozen_height.is_some());
+        assert!(diagnostics
+            .to_string()
+            .contains("trusting_period_remaining: expired"));
+
+        // before the first fork
+        let consensus_state = ConsensusState {
+            slot: U64(8 * 100),
+            ..consensus_state
+        };
+        let diagnostics = client_state.diagnostics(&consensus_state, now);
+        assert_eq!(diagnostics.fork_epoch, None);
+        assert_eq!(diagnostics.fork_version, Version([0, 0, 0, 0]));
+    }
+
+    #[test]
+    fn test_check_update_viability() {
+        let client_state = MinimalClientState {
+            slots_per_epoch: PRESET.SLOTS_PER_EPOCH,
+            epochs_per_sync_committee_period: PRESET.EPOCHS_PER_SYNC_COMMITTEE_PERIOD,
+            min_sync_committee_participants: 10.into(),
+            trust_level: Fraction::new(2, 3).unwrap(),
+            ..Default::default()
+        };
+        // 64 slots per period in the minimal preset
+        let trusted_consensus_state = ConsensusState {
+            slot: U64(64 + 1),
+            ..Default::default()
+        };
+        let update = |finalized_slot: u64, signature_slot: u64, participants: usize| {
+            let mut update = ConsensusUpdateInfo::<32>::default();
+            update.finalized_header.0.slot = U64(finalized_slot);
+            update.signature_slot = U64(signature_slot);
+            for i in 0..participants {
+                update.sync_aggregate.sync_committee_bits.set(i, true);
+            }
+            update
+        };
+
+        // signed by the current sync committee with 32 * 2 / 3 = 21.33... participants
+        let viability =
+            client_state.check_update_viability(&trusted_consensus_state, &update(72, 74, 22));
+        assert_eq!(
+            viability,
+            UpdateViability {
+                participants: 22,
+                required_participants: 22,
+                trusted_period: U64(1),
+                signature_period: U64(1),
+                is_next: false,
+                skips_period: false,
+                rotation_required: false,
+                has_next_sync_committee: false,
+                finalized_slot: U64(72),
+                advances: true,
+            }
+        );
+        assert!(viability.is_viable());
+
+        // below the trust level
+        let viability =
+            client_state.check_update_viability(&trusted_consensus_state, &update(72, 74, 21));
+        assert_eq!(viability.participants, 21);
+        assert!(!viability.is_viable());
+        // `min_sync_committee_participants` exceeds the trust level
+        let viability = MinimalClientState {
+            min_sync_committee_participants: 30.into(),
+            ..client_state.clone()
+        }
+        .check_update_viability(&trusted_consensus_state, &update(72, 74, 29));
+        assert_eq!(viability.required_participants, 30);
+        assert!(!viability.is_viable());
+
+        // signed and finalized in the next period, which rotates the sync committees
+        let mut rotation = update(128 + 8, 128 + 10, 32);
+        let viability = client_state.check_update_viability(&trusted_consensus_state, &rotation);
+        assert!(viability.is_next);
+        assert!(viability.rotation_required);
+        assert!(!viability.is_viable());
+        rotation.next_sync_committee = Some((SyncCommittee::default(), vec![]));
+        let viability = client_state.check_update_viability(&trusted_consensus_state, &rotation);
+        assert!(viability.has_next_sync_committee);
+        assert!(viability.is_viable());
+
+        // signed in the next period but finalized in the trusted period
+        let viability =
+            client_state.check_update_viability(&trusted_consensus_state, &update(72, 130, 32));
+        assert!(viability.is_next);
+        assert!(!viability.rotation_required);
+        assert!(viability.is_viable());
+
+        // signed two periods after the trusted period
+        let viability = client_state
+            .check_update_viability(&trusted_consensus_state, &update(192 + 8, 192 + 10, 32));
+        assert_eq!(viability.signature_period, U64(3));
+        assert!(viability.skips_period);
+        assert!(!viability.is_viable());
+
+        // the finalized header is not newer than the trusted consensus state
+        let viability =
+            client_state.check_update_viability(&trusted_consensus_state, &update(65, 74, 32));
+        assert!(!viability.advances);
+        assert!(!viability.is_viable());
+    }
+}
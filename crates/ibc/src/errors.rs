@@ -1,8 +1,10 @@
+use crate::bls::PointError;
+use crate::commitment::TrieWalkError;
 use crate::internal_prelude::*;
 use core::time::Duration;
 use displaydoc::Display;
 use ethereum_consensus::{
-    beacon::{BeaconBlockHeader, Slot},
+    beacon::{BeaconBlockHeader, Domain, Slot, Version},
     bls::PublicKey,
     sync_protocol::SyncCommitteePeriod,
     types::{H256, U64},
@@ -10,6 +12,7 @@ use ethereum_consensus::{
 use ibc::{
     core::{
         ics02_client::error::ClientError,
+        ics23_commitment::error::CommitmentError,
         ics24_host::{error::ValidationError, identifier::ClientId},
         ContextError,
     },
@@ -23,21 +26,151 @@ pub enum Error {
     InvalidRawConsensusState { reason: String },
     /// verification error: {0}
     VerificationError(ethereum_light_client_verifier::errors::Error),
-    /// mpt verification error: {0} state_root={1} address={2} account_proof={3:?}
-    MPTVerificationError(
-        ethereum_light_client_verifier::errors::Error,
-        H256,
-        String,
-        Vec<String>,
-    ),
     /// consensus update doesn't have next sync committee: store_period={0} update_period={1}
     NoNextSyncCommitteeInConsensusUpdate(U64, U64),
-    /// invalid current sync committee keys: expected={0:?} actual={1:?}
-    InvalidCurrentSyncCommitteeKeys(PublicKey, PublicKey),
-    /// invalid next sync committee keys: expected={0:?} actual={1:?}
-    InvalidNextSyncCommitteeKeys(PublicKey, PublicKey),
+    /// invalid current sync committee keys: period={period} stored={stored:?} provided={provided:?}
+    InvalidCurrentSyncCommitteeKeys {
+        period: SyncCommitteePeriod,
+        stored: PublicKey,
+        provided: PublicKey,
+    },
+    /// invalid next sync committee keys: period={period} stored={stored:?} provided={provided:?}
+    InvalidNextSyncCommitteeKeys {
+        period: SyncCommitteePeriod,
+        stored: PublicKey,
+        provided: PublicKey,
+    },
+    /// consensus update verification error: signature_slot={signature_slot} signature_period={signature_period} store_period={store_period} attested_slot={attested_slot} finalized_slot={finalized_slot} fork_version={fork_version:?} signature_domain={signature_domain:?} {err}
+    ConsensusUpdateVerificationError {
+        signature_slot: Slot,
+        signature_period: SyncCommitteePeriod,
+        store_period: SyncCommitteePeriod,
+        attested_slot: Slot,
+        finalized_slot: Slot,
+        fork_version: Version,
+        signature_domain: Domain,
+        err: ethereum_light_client_verifier::errors::Error,
+    },
+    /// execution update verification error: finalized_execution_root={finalized_execution_root} state_root={state_root} block_number={block_number} {err}
+    ExecutionUpdateVerificationError {
+        finalized_execution_root: H256,
+        state_root: H256,
+        block_number: U64,
+        err: ethereum_light_client_verifier::errors::Error,
+    },
+    /// consensus state doesn't have next sync committee
+    NoNextSyncCommitteeInConsensusState,
+    /// the signature period must not be before the trusted period: trusted_period={trusted_period} signature_period={signature_period}
+    SignaturePeriodOutOfRange {
+        trusted_period: SyncCommitteePeriod,
+        signature_period: SyncCommitteePeriod,
+    },
+    /// the trusted sync committee must be the next sync committee if and only if the signature period is the next period of the trusted period: is_next={is_next} trusted_period={trusted_period} signature_period={signature_period}
+    UnexpectedTrustedSyncCommittee {
+        is_next: bool,
+        trusted_period: SyncCommitteePeriod,
+        signature_period: SyncCommitteePeriod,
+    },
+    /// the update skips a sync committee period, so the trusted consensus state does not know the sync committee that signed it: trusted_period={trusted_period} signature_period={signature_period}. Submit the header with intermediate consensus updates for the skipped periods
+    SyncCommitteePeriodSkipped {
+        trusted_period: SyncCommitteePeriod,
+        signature_period: SyncCommitteePeriod,
+    },
+    /// slot {slot} is beyond the last configured fork; did you configure the fork schedule for this network? epoch={epoch} last_fork_epoch={last_fork_epoch} fork_version={fork_version:?} genesis_validators_root={genesis_validators_root:?} {err}
+    SlotBeyondLastFork {
+        slot: Slot,
+        epoch: U64,
+        last_fork_epoch: U64,
+        fork_version: Version,
+        genesis_validators_root: H256,
+        err: Box<Error>,
+    },
+    /// the finalized slot of the intermediate consensus update is not after the trusted slot: finalized_slot={finalized_slot} trusted_slot={trusted_slot}
+    FinalizedSlotNotAdvanced {
+        finalized_slot: Slot,
+        trusted_slot: Slot,
+    },
+    /// the block hash branch must have the length of the state root branch if the block hash is present, and be empty otherwise: has_block_hash={has_block_hash} state_root_branch={state_root_branch} block_hash_branch={block_hash_branch}
+    InconsistentExecutionBlockHashBranch {
+        has_block_hash: bool,
+        state_root_branch: usize,
+        block_hash_branch: usize,
+    },
+    /// invalid execution block hash branch: finalized_execution_root={finalized_execution_root} block_hash={block_hash} gindex={gindex}
+    InvalidExecutionBlockHashBranch {
+        finalized_execution_root: H256,
+        block_hash: H256,
+        gindex: u64,
+    },
+    /// no sync committee participants in the sync aggregate: signature_slot={signature_slot}
+    ZeroSyncCommitteeParticipants { signature_slot: Slot },
+    /// insufficient number of sync committee participants: min_sync_committee_participants={min_sync_committee_participants} participants={participants}
+    InsufficientSyncCommitteeParticipants {
+        min_sync_committee_participants: usize,
+        participants: usize,
+    },
+    /// insufficient sync committee participation for the trust level: participants={participants} sync_committee_size={sync_committee_size} trust_level={numerator}/{denominator}
+    InsufficientTrustLevelParticipation {
+        participants: usize,
+        sync_committee_size: usize,
+        numerator: u64,
+        denominator: u64,
+    },
+    /// trust level must be in the range [1/3, 1]: numerator={numerator} denominator={denominator}
+    InvalidTrustLevel { numerator: u64, denominator: u64 },
     /// invalid proof format error: {0}
     InvalidProofFormatError(String),
+    /// rlp decode error: `{0}`
+    RlpDecodeError(rlp::DecoderError),
+    /// the proof response is for a different account: expected={expected} got={got}
+    ProofResponseAddressMismatch { expected: String, got: String },
+    /// the proof response does not contain the storage proof of the key: key={key}
+    StorageProofNotFound { key: H256 },
+    /// the storage proof does not start at the storage hash of the proof response: key={key} storage_hash={storage_hash} root={root}
+    StorageProofRootMismatch {
+        key: H256,
+        storage_hash: H256,
+        root: H256,
+    },
+    /// storage value proof verification error: key={key} {err}
+    StorageValueProofWalkError { key: H256, err: TrieWalkError },
+    /// malformed storage value proof: key={key} {description}
+    MalformedStorageValueProof { key: H256, description: String },
+    /// unexpected commitment prefix: expected={expected:?} got={got:?}
+    UnexpectedCommitmentPrefix { expected: Vec<u8>, got: Vec<u8> },
+    /// unknown commitment value codec: {0}
+    UnknownCommitmentValueCodec(i32),
+    /// raw commitment value must be at most 32 bytes: path={path} length={length}
+    InvalidRawCommitmentValueLength { path: String, length: usize },
+    /// commitment not found: path={path} key={key}
+    CommitmentNotFound { path: String, key: H256 },
+    /// commitment value mismatch: path={path} expected={expected} got={got}
+    CommitmentValueMismatch {
+        path: String,
+        expected: H256,
+        got: H256,
+    },
+    /// commitment must not exist: path={path} got={got}
+    UnexpectedCommitment { path: String, got: H256 },
+    /// malformed commitment proof: path={path} {description}
+    MalformedCommitmentProof { path: String, description: String },
+    /// storage proof verification error: path={path} key={key} {err}
+    StorageProofWalkError {
+        path: String,
+        key: H256,
+        err: TrieWalkError,
+    },
+    /// batch membership verification error: index={index} {err}
+    BatchMembershipVerificationError { index: usize, err: Box<Error> },
+    /// the storage root is zero, so the IBC contract has not been initialized yet: path={path}
+    ZeroStorageRoot { path: String },
+    /// account proof verification error: address={address} {err}
+    AccountProofWalkError { address: String, err: TrieWalkError },
+    /// malformed account proof: address={address} {description}
+    MalformedAccountProof {
+        address: String,
+        description: String,
+    },
     /// account storage root mismatch: expected={0} actual={1} state_root={2} address={3} account_proof={4:?}
     AccountStorageRootMismatch(H256, H256, H256, String, Vec<String>),
     /// store does not support the finalized_period: store_period={0} finalized_period={1}
@@ -80,13 +213,42 @@ pub enum Error {
     UninitializedClientStateField(&'static str),
     /// uninitialized consensus state field: {0}
     UninitializedConsensusStateField(&'static str),
+    /// invalid storage root length: expected=32 actual={0}
+    InvalidStorageRootLength(usize),
+    /// sync committee aggregate public key is the point at infinity: {0}
+    InfinitySyncCommitteeAggregatePubkey(&'static str),
     /// missing bellatrix fork
     MissingBellatrixFork,
-    /// client frozen: frozen_height={frozen_height} target_height={target_height}
-    ClientFrozen {
-        frozen_height: Height,
-        target_height: Height,
+    /// invalid fork version length: fork={fork} length={length}
+    InvalidForkVersionLength { fork: String, length: usize },
+    /// fork epochs must be in ascending order: index={index} prev_epoch={prev_epoch} epoch={epoch}
+    UnorderedForkEpochs {
+        index: usize,
+        prev_epoch: U64,
+        epoch: U64,
+    },
+    /// duplicated fork version: index={index} version={version:?}
+    DuplicatedForkVersion { index: usize, version: Version },
+    /// slot is before the first fork epoch: slot={slot} epoch={epoch} first_fork_epoch={first_fork_epoch}
+    SlotBeforeFirstFork {
+        slot: Slot,
+        epoch: U64,
+        first_fork_epoch: U64,
+    },
+    /// initial consensus state timestamp is inconsistent with the genesis time and slot: slot={slot} expected={expected} actual={actual}
+    InitialConsensusStateTimestampMismatch {
+        slot: Slot,
+        expected: Timestamp,
+        actual: Timestamp,
     },
+    /// initial consensus state slot is before the bellatrix fork: slot={slot} epoch={epoch} bellatrix_epoch={bellatrix_epoch}
+    InitialConsensusStateBeforeBellatrixFork {
+        slot: Slot,
+        epoch: U64,
+        bellatrix_epoch: U64,
+    },
+    /// client frozen: frozen_height={frozen_height}
+    ClientFrozen { frozen_height: Height },
     /// ethereum consensus error: `{0}`
     EthereumConsensusError(ethereum_consensus::errors::Error),
     /// decode error: `{0}`
@@ -101,24 +263,116 @@ pub enum Error {
     ZeroTimestampError,
     /// zero block number error
     ZeroBlockNumberError,
+    /// finalized slot must be less than or equal to attested slot: finalized_slot={finalized_slot} attested_slot={attested_slot} signature_slot={signature_slot}
+    FinalizedSlotAfterAttestedSlot {
+        finalized_slot: Slot,
+        attested_slot: Slot,
+        signature_slot: Slot,
+    },
+    /// attested slot must be less than signature slot: finalized_slot={finalized_slot} attested_slot={attested_slot} signature_slot={signature_slot}
+    AttestedSlotNotBeforeSignatureSlot {
+        finalized_slot: Slot,
+        attested_slot: Slot,
+        signature_slot: Slot,
+    },
+    /// empty branch: {0}
+    EmptyBranch(&'static str),
+    /// the state root branch and block number branch must have the same length: state_root_branch={state_root_branch} block_number_branch={block_number_branch}
+    InconsistentExecutionBranchLengths {
+        state_root_branch: usize,
+        block_number_branch: usize,
+    },
+    /// empty account proof
+    EmptyAccountProof,
+    /// empty node in the account proof: index={index}
+    EmptyAccountProofNode { index: usize },
+    /// the account storage root is zero
+    ZeroAccountStorageRoot,
+    /// the account update is empty
+    EmptyAccountUpdate,
+    /// intermediate consensus update must contain the next sync committee: index={index}
+    MissingNextSyncCommitteeInIntermediateUpdate { index: usize },
+    /// too many intermediate consensus updates: max={max} actual={actual}
+    TooManyIntermediateConsensusUpdates { max: U64, actual: usize },
+    /// intermediate consensus update verification error: index={index} {err}
+    IntermediateConsensusUpdateError { index: usize, err: Box<Error> },
     /// timestamp overflow error: `{0}`
     TimestampOverflowError(TimestampOverflowError),
     /// parse timestamp error: `{0}`
     ParseTimestampError(ParseTimestampError),
-    /// deserialize sync committee bits error: `{parent}` sync_committee_size={sync_committee_size} sync_committee_bits={sync_committee_bits:?}
+    /// deserialize sync committee bits error: `{parent}` sync_committee_size={sync_committee_size} expected_len={expected_len} actual_len={actual_len} sync_committee_bits={sync_committee_bits:?}
     DeserializeSyncCommitteeBitsError {
         parent: ssz_rs::DeserializeError,
         sync_committee_size: usize,
+        expected_len: usize,
+        actual_len: usize,
         sync_committee_bits: Vec<u8>,
     },
+    /// unexpected byte length of the sync committee bits: sync_committee_size={sync_committee_size} expected_len={expected_len} actual_len={actual_len}
+    UnexpectedSyncCommitteeBitsLength {
+        sync_committee_size: usize,
+        expected_len: usize,
+        actual_len: usize,
+    },
+    /// non-canonical sync committee bits with a non-zero padding bit: sync_committee_size={sync_committee_size} last_byte={last_byte:#04x}
+    NonZeroSyncCommitteeBitsPadding {
+        sync_committee_size: usize,
+        last_byte: u8,
+    },
+    /// unexpected number of public keys in the sync committee: expected={expected} actual={actual}
+    UnexpectedSyncCommitteeSize { expected: usize, actual: usize },
     /// proto missing field error: `{0}`
     ProtoMissingFieldError(String),
-    /// unknown message type: `{0}`
-    UnknownMessageType(String),
+    /// failed to decode `{field}`: {source}
+    DecodeError { field: String, source: Box<Error> },
+    /// invalid bytes length: expected={expected} actual={actual}
+    InvalidBytesLength { expected: usize, actual: usize },
+    /// invalid merkle path: {0}
+    InvalidMerklePath(String),
+    /// decode limit exceeded: limit={limit} max={max} actual={actual}
+    DecodeLimitExceeded {
+        limit: &'static str,
+        max: usize,
+        actual: usize,
+    },
+    /// invalid public key: index={index} {reason}
+    InvalidPublicKey { index: usize, reason: PointError },
+    /// invalid aggregate public key: {reason}
+    InvalidAggregatePublicKey { reason: PointError },
+    /// invalid signature: {reason}
+    InvalidSignature { reason: PointError },
+    /// unexpected client message type: url={url}
+    UnexpectedClientMessageType { url: String },
+    /// Any payload is not the canonical encoding of the message: type_url={type_url}
+    UnexpectedAnyPayload { type_url: &'static str },
+    /// unexpected type url: expected={expected:?} got={got}
+    UnexpectedTypeUrl {
+        expected: &'static [&'static str],
+        got: String,
+    },
     /// cannot initialize frozen client
     CannotInitializeFrozenClient,
     /// unexpected client ID in misbehaviour: expected={0} got={1}
     UnexpectedClientIdInMisbehaviour(ClientId, ClientId),
+    /// the finalized headers in the misbehaviour must have the same slot: slot_1={slot_1} slot_2={slot_2}
+    FinalizedHeaderMisbehaviourSlotMismatch { slot_1: Slot, slot_2: Slot },
+    /// the misbehaviour must not contain both the deprecated `trusted_sync_committee` and the per-update trusted sync committees
+    AmbiguousTrustedSyncCommitteesInMisbehaviour,
+    /// misbehaviour consensus update verification error: index={index} {err}
+    MisbehaviourConsensusUpdateError { index: usize, err: Box<Error> },
+    /// the consensus updates in the misbehaviour are identical
+    IdenticalConsensusUpdatesInMisbehaviour,
+    /// the finalized headers in the misbehaviour are identical: slot={0}
+    IdenticalFinalizedHeadersInMisbehaviour(Slot),
+    /// the consensus update in the misbehaviour must contain the next sync committee: index={index}
+    NoNextSyncCommitteeInMisbehaviour { index: usize },
+    /// the next sync committees in the misbehaviour are identical: aggregate_pubkey={0:?}
+    IdenticalNextSyncCommitteesInMisbehaviour(PublicKey),
+    /// the attested headers in the misbehaviour must be in the same sync committee period: period_1={period_1} period_2={period_2}
+    NextSyncCommitteeMisbehaviourPeriodMismatch {
+        period_1: SyncCommitteePeriod,
+        period_2: SyncCommitteePeriod,
+    },
     /// Processed time for the client `{client_id}` at height `{height}` not found
     ProcessedTimeNotFound { client_id: ClientId, height: Height },
     /// Processed height for the client `{client_id}` at height `{height}` not found
@@ -133,18 +387,294 @@ pub enum Error {
         current_height: Height,
         earliest_height: Height,
     },
+    /// the update stores a consensus state at height `{height}` other than the latest height `{latest_height}`, which the update handler of ibc-rs cannot write
+    HistoricalConsensusStateNotSupported {
+        height: Height,
+        latest_height: Height,
+    },
+    /// the consensus state at height `{height}` is already stored, so the update changes nothing and must not be written by the update handler of ibc-rs
+    ConsensusStateAlreadyStored { height: Height },
+    /// the storage root of the consensus state is zero, which can only be proven for an account that does not exist
+    ZeroConsensusStateStorageRoot,
 }
 
 impl Error {
-    pub fn proto_missing(s: &str) -> Self {
-        Error::ProtoMissingFieldError(s.to_string())
+    /// Returns the error for the missing field `field` of the proto message `message`, e.g. `TrustedSyncCommittee.sync_committee`
+    pub fn proto_missing(message: &str, field: &str) -> Self {
+        Error::ProtoMissingFieldError(format!("{}.{}", message, field))
+    }
+
+    /// Wraps `err` with the name of the field that failed to decode
+    ///
+    /// If `err` is already a decode error of a nested field, `field` is prepended to its dotted path.
+    /// A missing field error is wrapped as is, so that it keeps the message type enclosing the missing field.
+    pub fn decode_error(field: &str, err: Error) -> Self {
+        match err {
+            Error::DecodeError {
+                field: nested,
+                source,
+            } => Error::DecodeError {
+                field: format!("{}.{}", field, nested),
+                source,
+            },
+            err => Error::DecodeError {
+                field: field.to_string(),
+                source: Box::new(err),
+            },
+        }
+    }
+
+    /// Returns the stable numeric code of the error for hosts that can only surface a code and a message
+    ///
+    /// The codes are grouped by the thousands digit:
+    /// - 1xxx: decoding of messages and states
+    /// - 2xxx: consensus verification of headers (21xx for misbehaviour)
+    /// - 3xxx: commitment and account proofs
+    /// - 4xxx: client state and consensus state lifecycle
+    /// - 5xxx: errors of the host and ibc-rs
+    ///
+    /// The code depends only on the variant, not on its fields or nested errors.
+    /// The numbering is append-only: a new variant takes the next unused code of its group, and the code of a removed variant is never reused.
+    pub fn code(&self) -> u32 {
+        match self {
+            Error::InvalidRawConsensusState { .. } => 1001,
+            Error::Decode(..) => 1002,
+            Error::DeserializeSyncCommitteeBitsError { .. } => 1003,
+            Error::ProtoMissingFieldError(..) => 1004,
+            Error::DecodeError { .. } => 1005,
+            Error::InvalidBytesLength { .. } => 1006,
+            Error::UnexpectedClientMessageType { .. } => 1007,
+            Error::UnexpectedAnyPayload { .. } => 1008,
+            Error::UnexpectedTypeUrl { .. } => 1009,
+            Error::UnknownCommitmentValueCodec(..) => 1010,
+            Error::InvalidForkVersionLength { .. } => 1011,
+            Error::InvalidStorageRootLength(..) => 1012,
+            Error::InvalidMerklePath(..) => 1013,
+            Error::DecodeLimitExceeded { .. } => 1014,
+            Error::InvalidPublicKey { .. } => 1015,
+            Error::InvalidAggregatePublicKey { .. } => 1016,
+            Error::InvalidSignature { .. } => 1017,
+            Error::UnexpectedSyncCommitteeBitsLength { .. } => 1018,
+            Error::NonZeroSyncCommitteeBitsPadding { .. } => 1019,
+            Error::EmptyAccountUpdate => 1020,
+            Error::UnexpectedSyncCommitteeSize { .. } => 1021,
+            Error::VerificationError(..) => 2001,
+            Error::ConsensusUpdateVerificationError { .. } => 2002,
+            Error::ExecutionUpdateVerificationError { .. } => 2003,
+            Error::IntermediateConsensusUpdateError { .. } => 2004,
+            Error::InvalidCurrentSyncCommitteeKeys { .. } => 2005,
+            Error::InvalidNextSyncCommitteeKeys { .. } => 2006,
+            Error::NoNextSyncCommitteeInConsensusState => 2007,
+            Error::NoNextSyncCommitteeInConsensusUpdate(..) => 2008,
+            Error::StoreNotSupportedFinalizedPeriod(..) => 2009,
+            Error::InsufficientSyncCommitteeParticipants { .. } => 2010,
+            Error::InsufficientTrustLevelParticipation { .. } => 2011,
+            Error::SlotBeforeFirstFork { .. } => 2012,
+            Error::FinalizedSlotAfterAttestedSlot { .. } => 2013,
+            Error::AttestedSlotNotBeforeSignatureSlot { .. } => 2014,
+            Error::EmptyBranch(..) => 2015,
+            Error::InconsistentExecutionBranchLengths { .. } => 2016,
+            Error::MissingNextSyncCommitteeInIntermediateUpdate { .. } => 2017,
+            Error::TooManyIntermediateConsensusUpdates { .. } => 2018,
+            Error::UnexpectedTimestamp(..) => 2019,
+            Error::ZeroTimestampError => 2020,
+            Error::ZeroBlockNumberError => 2021,
+            Error::OutOfTrustingPeriod { .. } => 2022,
+            Error::HeaderFromFuture { .. } => 2023,
+            Error::EthereumConsensusError(..) => 2024,
+            Error::SignaturePeriodOutOfRange { .. } => 2025,
+            Error::UnexpectedTrustedSyncCommittee { .. } => 2026,
+            Error::SyncCommitteePeriodSkipped { .. } => 2027,
+            Error::SlotBeyondLastFork { .. } => 2028,
+            Error::ZeroSyncCommitteeParticipants { .. } => 2029,
+            Error::FinalizedSlotNotAdvanced { .. } => 2030,
+            Error::InconsistentExecutionBlockHashBranch { .. } => 2031,
+            Error::InvalidExecutionBlockHashBranch { .. } => 2032,
+            Error::DifferentPeriodInNextSyncCommitteeMisbehaviour(..) => 2101,
+            Error::NoNextSyncCommitteeInNextSyncCommitteeMisbehaviour => 2102,
+            Error::SameNextSyncCommitteeInNextSyncCommitteeMisbehaviour(..) => 2103,
+            Error::DifferentSlotInFinalizedHeaderMisbehaviour(..) => 2104,
+            Error::SameFinalizedHeaderInFinalizedHeaderMisbehaviour(..) => 2105,
+            Error::UnexpectedClientIdInMisbehaviour(..) => 2106,
+            Error::FinalizedHeaderMisbehaviourSlotMismatch { .. } => 2107,
+            Error::AmbiguousTrustedSyncCommitteesInMisbehaviour => 2108,
+            Error::MisbehaviourConsensusUpdateError { .. } => 2109,
+            Error::IdenticalConsensusUpdatesInMisbehaviour => 2110,
+            Error::IdenticalFinalizedHeadersInMisbehaviour(..) => 2111,
+            Error::NoNextSyncCommitteeInMisbehaviour { .. } => 2112,
+            Error::IdenticalNextSyncCommitteesInMisbehaviour(..) => 2113,
+            Error::NextSyncCommitteeMisbehaviourPeriodMismatch { .. } => 2114,
+            Error::InvalidProofFormatError(..) => 3001,
+            Error::UnexpectedCommitmentPrefix { .. } => 3002,
+            Error::InvalidRawCommitmentValueLength { .. } => 3003,
+            Error::CommitmentNotFound { .. } => 3004,
+            Error::CommitmentValueMismatch { .. } => 3005,
+            Error::UnexpectedCommitment { .. } => 3006,
+            Error::MalformedCommitmentProof { .. } => 3007,
+            Error::StorageProofWalkError { .. } => 3008,
+            Error::BatchMembershipVerificationError { .. } => 3009,
+            Error::ZeroStorageRoot { .. } => 3010,
+            Error::AccountProofWalkError { .. } => 3011,
+            Error::MalformedAccountProof { .. } => 3012,
+            Error::AccountStorageRootMismatch(..) => 3013,
+            Error::EmptyAccountProof => 3014,
+            Error::RlpDecodeError(..) => 3015,
+            Error::ProofResponseAddressMismatch { .. } => 3016,
+            Error::StorageProofNotFound { .. } => 3017,
+            Error::StorageProofRootMismatch { .. } => 3018,
+            Error::StorageValueProofWalkError { .. } => 3019,
+            Error::MalformedStorageValueProof { .. } => 3020,
+            Error::EmptyAccountProofNode { .. } => 3021,
+            Error::ZeroAccountStorageRoot => 3022,
+            Error::InsufficientHeight { .. } => 4001,
+            Error::UnexpectedHeightRevisionNumber { .. } => 4002,
+            Error::ClientFrozen { .. } => 4003,
+            Error::CannotInitializeFrozenClient => 4004,
+            Error::UninitializedClientStateField(..) => 4005,
+            Error::UninitializedConsensusStateField(..) => 4006,
+            Error::InfinitySyncCommitteeAggregatePubkey(..) => 4007,
+            Error::InvalidTrustLevel { .. } => 4008,
+            Error::MissingTrustingPeriod => 4009,
+            Error::NegativeMaxClockDrift => 4010,
+            Error::MissingBellatrixFork => 4011,
+            Error::UnorderedForkEpochs { .. } => 4012,
+            Error::DuplicatedForkVersion { .. } => 4013,
+            Error::InitialConsensusStateTimestampMismatch { .. } => 4014,
+            Error::InitialConsensusStateBeforeBellatrixFork { .. } => 4015,
+            Error::ProcessedTimeNotFound { .. } => 4016,
+            Error::ProcessedHeightNotFound { .. } => 4017,
+            Error::NotEnoughTimeElapsed { .. } => 4018,
+            Error::NotEnoughBlocksElapsed { .. } => 4019,
+            Error::HistoricalConsensusStateNotSupported { .. } => 4020,
+            Error::ConsensusStateAlreadyStored { .. } => 4021,
+            Error::ZeroConsensusStateStorageRoot => 4022,
+            Error::ICS02(..) => 5001,
+            Error::ICS24(..) => 5002,
+            Error::ContextError(..) => 5003,
+            Error::TimestampOverflowError(..) => 5004,
+            Error::ParseTimestampError(..) => 5005,
+        }
+    }
+
+    /// Returns `true` if the error rejects the misbehaviour submitted to the client
+    fn is_misbehaviour_error(&self) -> bool {
+        matches!(
+            self,
+            Error::DifferentPeriodInNextSyncCommitteeMisbehaviour(..)
+                | Error::NoNextSyncCommitteeInNextSyncCommitteeMisbehaviour
+                | Error::SameNextSyncCommitteeInNextSyncCommitteeMisbehaviour(..)
+                | Error::DifferentSlotInFinalizedHeaderMisbehaviour(..)
+                | Error::SameFinalizedHeaderInFinalizedHeaderMisbehaviour(..)
+                | Error::UnexpectedClientIdInMisbehaviour(..)
+                | Error::FinalizedHeaderMisbehaviourSlotMismatch { .. }
+                | Error::AmbiguousTrustedSyncCommitteesInMisbehaviour
+                | Error::MisbehaviourConsensusUpdateError { .. }
+                | Error::IdenticalConsensusUpdatesInMisbehaviour
+                | Error::IdenticalFinalizedHeadersInMisbehaviour(..)
+                | Error::NoNextSyncCommitteeInMisbehaviour { .. }
+                | Error::IdenticalNextSyncCommitteesInMisbehaviour(..)
+                | Error::NextSyncCommitteeMisbehaviourPeriodMismatch { .. }
+        )
+    }
+
+    /// Returns `true` if the error rejects the header submitted to the client, including its account update
+    fn is_header_error(&self) -> bool {
+        matches!(
+            self,
+            Error::VerificationError(..)
+                | Error::ConsensusUpdateVerificationError { .. }
+                | Error::ExecutionUpdateVerificationError { .. }
+                | Error::IntermediateConsensusUpdateError { .. }
+                | Error::InvalidCurrentSyncCommitteeKeys { .. }
+                | Error::InvalidNextSyncCommitteeKeys { .. }
+                | Error::NoNextSyncCommitteeInConsensusState
+                | Error::SignaturePeriodOutOfRange { .. }
+                | Error::UnexpectedTrustedSyncCommittee { .. }
+                | Error::SyncCommitteePeriodSkipped { .. }
+                | Error::SlotBeyondLastFork { .. }
+                | Error::ZeroSyncCommitteeParticipants { .. }
+                | Error::FinalizedSlotNotAdvanced { .. }
+                | Error::InconsistentExecutionBlockHashBranch { .. }
+                | Error::InvalidExecutionBlockHashBranch { .. }
+                | Error::NoNextSyncCommitteeInConsensusUpdate(..)
+                | Error::StoreNotSupportedFinalizedPeriod(..)
+                | Error::InsufficientSyncCommitteeParticipants { .. }
+                | Error::InsufficientTrustLevelParticipation { .. }
+                | Error::SlotBeforeFirstFork { .. }
+                | Error::FinalizedSlotAfterAttestedSlot { .. }
+                | Error::AttestedSlotNotBeforeSignatureSlot { .. }
+                | Error::EmptyBranch(..)
+                | Error::InconsistentExecutionBranchLengths { .. }
+                | Error::MissingNextSyncCommitteeInIntermediateUpdate { .. }
+                | Error::TooManyIntermediateConsensusUpdates { .. }
+                | Error::UnexpectedTimestamp(..)
+                | Error::ZeroTimestampError
+                | Error::ZeroBlockNumberError
+                | Error::HeaderFromFuture { .. }
+                | Error::AccountProofWalkError { .. }
+                | Error::MalformedAccountProof { .. }
+                | Error::AccountStorageRootMismatch(..)
+                | Error::EmptyAccountProof
+                | Error::EmptyAccountProofNode { .. }
+                | Error::ZeroAccountStorageRoot
+        )
     }
 }
 
+/// Maps the error onto the `ClientError` variant of its category so that the host can match on the kind of the failure
+///
+/// - header and consensus update verification errors: `HeaderVerificationFailure`
+/// - misbehaviour errors: `MisbehaviourHandlingFailure`
+/// - `OutOfTrustingPeriod`: `HeaderNotWithinTrustPeriod`
+/// - `InsufficientHeight`: `InvalidProofHeight`
+/// - commitment proof errors: `InvalidCommitmentProof`
+/// - wrapped `ClientError`s: the wrapped error as is
+///
+/// The other errors, including `ClientFrozen` whose message carries the frozen height, are converted into `ClientSpecific` with the message of the whole error chain.
 impl From<Error> for ClientError {
     fn from(value: Error) -> Self {
-        ClientError::ClientSpecific {
-            description: format!("{}", value),
+        match value {
+            Error::ICS02(err) => err,
+            Error::ContextError(ContextError::ClientError(err)) => err,
+            Error::OutOfTrustingPeriod {
+                current_timestamp,
+                trusting_period_end,
+            } => ClientError::HeaderNotWithinTrustPeriod {
+                latest_time: trusting_period_end,
+                update_time: current_timestamp,
+            },
+            Error::InsufficientHeight {
+                latest_height,
+                target_height,
+            } => ClientError::InvalidProofHeight {
+                latest_height,
+                proof_height: target_height,
+            },
+            Error::InvalidProofFormatError(_)
+            | Error::RlpDecodeError(_)
+            | Error::InvalidMerklePath(_)
+            | Error::DecodeLimitExceeded { .. }
+            | Error::MalformedCommitmentProof { .. }
+            | Error::MalformedStorageValueProof { .. } => {
+                ClientError::InvalidCommitmentProof(CommitmentError::InvalidMerkleProof)
+            }
+            Error::CommitmentNotFound { .. }
+            | Error::CommitmentValueMismatch { .. }
+            | Error::UnexpectedCommitment { .. }
+            | Error::StorageProofWalkError { .. }
+            | Error::StorageValueProofWalkError { .. }
+            | Error::BatchMembershipVerificationError { .. } => {
+                ClientError::InvalidCommitmentProof(CommitmentError::VerificationFailure)
+            }
+            err if err.is_misbehaviour_error() => ClientError::MisbehaviourHandlingFailure {
+                reason: err.to_string(),
+            },
+            err if err.is_header_error() => ClientError::HeaderVerificationFailure {
+                reason: err.to_string(),
+            },
+            err => ClientError::ClientSpecific {
+                description: err.to_string(),
+            },
         }
     }
 }
@@ -190,3 +720,821 @@ impl From<ParseTimestampError> for Error {
         Self::ParseTimestampError(value)
     }
 }
+
+/// `source` returns the wrapped error, so that the whole chain down to the library error that caused the failure can be walked
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::DecodeError { source, .. } => Some(source.as_ref()),
+            Error::BatchMembershipVerificationError { err, .. }
+            | Error::IntermediateConsensusUpdateError { err, .. }
+            | Error::MisbehaviourConsensusUpdateError { err, .. }
+            | Error::SlotBeyondLastFork { err, .. } => Some(err.as_ref()),
+            Error::StorageProofWalkError { err, .. }
+            | Error::StorageValueProofWalkError { err, .. }
+            | Error::AccountProofWalkError { err, .. } => Some(err),
+            Error::DeserializeSyncCommitteeBitsError { parent, .. } => Some(parent),
+            Error::RlpDecodeError(err) => Some(err),
+            Error::Decode(err) => Some(err),
+            Error::ICS02(err) => Some(err),
+            Error::ICS24(err) => Some(err),
+            Error::ContextError(err) => Some(err),
+            Error::TimestampOverflowError(err) => Some(err),
+            Error::ParseTimestampError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::str::FromStr;
+    use ethereum_ibc_proto::ibc::lightclients::ethereum::v1::ConsensusState as RawConsensusState;
+    use prost::Message;
+    use ssz_rs::{Bitvector, Deserialize};
+
+    /// The checked-in codes of all variants, which must not change
+    const ERROR_CODES: &[(&str, u32)] = &[
+        ("InvalidRawConsensusState", 1001),
+        ("Decode", 1002),
+        ("DeserializeSyncCommitteeBitsError", 1003),
+        ("ProtoMissingFieldError", 1004),
+        ("DecodeError", 1005),
+        ("InvalidBytesLength", 1006),
+        ("UnexpectedClientMessageType", 1007),
+        ("UnexpectedAnyPayload", 1008),
+        ("UnexpectedTypeUrl", 1009),
+        ("UnknownCommitmentValueCodec", 1010),
+        ("InvalidForkVersionLength", 1011),
+        ("InvalidStorageRootLength", 1012),
+        ("InvalidMerklePath", 1013),
+        ("DecodeLimitExceeded", 1014),
+        ("InvalidPublicKey", 1015),
+        ("InvalidAggregatePublicKey", 1016),
+        ("InvalidSignature", 1017),
+        ("UnexpectedSyncCommitteeBitsLength", 1018),
+        ("NonZeroSyncCommitteeBitsPadding", 1019),
+        ("EmptyAccountUpdate", 1020),
+        ("UnexpectedSyncCommitteeSize", 1021),
+        ("VerificationError", 2001),
+        ("ConsensusUpdateVerificationError", 2002),
+        ("ExecutionUpdateVerificationError", 2003),
+        ("IntermediateConsensusUpdateError", 2004),
+        ("InvalidCurrentSyncCommitteeKeys", 2005),
+        ("InvalidNextSyncCommitteeKeys", 2006),
+        ("NoNextSyncCommitteeInConsensusState", 2007),
+        ("NoNextSyncCommitteeInConsensusUpdate", 2008),
+        ("StoreNotSupportedFinalizedPeriod", 2009),
+        ("InsufficientSyncCommitteeParticipants", 2010),
+        ("InsufficientTrustLevelParticipation", 2011),
+        ("SlotBeforeFirstFork", 2012),
+        ("FinalizedSlotAfterAttestedSlot", 2013),
+        ("AttestedSlotNotBeforeSignatureSlot", 2014),
+        ("EmptyBranch", 2015),
+        ("InconsistentExecutionBranchLengths", 2016),
+        ("MissingNextSyncCommitteeInIntermediateUpdate", 2017),
+        ("TooManyIntermediateConsensusUpdates", 2018),
+        ("UnexpectedTimestamp", 2019),
+        ("ZeroTimestampError", 2020),
+        ("ZeroBlockNumberError", 2021),
+        ("OutOfTrustingPeriod", 2022),
+        ("HeaderFromFuture", 2023),
+        ("EthereumConsensusError", 2024),
+        ("SignaturePeriodOutOfRange", 2025),
+        ("UnexpectedTrustedSyncCommittee", 2026),
+        ("SyncCommitteePeriodSkipped", 2027),
+        ("SlotBeyondLastFork", 2028),
+        ("ZeroSyncCommitteeParticipants", 2029),
+        ("FinalizedSlotNotAdvanced", 2030),
+        ("InconsistentExecutionBlockHashBranch", 2031),
+        ("InvalidExecutionBlockHashBranch", 2032),
+        ("DifferentPeriodInNextSyncCommitteeMisbehaviour", 2101),
+        ("NoNextSyncCommitteeInNextSyncCommitteeMisbehaviour", 2102),
+        ("SameNextSyncCommitteeInNextSyncCommitteeMisbehaviour", 2103),
+        ("DifferentSlotInFinalizedHeaderMisbehaviour", 2104),
+        ("SameFinalizedHeaderInFinalizedHeaderMisbehaviour", 2105),
+        ("UnexpectedClientIdInMisbehaviour", 2106),
+        ("FinalizedHeaderMisbehaviourSlotMismatch", 2107),
+        ("AmbiguousTrustedSyncCommitteesInMisbehaviour", 2108),
+        ("MisbehaviourConsensusUpdateError", 2109),
+        ("IdenticalConsensusUpdatesInMisbehaviour", 2110),
+        ("IdenticalFinalizedHeadersInMisbehaviour", 2111),
+        ("NoNextSyncCommitteeInMisbehaviour", 2112),
+        ("IdenticalNextSyncCommitteesInMisbehaviour", 2113),
+        ("NextSyncCommitteeMisbehaviourPeriodMismatch", 2114),
+        ("InvalidProofFormatError", 3001),
+        ("UnexpectedCommitmentPrefix", 3002),
+        ("InvalidRawCommitmentValueLength", 3003),
+        ("CommitmentNotFound", 3004),
+        ("CommitmentValueMismatch", 3005),
+        ("UnexpectedCommitment", 3006),
+        ("MalformedCommitmentProof", 3007),
+        ("StorageProofWalkError", 3008),
+        ("BatchMembershipVerificationError", 3009),
+        ("ZeroStorageRoot", 3010),
+        ("AccountProofWalkError", 3011),
+        ("MalformedAccountProof", 3012),
+        ("AccountStorageRootMismatch", 3013),
+        ("EmptyAccountProof", 3014),
+        ("RlpDecodeError", 3015),
+        ("ProofResponseAddressMismatch", 3016),
+        ("StorageProofNotFound", 3017),
+        ("StorageProofRootMismatch", 3018),
+        ("StorageValueProofWalkError", 3019),
+        ("MalformedStorageValueProof", 3020),
+        ("EmptyAccountProofNode", 3021),
+        ("ZeroAccountStorageRoot", 3022),
+        ("InsufficientHeight", 4001),
+        ("UnexpectedHeightRevisionNumber", 4002),
+        ("ClientFrozen", 4003),
+        ("CannotInitializeFrozenClient", 4004),
+        ("UninitializedClientStateField", 4005),
+        ("UninitializedConsensusStateField", 4006),
+        ("InfinitySyncCommitteeAggregatePubkey", 4007),
+        ("InvalidTrustLevel", 4008),
+        ("MissingTrustingPeriod", 4009),
+        ("NegativeMaxClockDrift", 4010),
+        ("MissingBellatrixFork", 4011),
+        ("UnorderedForkEpochs", 4012),
+        ("DuplicatedForkVersion", 4013),
+        ("InitialConsensusStateTimestampMismatch", 4014),
+        ("InitialConsensusStateBeforeBellatrixFork", 4015),
+        ("ProcessedTimeNotFound", 4016),
+        ("ProcessedHeightNotFound", 4017),
+        ("NotEnoughTimeElapsed", 4018),
+        ("NotEnoughBlocksElapsed", 4019),
+        ("HistoricalConsensusStateNotSupported", 4020),
+        ("ConsensusStateAlreadyStored", 4021),
+        ("ZeroConsensusStateStorageRoot", 4022),
+        ("ICS02", 5001),
+        ("ICS24", 5002),
+        ("ContextError", 5003),
+        ("TimestampOverflowError", 5004),
+        ("ParseTimestampError", 5005),
+    ];
+
+    fn verifier_error() -> ethereum_light_client_verifier::errors::Error {
+        ethereum_light_client_verifier::errors::Error::IrrelevantConsensusUpdates(String::new())
+    }
+
+    fn trie_walk_error() -> TrieWalkError {
+        TrieWalkError {
+            root: H256::default(),
+            node_index: 0,
+            expected_hash: H256::default(),
+            got_hash: None,
+            consumed_nibbles: 0,
+            description: String::new(),
+        }
+    }
+
+    fn height(revision_height: u64) -> Height {
+        Height::new(0, revision_height).unwrap()
+    }
+
+    fn client_id() -> ClientId {
+        ClientId::new(crate::eth_client_type(), 0).unwrap()
+    }
+
+    /// One instance of every variant, in the order of `ERROR_CODES`
+    fn all_errors() -> Vec<Error> {
+        vec![
+            Error::InvalidRawConsensusState {
+                reason: String::new(),
+            },
+            Error::Decode(RawConsensusState::decode([0xffu8].as_slice()).unwrap_err()),
+            Error::DeserializeSyncCommitteeBitsError {
+                parent: Bitvector::<32>::deserialize(&[]).unwrap_err(),
+                sync_committee_size: 32,
+                expected_len: 4,
+                actual_len: 0,
+                sync_committee_bits: Vec::new(),
+            },
+            Error::proto_missing("Message", "field"),
+            Error::decode_error("field", Error::ZeroTimestampError),
+            Error::InvalidBytesLength {
+                expected: 32,
+                actual: 0,
+            },
+            Error::UnexpectedClientMessageType { url: String::new() },
+            Error::UnexpectedAnyPayload { type_url: "" },
+            Error::UnexpectedTypeUrl {
+                expected: &[],
+                got: String::new(),
+            },
+            Error::UnknownCommitmentValueCodec(2),
+            Error::InvalidForkVersionLength {
+                fork: String::new(),
+                length: 0,
+            },
+            Error::InvalidStorageRootLength(0),
+            Error::InvalidMerklePath(String::new()),
+            Error::DecodeLimitExceeded {
+                limit: "",
+                max: 0,
+                actual: 0,
+            },
+            Error::InvalidPublicKey {
+                index: 0,
+                reason: PointError::Infinity,
+            },
+            Error::InvalidAggregatePublicKey {
+                reason: PointError::Infinity,
+            },
+            Error::InvalidSignature {
+                reason: PointError::NotOnCurve,
+            },
+            Error::UnexpectedSyncCommitteeBitsLength {
+                sync_committee_size: 32,
+                expected_len: 4,
+                actual_len: 3,
+            },
+            Error::NonZeroSyncCommitteeBitsPadding {
+                sync_committee_size: 30,
+                last_byte: 0x40,
+            },
+            Error::EmptyAccountUpdate,
+            Error::UnexpectedSyncCommitteeSize {
+                expected: 32,
+                actual: 31,
+            },
+            Error::VerificationError(verifier_error()),
+            Error::ConsensusUpdateVerificationError {
+                signature_slot: U64(0),
+                signature_period: U64(0),
+                store_period: U64(0),
+                attested_slot: U64(0),
+                finalized_slot: U64(0),
+                fork_version: Version::default(),
+                signature_domain: Domain::default(),
+                err: verifier_error(),
+            },
+            Error::ExecutionUpdateVerificationError {
+                finalized_execution_root: H256::default(),
+                state_root: H256::default(),
+                block_number: U64(0),
+                err: verifier_error(),
+            },
+            Error::IntermediateConsensusUpdateError {
+                index: 0,
+                err: Box::new(Error::ZeroTimestampError),
+            },
+            Error::InvalidCurrentSyncCommitteeKeys {
+                period: U64(0),
+                stored: PublicKey::default(),
+                provided: PublicKey::default(),
+            },
+            Error::InvalidNextSyncCommitteeKeys {
+                period: U64(0),
+                stored: PublicKey::default(),
+                provided: PublicKey::default(),
+            },
+            Error::NoNextSyncCommitteeInConsensusState,
+            Error::NoNextSyncCommitteeInConsensusUpdate(U64(0), U64(1)),
+            Error::StoreNotSupportedFinalizedPeriod(U64(0), U64(2)),
+            Error::InsufficientSyncCommitteeParticipants {
+                min_sync_committee_participants: 1,
+                participants: 0,
+            },
+            Error::InsufficientTrustLevelParticipation {
+                participants: 0,
+                sync_committee_size: 32,
+                numerator: 2,
+                denominator: 3,
+            },
+            Error::SlotBeforeFirstFork {
+                slot: U64(0),
+                epoch: U64(0),
+                first_fork_epoch: U64(1),
+            },
+            Error::FinalizedSlotAfterAttestedSlot {
+                finalized_slot: U64(1),
+                attested_slot: U64(0),
+                signature_slot: U64(1),
+            },
+            Error::AttestedSlotNotBeforeSignatureSlot {
+                finalized_slot: U64(0),
+                attested_slot: U64(0),
+                signature_slot: U64(0),
+            },
+            Error::EmptyBranch("branch"),
+            Error::InconsistentExecutionBranchLengths {
+                state_root_branch: 1,
+                block_number_branch: 2,
+            },
+            Error::MissingNextSyncCommitteeInIntermediateUpdate { index: 0 },
+            Error::TooManyIntermediateConsensusUpdates {
+                max: U64(0),
+                actual: 1,
+            },
+            Error::UnexpectedTimestamp(0, 1),
+            Error::ZeroTimestampError,
+            Error::ZeroBlockNumberError,
+            Error::OutOfTrustingPeriod {
+                current_timestamp: Timestamp::none(),
+                trusting_period_end: Timestamp::none(),
+            },
+            Error::HeaderFromFuture {
+                current_timestamp: Timestamp::none(),
+                clock_drift: Duration::default(),
+                header_timestamp: Timestamp::none(),
+            },
+            Error::EthereumConsensusError(PublicKey::try_from(Vec::new()).unwrap_err()),
+            Error::SignaturePeriodOutOfRange {
+                trusted_period: U64(1),
+                signature_period: U64(0),
+            },
+            Error::UnexpectedTrustedSyncCommittee {
+                is_next: false,
+                trusted_period: U64(1),
+                signature_period: U64(2),
+            },
+            Error::SyncCommitteePeriodSkipped {
+                trusted_period: U64(1),
+                signature_period: U64(3),
+            },
+            Error::SlotBeyondLastFork {
+                slot: U64(64),
+                epoch: U64(8),
+                last_fork_epoch: U64(4),
+                fork_version: Version([0; 4]),
+                genesis_validators_root: H256::default(),
+                err: Box::new(Error::ZeroTimestampError),
+            },
+            Error::ZeroSyncCommitteeParticipants {
+                signature_slot: U64(64),
+            },
+            Error::FinalizedSlotNotAdvanced {
+                finalized_slot: U64(64),
+                trusted_slot: U64(64),
+            },
+            Error::InconsistentExecutionBlockHashBranch {
+                has_block_hash: true,
+                state_root_branch: 4,
+                block_hash_branch: 0,
+            },
+            Error::InvalidExecutionBlockHashBranch {
+                finalized_execution_root: H256::default(),
+                block_hash: H256::default(),
+                gindex: 28,
+            },
+            Error::DifferentPeriodInNextSyncCommitteeMisbehaviour(U64(0), U64(1)),
+            Error::NoNextSyncCommitteeInNextSyncCommitteeMisbehaviour,
+            Error::SameNextSyncCommitteeInNextSyncCommitteeMisbehaviour(PublicKey::default()),
+            Error::DifferentSlotInFinalizedHeaderMisbehaviour(U64(0), U64(1)),
+            Error::SameFinalizedHeaderInFinalizedHeaderMisbehaviour(BeaconBlockHeader::default()),
+            Error::UnexpectedClientIdInMisbehaviour(client_id(), client_id()),
+            Error::FinalizedHeaderMisbehaviourSlotMismatch {
+                slot_1: U64(0),
+                slot_2: U64(1),
+            },
+            Error::AmbiguousTrustedSyncCommitteesInMisbehaviour,
+            Error::MisbehaviourConsensusUpdateError {
+                index: 0,
+                err: Box::new(Error::ZeroTimestampError),
+            },
+            Error::IdenticalConsensusUpdatesInMisbehaviour,
+            Error::IdenticalFinalizedHeadersInMisbehaviour(U64(0)),
+            Error::NoNextSyncCommitteeInMisbehaviour { index: 0 },
+            Error::IdenticalNextSyncCommitteesInMisbehaviour(PublicKey::default()),
+            Error::NextSyncCommitteeMisbehaviourPeriodMismatch {
+                period_1: U64(0),
+                period_2: U64(1),
+            },
+            Error::InvalidProofFormatError(String::new()),
+            Error::UnexpectedCommitmentPrefix {
+                expected: Vec::new(),
+                got: Vec::new(),
+            },
+            Error::InvalidRawCommitmentValueLength {
+                path: String::new(),
+                length: 33,
+            },
+            Error::CommitmentNotFound {
+                path: String::new(),
+                key: H256::default(),
+            },
+            Error::CommitmentValueMismatch {
+                path: String::new(),
+                expected: H256::default(),
+                got: H256::default(),
+            },
+            Error::UnexpectedCommitment {
+                path: String::new(),
+                got: H256::default(),
+            },
+            Error::MalformedCommitmentProof {
+                path: String::new(),
+                description: String::new(),
+            },
+            Error::StorageProofWalkError {
+                path: String::new(),
+                key: H256::default(),
+                err: trie_walk_error(),
+            },
+            Error::BatchMembershipVerificationError {
+                index: 0,
+                err: Box::new(Error::ZeroTimestampError),
+            },
+            Error::ZeroStorageRoot {
+                path: String::new(),
+            },
+            Error::AccountProofWalkError {
+                address: String::new(),
+                err: trie_walk_error(),
+            },
+            Error::MalformedAccountProof {
+                address: String::new(),
+                description: String::new(),
+            },
+            Error::AccountStorageRootMismatch(
+                H256::default(),
+                H256::default(),
+                H256::default(),
+                String::new(),
+                Vec::new(),
+            ),
+            Error::EmptyAccountProof,
+            Error::RlpDecodeError(rlp::DecoderError::RlpIsTooShort),
+            Error::ProofResponseAddressMismatch {
+                expected: String::new(),
+                got: String::new(),
+            },
+            Error::StorageProofNotFound {
+                key: H256::default(),
+            },
+            Error::StorageProofRootMismatch {
+                key: H256::default(),
+                storage_hash: H256::default(),
+                root: H256::default(),
+            },
+            Error::StorageValueProofWalkError {
+                key: H256::default(),
+                err: trie_walk_error(),
+            },
+            Error::MalformedStorageValueProof {
+                key: H256::default(),
+                description: String::new(),
+            },
+            Error::EmptyAccountProofNode { index: 0 },
+            Error::ZeroAccountStorageRoot,
+            Error::InsufficientHeight {
+                latest_height: height(1),
+                target_height: height(2),
+            },
+            Error::UnexpectedHeightRevisionNumber {
+                expected: 0,
+                got: 1,
+            },
+            Error::ClientFrozen {
+                frozen_height: height(1),
+            },
+            Error::CannotInitializeFrozenClient,
+            Error::UninitializedClientStateField("field"),
+            Error::UninitializedConsensusStateField("field"),
+            Error::InfinitySyncCommitteeAggregatePubkey("field"),
+            Error::InvalidTrustLevel {
+                numerator: 1,
+                denominator: 4,
+            },
+            Error::MissingTrustingPeriod,
+            Error::NegativeMaxClockDrift,
+            Error::MissingBellatrixFork,
+            Error::UnorderedForkEpochs {
+                index: 1,
+                prev_epoch: U64(1),
+                epoch: U64(0),
+            },
+            Error::DuplicatedForkVersion {
+                index: 1,
+                version: Version([0; 4]),
+            },
+            Error::InitialConsensusStateTimestampMismatch {
+                slot: U64(0),
+                expected: Timestamp::none(),
+                actual: Timestamp::none(),
+            },
+            Error::InitialConsensusStateBeforeBellatrixFork {
+                slot: U64(0),
+                epoch: U64(0),
+                bellatrix_epoch: U64(1),
+            },
+            Error::ProcessedTimeNotFound {
+                client_id: client_id(),
+                height: height(1),
+            },
+            Error::ProcessedHeightNotFound {
+                client_id: client_id(),
+                height: height(1),
+            },
+            Error::NotEnoughTimeElapsed {
+                current_timestamp: Timestamp::none(),
+                earliest_time: Timestamp::none(),
+            },
+            Error::NotEnoughBlocksElapsed {
+                current_height: height(1),
+                earliest_height: height(2),
+            },
+            Error::HistoricalConsensusStateNotSupported {
+                height: height(1),
+                latest_height: height(2),
+            },
+            Error::ConsensusStateAlreadyStored { height: height(1) },
+            Error::ZeroConsensusStateStorageRoot,
+            Error::ICS02(ClientError::ClientSpecific {
+                description: String::new(),
+            }),
+            Error::ICS24(ClientId::from_str("").unwrap_err()),
+            Error::ContextError(ContextError::ClientError(ClientError::ClientSpecific {
+                description: String::new(),
+            })),
+            Error::TimestampOverflowError(TimestampOverflowError::TimestampOverflow),
+            Error::ParseTimestampError(Timestamp::from_str("x").unwrap_err()),
+        ]
+    }
+
+    #[test]
+    fn test_error_codes() {
+        let errors = all_errors();
+        assert_eq!(errors.len(), ERROR_CODES.len());
+        for (err, (name, code)) in errors.iter().zip(ERROR_CODES) {
+            let debug = format!("{:?}", err);
+            let variant = debug
+                .split(|c: char| !c.is_ascii_alphanumeric())
+                .next()
+                .unwrap();
+            assert_eq!(variant, *name);
+            assert_eq!(err.code(), *code, "{}", name);
+        }
+
+        let mut codes: Vec<u32> = ERROR_CODES.iter().map(|(_, code)| *code).collect();
+        codes.sort();
+        codes.dedup();
+        assert_eq!(codes.len(), ERROR_CODES.len(), "error codes must be unique");
+        let mut names: Vec<&str> = ERROR_CODES.iter().map(|(name, _)| *name).collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(
+            names.len(),
+            ERROR_CODES.len(),
+            "variants must be listed once"
+        );
+
+        // the code of a wrapper does not depend on the nested error
+        let wrapped = |err: Error| {
+            Error::IntermediateConsensusUpdateError {
+                index: 0,
+                err: Box::new(err),
+            }
+            .code()
+        };
+        assert_eq!(wrapped(Error::ZeroTimestampError), 2004);
+        assert_eq!(wrapped(Error::EmptyAccountProof), 2004);
+        assert_eq!(
+            Error::decode_error("field", Error::EmptyAccountProof).code(),
+            1005
+        );
+    }
+
+    #[test]
+    fn test_verification_error_messages() {
+        let irrelevant = || {
+            ethereum_light_client_verifier::errors::Error::IrrelevantConsensusUpdates(
+                "irrelevant".to_string(),
+            )
+        };
+
+        let err = Error::ConsensusUpdateVerificationError {
+            signature_slot: U64(203),
+            signature_period: U64(3),
+            store_period: U64(2),
+            attested_slot: U64(202),
+            finalized_slot: U64(192),
+            fork_version: Version([4, 0, 0, 0]),
+            signature_domain: Domain::default(),
+            err: irrelevant(),
+        };
+        assert_eq!(
+            err.to_string(),
+            format!("consensus update verification error: signature_slot=203 signature_period=3 store_period=2 attested_slot=202 finalized_slot=192 fork_version={:?} signature_domain={:?} {}", Version([4, 0, 0, 0]), Domain::default(), irrelevant())
+        );
+
+        let finalized_execution_root = H256([1u8; 32]);
+        let state_root = H256([2u8; 32]);
+        let err = Error::ExecutionUpdateVerificationError {
+            finalized_execution_root,
+            state_root,
+            block_number: U64(100),
+            err: irrelevant(),
+        };
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "execution update verification error: finalized_execution_root={} state_root={} block_number=100 {}",
+                finalized_execution_root,
+                state_root,
+                irrelevant()
+            )
+        );
+
+        let err = Error::IntermediateConsensusUpdateError {
+            index: 1,
+            err: Box::new(err),
+        };
+        assert!(err
+            .to_string()
+            .starts_with("intermediate consensus update verification error: index=1 execution update verification error: "));
+
+        let err = Error::InsufficientHeight {
+            latest_height: Height::new(0, 10).unwrap(),
+            target_height: Height::new(0, 11).unwrap(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "the height is insufficient: latest_height=`0-10` target_height=`0-11`"
+        );
+
+        let err = Error::StoreNotSupportedFinalizedPeriod(U64(2), U64(4));
+        assert_eq!(
+            err.to_string(),
+            "store does not support the finalized_period: store_period=2 finalized_period=4"
+        );
+    }
+
+    #[test]
+    fn test_client_error_conversion() {
+        // invalid header
+        let err = Error::ConsensusUpdateVerificationError {
+            signature_slot: U64(203),
+            signature_period: U64(3),
+            store_period: U64(2),
+            attested_slot: U64(202),
+            finalized_slot: U64(192),
+            fork_version: Version::default(),
+            signature_domain: Domain::default(),
+            err: verifier_error(),
+        };
+        let expected = err.to_string();
+        match ClientError::from(err) {
+            ClientError::HeaderVerificationFailure { reason } => assert_eq!(reason, expected),
+            err => panic!("unexpected error: {:?}", err),
+        }
+        let err = Error::IntermediateConsensusUpdateError {
+            index: 1,
+            err: Box::new(Error::InvalidCurrentSyncCommitteeKeys {
+                period: U64(1),
+                stored: PublicKey::default(),
+                provided: PublicKey::default(),
+            }),
+        };
+        let expected = err.to_string();
+        match ClientError::from(err) {
+            ClientError::HeaderVerificationFailure { reason } => assert_eq!(reason, expected),
+            err => panic!("unexpected error: {:?}", err),
+        }
+
+        // misbehaviour
+        let err = Error::IdenticalConsensusUpdatesInMisbehaviour;
+        let expected = err.to_string();
+        match ClientError::from(err) {
+            ClientError::MisbehaviourHandlingFailure { reason } => assert_eq!(reason, expected),
+            err => panic!("unexpected error: {:?}", err),
+        }
+
+        // expired
+        let current_timestamp = Timestamp::from_nanoseconds(2).unwrap();
+        let trusting_period_end = Timestamp::from_nanoseconds(1).unwrap();
+        match ClientError::from(Error::OutOfTrustingPeriod {
+            current_timestamp,
+            trusting_period_end,
+        }) {
+            ClientError::HeaderNotWithinTrustPeriod {
+                latest_time,
+                update_time,
+            } => {
+                assert_eq!(latest_time, trusting_period_end);
+                assert_eq!(update_time, current_timestamp);
+            }
+            err => panic!("unexpected error: {:?}", err),
+        }
+
+        // invalid proof
+        match ClientError::from(Error::InsufficientHeight {
+            latest_height: height(1),
+            target_height: height(2),
+        }) {
+            ClientError::InvalidProofHeight {
+                latest_height,
+                proof_height,
+            } => {
+                assert_eq!(latest_height, height(1));
+                assert_eq!(proof_height, height(2));
+            }
+            err => panic!("unexpected error: {:?}", err),
+        }
+        assert!(matches!(
+            ClientError::from(Error::CommitmentValueMismatch {
+                path: String::new(),
+                expected: H256::default(),
+                got: H256::default(),
+            }),
+            ClientError::InvalidCommitmentProof(CommitmentError::VerificationFailure)
+        ));
+        assert!(matches!(
+            ClientError::from(Error::MalformedCommitmentProof {
+                path: String::new(),
+                description: String::new(),
+            }),
+            ClientError::InvalidCommitmentProof(CommitmentError::InvalidMerkleProof)
+        ));
+
+        // wrapped client errors are returned as is
+        assert!(matches!(
+            ClientError::from(Error::ICS02(ClientError::ClientFrozen {
+                client_id: client_id()
+            })),
+            ClientError::ClientFrozen { .. }
+        ));
+        assert!(matches!(
+            ClientError::from(Error::ContextError(ContextError::ClientError(
+                ClientError::ClientFrozen {
+                    client_id: client_id()
+                }
+            ))),
+            ClientError::ClientFrozen { .. }
+        ));
+
+        // the others are client specific errors with the message of the whole chain
+        let err = Error::decode_error(
+            "trusted_sync_committee",
+            Error::ClientFrozen {
+                frozen_height: height(1),
+            },
+        );
+        let expected = err.to_string();
+        assert!(expected.contains("frozen_height=0-1"), "{}", expected);
+        match ClientError::from(err) {
+            ClientError::ClientSpecific { description } => assert_eq!(description, expected),
+            err => panic!("unexpected error: {:?}", err),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_error_source_chain() {
+        use crate::types::AccountUpdateInfo;
+        use ethereum_ibc_proto::ibc::lightclients::ethereum::v1::AccountUpdate as ProtoAccountUpdate;
+        use std::error::Error as _;
+
+        fn innermost(
+            err: &(dyn std::error::Error + 'static),
+        ) -> &(dyn std::error::Error + 'static) {
+            let mut err = err;
+            while let Some(source) = err.source() {
+                err = source;
+            }
+            err
+        }
+
+        // a list containing a truncated long string
+        let err = AccountUpdateInfo::try_from(ProtoAccountUpdate {
+            account_proof: vec![0xc1, 0xb8],
+            account_storage_root: vec![0u8; 32],
+        })
+        .unwrap_err();
+        assert!(matches!(err, Error::DecodeError { ref field, .. } if field == "account_proof"));
+        assert!(matches!(
+            err.source().unwrap().downcast_ref::<Error>(),
+            Some(Error::RlpDecodeError(_))
+        ));
+        assert!(innermost(&err)
+            .downcast_ref::<rlp::DecoderError>()
+            .is_some());
+
+        // the chain continues through the wrappers of the nested errors
+        let err = Error::IntermediateConsensusUpdateError {
+            index: 0,
+            err: Box::new(Error::decode_error(
+                "sync_committee_bits",
+                Error::DeserializeSyncCommitteeBitsError {
+                    parent: Bitvector::<32>::deserialize(&[0u8; 3]).unwrap_err(),
+                    sync_committee_size: 32,
+                    expected_len: 4,
+                    actual_len: 3,
+                    sync_committee_bits: vec![0u8; 3],
+                },
+            )),
+        };
+        assert!(innermost(&err)
+            .downcast_ref::<ssz_rs::DeserializeError>()
+            .is_some());
+
+        let err = Error::from(ClientError::ClientSpecific {
+            description: "client".to_string(),
+        });
+        assert!(innermost(&err).downcast_ref::<ClientError>().is_some());
+        assert!(Error::ZeroTimestampError.source().is_none());
+    }
+}
@@ -0,0 +1,178 @@
+//! Storage locations of Solidity state variables
+//!
+//! These follow the storage layout rules of Solidity: https://docs.soliditylang.org/en/v0.8.20/internals/layout_in_storage.html
+use crate::commitment::keccak_256;
+use ethereum_consensus::types::H256;
+
+/// Calculate the storage slot of the value of a mapping at `base_slot` for the key encoded as `key_bytes`
+///
+/// The slot is `keccak256(key_bytes ++ base_slot)`. `key_bytes` must be the key encoded as Solidity does:
+/// a value type, e.g. `uint256`, `address` or `bytes32`, is padded to a 32-byte word as in the ABI encoding,
+/// and `bytes` or `string` is its bytes without padding or length.
+pub fn mapping_slot(key_bytes: &[u8], base_slot: &H256) -> H256 {
+    H256(keccak_256(&[key_bytes, base_slot.as_bytes()].concat()))
+}
+
+/// Calculate the storage slot of the value of nested mappings at `base_slot` for `keys`, from the outermost mapping
+///
+/// e.g. `m[k1][k2]` of `mapping(K1 => mapping(K2 => V)) m` is `nested_mapping_slot(&[k1, k2], m)`,
+/// where each key is encoded as in `mapping_slot`. Returns `base_slot` itself if `keys` is empty.
+pub fn nested_mapping_slot(keys: &[&[u8]], base_slot: &H256) -> H256 {
+    keys.iter()
+        .fold(*base_slot, |slot, key| mapping_slot(key, &slot))
+}
+
+/// Calculate the storage slot of the element at `index` of a dynamic array at `base_slot` whose elements are `elem_size` bytes
+///
+/// The elements are stored from `keccak256(base_slot)`. An element smaller than 32 bytes is packed with its neighbours,
+/// i.e. a slot holds `32 / elem_size` elements and this returns the slot containing the element,
+/// and an element of 32 bytes or more, e.g. a struct, occupies `ceil(elem_size / 32)` slots and this returns its first slot.
+/// The offset into the slot is wrapped around 2^256 as in Solidity.
+///
+/// # Panics
+///
+/// Panics if `elem_size` is zero.
+pub fn array_element_slot(base_slot: &H256, index: u64, elem_size: usize) -> H256 {
+    assert!(elem_size > 0, "array element size must be non-zero");
+    let offset = if elem_size < 32 {
+        (index / (32 / elem_size) as u64) as u128
+    } else {
+        index as u128 * ((elem_size + 31) / 32) as u128
+    };
+    add_to_slot(&H256(keccak_256(base_slot.as_bytes())), offset)
+}
+
+/// Returns `slot + offset` modulo 2^256
+fn add_to_slot(slot: &H256, offset: u128) -> H256 {
+    let mut out = slot.0;
+    let mut carry = offset;
+    for b in out.iter_mut().rev() {
+        if carry == 0 {
+            break;
+        }
+        let sum = *b as u128 + (carry & 0xff);
+        *b = sum as u8;
+        carry = (carry >> 8) + (sum >> 8);
+    }
+    H256(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+
+    // The slots of the following contract:
+    //
+    // contract Layout {
+    //     uint256 counter;                                        // slot 0
+    //     mapping(bytes32 => bytes32) commitments;                // slot 1
+    //     mapping(string => mapping(uint64 => bytes32)) nested;   // slot 2
+    //     uint64[] packed;                                        // slot 3
+    //     bytes32[] words;                                        // slot 4
+    //     Triple[] triples;                                       // slot 5, `struct Triple { uint256 a; uint256 b; uint256 c; }`
+    //     mapping(address => uint256) balances;                   // slot 6
+    // }
+    fn slot(n: u8) -> H256 {
+        let mut slot = [0u8; 32];
+        slot[31] = n;
+        H256(slot)
+    }
+
+    #[test]
+    fn test_mapping_slot() {
+        // commitments[keccak256("connections/connection-0")]
+        assert_eq!(
+            mapping_slot(&keccak_256(b"connections/connection-0"), &slot(1)),
+            H256(hex!(
+                "8fa11e2c54684274bc15bcf40dd2fb5f578d16fab2b198a61426d6ded25663f6"
+            ))
+        );
+        // balances[0x00000000000000000000000000000000000000aa]
+        let mut address = [0u8; 32];
+        address[31] = 0xaa;
+        assert_eq!(
+            mapping_slot(&address, &slot(6)),
+            H256(hex!(
+                "2497362b1265b72a2d08ffe21ad6eae809e05da456ede5913d1c8c28d6751f15"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_nested_mapping_slot() {
+        // nested["transfer"][7]
+        let mut seven = [0u8; 32];
+        seven[31] = 7;
+        let expected = H256(hex!(
+            "d17113d791d35bb420abca0021611ba5c870137a5c203572744754b77d1b12c7"
+        ));
+        assert_eq!(
+            nested_mapping_slot(&[b"transfer".as_slice(), seven.as_slice()], &slot(2)),
+            expected
+        );
+        assert_eq!(
+            mapping_slot(&seven, &mapping_slot(b"transfer", &slot(2))),
+            expected
+        );
+        assert_eq!(nested_mapping_slot(&[], &slot(2)), slot(2));
+    }
+
+    #[test]
+    fn test_array_element_slot() {
+        let packed_base = H256(hex!(
+            "c2575a0e9e593c00f959f8c92f12db2869c3395a3b0502d05e2516446f71f85b"
+        ));
+        // packed[0..4] share the first slot and packed[4..8] the next one
+        for index in 0..4 {
+            assert_eq!(array_element_slot(&slot(3), index, 8), packed_base);
+        }
+        assert_eq!(
+            array_element_slot(&slot(3), 5, 8),
+            H256(hex!(
+                "c2575a0e9e593c00f959f8c92f12db2869c3395a3b0502d05e2516446f71f85c"
+            ))
+        );
+        // words[2]
+        assert_eq!(
+            array_element_slot(&slot(4), 2, 32),
+            H256(hex!(
+                "8a35acfbc15ff81a39ae7d344fd709f28e8600b4aa8c65c6b64bfe7fe36bd19d"
+            ))
+        );
+        // triples[2] starts at the 6th slot from the base
+        assert_eq!(
+            array_element_slot(&slot(5), 2, 96),
+            H256(hex!(
+                "036b6384b5eca791c62761152d0c79bb0604c104a5fb6f4eb0703f3154bb3db6"
+            ))
+        );
+        // a struct of 33 bytes occupies 2 slots
+        assert_eq!(
+            array_element_slot(&slot(5), 3, 33),
+            array_element_slot(&slot(5), 6, 32)
+        );
+    }
+
+    #[test]
+    fn test_add_to_slot() {
+        assert_eq!(add_to_slot(&slot(0xff), 1), {
+            let mut expected = [0u8; 32];
+            expected[30] = 1;
+            H256(expected)
+        });
+        assert_eq!(add_to_slot(&H256([0xff; 32]), 1), H256::default());
+        assert_eq!(add_to_slot(&H256([0xff; 32]), u128::MAX), {
+            let mut expected = [0u8; 32];
+            expected[16..].copy_from_slice(&(u128::MAX - 1).to_be_bytes());
+            H256(expected)
+        });
+        assert_eq!(add_to_slot(&slot(1), 0), slot(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "array element size must be non-zero")]
+    fn test_array_element_slot_zero_size() {
+        array_element_slot(&slot(3), 0, 0);
+    }
+}
@@ -0,0 +1,534 @@
+//! Conversion of captured beacon API light client updates and `eth_getProof` responses into header fixtures
+//!
+//! `build_fixture` takes a `FixtureInput`, which consists of the chain constants as a `ClientState`, the trusted states,
+//! the `data` of a `LightClientUpdate` of the beacon API (`/eth/v1/beacon/light_client/updates`) and the `eth_getProof` response of the IBC contract at the finalized block.
+//! It verifies the resulting header against the trusted states and returns the header, the updated states and their proto-encoded forms.
+//! The `ethereum-ibc-fixture` binary of the `tools` feature runs it on a JSON file.
+//!
+//! The execution payload header of the finalized header is merkleized here to derive the finalized execution root and the branches of the state root, the block number and the block hash,
+//! which the beacon API does not provide.
+use crate::client_state::ClientState;
+use crate::consensus_state::ConsensusState;
+use crate::header::Header;
+use crate::internal_prelude::*;
+use crate::json::{BeaconBlockHeaderJson, BeaconJson, SyncAggregateJson, SyncCommitteeJson};
+use crate::merkle::{hash_pair, merkle_root_and_branches};
+use crate::misc::compute_timestamp_at_slot;
+use crate::types::{
+    AccountUpdateInfo, ConsensusUpdateInfo, ExecutionUpdateInfo, TrustedSyncCommittee,
+};
+use crate::verify::verify_header;
+use alloy_rpc_types_eth::EIP1186AccountProofResponse;
+use ethereum_consensus::beacon::BeaconBlockHeader;
+use ethereum_consensus::sync_protocol::{SyncAggregate, SyncCommittee};
+use ethereum_consensus::types::{H256, U64};
+use ethereum_ibc_proto::ibc::lightclients::ethereum::v1::Header as RawHeader;
+use ibc::timestamp::Timestamp;
+use ibc_proto::google::protobuf::Any as IBCAny;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+
+/// The index of `state_root` among the fields of the execution payload header
+const EXECUTION_PAYLOAD_STATE_ROOT_INDEX: usize = 2;
+/// The index of `block_number` among the fields of the execution payload header
+const EXECUTION_PAYLOAD_BLOCK_NUMBER_INDEX: usize = 6;
+/// The index of `block_hash` among the fields of the execution payload header
+const EXECUTION_PAYLOAD_BLOCK_HASH_INDEX: usize = 12;
+
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct FixtureInput<const SYNC_COMMITTEE_SIZE: usize> {
+    /// The client state before the update, which gives the chain constants
+    pub client_state: ClientState<SYNC_COMMITTEE_SIZE>,
+    /// The consensus state stored at `trusted_sync_committee.height`
+    pub trusted_consensus_state: ConsensusState,
+    pub trusted_sync_committee: TrustedSyncCommittee<SYNC_COMMITTEE_SIZE>,
+    /// The updates applied before `light_client_update`, each of which must contain the next sync committee
+    #[serde(default)]
+    pub intermediate_light_client_updates: Vec<LightClientUpdateJson>,
+    pub light_client_update: LightClientUpdateJson,
+    /// The `eth_getProof` response of the IBC contract at the finalized execution block
+    pub eth_get_proof: EIP1186AccountProofResponse,
+    /// The current time of the host in nanoseconds
+    #[serde(with = "crate::json")]
+    pub now: Timestamp,
+}
+
+/// The `data` of a `LightClientUpdate` of the beacon API
+#[derive(Serialize, Deserialize)]
+pub struct LightClientUpdateJson {
+    pub attested_header: LightClientHeaderJson,
+    #[serde(default)]
+    pub next_sync_committee: Option<SyncCommitteeJson>,
+    #[serde(default)]
+    pub next_sync_committee_branch: Option<Vec<String>>,
+    pub finalized_header: LightClientHeaderJson,
+    pub finality_branch: Vec<String>,
+    pub sync_aggregate: SyncAggregateJson,
+    pub signature_slot: String,
+}
+
+/// The `LightClientHeader` of the beacon API since Capella
+#[derive(Serialize, Deserialize)]
+pub struct LightClientHeaderJson {
+    pub beacon: BeaconBlockHeaderJson,
+    pub execution: ExecutionPayloadHeaderJson,
+    pub execution_branch: Vec<String>,
+}
+
+/// The `ExecutionPayloadHeader` of the beacon API, of which the blob fields are present since Deneb
+#[derive(Serialize, Deserialize)]
+pub struct ExecutionPayloadHeaderJson {
+    pub parent_hash: String,
+    pub fee_recipient: String,
+    pub state_root: String,
+    pub receipts_root: String,
+    pub logs_bloom: String,
+    pub prev_randao: String,
+    pub block_number: String,
+    pub gas_limit: String,
+    pub gas_used: String,
+    pub timestamp: String,
+    pub extra_data: String,
+    pub base_fee_per_gas: String,
+    pub block_hash: String,
+    pub transactions_root: String,
+    pub withdrawals_root: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blob_gas_used: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub excess_blob_gas: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct Fixture<const SYNC_COMMITTEE_SIZE: usize> {
+    pub header: Header<SYNC_COMMITTEE_SIZE>,
+    /// The proto-encoded `Header` as a 0x-prefixed hex string
+    pub header_proto: String,
+    /// The proto-encoded `Any` of the header as a 0x-prefixed hex string
+    pub header_any: String,
+    /// The client state after applying the header
+    pub client_state: ClientState<SYNC_COMMITTEE_SIZE>,
+    pub client_state_any: String,
+    /// The consensus state after applying the header
+    pub consensus_state: ConsensusState,
+    pub consensus_state_any: String,
+}
+
+/// Build the header of `input` and verify it against the trusted states of `input`
+pub fn build_fixture<const SYNC_COMMITTEE_SIZE: usize>(
+    input: FixtureInput<SYNC_COMMITTEE_SIZE>,
+) -> Result<Fixture<SYNC_COMMITTEE_SIZE>, String> {
+    let header = build_header(&input)?;
+    let (client_state, consensus_state) = verify_header(
+        &input.client_state,
+        &input.trusted_consensus_state,
+        header.clone(),
+        input.now,
+    )
+    .map_err(|e| format!("the header is rejected: {}", e))?;
+    Ok(Fixture {
+        header_proto: encode_hex(&RawHeader::from(header.clone()).encode_to_vec()),
+        header_any: encode_hex(&IBCAny::from(header.clone()).encode_to_vec()),
+        header,
+        client_state_any: encode_hex(&IBCAny::from(client_state.clone()).encode_to_vec()),
+        client_state,
+        consensus_state_any: encode_hex(&IBCAny::from(consensus_state.clone()).encode_to_vec()),
+        consensus_state,
+    })
+}
+
+/// Build the header of `input` without verifying it
+pub fn build_header<const SYNC_COMMITTEE_SIZE: usize>(
+    input: &FixtureInput<SYNC_COMMITTEE_SIZE>,
+) -> Result<Header<SYNC_COMMITTEE_SIZE>, String> {
+    let intermediate_consensus_updates = input
+        .intermediate_light_client_updates
+        .iter()
+        .enumerate()
+        .map(|(i, update)| {
+            to_consensus_update_info(update)
+                .map_err(|e| format!("intermediate_light_client_updates[{}]: {}", i, e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let consensus_update = field(
+        "light_client_update",
+        to_consensus_update_info(&input.light_client_update),
+    )?;
+    let execution_update = field(
+        "light_client_update.finalized_header.execution",
+        to_execution_update_info(&input.light_client_update.finalized_header.execution),
+    )?;
+    let account_update = AccountUpdateInfo::try_from_proof_response(
+        &input.eth_get_proof,
+        &input.client_state.ibc_address,
+        execution_update.state_root,
+    )
+    .map_err(|e| format!("eth_get_proof: {}", e))?;
+    let timestamp = compute_timestamp_at_slot(
+        input.client_state.genesis_time,
+        input.client_state.seconds_per_slot,
+        consensus_update.finalized_header.0.slot,
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(Header {
+        trusted_sync_committee: input.trusted_sync_committee.clone(),
+        intermediate_consensus_updates,
+        consensus_update,
+        execution_update,
+        account_update,
+        timestamp,
+    })
+}
+
+fn to_consensus_update_info<const SYNC_COMMITTEE_SIZE: usize>(
+    update: &LightClientUpdateJson,
+) -> Result<ConsensusUpdateInfo<SYNC_COMMITTEE_SIZE>, String> {
+    let next_sync_committee =
+        match (
+            &update.next_sync_committee,
+            &update.next_sync_committee_branch,
+        ) {
+            (Some(sync_committee), Some(branch)) => Some((
+                field(
+                    "next_sync_committee",
+                    SyncCommittee::from_json(sync_committee.clone()),
+                )?,
+                field("next_sync_committee_branch", branch_from_json(branch))?,
+            )),
+            (None, None) => None,
+            _ => return Err(
+                "next_sync_committee and next_sync_committee_branch must be both present or absent"
+                    .into(),
+            ),
+        };
+    let (finalized_execution_root, _) = field(
+        "finalized_header.execution",
+        merkleize_execution_payload_header(&update.finalized_header.execution),
+    )?;
+    Ok(ConsensusUpdateInfo {
+        attested_header: field(
+            "attested_header.beacon",
+            BeaconBlockHeader::from_json(update.attested_header.beacon.clone()),
+        )?,
+        next_sync_committee,
+        finalized_header: (
+            field(
+                "finalized_header.beacon",
+                BeaconBlockHeader::from_json(update.finalized_header.beacon.clone()),
+            )?,
+            field("finality_branch", branch_from_json(&update.finality_branch))?,
+        ),
+        sync_aggregate: field(
+            "sync_aggregate",
+            SyncAggregate::from_json(update.sync_aggregate.clone()),
+        )?,
+        signature_slot: field(
+            "signature_slot",
+            U64::from_json(update.signature_slot.clone()),
+        )?,
+        finalized_execution_root,
+        finalized_execution_branch: field(
+            "finalized_header.execution_branch",
+            branch_from_json(&update.finalized_header.execution_branch),
+        )?,
+    })
+}
+
+fn to_execution_update_info(
+    execution: &ExecutionPayloadHeaderJson,
+) -> Result<ExecutionUpdateInfo, String> {
+    let (_, branches) = merkleize_execution_payload_header(execution)?;
+    let [state_root_branch, block_number_branch, block_hash_branch] = branches;
+    Ok(ExecutionUpdateInfo {
+        state_root: field("state_root", H256::from_json(execution.state_root.clone()))?,
+        state_root_branch,
+        block_number: field(
+            "block_number",
+            U64::from_json(execution.block_number.clone()),
+        )?,
+        block_number_branch,
+        block_hash: Some(field(
+            "block_hash",
+            H256::from_json(execution.block_hash.clone()),
+        )?),
+        block_hash_branch,
+    })
+}
+
+/// Returns the hash tree root of the execution payload header and the branches of `state_root`, `block_number` and `block_hash`
+fn merkleize_execution_payload_header(
+    execution: &ExecutionPayloadHeaderJson,
+) -> Result<(H256, [Vec<H256>; 3]), String> {
+    let mut leaves = Vec::from([
+        field("parent_hash", bytes_chunk(&execution.parent_hash, 32))?,
+        field("fee_recipient", bytes_chunk(&execution.fee_recipient, 20))?,
+        field("state_root", bytes_chunk(&execution.state_root, 32))?,
+        field("receipts_root", bytes_chunk(&execution.receipts_root, 32))?,
+        field("logs_bloom", logs_bloom_root(&execution.logs_bloom))?,
+        field("prev_randao", bytes_chunk(&execution.prev_randao, 32))?,
+        field("block_number", uint64_chunk(&execution.block_number))?,
+        field("gas_limit", uint64_chunk(&execution.gas_limit))?,
+        field("gas_used", uint64_chunk(&execution.gas_used))?,
+        field("timestamp", uint64_chunk(&execution.timestamp))?,
+        field("extra_data", extra_data_root(&execution.extra_data))?,
+        field(
+            "base_fee_per_gas",
+            uint256_chunk(&execution.base_fee_per_gas),
+        )?,
+        field("block_hash", bytes_chunk(&execution.block_hash, 32))?,
+        field(
+            "transactions_root",
+            bytes_chunk(&execution.transactions_root, 32),
+        )?,
+        field(
+            "withdrawals_root",
+            bytes_chunk(&execution.withdrawals_root, 32),
+        )?,
+    ]);
+    match (&execution.blob_gas_used, &execution.excess_blob_gas) {
+        (Some(blob_gas_used), Some(excess_blob_gas)) => {
+            leaves.push(field("blob_gas_used", uint64_chunk(blob_gas_used))?);
+            leaves.push(field("excess_blob_gas", uint64_chunk(excess_blob_gas))?);
+        }
+        (None, None) => {}
+        _ => return Err("blob_gas_used and excess_blob_gas must be both present or absent".into()),
+    }
+    let (root, mut branches) = merkle_root_and_branches(
+        &leaves,
+        &[
+            EXECUTION_PAYLOAD_STATE_ROOT_INDEX,
+            EXECUTION_PAYLOAD_BLOCK_NUMBER_INDEX,
+            EXECUTION_PAYLOAD_BLOCK_HASH_INDEX,
+        ],
+    );
+    let block_hash_branch = branches.pop().unwrap();
+    let block_number_branch = branches.pop().unwrap();
+    let state_root_branch = branches.pop().unwrap();
+    Ok((
+        H256(root),
+        [state_root_branch, block_number_branch, block_hash_branch],
+    ))
+}
+
+/// A byte vector of at most 32 bytes, right-padded with zeros
+fn bytes_chunk(s: &str, len: usize) -> Result<[u8; 32], String> {
+    let bz = decode_hex(s)?;
+    if bz.len() != len {
+        return Err(format!("expected {} bytes but got {} bytes", len, bz.len()));
+    }
+    let mut chunk = [0u8; 32];
+    chunk[..len].copy_from_slice(&bz);
+    Ok(chunk)
+}
+
+fn uint64_chunk(s: &str) -> Result<[u8; 32], String> {
+    let value = U64::from_json(s.to_string())?;
+    let mut chunk = [0u8; 32];
+    chunk[..8].copy_from_slice(&value.0.to_le_bytes());
+    Ok(chunk)
+}
+
+/// A decimal string of a uint256 in little-endian
+fn uint256_chunk(s: &str) -> Result<[u8; 32], String> {
+    if s.is_empty() {
+        return Err("empty decimal string".into());
+    }
+    let mut chunk = [0u8; 32];
+    for c in s.chars() {
+        let digit = c
+            .to_digit(10)
+            .ok_or_else(|| format!("invalid decimal string `{}`", s))?;
+        let mut carry = digit;
+        for b in chunk.iter_mut() {
+            let v = *b as u32 * 10 + carry;
+            *b = v as u8;
+            carry = v >> 8;
+        }
+        if carry != 0 {
+            return Err(format!("`{}` overflows uint256", s));
+        }
+    }
+    Ok(chunk)
+}
+
+/// The hash tree root of `ByteVector[256]`
+fn logs_bloom_root(s: &str) -> Result<[u8; 32], String> {
+    let bz = decode_hex(s)?;
+    if bz.len() != 256 {
+        return Err(format!("expected 256 bytes but got {} bytes", bz.len()));
+    }
+    let chunks: Vec<[u8; 32]> = bz
+        .chunks(32)
+        .map(|chunk| chunk.try_into().unwrap())
+        .collect();
+    Ok(merkle_root_and_branches(&chunks, &[]).0)
+}
+
+/// The hash tree root of `ByteList[32]`, i.e. the chunk of the data mixed in with its length
+fn extra_data_root(s: &str) -> Result<[u8; 32], String> {
+    let bz = decode_hex(s)?;
+    if bz.len() > 32 {
+        return Err(format!(
+            "expected at most 32 bytes but got {} bytes",
+            bz.len()
+        ));
+    }
+    let mut chunk = [0u8; 32];
+    chunk[..bz.len()].copy_from_slice(&bz);
+    let mut length = [0u8; 32];
+    length[..8].copy_from_slice(&(bz.len() as u64).to_le_bytes());
+    Ok(hash_pair(&chunk, &length))
+}
+
+fn branch_from_json(json: &[String]) -> Result<Vec<H256>, String> {
+    Vec::<H256>::from_json(json.to_vec())
+}
+
+fn field<T>(name: &str, res: Result<T, String>) -> Result<T, String> {
+    res.map_err(|e| format!("{}: {}", name, e))
+}
+
+fn encode_hex(bz: &[u8]) -> String {
+    format!("0x{}", hex::encode(bz))
+}
+
+fn decode_hex(s: &str) -> Result<Vec<u8>, String> {
+    let hex_str = s
+        .strip_prefix("0x")
+        .ok_or_else(|| format!("hex string must start with `0x`: `{}`", s))?;
+    hex::decode(hex_str).map_err(|e| format!("invalid hex string `{}`: {}", s, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn execution_payload_header() -> ExecutionPayloadHeaderJson {
+        let word = |b: u8| format!("0x{}", hex::encode([b; 32]));
+        ExecutionPayloadHeaderJson {
+            parent_hash: word(1),
+            fee_recipient: format!("0x{}", hex::encode([2u8; 20])),
+            state_root: word(3),
+            receipts_root: word(4),
+            logs_bloom: format!("0x{}", hex::encode([5u8; 256])),
+            prev_randao: word(6),
+            block_number: "7".into(),
+            gas_limit: "8".into(),
+            gas_used: "9".into(),
+            timestamp: "10".into(),
+            extra_data: "0x0b".into(),
+            base_fee_per_gas: "12".into(),
+            block_hash: word(13),
+            transactions_root: word(14),
+            withdrawals_root: word(15),
+            blob_gas_used: Some("16".into()),
+            excess_blob_gas: Some("17".into()),
+        }
+    }
+
+    fn compute_root(leaf: [u8; 32], mut index: usize, branch: &[H256]) -> [u8; 32] {
+        branch.iter().fold(leaf, |node, sibling| {
+            let node = if index % 2 == 0 {
+                hash_pair(&node, &sibling.0)
+            } else {
+                hash_pair(&sibling.0, &node)
+            };
+            index /= 2;
+            node
+        })
+    }
+
+    #[test]
+    fn test_merkleize_execution_payload_header() {
+        let execution = execution_payload_header();
+        let (root, [state_root_branch, block_number_branch, block_hash_branch]) =
+            merkleize_execution_payload_header(&execution).unwrap();
+        // 17 fields since Deneb make a tree of depth 5
+        assert_eq!(state_root_branch.len(), 5);
+        assert_eq!(
+            compute_root(
+                [3u8; 32],
+                EXECUTION_PAYLOAD_STATE_ROOT_INDEX,
+                &state_root_branch
+            ),
+            root.0
+        );
+        assert_eq!(
+            compute_root(
+                uint64_chunk("7").unwrap(),
+                EXECUTION_PAYLOAD_BLOCK_NUMBER_INDEX,
+                &block_number_branch
+            ),
+            root.0
+        );
+        assert_eq!(
+            compute_root(
+                [13u8; 32],
+                EXECUTION_PAYLOAD_BLOCK_HASH_INDEX,
+                &block_hash_branch
+            ),
+            root.0
+        );
+        let execution_update = to_execution_update_info(&execution).unwrap();
+        assert_eq!(execution_update.state_root, H256([3u8; 32]));
+        assert_eq!(execution_update.block_number, U64(7));
+        assert_eq!(execution_update.state_root_branch, state_root_branch);
+        assert_eq!(execution_update.block_hash, Some(H256([13u8; 32])));
+        assert_eq!(execution_update.block_hash_branch, block_hash_branch);
+
+        // 15 fields in Capella make a tree of depth 4
+        let capella = ExecutionPayloadHeaderJson {
+            blob_gas_used: None,
+            excess_blob_gas: None,
+            ..execution_payload_header()
+        };
+        let (capella_root, [state_root_branch, _, _]) =
+            merkleize_execution_payload_header(&capella).unwrap();
+        assert_eq!(state_root_branch.len(), 4);
+        assert_ne!(capella_root, root);
+
+        let res = merkleize_execution_payload_header(&ExecutionPayloadHeaderJson {
+            excess_blob_gas: None,
+            ..execution_payload_header()
+        });
+        assert!(res.is_err(), "{:?}", res);
+        let res = merkleize_execution_payload_header(&ExecutionPayloadHeaderJson {
+            extra_data: format!("0x{}", hex::encode([0u8; 33])),
+            ..execution_payload_header()
+        });
+        assert!(res.unwrap_err().starts_with("extra_data: "));
+    }
+
+    #[test]
+    fn test_ssz_chunks() {
+        let mut expected = [0u8; 32];
+        expected[0] = 1;
+        assert_eq!(uint256_chunk("1").unwrap(), expected);
+        expected[0] = 0;
+        expected[1] = 1;
+        assert_eq!(uint256_chunk("256").unwrap(), expected);
+        assert_eq!(
+            uint256_chunk(
+                "115792089237316195423570985008687907853269984665640564039457584007913129639935"
+            )
+            .unwrap(),
+            [0xffu8; 32]
+        );
+        assert!(uint256_chunk(
+            "115792089237316195423570985008687907853269984665640564039457584007913129639936"
+        )
+        .is_err());
+        assert!(uint256_chunk("").is_err());
+        assert!(uint256_chunk("0x1").is_err());
+
+        // the empty extra data is the zero chunk mixed in with the zero length
+        assert_eq!(
+            extra_data_root("0x").unwrap(),
+            hash_pair(&[0u8; 32], &[0u8; 32])
+        );
+        assert!(bytes_chunk("0x0102", 20).is_err());
+        assert!(logs_bloom_root("0x00").is_err());
+    }
+}
@@ -1,18 +1,21 @@
+use crate::cache::{NoCache, SyncCommitteeCache};
 use crate::errors::Error;
 use crate::internal_prelude::*;
 use crate::misbehaviour::{
     Misbehaviour, ETHEREUM_FINALIZED_HEADER_MISBEHAVIOUR_TYPE_URL,
     ETHEREUM_NEXT_SYNC_COMMITTEE_MISBEHAVIOUR_TYPE_URL,
 };
+use crate::misc::{compute_timestamp_at_slot, height_from_block_number};
+use crate::trace::{trace_span, traced};
 use crate::types::{
     convert_consensus_update_to_proto, convert_execution_update_to_proto,
-    convert_proto_to_consensus_update, convert_proto_to_execution_update, AccountUpdateInfo,
-    ConsensusUpdateInfo, ExecutionUpdateInfo, TrustedSyncCommittee,
+    convert_proto_to_consensus_update, convert_proto_to_execution_update, decode_required,
+    AccountUpdateInfo, ConsensusUpdateInfo, ExecutionUpdateInfo, TrustedSyncCommittee,
 };
 use bytes::Buf;
-use ethereum_consensus::compute::compute_timestamp_at_slot;
 use ethereum_consensus::context::ChainContext;
 use ethereum_consensus::types::U64;
+use ethereum_ibc_proto::any::AnyMessage;
 use ethereum_ibc_proto::ibc::lightclients::ethereum::v1::Header as RawHeader;
 use ethereum_light_client_verifier::updates::ConsensusUpdate;
 use ibc::core::ics02_client::error::ClientError;
@@ -22,8 +25,9 @@ use ibc_proto::google::protobuf::Any as IBCAny;
 use ibc_proto::protobuf::Protobuf;
 use prost::Message;
 
-pub const ETHEREUM_HEADER_TYPE_URL: &str = "/ibc.lightclients.ethereum.v1.Header";
+pub const ETHEREUM_HEADER_TYPE_URL: &str = RawHeader::TYPE_URL;
 
+/// A client message submitted to the client, which is a header or a misbehaviour depending on the type URL of `Any`
 #[allow(clippy::large_enum_variant)]
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 pub enum ClientMessage<const SYNC_COMMITTEE_SIZE: usize> {
@@ -47,7 +51,7 @@ impl<const SYNC_COMMITTEE_SIZE: usize> TryFrom<IBCAny> for ClientMessage<SYNC_CO
                 let misbehaviour = Misbehaviour::<SYNC_COMMITTEE_SIZE>::try_from(raw)?;
                 Ok(Self::Misbehaviour(misbehaviour))
             }
-            _ => Err(Error::UnknownMessageType(raw.type_url)),
+            _ => Err(Error::UnexpectedClientMessageType { url: raw.type_url }),
         }
     }
 }
@@ -63,15 +67,23 @@ impl<const SYNC_COMMITTEE_SIZE: usize> From<ClientMessage<SYNC_COMMITTEE_SIZE>>
 
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct Header<const SYNC_COMMITTEE_SIZE: usize> {
-    /// trusted sync committee corresponding to the period of the signature slot of the `consensus_update`
+    /// trusted sync committee corresponding to the period of the signature slot of the first consensus update
     pub trusted_sync_committee: TrustedSyncCommittee<SYNC_COMMITTEE_SIZE>,
-    /// consensus update attested by the `trusted_sync_committee`
+    /// consensus updates applied in order before `consensus_update`
+    ///
+    /// Each update must contain the next sync committee, which is used to verify the following update.
+    pub intermediate_consensus_updates: Vec<ConsensusUpdateInfo<SYNC_COMMITTEE_SIZE>>,
+    /// consensus update attested by the `trusted_sync_committee`, or by the next sync committee of the last intermediate consensus update if any
     pub consensus_update: ConsensusUpdateInfo<SYNC_COMMITTEE_SIZE>,
     /// execution update based on the `consensus_update.finalized_header`
     pub execution_update: ExecutionUpdateInfo,
     /// account update based on the `execution_update.state_root`
+    ///
+    /// It is required even if the IBC contract's storage has not changed since the trusted consensus state,
+    /// since the storage root of the new consensus state is only trusted once it is proven against the new state root.
     pub account_update: AccountUpdateInfo,
     /// timestamp of the `consensus_update.finalized_header`
+    #[cfg_attr(feature = "json", serde(with = "crate::json"))]
     pub timestamp: Timestamp,
 }
 
@@ -82,41 +94,146 @@ pub fn decode_header<const SYNC_COMMITTEE_SIZE: usize, B: Buf>(
 }
 
 impl<const SYNC_COMMITTEE_SIZE: usize> Header<SYNC_COMMITTEE_SIZE> {
+    /// Returns the height at which the new consensus state is stored, i.e. the block number of the `execution_update`
+    ///
+    /// Panics if the block number is zero, which is rejected by `validate_basic`.
+    pub fn height(&self) -> ibc::Height {
+        height_from_block_number(self.execution_update.block_number).unwrap()
+    }
+
+    /// Returns the height of the trusted consensus state that the header builds on
+    ///
+    /// Its revision number is checked by `validate`.
+    pub fn trusted_height(&self) -> ibc::Height {
+        self.trusted_sync_committee.height
+    }
+
+    /// Returns the length of the proto-encoded header without encoding it
+    ///
+    /// The length is not bounded by the header type itself: it grows with each intermediate consensus update,
+    /// which is limited only by `max_intermediate_consensus_updates` of the client state, and with the account proof,
+    /// whose length depends on the depth of the IBC contract in the state trie and is bounded by `MAX_PROOF_SIZE` on decoding.
+    /// A consensus update with the next sync committee is larger by the committee and its branch.
+    pub fn encoded_len(&self) -> usize {
+        RawHeader::from(self.clone()).encoded_len()
+    }
+
+    /// Stateless validation of the consistency between the updates in the header
+    ///
+    /// This can be called by relayers before submitting the header. The following conditions are checked:
+    /// - the block number of the execution update is non-zero, so that `height` does not panic
+    /// - `finalized_slot <= attested_slot < signature_slot` for each consensus update
+    /// - all merkle branches are non-empty
+    /// - the account update passes `AccountUpdateInfo::validate`
+    /// - the state root branch and the block number branch have the same length since both are in the execution payload tree
+    /// - the block hash branch has the same length too if the block hash is present, and is empty otherwise
+    /// - each intermediate consensus update contains the next sync committee
+    pub fn validate_basic(&self) -> Result<(), Error> {
+        if self.execution_update.block_number == U64(0) {
+            return Err(Error::ZeroBlockNumberError);
+        }
+        for (index, update) in self.intermediate_consensus_updates.iter().enumerate() {
+            if update.next_sync_committee.is_none() {
+                return Err(Error::MissingNextSyncCommitteeInIntermediateUpdate { index });
+            }
+            validate_consensus_update_basic(update)?;
+        }
+        validate_consensus_update_basic(&self.consensus_update)?;
+        let execution_update = &self.execution_update;
+        if execution_update.state_root_branch.is_empty() {
+            return Err(Error::EmptyBranch("state_root_branch"));
+        }
+        if execution_update.block_number_branch.is_empty() {
+            return Err(Error::EmptyBranch("block_number_branch"));
+        }
+        if execution_update.state_root_branch.len() != execution_update.block_number_branch.len() {
+            return Err(Error::InconsistentExecutionBranchLengths {
+                state_root_branch: execution_update.state_root_branch.len(),
+                block_number_branch: execution_update.block_number_branch.len(),
+            });
+        }
+        let expected_block_hash_branch = match execution_update.block_hash {
+            Some(_) => execution_update.state_root_branch.len(),
+            None => 0,
+        };
+        if execution_update.block_hash_branch.len() != expected_block_hash_branch {
+            return Err(Error::InconsistentExecutionBlockHashBranch {
+                has_block_hash: execution_update.block_hash.is_some(),
+                state_root_branch: execution_update.state_root_branch.len(),
+                block_hash_branch: execution_update.block_hash_branch.len(),
+            });
+        }
+        self.account_update.validate()?;
+        Ok(())
+    }
+
     pub fn validate<C: ChainContext>(&self, ctx: &C) -> Result<(), Error> {
-        self.trusted_sync_committee.validate()?;
+        self.validate_with_cache(ctx, &mut NoCache)
+    }
+
+    /// `validate` that skips the validation of the trusted sync committee if it is in `cache`
+    pub fn validate_with_cache<
+        C: ChainContext,
+        SC: SyncCommitteeCache<SYNC_COMMITTEE_SIZE> + ?Sized,
+    >(
+        &self,
+        ctx: &C,
+        cache: &mut SC,
+    ) -> Result<(), Error> {
+        self.trusted_sync_committee.validate_with_cache(cache)?;
         if self.timestamp.into_tm_time().is_none() {
             return Err(Error::ZeroTimestampError);
         }
-        if self.execution_update.block_number == U64(0) {
-            return Err(Error::ZeroBlockNumberError);
-        }
-        let header_timestamp_nanos = self
-            .timestamp
-            .into_tm_time()
-            .unwrap()
-            .unix_timestamp_nanos();
-        let timestamp_secs =
-            compute_timestamp_at_slot(ctx, self.consensus_update.finalized_beacon_header().slot);
-        let timestamp_nanos = i128::from(timestamp_secs.0)
-            .checked_mul(1_000_000_000)
-            .ok_or_else(|| {
-                Error::TimestampOverflowError(
-                    ibc::timestamp::TimestampOverflowError::TimestampOverflow,
-                )
-            })?;
-        if header_timestamp_nanos != timestamp_nanos {
+        let timestamp = compute_timestamp_at_slot(
+            ctx.genesis_time(),
+            ctx.seconds_per_slot(),
+            self.consensus_update.finalized_beacon_header().slot,
+        )?;
+        if self.timestamp != timestamp {
             return Err(Error::UnexpectedTimestamp(
-                timestamp_nanos,
-                header_timestamp_nanos,
+                timestamp.nanoseconds().into(),
+                self.timestamp.nanoseconds().into(),
             ));
         }
         Ok(())
     }
 }
 
+pub(crate) fn validate_consensus_update_basic<const SYNC_COMMITTEE_SIZE: usize>(
+    update: &ConsensusUpdateInfo<SYNC_COMMITTEE_SIZE>,
+) -> Result<(), Error> {
+    let finalized_slot = update.finalized_header.0.slot;
+    let attested_slot = update.attested_header.slot;
+    let signature_slot = update.signature_slot;
+    if finalized_slot > attested_slot {
+        return Err(Error::FinalizedSlotAfterAttestedSlot {
+            finalized_slot,
+            attested_slot,
+            signature_slot,
+        });
+    }
+    if attested_slot >= signature_slot {
+        return Err(Error::AttestedSlotNotBeforeSignatureSlot {
+            finalized_slot,
+            attested_slot,
+            signature_slot,
+        });
+    }
+    if update.finalized_header.1.is_empty() {
+        return Err(Error::EmptyBranch("finalized_header_branch"));
+    }
+    if matches!(&update.next_sync_committee, Some((_, branch)) if branch.is_empty()) {
+        return Err(Error::EmptyBranch("next_sync_committee_branch"));
+    }
+    if update.finalized_execution_branch.is_empty() {
+        return Err(Error::EmptyBranch("finalized_execution_branch"));
+    }
+    Ok(())
+}
+
 impl<const SYNC_COMMITTEE_SIZE: usize> Ics02Header for Header<SYNC_COMMITTEE_SIZE> {
     fn height(&self) -> ibc::Height {
-        ibc::Height::new(0, self.execution_update.block_number.into()).unwrap()
+        Header::height(self)
     }
 
     fn timestamp(&self) -> Timestamp {
@@ -129,33 +246,64 @@ impl<const SYNC_COMMITTEE_SIZE: usize> Protobuf<RawHeader> for Header<SYNC_COMMI
 impl<const SYNC_COMMITTEE_SIZE: usize> TryFrom<RawHeader> for Header<SYNC_COMMITTEE_SIZE> {
     type Error = Error;
     fn try_from(value: RawHeader) -> Result<Self, Self::Error> {
-        let trusted_sync_committee = value
-            .trusted_sync_committee
-            .ok_or(Error::proto_missing("trusted_sync_committee"))?;
-        let consensus_update = value
-            .consensus_update
-            .ok_or(Error::proto_missing("consensus_update"))?;
-        let execution_update = value
-            .execution_update
-            .ok_or(Error::proto_missing("execution_update"))?;
-        let account_update = value
-            .account_update
-            .ok_or(Error::proto_missing("account_update"))?;
+        let trusted_sync_committee = decode_required(
+            "Header",
+            "trusted_sync_committee",
+            value.trusted_sync_committee,
+            TrustedSyncCommittee::try_from,
+        )?;
+        let consensus_update = decode_required(
+            "Header",
+            "consensus_update",
+            value.consensus_update,
+            convert_proto_to_consensus_update,
+        )?;
+        let execution_update = decode_required(
+            "Header",
+            "execution_update",
+            value.execution_update,
+            convert_proto_to_execution_update,
+        )?;
+        let account_update = decode_required(
+            "Header",
+            "account_update",
+            value.account_update,
+            |account_update| {
+                if account_update == Default::default() {
+                    return Err(Error::EmptyAccountUpdate);
+                }
+                AccountUpdateInfo::try_from(account_update)
+            },
+        )?;
         let timestamp = Timestamp::from_nanoseconds(
             value.timestamp.checked_mul(1_000_000_000).ok_or_else(|| {
-                Error::TimestampOverflowError(
-                    ibc::timestamp::TimestampOverflowError::TimestampOverflow,
+                Error::decode_error(
+                    "timestamp",
+                    Error::TimestampOverflowError(
+                        ibc::timestamp::TimestampOverflowError::TimestampOverflow,
+                    ),
                 )
             })?,
-        )?;
+        )
+        .map_err(|e| Error::decode_error("timestamp", e.into()))?;
         if timestamp.into_datetime().is_none() {
-            return Err(Error::ZeroTimestampError);
+            return Err(Error::decode_error("timestamp", Error::ZeroTimestampError));
         }
         Ok(Self {
-            trusted_sync_committee: trusted_sync_committee.try_into()?,
-            consensus_update: convert_proto_to_consensus_update(consensus_update)?,
-            execution_update: convert_proto_to_execution_update(execution_update),
-            account_update: account_update.try_into()?,
+            trusted_sync_committee,
+            intermediate_consensus_updates: value
+                .intermediate_consensus_updates
+                .into_iter()
+                .enumerate()
+                .map(|(i, update)| {
+                    convert_proto_to_consensus_update(update).map_err(|e| {
+                        Error::decode_error(&format!("intermediate_consensus_updates[{}]", i), e)
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+            consensus_update,
+            execution_update,
+            account_update,
             timestamp,
         })
     }
@@ -169,6 +317,11 @@ impl<const SYNC_COMMITTEE_SIZE: usize> From<Header<SYNC_COMMITTEE_SIZE>> for Raw
 
         Self {
             trusted_sync_committee: Some(value.trusted_sync_committee.into()),
+            intermediate_consensus_updates: value
+                .intermediate_consensus_updates
+                .into_iter()
+                .map(convert_consensus_update_to_proto)
+                .collect(),
             consensus_update: Some(convert_consensus_update_to_proto(consensus_update)),
             execution_update: Some(convert_execution_update_to_proto(execution_update)),
             account_update: Some(account_update.into()),
@@ -182,14 +335,32 @@ impl<const SYNC_COMMITTEE_SIZE: usize> Protobuf<IBCAny> for Header<SYNC_COMMITTE
 impl<const SYNC_COMMITTEE_SIZE: usize> TryFrom<IBCAny> for Header<SYNC_COMMITTEE_SIZE> {
     type Error = ClientError;
 
+    /// Decode the header from `Any`
+    ///
+    /// The payload must be the canonical encoding of the header, i.e. it must not contain unknown fields or trailing bytes.
     fn try_from(raw: IBCAny) -> Result<Self, Self::Error> {
-        use core::ops::Deref;
+        fn decode_canonical_header<const SYNC_COMMITTEE_SIZE: usize>(
+            bz: &[u8],
+        ) -> Result<Header<SYNC_COMMITTEE_SIZE>, Error> {
+            let raw_header = RawHeader::decode(bz).map_err(Error::Decode)?;
+            if raw_header.encode_to_vec() != bz {
+                return Err(Error::UnexpectedAnyPayload {
+                    type_url: ETHEREUM_HEADER_TYPE_URL,
+                });
+            }
+            raw_header.try_into()
+        }
 
         match raw.type_url.as_str() {
-            ETHEREUM_HEADER_TYPE_URL => decode_header(raw.value.deref()).map_err(Into::into),
-            _ => Err(ClientError::UnknownHeaderType {
-                header_type: raw.type_url,
-            }),
+            ETHEREUM_HEADER_TYPE_URL => {
+                trace_span!("decode_header", len = raw.value.len() as u64);
+                traced(decode_canonical_header(&raw.value)).map_err(Into::into)
+            }
+            _ => Err(Error::UnexpectedTypeUrl {
+                expected: &[ETHEREUM_HEADER_TYPE_URL],
+                got: raw.type_url,
+            }
+            .into()),
         }
     }
 }
@@ -208,13 +379,22 @@ impl<const SYNC_COMMITTEE_SIZE: usize> From<Header<SYNC_COMMITTEE_SIZE>> for IBC
 mod tests {
     use super::*;
     use crate::client_state::ETHEREUM_CLIENT_REVISION_NUMBER;
+    use crate::types::tests::{
+        arb_account_update, arb_consensus_update, arb_execution_update, arb_trusted_sync_committee,
+        pubkey_pool, signature_pool,
+    };
+    use crate::{MAINNET_SYNC_COMMITTEE_SIZE, MINIMAL_SYNC_COMMITTEE_SIZE};
+    use ethereum_consensus::bls::{PublicKey, Signature};
+    use ethereum_consensus::compute::compute_timestamp_at_slot;
     use ethereum_consensus::context::ChainContext;
     use ethereum_consensus::{config, types::U64};
+    use ethereum_ibc_proto::ibc::lightclients::ethereum::v1::AccountUpdate as RawAccountUpdate;
     use ethereum_light_client_verifier::{
         consensus::test_utils::{gen_light_client_update_with_params, MockSyncCommitteeManager},
         context::{Fraction, LightClientContext},
         updates::ConsensusUpdateInfo as EthConsensusUpdateInfo,
     };
+    use proptest::prelude::*;
     use std::time::SystemTime;
 
     #[test]
@@ -239,6 +419,10 @@ mod tests {
         let base_finalized_epoch = base_attested_slot / ctx.slots_per_epoch();
         let dummy_execution_state_root = [1u8; 32].into();
         let dummy_execution_block_number = 1;
+        let account_update = AccountUpdateInfo {
+            account_proof: vec![vec![0xc2, 0x80, 0x80]],
+            account_storage_root: [1u8; 32].into(),
+        };
 
         for b in [false, true] {
             let (update, _) = gen_light_client_update_with_params::<32, _>(
@@ -256,16 +440,17 @@ mod tests {
             let update = to_consensus_update_info(update);
             let header = Header {
                 trusted_sync_committee: TrustedSyncCommittee {
-                    height: ibc::Height::new(ETHEREUM_CLIENT_REVISION_NUMBER, 1).unwrap(),
+                    height: height_from_block_number(U64(1)).unwrap(),
                     sync_committee: current_sync_committee.to_committee().clone(),
                     is_next: true,
                 },
+                intermediate_consensus_updates: vec![],
                 consensus_update: update.clone(),
                 execution_update: ExecutionUpdateInfo {
                     block_number: U64(2),
                     ..Default::default()
                 },
-                account_update: AccountUpdateInfo::default(),
+                account_update: account_update.clone(),
                 timestamp: Timestamp::from_nanoseconds(
                     compute_timestamp_at_slot(&ctx, update.finalized_beacon_header().slot).0
                         * 1_000_000_000,
@@ -274,22 +459,220 @@ mod tests {
             };
             let res = header.validate(&ctx);
             assert!(res.is_ok(), "header validation failed: {:?}", res);
+            assert_eq!(header.height(), height_from_block_number(U64(2)).unwrap());
+            assert_eq!(
+                header.trusted_height(),
+                height_from_block_number(U64(1)).unwrap()
+            );
+            assert_eq!(Ics02Header::height(&header), header.height());
+
+            let mut h = header.clone();
+            h.trusted_sync_committee.height = ibc::Height::new(1, 1).unwrap();
+            let res = h.validate(&ctx);
+            assert!(
+                matches!(
+                    res,
+                    Err(Error::UnexpectedHeightRevisionNumber {
+                        expected: ETHEREUM_CLIENT_REVISION_NUMBER,
+                        got: 1
+                    })
+                ),
+                "{:?}",
+                res
+            );
             let any = IBCAny::from(header.clone());
-            let decoded = Header::<32>::try_from(any).unwrap();
+            let decoded = Header::<32>::try_from(any.clone()).unwrap();
             assert_eq!(header, decoded);
+            // an unknown field is skipped by prost, but the payload is not canonical
+            let mut non_canonical = any.clone();
+            non_canonical.value.extend_from_slice(&[0x78, 0x01]);
+            assert!(RawHeader::decode(non_canonical.value.as_slice()).is_ok());
+            let res = Header::<32>::try_from(non_canonical);
+            assert!(
+                res.as_ref().unwrap_err().to_string().contains(
+                    &Error::UnexpectedAnyPayload {
+                        type_url: ETHEREUM_HEADER_TYPE_URL
+                    }
+                    .to_string()
+                ),
+                "{:?}",
+                res
+            );
+            // the client message is dispatched by the type URL
+            assert!(matches!(
+                ClientMessage::<32>::try_from(any.clone()),
+                Ok(ClientMessage::Header(h)) if h == header
+            ));
+            let res = ClientMessage::<32>::try_from(IBCAny {
+                type_url: ETHEREUM_FINALIZED_HEADER_MISBEHAVIOUR_TYPE_URL.to_string(),
+                value: any.value,
+            });
+            assert!(res.is_err());
+            let res = ClientMessage::<32>::try_from(IBCAny {
+                type_url: "/ibc.lightclients.tendermint.v1.Header".to_string(),
+                value: vec![],
+            });
+            assert!(
+                matches!(
+                    res,
+                    Err(Error::UnexpectedClientMessageType { ref url })
+                        if url == "/ibc.lightclients.tendermint.v1.Header"
+                ),
+                "{:?}",
+                res.err()
+            );
+
+            // the account update is required, and a default one is rejected
+            let raw = RawHeader::from(header.clone());
+            let res = Header::<32>::try_from(RawHeader {
+                account_update: None,
+                ..raw.clone()
+            });
+            assert_eq!(
+                res.unwrap_err().to_string(),
+                "proto missing field error: `Header.account_update`"
+            );
+            let res = Header::<32>::try_from(RawHeader {
+                account_update: Some(RawAccountUpdate::default()),
+                ..raw.clone()
+            });
+            assert_eq!(
+                res.unwrap_err().to_string(),
+                "failed to decode `account_update`: the account update is empty"
+            );
+            let res = Header::<32>::try_from(RawHeader {
+                account_update: Some(RawAccountUpdate {
+                    account_storage_root: vec![1u8; 32],
+                    ..Default::default()
+                }),
+                ..raw.clone()
+            });
+            assert_eq!(
+                res.unwrap_err().to_string(),
+                "failed to decode `account_update.account_proof`: invalid proof format error: proof must be rlp list"
+            );
+            let res = Header::<32>::try_from(RawHeader {
+                account_update: Some(RawAccountUpdate {
+                    account_proof: vec![0xc3, 0xc2, 0x80, 0x80],
+                    account_storage_root: vec![0u8; 32],
+                }),
+                ..raw.clone()
+            });
+            assert_eq!(
+                res.unwrap_err().to_string(),
+                "failed to decode `account_update.account_storage_root`: the account storage root is zero"
+            );
+            let res = Header::<32>::try_from(RawHeader {
+                account_update: Some(RawAccountUpdate {
+                    account_proof: vec![0xc0],
+                    account_storage_root: vec![0u8; 32],
+                }),
+                ..raw.clone()
+            });
+            assert_eq!(
+                res.unwrap_err().to_string(),
+                "failed to decode `account_update.account_proof`: empty account proof"
+            );
+
+            // the decode errors contain the dotted path of the offending field, and missing field errors contain the enclosing message
+            let mut r = raw.clone();
+            r.trusted_sync_committee.as_mut().unwrap().trusted_height = None;
+            assert_eq!(
+                Header::<32>::try_from(r).unwrap_err().to_string(),
+                "failed to decode `trusted_sync_committee`: proto missing field error: `TrustedSyncCommittee.trusted_height`"
+            );
+            let mut r = raw.clone();
+            r.trusted_sync_committee
+                .as_mut()
+                .unwrap()
+                .sync_committee
+                .as_mut()
+                .unwrap()
+                .aggregate_pubkey
+                .pop();
+            assert!(Header::<32>::try_from(r)
+                .unwrap_err()
+                .to_string()
+                .starts_with(
+                    "failed to decode `trusted_sync_committee.sync_committee.aggregate_pubkey`: "
+                ));
+            let mut r = raw.clone();
+            r.consensus_update.as_mut().unwrap().finalized_header = None;
+            assert_eq!(
+                Header::<32>::try_from(r).unwrap_err().to_string(),
+                "failed to decode `consensus_update`: proto missing field error: `ConsensusUpdate.finalized_header`"
+            );
+            let mut r = raw.clone();
+            r.execution_update = None;
+            assert_eq!(
+                Header::<32>::try_from(r).unwrap_err().to_string(),
+                "proto missing field error: `Header.execution_update`"
+            );
+            let mut r = raw.clone();
+            r.consensus_update
+                .as_mut()
+                .unwrap()
+                .attested_header
+                .as_mut()
+                .unwrap()
+                .state_root
+                .pop();
+            assert_eq!(
+                Header::<32>::try_from(r).unwrap_err().to_string(),
+                "failed to decode `consensus_update.attested_header.state_root`: invalid bytes length: expected=32 actual=31"
+            );
+            let mut r = raw.clone();
+            r.consensus_update
+                .as_mut()
+                .unwrap()
+                .sync_aggregate
+                .as_mut()
+                .unwrap()
+                .sync_committee_signature
+                .clear();
+            assert!(Header::<32>::try_from(r)
+                .unwrap_err()
+                .to_string()
+                .starts_with(
+                    "failed to decode `consensus_update.sync_aggregate.sync_committee_signature`: "
+                ));
+            let mut r = raw.clone();
+            r.execution_update.as_mut().unwrap().state_root_branch = vec![vec![0u8; 31]];
+            assert_eq!(
+                Header::<32>::try_from(r).unwrap_err().to_string(),
+                "failed to decode `execution_update.state_root_branch[0]`: invalid bytes length: expected=32 actual=31"
+            );
+            let mut r = raw.clone();
+            let mut intermediate_update = r.consensus_update.clone().unwrap();
+            intermediate_update.finalized_execution_root = vec![];
+            r.intermediate_consensus_updates =
+                vec![r.consensus_update.clone().unwrap(), intermediate_update];
+            assert_eq!(
+                Header::<32>::try_from(r).unwrap_err().to_string(),
+                "failed to decode `intermediate_consensus_updates[1].finalized_execution_root`: invalid bytes length: expected=32 actual=0"
+            );
+            let r = RawHeader {
+                timestamp: 0,
+                ..raw.clone()
+            };
+            assert_eq!(
+                Header::<32>::try_from(r).unwrap_err().to_string(),
+                "failed to decode `timestamp`: zero timestamp error"
+            );
 
             let header = Header {
                 trusted_sync_committee: TrustedSyncCommittee {
-                    height: ibc::Height::new(ETHEREUM_CLIENT_REVISION_NUMBER, 1).unwrap(),
+                    height: height_from_block_number(U64(1)).unwrap(),
                     sync_committee: current_sync_committee.to_committee().clone(),
                     is_next: true,
                 },
+                intermediate_consensus_updates: vec![],
                 consensus_update: update,
                 execution_update: ExecutionUpdateInfo {
                     block_number: U64(2),
                     ..Default::default()
                 },
-                account_update: AccountUpdateInfo::default(),
+                account_update: account_update.clone(),
                 timestamp: Timestamp::from_nanoseconds(0).unwrap(),
             };
             let any = IBCAny::from(header.clone());
@@ -312,13 +695,14 @@ mod tests {
         let update = to_consensus_update_info(update);
         let header = Header {
             trusted_sync_committee: TrustedSyncCommittee {
-                height: ibc::Height::new(ETHEREUM_CLIENT_REVISION_NUMBER, 1).unwrap(),
+                height: height_from_block_number(U64(1)).unwrap(),
                 sync_committee: current_sync_committee.to_committee().clone(),
                 is_next: true,
             },
+            intermediate_consensus_updates: vec![],
             consensus_update: update.clone(),
             execution_update: ExecutionUpdateInfo::default(),
-            account_update: AccountUpdateInfo::default(),
+            account_update,
             timestamp: Timestamp::from_nanoseconds(
                 compute_timestamp_at_slot(&ctx, update.finalized_beacon_header().slot).0
                     * 1_000_000_000
@@ -333,6 +717,388 @@ mod tests {
         );
     }
 
+    /// The timestamp is in seconds as the proto timestamp is
+    pub(crate) fn arb_header<const SYNC_COMMITTEE_SIZE: usize>(
+        pubkeys: Vec<PublicKey>,
+        signatures: Vec<Signature>,
+    ) -> impl Strategy<Value = Header<SYNC_COMMITTEE_SIZE>> {
+        (
+            arb_trusted_sync_committee(pubkeys.clone()),
+            prop::collection::vec(
+                arb_consensus_update(pubkeys.clone(), signatures.clone()),
+                0..3,
+            ),
+            arb_consensus_update(pubkeys, signatures),
+            arb_execution_update(),
+            arb_account_update(),
+            1..=u32::MAX as u64,
+        )
+            .prop_map(
+                |(
+                    trusted_sync_committee,
+                    intermediate_consensus_updates,
+                    consensus_update,
+                    execution_update,
+                    account_update,
+                    seconds,
+                )| Header {
+                    trusted_sync_committee,
+                    intermediate_consensus_updates,
+                    consensus_update,
+                    execution_update,
+                    account_update,
+                    timestamp: Timestamp::from_nanoseconds(seconds * 1_000_000_000).unwrap(),
+                },
+            )
+    }
+
+    fn header_round_trip<const SYNC_COMMITTEE_SIZE: usize>(
+        header: Header<SYNC_COMMITTEE_SIZE>,
+    ) -> Result<(), TestCaseError> {
+        let bz = RawHeader::from(header.clone()).encode_to_vec();
+        prop_assert_eq!(header.encoded_len(), bz.len());
+        prop_assert_eq!(
+            decode_header::<SYNC_COMMITTEE_SIZE, _>(bz.as_slice()).unwrap(),
+            header.clone()
+        );
+        let any = IBCAny::from(header.clone());
+        prop_assert_eq!(
+            Header::<SYNC_COMMITTEE_SIZE>::try_from(any).unwrap(),
+            header
+        );
+        Ok(())
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn prop_minimal_header_round_trip(
+            header in arb_header::<MINIMAL_SYNC_COMMITTEE_SIZE>(pubkey_pool(), signature_pool())
+        ) {
+            header_round_trip(header)?;
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(16))]
+
+        #[test]
+        fn prop_mainnet_header_round_trip(
+            header in arb_header::<MAINNET_SYNC_COMMITTEE_SIZE>(pubkey_pool(), signature_pool())
+        ) {
+            header_round_trip(header)?;
+        }
+    }
+
+    #[test]
+    fn test_header_encoded_len() {
+        let (_, _, mut header, _) = crate::verify::tests::header_scenario();
+        assert_eq!(
+            header.encoded_len(),
+            RawHeader::from(header.clone()).encode_to_vec().len()
+        );
+
+        header.consensus_update.next_sync_committee = None;
+        let without_next_sync_committee = header.encoded_len();
+        header.consensus_update.next_sync_committee = Some((
+            header.trusted_sync_committee.sync_committee.clone(),
+            vec![Default::default(); 5],
+        ));
+        let with_next_sync_committee = header.encoded_len();
+        // the tag and the length prefix (3), 32 public keys and the aggregate public key of 50 bytes each, and 5 branch nodes of 34 bytes
+        assert_eq!(
+            with_next_sync_committee - without_next_sync_committee,
+            3 + 33 * 50 + 5 * 34
+        );
+
+        // each intermediate consensus update adds its length with the tag and the length prefix
+        let update_len =
+            convert_consensus_update_to_proto(header.consensus_update.clone()).encoded_len();
+        for n in 1..=3 {
+            header
+                .intermediate_consensus_updates
+                .push(header.consensus_update.clone());
+            assert_eq!(
+                header.encoded_len(),
+                with_next_sync_committee
+                    + n * (1 + prost::length_delimiter_len(update_len) + update_len)
+            );
+        }
+        assert_eq!(
+            header.encoded_len(),
+            RawHeader::from(header).encode_to_vec().len()
+        );
+    }
+
+    #[test]
+    fn test_header_validate_basic_slot_order() {
+        let (_, _, header, _) = crate::verify::tests::header_scenario();
+        assert!(header.validate_basic().is_ok());
+        let slot = header.consensus_update.attested_header.slot;
+        let validate = |finalized_slot: U64, attested_slot: U64, signature_slot: U64| {
+            let mut h = header.clone();
+            h.consensus_update.finalized_header.0.slot = finalized_slot;
+            h.consensus_update.attested_header.slot = attested_slot;
+            h.consensus_update.signature_slot = signature_slot;
+            h.validate_basic()
+        };
+
+        assert!(validate(slot - 1, slot, slot + 1).is_ok());
+        // the finalized header may be the attested header itself
+        assert!(validate(slot, slot, slot + 1).is_ok());
+        let res = validate(slot - 1, slot, slot);
+        assert!(
+            matches!(res, Err(Error::AttestedSlotNotBeforeSignatureSlot { finalized_slot, attested_slot, signature_slot })
+                if finalized_slot == slot - 1 && attested_slot == slot && signature_slot == slot),
+            "{:?}",
+            res
+        );
+        let res = validate(slot, slot, slot);
+        assert!(
+            matches!(res, Err(Error::AttestedSlotNotBeforeSignatureSlot { finalized_slot, attested_slot, signature_slot })
+                if finalized_slot == slot && attested_slot == slot && signature_slot == slot),
+            "{:?}",
+            res
+        );
+        let res = validate(slot + 1, slot, slot + 2);
+        assert!(
+            matches!(res, Err(Error::FinalizedSlotAfterAttestedSlot { finalized_slot, attested_slot, signature_slot })
+                if finalized_slot == slot + 1 && attested_slot == slot && signature_slot == slot + 2),
+            "{:?}",
+            res
+        );
+        // a fully reversed update is rejected by the first violated inequality
+        let res = validate(slot + 1, slot, slot - 1);
+        assert!(
+            matches!(res, Err(Error::FinalizedSlotAfterAttestedSlot { .. })),
+            "{:?}",
+            res
+        );
+    }
+
+    #[test]
+    fn test_header_validate_basic() {
+        let scm = MockSyncCommitteeManager::<32>::new(1, 4);
+        let ctx = LightClientContext::new_with_config(
+            config::minimal::get_config(),
+            Default::default(),
+            Default::default(),
+            Fraction::new(2, 3).unwrap(),
+            SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap()
+                .as_secs()
+                .into(),
+        );
+        let period_1 = U64(1) * ctx.slots_per_epoch() * ctx.epochs_per_sync_committee_period();
+        let current_sync_committee = scm.get_committee(1);
+        let signature_slot = period_1 + 11;
+        let attested_slot = signature_slot - 1;
+        let (update, _) = gen_light_client_update_with_params::<32, _>(
+            &ctx,
+            signature_slot,
+            attested_slot,
+            attested_slot / ctx.slots_per_epoch(),
+            [1u8; 32].into(),
+            1.into(),
+            current_sync_committee,
+            scm.get_committee(2),
+            true,
+            32,
+        );
+        let update = to_consensus_update_info(update);
+        let header = Header {
+            trusted_sync_committee: TrustedSyncCommittee {
+                height: height_from_block_number(U64(1)).unwrap(),
+                sync_committee: current_sync_committee.to_committee().clone(),
+                is_next: true,
+            },
+            intermediate_consensus_updates: vec![],
+            consensus_update: update.clone(),
+            execution_update: ExecutionUpdateInfo {
+                state_root: [1u8; 32].into(),
+                state_root_branch: vec![Default::default(); 4],
+                block_number: U64(2),
+                block_number_branch: vec![Default::default(); 4],
+                block_hash: None,
+                block_hash_branch: vec![],
+            },
+            account_update: AccountUpdateInfo {
+                account_proof: vec![vec![0x80]],
+                account_storage_root: [1u8; 32].into(),
+            },
+            timestamp: Timestamp::from_nanoseconds(
+                compute_timestamp_at_slot(&ctx, update.finalized_beacon_header().slot).0
+                    * 1_000_000_000,
+            )
+            .unwrap(),
+        };
+        let res = header.validate_basic();
+        assert!(res.is_ok(), "{:?}", res);
+
+        // the genesis block has no height
+        let mut h = header.clone();
+        h.execution_update.block_number = U64(0);
+        let res = h.validate_basic();
+        assert!(matches!(res, Err(Error::ZeroBlockNumberError)), "{:?}", res);
+
+        let mut h = header.clone();
+        h.consensus_update.finalized_header.0.slot = attested_slot + 1;
+        let res = h.validate_basic();
+        assert!(
+            matches!(res, Err(Error::FinalizedSlotAfterAttestedSlot { .. })),
+            "{:?}",
+            res
+        );
+
+        let mut h = header.clone();
+        h.consensus_update.signature_slot = attested_slot;
+        let res = h.validate_basic();
+        assert!(
+            matches!(res, Err(Error::AttestedSlotNotBeforeSignatureSlot { .. })),
+            "{:?}",
+            res
+        );
+
+        let mut h = header.clone();
+        h.consensus_update.finalized_header.1.clear();
+        let res = h.validate_basic();
+        assert!(
+            matches!(res, Err(Error::EmptyBranch("finalized_header_branch"))),
+            "{:?}",
+            res
+        );
+
+        let mut h = header.clone();
+        h.consensus_update
+            .next_sync_committee
+            .as_mut()
+            .unwrap()
+            .1
+            .clear();
+        let res = h.validate_basic();
+        assert!(
+            matches!(res, Err(Error::EmptyBranch("next_sync_committee_branch"))),
+            "{:?}",
+            res
+        );
+
+        let mut h = header.clone();
+        h.consensus_update.finalized_execution_branch.clear();
+        let res = h.validate_basic();
+        assert!(
+            matches!(res, Err(Error::EmptyBranch("finalized_execution_branch"))),
+            "{:?}",
+            res
+        );
+
+        let mut h = header.clone();
+        h.execution_update.state_root_branch.clear();
+        let res = h.validate_basic();
+        assert!(
+            matches!(res, Err(Error::EmptyBranch("state_root_branch"))),
+            "{:?}",
+            res
+        );
+
+        let mut h = header.clone();
+        h.execution_update.block_number_branch.clear();
+        let res = h.validate_basic();
+        assert!(
+            matches!(res, Err(Error::EmptyBranch("block_number_branch"))),
+            "{:?}",
+            res
+        );
+
+        let mut h = header.clone();
+        h.execution_update.block_number_branch.pop();
+        let res = h.validate_basic();
+        assert!(
+            matches!(
+                res,
+                Err(Error::InconsistentExecutionBranchLengths {
+                    state_root_branch: 4,
+                    block_number_branch: 3
+                })
+            ),
+            "{:?}",
+            res
+        );
+
+        // the block hash is optional, but its branch must be in the same tree as the state root
+        let mut h = header.clone();
+        h.execution_update.block_hash = Some([2u8; 32].into());
+        h.execution_update.block_hash_branch = vec![Default::default(); 4];
+        let res = h.validate_basic();
+        assert!(res.is_ok(), "{:?}", res);
+        h.execution_update.block_hash_branch.pop();
+        let res = h.validate_basic();
+        assert!(
+            matches!(
+                res,
+                Err(Error::InconsistentExecutionBlockHashBranch {
+                    has_block_hash: true,
+                    state_root_branch: 4,
+                    block_hash_branch: 3
+                })
+            ),
+            "{:?}",
+            res
+        );
+        h.execution_update.block_hash = None;
+        let res = h.validate_basic();
+        assert!(
+            matches!(
+                res,
+                Err(Error::InconsistentExecutionBlockHashBranch {
+                    has_block_hash: false,
+                    state_root_branch: 4,
+                    block_hash_branch: 3
+                })
+            ),
+            "{:?}",
+            res
+        );
+
+        let mut h = header.clone();
+        let mut intermediate_update = update;
+        intermediate_update.next_sync_committee = None;
+        h.intermediate_consensus_updates = vec![intermediate_update];
+        let res = h.validate_basic();
+        assert!(
+            matches!(
+                res,
+                Err(Error::MissingNextSyncCommitteeInIntermediateUpdate { index: 0 })
+            ),
+            "{:?}",
+            res
+        );
+
+        let mut h = header.clone();
+        h.account_update.account_proof.clear();
+        let res = h.validate_basic();
+        assert!(matches!(res, Err(Error::EmptyAccountProof)), "{:?}", res);
+
+        let mut h = header.clone();
+        h.account_update.account_proof.push(vec![]);
+        let res = h.validate_basic();
+        assert!(
+            matches!(res, Err(Error::EmptyAccountProofNode { index: 1 })),
+            "{:?}",
+            res
+        );
+
+        let mut h = header;
+        h.account_update.account_storage_root = Default::default();
+        let res = h.validate_basic();
+        assert!(
+            matches!(res, Err(Error::ZeroAccountStorageRoot)),
+            "{:?}",
+            res
+        );
+    }
+
     fn to_consensus_update_info<const SYNC_COMMITTEE_SIZE: usize>(
         consensus_update: EthConsensusUpdateInfo<SYNC_COMMITTEE_SIZE>,
     ) -> ConsensusUpdateInfo<SYNC_COMMITTEE_SIZE> {
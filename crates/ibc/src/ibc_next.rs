@@ -0,0 +1,1036 @@
+//! The client traits of ibc-rs 0.48 implemented on top of the verification of this crate
+//!
+//! ibc-rs 0.48 splits the client state trait into `ClientStateCommon`, `ClientStateValidation` and `ClientStateExecution`,
+//! which read and write the store of the host through the contexts given to each method.
+//! This module implements them for `ClientState` with the functions of `verify`, so the states, the protos and the verification are the same as the ibc-rs 0.29 implementation.
+//! Only the consensus state is wrapped in `ConsensusState`, because the `root` of the trait returns the commitment root of ibc-rs 0.48.
+//! The host implements `CommonContext` in addition to `ClientValidationContext` and `ClientExecutionContext`.
+use crate::client_state::ClientState;
+use crate::commitment;
+use crate::consensus_state::ConsensusState as EthConsensusState;
+use crate::errors::Error;
+use crate::header::{ClientMessage, Header};
+use crate::internal_prelude::*;
+use crate::misbehaviour::Misbehaviour;
+use crate::trace::trace_span;
+use crate::update::UpdateRoute;
+use crate::verify;
+use crate::ETHEREUM_CLIENT_TYPE;
+use core::str::FromStr;
+use ibc_next::core::client::context::client_state::{
+    ClientStateCommon, ClientStateExecution, ClientStateValidation,
+};
+use ibc_next::core::client::context::consensus_state::ConsensusState as ConsensusStateTrait;
+use ibc_next::core::client::context::{ClientExecutionContext, ClientValidationContext};
+use ibc_next::core::client::types::error::ClientError;
+use ibc_next::core::client::types::{Height, Status, UpdateKind};
+use ibc_next::core::commitment_types::commitment::{
+    CommitmentPrefix, CommitmentProofBytes, CommitmentRoot,
+};
+use ibc_next::core::handler::types::error::ContextError;
+use ibc_next::core::host::types::identifiers::{ClientId, ClientType};
+use ibc_next::core::host::types::path::{ClientConsensusStatePath, ClientStatePath, Path};
+use ibc_next::primitives::proto::Any;
+use ibc_next::primitives::Timestamp;
+use prost::Message;
+
+/// The store of the host read by the client, in addition to `ClientValidationContext` and `ClientExecutionContext`
+pub trait CommonContext {
+    type ConversionError: ToString;
+    type AnyConsensusState: TryInto<ConsensusState, Error = Self::ConversionError>;
+
+    /// Returns the consensus state at the path, or `ClientError::ConsensusStateNotFound` if it is not stored
+    fn consensus_state(
+        &self,
+        client_cons_state_path: &ClientConsensusStatePath,
+    ) -> Result<Self::AnyConsensusState, ContextError>;
+
+    /// Returns the heights of the consensus states stored for the client, which are considered for pruning
+    fn consensus_state_heights(&self, client_id: &ClientId) -> Result<Vec<Height>, ContextError>;
+
+    fn host_timestamp(&self) -> Result<Timestamp, ContextError>;
+
+    fn host_height(&self) -> Result<Height, ContextError>;
+}
+
+/// The consensus state of this crate for the `ConsensusState` trait of ibc-rs 0.48
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConsensusState {
+    inner: EthConsensusState,
+    root: CommitmentRoot,
+}
+
+impl ConsensusState {
+    pub fn inner(&self) -> &EthConsensusState {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> EthConsensusState {
+        self.inner
+    }
+}
+
+impl From<EthConsensusState> for ConsensusState {
+    fn from(inner: EthConsensusState) -> Self {
+        Self {
+            root: CommitmentRoot::from_bytes(inner.storage_root.as_bytes()),
+            inner,
+        }
+    }
+}
+
+impl From<ConsensusState> for EthConsensusState {
+    fn from(value: ConsensusState) -> Self {
+        value.inner
+    }
+}
+
+impl TryFrom<Any> for ConsensusState {
+    type Error = ClientError;
+
+    fn try_from(any: Any) -> Result<Self, Self::Error> {
+        EthConsensusState::try_from(from_any(any))
+            .map(Self::from)
+            .map_err(client_error)
+    }
+}
+
+impl From<ConsensusState> for Any {
+    fn from(value: ConsensusState) -> Self {
+        to_any(value.inner.into())
+    }
+}
+
+impl ConsensusStateTrait for ConsensusState {
+    fn root(&self) -> &CommitmentRoot {
+        &self.root
+    }
+
+    fn timestamp(&self) -> Timestamp {
+        to_timestamp(self.inner.timestamp)
+    }
+
+    fn encode_vec(self) -> Vec<u8> {
+        ibc_proto::google::protobuf::Any::from(self.inner).encode_to_vec()
+    }
+}
+
+impl<const SYNC_COMMITTEE_SIZE: usize> ClientStateCommon for ClientState<SYNC_COMMITTEE_SIZE> {
+    fn verify_consensus_state(&self, consensus_state: Any) -> Result<(), ClientError> {
+        let consensus_state = ConsensusState::try_from(consensus_state)?;
+        consensus_state.inner.validate()?;
+        Ok(())
+    }
+
+    fn client_type(&self) -> ClientType {
+        ClientType::from_str(ETHEREUM_CLIENT_TYPE).expect("the client type must be valid")
+    }
+
+    fn latest_height(&self) -> Height {
+        to_height(latest_height(self))
+    }
+
+    fn validate_proof_height(&self, proof_height: Height) -> Result<(), ClientError> {
+        self.verify_height(from_height(proof_height)?)?;
+        Ok(())
+    }
+
+    fn verify_upgrade_client(
+        &self,
+        _upgraded_client_state: Any,
+        _upgraded_consensus_state: Any,
+        _proof_upgrade_client: CommitmentProofBytes,
+        _proof_upgrade_consensus_state: CommitmentProofBytes,
+        _root: &CommitmentRoot,
+    ) -> Result<(), ClientError> {
+        Err(upgrade_not_supported())
+    }
+
+    fn verify_membership(
+        &self,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        path: Path,
+        value: Vec<u8>,
+    ) -> Result<(), ClientError> {
+        self.commitment_prefix.verify(&from_prefix(prefix)?)?;
+        commitment::verify_membership(
+            &from_root(root),
+            &self.ibc_commitments_slot,
+            self.commitment_value_codec,
+            &from_proof(proof)?,
+            from_path(path)?,
+            &value,
+        )?;
+        Ok(())
+    }
+
+    fn verify_non_membership(
+        &self,
+        prefix: &CommitmentPrefix,
+        proof: &CommitmentProofBytes,
+        root: &CommitmentRoot,
+        path: Path,
+    ) -> Result<(), ClientError> {
+        self.commitment_prefix.verify(&from_prefix(prefix)?)?;
+        commitment::verify_non_membership(
+            &from_root(root),
+            &self.ibc_commitments_slot,
+            &from_proof(proof)?,
+            from_path(path)?,
+        )?;
+        Ok(())
+    }
+}
+
+impl<const SYNC_COMMITTEE_SIZE: usize, V> ClientStateValidation<V>
+    for ClientState<SYNC_COMMITTEE_SIZE>
+where
+    V: ClientValidationContext + CommonContext,
+{
+    fn verify_client_message(
+        &self,
+        ctx: &V,
+        client_id: &ClientId,
+        client_message: Any,
+        _update_kind: &UpdateKind,
+    ) -> Result<(), ClientError> {
+        match decode_client_message(client_message)? {
+            ClientMessage::Header(header) => {
+                verify_header_in(self, ctx, client_id, header)?;
+            }
+            ClientMessage::Misbehaviour(misbehaviour) => {
+                verify_misbehaviour_in(self, ctx, client_id, misbehaviour)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the consensus state of the header conflicts with the one stored at its height, see `ClientState::check_for_misbehaviour`,
+    /// or if the header finalizes the slot of the trusted consensus state with different data, see `ClientState::route_update`
+    ///
+    /// The header is verified again to compute its consensus state, because ibc-rs does not pass the result of `verify_client_message`.
+    /// A misbehaviour verified by `verify_client_message` is always a misbehaviour.
+    fn check_for_misbehaviour(
+        &self,
+        ctx: &V,
+        client_id: &ClientId,
+        client_message: Any,
+        _update_kind: &UpdateKind,
+    ) -> Result<bool, ClientError> {
+        match decode_client_message(client_message)? {
+            ClientMessage::Header(header) => {
+                let verified = verify_header_in(self, ctx, client_id, header)?;
+                let route = self.route_update(
+                    verified.trusted_height,
+                    &verified.trusted_consensus_state,
+                    verified.height,
+                    &verified.consensus_state,
+                );
+                Ok(route == UpdateRoute::Misbehaviour
+                    || self.check_for_misbehaviour(
+                        &verified.consensus_state,
+                        verified.stored_consensus_state.as_ref(),
+                    ))
+            }
+            ClientMessage::Misbehaviour(_) => Ok(true),
+        }
+    }
+
+    fn status(&self, ctx: &V, client_id: &ClientId) -> Result<Status, ClientError> {
+        if self.frozen_height.is_some() {
+            return Ok(Status::Frozen);
+        }
+        let latest_consensus_state =
+            match maybe_consensus_state(ctx, client_id, latest_height(self))? {
+                Some(cs) => cs,
+                None => return Ok(Status::Expired),
+            };
+        let host_timestamp = host_timestamp(ctx)?;
+        if self
+            .diagnostics(&latest_consensus_state, host_timestamp)
+            .trusting_period_remaining
+            .is_none()
+        {
+            Ok(Status::Expired)
+        } else {
+            Ok(Status::Active)
+        }
+    }
+}
+
+impl<const SYNC_COMMITTEE_SIZE: usize, E> ClientStateExecution<E>
+    for ClientState<SYNC_COMMITTEE_SIZE>
+where
+    E: ClientExecutionContext + CommonContext,
+    <E as ClientExecutionContext>::AnyClientState: From<ClientState<SYNC_COMMITTEE_SIZE>>,
+    <E as ClientExecutionContext>::AnyConsensusState: From<ConsensusState>,
+{
+    fn initialise(
+        &self,
+        ctx: &mut E,
+        client_id: &ClientId,
+        consensus_state: Any,
+    ) -> Result<(), ClientError> {
+        let consensus_state = from_any(consensus_state);
+        ibc::core::ics02_client::client_state::ClientState::initialise(
+            self,
+            consensus_state.clone(),
+        )
+        .map_err(client_error)?;
+        let consensus_state = EthConsensusState::try_from(consensus_state).map_err(client_error)?;
+        let height = ClientStateCommon::latest_height(self);
+        let host_timestamp = ctx.host_timestamp().map_err(context_error)?;
+        let host_height = ctx.host_height().map_err(context_error)?;
+
+        ctx.store_client_state(ClientStatePath::new(client_id), self.clone().into())
+            .map_err(context_error)?;
+        ctx.store_consensus_state(
+            consensus_state_path(client_id, height),
+            ConsensusState::from(consensus_state).into(),
+        )
+        .map_err(context_error)?;
+        ctx.store_update_time(client_id.clone(), height, host_timestamp)
+            .map_err(context_error)?;
+        ctx.store_update_height(client_id.clone(), height, host_height)
+            .map_err(context_error)
+    }
+
+    /// Writes the consensus state and the client state of the header and prunes the expired consensus states, see `ClientState::update_state`
+    ///
+    /// The consensus state already stored at the height of the header is never overwritten.
+    fn update_state(
+        &self,
+        ctx: &mut E,
+        client_id: &ClientId,
+        header: Any,
+    ) -> Result<Vec<Height>, ClientError> {
+        let header =
+            Header::<SYNC_COMMITTEE_SIZE>::try_from(from_any(header)).map_err(client_error)?;
+        let verified = verify_header_in(self, ctx, client_id, header)?;
+        let height = to_height(verified.height);
+        if verified.stored_consensus_state.is_some() {
+            return Ok(core::iter::once(height).collect());
+        }
+
+        trace_span!("store_state", height = height.revision_height());
+        let mut consensus_states = Vec::new();
+        for h in ctx
+            .consensus_state_heights(client_id)
+            .map_err(context_error)?
+        {
+            let h = from_height(h)?;
+            if let Some(cs) = maybe_consensus_state(ctx, client_id, h)? {
+                consensus_states.push((h, cs));
+            }
+        }
+        let prunable_heights = verified.client_state.expired_heights(
+            &consensus_states,
+            verified.timestamp,
+            verified.trusted_height,
+        );
+        for h in prunable_heights {
+            let h = to_height(h);
+            ctx.delete_consensus_state(consensus_state_path(client_id, h))
+                .map_err(context_error)?;
+            ctx.delete_update_time(client_id.clone(), h)
+                .map_err(context_error)?;
+            ctx.delete_update_height(client_id.clone(), h)
+                .map_err(context_error)?;
+        }
+
+        let host_timestamp = ctx.host_timestamp().map_err(context_error)?;
+        let host_height = ctx.host_height().map_err(context_error)?;
+        ctx.store_consensus_state(
+            consensus_state_path(client_id, height),
+            ConsensusState::from(verified.consensus_state).into(),
+        )
+        .map_err(context_error)?;
+        ctx.store_client_state(
+            ClientStatePath::new(client_id),
+            verified.client_state.into(),
+        )
+        .map_err(context_error)?;
+        ctx.store_update_time(client_id.clone(), height, host_timestamp)
+            .map_err(context_error)?;
+        ctx.store_update_height(client_id.clone(), height, host_height)
+            .map_err(context_error)?;
+        Ok(core::iter::once(height).collect())
+    }
+
+    /// Freezes the client at the height of the header or the misbehaviour
+    fn update_state_on_misbehaviour(
+        &self,
+        ctx: &mut E,
+        client_id: &ClientId,
+        client_message: Any,
+        _update_kind: &UpdateKind,
+    ) -> Result<(), ClientError> {
+        let frozen_client_state = match decode_client_message(client_message)? {
+            ClientMessage::Header(header) => {
+                // `Header::height` requires a valid header
+                header.validate_basic()?;
+                self.clone().with_frozen_height(header.height())
+            }
+            ClientMessage::Misbehaviour(misbehaviour) => {
+                let evidence = misbehaviour.evidence(self)?;
+                self.clone()
+                    .with_frozen_height(misbehaviour.height())
+                    .with_misbehaviour_evidence_digest(evidence.digest())
+            }
+        };
+        ctx.store_client_state(ClientStatePath::new(client_id), frozen_client_state.into())
+            .map_err(context_error)
+    }
+
+    fn update_state_on_upgrade(
+        &self,
+        _ctx: &mut E,
+        _client_id: &ClientId,
+        _upgraded_client_state: Any,
+        _upgraded_consensus_state: Any,
+    ) -> Result<Height, ClientError> {
+        Err(upgrade_not_supported())
+    }
+}
+
+/// The header verified against the consensus states of the host
+struct VerifiedHeader<const SYNC_COMMITTEE_SIZE: usize> {
+    height: ibc::Height,
+    trusted_height: ibc::Height,
+    timestamp: ibc::timestamp::Timestamp,
+    client_state: ClientState<SYNC_COMMITTEE_SIZE>,
+    consensus_state: EthConsensusState,
+    trusted_consensus_state: EthConsensusState,
+    stored_consensus_state: Option<EthConsensusState>,
+}
+
+/// Decode the client message, which is a header or a misbehaviour depending on its type URL
+///
+/// ibc-rs 0.48 passes the kind of the message that carried the client message, but like ibc-go,
+/// a header can be submitted as a misbehaviour and vice versa, so the type URL decides how the message is verified.
+fn decode_client_message<const SYNC_COMMITTEE_SIZE: usize>(
+    client_message: Any,
+) -> Result<ClientMessage<SYNC_COMMITTEE_SIZE>, ClientError> {
+    ClientMessage::<SYNC_COMMITTEE_SIZE>::try_from(from_any(client_message)).map_err(client_error)
+}
+
+fn verify_header_in<const SYNC_COMMITTEE_SIZE: usize, C: CommonContext>(
+    client_state: &ClientState<SYNC_COMMITTEE_SIZE>,
+    ctx: &C,
+    client_id: &ClientId,
+    header: Header<SYNC_COMMITTEE_SIZE>,
+) -> Result<VerifiedHeader<SYNC_COMMITTEE_SIZE>, ClientError> {
+    // `Header::height` requires a valid header
+    header.validate_basic()?;
+    let height = header.height();
+    let trusted_height = header.trusted_height();
+    let timestamp = header.timestamp;
+
+    let trusted_consensus_state = trusted_consensus_state(ctx, client_id, trusted_height)?;
+    let stored_consensus_state = maybe_consensus_state(ctx, client_id, height)?;
+    let (new_client_state, new_consensus_state) = verify::verify_header(
+        client_state,
+        &trusted_consensus_state,
+        header,
+        host_timestamp(ctx)?,
+    )?;
+    Ok(VerifiedHeader {
+        height,
+        trusted_height,
+        timestamp,
+        client_state: new_client_state,
+        consensus_state: new_consensus_state,
+        trusted_consensus_state,
+        stored_consensus_state,
+    })
+}
+
+fn verify_misbehaviour_in<const SYNC_COMMITTEE_SIZE: usize, C: CommonContext>(
+    client_state: &ClientState<SYNC_COMMITTEE_SIZE>,
+    ctx: &C,
+    client_id: &ClientId,
+    misbehaviour: Misbehaviour<SYNC_COMMITTEE_SIZE>,
+) -> Result<(), ClientError> {
+    let expected_client_id = from_client_id(client_id)?;
+    if misbehaviour.client_id != expected_client_id {
+        return Err(Error::UnexpectedClientIdInMisbehaviour(
+            expected_client_id,
+            misbehaviour.client_id,
+        )
+        .into());
+    }
+    let (trusted_sync_committee_1, trusted_sync_committee_2) =
+        misbehaviour.trusted_sync_committees();
+    let trusted_consensus_state_1 =
+        trusted_consensus_state(ctx, client_id, trusted_sync_committee_1.height)?;
+    let trusted_consensus_state_2 =
+        trusted_consensus_state(ctx, client_id, trusted_sync_committee_2.height)?;
+    verify::verify_misbehaviour(
+        client_state,
+        (&trusted_consensus_state_1, &trusted_consensus_state_2),
+        &misbehaviour,
+        host_timestamp(ctx)?,
+    )?;
+    Ok(())
+}
+
+fn maybe_consensus_state<C: CommonContext>(
+    ctx: &C,
+    client_id: &ClientId,
+    height: ibc::Height,
+) -> Result<Option<EthConsensusState>, ClientError> {
+    match ctx.consensus_state(&consensus_state_path(client_id, to_height(height))) {
+        Ok(cs) => Ok(Some(
+            cs.try_into()
+                .map_err(|e: C::ConversionError| ClientError::Other {
+                    description: e.to_string(),
+                })?
+                .into_inner(),
+        )),
+        Err(ContextError::ClientError(ClientError::ConsensusStateNotFound { .. })) => Ok(None),
+        Err(e) => Err(context_error(e)),
+    }
+}
+
+fn trusted_consensus_state<C: CommonContext>(
+    ctx: &C,
+    client_id: &ClientId,
+    height: ibc::Height,
+) -> Result<EthConsensusState, ClientError> {
+    maybe_consensus_state(ctx, client_id, height)?.ok_or_else(|| {
+        ClientError::ConsensusStateNotFound {
+            client_id: client_id.clone(),
+            height: to_height(height),
+        }
+    })
+}
+
+fn host_timestamp<C: CommonContext>(ctx: &C) -> Result<ibc::timestamp::Timestamp, ClientError> {
+    let timestamp = ctx.host_timestamp().map_err(context_error)?;
+    ibc::timestamp::Timestamp::from_nanoseconds(timestamp.nanoseconds()).map_err(client_error)
+}
+
+fn latest_height<const SYNC_COMMITTEE_SIZE: usize>(
+    client_state: &ClientState<SYNC_COMMITTEE_SIZE>,
+) -> ibc::Height {
+    ibc::core::ics02_client::client_state::ClientState::latest_height(client_state)
+}
+
+fn consensus_state_path(client_id: &ClientId, height: Height) -> ClientConsensusStatePath {
+    ClientConsensusStatePath::new(
+        client_id.clone(),
+        height.revision_number(),
+        height.revision_height(),
+    )
+}
+
+fn upgrade_not_supported() -> ClientError {
+    ClientError::ClientSpecific {
+        description: "upgrade is not supported for Ethereum client".into(),
+    }
+}
+
+// The conversions between the types of ibc-rs 0.29 used by this crate and ibc-rs 0.48
+
+fn client_error(e: impl ToString) -> ClientError {
+    ClientError::ClientSpecific {
+        description: e.to_string(),
+    }
+}
+
+fn context_error(e: ContextError) -> ClientError {
+    match e {
+        ContextError::ClientError(e) => e,
+        e => ClientError::Other {
+            description: e.to_string(),
+        },
+    }
+}
+
+impl From<Error> for ClientError {
+    fn from(e: Error) -> Self {
+        client_error(e)
+    }
+}
+
+fn to_height(height: ibc::Height) -> Height {
+    Height::new(height.revision_number(), height.revision_height())
+        .expect("the height must be non-zero")
+}
+
+fn from_height(height: Height) -> Result<ibc::Height, ClientError> {
+    ibc::Height::new(height.revision_number(), height.revision_height()).map_err(client_error)
+}
+
+fn to_timestamp(timestamp: ibc::timestamp::Timestamp) -> Timestamp {
+    Timestamp::from_nanoseconds(timestamp.nanoseconds())
+        .expect("the timestamp of the consensus state must be valid")
+}
+
+fn to_any(any: ibc_proto::google::protobuf::Any) -> Any {
+    Any {
+        type_url: any.type_url,
+        value: any.value,
+    }
+}
+
+fn from_any(any: Any) -> ibc_proto::google::protobuf::Any {
+    ibc_proto::google::protobuf::Any {
+        type_url: any.type_url,
+        value: any.value,
+    }
+}
+
+fn from_client_id(
+    client_id: &ClientId,
+) -> Result<ibc::core::ics24_host::identifier::ClientId, ClientError> {
+    ibc::core::ics24_host::identifier::ClientId::from_str(client_id.as_str()).map_err(client_error)
+}
+
+fn from_prefix(
+    prefix: &CommitmentPrefix,
+) -> Result<ibc::core::ics23_commitment::commitment::CommitmentPrefix, ClientError> {
+    ibc::core::ics23_commitment::commitment::CommitmentPrefix::try_from(prefix.as_bytes().to_vec())
+        .map_err(client_error)
+}
+
+fn from_proof(
+    proof: &CommitmentProofBytes,
+) -> Result<ibc::core::ics23_commitment::commitment::CommitmentProofBytes, ClientError> {
+    let proof: Vec<u8> = proof.clone().into();
+    ibc::core::ics23_commitment::commitment::CommitmentProofBytes::try_from(proof)
+        .map_err(client_error)
+}
+
+fn from_root(root: &CommitmentRoot) -> ibc::core::ics23_commitment::commitment::CommitmentRoot {
+    ibc::core::ics23_commitment::commitment::CommitmentRoot::from_bytes(root.as_bytes())
+}
+
+fn from_path(path: Path) -> Result<ibc::core::ics24_host::Path, ClientError> {
+    ibc::core::ics24_host::Path::from_str(&path.to_string()).map_err(client_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client_state::tests::get_membership_proof;
+    use crate::verify::tests::{header_scenario, misbehaviour_scenario};
+    use crate::MinimalClientState;
+    use alloc::collections::BTreeMap;
+    use core::convert::Infallible;
+
+    #[derive(Default)]
+    struct MockContext {
+        host_timestamp: Option<Timestamp>,
+        client_states: BTreeMap<ClientId, MinimalClientState>,
+        consensus_states: BTreeMap<(ClientId, Height), ConsensusState>,
+        update_times: BTreeMap<(ClientId, Height), Timestamp>,
+        update_heights: BTreeMap<(ClientId, Height), Height>,
+    }
+
+    impl MockContext {
+        fn new(now: ibc::timestamp::Timestamp) -> Self {
+            Self {
+                host_timestamp: Some(to_timestamp(now)),
+                ..Default::default()
+            }
+        }
+
+        fn key(path: &ClientConsensusStatePath) -> (ClientId, Height) {
+            (
+                path.client_id.clone(),
+                Height::new(path.revision_number, path.revision_height).unwrap(),
+            )
+        }
+    }
+
+    impl CommonContext for MockContext {
+        type ConversionError = Infallible;
+        type AnyConsensusState = ConsensusState;
+
+        fn consensus_state(
+            &self,
+            client_cons_state_path: &ClientConsensusStatePath,
+        ) -> Result<ConsensusState, ContextError> {
+            let (client_id, height) = Self::key(client_cons_state_path);
+            self.consensus_states
+                .get(&(client_id.clone(), height))
+                .cloned()
+                .ok_or(ContextError::ClientError(
+                    ClientError::ConsensusStateNotFound { client_id, height },
+                ))
+        }
+
+        fn consensus_state_heights(
+            &self,
+            client_id: &ClientId,
+        ) -> Result<Vec<Height>, ContextError> {
+            Ok(self
+                .consensus_states
+                .keys()
+                .filter(|(id, _)| id == client_id)
+                .map(|(_, height)| *height)
+                .collect())
+        }
+
+        fn host_timestamp(&self) -> Result<Timestamp, ContextError> {
+            Ok(self.host_timestamp.unwrap())
+        }
+
+        fn host_height(&self) -> Result<Height, ContextError> {
+            Ok(Height::new(0, 100).unwrap())
+        }
+    }
+
+    impl ClientValidationContext for MockContext {
+        fn client_update_time(
+            &self,
+            client_id: &ClientId,
+            height: &Height,
+        ) -> Result<Timestamp, ContextError> {
+            Ok(self.update_times[&(client_id.clone(), *height)])
+        }
+
+        fn client_update_height(
+            &self,
+            client_id: &ClientId,
+            height: &Height,
+        ) -> Result<Height, ContextError> {
+            Ok(self.update_heights[&(client_id.clone(), *height)])
+        }
+    }
+
+    impl ClientExecutionContext for MockContext {
+        type V = Self;
+        type AnyClientState = MinimalClientState;
+        type AnyConsensusState = ConsensusState;
+
+        fn store_client_state(
+            &mut self,
+            client_state_path: ClientStatePath,
+            client_state: MinimalClientState,
+        ) -> Result<(), ContextError> {
+            self.client_states.insert(client_state_path.0, client_state);
+            Ok(())
+        }
+
+        fn store_consensus_state(
+            &mut self,
+            consensus_state_path: ClientConsensusStatePath,
+            consensus_state: ConsensusState,
+        ) -> Result<(), ContextError> {
+            self.consensus_states
+                .insert(Self::key(&consensus_state_path), consensus_state);
+            Ok(())
+        }
+
+        fn delete_consensus_state(
+            &mut self,
+            consensus_state_path: ClientConsensusStatePath,
+        ) -> Result<(), ContextError> {
+            self.consensus_states
+                .remove(&Self::key(&consensus_state_path));
+            Ok(())
+        }
+
+        fn store_update_time(
+            &mut self,
+            client_id: ClientId,
+            height: Height,
+            host_timestamp: Timestamp,
+        ) -> Result<(), ContextError> {
+            self.update_times
+                .insert((client_id, height), host_timestamp);
+            Ok(())
+        }
+
+        fn store_update_height(
+            &mut self,
+            client_id: ClientId,
+            height: Height,
+            host_height: Height,
+        ) -> Result<(), ContextError> {
+            self.update_heights.insert((client_id, height), host_height);
+            Ok(())
+        }
+
+        fn delete_update_time(
+            &mut self,
+            client_id: ClientId,
+            height: Height,
+        ) -> Result<(), ContextError> {
+            self.update_times.remove(&(client_id, height));
+            Ok(())
+        }
+
+        fn delete_update_height(
+            &mut self,
+            client_id: ClientId,
+            height: Height,
+        ) -> Result<(), ContextError> {
+            self.update_heights.remove(&(client_id, height));
+            Ok(())
+        }
+    }
+
+    fn client_id() -> ClientId {
+        ClientId::from_str("ethereum-0").unwrap()
+    }
+
+    // the same scenario as `verify::tests::test_verify_header`
+    #[test]
+    fn test_update_and_membership() {
+        let (client_state, trusted_consensus_state, header, now) = header_scenario();
+        let mut ctx = MockContext::new(now);
+        ClientStateExecution::initialise(
+            &client_state,
+            &mut ctx,
+            &client_id(),
+            to_any(trusted_consensus_state.clone().into()),
+        )
+        .unwrap();
+        assert_eq!(
+            ClientStateValidation::status(&client_state, &ctx, &client_id()).unwrap(),
+            Status::Active
+        );
+
+        let header_height = to_height(header.height());
+        let header = to_any(header.into());
+        let res = ClientStateValidation::verify_client_message(
+            &client_state,
+            &ctx,
+            &client_id(),
+            header.clone(),
+            &UpdateKind::UpdateClient,
+        );
+        assert!(res.is_ok(), "{:?}", res);
+        assert!(!ClientStateValidation::check_for_misbehaviour(
+            &client_state,
+            &ctx,
+            &client_id(),
+            header.clone(),
+            &UpdateKind::UpdateClient,
+        )
+        .unwrap());
+        let heights = ClientStateExecution::update_state(
+            &client_state,
+            &mut ctx,
+            &client_id(),
+            header.clone(),
+        )
+        .unwrap();
+        assert_eq!(heights, vec![header_height]);
+
+        let new_client_state = ctx.client_states[&client_id()].clone();
+        assert_eq!(
+            ClientStateCommon::latest_height(&new_client_state),
+            header_height
+        );
+        let new_consensus_state = ctx.consensus_states[&(client_id(), header_height)].clone();
+        // the storage of the IBC contract is unchanged, so the proven storage root is the trusted one
+        assert_eq!(
+            new_consensus_state.inner().storage_root,
+            trusted_consensus_state.storage_root
+        );
+        assert!(ctx.update_times.contains_key(&(client_id(), header_height)));
+
+        let (path, proof, value) = get_membership_proof();
+        ClientStateCommon::validate_proof_height(&new_client_state, header_height).unwrap();
+        let res = ClientStateCommon::verify_membership(
+            &new_client_state,
+            &CommitmentPrefix::try_from(b"ibc".to_vec()).unwrap(),
+            &CommitmentProofBytes::try_from(proof).unwrap(),
+            new_consensus_state.root(),
+            Path::from_str(&path).unwrap(),
+            value,
+        );
+        assert!(res.is_ok(), "{:?}", res);
+
+        // the consensus state already stored is not a misbehaviour, and is not overwritten
+        assert!(!ClientStateValidation::check_for_misbehaviour(
+            &client_state,
+            &ctx,
+            &client_id(),
+            header.clone(),
+            &UpdateKind::UpdateClient,
+        )
+        .unwrap());
+
+        // the trusted consensus state has expired
+        ctx.host_timestamp = Some(to_timestamp((now + client_state.trusting_period).unwrap()));
+        let res = ClientStateValidation::verify_client_message(
+            &client_state,
+            &ctx,
+            &client_id(),
+            header,
+            &UpdateKind::UpdateClient,
+        );
+        assert!(res.is_err());
+        assert_eq!(
+            ClientStateValidation::status(&new_client_state, &ctx, &client_id()).unwrap(),
+            Status::Expired
+        );
+    }
+
+    // the same scenario as `verify::tests::test_verify_misbehaviour`
+    #[test]
+    fn test_misbehaviour() {
+        let (client_state, trusted_consensus_state, misbehaviour, now) = misbehaviour_scenario();
+        let mut ctx = MockContext::new(now);
+        ClientStateExecution::initialise(
+            &client_state,
+            &mut ctx,
+            &client_id(),
+            to_any(trusted_consensus_state.into()),
+        )
+        .unwrap();
+
+        let misbehaviour_height = misbehaviour.height();
+        let misbehaviour = to_any(misbehaviour.into());
+        let res = ClientStateValidation::verify_client_message(
+            &client_state,
+            &ctx,
+            &client_id(),
+            misbehaviour.clone(),
+            &UpdateKind::SubmitMisbehaviour,
+        );
+        assert!(res.is_ok(), "{:?}", res);
+        assert!(ClientStateValidation::check_for_misbehaviour(
+            &client_state,
+            &ctx,
+            &client_id(),
+            misbehaviour.clone(),
+            &UpdateKind::SubmitMisbehaviour,
+        )
+        .unwrap());
+
+        // the misbehaviour of another client is rejected
+        let res = ClientStateValidation::verify_client_message(
+            &client_state,
+            &ctx,
+            &ClientId::from_str("ethereum-1").unwrap(),
+            misbehaviour.clone(),
+            &UpdateKind::SubmitMisbehaviour,
+        );
+        assert!(res.is_err());
+
+        ClientStateExecution::update_state_on_misbehaviour(
+            &client_state,
+            &mut ctx,
+            &client_id(),
+            misbehaviour.clone(),
+            &UpdateKind::SubmitMisbehaviour,
+        )
+        .unwrap();
+        let frozen_client_state = ctx.client_states[&client_id()].clone();
+        assert_eq!(frozen_client_state.frozen_height, Some(misbehaviour_height));
+        assert!(frozen_client_state.misbehaviour_evidence_digest.is_some());
+        assert_eq!(
+            ClientStateValidation::status(&frozen_client_state, &ctx, &client_id()).unwrap(),
+            Status::Frozen
+        );
+
+        // a frozen client cannot be frozen again
+        let res = ClientStateValidation::verify_client_message(
+            &frozen_client_state,
+            &ctx,
+            &client_id(),
+            misbehaviour,
+            &UpdateKind::SubmitMisbehaviour,
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_client_message_dispatch() {
+        let (client_state, trusted_consensus_state, header, now) = header_scenario();
+        let mut ctx = MockContext::new(now);
+        ClientStateExecution::initialise(
+            &client_state,
+            &mut ctx,
+            &client_id(),
+            to_any(trusted_consensus_state.into()),
+        )
+        .unwrap();
+        let header = to_any(header.into());
+        // the type URL decides how the message is verified regardless of the update kind
+        for update_kind in [UpdateKind::UpdateClient, UpdateKind::SubmitMisbehaviour] {
+            let res = ClientStateValidation::verify_client_message(
+                &client_state,
+                &ctx,
+                &client_id(),
+                header.clone(),
+                &update_kind,
+            );
+            assert!(res.is_ok(), "{:?}", res);
+        }
+
+        // a header submitted under the type URL of a misbehaviour fails to decode
+        for type_url in [
+            crate::misbehaviour::ETHEREUM_FINALIZED_HEADER_MISBEHAVIOUR_TYPE_URL,
+            crate::misbehaviour::ETHEREUM_NEXT_SYNC_COMMITTEE_MISBEHAVIOUR_TYPE_URL,
+        ] {
+            let res = ClientStateValidation::verify_client_message(
+                &client_state,
+                &ctx,
+                &client_id(),
+                Any {
+                    type_url: type_url.to_string(),
+                    value: header.value.clone(),
+                },
+                &UpdateKind::SubmitMisbehaviour,
+            );
+            assert!(res.is_err(), "{}", type_url);
+        }
+
+        let unknown_type_url = "/ibc.lightclients.tendermint.v1.Header";
+        let res = ClientStateValidation::verify_client_message(
+            &client_state,
+            &ctx,
+            &client_id(),
+            Any {
+                type_url: unknown_type_url.to_string(),
+                value: header.value,
+            },
+            &UpdateKind::UpdateClient,
+        );
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            client_error(Error::UnexpectedClientMessageType {
+                url: unknown_type_url.to_string()
+            })
+            .to_string()
+        );
+
+        let (client_state, trusted_consensus_state, misbehaviour, now) = misbehaviour_scenario();
+        let mut ctx = MockContext::new(now);
+        ClientStateExecution::initialise(
+            &client_state,
+            &mut ctx,
+            &client_id(),
+            to_any(trusted_consensus_state.into()),
+        )
+        .unwrap();
+        let misbehaviour = to_any(misbehaviour.into());
+        for update_kind in [UpdateKind::UpdateClient, UpdateKind::SubmitMisbehaviour] {
+            let res = ClientStateValidation::verify_client_message(
+                &client_state,
+                &ctx,
+                &client_id(),
+                misbehaviour.clone(),
+                &update_kind,
+            );
+            assert!(res.is_ok(), "{:?}", res);
+            assert!(ClientStateValidation::check_for_misbehaviour(
+                &client_state,
+                &ctx,
+                &client_id(),
+                misbehaviour.clone(),
+                &update_kind,
+            )
+            .unwrap());
+        }
+    }
+}
@@ -0,0 +1,397 @@
+//! Decoding of the proto-encoded messages of the client for debugging
+//!
+//! `decode_message` decodes an `Any`, which is given as hex or base64 e.g. from a rejected transaction,
+//! into the client state, the consensus state, the header or the misbehaviour according to its type URL.
+//! The messages are decoded with the same strict conversions as the client, so a message that decodes here is accepted by the decoding of the client.
+//! The `ethereum-ibc-decode` binary of the `tools` feature prints the summary and the JSON of a message.
+use crate::client_state::{ClientState, ETHEREUM_CLIENT_STATE_TYPE_URL};
+use crate::consensus_state::{ConsensusState, ETHEREUM_CONSENSUS_STATE_TYPE_URL};
+use crate::diagnostics::fingerprint;
+use crate::errors::Error;
+use crate::header::{Header, ETHEREUM_HEADER_TYPE_URL};
+use crate::internal_prelude::*;
+use crate::misbehaviour::{
+    Misbehaviour, ETHEREUM_FINALIZED_HEADER_MISBEHAVIOUR_TYPE_URL,
+    ETHEREUM_NEXT_SYNC_COMMITTEE_MISBEHAVIOUR_TYPE_URL,
+};
+use crate::types::ConsensusUpdateInfo;
+use base64::Engine;
+use core::fmt;
+use ethereum_light_client_verifier::misbehaviour::Misbehaviour as MisbehaviourData;
+use ibc_proto::google::protobuf::Any as IBCAny;
+use prost::Message;
+
+/// The type URLs that `decode_message` accepts
+pub const DECODABLE_TYPE_URLS: &[&str] = &[
+    ETHEREUM_CLIENT_STATE_TYPE_URL,
+    ETHEREUM_CONSENSUS_STATE_TYPE_URL,
+    ETHEREUM_HEADER_TYPE_URL,
+    ETHEREUM_FINALIZED_HEADER_MISBEHAVIOUR_TYPE_URL,
+    ETHEREUM_NEXT_SYNC_COMMITTEE_MISBEHAVIOUR_TYPE_URL,
+];
+
+/// A message of the client decoded from `Any`
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecodedMessage<const SYNC_COMMITTEE_SIZE: usize> {
+    ClientState(ClientState<SYNC_COMMITTEE_SIZE>),
+    ConsensusState(ConsensusState),
+    Header(Header<SYNC_COMMITTEE_SIZE>),
+    Misbehaviour(Misbehaviour<SYNC_COMMITTEE_SIZE>),
+}
+
+impl<const SYNC_COMMITTEE_SIZE: usize> DecodedMessage<SYNC_COMMITTEE_SIZE> {
+    /// Returns the JSON of the message, which follows the beacon API conventions of the `json` feature
+    pub fn to_json(&self) -> serde_json::Value {
+        let res = match self {
+            Self::ClientState(client_state) => serde_json::to_value(client_state),
+            Self::ConsensusState(consensus_state) => serde_json::to_value(consensus_state),
+            Self::Header(header) => serde_json::to_value(header),
+            Self::Misbehaviour(misbehaviour) => serde_json::to_value(misbehaviour),
+        };
+        res.expect("the messages are serializable to JSON")
+    }
+}
+
+/// Decodes the hex or base64 encoding of an `Any` into the message of its type URL
+///
+/// Whitespace is ignored, and the hex may have a `0x` prefix.
+/// Returns the error of the decoding of the client if the payload is not accepted by it.
+pub fn decode_message<const SYNC_COMMITTEE_SIZE: usize>(
+    input: &str,
+) -> Result<DecodedMessage<SYNC_COMMITTEE_SIZE>, String> {
+    let bz = decode_input(input)?;
+    let any = IBCAny::decode(bz.as_slice()).map_err(|e| Error::Decode(e).to_string())?;
+    decode_any(any).map_err(|e| e.to_string())
+}
+
+/// Decodes `any` into the message of its type URL with the conversions of the client
+pub fn decode_any<const SYNC_COMMITTEE_SIZE: usize>(
+    any: IBCAny,
+) -> Result<DecodedMessage<SYNC_COMMITTEE_SIZE>, ibc::core::ics02_client::error::ClientError> {
+    Ok(match any.type_url.as_str() {
+        ETHEREUM_CLIENT_STATE_TYPE_URL => DecodedMessage::ClientState(any.try_into()?),
+        ETHEREUM_CONSENSUS_STATE_TYPE_URL => DecodedMessage::ConsensusState(any.try_into()?),
+        ETHEREUM_HEADER_TYPE_URL => DecodedMessage::Header(any.try_into()?),
+        ETHEREUM_FINALIZED_HEADER_MISBEHAVIOUR_TYPE_URL
+        | ETHEREUM_NEXT_SYNC_COMMITTEE_MISBEHAVIOUR_TYPE_URL => {
+            DecodedMessage::Misbehaviour(any.try_into()?)
+        }
+        _ => {
+            return Err(Error::UnexpectedTypeUrl {
+                expected: DECODABLE_TYPE_URLS,
+                got: any.type_url,
+            }
+            .into())
+        }
+    })
+}
+
+/// Returns the bytes of `input`, which is hex if it consists of hex digits and base64 otherwise
+fn decode_input(input: &str) -> Result<Vec<u8>, String> {
+    let input: String = input.chars().filter(|c| !c.is_whitespace()).collect();
+    if input.is_empty() {
+        return Err("empty input".into());
+    }
+    let hex_input = input.strip_prefix("0x").unwrap_or(&input);
+    if hex_input.len() % 2 == 0 && hex_input.chars().all(|c| c.is_ascii_hexdigit()) {
+        hex::decode(hex_input).map_err(|e| format!("invalid hex: {}", e))
+    } else {
+        base64::engine::general_purpose::STANDARD
+            .decode(&input)
+            .map_err(|e| format!("invalid base64: {}", e))
+    }
+}
+
+/// The summary of the message, which has a line for each field relevant to debugging
+impl<const SYNC_COMMITTEE_SIZE: usize> fmt::Display for DecodedMessage<SYNC_COMMITTEE_SIZE> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ClientState(client_state) => {
+                writeln!(f, "type: {}", ETHEREUM_CLIENT_STATE_TYPE_URL)?;
+                writeln!(
+                    f,
+                    "genesis_validators_root: 0x{}",
+                    hex::encode(client_state.genesis_validators_root.as_bytes())
+                )?;
+                writeln!(
+                    f,
+                    "latest_execution_block_number: {}",
+                    client_state.latest_execution_block_number
+                )?;
+                writeln!(
+                    f,
+                    "forks: {}",
+                    client_state
+                        .fork_parameters
+                        .forks()
+                        .iter()
+                        .map(|fork| format!("0x{}@{}", hex::encode(fork.version.0), fork.epoch))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                )?;
+                writeln!(
+                    f,
+                    "trust_level: {}/{}",
+                    client_state.trust_level.numerator(),
+                    client_state.trust_level.denominator()
+                )?;
+                writeln!(
+                    f,
+                    "trusting_period: {}s",
+                    client_state.trusting_period.as_secs()
+                )?;
+                writeln!(f, "ibc_address: {}", client_state.ibc_address)?;
+                match client_state.frozen_height {
+                    Some(h) => write!(f, "frozen_height: {}", h),
+                    None => write!(f, "frozen_height: none"),
+                }
+            }
+            Self::ConsensusState(consensus_state) => {
+                writeln!(f, "type: {}", ETHEREUM_CONSENSUS_STATE_TYPE_URL)?;
+                writeln!(f, "slot: {}", consensus_state.slot)?;
+                writeln!(
+                    f,
+                    "storage_root: 0x{}",
+                    hex::encode(consensus_state.storage_root.as_bytes())
+                )?;
+                writeln!(f, "timestamp: {}", consensus_state.timestamp.nanoseconds())?;
+                writeln!(
+                    f,
+                    "current_sync_committee: {}",
+                    fingerprint(&consensus_state.current_sync_committee)
+                )?;
+                write!(
+                    f,
+                    "next_sync_committee: {}",
+                    fingerprint(&consensus_state.next_sync_committee)
+                )
+            }
+            Self::Header(header) => {
+                writeln!(f, "type: {}", ETHEREUM_HEADER_TYPE_URL)?;
+                writeln!(f, "trusted_height: {}", header.trusted_height())?;
+                writeln!(f, "is_next: {}", header.trusted_sync_committee.is_next)?;
+                writeln!(
+                    f,
+                    "execution_block_number: {}",
+                    header.execution_update.block_number
+                )?;
+                writeln!(
+                    f,
+                    "intermediate_consensus_updates: {}",
+                    header.intermediate_consensus_updates.len()
+                )?;
+                write_consensus_update(f, "consensus_update", &header.consensus_update)?;
+                writeln!(
+                    f,
+                    "account_storage_root: 0x{}",
+                    hex::encode(header.account_update.account_storage_root.as_bytes())
+                )?;
+                write!(f, "timestamp: {}", header.timestamp.nanoseconds())
+            }
+            Self::Misbehaviour(misbehaviour) => {
+                let type_url = match misbehaviour.data {
+                    MisbehaviourData::FinalizedHeader(_) => {
+                        ETHEREUM_FINALIZED_HEADER_MISBEHAVIOUR_TYPE_URL
+                    }
+                    MisbehaviourData::NextSyncCommittee(_) => {
+                        ETHEREUM_NEXT_SYNC_COMMITTEE_MISBEHAVIOUR_TYPE_URL
+                    }
+                };
+                writeln!(f, "type: {}", type_url)?;
+                writeln!(f, "client_id: {}", misbehaviour.client_id)?;
+                let (trusted_1, trusted_2) = misbehaviour.trusted_sync_committees();
+                writeln!(
+                    f,
+                    "trusted_heights: {} {}",
+                    trusted_1.height, trusted_2.height
+                )?;
+                let (update_1, update_2) = misbehaviour.consensus_updates();
+                write_consensus_update(f, "consensus_update_1", update_1)?;
+                write_consensus_update(f, "consensus_update_2", update_2)?;
+                write!(
+                    f,
+                    "finalized_headers_conflict: {}",
+                    update_1.finalized_header.0 != update_2.finalized_header.0
+                )
+            }
+        }
+    }
+}
+
+fn write_consensus_update<const SYNC_COMMITTEE_SIZE: usize>(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    update: &ConsensusUpdateInfo<SYNC_COMMITTEE_SIZE>,
+) -> fmt::Result {
+    writeln!(
+        f,
+        "{}: signature_slot={} attested_slot={} finalized_slot={} participants={}/{} next_sync_committee={}",
+        name,
+        update.signature_slot,
+        update.attested_header.slot,
+        update.finalized_header.0.slot,
+        update.sync_aggregate.sync_committee_bits.count_ones(),
+        SYNC_COMMITTEE_SIZE,
+        update.next_sync_committee.is_some()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client_state::tests::new_client_state;
+    use crate::eth_client_type;
+    use crate::MINIMAL_SYNC_COMMITTEE_SIZE;
+    use ethereum_consensus::types::H256;
+    use ethereum_light_client_verifier::misbehaviour::FinalizedHeaderMisbehaviour;
+    use hex_literal::hex;
+    use ibc::core::ics24_host::identifier::ClientId;
+
+    const HEADER_JSON: &str = include_str!("../testdata/header.json");
+
+    /// The sync committee size of the header fixture
+    const SIZE: usize = 8;
+
+    /// The snapshot of `test_consensus_state_encoding_snapshot` as an encoded `Any`
+    fn consensus_state_any() -> IBCAny {
+        IBCAny {
+            type_url: ETHEREUM_CONSENSUS_STATE_TYPE_URL.into(),
+            value: hex!("0801122027cd08827e6bf1e435832f4b2660107beb562314287b3fa534f3b189574c0cca1a060880af85a7062230a145063e1b5eda80fa55960296f2c4b2c021f75767318ea2572a9f7abb649010b746754ca7fc2ba57c1156881516a3572a30a42dffb90d85cec7acfcb53be0e8792155d8f18c0dc9efc2a5587d5a0ba3e578df366fc3e2b743de6ecd3b53e345c266").to_vec(),
+        }
+    }
+
+    fn fixture_header() -> Header<SIZE> {
+        serde_json::from_str(HEADER_JSON).unwrap()
+    }
+
+    #[test]
+    fn test_decode_client_state() {
+        let client_state = new_client_state();
+        let bz = IBCAny::from(client_state.clone()).encode_to_vec();
+        let decoded = decode_message::<MINIMAL_SYNC_COMMITTEE_SIZE>(&hex::encode(&bz)).unwrap();
+        assert_eq!(decoded, DecodedMessage::ClientState(client_state.clone()));
+        assert_eq!(
+            decoded.to_json(),
+            serde_json::to_value(&client_state).unwrap()
+        );
+        let summary = decoded.to_string();
+        assert!(summary.contains("trust_level: 2/3"), "{}", summary);
+        assert!(summary.ends_with("frozen_height: none"), "{}", summary);
+
+        // base64 and hex with a prefix and whitespace
+        let base64 = base64::engine::general_purpose::STANDARD.encode(&bz);
+        assert_eq!(
+            decode_message::<MINIMAL_SYNC_COMMITTEE_SIZE>(&base64).unwrap(),
+            decoded
+        );
+        let prefixed = format!("0x{}\n", hex::encode(&bz));
+        assert_eq!(
+            decode_message::<MINIMAL_SYNC_COMMITTEE_SIZE>(&prefixed).unwrap(),
+            decoded
+        );
+    }
+
+    #[test]
+    fn test_decode_consensus_state() {
+        let bz = consensus_state_any().encode_to_vec();
+        let decoded = decode_message::<SIZE>(&hex::encode(&bz)).unwrap();
+        let consensus_state = match &decoded {
+            DecodedMessage::ConsensusState(consensus_state) => consensus_state.clone(),
+            decoded => panic!("unexpected message: {:?}", decoded),
+        };
+        assert_eq!(consensus_state.slot, 1.into());
+        assert_eq!(
+            decoded.to_string(),
+            format!(
+                "type: {}\nslot: 1\nstorage_root: 0x27cd08827e6bf1e435832f4b2660107beb562314287b3fa534f3b189574c0cca\ntimestamp: 1692489600000000000\ncurrent_sync_committee: 0xa145063e1b5eda80\nnext_sync_committee: 0xa42dffb90d85cec7",
+                ETHEREUM_CONSENSUS_STATE_TYPE_URL
+            )
+        );
+
+        // the strict decoding of the client rejects trailing garbage in the payload
+        let mut any = consensus_state_any();
+        any.value.push(0);
+        let err = decode_message::<SIZE>(&hex::encode(any.encode_to_vec())).unwrap_err();
+        assert_eq!(err, ConsensusState::try_from(any).unwrap_err().to_string());
+    }
+
+    #[test]
+    fn test_decode_header() {
+        let header = fixture_header();
+        let bz = IBCAny::from(header.clone()).encode_to_vec();
+        let decoded = decode_message::<SIZE>(&hex::encode(&bz)).unwrap();
+        assert_eq!(decoded, DecodedMessage::Header(header));
+        let expected: serde_json::Value = serde_json::from_str(HEADER_JSON).unwrap();
+        assert_eq!(decoded.to_json(), expected);
+        let summary = decoded.to_string();
+        assert!(
+            summary.contains("consensus_update: signature_slot=75 attested_slot=74 finalized_slot=72 participants=8/8 next_sync_committee=true"),
+            "{}",
+            summary
+        );
+        assert!(summary.contains("execution_block_number: 2"), "{}", summary);
+
+        // a header decoded with another sync committee size
+        let res = decode_message::<MINIMAL_SYNC_COMMITTEE_SIZE>(&hex::encode(&bz));
+        assert!(res.is_err(), "{:?}", res);
+    }
+
+    #[test]
+    fn test_decode_misbehaviour() {
+        let header = fixture_header();
+        let update_1 = header.consensus_update.clone();
+        let mut update_2 = update_1.clone();
+        update_2.finalized_header.0.state_root = H256([0xff; 32]);
+        let misbehaviour = Misbehaviour::<SIZE> {
+            client_id: ClientId::new(eth_client_type(), 0).unwrap(),
+            trusted_sync_committee_1: header.trusted_sync_committee.clone(),
+            trusted_sync_committee_2: header.trusted_sync_committee,
+            data: MisbehaviourData::FinalizedHeader(FinalizedHeaderMisbehaviour {
+                consensus_update_1: update_1,
+                consensus_update_2: update_2,
+            }),
+        };
+        let bz = IBCAny::from(misbehaviour.clone()).encode_to_vec();
+        let decoded = decode_message::<SIZE>(&hex::encode(bz)).unwrap();
+        assert_eq!(decoded, DecodedMessage::Misbehaviour(misbehaviour.clone()));
+        assert_eq!(
+            decoded.to_json(),
+            serde_json::to_value(&misbehaviour).unwrap()
+        );
+        let summary = decoded.to_string();
+        assert!(
+            summary.starts_with(&format!(
+                "type: {}\nclient_id: ethereum-0\n",
+                ETHEREUM_FINALIZED_HEADER_MISBEHAVIOUR_TYPE_URL
+            )),
+            "{}",
+            summary
+        );
+        assert!(
+            summary.ends_with("finalized_headers_conflict: true"),
+            "{}",
+            summary
+        );
+    }
+
+    #[test]
+    fn test_decode_invalid_input() {
+        let res = decode_message::<SIZE>("");
+        assert_eq!(res.unwrap_err(), "empty input");
+        let res = decode_message::<SIZE>("not base64!");
+        assert!(res.unwrap_err().starts_with("invalid base64"));
+
+        // a message of another client
+        let any = IBCAny {
+            type_url: "/ibc.lightclients.tendermint.v1.ClientState".into(),
+            value: vec![],
+        };
+        let err = decode_message::<SIZE>(&hex::encode(any.encode_to_vec())).unwrap_err();
+        assert!(
+            err.contains("/ibc.lightclients.tendermint.v1.ClientState"),
+            "{}",
+            err
+        );
+    }
+}
@@ -0,0 +1,488 @@
+//! JSON representation of the update types in the conventions of the beacon API
+//!
+//! With the `json` feature, the fields of `Header`, `ConsensusUpdateInfo`, `ExecutionUpdateInfo`, `AccountUpdateInfo` and `TrustedSyncCommittee`
+//! are serialized by this module with `#[serde(with = "crate::json")]`.
+//! Human-readable formats such as JSON encode integers as decimal strings, and roots, keys, signatures, bits and proofs as 0x-prefixed hex strings.
+//! Non-human-readable formats keep the derived representation.
+use crate::internal_prelude::*;
+use crate::types::{
+    convert_proto_sync_aggregate, convert_proto_to_sync_committee, convert_sync_aggregate_to_proto,
+};
+use ethereum_consensus::beacon::BeaconBlockHeader;
+use ethereum_consensus::sync_protocol::{SyncAggregate, SyncCommittee};
+use ethereum_consensus::types::{H256, U64};
+use ethereum_ibc_proto::ibc::lightclients::ethereum::v1::{
+    SyncAggregate as ProtoSyncAggregate, SyncCommittee as ProtoSyncCommittee,
+};
+use ibc::timestamp::Timestamp;
+use ibc::Height;
+use serde::de::{DeserializeOwned, Error as _};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A type that has a JSON representation in the conventions of the beacon API
+pub trait BeaconJson: Sized {
+    /// The JSON representation of the type
+    type Json: Serialize + DeserializeOwned;
+
+    fn to_json(&self) -> Self::Json;
+
+    /// Returns an error message if `json` is malformed
+    fn from_json(json: Self::Json) -> Result<Self, String>;
+}
+
+/// Serializes `value` as `BeaconJson::Json` if the serializer is human-readable
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: BeaconJson + Serialize,
+    S: Serializer,
+{
+    if serializer.is_human_readable() {
+        value.to_json().serialize(serializer)
+    } else {
+        value.serialize(serializer)
+    }
+}
+
+/// Deserializes a value from `BeaconJson::Json` if the deserializer is human-readable
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: BeaconJson + Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        T::from_json(T::Json::deserialize(deserializer)?).map_err(D::Error::custom)
+    } else {
+        T::deserialize(deserializer)
+    }
+}
+
+impl BeaconJson for U64 {
+    type Json = String;
+
+    fn to_json(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn from_json(json: String) -> Result<Self, String> {
+        json.parse::<u64>()
+            .map(U64)
+            .map_err(|e| format!("invalid decimal string `{}`: {}", json, e))
+    }
+}
+
+impl BeaconJson for H256 {
+    type Json = String;
+
+    fn to_json(&self) -> String {
+        encode_hex(self.as_bytes())
+    }
+
+    fn from_json(json: String) -> Result<Self, String> {
+        Ok(H256::from_slice(&decode_hex_fixed(&json, 32)?))
+    }
+}
+
+impl BeaconJson for Vec<H256> {
+    type Json = Vec<String>;
+
+    fn to_json(&self) -> Vec<String> {
+        self.iter().map(|h| h.to_json()).collect()
+    }
+
+    fn from_json(json: Vec<String>) -> Result<Self, String> {
+        json.into_iter()
+            .enumerate()
+            .map(|(i, h)| H256::from_json(h).map_err(|e| format!("[{}]: {}", i, e)))
+            .collect()
+    }
+}
+
+impl BeaconJson for Vec<Vec<u8>> {
+    type Json = Vec<String>;
+
+    fn to_json(&self) -> Vec<String> {
+        self.iter().map(|bz| encode_hex(bz)).collect()
+    }
+
+    fn from_json(json: Vec<String>) -> Result<Self, String> {
+        json.iter()
+            .enumerate()
+            .map(|(i, bz)| decode_hex(bz).map_err(|e| format!("[{}]: {}", i, e)))
+            .collect()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BeaconBlockHeaderJson {
+    pub slot: String,
+    pub proposer_index: String,
+    pub parent_root: String,
+    pub state_root: String,
+    pub body_root: String,
+}
+
+impl BeaconJson for BeaconBlockHeader {
+    type Json = BeaconBlockHeaderJson;
+
+    fn to_json(&self) -> BeaconBlockHeaderJson {
+        BeaconBlockHeaderJson {
+            slot: self.slot.to_json(),
+            proposer_index: self.proposer_index.to_json(),
+            parent_root: self.parent_root.to_json(),
+            state_root: self.state_root.to_json(),
+            body_root: self.body_root.to_json(),
+        }
+    }
+
+    fn from_json(json: BeaconBlockHeaderJson) -> Result<Self, String> {
+        Ok(BeaconBlockHeader {
+            slot: field("slot", U64::from_json(json.slot))?,
+            proposer_index: field("proposer_index", U64::from_json(json.proposer_index))?,
+            parent_root: field("parent_root", H256::from_json(json.parent_root))?,
+            state_root: field("state_root", H256::from_json(json.state_root))?,
+            body_root: field("body_root", H256::from_json(json.body_root))?,
+        })
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SyncCommitteeJson {
+    pub pubkeys: Vec<String>,
+    pub aggregate_pubkey: String,
+}
+
+impl<const SYNC_COMMITTEE_SIZE: usize> BeaconJson for SyncCommittee<SYNC_COMMITTEE_SIZE> {
+    type Json = SyncCommitteeJson;
+
+    fn to_json(&self) -> SyncCommitteeJson {
+        SyncCommitteeJson {
+            pubkeys: self
+                .pubkeys
+                .iter()
+                .map(|pk| encode_hex(&pk.to_vec()))
+                .collect(),
+            aggregate_pubkey: encode_hex(&self.aggregate_pubkey.to_vec()),
+        }
+    }
+
+    fn from_json(json: SyncCommitteeJson) -> Result<Self, String> {
+        if json.pubkeys.len() != SYNC_COMMITTEE_SIZE {
+            return Err(format!(
+                "pubkeys: expected {} keys but got {} keys",
+                SYNC_COMMITTEE_SIZE,
+                json.pubkeys.len()
+            ));
+        }
+        let pubkeys = json
+            .pubkeys
+            .iter()
+            .enumerate()
+            .map(|(i, pk)| decode_hex_fixed(pk, 48).map_err(|e| format!("pubkeys[{}]: {}", i, e)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let aggregate_pubkey = field(
+            "aggregate_pubkey",
+            decode_hex_fixed(&json.aggregate_pubkey, 48),
+        )?;
+        convert_proto_to_sync_committee(ProtoSyncCommittee {
+            pubkeys,
+            aggregate_pubkey,
+        })
+        .map_err(|e| e.to_string())
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SyncAggregateJson {
+    pub sync_committee_bits: String,
+    pub sync_committee_signature: String,
+}
+
+impl<const SYNC_COMMITTEE_SIZE: usize> BeaconJson for SyncAggregate<SYNC_COMMITTEE_SIZE> {
+    type Json = SyncAggregateJson;
+
+    /// CONTRACT: `SYNC_COMMITTEE_SIZE` must be greater than 0, see `convert_sync_aggregate_to_proto`
+    fn to_json(&self) -> SyncAggregateJson {
+        let proto = convert_sync_aggregate_to_proto(self.clone());
+        SyncAggregateJson {
+            sync_committee_bits: encode_hex(&proto.sync_committee_bits),
+            sync_committee_signature: encode_hex(&proto.sync_committee_signature),
+        }
+    }
+
+    fn from_json(json: SyncAggregateJson) -> Result<Self, String> {
+        let sync_committee_bits = field(
+            "sync_committee_bits",
+            decode_hex_fixed(&json.sync_committee_bits, SYNC_COMMITTEE_SIZE.div_ceil(8)),
+        )?;
+        let sync_committee_signature = field(
+            "sync_committee_signature",
+            decode_hex_fixed(&json.sync_committee_signature, 96),
+        )?;
+        convert_proto_sync_aggregate(ProtoSyncAggregate {
+            sync_committee_bits,
+            sync_committee_signature,
+        })
+        .map_err(|e| e.to_string())
+    }
+}
+
+impl<A: BeaconJson, B: BeaconJson> BeaconJson for (A, B) {
+    type Json = (A::Json, B::Json);
+
+    fn to_json(&self) -> Self::Json {
+        (self.0.to_json(), self.1.to_json())
+    }
+
+    fn from_json(json: Self::Json) -> Result<Self, String> {
+        Ok((
+            field("0", A::from_json(json.0))?,
+            field("1", B::from_json(json.1))?,
+        ))
+    }
+}
+
+impl<T: BeaconJson> BeaconJson for Option<T> {
+    type Json = Option<T::Json>;
+
+    fn to_json(&self) -> Self::Json {
+        self.as_ref().map(T::to_json)
+    }
+
+    fn from_json(json: Self::Json) -> Result<Self, String> {
+        json.map(T::from_json).transpose()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct HeightJson {
+    pub revision_number: String,
+    pub revision_height: String,
+}
+
+impl BeaconJson for Height {
+    type Json = HeightJson;
+
+    fn to_json(&self) -> HeightJson {
+        HeightJson {
+            revision_number: self.revision_number().to_string(),
+            revision_height: self.revision_height().to_string(),
+        }
+    }
+
+    fn from_json(json: HeightJson) -> Result<Self, String> {
+        let revision_number = field("revision_number", U64::from_json(json.revision_number))?;
+        let revision_height = field("revision_height", U64::from_json(json.revision_height))?;
+        Height::new(revision_number.0, revision_height.0).map_err(|e| e.to_string())
+    }
+}
+
+/// The timestamp is represented in nanoseconds
+impl BeaconJson for Timestamp {
+    type Json = String;
+
+    fn to_json(&self) -> String {
+        self.nanoseconds().to_string()
+    }
+
+    fn from_json(json: String) -> Result<Self, String> {
+        let nanos = U64::from_json(json)?;
+        Timestamp::from_nanoseconds(nanos.0).map_err(|e| e.to_string())
+    }
+}
+
+fn field<T>(name: &str, res: Result<T, String>) -> Result<T, String> {
+    res.map_err(|e| format!("{}: {}", name, e))
+}
+
+fn encode_hex(bz: &[u8]) -> String {
+    format!("0x{}", hex::encode(bz))
+}
+
+fn decode_hex(s: &str) -> Result<Vec<u8>, String> {
+    let hex_str = s
+        .strip_prefix("0x")
+        .ok_or_else(|| format!("hex string must start with `0x`: `{}`", s))?;
+    hex::decode(hex_str).map_err(|e| format!("invalid hex string `{}`: {}", s, e))
+}
+
+fn decode_hex_fixed(s: &str, len: usize) -> Result<Vec<u8>, String> {
+    let bz = decode_hex(s)?;
+    if bz.len() != len {
+        return Err(format!(
+            "expected {} bytes but got {} bytes: `{}`",
+            len,
+            bz.len(),
+            s
+        ));
+    }
+    Ok(bz)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::header::Header;
+    use crate::misc::height_from_block_number;
+    use crate::types::{
+        AccountUpdateInfo, ConsensusUpdateInfo, ExecutionUpdateInfo, TrustedSyncCommittee,
+    };
+    use ethereum_consensus::bls::{PublicKey, Signature};
+    use hex_literal::hex;
+    use ssz_rs::Vector;
+
+    const HEADER_JSON: &str = include_str!("../testdata/header.json");
+
+    /// The sync committee size of the fixture, which is smaller than the presets to keep it short
+    const SIZE: usize = 8;
+
+    fn h256(b: u8) -> H256 {
+        H256([b; 32])
+    }
+
+    fn beacon_block_header(slot: u64, proposer_index: u64, b: u8) -> BeaconBlockHeader {
+        BeaconBlockHeader {
+            slot: slot.into(),
+            proposer_index: proposer_index.into(),
+            parent_root: h256(b),
+            state_root: h256(b + 1),
+            body_root: h256(b + 2),
+        }
+    }
+
+    fn fixture_header() -> Header<SIZE> {
+        let pubkey = PublicKey::try_from(hex!("a145063e1b5eda80fa55960296f2c4b2c021f75767318ea2572a9f7abb649010b746754ca7fc2ba57c1156881516a357").to_vec()).unwrap();
+        let sync_committee = SyncCommittee::<SIZE> {
+            pubkeys: Vector::from_iter(vec![pubkey.clone(); SIZE]),
+            aggregate_pubkey: pubkey,
+        };
+        let mut sync_aggregate = SyncAggregate::<SIZE> {
+            sync_committee_signature: Signature::try_from([&[0xc0u8][..], &[0u8; 95][..]].concat())
+                .unwrap(),
+            ..Default::default()
+        };
+        for i in 0..SIZE {
+            sync_aggregate.sync_committee_bits.set(i, true);
+        }
+        Header {
+            trusted_sync_committee: TrustedSyncCommittee {
+                height: height_from_block_number(U64(1)).unwrap(),
+                sync_committee: sync_committee.clone(),
+                is_next: false,
+            },
+            intermediate_consensus_updates: vec![],
+            consensus_update: ConsensusUpdateInfo {
+                attested_header: beacon_block_header(74, 3, 0x11),
+                next_sync_committee: Some((sync_committee, vec![h256(0x21), h256(0x22)])),
+                finalized_header: (
+                    beacon_block_header(72, 5, 0x31),
+                    vec![h256(0x41), h256(0x42)],
+                ),
+                sync_aggregate,
+                signature_slot: 75.into(),
+                finalized_execution_root: h256(0x51),
+                finalized_execution_branch: vec![h256(0x61), h256(0x62)],
+            },
+            execution_update: ExecutionUpdateInfo {
+                state_root: h256(0x71),
+                state_root_branch: vec![h256(0x81), h256(0x82)],
+                block_number: 2.into(),
+                block_number_branch: vec![h256(0x91), h256(0x92)],
+                block_hash: Some(h256(0x93)),
+                block_hash_branch: vec![h256(0x94), h256(0x95)],
+            },
+            account_update: AccountUpdateInfo {
+                account_proof: vec![vec![0xc1, 0x80], vec![0xc2, 0x01, 0x02]],
+                account_storage_root: h256(0xa1),
+            },
+            timestamp: Timestamp::from_nanoseconds(433_000_000_000).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_header_json_fixture() {
+        let header = fixture_header();
+        let expected: serde_json::Value = serde_json::from_str(HEADER_JSON).unwrap();
+        assert_eq!(serde_json::to_value(&header).unwrap(), expected);
+        let decoded: Header<SIZE> = serde_json::from_str(HEADER_JSON).unwrap();
+        assert_eq!(decoded, header);
+
+        // the fixture also covers the JSON of each update type
+        assert_eq!(
+            serde_json::to_value(&header.consensus_update).unwrap(),
+            expected["consensus_update"]
+        );
+        assert_eq!(
+            serde_json::to_value(&header.execution_update).unwrap(),
+            expected["execution_update"]
+        );
+        assert_eq!(
+            serde_json::to_value(&header.account_update).unwrap(),
+            expected["account_update"]
+        );
+    }
+
+    #[test]
+    fn test_malformed_json() {
+        let expected: serde_json::Value = serde_json::from_str(HEADER_JSON).unwrap();
+        let cases: [(&str, serde_json::Value, &str); 7] = [
+            (
+                "/execution_update/state_root",
+                "71".repeat(32).into(),
+                "hex string must start with `0x`",
+            ),
+            (
+                "/execution_update/state_root",
+                format!("0x{}zz", "71".repeat(31)).into(),
+                "invalid hex string",
+            ),
+            (
+                "/execution_update/state_root_branch/1",
+                format!("0x{}", "82".repeat(31)).into(),
+                "[1]: expected 32 bytes but got 31 bytes",
+            ),
+            (
+                "/consensus_update/signature_slot",
+                75.into(),
+                "invalid type",
+            ),
+            (
+                "/consensus_update/attested_header/slot",
+                "0x4a".into(),
+                "slot: invalid decimal string `0x4a`",
+            ),
+            (
+                "/consensus_update/sync_aggregate/sync_committee_bits",
+                "0xffff".into(),
+                "sync_committee_bits: expected 1 bytes but got 2 bytes",
+            ),
+            (
+                "/trusted_sync_committee/sync_committee/pubkeys/3",
+                "0xa145".into(),
+                "pubkeys[3]: expected 48 bytes but got 2 bytes",
+            ),
+        ];
+        for (pointer, value, message) in cases {
+            let mut json = expected.clone();
+            *json.pointer_mut(pointer).unwrap() = value;
+            let res = serde_json::from_value::<Header<SIZE>>(json);
+            assert!(
+                matches!(&res, Err(e) if e.to_string().contains(message)),
+                "{}: {:?}",
+                pointer,
+                res
+            );
+        }
+
+        let mut json = expected;
+        json["trusted_sync_committee"]["sync_committee"]["pubkeys"]
+            .as_array_mut()
+            .unwrap()
+            .pop();
+        let res = serde_json::from_value::<Header<SIZE>>(json);
+        assert!(
+            matches!(&res, Err(e) if e.to_string().contains("expected 8 keys but got 7 keys")),
+            "{:?}",
+            res
+        );
+    }
+}
@@ -1,18 +1,69 @@
 #![allow(clippy::result_large_err)]
 #![cfg_attr(not(test), no_std)]
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
+pub mod address;
+pub mod bls;
+pub mod cache;
 pub mod client_state;
 pub mod commitment;
 pub mod consensus_state;
+pub mod diagnostics;
 pub mod errors;
+pub mod evm_layout;
+#[cfg(feature = "tools")]
+pub mod fixture;
 pub mod header;
+#[cfg(feature = "ibc-next")]
+pub mod ibc_next;
+#[cfg(feature = "tools")]
+pub mod inspect;
+#[cfg(feature = "json")]
+pub mod json;
+mod merkle;
 pub mod misbehaviour;
+pub mod misc;
+pub mod paths;
+pub mod preset;
+#[cfg(feature = "rpc-types")]
+pub mod rpc_types;
+#[cfg(feature = "schema")]
+pub mod schema;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod testing;
+mod trace;
 pub mod types;
 pub mod update;
+pub mod verify;
+#[cfg(feature = "cosmwasm")]
+pub mod wasm;
 pub use ethereum_consensus as consensus;
 pub use ethereum_light_client_verifier as light_client_verifier;
 
+pub use client_state::ETHEREUM_CLIENT_STATE_TYPE_URL;
+pub use consensus_state::ETHEREUM_CONSENSUS_STATE_TYPE_URL;
+pub use header::ETHEREUM_HEADER_TYPE_URL;
+pub use misbehaviour::{
+    ETHEREUM_FINALIZED_HEADER_MISBEHAVIOUR_TYPE_URL,
+    ETHEREUM_NEXT_SYNC_COMMITTEE_MISBEHAVIOUR_TYPE_URL,
+};
+
+/// Sync committee size of the mainnet preset
+pub const MAINNET_SYNC_COMMITTEE_SIZE: usize =
+    ethereum_consensus::preset::mainnet::PRESET.SYNC_COMMITTEE_SIZE;
+/// Sync committee size of the minimal preset
+pub const MINIMAL_SYNC_COMMITTEE_SIZE: usize =
+    ethereum_consensus::preset::minimal::PRESET.SYNC_COMMITTEE_SIZE;
+
+pub type MainnetClientState = client_state::ClientState<MAINNET_SYNC_COMMITTEE_SIZE>;
+pub type MainnetHeader = header::Header<MAINNET_SYNC_COMMITTEE_SIZE>;
+pub type MainnetMisbehaviour = misbehaviour::Misbehaviour<MAINNET_SYNC_COMMITTEE_SIZE>;
+pub type MinimalClientState = client_state::ClientState<MINIMAL_SYNC_COMMITTEE_SIZE>;
+pub type MinimalHeader = header::Header<MINIMAL_SYNC_COMMITTEE_SIZE>;
+pub type MinimalMisbehaviour = misbehaviour::Misbehaviour<MINIMAL_SYNC_COMMITTEE_SIZE>;
+
 mod internal_prelude {
     pub use alloc::boxed::Box;
     pub use alloc::format;
@@ -0,0 +1,122 @@
+//! Merkle branches of SSZ containers
+//!
+//! The upstream verifier checks the branches of the finalized header and the execution payload fields it knows about.
+//! This module checks the branches of the other fields, e.g. the block hash of the execution payload, against a root verified by the upstream verifier.
+use crate::internal_prelude::*;
+use ethereum_consensus::types::H256;
+use sha2::{Digest, Sha256};
+
+/// Returns `true` if `branch` proves `leaf` at the generalized index `gindex` of the tree whose root is `root`
+///
+/// The depth of the tree is given by `gindex`, so a branch of another length is rejected.
+pub(crate) fn is_valid_merkle_branch(leaf: H256, branch: &[H256], gindex: u64, root: H256) -> bool {
+    if gindex == 0 || branch.len() != (63 - gindex.leading_zeros()) as usize {
+        return false;
+    }
+    let mut index = gindex;
+    let node = branch.iter().fold(leaf.0, |node, sibling| {
+        let node = if index % 2 == 0 {
+            hash_pair(&node, &sibling.0)
+        } else {
+            hash_pair(&sibling.0, &node)
+        };
+        index /= 2;
+        node
+    });
+    H256(node) == root
+}
+
+/// Returns the merkle root of `leaves` padded with zero chunks to a power of two, and the branch of each of `indices` from the leaf to the root
+#[cfg(any(test, feature = "tools"))]
+pub(crate) fn merkle_root_and_branches(
+    leaves: &[[u8; 32]],
+    indices: &[usize],
+) -> ([u8; 32], Vec<Vec<H256>>) {
+    let mut layer = leaves.to_vec();
+    layer.resize(leaves.len().next_power_of_two().max(1), [0u8; 32]);
+    let mut indices = indices.to_vec();
+    let mut branches: Vec<Vec<H256>> = indices.iter().map(|_| Vec::new()).collect();
+    while layer.len() > 1 {
+        for (index, branch) in indices.iter_mut().zip(branches.iter_mut()) {
+            branch.push(H256(layer[*index ^ 1]));
+            *index /= 2;
+        }
+        layer = layer
+            .chunks(2)
+            .map(|pair| hash_pair(&pair[0], &pair[1]))
+            .collect();
+    }
+    (layer[0], branches)
+}
+
+pub(crate) fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid_merkle_branch() {
+        let leaves: Vec<[u8; 32]> = (0..13u8).map(|i| [i + 1; 32]).collect();
+        let (root, branches) = merkle_root_and_branches(&leaves, &[2, 12]);
+        let root = H256(root);
+        // 13 leaves make a tree of depth 4, i.e. the leaf at index `i` has the generalized index `16 + i`
+        assert_eq!(branches[0].len(), 4);
+        assert!(is_valid_merkle_branch(
+            H256([3; 32]),
+            &branches[0],
+            18,
+            root
+        ));
+        assert!(is_valid_merkle_branch(
+            H256([13; 32]),
+            &branches[1],
+            28,
+            root
+        ));
+
+        // another leaf, index or root
+        assert!(!is_valid_merkle_branch(
+            H256([4; 32]),
+            &branches[0],
+            18,
+            root
+        ));
+        assert!(!is_valid_merkle_branch(
+            H256([3; 32]),
+            &branches[0],
+            19,
+            root
+        ));
+        assert!(!is_valid_merkle_branch(
+            H256([3; 32]),
+            &branches[0],
+            18,
+            H256([0; 32])
+        ));
+        // the depth of the generalized index does not match the branch
+        assert!(!is_valid_merkle_branch(
+            H256([3; 32]),
+            &branches[0],
+            34,
+            root
+        ));
+        assert!(!is_valid_merkle_branch(
+            H256([3; 32]),
+            &branches[0][..3],
+            10,
+            root
+        ));
+        assert!(!is_valid_merkle_branch(H256([3; 32]), &[], 0, root));
+
+        // an inner node is proven at its own generalized index, but not at the index of a leaf
+        let inner = H256(hash_pair(&leaves[12], &[0; 32]));
+        assert!(is_valid_merkle_branch(inner, &branches[1][1..], 14, root));
+        assert!(!is_valid_merkle_branch(inner, &branches[1][1..], 28, root));
+    }
+}
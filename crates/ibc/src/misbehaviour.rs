@@ -1,16 +1,24 @@
+use crate::client_state::ClientState;
+use crate::header::validate_consensus_update_basic;
 use crate::{
     errors::Error,
     types::{
-        convert_consensus_update_to_proto, convert_proto_to_consensus_update, ConsensusUpdateInfo,
-        TrustedSyncCommittee,
+        convert_consensus_update_to_proto, convert_proto_to_consensus_update, decode_required,
+        ConsensusUpdateInfo, TrustedSyncCommittee,
     },
 };
 use alloc::string::ToString;
-use bytes::Buf;
+use core::cmp::min;
 use core::str::FromStr;
+use ethereum_consensus::beacon::{Root, Slot};
+use ethereum_consensus::compute::hash_tree_root;
+use ethereum_consensus::sync_protocol::SyncCommitteePeriod;
+use ethereum_consensus::types::H256;
+use ethereum_ibc_proto::any::AnyMessage;
 use ethereum_ibc_proto::ibc::lightclients::ethereum::v1::{
     FinalizedHeaderMisbehaviour as RawFinalizedHeaderMisbehaviour,
     NextSyncCommitteeMisbehaviour as RawNextSyncCommitteeMisbehaviour,
+    TrustedSyncCommittee as ProtoTrustedSyncCommittee,
 };
 use ethereum_light_client_verifier::misbehaviour::{
     FinalizedHeaderMisbehaviour, Misbehaviour as MisbehaviourData, NextSyncCommitteeMisbehaviour,
@@ -23,27 +31,238 @@ use ibc_proto::google::protobuf::Any as IBCAny;
 use ibc_proto::protobuf::Protobuf;
 use prost::Message;
 use serde::{Deserialize, Serialize};
+use tiny_keccak::{Hasher, Keccak};
 
 pub const ETHEREUM_FINALIZED_HEADER_MISBEHAVIOUR_TYPE_URL: &str =
-    "/ibc.lightclients.ethereum.v1.FinalizedHeaderMisbehaviour";
+    RawFinalizedHeaderMisbehaviour::TYPE_URL;
 pub const ETHEREUM_NEXT_SYNC_COMMITTEE_MISBEHAVIOUR_TYPE_URL: &str =
-    "/ibc.lightclients.ethereum.v1.NextSyncCommitteeMisbehaviour";
+    RawNextSyncCommitteeMisbehaviour::TYPE_URL;
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Misbehaviour<const SYNC_COMMITTEE_SIZE: usize> {
     /// The client identifier
     pub client_id: ClientId,
-    /// The sync committee related to the misbehaviour
-    pub trusted_sync_committee: TrustedSyncCommittee<SYNC_COMMITTEE_SIZE>,
+    /// The sync committee used to verify the first consensus update
+    pub trusted_sync_committee_1: TrustedSyncCommittee<SYNC_COMMITTEE_SIZE>,
+    /// The sync committee used to verify the second consensus update
+    pub trusted_sync_committee_2: TrustedSyncCommittee<SYNC_COMMITTEE_SIZE>,
     /// The misbehaviour data
     pub data: MisbehaviourData<SYNC_COMMITTEE_SIZE, ConsensusUpdateInfo<SYNC_COMMITTEE_SIZE>>,
 }
 
 impl<const SYNC_COMMITTEE_SIZE: usize> Misbehaviour<SYNC_COMMITTEE_SIZE> {
+    /// Returns the trusted sync committees corresponding to the two consensus updates of the misbehaviour
+    pub fn trusted_sync_committees(
+        &self,
+    ) -> (
+        &TrustedSyncCommittee<SYNC_COMMITTEE_SIZE>,
+        &TrustedSyncCommittee<SYNC_COMMITTEE_SIZE>,
+    ) {
+        (
+            &self.trusted_sync_committee_1,
+            &self.trusted_sync_committee_2,
+        )
+    }
+
+    /// Returns the two consensus updates of the misbehaviour
+    pub fn consensus_updates(
+        &self,
+    ) -> (
+        &ConsensusUpdateInfo<SYNC_COMMITTEE_SIZE>,
+        &ConsensusUpdateInfo<SYNC_COMMITTEE_SIZE>,
+    ) {
+        match &self.data {
+            MisbehaviourData::FinalizedHeader(data) => {
+                (&data.consensus_update_1, &data.consensus_update_2)
+            }
+            MisbehaviourData::NextSyncCommittee(data) => {
+                (&data.consensus_update_1, &data.consensus_update_2)
+            }
+        }
+    }
+
+    /// Stateless validation of the structure of the misbehaviour
+    ///
+    /// The following conditions are checked:
+    /// - `client_id` is a valid identifier
+    /// - `trusted_sync_committee_1` and `trusted_sync_committee_2` are valid
+    /// - each consensus update is structurally valid as checked by `Header::validate_basic`
+    /// - the two consensus updates are not identical
+    pub fn validate_basic(&self) -> Result<(), Error> {
+        ClientId::from_str(self.client_id.as_str())?;
+        self.trusted_sync_committee_1.validate()?;
+        self.trusted_sync_committee_2.validate()?;
+        let (update_1, update_2) = self.consensus_updates();
+        validate_consensus_update_basic(update_1)?;
+        validate_consensus_update_basic(update_2)?;
+        if update_1 == update_2 {
+            return Err(Error::IdenticalConsensusUpdatesInMisbehaviour);
+        }
+        Ok(())
+    }
+
+    /// Stateless validation of the misbehaviour
+    ///
+    /// In addition to `validate_basic`, the following conditions are checked:
+    /// - For `FinalizedHeader`, the finalized headers of the two updates must have the same slot and be different.
+    /// - For `NextSyncCommittee`, both updates must contain the next sync committee and their aggregate public keys must be different.
+    ///
+    /// The sync committee periods of the attested headers are checked by `ClientState::verify_misbehaviour`.
     pub fn validate(&self) -> Result<(), Error> {
-        self.trusted_sync_committee.validate()?;
+        self.validate_basic()?;
+        match &self.data {
+            MisbehaviourData::FinalizedHeader(data) => {
+                let header_1 = &data.consensus_update_1.finalized_header.0;
+                let header_2 = &data.consensus_update_2.finalized_header.0;
+                if header_1.slot != header_2.slot {
+                    return Err(Error::FinalizedHeaderMisbehaviourSlotMismatch {
+                        slot_1: header_1.slot,
+                        slot_2: header_2.slot,
+                    });
+                }
+                if header_1 == header_2 {
+                    return Err(Error::IdenticalFinalizedHeadersInMisbehaviour(
+                        header_1.slot,
+                    ));
+                }
+            }
+            MisbehaviourData::NextSyncCommittee(data) => {
+                let committee_1 = match &data.consensus_update_1.next_sync_committee {
+                    Some((committee, _)) => committee,
+                    None => return Err(Error::NoNextSyncCommitteeInMisbehaviour { index: 0 }),
+                };
+                let committee_2 = match &data.consensus_update_2.next_sync_committee {
+                    Some((committee, _)) => committee,
+                    None => return Err(Error::NoNextSyncCommitteeInMisbehaviour { index: 1 }),
+                };
+                if committee_1.aggregate_pubkey == committee_2.aggregate_pubkey {
+                    return Err(Error::IdenticalNextSyncCommitteesInMisbehaviour(
+                        committee_1.aggregate_pubkey.clone(),
+                    ));
+                }
+            }
+        }
         Ok(())
     }
+
+    /// Returns the length of the proto-encoded misbehaviour without encoding it
+    ///
+    /// The length is dominated by the two trusted sync committees and, for `NextSyncCommittee`, by the next sync committees of the updates,
+    /// each of which takes 50 bytes per public key. The merkle branches are not bounded until the misbehaviour is verified.
+    pub fn encoded_len(&self) -> usize {
+        match self.data {
+            MisbehaviourData::FinalizedHeader(_) => {
+                RawFinalizedHeaderMisbehaviour::from(self.clone()).encoded_len()
+            }
+            MisbehaviourData::NextSyncCommittee(_) => {
+                RawNextSyncCommitteeMisbehaviour::from(self.clone()).encoded_len()
+            }
+        }
+    }
+
+    /// Returns the summary of the misbehaviour to be recorded when the client is frozen by it
+    ///
+    /// The sync committee periods are computed with the parameters of `client_state`.
+    /// For `NextSyncCommittee`, both updates must contain the next sync committee as `validate` requires.
+    pub fn evidence(
+        &self,
+        client_state: &ClientState<SYNC_COMMITTEE_SIZE>,
+    ) -> Result<MisbehaviourEvidence, Error> {
+        let (update_1, update_2) = self.consensus_updates();
+        let (kind, conflicting_roots) = match &self.data {
+            MisbehaviourData::FinalizedHeader(_) => (
+                MisbehaviourKind::FinalizedHeader,
+                (
+                    hash_tree_root(update_1.finalized_header.0.clone())?,
+                    hash_tree_root(update_2.finalized_header.0.clone())?,
+                ),
+            ),
+            MisbehaviourData::NextSyncCommittee(_) => {
+                let mut roots = [Root::default(); 2];
+                for (index, update) in [update_1, update_2].into_iter().enumerate() {
+                    let (committee, _) = update
+                        .next_sync_committee
+                        .as_ref()
+                        .ok_or(Error::NoNextSyncCommitteeInMisbehaviour { index })?;
+                    roots[index] = hash_tree_root(committee.clone())?;
+                }
+                (MisbehaviourKind::NextSyncCommittee, (roots[0], roots[1]))
+            }
+        };
+        Ok(MisbehaviourEvidence {
+            client_id: self.client_id.clone(),
+            kind,
+            finalized_slots: (
+                update_1.finalized_header.0.slot,
+                update_2.finalized_header.0.slot,
+            ),
+            attested_periods: (
+                client_state.compute_period_at_slot(update_1.attested_header.slot),
+                client_state.compute_period_at_slot(update_2.attested_header.slot),
+            ),
+            conflicting_roots,
+            signature_slots: (update_1.signature_slot, update_2.signature_slot),
+        })
+    }
+}
+
+/// The kind of a misbehaviour, see `MisbehaviourData`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MisbehaviourKind {
+    /// two different finalized headers at the same slot
+    FinalizedHeader = 1,
+    /// two different next sync committees in the same sync committee period
+    NextSyncCommittee = 2,
+}
+
+/// The summary of a misbehaviour that froze the client, which the host can keep to justify the freeze
+///
+/// Each pair is in the order of the consensus updates of the misbehaviour.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MisbehaviourEvidence {
+    /// The client identifier submitted with the misbehaviour
+    pub client_id: ClientId,
+    /// The kind of the misbehaviour
+    pub kind: MisbehaviourKind,
+    /// The slots of the finalized headers
+    pub finalized_slots: (Slot, Slot),
+    /// The sync committee periods of the attested headers
+    pub attested_periods: (SyncCommitteePeriod, SyncCommitteePeriod),
+    /// The hash tree roots of the finalized headers for `FinalizedHeader`, or of the next sync committees for `NextSyncCommittee`
+    pub conflicting_roots: (Root, Root),
+    /// The signature slots of the consensus updates
+    pub signature_slots: (Slot, Slot),
+}
+
+impl MisbehaviourEvidence {
+    /// Returns the keccak256 digest of the evidence, which is stored in the frozen client state
+    ///
+    /// The digest does not depend on how the misbehaviour was encoded since it is computed over the following fixed layout:
+    /// `kind (1 byte) || len(client_id) (8 bytes) || client_id || finalized_slots || attested_periods || conflicting_roots || signature_slots`,
+    /// where the lengths, slots and periods are big-endian `u64`s and the roots are 32 bytes.
+    pub fn digest(&self) -> H256 {
+        let mut hasher = Keccak::v256();
+        hasher.update(&[self.kind as u8]);
+        let client_id = self.client_id.as_str().as_bytes();
+        hasher.update(&(client_id.len() as u64).to_be_bytes());
+        hasher.update(client_id);
+        for value in [
+            self.finalized_slots.0,
+            self.finalized_slots.1,
+            self.attested_periods.0,
+            self.attested_periods.1,
+        ] {
+            hasher.update(&value.0.to_be_bytes());
+        }
+        hasher.update(self.conflicting_roots.0.as_bytes());
+        hasher.update(self.conflicting_roots.1.as_bytes());
+        for slot in [self.signature_slots.0, self.signature_slots.1] {
+            hasher.update(&slot.0.to_be_bytes());
+        }
+        let mut output = [0u8; 32];
+        hasher.finalize(&mut output);
+        H256(output)
+    }
 }
 
 impl<const SYNC_COMMITTEE_SIZE: usize> Ics02Misbehaviour for Misbehaviour<SYNC_COMMITTEE_SIZE> {
@@ -51,8 +270,12 @@ impl<const SYNC_COMMITTEE_SIZE: usize> Ics02Misbehaviour for Misbehaviour<SYNC_C
         &self.client_id
     }
 
+    /// Returns the lower of the two trusted heights
     fn height(&self) -> ibc::Height {
-        self.trusted_sync_committee.height
+        min(
+            self.trusted_sync_committee_1.height,
+            self.trusted_sync_committee_2.height,
+        )
     }
 }
 
@@ -71,22 +294,32 @@ impl<const SYNC_COMMITTEE_SIZE: usize> TryFrom<RawFinalizedHeaderMisbehaviour>
 {
     type Error = Error;
     fn try_from(value: RawFinalizedHeaderMisbehaviour) -> Result<Self, Self::Error> {
+        if value.consensus_update_1 == value.consensus_update_2 {
+            return Err(Error::IdenticalConsensusUpdatesInMisbehaviour);
+        }
+        let (trusted_sync_committee_1, trusted_sync_committee_2) =
+            convert_proto_to_trusted_sync_committees(
+                "FinalizedHeaderMisbehaviour",
+                value.trusted_sync_committee,
+                value.trusted_sync_committee_1,
+                value.trusted_sync_committee_2,
+            )?;
         Ok(Self {
             client_id: ClientId::from_str(&value.client_id)?,
-            trusted_sync_committee: value
-                .trusted_sync_committee
-                .ok_or(Error::proto_missing("trusted_sync_committee"))?
-                .try_into()?,
+            trusted_sync_committee_1,
+            trusted_sync_committee_2,
             data: MisbehaviourData::FinalizedHeader(FinalizedHeaderMisbehaviour {
-                consensus_update_1: convert_proto_to_consensus_update(
-                    value
-                        .consensus_update_1
-                        .ok_or(Error::proto_missing("consensus_update_1"))?,
+                consensus_update_1: decode_required(
+                    "FinalizedHeaderMisbehaviour",
+                    "consensus_update_1",
+                    value.consensus_update_1,
+                    convert_proto_to_consensus_update,
                 )?,
-                consensus_update_2: convert_proto_to_consensus_update(
-                    value
-                        .consensus_update_2
-                        .ok_or(Error::proto_missing("consensus_update_2"))?,
+                consensus_update_2: decode_required(
+                    "FinalizedHeaderMisbehaviour",
+                    "consensus_update_2",
+                    value.consensus_update_2,
+                    convert_proto_to_consensus_update,
                 )?,
             }),
         })
@@ -98,22 +331,32 @@ impl<const SYNC_COMMITTEE_SIZE: usize> TryFrom<RawNextSyncCommitteeMisbehaviour>
 {
     type Error = Error;
     fn try_from(value: RawNextSyncCommitteeMisbehaviour) -> Result<Self, Self::Error> {
+        if value.consensus_update_1 == value.consensus_update_2 {
+            return Err(Error::IdenticalConsensusUpdatesInMisbehaviour);
+        }
+        let (trusted_sync_committee_1, trusted_sync_committee_2) =
+            convert_proto_to_trusted_sync_committees(
+                "NextSyncCommitteeMisbehaviour",
+                value.trusted_sync_committee,
+                value.trusted_sync_committee_1,
+                value.trusted_sync_committee_2,
+            )?;
         Ok(Self {
             client_id: ClientId::from_str(&value.client_id)?,
-            trusted_sync_committee: value
-                .trusted_sync_committee
-                .ok_or(Error::proto_missing("trusted_sync_committee"))?
-                .try_into()?,
+            trusted_sync_committee_1,
+            trusted_sync_committee_2,
             data: MisbehaviourData::NextSyncCommittee(NextSyncCommitteeMisbehaviour {
-                consensus_update_1: convert_proto_to_consensus_update(
-                    value
-                        .consensus_update_1
-                        .ok_or(Error::proto_missing("consensus_update_1"))?,
+                consensus_update_1: decode_required(
+                    "NextSyncCommitteeMisbehaviour",
+                    "consensus_update_1",
+                    value.consensus_update_1,
+                    convert_proto_to_consensus_update,
                 )?,
-                consensus_update_2: convert_proto_to_consensus_update(
-                    value
-                        .consensus_update_2
-                        .ok_or(Error::proto_missing("consensus_update_2"))?,
+                consensus_update_2: decode_required(
+                    "NextSyncCommitteeMisbehaviour",
+                    "consensus_update_2",
+                    value.consensus_update_2,
+                    convert_proto_to_consensus_update,
                 )?,
             }),
         })
@@ -130,9 +373,11 @@ impl<const SYNC_COMMITTEE_SIZE: usize> From<Misbehaviour<SYNC_COMMITTEE_SIZE>>
         };
         Self {
             client_id: value.client_id.as_str().to_string(),
-            trusted_sync_committee: Some(value.trusted_sync_committee.into()),
+            trusted_sync_committee: None,
             consensus_update_1: Some(convert_consensus_update_to_proto(data.consensus_update_1)),
             consensus_update_2: Some(convert_consensus_update_to_proto(data.consensus_update_2)),
+            trusted_sync_committee_1: Some(value.trusted_sync_committee_1.into()),
+            trusted_sync_committee_2: Some(value.trusted_sync_committee_2.into()),
         }
     }
 }
@@ -147,9 +392,11 @@ impl<const SYNC_COMMITTEE_SIZE: usize> From<Misbehaviour<SYNC_COMMITTEE_SIZE>>
         };
         Self {
             client_id: value.client_id.as_str().to_string(),
-            trusted_sync_committee: Some(value.trusted_sync_committee.into()),
+            trusted_sync_committee: None,
             consensus_update_1: Some(convert_consensus_update_to_proto(data.consensus_update_1)),
             consensus_update_2: Some(convert_consensus_update_to_proto(data.consensus_update_2)),
+            trusted_sync_committee_1: Some(value.trusted_sync_committee_1.into()),
+            trusted_sync_committee_2: Some(value.trusted_sync_committee_2.into()),
         }
     }
 }
@@ -169,9 +416,14 @@ impl<const SYNC_COMMITTEE_SIZE: usize> TryFrom<IBCAny> for Misbehaviour<SYNC_COM
             ETHEREUM_NEXT_SYNC_COMMITTEE_MISBEHAVIOUR_TYPE_URL => {
                 decode_next_sync_committee_misbehaviour(raw.value.deref()).map_err(Into::into)
             }
-            _ => Err(ClientError::UnknownMisbehaviourType {
-                misbehaviour_type: raw.type_url,
-            }),
+            _ => Err(Error::UnexpectedTypeUrl {
+                expected: &[
+                    ETHEREUM_FINALIZED_HEADER_MISBEHAVIOUR_TYPE_URL,
+                    ETHEREUM_NEXT_SYNC_COMMITTEE_MISBEHAVIOUR_TYPE_URL,
+                ],
+                got: raw.type_url,
+            }
+            .into()),
         }
     }
 }
@@ -193,27 +445,82 @@ impl<const SYNC_COMMITTEE_SIZE: usize> From<Misbehaviour<SYNC_COMMITTEE_SIZE>> f
     }
 }
 
-fn decode_finalized_header_misbehaviour<const SYNC_COMMITTEE_SIZE: usize, B: Buf>(
-    buf: B,
+/// Converts the trusted sync committees of a misbehaviour
+///
+/// If neither `trusted_sync_committee_1` nor `trusted_sync_committee_2` is set, the deprecated `trusted_sync_committee` is used for both updates.
+/// `message` is the name of the misbehaviour message, which is reported in missing field errors.
+fn convert_proto_to_trusted_sync_committees<const SYNC_COMMITTEE_SIZE: usize>(
+    message: &str,
+    trusted_sync_committee: Option<ProtoTrustedSyncCommittee>,
+    trusted_sync_committee_1: Option<ProtoTrustedSyncCommittee>,
+    trusted_sync_committee_2: Option<ProtoTrustedSyncCommittee>,
+) -> Result<
+    (
+        TrustedSyncCommittee<SYNC_COMMITTEE_SIZE>,
+        TrustedSyncCommittee<SYNC_COMMITTEE_SIZE>,
+    ),
+    Error,
+> {
+    if trusted_sync_committee_1.is_none() && trusted_sync_committee_2.is_none() {
+        let trusted_sync_committee: TrustedSyncCommittee<SYNC_COMMITTEE_SIZE> =
+            trusted_sync_committee
+                .ok_or(Error::proto_missing(message, "trusted_sync_committee_1"))?
+                .try_into()
+                .map_err(|e| Error::decode_error("trusted_sync_committee", e))?;
+        return Ok((trusted_sync_committee.clone(), trusted_sync_committee));
+    }
+    if trusted_sync_committee.is_some() {
+        return Err(Error::AmbiguousTrustedSyncCommitteesInMisbehaviour);
+    }
+    Ok((
+        decode_required(
+            message,
+            "trusted_sync_committee_1",
+            trusted_sync_committee_1,
+            TrustedSyncCommittee::try_from,
+        )?,
+        decode_required(
+            message,
+            "trusted_sync_committee_2",
+            trusted_sync_committee_2,
+            TrustedSyncCommittee::try_from,
+        )?,
+    ))
+}
+
+/// The payload must be the canonical encoding of the misbehaviour, i.e. it must not contain unknown fields or trailing bytes.
+fn decode_finalized_header_misbehaviour<const SYNC_COMMITTEE_SIZE: usize>(
+    bz: &[u8],
 ) -> Result<Misbehaviour<SYNC_COMMITTEE_SIZE>, Error> {
-    RawFinalizedHeaderMisbehaviour::decode(buf)
-        .map_err(Error::Decode)?
-        .try_into()
+    let raw = RawFinalizedHeaderMisbehaviour::decode(bz).map_err(Error::Decode)?;
+    if raw.encode_to_vec() != bz {
+        return Err(Error::UnexpectedAnyPayload {
+            type_url: ETHEREUM_FINALIZED_HEADER_MISBEHAVIOUR_TYPE_URL,
+        });
+    }
+    raw.try_into()
 }
 
-fn decode_next_sync_committee_misbehaviour<const SYNC_COMMITTEE_SIZE: usize, B: Buf>(
-    buf: B,
+/// The payload must be the canonical encoding of the misbehaviour, i.e. it must not contain unknown fields or trailing bytes.
+fn decode_next_sync_committee_misbehaviour<const SYNC_COMMITTEE_SIZE: usize>(
+    bz: &[u8],
 ) -> Result<Misbehaviour<SYNC_COMMITTEE_SIZE>, Error> {
-    RawNextSyncCommitteeMisbehaviour::decode(buf)
-        .map_err(Error::Decode)?
-        .try_into()
+    let raw = RawNextSyncCommitteeMisbehaviour::decode(bz).map_err(Error::Decode)?;
+    if raw.encode_to_vec() != bz {
+        return Err(Error::UnexpectedAnyPayload {
+            type_url: ETHEREUM_NEXT_SYNC_COMMITTEE_MISBEHAVIOUR_TYPE_URL,
+        });
+    }
+    raw.try_into()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::eth_client_type;
+    use crate::misc::height_from_block_number;
     use ethereum_consensus::context::ChainContext;
+    use ethereum_consensus::sync_protocol::SyncCommittee;
     use ethereum_consensus::{config, types::U64};
     use ethereum_light_client_verifier::{
         consensus::test_utils::{gen_light_client_update_with_params, MockSyncCommitteeManager},
@@ -222,6 +529,175 @@ mod tests {
     };
     use std::time::SystemTime;
 
+    #[test]
+    fn test_misbehaviour_evidence() {
+        let (client_state, _, misbehaviour, _) = crate::verify::tests::misbehaviour_scenario();
+        let (update_1, update_2) = misbehaviour.consensus_updates();
+        let evidence = misbehaviour.evidence(&client_state).unwrap();
+        assert_eq!(evidence.client_id, misbehaviour.client_id);
+        assert_eq!(evidence.kind, MisbehaviourKind::FinalizedHeader);
+        assert_eq!(
+            evidence.finalized_slots,
+            (
+                update_1.finalized_header.0.slot,
+                update_2.finalized_header.0.slot
+            )
+        );
+        assert_eq!(
+            evidence.attested_periods,
+            (
+                client_state.compute_period_at_slot(update_1.attested_header.slot),
+                client_state.compute_period_at_slot(update_2.attested_header.slot)
+            )
+        );
+        assert_eq!(
+            evidence.conflicting_roots,
+            (
+                hash_tree_root(update_1.finalized_header.0.clone()).unwrap(),
+                hash_tree_root(update_2.finalized_header.0.clone()).unwrap()
+            )
+        );
+        assert_ne!(evidence.conflicting_roots.0, evidence.conflicting_roots.1);
+        assert_eq!(
+            evidence.signature_slots,
+            (update_1.signature_slot, update_2.signature_slot)
+        );
+
+        // the evidence and its digest do not depend on the encoding of the misbehaviour
+        let decoded = Misbehaviour::<32>::try_from(IBCAny::from(misbehaviour.clone())).unwrap();
+        assert_eq!(decoded.evidence(&client_state).unwrap(), evidence);
+
+        let committee_1 = misbehaviour.trusted_sync_committee_1.sync_committee.clone();
+        let committee_2 = SyncCommittee::<32>::default();
+        let mut update_1 = update_1.clone();
+        update_1.next_sync_committee = Some((committee_1.clone(), vec![Default::default(); 5]));
+        let mut update_2 = update_2.clone();
+        update_2.next_sync_committee = None;
+        let mut misbehaviour = Misbehaviour {
+            data: MisbehaviourData::NextSyncCommittee(NextSyncCommitteeMisbehaviour {
+                consensus_update_1: update_1,
+                consensus_update_2: update_2,
+            }),
+            ..misbehaviour
+        };
+        let res = misbehaviour.evidence(&client_state);
+        assert!(
+            matches!(
+                res,
+                Err(Error::NoNextSyncCommitteeInMisbehaviour { index: 1 })
+            ),
+            "{:?}",
+            res
+        );
+        if let MisbehaviourData::NextSyncCommittee(data) = &mut misbehaviour.data {
+            data.consensus_update_2.next_sync_committee =
+                Some((committee_2.clone(), vec![Default::default(); 5]));
+        }
+        let evidence = misbehaviour.evidence(&client_state).unwrap();
+        assert_eq!(evidence.kind, MisbehaviourKind::NextSyncCommittee);
+        assert_eq!(
+            evidence.conflicting_roots,
+            (
+                hash_tree_root(committee_1).unwrap(),
+                hash_tree_root(committee_2).unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn test_misbehaviour_evidence_digest() {
+        let evidence = MisbehaviourEvidence {
+            client_id: ClientId::new(eth_client_type(), 0).unwrap(),
+            kind: MisbehaviourKind::FinalizedHeader,
+            finalized_slots: (U64(75), U64(75)),
+            attested_periods: (U64(1), U64(1)),
+            conflicting_roots: (H256([1u8; 32]), H256([2u8; 32])),
+            signature_slots: (U64(76), U64(77)),
+        };
+        // the digest is stable across releases since frozen client states store it
+        assert_eq!(
+            evidence.digest(),
+            H256(hex_literal::hex!(
+                "bc94e27f79368c88783b178aecb1f32dd926959097e2adee67b90beefc0e5b7c"
+            ))
+        );
+
+        // every field is committed to by the digest
+        for modified in [
+            MisbehaviourEvidence {
+                kind: MisbehaviourKind::NextSyncCommittee,
+                ..evidence.clone()
+            },
+            MisbehaviourEvidence {
+                client_id: ClientId::new(eth_client_type(), 1).unwrap(),
+                ..evidence.clone()
+            },
+            MisbehaviourEvidence {
+                finalized_slots: (U64(75), U64(76)),
+                ..evidence.clone()
+            },
+            MisbehaviourEvidence {
+                attested_periods: (U64(1), U64(2)),
+                ..evidence.clone()
+            },
+            MisbehaviourEvidence {
+                conflicting_roots: (H256([2u8; 32]), H256([1u8; 32])),
+                ..evidence.clone()
+            },
+            MisbehaviourEvidence {
+                signature_slots: (U64(77), U64(76)),
+                ..evidence.clone()
+            },
+        ] {
+            assert_ne!(modified.digest(), evidence.digest(), "{:?}", modified);
+        }
+    }
+
+    #[test]
+    fn test_misbehaviour_validate_basic_slot_order() {
+        let (_, _, misbehaviour, _) = crate::verify::tests::misbehaviour_scenario();
+        assert!(misbehaviour.validate_basic().is_ok());
+        for index in 0..2 {
+            let mut m = misbehaviour.clone();
+            let update = match &mut m.data {
+                MisbehaviourData::FinalizedHeader(data) if index == 0 => {
+                    &mut data.consensus_update_1
+                }
+                MisbehaviourData::FinalizedHeader(data) => &mut data.consensus_update_2,
+                _ => unreachable!(),
+            };
+            update.signature_slot = update.attested_header.slot;
+            let res = m.validate_basic();
+            assert!(
+                matches!(res, Err(Error::AttestedSlotNotBeforeSignatureSlot { .. })),
+                "{:?}",
+                res
+            );
+        }
+    }
+
+    #[test]
+    fn test_misbehaviour_encoded_len() {
+        let (_, _, misbehaviour, _) = crate::verify::tests::misbehaviour_scenario();
+        assert_eq!(
+            misbehaviour.encoded_len(),
+            IBCAny::from(misbehaviour.clone()).value.len()
+        );
+
+        let (consensus_update_1, consensus_update_2) = misbehaviour.consensus_updates();
+        let misbehaviour = Misbehaviour {
+            data: MisbehaviourData::NextSyncCommittee(NextSyncCommitteeMisbehaviour {
+                consensus_update_1: consensus_update_1.clone(),
+                consensus_update_2: consensus_update_2.clone(),
+            }),
+            ..misbehaviour.clone()
+        };
+        assert_eq!(
+            misbehaviour.encoded_len(),
+            IBCAny::from(misbehaviour.clone()).value.len()
+        );
+    }
+
     #[test]
     fn test_mibehaviour_conversion() {
         let scm = MockSyncCommitteeManager::<32>::new(1, 4);
@@ -271,13 +747,15 @@ mod tests {
         );
         let update_1 = to_consensus_update_info(update_1);
         let update_2 = to_consensus_update_info(update_2);
+        let trusted_sync_committee = TrustedSyncCommittee {
+            height: height_from_block_number(U64(1)).unwrap(),
+            sync_committee: current_sync_committee.to_committee().clone(),
+            is_next: true,
+        };
         let misbehaviour = Misbehaviour {
             client_id: ClientId::new(eth_client_type(), 0).unwrap(),
-            trusted_sync_committee: TrustedSyncCommittee {
-                height: ibc::Height::new(1, 1).unwrap(),
-                sync_committee: current_sync_committee.to_committee().clone(),
-                is_next: true,
-            },
+            trusted_sync_committee_1: trusted_sync_committee.clone(),
+            trusted_sync_committee_2: trusted_sync_committee.clone(),
             data: MisbehaviourData::NextSyncCommittee(NextSyncCommitteeMisbehaviour {
                 consensus_update_1: update_1.clone(),
                 consensus_update_2: update_2.clone(),
@@ -304,11 +782,8 @@ mod tests {
         let update_3 = to_consensus_update_info(update_3);
         let misbehaviour = Misbehaviour {
             client_id: ClientId::new(eth_client_type(), 0).unwrap(),
-            trusted_sync_committee: TrustedSyncCommittee {
-                height: ibc::Height::new(1, 1).unwrap(),
-                sync_committee: current_sync_committee.to_committee().clone(),
-                is_next: true,
-            },
+            trusted_sync_committee_1: trusted_sync_committee.clone(),
+            trusted_sync_committee_2: trusted_sync_committee.clone(),
             data: MisbehaviourData::FinalizedHeader(FinalizedHeaderMisbehaviour {
                 consensus_update_1: update_1.clone(),
                 consensus_update_2: update_3.clone(),
@@ -317,6 +792,166 @@ mod tests {
         let any = IBCAny::from(misbehaviour.clone());
         let decoded = Misbehaviour::<32>::try_from(any).unwrap();
         assert_eq!(misbehaviour, decoded);
+
+        // round trip through proto bytes
+        let bz = Protobuf::<RawFinalizedHeaderMisbehaviour>::encode_vec(&misbehaviour).unwrap();
+        let decoded = decode_finalized_header_misbehaviour::<32>(&bz).unwrap();
+        assert_eq!(misbehaviour, decoded);
+        let res = decode_next_sync_committee_misbehaviour::<32>(&bz);
+        assert!(res.is_err());
+
+        // missing fields
+        let raw = RawFinalizedHeaderMisbehaviour::from(misbehaviour.clone());
+        for (field, raw) in [
+            (
+                "trusted_sync_committee_1",
+                RawFinalizedHeaderMisbehaviour {
+                    trusted_sync_committee_1: None,
+                    trusted_sync_committee_2: None,
+                    ..raw.clone()
+                },
+            ),
+            (
+                "trusted_sync_committee_1",
+                RawFinalizedHeaderMisbehaviour {
+                    trusted_sync_committee_1: None,
+                    ..raw.clone()
+                },
+            ),
+            (
+                "trusted_sync_committee_2",
+                RawFinalizedHeaderMisbehaviour {
+                    trusted_sync_committee_2: None,
+                    ..raw.clone()
+                },
+            ),
+            (
+                "consensus_update_1",
+                RawFinalizedHeaderMisbehaviour {
+                    consensus_update_1: None,
+                    ..raw.clone()
+                },
+            ),
+            (
+                "consensus_update_2",
+                RawFinalizedHeaderMisbehaviour {
+                    consensus_update_2: None,
+                    ..raw.clone()
+                },
+            ),
+        ] {
+            let res = Misbehaviour::<32>::try_from(raw);
+            assert_eq!(
+                res.unwrap_err().to_string(),
+                format!(
+                    "proto missing field error: `FinalizedHeaderMisbehaviour.{}`",
+                    field
+                )
+            );
+        }
+        let res = Misbehaviour::<32>::try_from(RawFinalizedHeaderMisbehaviour {
+            client_id: "".to_string(),
+            ..raw.clone()
+        });
+        assert!(res.is_err());
+
+        // identical updates
+        let res = Misbehaviour::<32>::try_from(RawFinalizedHeaderMisbehaviour {
+            consensus_update_2: raw.consensus_update_1.clone(),
+            ..raw.clone()
+        });
+        assert!(
+            matches!(res, Err(Error::IdenticalConsensusUpdatesInMisbehaviour)),
+            "{:?}",
+            res
+        );
+        let res = Misbehaviour::<32>::try_from(RawNextSyncCommitteeMisbehaviour {
+            client_id: raw.client_id.clone(),
+            trusted_sync_committee: None,
+            consensus_update_1: raw.consensus_update_1.clone(),
+            consensus_update_2: raw.consensus_update_1.clone(),
+            trusted_sync_committee_1: raw.trusted_sync_committee_1.clone(),
+            trusted_sync_committee_2: raw.trusted_sync_committee_2.clone(),
+        });
+        assert!(
+            matches!(res, Err(Error::IdenticalConsensusUpdatesInMisbehaviour)),
+            "{:?}",
+            res
+        );
+
+        // the deprecated single trusted sync committee is used for both updates
+        let legacy = RawFinalizedHeaderMisbehaviour {
+            trusted_sync_committee: raw.trusted_sync_committee_1.clone(),
+            trusted_sync_committee_1: None,
+            trusted_sync_committee_2: None,
+            ..raw.clone()
+        };
+        let decoded = decode_finalized_header_misbehaviour::<32>(&legacy.encode_to_vec()).unwrap();
+        assert_eq!(misbehaviour, decoded);
+        let res = Misbehaviour::<32>::try_from(RawFinalizedHeaderMisbehaviour {
+            trusted_sync_committee: raw.trusted_sync_committee_1.clone(),
+            ..raw.clone()
+        });
+        assert!(
+            matches!(
+                res,
+                Err(Error::AmbiguousTrustedSyncCommitteesInMisbehaviour)
+            ),
+            "{:?}",
+            res
+        );
+
+        // the trusted heights of the two updates differ
+        let next_trusted_sync_committee = TrustedSyncCommittee {
+            height: height_from_block_number(U64(2)).unwrap(),
+            sync_committee: scm.get_committee(2).to_committee().clone(),
+            is_next: false,
+        };
+        let misbehaviour_with_different_heights = Misbehaviour {
+            trusted_sync_committee_2: next_trusted_sync_committee.clone(),
+            ..misbehaviour.clone()
+        };
+        let any = IBCAny::from(misbehaviour_with_different_heights.clone());
+        let decoded = Misbehaviour::<32>::try_from(any).unwrap();
+        assert_eq!(misbehaviour_with_different_heights, decoded);
+        assert_eq!(
+            decoded.trusted_sync_committees(),
+            (&trusted_sync_committee, &next_trusted_sync_committee)
+        );
+        assert_eq!(decoded.height(), trusted_sync_committee.height);
+
+        // non-canonical payloads
+        let mut any = IBCAny::from(misbehaviour.clone());
+        any.value.extend_from_slice(&[0x78, 0x01]);
+        let res = Misbehaviour::<32>::try_from(any);
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            ClientError::from(Error::UnexpectedAnyPayload {
+                type_url: ETHEREUM_FINALIZED_HEADER_MISBEHAVIOUR_TYPE_URL
+            })
+            .to_string()
+        );
+
+        // validate_basic
+        assert!(misbehaviour.validate_basic().is_ok());
+        let mut invalid = misbehaviour.clone();
+        if let MisbehaviourData::FinalizedHeader(data) = &mut invalid.data {
+            data.consensus_update_2.finalized_execution_branch.clear();
+        }
+        let res = invalid.validate_basic();
+        assert!(
+            matches!(res, Err(Error::EmptyBranch("finalized_execution_branch"))),
+            "{:?}",
+            res
+        );
+        let mut invalid = misbehaviour;
+        invalid.trusted_sync_committee_2.height = ibc::Height::new(2, 1).unwrap();
+        let res = invalid.validate_basic();
+        assert!(
+            matches!(res, Err(Error::UnexpectedHeightRevisionNumber { .. })),
+            "{:?}",
+            res
+        );
     }
 
     fn to_consensus_update_info<const SYNC_COMMITTEE_SIZE: usize>(
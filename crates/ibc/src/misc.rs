@@ -0,0 +1,137 @@
+use crate::client_state::ETHEREUM_CLIENT_REVISION_NUMBER;
+use crate::errors::Error;
+use ethereum_consensus::beacon::Slot;
+use ethereum_consensus::types::U64;
+use ibc::timestamp::{Timestamp, TimestampOverflowError};
+use ibc::Height;
+
+/// The source of the current time of the host
+///
+/// The client handles every time as `Timestamp`, which is in nanoseconds since the unix epoch.
+/// This includes the current time of the host, the timestamps of the consensus states, and the header timestamps, which are in seconds in the proto and converted on decoding.
+/// A host whose clock is in seconds must convert it to nanoseconds, e.g. with `Timestamp::from_nanoseconds(secs * 1_000_000_000)`.
+/// A raw integer is not a `HostTime`, so a time passed without the conversion fails to compile.
+pub trait HostTime {
+    /// Returns the current time of the host
+    fn now(&self) -> Timestamp;
+}
+
+impl HostTime for Timestamp {
+    fn now(&self) -> Timestamp {
+        *self
+    }
+}
+
+impl<T: HostTime + ?Sized> HostTime for &T {
+    fn now(&self) -> Timestamp {
+        (**self).now()
+    }
+}
+
+/// Returns the timestamp at the given slot, i.e. `genesis_time + slot * seconds_per_slot`
+///
+/// Unlike `ethereum_consensus::compute::compute_timestamp_at_slot`, this returns an error instead of overflowing.
+pub fn compute_timestamp_at_slot(
+    genesis_time: U64,
+    seconds_per_slot: U64,
+    slot: Slot,
+) -> Result<Timestamp, Error> {
+    let nanos = slot
+        .0
+        .checked_mul(seconds_per_slot.0)
+        .and_then(|secs| secs.checked_add(genesis_time.0))
+        .and_then(|secs| secs.checked_mul(1_000_000_000))
+        .ok_or(Error::TimestampOverflowError(
+            TimestampOverflowError::TimestampOverflow,
+        ))?;
+    Ok(Timestamp::from_nanoseconds(nanos)?)
+}
+
+/// Returns the height of the execution block `block_number`
+///
+/// The client uses the execution block number as the revision height with `ETHEREUM_CLIENT_REVISION_NUMBER`.
+/// Returns `Error::ZeroBlockNumberError` for the genesis block since a height must be non-zero.
+pub fn height_from_block_number(block_number: U64) -> Result<Height, Error> {
+    if block_number == U64(0) {
+        return Err(Error::ZeroBlockNumberError);
+    }
+    Ok(Height::new(
+        ETHEREUM_CLIENT_REVISION_NUMBER,
+        block_number.0,
+    )?)
+}
+
+/// Returns the execution block number of `height`, which is the inverse of `height_from_block_number`
+///
+/// Returns `Error::UnexpectedHeightRevisionNumber` if the revision number is not `ETHEREUM_CLIENT_REVISION_NUMBER`.
+pub fn block_number_from_height(height: Height) -> Result<U64, Error> {
+    if height.revision_number() != ETHEREUM_CLIENT_REVISION_NUMBER {
+        return Err(Error::UnexpectedHeightRevisionNumber {
+            expected: ETHEREUM_CLIENT_REVISION_NUMBER,
+            got: height.revision_number(),
+        });
+    }
+    Ok(U64(height.revision_height()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethereum_consensus::preset::mainnet::PRESET;
+    use time::macros::datetime;
+
+    #[test]
+    fn test_compute_timestamp_at_slot() {
+        let genesis_time = U64(1606824023);
+        let timestamp = compute_timestamp_at_slot(genesis_time, PRESET.SECONDS_PER_SLOT, U64(0));
+        assert_eq!(timestamp.unwrap().nanoseconds(), 1606824023 * 1_000_000_000);
+
+        // the first slot of the bellatrix fork on mainnet
+        let timestamp =
+            compute_timestamp_at_slot(genesis_time, PRESET.SECONDS_PER_SLOT, U64(4636672));
+        assert_eq!(
+            timestamp.unwrap().nanoseconds(),
+            datetime!(2022-09-06 11:34:47 UTC).unix_timestamp_nanos() as u64
+        );
+
+        // overflow in the conversion to nanoseconds
+        let res =
+            compute_timestamp_at_slot(genesis_time, PRESET.SECONDS_PER_SLOT, U64(2_000_000_000));
+        assert!(
+            matches!(res, Err(Error::TimestampOverflowError(_))),
+            "{:?}",
+            res
+        );
+        // overflow in the conversion to seconds
+        let res = compute_timestamp_at_slot(genesis_time, PRESET.SECONDS_PER_SLOT, U64(u64::MAX));
+        assert!(
+            matches!(res, Err(Error::TimestampOverflowError(_))),
+            "{:?}",
+            res
+        );
+    }
+
+    #[test]
+    fn test_height_block_number_conversion() {
+        // the genesis block has no height
+        let res = height_from_block_number(U64(0));
+        assert!(matches!(res, Err(Error::ZeroBlockNumberError)), "{:?}", res);
+
+        for block_number in [1, 100, u64::MAX] {
+            let height = height_from_block_number(U64(block_number)).unwrap();
+            assert_eq!(height.revision_number(), ETHEREUM_CLIENT_REVISION_NUMBER);
+            assert_eq!(height.revision_height(), block_number);
+            assert_eq!(block_number_from_height(height).unwrap(), U64(block_number));
+        }
+
+        // a height of a foreign revision
+        let res = block_number_from_height(Height::new(1, 100).unwrap());
+        match res {
+            Err(Error::UnexpectedHeightRevisionNumber { expected, got }) => {
+                assert_eq!(expected, ETHEREUM_CLIENT_REVISION_NUMBER);
+                assert_eq!(got, 1);
+            }
+            res => panic!("unexpected result: {:?}", res),
+        }
+    }
+}
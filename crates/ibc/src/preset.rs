@@ -0,0 +1,335 @@
+//! Chain constants of the public Ethereum networks and the `ClientState` constructors using them.
+//!
+//! Only the chain-specific fields are filled in by the constructors. The deployment-specific fields
+//! such as the IBC contract address are passed by the caller.
+
+use crate::address::Address;
+use crate::client_state::ClientState;
+use crate::commitment::CommitmentValueCodec;
+use crate::MAINNET_SYNC_COMMITTEE_SIZE;
+use core::time::Duration;
+use ethereum_consensus::beacon::{Root, Version};
+use ethereum_consensus::fork::{
+    altair::ALTAIR_FORK_SPEC, bellatrix::BELLATRIX_FORK_SPEC, capella::CAPELLA_FORK_SPEC,
+    deneb::DENEB_FORK_SPEC, ForkParameter, ForkParameters,
+};
+use ethereum_consensus::preset::mainnet::PRESET;
+use ethereum_consensus::types::{H256, U64};
+use ethereum_light_client_verifier::context::Fraction;
+
+/// `genesis_validators_root` of Mainnet
+pub const MAINNET_GENESIS_VALIDATORS_ROOT: Root = H256([
+    0x4b, 0x36, 0x3d, 0xb9, 0x4e, 0x28, 0x61, 0x20, 0xd7, 0x6e, 0xb9, 0x05, 0x34, 0x0f, 0xdd, 0x4e,
+    0x54, 0xbf, 0xe9, 0xf0, 0x6b, 0xf3, 0x3f, 0xf6, 0xcf, 0x5a, 0xd2, 0x7f, 0x51, 0x1b, 0xfe, 0x95,
+]);
+/// `genesis_time` of Mainnet
+pub const MAINNET_GENESIS_TIME: U64 = U64(1606824023);
+
+/// `genesis_validators_root` of Sepolia
+pub const SEPOLIA_GENESIS_VALIDATORS_ROOT: Root = H256([
+    0xd8, 0xea, 0x17, 0x1f, 0x3c, 0x94, 0xae, 0xa2, 0x1e, 0xbc, 0x42, 0xa1, 0xed, 0x61, 0x05, 0x2a,
+    0xcf, 0x3f, 0x92, 0x09, 0xc0, 0x0e, 0x4e, 0xfb, 0xaa, 0xdd, 0xac, 0x09, 0xed, 0x9b, 0x80, 0x78,
+]);
+/// `genesis_time` of Sepolia
+pub const SEPOLIA_GENESIS_TIME: U64 = U64(1655733600);
+
+/// `genesis_validators_root` of Holesky
+pub const HOLESKY_GENESIS_VALIDATORS_ROOT: Root = H256([
+    0x91, 0x43, 0xaa, 0x7c, 0x61, 0x5a, 0x7f, 0x71, 0x15, 0xe2, 0xb6, 0xaa, 0xc3, 0x19, 0xc0, 0x35,
+    0x29, 0xdf, 0x82, 0x42, 0xae, 0x70, 0x5f, 0xba, 0x9d, 0xf3, 0x9b, 0x79, 0xc5, 0x9f, 0xa8, 0xb1,
+]);
+/// `genesis_time` of Holesky
+pub const HOLESKY_GENESIS_TIME: U64 = U64(1695902400);
+
+/// Returns the fork parameters of Mainnet
+pub fn mainnet_fork_parameters() -> ForkParameters {
+    ForkParameters::new(
+        Version([0x00, 0x00, 0x00, 0x00]),
+        [
+            ForkParameter::new(
+                Version([0x01, 0x00, 0x00, 0x00]),
+                U64(74240),
+                ALTAIR_FORK_SPEC,
+            ),
+            ForkParameter::new(
+                Version([0x02, 0x00, 0x00, 0x00]),
+                U64(144896),
+                BELLATRIX_FORK_SPEC,
+            ),
+            ForkParameter::new(
+                Version([0x03, 0x00, 0x00, 0x00]),
+                U64(194048),
+                CAPELLA_FORK_SPEC,
+            ),
+            ForkParameter::new(
+                Version([0x04, 0x00, 0x00, 0x00]),
+                U64(269568),
+                DENEB_FORK_SPEC,
+            ),
+        ]
+        .into(),
+    )
+    .expect("mainnet fork parameters must be valid")
+}
+
+/// Returns the fork parameters of Sepolia
+pub fn sepolia_fork_parameters() -> ForkParameters {
+    ForkParameters::new(
+        Version([0x90, 0x00, 0x00, 0x69]),
+        [
+            ForkParameter::new(Version([0x90, 0x00, 0x00, 0x70]), U64(50), ALTAIR_FORK_SPEC),
+            ForkParameter::new(
+                Version([0x90, 0x00, 0x00, 0x71]),
+                U64(100),
+                BELLATRIX_FORK_SPEC,
+            ),
+            ForkParameter::new(
+                Version([0x90, 0x00, 0x00, 0x72]),
+                U64(56832),
+                CAPELLA_FORK_SPEC,
+            ),
+            ForkParameter::new(
+                Version([0x90, 0x00, 0x00, 0x73]),
+                U64(132608),
+                DENEB_FORK_SPEC,
+            ),
+        ]
+        .into(),
+    )
+    .expect("sepolia fork parameters must be valid")
+}
+
+/// Returns the fork parameters of Holesky
+pub fn holesky_fork_parameters() -> ForkParameters {
+    ForkParameters::new(
+        Version([0x01, 0x01, 0x70, 0x00]),
+        [
+            ForkParameter::new(Version([0x02, 0x01, 0x70, 0x00]), U64(0), ALTAIR_FORK_SPEC),
+            ForkParameter::new(
+                Version([0x03, 0x01, 0x70, 0x00]),
+                U64(0),
+                BELLATRIX_FORK_SPEC,
+            ),
+            ForkParameter::new(
+                Version([0x04, 0x01, 0x70, 0x00]),
+                U64(256),
+                CAPELLA_FORK_SPEC,
+            ),
+            ForkParameter::new(
+                Version([0x05, 0x01, 0x70, 0x00]),
+                U64(29696),
+                DENEB_FORK_SPEC,
+            ),
+        ]
+        .into(),
+    )
+    .expect("holesky fork parameters must be valid")
+}
+
+impl ClientState<MAINNET_SYNC_COMMITTEE_SIZE> {
+    /// Returns a client state for Mainnet
+    ///
+    /// See [`ClientState::with_chain_parameters`] for the default values of the other fields.
+    pub fn mainnet(
+        ibc_address: Address,
+        ibc_commitments_slot: H256,
+        trusting_period: Duration,
+        latest_execution_block_number: U64,
+    ) -> Self {
+        Self::with_chain_parameters(
+            MAINNET_GENESIS_VALIDATORS_ROOT,
+            MAINNET_GENESIS_TIME,
+            mainnet_fork_parameters(),
+            ibc_address,
+            ibc_commitments_slot,
+            trusting_period,
+            latest_execution_block_number,
+        )
+    }
+
+    /// Returns a client state for Sepolia
+    ///
+    /// See [`ClientState::with_chain_parameters`] for the default values of the other fields.
+    pub fn sepolia(
+        ibc_address: Address,
+        ibc_commitments_slot: H256,
+        trusting_period: Duration,
+        latest_execution_block_number: U64,
+    ) -> Self {
+        Self::with_chain_parameters(
+            SEPOLIA_GENESIS_VALIDATORS_ROOT,
+            SEPOLIA_GENESIS_TIME,
+            sepolia_fork_parameters(),
+            ibc_address,
+            ibc_commitments_slot,
+            trusting_period,
+            latest_execution_block_number,
+        )
+    }
+
+    /// Returns a client state for Holesky
+    ///
+    /// See [`ClientState::with_chain_parameters`] for the default values of the other fields.
+    pub fn holesky(
+        ibc_address: Address,
+        ibc_commitments_slot: H256,
+        trusting_period: Duration,
+        latest_execution_block_number: U64,
+    ) -> Self {
+        Self::with_chain_parameters(
+            HOLESKY_GENESIS_VALIDATORS_ROOT,
+            HOLESKY_GENESIS_TIME,
+            holesky_fork_parameters(),
+            ibc_address,
+            ibc_commitments_slot,
+            trusting_period,
+            latest_execution_block_number,
+        )
+    }
+
+    /// Returns a client state with the given chain parameters and the mainnet preset, e.g. for a devnet
+    ///
+    /// The other fields are set to the following values, which can be overridden by the caller:
+    /// - `min_sync_committee_participants`: 1
+    /// - `trust_level`: 2/3
+    /// - `max_clock_drift`: `SECONDS_PER_SLOT`
+    /// - `max_intermediate_consensus_updates`: 0
+    /// - `commitment_value_codec`: `CommitmentValueCodec::Keccak256`
+    #[allow(clippy::too_many_arguments)]
+    pub fn with_chain_parameters(
+        genesis_validators_root: Root,
+        genesis_time: U64,
+        fork_parameters: ForkParameters,
+        ibc_address: Address,
+        ibc_commitments_slot: H256,
+        trusting_period: Duration,
+        latest_execution_block_number: U64,
+    ) -> Self {
+        Self {
+            genesis_validators_root,
+            min_sync_committee_participants: 1.into(),
+            genesis_time,
+            fork_parameters,
+            seconds_per_slot: PRESET.SECONDS_PER_SLOT,
+            slots_per_epoch: PRESET.SLOTS_PER_EPOCH,
+            epochs_per_sync_committee_period: PRESET.EPOCHS_PER_SYNC_COMMITTEE_PERIOD,
+            ibc_address,
+            ibc_commitments_slot,
+            commitment_value_codec: CommitmentValueCodec::Keccak256,
+            commitment_prefix: Default::default(),
+            trust_level: Fraction::new(2, 3).expect("2/3 must be a valid fraction"),
+            trusting_period,
+            max_clock_drift: Duration::from_secs(PRESET.SECONDS_PER_SLOT.0),
+            max_intermediate_consensus_updates: U64(0),
+            latest_execution_block_number,
+            frozen_height: None,
+            misbehaviour_evidence_digest: None,
+            consensus_verifier: Default::default(),
+            execution_verifier: Default::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus_state::ConsensusState;
+    use ethereum_consensus::fork::BELLATRIX_INDEX;
+    use hex_literal::hex;
+    use ibc::timestamp::Timestamp;
+    use ibc_proto::google::protobuf::Any;
+
+    fn new_client_state(
+        f: fn(Address, H256, Duration, U64) -> ClientState<MAINNET_SYNC_COMMITTEE_SIZE>,
+    ) -> ClientState<MAINNET_SYNC_COMMITTEE_SIZE> {
+        f(
+            Address(hex!("ff77D90D6aA12db33d3Ba50A34fB25401f6e4c4F")),
+            H256(hex!(
+                "1ee222554989dda120e26ecacf756fe1235cd8d726706b57517715dde4f0c900"
+            )),
+            Duration::from_secs(60 * 60 * 27),
+            1.into(),
+        )
+    }
+
+    #[test]
+    fn test_preset_client_states() {
+        for client_state in [
+            new_client_state(ClientState::mainnet),
+            new_client_state(ClientState::sepolia),
+            new_client_state(ClientState::holesky),
+        ] {
+            let res = client_state.validate();
+            assert!(res.is_ok(), "{:?}", res);
+            let any_client_state: Any = client_state.clone().into();
+            assert_eq!(
+                client_state,
+                ClientState::try_from(any_client_state).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_preset_constants() {
+        let mainnet = new_client_state(ClientState::mainnet);
+        let sepolia = new_client_state(ClientState::sepolia);
+        let holesky = new_client_state(ClientState::holesky);
+
+        // chain-specific constants
+        assert_ne!(
+            mainnet.genesis_validators_root,
+            sepolia.genesis_validators_root
+        );
+        assert_ne!(mainnet.genesis_time, sepolia.genesis_time);
+        assert_ne!(mainnet.fork_parameters, sepolia.fork_parameters);
+        assert_ne!(sepolia.fork_parameters, holesky.fork_parameters);
+        assert_eq!(
+            mainnet.fork_parameters.genesis_version(),
+            &Version([0, 0, 0, 0])
+        );
+        assert_eq!(
+            sepolia.fork_parameters.genesis_version(),
+            &Version([0x90, 0, 0, 0x69])
+        );
+        let bellatrix = &mainnet.fork_parameters.forks()[BELLATRIX_INDEX];
+        assert_eq!(bellatrix.epoch, U64(144896));
+        assert_eq!(bellatrix.version, Version([2, 0, 0, 0]));
+        let bellatrix = &sepolia.fork_parameters.forks()[BELLATRIX_INDEX];
+        assert_eq!(bellatrix.epoch, U64(100));
+        assert_eq!(bellatrix.version, Version([0x90, 0, 0, 0x71]));
+
+        // the preset and the deployment-specific fields are shared
+        for client_state in [&sepolia, &holesky] {
+            assert_eq!(client_state.seconds_per_slot, mainnet.seconds_per_slot);
+            assert_eq!(client_state.slots_per_epoch, mainnet.slots_per_epoch);
+            assert_eq!(
+                client_state.epochs_per_sync_committee_period,
+                mainnet.epochs_per_sync_committee_period
+            );
+            assert_eq!(client_state.ibc_address, mainnet.ibc_address);
+        }
+    }
+
+    #[test]
+    fn test_mainnet_initial_consensus_state() {
+        let client_state = new_client_state(ClientState::mainnet);
+        // the first slot of the bellatrix fork on mainnet
+        let slot = U64(144896) * client_state.slots_per_epoch;
+        let consensus_state = ConsensusState {
+            slot,
+            // 2022-09-06 11:34:47 UTC
+            timestamp: Timestamp::from_nanoseconds(1662464087 * 1_000_000_000).unwrap(),
+            ..Default::default()
+        };
+        let res = client_state.validate_initial_consensus_state(&consensus_state);
+        assert!(res.is_ok(), "{:?}", res);
+
+        let consensus_state = ConsensusState {
+            slot: slot - 1,
+            timestamp: Timestamp::from_nanoseconds(1662464075 * 1_000_000_000).unwrap(),
+            ..Default::default()
+        };
+        let res = client_state.validate_initial_consensus_state(&consensus_state);
+        assert!(res.is_err(), "{:?}", res);
+    }
+}
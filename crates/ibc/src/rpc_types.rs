@@ -0,0 +1,210 @@
+//! Conversions from the `eth_getProof` response of alloy into the proofs of this client
+//!
+//! `AccountUpdateInfo::try_from_proof_response` builds the account update of a header, and
+//! `storage_proof_bytes` and `commitment_proof_bytes` build the `proof` of `ClientState::verify_membership` and `ClientState::verify_non_membership`.
+use crate::address::Address;
+use crate::commitment::{
+    calculate_ibc_commitment_storage_location, keccak_256, verify_account_storage_root,
+};
+use crate::errors::Error;
+use crate::internal_prelude::*;
+use crate::types::{encode_account_proof, AccountUpdateInfo};
+use alloy_rpc_types_eth::EIP1186AccountProofResponse;
+use ethereum_consensus::types::H256;
+use ibc::core::ics24_host::Path;
+
+impl AccountUpdateInfo {
+    /// Build the account update of the account of `address` from the `eth_getProof` response at the block of `state_root`
+    ///
+    /// The account proof of the response must prove that the storage root of the account in the state trie of `state_root` is the `storageHash` of the response,
+    /// which becomes `account_storage_root`.
+    pub fn try_from_proof_response(
+        response: &EIP1186AccountProofResponse,
+        address: &Address,
+        state_root: H256,
+    ) -> Result<Self, Error> {
+        if response.address.as_slice() != address.0.as_slice() {
+            return Err(Error::ProofResponseAddressMismatch {
+                expected: address.to_string(),
+                got: Address(response.address.0 .0).to_string(),
+            });
+        }
+        let account_update = AccountUpdateInfo {
+            account_proof: response
+                .account_proof
+                .iter()
+                .map(|node| node.to_vec())
+                .collect(),
+            account_storage_root: H256(response.storage_hash.0),
+        };
+        account_update.validate()?;
+        verify_account_storage_root(
+            state_root,
+            address,
+            &account_update.account_proof,
+            account_update.account_storage_root,
+        )?;
+        Ok(account_update)
+    }
+}
+
+/// Returns the storage proof of `key` in the `eth_getProof` response, encoded as the `proof` of `ClientState::verify_membership`
+///
+/// The proof must start at the `storageHash` of the response, i.e. the `account_storage_root` of the consensus state.
+pub fn storage_proof_bytes(
+    response: &EIP1186AccountProofResponse,
+    key: H256,
+) -> Result<Vec<u8>, Error> {
+    let storage_proof = response
+        .storage_proof
+        .iter()
+        .find(|storage_proof| storage_proof.key.0 .0 == key.0)
+        .ok_or(Error::StorageProofNotFound { key })?;
+    let proof: Vec<Vec<u8>> = storage_proof
+        .proof
+        .iter()
+        .map(|node| node.to_vec())
+        .collect();
+    if let Some(root_node) = proof.first() {
+        let root = H256(keccak_256(root_node));
+        if root.0 != response.storage_hash.0 {
+            return Err(Error::StorageProofRootMismatch {
+                key,
+                storage_hash: H256(response.storage_hash.0),
+                root,
+            });
+        }
+    }
+    Ok(encode_account_proof(proof))
+}
+
+/// Returns the storage proof of the commitment for `path` in the `eth_getProof` response, see `storage_proof_bytes`
+///
+/// The response must be requested with the storage key `calculate_ibc_commitment_storage_location(ibc_commitments_slot, path)`.
+pub fn commitment_proof_bytes(
+    response: &EIP1186AccountProofResponse,
+    ibc_commitments_slot: &H256,
+    path: Path,
+) -> Result<Vec<u8>, Error> {
+    storage_proof_bytes(
+        response,
+        calculate_ibc_commitment_storage_location(ibc_commitments_slot, path),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commitment::{verify_membership, CommitmentValueCodec};
+    use core::str::FromStr;
+    use hex_literal::hex;
+    use ibc::core::ics23_commitment::commitment::CommitmentRoot;
+
+    // the account of `test_verify_account_storage` of `client_state`
+    const ACCOUNT_RESPONSE: &str = include_str!("../testdata/eth_get_proof_account.json");
+    // the storage proof of `get_membership_proof` of `client_state`, of which the account proof is omitted
+    const STORAGE_RESPONSE: &str = include_str!("../testdata/eth_get_proof_storage.json");
+
+    #[test]
+    fn test_account_update_from_proof_response() {
+        let response: EIP1186AccountProofResponse = serde_json::from_str(ACCOUNT_RESPONSE).unwrap();
+        let address = Address(hex!("ff77D90D6aA12db33d3Ba50A34fB25401f6e4c4F"));
+        let state_root = H256(hex!(
+            "48b7747ba1094684d9197bbaa5dcb134587d23e493fb53a29e400c50e50f5147"
+        ));
+        let account_update =
+            AccountUpdateInfo::try_from_proof_response(&response, &address, state_root).unwrap();
+        assert_eq!(
+            account_update.account_storage_root,
+            H256(hex!(
+                "d70e9391a3dd508a60195d2a5e12fb2f7e49582f9ce2c12477299377ccfadaad"
+            ))
+        );
+        assert_eq!(
+            account_update.account_proof.len(),
+            response.account_proof.len()
+        );
+
+        // the response is for another account
+        let res = AccountUpdateInfo::try_from_proof_response(
+            &response,
+            &Address(hex!("a7f733a4fEA1071f58114b203F57444969b86524")),
+            state_root,
+        );
+        assert!(
+            matches!(res, Err(Error::ProofResponseAddressMismatch { .. })),
+            "{:?}",
+            res
+        );
+
+        // the storage hash is not the one proven by the account proof
+        let mut tampered = response.clone();
+        tampered.storage_hash = [1u8; 32].into();
+        let res = AccountUpdateInfo::try_from_proof_response(&tampered, &address, state_root);
+        assert!(
+            matches!(res, Err(Error::AccountStorageRootMismatch(..))),
+            "{:?}",
+            res
+        );
+        // a zero storage hash is rejected before the account proof is verified
+        tampered.storage_hash = Default::default();
+        let res = AccountUpdateInfo::try_from_proof_response(&tampered, &address, state_root);
+        assert!(
+            matches!(res, Err(Error::ZeroAccountStorageRoot)),
+            "{:?}",
+            res
+        );
+
+        // the response is not at the block of the state root
+        let res = AccountUpdateInfo::try_from_proof_response(&response, &address, H256::default());
+        assert!(res.is_err(), "{:?}", res);
+    }
+
+    #[test]
+    fn test_commitment_proof_bytes() {
+        let response: EIP1186AccountProofResponse = serde_json::from_str(STORAGE_RESPONSE).unwrap();
+        let ibc_commitments_slot = H256(hex!(
+            "1ee222554989dda120e26ecacf756fe1235cd8d726706b57517715dde4f0c900"
+        ));
+        let root = CommitmentRoot::from_bytes(&response.storage_hash.0);
+        let path = Path::from_str("clients/lcp-client-0/clientState").unwrap();
+        let value = hex!("0a242f6962632e6c69676874636c69656e74732e6c63702e76312e436c69656e74537461746512ed010a208083673c69fe3f098ea79a799d9dbb99c39b4b4f17a1a79ef58bdf8ae86299951080f524220310fb012a1353575f48415244454e494e475f4e45454445442a1147524f55505f4f55545f4f465f44415445320e494e54454c2d53412d3030323139320e494e54454c2d53412d3030323839320e494e54454c2d53412d3030333334320e494e54454c2d53412d3030343737320e494e54454c2d53412d3030363134320e494e54454c2d53412d3030363135320e494e54454c2d53412d3030363137320e494e54454c2d53412d30303832383a14cb96f8d6c2d543102184d679d7829b39434e4eec48015001");
+
+        let proof = commitment_proof_bytes(&response, &ibc_commitments_slot, path.clone()).unwrap();
+        let res = verify_membership(
+            &root,
+            &ibc_commitments_slot,
+            CommitmentValueCodec::Keccak256,
+            &proof.try_into().unwrap(),
+            path,
+            &value,
+        );
+        assert!(res.is_ok(), "{:?}", res);
+
+        // the response does not contain the storage proof of the path
+        let res = commitment_proof_bytes(
+            &response,
+            &ibc_commitments_slot,
+            Path::from_str("clients/lcp-client-1/clientState").unwrap(),
+        );
+        assert!(
+            matches!(res, Err(Error::StorageProofNotFound { .. })),
+            "{:?}",
+            res
+        );
+
+        // the storage proof does not start at the storage hash
+        let mut tampered = response;
+        tampered.storage_hash = Default::default();
+        let key = calculate_ibc_commitment_storage_location(
+            &ibc_commitments_slot,
+            Path::from_str("clients/lcp-client-0/clientState").unwrap(),
+        );
+        let res = storage_proof_bytes(&tampered, key);
+        assert!(
+            matches!(res, Err(Error::StorageProofRootMismatch { .. })),
+            "{:?}",
+            res
+        );
+    }
+}
@@ -0,0 +1,334 @@
+//! JSON schemas of the configuration-facing types
+//!
+//! With the `schema` feature, `ClientState`, `ConsensusState`, `TrustedSyncCommittee` and `CommitmentValueCodec` derive `schemars::JsonSchema`.
+//! The fields of the types defined in `ethereum-consensus`, `ethereum-light-client-verifier` and `ibc` are described by the types of this module
+//! with `#[schemars(with = "...")]`, which mirror the human-readable serde representations of the types:
+//! `U64` is a decimal string, and hashes, addresses, versions and public keys are 0x-prefixed hex strings.
+//! Note that a schema only describes the shape of a value; use `ClientState::deserialize_validated` to reject misconfigured values.
+use crate::internal_prelude::*;
+use schemars::gen::SchemaGenerator;
+use schemars::schema::{
+    ArrayValidation, InstanceType, Metadata, ObjectValidation, Schema, SchemaObject,
+    StringValidation,
+};
+use schemars::JsonSchema;
+
+macro_rules! hex_string_schema {
+    ($(#[doc = $doc:literal] $name:ident($len:expr);)*) => {
+        $(
+            #[doc = $doc]
+            pub struct $name;
+
+            impl JsonSchema for $name {
+                fn schema_name() -> String {
+                    stringify!($name).into()
+                }
+
+                fn json_schema(_: &mut SchemaGenerator) -> Schema {
+                    string_schema(format!("^0x[0-9a-fA-F]{{{}}}$", 2 * $len), $doc.trim())
+                }
+            }
+        )*
+    };
+}
+
+hex_string_schema! {
+    /// A 32-byte hash as a 0x-prefixed hex string
+    H256(32);
+    /// A 20-byte address as a 0x-prefixed hex string
+    Address(20);
+    /// A 4-byte fork version as a 0x-prefixed hex string
+    Version(4);
+    /// A compressed BLS12-381 public key as a 0x-prefixed hex string
+    PublicKey(48);
+}
+
+/// A 64-bit unsigned integer as a decimal string
+pub struct U64;
+
+impl JsonSchema for U64 {
+    fn schema_name() -> String {
+        "U64".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        string_schema(
+            "^[0-9]+$".into(),
+            "A 64-bit unsigned integer as a decimal string",
+        )
+    }
+}
+
+/// A non-empty byte string as a 0x-prefixed hex string
+pub struct Bytes;
+
+impl JsonSchema for Bytes {
+    fn schema_name() -> String {
+        "Bytes".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        string_schema(
+            "^0x([0-9a-fA-F]{2})+$".into(),
+            "A non-empty byte string as a 0x-prefixed hex string",
+        )
+    }
+}
+
+/// A timestamp as an RFC 3339 date-time, which is null if the timestamp is unset
+pub struct Timestamp;
+
+impl JsonSchema for Timestamp {
+    fn schema_name() -> String {
+        "Timestamp".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        let time = SchemaObject {
+            instance_type: Some(vec![InstanceType::String, InstanceType::Null].into()),
+            format: Some("date-time".into()),
+            ..Default::default()
+        };
+        SchemaObject {
+            metadata: Some(Box::new(Metadata {
+                description: Some("A timestamp as an RFC 3339 date-time".into()),
+                ..Default::default()
+            })),
+            instance_type: Some(InstanceType::Object.into()),
+            object: Some(Box::new(ObjectValidation {
+                properties: [("time".into(), time.into())].into_iter().collect(),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+/// A sync committee of `SYNC_COMMITTEE_SIZE` public keys and their aggregate public key
+pub struct SyncCommittee<const SYNC_COMMITTEE_SIZE: usize>;
+
+impl<const SYNC_COMMITTEE_SIZE: usize> JsonSchema for SyncCommittee<SYNC_COMMITTEE_SIZE> {
+    fn schema_name() -> String {
+        format!("SyncCommittee_{}", SYNC_COMMITTEE_SIZE)
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        let pubkeys = SchemaObject {
+            instance_type: Some(InstanceType::Array.into()),
+            array: Some(Box::new(ArrayValidation {
+                items: Some(gen.subschema_for::<PublicKey>().into()),
+                min_items: Some(SYNC_COMMITTEE_SIZE as u32),
+                max_items: Some(SYNC_COMMITTEE_SIZE as u32),
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+        SchemaObject {
+            metadata: Some(Box::new(Metadata {
+                description: Some(format!(
+                    "A sync committee of {} members",
+                    SYNC_COMMITTEE_SIZE
+                )),
+                ..Default::default()
+            })),
+            instance_type: Some(InstanceType::Object.into()),
+            object: Some(Box::new(ObjectValidation {
+                required: ["aggregate_pubkey".into(), "pubkeys".into()].into(),
+                properties: [
+                    ("pubkeys".into(), pubkeys.into()),
+                    ("aggregate_pubkey".into(), gen.subschema_for::<PublicKey>()),
+                ]
+                .into_iter()
+                .collect(),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+/// The fork parameters of the target beacon chain
+#[derive(JsonSchema)]
+pub struct ForkParameters {
+    /// The fork version of the genesis
+    pub genesis_version: Version,
+    /// The forks in ascending order of the epochs
+    pub forks: Vec<ForkParameter>,
+}
+
+/// A fork of the target beacon chain
+#[derive(JsonSchema)]
+pub struct ForkParameter {
+    /// The fork version
+    pub version: Version,
+    /// The epoch at which the fork is activated
+    pub epoch: U64,
+    /// The generalized indices of the fork
+    pub spec: ForkSpec,
+}
+
+/// The generalized indices of the fields verified by the light client
+#[derive(JsonSchema)]
+pub struct ForkSpec {
+    pub finalized_root_gindex: u32,
+    pub current_sync_committee_gindex: u32,
+    pub next_sync_committee_gindex: u32,
+    pub execution_payload_gindex: u32,
+    pub execution_payload_state_root_gindex: u32,
+    pub execution_payload_block_number_gindex: u32,
+}
+
+/// A fraction of `numerator / denominator`
+#[derive(JsonSchema)]
+pub struct Fraction {
+    pub numerator: u64,
+    pub denominator: u64,
+}
+
+/// A height, whose revision height is an execution block number
+#[derive(JsonSchema)]
+pub struct Height {
+    pub revision_number: u64,
+    pub revision_height: u64,
+}
+
+/// A height with decimal strings, which is the representation of the `json` feature
+#[derive(JsonSchema)]
+#[schemars(rename = "Height")]
+pub struct HeightJson {
+    pub revision_number: U64,
+    pub revision_height: U64,
+}
+
+/// The 32-byte storage root of the IBC contract
+#[derive(JsonSchema)]
+pub struct CommitmentRoot {
+    #[schemars(length(equal = 32))]
+    pub bytes: Vec<u8>,
+}
+
+fn string_schema(pattern: String, description: &str) -> Schema {
+    SchemaObject {
+        metadata: Some(Box::new(Metadata {
+            description: Some(description.into()),
+            ..Default::default()
+        })),
+        instance_type: Some(InstanceType::String.into()),
+        string: Some(Box::new(StringValidation {
+            pattern: Some(pattern),
+            ..Default::default()
+        })),
+        ..Default::default()
+    }
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::client_state::tests::new_client_state;
+    use crate::MinimalClientState;
+    use schemars::schema_for;
+    use serde_json::{Map, Value};
+
+    // regenerate with `UPDATE_SCHEMA_SNAPSHOT=1 cargo test --features schema` after a deliberate change of the representation
+    const CLIENT_STATE_SCHEMA_PATH: &str = "testdata/client_state.schema.json";
+
+    #[test]
+    fn test_client_state_schema_snapshot() {
+        let schema = serde_json::to_value(schema_for!(MinimalClientState)).unwrap();
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(CLIENT_STATE_SCHEMA_PATH);
+        if std::env::var_os("UPDATE_SCHEMA_SNAPSHOT").is_some() {
+            let json = serde_json::to_string_pretty(&schema).unwrap();
+            std::fs::write(&path, json + "\n").unwrap();
+        }
+        let expected: Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(
+            schema,
+            expected,
+            "the schema of the client state changed:\n{}",
+            serde_json::to_string_pretty(&schema).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_client_state_schema_describes_serialized_value() {
+        let schema = serde_json::to_value(schema_for!(MinimalClientState)).unwrap();
+        let definitions = schema["definitions"].as_object().unwrap();
+
+        let client_state = new_client_state();
+        let json = serde_json::to_value(&client_state).unwrap();
+        assert!(matches_schema(&json, &schema, definitions), "{}", json);
+
+        let frozen = client_state
+            .with_frozen_height(ibc::Height::new(0, 2).unwrap())
+            .with_misbehaviour_evidence_digest(Default::default());
+        let json = serde_json::to_value(&frozen).unwrap();
+        assert!(matches_schema(&json, &schema, definitions), "{}", json);
+
+        // an unknown field
+        let mut typo = json;
+        let obj = typo.as_object_mut().unwrap();
+        let value = obj.remove("trusting_period").unwrap();
+        obj.insert("trustng_period".into(), value);
+        assert!(!matches_schema(&typo, &schema, definitions));
+    }
+
+    /// Returns true if `value` matches the subset of JSON schema that the schemas of this crate use, ignoring the patterns and the formats
+    fn matches_schema(value: &Value, schema: &Value, definitions: &Map<String, Value>) -> bool {
+        if let Some(reference) = schema["$ref"].as_str() {
+            let name = reference.trim_start_matches("#/definitions/");
+            return matches_schema(value, &definitions[name], definitions);
+        }
+        if let Some(all_of) = schema["allOf"].as_array() {
+            if !all_of.iter().all(|s| matches_schema(value, s, definitions)) {
+                return false;
+            }
+        }
+        for key in ["anyOf", "oneOf"] {
+            if let Some(subschemas) = schema[key].as_array() {
+                if !subschemas
+                    .iter()
+                    .any(|s| matches_schema(value, s, definitions))
+                {
+                    return false;
+                }
+            }
+        }
+        if let Some(variants) = schema["enum"].as_array() {
+            if !variants.contains(value) {
+                return false;
+            }
+        }
+        let types = match &schema["type"] {
+            Value::String(ty) => vec![ty.as_str()],
+            Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+            _ => return true,
+        };
+        types.into_iter().any(|ty| match (ty, value) {
+            ("null", Value::Null) | ("boolean", Value::Bool(_)) | ("string", Value::String(_)) => {
+                true
+            }
+            ("integer", Value::Number(n)) => n.is_u64(),
+            ("array", Value::Array(items)) => items
+                .iter()
+                .all(|item| matches_schema(item, &schema["items"], definitions)),
+            ("object", Value::Object(obj)) => {
+                let properties = schema["properties"].as_object();
+                let required = schema["required"].as_array().cloned().unwrap_or_default();
+                required
+                    .iter()
+                    .all(|key| obj.contains_key(key.as_str().unwrap()))
+                    && obj.iter().all(|(key, value)| {
+                        match properties.and_then(|properties| properties.get(key)) {
+                            Some(property) => matches_schema(value, property, definitions),
+                            None => schema["additionalProperties"] != Value::Bool(false),
+                        }
+                    })
+            }
+            _ => false,
+        })
+    }
+}
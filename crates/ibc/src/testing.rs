@@ -0,0 +1,1032 @@
+//! An in-memory host for testing the client end-to-end with the `ValidationContext` of ibc-rs
+//!
+//! `MockContext` stores the client states and the consensus states keyed by height, and records the host timestamp and height at which each consensus state is stored,
+//! as the client handlers of ibc-rs do. The host timestamp and height are controlled by the test, e.g. advanced past the trusting period to expire the trusted consensus states.
+//! `create_client` and `submit_misbehaviour` run the entry points of the `ClientState` trait against the context and store their results.
+//! `deliver` runs a message through the handlers of ibc-rs with the `ExecutionContext` of the context, as the host does, and `update_client` delivers `MsgUpdateClient` with it.
+//!
+//! Only the client stores are implemented. The connection, channel and packet stores, the host consensus states and the routing are not supported and return errors or nothing.
+//! The events and the log messages of the handlers are discarded.
+use crate::client_state::{ClientState, MisbehaviourResult, UpdateResult};
+use crate::commitment::keccak_256;
+use crate::consensus_state::ConsensusState;
+use crate::internal_prelude::*;
+use crate::misc::HostTime;
+use alloc::collections::BTreeMap;
+use core::str::FromStr;
+use core::time::Duration;
+use ibc::core::context::Router;
+use ibc::core::ics02_client::client_state::ClientState as Ics2ClientState;
+use ibc::core::ics02_client::client_type::ClientType;
+use ibc::core::ics02_client::consensus_state::ConsensusState as Ics02ConsensusState;
+use ibc::core::ics02_client::error::ClientError;
+use ibc::core::ics02_client::msgs::update_client::MsgUpdateClient;
+use ibc::core::ics02_client::msgs::ClientMsg;
+use ibc::core::ics03_connection::connection::ConnectionEnd;
+use ibc::core::ics03_connection::error::ConnectionError;
+use ibc::core::ics04_channel::channel::ChannelEnd;
+use ibc::core::ics04_channel::commitment::{AcknowledgementCommitment, PacketCommitment};
+use ibc::core::ics04_channel::packet::{Receipt, Sequence};
+use ibc::core::ics23_commitment::commitment::CommitmentPrefix;
+use ibc::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
+use ibc::core::ics24_host::path::{
+    AckPath, ChannelEndPath, ClientConnectionPath, ClientConsensusStatePath, ClientStatePath,
+    ClientTypePath, CommitmentPath, ConnectionPath, ReceiptPath, SeqAckPath, SeqRecvPath,
+    SeqSendPath,
+};
+use ibc::core::ics26_routing::context::{Module, ModuleId};
+use ibc::core::ics26_routing::error::RouterError;
+use ibc::core::ics26_routing::msgs::MsgEnvelope;
+use ibc::core::{ContextError, ExecutionContext, ValidationContext};
+use ibc::events::IbcEvent;
+use ibc::signer::Signer;
+use ibc::timestamp::Timestamp;
+use ibc::Height;
+use ibc_proto::google::protobuf::Any;
+
+/// The commitment prefix of the host
+pub const MOCK_COMMITMENT_PREFIX: &[u8] = b"ibc";
+
+/// An in-memory host of the clients of `SYNC_COMMITTEE_SIZE`
+#[derive(Clone, Debug)]
+pub struct MockContext<const SYNC_COMMITTEE_SIZE: usize> {
+    host_timestamp: Timestamp,
+    host_height: Height,
+    /// the expected time per block, which converts the delay period to the number of blocks
+    pub max_expected_time_per_block: Duration,
+    client_states: BTreeMap<ClientId, ClientState<SYNC_COMMITTEE_SIZE>>,
+    consensus_states: BTreeMap<ClientId, BTreeMap<Height, ConsensusState>>,
+    update_times: BTreeMap<(ClientId, Height), Timestamp>,
+    update_heights: BTreeMap<(ClientId, Height), Height>,
+}
+
+impl<const SYNC_COMMITTEE_SIZE: usize> MockContext<SYNC_COMMITTEE_SIZE> {
+    pub fn new(host_timestamp: Timestamp, host_height: Height) -> Self {
+        Self {
+            host_timestamp,
+            host_height,
+            max_expected_time_per_block: Duration::from_secs(12),
+            client_states: Default::default(),
+            consensus_states: Default::default(),
+            update_times: Default::default(),
+            update_heights: Default::default(),
+        }
+    }
+
+    pub fn set_host_timestamp(&mut self, host_timestamp: Timestamp) {
+        self.host_timestamp = host_timestamp;
+    }
+
+    pub fn set_host_height(&mut self, host_height: Height) {
+        self.host_height = host_height;
+    }
+
+    /// Advance the host clock by `duration` and the host height by the number of blocks produced in `duration`
+    ///
+    /// Panics if the timestamp overflows.
+    pub fn advance(&mut self, duration: Duration) {
+        self.host_timestamp = (self.host_timestamp + duration).expect("host timestamp overflow");
+        let blocks = duration.as_nanos() / self.max_expected_time_per_block.as_nanos().max(1);
+        self.host_height = self.host_height.add(blocks as u64);
+    }
+
+    /// Returns the client state stored for `client_id` if any
+    pub fn stored_client_state(
+        &self,
+        client_id: &ClientId,
+    ) -> Option<&ClientState<SYNC_COMMITTEE_SIZE>> {
+        self.client_states.get(client_id)
+    }
+
+    /// Returns the consensus state stored for `client_id` at `height` if any
+    pub fn stored_consensus_state(
+        &self,
+        client_id: &ClientId,
+        height: &Height,
+    ) -> Option<&ConsensusState> {
+        self.consensus_states.get(client_id)?.get(height)
+    }
+
+    /// Returns the heights of the consensus states stored for `client_id` in ascending order
+    pub fn consensus_state_heights(&self, client_id: &ClientId) -> Vec<Height> {
+        self.consensus_states
+            .get(client_id)
+            .map(|states| states.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns the consensus states stored for `client_id` with their heights in ascending order
+    pub fn stored_consensus_states(&self, client_id: &ClientId) -> Vec<(Height, ConsensusState)> {
+        self.consensus_states
+            .get(client_id)
+            .map(|states| states.iter().map(|(h, cs)| (*h, cs.clone())).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn store_client_state(
+        &mut self,
+        client_id: ClientId,
+        client_state: ClientState<SYNC_COMMITTEE_SIZE>,
+    ) {
+        self.client_states.insert(client_id, client_state);
+    }
+
+    /// Store `consensus_state` at `height` with the current host timestamp and height as its update time and height
+    pub fn store_consensus_state(
+        &mut self,
+        client_id: ClientId,
+        height: Height,
+        consensus_state: ConsensusState,
+    ) {
+        self.update_times
+            .insert((client_id.clone(), height), self.host_timestamp);
+        self.update_heights
+            .insert((client_id.clone(), height), self.host_height);
+        self.consensus_states
+            .entry(client_id)
+            .or_default()
+            .insert(height, consensus_state);
+    }
+
+    /// Delete the consensus state of `client_id` at `height` with its update time and height
+    pub fn delete_consensus_state(&mut self, client_id: &ClientId, height: &Height) {
+        if let Some(states) = self.consensus_states.get_mut(client_id) {
+            states.remove(height);
+        }
+        self.update_times.remove(&(client_id.clone(), *height));
+        self.update_heights.remove(&(client_id.clone(), *height));
+    }
+
+    /// Initialise the client of `client_id` with `client_state` and `consensus_state` as `MsgCreateClient` does
+    pub fn create_client(
+        &mut self,
+        client_id: ClientId,
+        client_state: ClientState<SYNC_COMMITTEE_SIZE>,
+        consensus_state: ConsensusState,
+    ) -> Result<(), ClientError> {
+        Ics2ClientState::initialise(&client_state, consensus_state.clone().into())?;
+        let height = Ics2ClientState::latest_height(&client_state);
+        self.store_client_state(client_id.clone(), client_state);
+        self.store_consensus_state(client_id, height, consensus_state);
+        Ok(())
+    }
+
+    /// Update the client of `client_id` with `header` by delivering `MsgUpdateClient` to the update handler of ibc-rs
+    ///
+    /// The handler calls `check_header_and_update_state` and stores the returned client state, and the returned consensus state at the latest height of the client state with the current host time and height.
+    /// The handler cannot delete consensus states, so nothing is pruned; see `apply_update` for a host that prunes.
+    pub fn update_client(&mut self, client_id: &ClientId, header: Any) -> Result<(), ClientError> {
+        let msg = MsgUpdateClient {
+            client_id: client_id.clone(),
+            header,
+            signer: mock_signer(),
+        };
+        self.deliver(MsgEnvelope::Client(ClientMsg::UpdateClient(msg)))
+            .map_err(|e| match e {
+                RouterError::ContextError(ContextError::ClientError(e)) => e,
+                e => ClientError::Other {
+                    description: e.to_string(),
+                },
+            })
+    }
+
+    /// Write the result of an update of the client of `client_id` at `UpdateResult::height`, e.g. computed by `ClientState::update_state` or `verify::simulate_update`
+    ///
+    /// This is the write of a host that handles the update itself instead of using the update handler of ibc-rs.
+    /// The consensus state already stored at `UpdateResult::height` is not overwritten, and the consensus states of `UpdateResult::prunable_heights` are deleted.
+    pub fn apply_update(&mut self, client_id: &ClientId, res: &UpdateResult<SYNC_COMMITTEE_SIZE>) {
+        if let Some(new_client_state) = res.client_state.clone() {
+            self.store_client_state(client_id.clone(), new_client_state);
+        }
+        if self
+            .stored_consensus_state(client_id, &res.height)
+            .is_none()
+        {
+            self.store_consensus_state(client_id.clone(), res.height, res.consensus_state.clone());
+        }
+        for height in res.prunable_heights.iter() {
+            self.delete_consensus_state(client_id, height);
+        }
+    }
+
+    /// Validate `msg` with the handler of ibc-rs and execute it if it is valid, as the host delivers a message
+    pub fn deliver(&mut self, msg: MsgEnvelope) -> Result<(), RouterError> {
+        ValidationContext::validate(self, msg.clone())?;
+        ExecutionContext::execute(self, msg)
+    }
+
+    /// Submit `misbehaviour` of the client of `client_id` as `MsgSubmitMisbehaviour` does, and store the frozen client state
+    pub fn submit_misbehaviour(
+        &mut self,
+        client_id: &ClientId,
+        misbehaviour: Any,
+    ) -> Result<MisbehaviourResult<SYNC_COMMITTEE_SIZE>, ClientError> {
+        let client_state = self.client_state_of(client_id)?;
+        let res =
+            client_state.update_state_on_misbehaviour(&*self, client_id.clone(), misbehaviour)?;
+        self.store_client_state(client_id.clone(), res.client_state.clone());
+        Ok(res)
+    }
+
+    fn client_state_of(
+        &self,
+        client_id: &ClientId,
+    ) -> Result<ClientState<SYNC_COMMITTEE_SIZE>, ClientError> {
+        self.client_states
+            .get(client_id)
+            .cloned()
+            .ok_or_else(|| ClientError::ClientNotFound {
+                client_id: client_id.clone(),
+            })
+    }
+
+    fn consensus_state_at(
+        &self,
+        client_id: &ClientId,
+        height: Height,
+    ) -> Result<ConsensusState, ClientError> {
+        self.stored_consensus_state(client_id, &height)
+            .cloned()
+            .ok_or_else(|| ClientError::ConsensusStateNotFound {
+                client_id: client_id.clone(),
+                height,
+            })
+    }
+}
+
+fn mock_signer() -> Signer {
+    Signer::from_str("mock-relayer").expect("the signer must not be empty")
+}
+
+fn unsupported(name: &str) -> ContextError {
+    ContextError::ClientError(ClientError::Other {
+        description: format!("{} is not supported by MockContext", name),
+    })
+}
+
+fn unexpected_state(name: &str) -> ContextError {
+    ContextError::ClientError(ClientError::Other {
+        description: format!("{} is not of the Ethereum client", name),
+    })
+}
+
+impl<const SYNC_COMMITTEE_SIZE: usize> HostTime for MockContext<SYNC_COMMITTEE_SIZE> {
+    fn now(&self) -> Timestamp {
+        self.host_timestamp
+    }
+}
+
+impl<const SYNC_COMMITTEE_SIZE: usize> Router for MockContext<SYNC_COMMITTEE_SIZE> {
+    fn get_route(&self, _module_id: &ModuleId) -> Option<&dyn Module> {
+        None
+    }
+
+    fn get_route_mut(&mut self, _module_id: &ModuleId) -> Option<&mut dyn Module> {
+        None
+    }
+
+    fn has_route(&self, _module_id: &ModuleId) -> bool {
+        false
+    }
+
+    fn lookup_module_by_port(&self, _port_id: &PortId) -> Option<ModuleId> {
+        None
+    }
+}
+
+impl<const SYNC_COMMITTEE_SIZE: usize> ValidationContext for MockContext<SYNC_COMMITTEE_SIZE> {
+    fn client_state(&self, client_id: &ClientId) -> Result<Box<dyn Ics2ClientState>, ContextError> {
+        Ok(self.client_state_of(client_id)?.into_box())
+    }
+
+    fn decode_client_state(
+        &self,
+        client_state: Any,
+    ) -> Result<Box<dyn Ics2ClientState>, ContextError> {
+        Ok(ClientState::<SYNC_COMMITTEE_SIZE>::try_from(client_state)?.into_box())
+    }
+
+    fn consensus_state(
+        &self,
+        client_cons_state_path: &ClientConsensusStatePath,
+    ) -> Result<Box<dyn Ics02ConsensusState>, ContextError> {
+        let height = Height::new(client_cons_state_path.epoch, client_cons_state_path.height)
+            .map_err(ContextError::ClientError)?;
+        Ok(self
+            .consensus_state_at(&client_cons_state_path.client_id, height)?
+            .into_box())
+    }
+
+    fn next_consensus_state(
+        &self,
+        client_id: &ClientId,
+        height: &Height,
+    ) -> Result<Option<Box<dyn Ics02ConsensusState>>, ContextError> {
+        Ok(self.consensus_states.get(client_id).and_then(|states| {
+            states
+                .iter()
+                .find(|(h, _)| *h > height)
+                .map(|(_, cs)| cs.clone().into_box())
+        }))
+    }
+
+    fn prev_consensus_state(
+        &self,
+        client_id: &ClientId,
+        height: &Height,
+    ) -> Result<Option<Box<dyn Ics02ConsensusState>>, ContextError> {
+        Ok(self.consensus_states.get(client_id).and_then(|states| {
+            states
+                .iter()
+                .rev()
+                .find(|(h, _)| *h < height)
+                .map(|(_, cs)| cs.clone().into_box())
+        }))
+    }
+
+    fn host_height(&self) -> Result<Height, ContextError> {
+        Ok(self.host_height)
+    }
+
+    fn host_timestamp(&self) -> Result<Timestamp, ContextError> {
+        Ok(self.host_timestamp)
+    }
+
+    fn pending_host_consensus_state(&self) -> Result<Box<dyn Ics02ConsensusState>, ContextError> {
+        Err(unsupported("pending_host_consensus_state"))
+    }
+
+    fn host_consensus_state(
+        &self,
+        _height: &Height,
+    ) -> Result<Box<dyn Ics02ConsensusState>, ContextError> {
+        Err(unsupported("host_consensus_state"))
+    }
+
+    fn client_counter(&self) -> Result<u64, ContextError> {
+        Ok(self.client_states.len() as u64)
+    }
+
+    fn connection_end(&self, _conn_id: &ConnectionId) -> Result<ConnectionEnd, ContextError> {
+        Err(unsupported("connection_end"))
+    }
+
+    fn validate_self_client(&self, _counterparty_client_state: Any) -> Result<(), ConnectionError> {
+        Ok(())
+    }
+
+    fn commitment_prefix(&self) -> CommitmentPrefix {
+        MOCK_COMMITMENT_PREFIX
+            .to_vec()
+            .try_into()
+            .expect("the commitment prefix must not be empty")
+    }
+
+    fn connection_counter(&self) -> Result<u64, ContextError> {
+        Ok(0)
+    }
+
+    fn channel_end(&self, _channel_end_path: &ChannelEndPath) -> Result<ChannelEnd, ContextError> {
+        Err(unsupported("channel_end"))
+    }
+
+    fn connection_channels(
+        &self,
+        _cid: &ConnectionId,
+    ) -> Result<Vec<(PortId, ChannelId)>, ContextError> {
+        Ok(Vec::new())
+    }
+
+    fn get_next_sequence_send(
+        &self,
+        _seq_send_path: &SeqSendPath,
+    ) -> Result<Sequence, ContextError> {
+        Err(unsupported("get_next_sequence_send"))
+    }
+
+    fn get_next_sequence_recv(
+        &self,
+        _seq_recv_path: &SeqRecvPath,
+    ) -> Result<Sequence, ContextError> {
+        Err(unsupported("get_next_sequence_recv"))
+    }
+
+    fn get_next_sequence_ack(&self, _seq_ack_path: &SeqAckPath) -> Result<Sequence, ContextError> {
+        Err(unsupported("get_next_sequence_ack"))
+    }
+
+    fn get_packet_commitment(
+        &self,
+        _commitment_path: &CommitmentPath,
+    ) -> Result<PacketCommitment, ContextError> {
+        Err(unsupported("get_packet_commitment"))
+    }
+
+    fn get_packet_receipt(&self, _receipt_path: &ReceiptPath) -> Result<Receipt, ContextError> {
+        Err(unsupported("get_packet_receipt"))
+    }
+
+    fn get_packet_acknowledgement(
+        &self,
+        _ack_path: &AckPath,
+    ) -> Result<AcknowledgementCommitment, ContextError> {
+        Err(unsupported("get_packet_acknowledgement"))
+    }
+
+    fn hash(&self, value: &[u8]) -> Vec<u8> {
+        keccak_256(value).to_vec()
+    }
+
+    fn client_update_time(
+        &self,
+        client_id: &ClientId,
+        height: &Height,
+    ) -> Result<Timestamp, ContextError> {
+        self.update_times
+            .get(&(client_id.clone(), *height))
+            .cloned()
+            .ok_or_else(|| {
+                ClientError::ConsensusStateNotFound {
+                    client_id: client_id.clone(),
+                    height: *height,
+                }
+                .into()
+            })
+    }
+
+    fn client_update_height(
+        &self,
+        client_id: &ClientId,
+        height: &Height,
+    ) -> Result<Height, ContextError> {
+        self.update_heights
+            .get(&(client_id.clone(), *height))
+            .cloned()
+            .ok_or_else(|| {
+                ClientError::ConsensusStateNotFound {
+                    client_id: client_id.clone(),
+                    height: *height,
+                }
+                .into()
+            })
+    }
+
+    fn channel_counter(&self) -> Result<u64, ContextError> {
+        Ok(0)
+    }
+
+    fn max_expected_time_per_block(&self) -> Duration {
+        self.max_expected_time_per_block
+    }
+}
+
+impl<const SYNC_COMMITTEE_SIZE: usize> ExecutionContext for MockContext<SYNC_COMMITTEE_SIZE> {
+    fn store_client_type(
+        &mut self,
+        _client_type_path: ClientTypePath,
+        _client_type: ClientType,
+    ) -> Result<(), ContextError> {
+        Ok(())
+    }
+
+    fn store_client_state(
+        &mut self,
+        client_state_path: ClientStatePath,
+        client_state: Box<dyn Ics2ClientState>,
+    ) -> Result<(), ContextError> {
+        let client_state = client_state
+            .as_any()
+            .downcast_ref::<ClientState<SYNC_COMMITTEE_SIZE>>()
+            .cloned()
+            .ok_or_else(|| unexpected_state("client state"))?;
+        self.client_states.insert(client_state_path.0, client_state);
+        Ok(())
+    }
+
+    fn store_consensus_state(
+        &mut self,
+        consensus_state_path: ClientConsensusStatePath,
+        consensus_state: Box<dyn Ics02ConsensusState>,
+    ) -> Result<(), ContextError> {
+        let height = Height::new(consensus_state_path.epoch, consensus_state_path.height)
+            .map_err(ContextError::ClientError)?;
+        let consensus_state = consensus_state
+            .as_any()
+            .downcast_ref::<ConsensusState>()
+            .cloned()
+            .ok_or_else(|| unexpected_state("consensus state"))?;
+        self.consensus_states
+            .entry(consensus_state_path.client_id)
+            .or_default()
+            .insert(height, consensus_state);
+        Ok(())
+    }
+
+    fn increase_client_counter(&mut self) {}
+
+    fn store_update_time(
+        &mut self,
+        client_id: ClientId,
+        height: Height,
+        timestamp: Timestamp,
+    ) -> Result<(), ContextError> {
+        self.update_times.insert((client_id, height), timestamp);
+        Ok(())
+    }
+
+    fn store_update_height(
+        &mut self,
+        client_id: ClientId,
+        height: Height,
+        host_height: Height,
+    ) -> Result<(), ContextError> {
+        self.update_heights.insert((client_id, height), host_height);
+        Ok(())
+    }
+
+    fn store_connection(
+        &mut self,
+        _connection_path: &ConnectionPath,
+        _connection_end: ConnectionEnd,
+    ) -> Result<(), ContextError> {
+        Err(unsupported("store_connection"))
+    }
+
+    fn store_connection_to_client(
+        &mut self,
+        _client_connection_path: &ClientConnectionPath,
+        _conn_id: ConnectionId,
+    ) -> Result<(), ContextError> {
+        Err(unsupported("store_connection_to_client"))
+    }
+
+    fn increase_connection_counter(&mut self) {}
+
+    fn store_packet_commitment(
+        &mut self,
+        _commitment_path: &CommitmentPath,
+        _commitment: PacketCommitment,
+    ) -> Result<(), ContextError> {
+        Err(unsupported("store_packet_commitment"))
+    }
+
+    fn delete_packet_commitment(
+        &mut self,
+        _commitment_path: &CommitmentPath,
+    ) -> Result<(), ContextError> {
+        Err(unsupported("delete_packet_commitment"))
+    }
+
+    fn store_packet_receipt(
+        &mut self,
+        _receipt_path: &ReceiptPath,
+        _receipt: Receipt,
+    ) -> Result<(), ContextError> {
+        Err(unsupported("store_packet_receipt"))
+    }
+
+    fn store_packet_acknowledgement(
+        &mut self,
+        _ack_path: &AckPath,
+        _ack_commitment: AcknowledgementCommitment,
+    ) -> Result<(), ContextError> {
+        Err(unsupported("store_packet_acknowledgement"))
+    }
+
+    fn delete_packet_acknowledgement(&mut self, _ack_path: &AckPath) -> Result<(), ContextError> {
+        Err(unsupported("delete_packet_acknowledgement"))
+    }
+
+    fn store_connection_channels(
+        &mut self,
+        _conn_id: ConnectionId,
+        _port_id: PortId,
+        _channel_id: ChannelId,
+    ) -> Result<(), ContextError> {
+        Err(unsupported("store_connection_channels"))
+    }
+
+    fn store_channel(
+        &mut self,
+        _channel_end_path: &ChannelEndPath,
+        _channel_end: ChannelEnd,
+    ) -> Result<(), ContextError> {
+        Err(unsupported("store_channel"))
+    }
+
+    fn store_next_sequence_send(
+        &mut self,
+        _seq_send_path: &SeqSendPath,
+        _seq: Sequence,
+    ) -> Result<(), ContextError> {
+        Err(unsupported("store_next_sequence_send"))
+    }
+
+    fn store_next_sequence_recv(
+        &mut self,
+        _seq_recv_path: &SeqRecvPath,
+        _seq: Sequence,
+    ) -> Result<(), ContextError> {
+        Err(unsupported("store_next_sequence_recv"))
+    }
+
+    fn store_next_sequence_ack(
+        &mut self,
+        _seq_ack_path: &SeqAckPath,
+        _seq: Sequence,
+    ) -> Result<(), ContextError> {
+        Err(unsupported("store_next_sequence_ack"))
+    }
+
+    fn increase_channel_counter(&mut self) {}
+
+    fn emit_ibc_event(&mut self, _event: IbcEvent) {}
+
+    fn log_message(&mut self, _message: String) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client_state::tests::get_membership_proof;
+    use crate::errors::Error;
+    use crate::eth_client_type;
+    use crate::header::Header;
+    use crate::verify::simulate_update;
+    use crate::verify::tests::{header_scenario, misbehaviour_scenario};
+    use ethereum_consensus::types::U64;
+    use ibc::core::ics24_host::Path;
+
+    fn client_id() -> ClientId {
+        ClientId::new(eth_client_type(), 0).unwrap()
+    }
+
+    /// Update the client as the host that writes the `UpdateResult` of `ClientState::update_state` does
+    fn update_with_result(ctx: &mut MockContext<32>, header: Header<32>) -> UpdateResult<32> {
+        let client_state = ctx.stored_client_state(&client_id()).unwrap().clone();
+        let stored_heights = ctx.consensus_state_heights(&client_id());
+        let res = client_state
+            .update_state(&*ctx, client_id(), header.into(), &stored_heights)
+            .unwrap();
+        ctx.apply_update(&client_id(), &res);
+        res
+    }
+
+    // the update handler of ibc-rs writes the consensus state of `check_header_and_update_state` at the latest height
+    #[test]
+    fn test_update_handler_below_latest_height() {
+        let (client_state, trusted_consensus_state, header, now) = header_scenario();
+        let mut ctx = MockContext::new(now, Height::new(0, 100).unwrap());
+        ctx.create_client(
+            client_id(),
+            client_state.clone(),
+            trusted_consensus_state.clone(),
+        )
+        .unwrap();
+        // the client has advanced beyond the header
+        let header_height = header.height();
+        let latest_height = header_height.add(100);
+        let mut advanced_client_state = client_state;
+        advanced_client_state.latest_execution_block_number = U64(latest_height.revision_height());
+        let latest_consensus_state = ConsensusState {
+            storage_root: [2u8; 32].to_vec().into(),
+            ..trusted_consensus_state
+        };
+        ctx.store_client_state(client_id(), advanced_client_state.clone());
+        ctx.store_consensus_state(client_id(), latest_height, latest_consensus_state.clone());
+        ctx.advance(Duration::from_secs(12));
+
+        // a new consensus state below the latest height is rejected rather than written at the latest height,
+        // and the processed time of the latest height is not reset
+        let processed_time = ctx.client_update_time(&client_id(), &latest_height);
+        let res = ctx.update_client(&client_id(), header.clone().into());
+        assert!(
+            matches!(&res, Err(e) if e.to_string().contains(&Error::HistoricalConsensusStateNotSupported { height: header_height, latest_height }.to_string())),
+            "{:?}",
+            res
+        );
+        assert_eq!(
+            ctx.stored_client_state(&client_id()),
+            Some(&advanced_client_state)
+        );
+        assert_eq!(
+            ctx.stored_consensus_state(&client_id(), &latest_height),
+            Some(&latest_consensus_state)
+        );
+        assert_eq!(
+            ctx.client_update_time(&client_id(), &latest_height),
+            processed_time
+        );
+        assert!(ctx
+            .stored_consensus_state(&client_id(), &header_height)
+            .is_none());
+
+        // `update_state` rejects it too, so the host cannot store it either
+        let res = advanced_client_state.update_state(&ctx, client_id(), header.into(), &[]);
+        assert!(res.is_err(), "{:?}", res);
+    }
+
+    // the expired consensus states are pruned by the host that writes the `UpdateResult` of `ClientState::update_state`
+    #[test]
+    fn test_update_state_prunes_expired_consensus_states() {
+        let (mut client_state, trusted_consensus_state, header, now) = header_scenario();
+        client_state.trusting_period = Duration::from_secs(120);
+        client_state.max_clock_drift = Duration::from_secs(60);
+        let mut ctx = MockContext::new(now, Height::new(0, 100).unwrap());
+        ctx.create_client(
+            client_id(),
+            client_state.clone(),
+            trusted_consensus_state.clone(),
+        )
+        .unwrap();
+        // a consensus state expires if it is older than `trusting_period + max_clock_drift` before the header timestamp.
+        // Only the timestamps of the consensus states matter to the pruning, not their heights.
+        let expiry = client_state.trusting_period + client_state.max_clock_drift;
+        let expiry_end = header.timestamp.nanoseconds() - expiry.as_nanos() as u64;
+        let height = |block_number| Height::new(0, block_number).unwrap();
+        for (block_number, timestamp) in
+            [(10, expiry_end - 1), (11, expiry_end), (12, expiry_end + 1)]
+        {
+            ctx.store_consensus_state(
+                client_id(),
+                height(block_number),
+                ConsensusState {
+                    timestamp: Timestamp::from_nanoseconds(timestamp).unwrap(),
+                    ..trusted_consensus_state.clone()
+                },
+            );
+        }
+        ctx.advance(Duration::from_secs(12));
+
+        // nothing is pruned unless the host passes the heights of the stored consensus states
+        let res = client_state
+            .update_state(&ctx, client_id(), header.clone().into(), &[])
+            .unwrap();
+        assert!(res.prunable_heights.is_empty());
+        // the update handler of ibc-rs cannot pass them
+        let mut handler_ctx = ctx.clone();
+        handler_ctx
+            .update_client(&client_id(), header.clone().into())
+            .unwrap();
+        assert!(handler_ctx
+            .stored_consensus_state(&client_id(), &height(10))
+            .is_some());
+
+        let res = update_with_result(&mut ctx, header.clone());
+        assert_eq!(res.prunable_heights, vec![height(10)]);
+        assert_eq!(
+            ctx.consensus_state_heights(&client_id()),
+            vec![
+                header.trusted_height(),
+                header.height(),
+                height(11),
+                height(12)
+            ]
+        );
+        assert!(ctx.client_update_time(&client_id(), &height(10)).is_err());
+        assert!(ctx.client_update_height(&client_id(), &height(10)).is_err());
+    }
+
+    // the same scenario as `verify::tests::test_verify_header`
+    #[test]
+    fn test_client_lifecycle() {
+        let (client_state, trusted_consensus_state, header, now) = header_scenario();
+        let mut ctx = MockContext::new(now, Height::new(0, 100).unwrap());
+        ctx.create_client(
+            client_id(),
+            client_state.clone(),
+            trusted_consensus_state.clone(),
+        )
+        .unwrap();
+        let trusted_height = header.trusted_height();
+        assert_eq!(
+            ctx.consensus_state_heights(&client_id()),
+            vec![trusted_height]
+        );
+
+        // the host time is recorded as the update time of the new consensus state
+        ctx.advance(Duration::from_secs(12));
+        let header_height = header.height();
+        ctx.update_client(&client_id(), header.clone().into())
+            .unwrap();
+        let new_client_state = ctx.stored_client_state(&client_id()).unwrap().clone();
+        assert_eq!(
+            Ics2ClientState::latest_height(&new_client_state),
+            header_height
+        );
+        let new_consensus_state = ctx
+            .stored_consensus_state(&client_id(), &header_height)
+            .unwrap()
+            .clone();
+        assert_eq!(new_consensus_state.timestamp, header.timestamp);
+        assert_eq!(
+            ctx.client_update_time(&client_id(), &header_height)
+                .unwrap(),
+            ctx.host_timestamp().unwrap()
+        );
+        assert_eq!(
+            ctx.client_update_height(&client_id(), &header_height)
+                .unwrap(),
+            ctx.host_height().unwrap()
+        );
+        assert_eq!(
+            ctx.consensus_state_heights(&client_id()),
+            vec![trusted_height, header_height]
+        );
+        assert!(ctx
+            .next_consensus_state(&client_id(), &trusted_height)
+            .unwrap()
+            .is_some());
+        assert!(ctx
+            .prev_consensus_state(&client_id(), &trusted_height)
+            .unwrap()
+            .is_none());
+
+        // the storage of the IBC contract is unchanged, so the membership proof verifies against the proven storage root
+        let (path, proof, value) = get_membership_proof();
+        let res = new_client_state.verify_membership(
+            header_height,
+            &ctx.commitment_prefix(),
+            &proof.try_into().unwrap(),
+            new_consensus_state.root(),
+            Path::from_str(&path).unwrap(),
+            value,
+        );
+        assert!(res.is_ok(), "{:?}", res);
+
+        // the duplicate header is rejected, so the consensus state and its processed time and height are kept
+        let processed_time = ctx
+            .client_update_time(&client_id(), &header_height)
+            .unwrap();
+        let processed_height = ctx
+            .client_update_height(&client_id(), &header_height)
+            .unwrap();
+        ctx.advance(Duration::from_secs(12));
+        let res = ctx.update_client(&client_id(), header.clone().into());
+        assert!(
+            matches!(&res, Err(e) if e.to_string().contains(&Error::ConsensusStateAlreadyStored { height: header_height }.to_string())),
+            "{:?}",
+            res
+        );
+        assert_eq!(
+            ctx.stored_consensus_state(&client_id(), &header_height),
+            Some(&new_consensus_state)
+        );
+        assert_eq!(
+            ctx.client_update_time(&client_id(), &header_height)
+                .unwrap(),
+            processed_time
+        );
+        assert_eq!(
+            ctx.client_update_height(&client_id(), &header_height)
+                .unwrap(),
+            processed_height
+        );
+
+        // the trusted consensus state expires after the trusting period
+        ctx.advance(client_state.trusting_period);
+        let res = ctx.update_client(&client_id(), header.into());
+        assert!(res.is_err(), "{:?}", res);
+
+        let res = ctx.update_client(
+            &ClientId::new(eth_client_type(), 1).unwrap(),
+            Any::default(),
+        );
+        assert!(
+            matches!(res, Err(ClientError::ClientNotFound { .. })),
+            "{:?}",
+            res
+        );
+    }
+
+    #[test]
+    fn test_simulate_update() {
+        let (client_state, trusted_consensus_state, header, now) = header_scenario();
+        let mut direct = MockContext::new(now, Height::new(0, 100).unwrap());
+        direct
+            .create_client(
+                client_id(),
+                client_state.clone(),
+                trusted_consensus_state.clone(),
+            )
+            .unwrap();
+        direct.advance(Duration::from_secs(12));
+        let mut simulated = direct.clone();
+        let mut handled = direct.clone();
+
+        let expected = update_with_result(&mut direct, header.clone());
+        let res = simulate_update(
+            &client_state,
+            &trusted_consensus_state,
+            None,
+            &simulated.stored_consensus_states(&client_id()),
+            header.clone(),
+            &simulated,
+        )
+        .unwrap();
+        assert_eq!(res, expected);
+        // the dry run writes nothing
+        assert_eq!(
+            simulated.consensus_state_heights(&client_id()),
+            vec![header.trusted_height()]
+        );
+
+        simulated.apply_update(&client_id(), &res);
+        assert_states_eq(&simulated, &direct);
+        // the update handler of ibc-rs writes the same states for an update that advances the latest height
+        handled
+            .update_client(&client_id(), header.clone().into())
+            .unwrap();
+        assert_states_eq(&handled, &direct);
+
+        // the re-submission of the header keeps the stored consensus state
+        let new_client_state = direct.stored_client_state(&client_id()).unwrap().clone();
+        let stored_consensus_state = direct
+            .stored_consensus_state(&client_id(), &header.height())
+            .cloned();
+        direct.advance(Duration::from_secs(12));
+        simulated.advance(Duration::from_secs(12));
+        let expected = update_with_result(&mut direct, header.clone());
+        let res = simulate_update(
+            &new_client_state,
+            &trusted_consensus_state,
+            stored_consensus_state.as_ref(),
+            &simulated.stored_consensus_states(&client_id()),
+            header,
+            &simulated,
+        )
+        .unwrap();
+        assert_eq!(res, expected);
+        simulated.apply_update(&client_id(), &res);
+        assert_states_eq(&simulated, &direct);
+    }
+
+    /// Asserts that the contexts store the same client state and the same consensus states with their update times and heights
+    fn assert_states_eq(actual: &MockContext<32>, expected: &MockContext<32>) {
+        assert_eq!(
+            actual.stored_client_state(&client_id()),
+            expected.stored_client_state(&client_id())
+        );
+        assert_eq!(
+            actual.stored_consensus_states(&client_id()),
+            expected.stored_consensus_states(&client_id())
+        );
+        for height in expected.consensus_state_heights(&client_id()) {
+            assert_eq!(
+                actual.client_update_time(&client_id(), &height).unwrap(),
+                expected.client_update_time(&client_id(), &height).unwrap()
+            );
+            assert_eq!(
+                actual.client_update_height(&client_id(), &height).unwrap(),
+                expected
+                    .client_update_height(&client_id(), &height)
+                    .unwrap()
+            );
+        }
+    }
+
+    // the same scenario as `verify::tests::test_verify_misbehaviour`
+    #[test]
+    fn test_misbehaviour_freezes_client() {
+        let (client_state, trusted_consensus_state, misbehaviour, now) = misbehaviour_scenario();
+        let mut ctx = MockContext::new(now, Height::new(0, 100).unwrap());
+        ctx.create_client(client_id(), client_state, trusted_consensus_state)
+            .unwrap();
+
+        let misbehaviour_height = misbehaviour.height();
+        let res = ctx
+            .submit_misbehaviour(&client_id(), misbehaviour.clone().into())
+            .unwrap();
+        let frozen_client_state = ctx.stored_client_state(&client_id()).unwrap();
+        assert_eq!(frozen_client_state, &res.client_state);
+        assert_eq!(frozen_client_state.frozen_height, Some(misbehaviour_height));
+        assert_eq!(
+            frozen_client_state.misbehaviour_evidence_digest,
+            Some(res.evidence.digest())
+        );
+        assert_eq!(res.evidence.client_id, client_id());
+
+        // a frozen client accepts neither misbehaviours nor headers, and the error reports the frozen height
+        let expected = ClientError::from(Error::ClientFrozen {
+            frozen_height: misbehaviour_height,
+        })
+        .to_string();
+        let res = ctx.submit_misbehaviour(&client_id(), misbehaviour.into());
+        assert_eq!(res.unwrap_err().to_string(), expected);
+        let (_, _, header, _) = header_scenario();
+        let res = ctx.client_state_of(&client_id()).unwrap().update_state(
+            &ctx,
+            client_id(),
+            header.clone().into(),
+            &ctx.consensus_state_heights(&client_id()),
+        );
+        assert_eq!(res.unwrap_err().to_string(), expected);
+        // the update handler of ibc-rs rejects the update before calling the client
+        let res = ctx.update_client(&client_id(), header.into());
+        assert!(
+            matches!(res, Err(ClientError::ClientFrozen { .. })),
+            "{:?}",
+            res
+        );
+    }
+}
@@ -0,0 +1,209 @@
+//! Instrumentation of the header verification with `tracing`, which is enabled by the `tracing` feature
+//!
+//! `verify::verify_header_with_cache` enters the span `verify_header` with the trusted height, the block number and the signature slot of the header,
+//! and the following spans for its phases in order:
+//! - `validate_trusted_sync_committee`: the trusted height, whether the trusted sync committee is the next one, and its period
+//! - `verify_sync_aggregate`: the signature slot and period; the sync committee participation, the signature and the merkle branches of the consensus update,
+//!   including the finality branch, are verified here since the verifier checks them together
+//! - `verify_execution_update`: the finalized slot and the block number
+//! - `verify_account_update`: the block number
+//! - `apply_updates`: the finalized slot and the block number of the new consensus state
+//!
+//! The proto decoding of a header from `Any` is in the span `decode_header` with the length of the payload,
+//! and the writes of the new states by the ibc-rs 0.48 client are in the span `store_state` with the height.
+//! A phase that rejects the header emits a `WARN` event in its span with the error code and the message of the error.
+//!
+//! Without the feature, the spans and the events are compiled away.
+use crate::errors::Error;
+
+/// Enters the span of the given name and fields until the end of the enclosing block
+macro_rules! trace_span {
+    ($name:literal $(, $key:ident = $value:expr)* $(,)?) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!($name $(, $key = $value)*).entered();
+    };
+}
+pub(crate) use trace_span;
+
+/// Emits the rejection event with the code of the error in the current span if `res` is an error, and returns `res` as is
+#[inline]
+pub(crate) fn traced<T>(res: Result<T, Error>) -> Result<T, Error> {
+    #[cfg(feature = "tracing")]
+    if let Err(err) = &res {
+        tracing::warn!(code = err.code(), error = %err, "rejected");
+    }
+    res
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use crate::verify::tests::header_scenario;
+    use crate::verify::verify_header;
+    use core::fmt::{Debug, Write};
+    use ethereum_consensus::types::H256;
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    #[derive(Debug)]
+    struct SpanRecord {
+        name: &'static str,
+        fields: String,
+        parent: Option<usize>,
+    }
+
+    #[derive(Debug)]
+    struct EventRecord {
+        span: Option<usize>,
+        fields: String,
+    }
+
+    #[derive(Default)]
+    struct Records {
+        spans: Vec<SpanRecord>,
+        events: Vec<EventRecord>,
+        stack: Vec<usize>,
+    }
+
+    /// A subscriber recording the spans in the order of creation and the events
+    #[derive(Clone, Default)]
+    struct Capture(Arc<Mutex<Records>>);
+
+    struct FieldsRecorder(String);
+
+    impl Visit for FieldsRecorder {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            write!(self.0, "{}={:?} ", field.name(), value).unwrap();
+        }
+    }
+
+    impl Subscriber for Capture {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, attrs: &Attributes<'_>) -> Id {
+            let mut fields = FieldsRecorder(String::new());
+            attrs.record(&mut fields);
+            let mut records = self.0.lock().unwrap();
+            let parent = match attrs.parent() {
+                Some(id) => Some(id.into_u64() as usize - 1),
+                None => records.stack.last().copied(),
+            };
+            records.spans.push(SpanRecord {
+                name: attrs.metadata().name(),
+                fields: fields.0,
+                parent,
+            });
+            Id::from_u64(records.spans.len() as u64)
+        }
+
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut fields = FieldsRecorder(String::new());
+            event.record(&mut fields);
+            let mut records = self.0.lock().unwrap();
+            let span = records.stack.last().copied();
+            records.events.push(EventRecord {
+                span,
+                fields: fields.0,
+            });
+        }
+
+        fn enter(&self, span: &Id) {
+            self.0
+                .lock()
+                .unwrap()
+                .stack
+                .push(span.into_u64() as usize - 1);
+        }
+
+        fn exit(&self, _: &Id) {
+            self.0.lock().unwrap().stack.pop();
+        }
+    }
+
+    /// Returns the names of the spans with their parents
+    fn span_tree(records: &Records) -> Vec<(&'static str, Option<&'static str>)> {
+        records
+            .spans
+            .iter()
+            .map(|span| (span.name, span.parent.map(|i| records.spans[i].name)))
+            .collect()
+    }
+
+    #[test]
+    fn test_verify_header_spans() {
+        let (client_state, trusted_consensus_state, header, now) = header_scenario();
+        let capture = Capture::default();
+        let res = tracing::subscriber::with_default(capture.clone(), || {
+            verify_header(&client_state, &trusted_consensus_state, header.clone(), now)
+        });
+        assert!(res.is_ok(), "{:?}", res);
+        let records = capture.0.lock().unwrap();
+        assert_eq!(
+            span_tree(&records),
+            [
+                ("verify_header", None),
+                ("validate_trusted_sync_committee", Some("verify_header")),
+                ("verify_sync_aggregate", Some("verify_header")),
+                ("verify_execution_update", Some("verify_header")),
+                ("verify_account_update", Some("verify_header")),
+                ("apply_updates", Some("verify_header")),
+            ]
+        );
+        let fields = &records.spans[0].fields;
+        assert!(
+            fields.contains(&format!(
+                "signature_slot={} ",
+                header.consensus_update.signature_slot
+            )),
+            "{}",
+            fields
+        );
+        assert!(
+            fields.contains(&format!(
+                "block_number={} ",
+                header.execution_update.block_number
+            )),
+            "{}",
+            fields
+        );
+        assert!(records.spans[2].fields.contains("period="));
+        assert!(records.events.is_empty(), "{:?}", records.events);
+        drop(records);
+
+        // the execution update is rejected, so the following phases are not entered
+        let mut invalid = header;
+        invalid.execution_update.state_root = H256::default();
+        let capture = Capture::default();
+        let res = tracing::subscriber::with_default(capture.clone(), || {
+            verify_header(&client_state, &trusted_consensus_state, invalid, now)
+        });
+        let err = res.unwrap_err();
+        assert_eq!(err.code(), 2003);
+        let records = capture.0.lock().unwrap();
+        assert_eq!(
+            span_tree(&records),
+            [
+                ("verify_header", None),
+                ("validate_trusted_sync_committee", Some("verify_header")),
+                ("verify_sync_aggregate", Some("verify_header")),
+                ("verify_execution_update", Some("verify_header")),
+            ]
+        );
+        assert_eq!(records.events.len(), 1, "{:?}", records.events);
+        let event = &records.events[0];
+        assert_eq!(event.span, Some(3));
+        assert!(event.fields.contains("code=2003 "), "{}", event.fields);
+        assert!(
+            event.fields.contains(&format!("error={} ", err)),
+            "{}",
+            event.fields
+        );
+    }
+}
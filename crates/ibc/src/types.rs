@@ -1,10 +1,13 @@
-use crate::client_state::ETHEREUM_CLIENT_REVISION_NUMBER;
-use crate::commitment::decode_eip1184_rlp_proof;
+use crate::bls::{validate_public_key, validate_signature};
+use crate::cache::{validate_sync_committee, NoCache, SyncCommitteeCache};
+use crate::client_state::{ClientState, ETHEREUM_CLIENT_REVISION_NUMBER};
+use crate::commitment::{check_limit, decode_eip1186_rlp_proof};
+use crate::consensus_state::ConsensusState;
 use crate::errors::Error;
 use crate::internal_prelude::*;
 use ethereum_consensus::beacon::{BeaconBlockHeader, Slot};
 use ethereum_consensus::bls::{PublicKey, Signature};
-use ethereum_consensus::sync_protocol::{SyncAggregate, SyncCommittee};
+use ethereum_consensus::sync_protocol::{SyncAggregate, SyncCommittee, SyncCommitteePeriod};
 use ethereum_consensus::types::{H256, U64};
 use ethereum_ibc_proto::ibc::core::client::v1::Height as ProtoHeight;
 use ethereum_ibc_proto::ibc::lightclients::ethereum::v1::{
@@ -20,25 +23,42 @@ use ssz_rs::{Bitvector, Deserialize, Vector};
 #[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct ConsensusUpdateInfo<const SYNC_COMMITTEE_SIZE: usize> {
     /// Header attested to by the sync committee
+    #[cfg_attr(feature = "json", serde(with = "crate::json"))]
     pub attested_header: BeaconBlockHeader,
     /// Next sync committee contained in `attested_header.state_root`
     /// 0: sync committee
     /// 1: branch indicating the next sync committee in the tree corresponding to `attested_header.state_root`
+    #[cfg_attr(feature = "json", serde(with = "crate::json"))]
     pub next_sync_committee: Option<(SyncCommittee<SYNC_COMMITTEE_SIZE>, Vec<H256>)>,
     /// Finalized header contained in `attested_header.state_root`
     /// 0: header
     /// 1. branch indicating the header in the tree corresponding to `attested_header.state_root`
+    #[cfg_attr(feature = "json", serde(with = "crate::json"))]
     pub finalized_header: (BeaconBlockHeader, Vec<H256>),
     /// Sync committee aggregate signature
+    #[cfg_attr(feature = "json", serde(with = "crate::json"))]
     pub sync_aggregate: SyncAggregate<SYNC_COMMITTEE_SIZE>,
     /// Slot at which the aggregate signature was created (untrusted)
+    #[cfg_attr(feature = "json", serde(with = "crate::json"))]
     pub signature_slot: Slot,
     /// Execution payload contained in the finalized beacon block's body
+    #[cfg_attr(feature = "json", serde(with = "crate::json"))]
     pub finalized_execution_root: H256,
     /// Execution payload branch indicating the payload in the tree corresponding to the finalized block's body
+    #[cfg_attr(feature = "json", serde(with = "crate::json"))]
     pub finalized_execution_branch: Vec<H256>,
 }
 
+impl<const SYNC_COMMITTEE_SIZE: usize> ConsensusUpdateInfo<SYNC_COMMITTEE_SIZE> {
+    /// Returns the sync committee period of `signature_slot`, i.e. the period of the sync committee that signed the update
+    pub fn signature_period(
+        &self,
+        client_state: &ClientState<SYNC_COMMITTEE_SIZE>,
+    ) -> SyncCommitteePeriod {
+        client_state.compute_period_at_slot(self.signature_slot)
+    }
+}
+
 impl<const SYNC_COMMITTEE_SIZE: usize> ConsensusUpdate<SYNC_COMMITTEE_SIZE>
     for ConsensusUpdateInfo<SYNC_COMMITTEE_SIZE>
 {
@@ -74,13 +94,27 @@ impl<const SYNC_COMMITTEE_SIZE: usize> ConsensusUpdate<SYNC_COMMITTEE_SIZE>
 #[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct ExecutionUpdateInfo {
     /// State root of the execution payload
+    #[cfg_attr(feature = "json", serde(with = "crate::json"))]
     pub state_root: H256,
     /// Branch indicating the state root in the tree corresponding to the execution payload
+    #[cfg_attr(feature = "json", serde(with = "crate::json"))]
     pub state_root_branch: Vec<H256>,
     /// Block number of the execution payload
+    #[cfg_attr(feature = "json", serde(with = "crate::json"))]
     pub block_number: U64,
     /// Branch indicating the block number in the tree corresponding to the execution payload
+    #[cfg_attr(feature = "json", serde(with = "crate::json"))]
     pub block_number_branch: Vec<H256>,
+    /// Block hash of the execution payload, which becomes `ConsensusState::current_block_hash`
+    ///
+    /// If `None`, the block hash is not proven and the new consensus state has no block hash.
+    #[serde(default)]
+    #[cfg_attr(feature = "json", serde(with = "crate::json"))]
+    pub block_hash: Option<H256>,
+    /// Branch indicating the block hash in the tree corresponding to the execution payload, which must be empty if `block_hash` is `None`
+    #[serde(default)]
+    #[cfg_attr(feature = "json", serde(with = "crate::json"))]
+    pub block_hash_branch: Vec<H256>,
 }
 
 impl ExecutionUpdate for ExecutionUpdateInfo {
@@ -102,10 +136,25 @@ impl ExecutionUpdate for ExecutionUpdateInfo {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct TrustedSyncCommittee<const SYNC_COMMITTEE_SIZE: usize> {
     /// height(i.e. execution's block number) of consensus state to trusted sync committee stored at
+    #[cfg_attr(feature = "json", serde(with = "crate::json"))]
+    #[cfg_attr(
+        all(feature = "schema", feature = "json"),
+        schemars(with = "crate::schema::HeightJson")
+    )]
+    #[cfg_attr(
+        all(feature = "schema", not(feature = "json")),
+        schemars(with = "crate::schema::Height")
+    )]
     pub height: Height,
     /// trusted sync committee
+    #[cfg_attr(feature = "json", serde(with = "crate::json"))]
+    #[cfg_attr(
+        feature = "schema",
+        schemars(with = "crate::schema::SyncCommittee<SYNC_COMMITTEE_SIZE>")
+    )]
     pub sync_committee: SyncCommittee<SYNC_COMMITTEE_SIZE>,
     /// since the consensus state contains a current and next sync committee, this flag determines which one to refer to
     pub is_next: bool,
@@ -113,14 +162,39 @@ pub struct TrustedSyncCommittee<const SYNC_COMMITTEE_SIZE: usize> {
 
 impl<const SYNC_COMMITTEE_SIZE: usize> TrustedSyncCommittee<SYNC_COMMITTEE_SIZE> {
     pub fn validate(&self) -> Result<(), Error> {
+        self.validate_with_cache(&mut NoCache)
+    }
+
+    /// `validate` that skips the validation of the sync committee if it is in `cache`
+    pub fn validate_with_cache<C: SyncCommitteeCache<SYNC_COMMITTEE_SIZE> + ?Sized>(
+        &self,
+        cache: &mut C,
+    ) -> Result<(), Error> {
         if self.height.revision_number() != ETHEREUM_CLIENT_REVISION_NUMBER {
             return Err(Error::UnexpectedHeightRevisionNumber {
                 expected: ETHEREUM_CLIENT_REVISION_NUMBER,
                 got: self.height.revision_number(),
             });
         }
-        self.sync_committee.validate()?;
-        Ok(())
+        validate_sync_committee(cache, &self.sync_committee)
+    }
+
+    /// Returns `true` if the trusted sync committee is the sync committee of `period`
+    ///
+    /// `consensus_state` is the consensus state stored at `height`. Its current sync committee is the one of the period of its slot,
+    /// and the next sync committee is the one of the following period. Relayers can use this to choose the trusted sync committee for an update signed in `period`.
+    pub fn covers_period(
+        &self,
+        client_state: &ClientState<SYNC_COMMITTEE_SIZE>,
+        consensus_state: &ConsensusState,
+        period: SyncCommitteePeriod,
+    ) -> bool {
+        let trusted_period = client_state.compute_period_at_slot(consensus_state.slot);
+        if self.is_next {
+            trusted_period + 1 == period
+        } else {
+            trusted_period == period
+        }
     }
 }
 
@@ -130,34 +204,22 @@ impl<const SYNC_COMMITTEE_SIZE: usize> TryFrom<ProtoTrustedSyncCommittee>
     type Error = Error;
 
     fn try_from(value: ProtoTrustedSyncCommittee) -> Result<Self, Error> {
-        let trusted_height = value
-            .trusted_height
-            .as_ref()
-            .ok_or(Error::proto_missing("trusted_height"))?;
+        let trusted_height = value.trusted_height.as_ref().ok_or(Error::proto_missing(
+            "TrustedSyncCommittee",
+            "trusted_height",
+        ))?;
         Ok(TrustedSyncCommittee {
             height: Height::new(
                 trusted_height.revision_number,
                 trusted_height.revision_height,
+            )
+            .map_err(|e| Error::decode_error("trusted_height", e.into()))?,
+            sync_committee: decode_required(
+                "TrustedSyncCommittee",
+                "sync_committee",
+                value.sync_committee,
+                convert_proto_to_sync_committee,
             )?,
-            sync_committee: SyncCommittee {
-                pubkeys: Vector::<PublicKey, SYNC_COMMITTEE_SIZE>::from_iter(
-                    value
-                        .sync_committee
-                        .as_ref()
-                        .ok_or(Error::proto_missing("sync_committee"))?
-                        .pubkeys
-                        .clone()
-                        .into_iter()
-                        .map(|pk| pk.try_into())
-                        .collect::<Result<Vec<PublicKey>, _>>()?,
-                ),
-                aggregate_pubkey: PublicKey::try_from(
-                    value
-                        .sync_committee
-                        .ok_or(Error::proto_missing("sync_committee"))?
-                        .aggregate_pubkey,
-                )?,
-            },
             is_next: value.is_next,
         })
     }
@@ -188,10 +250,31 @@ impl<const SYNC_COMMITTEE_SIZE: usize> From<TrustedSyncCommittee<SYNC_COMMITTEE_
 
 #[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct AccountUpdateInfo {
+    #[cfg_attr(feature = "json", serde(with = "crate::json"))]
     pub account_proof: Vec<Vec<u8>>,
+    #[cfg_attr(feature = "json", serde(with = "crate::json"))]
     pub account_storage_root: H256,
 }
 
+impl AccountUpdateInfo {
+    /// Stateless validation of the account update
+    ///
+    /// The account proof must be non-empty and must not contain an empty node, and the storage root must not be zero.
+    /// A zero storage root can only be proven for an account that does not exist, which is never a valid update of the IBC contract.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.account_proof.is_empty() {
+            return Err(Error::EmptyAccountProof);
+        }
+        if let Some(index) = self.account_proof.iter().position(|node| node.is_empty()) {
+            return Err(Error::EmptyAccountProofNode { index });
+        }
+        if self.account_storage_root == H256::default() {
+            return Err(Error::ZeroAccountStorageRoot);
+        }
+        Ok(())
+    }
+}
+
 impl From<AccountUpdateInfo> for ProtoAccountUpdate {
     fn from(value: AccountUpdateInfo) -> Self {
         Self {
@@ -204,14 +287,24 @@ impl From<AccountUpdateInfo> for ProtoAccountUpdate {
 impl TryFrom<ProtoAccountUpdate> for AccountUpdateInfo {
     type Error = Error;
     fn try_from(value: ProtoAccountUpdate) -> Result<Self, Self::Error> {
-        Ok(Self {
-            account_proof: decode_eip1184_rlp_proof(value.account_proof)?,
-            account_storage_root: H256::from_slice(&value.account_storage_root),
-        })
+        let account_update = Self {
+            account_proof: decode_eip1186_rlp_proof(value.account_proof)
+                .map_err(|e| Error::decode_error("account_proof", e))?,
+            account_storage_root: decode_h256("account_storage_root", &value.account_storage_root)?,
+        };
+        account_update.validate().map_err(|e| {
+            let field = match e {
+                Error::ZeroAccountStorageRoot => "account_storage_root",
+                _ => "account_proof",
+            };
+            Error::decode_error(field, e)
+        })?;
+        Ok(account_update)
     }
 }
 
-fn encode_account_proof(bz: Vec<Vec<u8>>) -> Vec<u8> {
+/// Encodes the proof nodes as the rlp list `List<List>` accepted by `decode_eip1186_rlp_proof`
+pub(crate) fn encode_account_proof(bz: Vec<Vec<u8>>) -> Vec<u8> {
     let proof: Vec<Vec<u8>> = bz.into_iter().map(|b| b.to_vec()).collect();
     let mut stream = rlp::RlpStream::new();
     stream.begin_list(proof.len());
@@ -227,9 +320,9 @@ pub(crate) fn convert_proto_to_header(
     Ok(BeaconBlockHeader {
         slot: header.slot.into(),
         proposer_index: header.proposer_index.into(),
-        parent_root: H256::from_slice(&header.parent_root),
-        state_root: H256::from_slice(&header.state_root),
-        body_root: H256::from_slice(&header.body_root),
+        parent_root: decode_h256("parent_root", &header.parent_root)?,
+        state_root: decode_h256("state_root", &header.state_root)?,
+        body_root: decode_h256("body_root", &header.body_root)?,
     })
 }
 
@@ -245,21 +338,22 @@ pub(crate) fn convert_header_to_proto(header: &BeaconBlockHeader) -> ProtoBeacon
 
 pub(crate) fn convert_proto_to_execution_update(
     execution_update: ProtoExecutionUpdate,
-) -> ExecutionUpdateInfo {
-    ExecutionUpdateInfo {
-        state_root: H256::from_slice(&execution_update.state_root),
-        state_root_branch: execution_update
-            .state_root_branch
-            .into_iter()
-            .map(|n| H256::from_slice(&n))
-            .collect(),
+) -> Result<ExecutionUpdateInfo, Error> {
+    Ok(ExecutionUpdateInfo {
+        state_root: decode_h256("state_root", &execution_update.state_root)?,
+        state_root_branch: decode_branch("state_root_branch", execution_update.state_root_branch)?,
         block_number: execution_update.block_number.into(),
-        block_number_branch: execution_update
-            .block_number_branch
-            .into_iter()
-            .map(|n| H256::from_slice(&n))
-            .collect(),
-    }
+        block_number_branch: decode_branch(
+            "block_number_branch",
+            execution_update.block_number_branch,
+        )?,
+        block_hash: if execution_update.block_hash.is_empty() {
+            None
+        } else {
+            Some(decode_h256("block_hash", &execution_update.block_hash)?)
+        },
+        block_hash_branch: decode_branch("block_hash_branch", execution_update.block_hash_branch)?,
+    })
 }
 
 pub(crate) fn convert_execution_update_to_proto(
@@ -278,6 +372,15 @@ pub(crate) fn convert_execution_update_to_proto(
             .into_iter()
             .map(|n| n.as_bytes().to_vec())
             .collect(),
+        block_hash: execution_update
+            .block_hash
+            .map(|h| h.as_bytes().to_vec())
+            .unwrap_or_default(),
+        block_hash_branch: execution_update
+            .block_hash_branch
+            .into_iter()
+            .map(|n| n.as_bytes().to_vec())
+            .collect(),
     }
 }
 
@@ -293,19 +396,68 @@ pub(crate) fn convert_sync_aggregate_to_proto<const SYNC_COMMITTEE_SIZE: usize>(
     }
 }
 
-pub(crate) fn convert_proto_sync_aggregate<const SYNC_COMMITTEE_SIZE: usize>(
+/// Returns the byte length of the sync committee bits of `sync_committee_size` members, which are SSZ-encoded as a `Bitvector`
+pub const fn sync_committee_bits_len(sync_committee_size: usize) -> usize {
+    sync_committee_size.div_ceil(8)
+}
+
+/// Validate that `bz` is the canonical encoding of the sync committee bits before it is deserialized
+///
+/// The bits are `sync_committee_bits_len(SYNC_COMMITTEE_SIZE)` bytes, and the padding bits of the last byte beyond `SYNC_COMMITTEE_SIZE` must be zero.
+/// The sync committee sizes of the presets are multiples of 8, so their encodings have no padding bits.
+fn validate_sync_committee_bits<const SYNC_COMMITTEE_SIZE: usize>(bz: &[u8]) -> Result<(), Error> {
+    let expected_len = sync_committee_bits_len(SYNC_COMMITTEE_SIZE);
+    if bz.len() != expected_len {
+        return Err(Error::UnexpectedSyncCommitteeBitsLength {
+            sync_committee_size: SYNC_COMMITTEE_SIZE,
+            expected_len,
+            actual_len: bz.len(),
+        });
+    }
+    let padding = SYNC_COMMITTEE_SIZE % 8;
+    match bz.last() {
+        Some(&last_byte) if padding != 0 && last_byte >> padding != 0 => {
+            Err(Error::NonZeroSyncCommitteeBitsPadding {
+                sync_committee_size: SYNC_COMMITTEE_SIZE,
+                last_byte,
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Decode the sync aggregate of a consensus update
+///
+/// The sync committee bits must be `sync_committee_bits_len(SYNC_COMMITTEE_SIZE)` bytes with zero padding bits, and the signature must be a valid G2 point.
+pub fn convert_proto_sync_aggregate<const SYNC_COMMITTEE_SIZE: usize>(
     sync_aggregate: ProtoSyncAggregate,
 ) -> Result<SyncAggregate<SYNC_COMMITTEE_SIZE>, Error> {
+    validate_sync_committee_bits::<SYNC_COMMITTEE_SIZE>(&sync_aggregate.sync_committee_bits)
+        .map_err(|e| Error::decode_error("sync_committee_bits", e))?;
     Ok(SyncAggregate {
         sync_committee_bits: Bitvector::<SYNC_COMMITTEE_SIZE>::deserialize(
             sync_aggregate.sync_committee_bits.as_slice(),
         )
-        .map_err(|e| Error::DeserializeSyncCommitteeBitsError {
-            parent: e,
-            sync_committee_size: SYNC_COMMITTEE_SIZE,
-            sync_committee_bits: sync_aggregate.sync_committee_bits,
+        .map_err(|e| {
+            Error::decode_error(
+                "sync_committee_bits",
+                Error::DeserializeSyncCommitteeBitsError {
+                    parent: e,
+                    sync_committee_size: SYNC_COMMITTEE_SIZE,
+                    expected_len: sync_committee_bits_len(SYNC_COMMITTEE_SIZE),
+                    actual_len: sync_aggregate.sync_committee_bits.len(),
+                    sync_committee_bits: sync_aggregate.sync_committee_bits,
+                },
+            )
         })?,
-        sync_committee_signature: Signature::try_from(sync_aggregate.sync_committee_signature)?,
+        sync_committee_signature: Signature::try_from(sync_aggregate.sync_committee_signature)
+            .map_err(Error::from)
+            .and_then(|signature| {
+                validate_signature(&signature)
+                    .map_err(|reason| Error::InvalidSignature { reason })?;
+                Ok(signature)
+            })
+            .map_err(|e| Error::decode_error("sync_committee_signature", e))?,
     })
 }
 
@@ -349,75 +501,861 @@ pub(crate) fn convert_consensus_update_to_proto<const SYNC_COMMITTEE_SIZE: usize
 pub(crate) fn convert_proto_to_consensus_update<const SYNC_COMMITTEE_SIZE: usize>(
     consensus_update: ProtoConsensusUpdate,
 ) -> Result<ConsensusUpdateInfo<SYNC_COMMITTEE_SIZE>, Error> {
-    let attested_header = convert_proto_to_header(
-        consensus_update
-            .attested_header
-            .as_ref()
-            .ok_or(Error::proto_missing("attested_header"))?,
+    let attested_header = decode_required(
+        "ConsensusUpdate",
+        "attested_header",
+        consensus_update.attested_header.as_ref(),
+        convert_proto_to_header,
     )?;
-    let finalized_header = convert_proto_to_header(
-        consensus_update
-            .finalized_header
-            .as_ref()
-            .ok_or(Error::proto_missing("finalized_header"))?,
+    let finalized_header = decode_required(
+        "ConsensusUpdate",
+        "finalized_header",
+        consensus_update.finalized_header.as_ref(),
+        convert_proto_to_header,
     )?;
 
-    let finalized_execution_branch = consensus_update
-        .finalized_execution_branch
-        .into_iter()
-        .map(|b| H256::from_slice(&b))
-        .collect::<Vec<H256>>();
+    let finalized_execution_branch = decode_branch(
+        "finalized_execution_branch",
+        consensus_update.finalized_execution_branch,
+    )?;
+    let next_sync_committee = match consensus_update.next_sync_committee {
+        // an empty branch is kept so that `validate_basic` rejects it rather than the sync committee being dropped
+        Some(next_sync_committee) if !next_sync_committee.pubkeys.is_empty() => Some((
+            convert_proto_to_sync_committee(next_sync_committee)
+                .map_err(|e| Error::decode_error("next_sync_committee", e))?,
+            decode_branch(
+                "next_sync_committee_branch",
+                consensus_update.next_sync_committee_branch,
+            )?,
+        )),
+        _ => None,
+    };
     let consensus_update = ConsensusUpdateInfo {
         attested_header,
-        next_sync_committee: if consensus_update.next_sync_committee.is_none()
-            || consensus_update
-                .next_sync_committee
-                .as_ref()
-                .ok_or(Error::proto_missing("next_sync_committee"))?
-                .pubkeys
-                .is_empty()
-            || consensus_update.next_sync_committee_branch.is_empty()
-        {
-            None
-        } else {
-            Some((
-                SyncCommittee {
-                    pubkeys: Vector::<PublicKey, SYNC_COMMITTEE_SIZE>::from_iter(
-                        consensus_update
-                            .next_sync_committee
-                            .clone()
-                            .ok_or(Error::proto_missing("next_sync_committee"))?
-                            .pubkeys
-                            .into_iter()
-                            .map(|pk| pk.try_into())
-                            .collect::<Result<Vec<PublicKey>, _>>()?,
-                    ),
-                    aggregate_pubkey: PublicKey::try_from(
-                        consensus_update
-                            .next_sync_committee
-                            .ok_or(Error::proto_missing("next_sync_committee"))?
-                            .aggregate_pubkey,
-                    )?,
-                },
-                decode_branch(consensus_update.next_sync_committee_branch),
-            ))
-        },
+        next_sync_committee,
         finalized_header: (
             finalized_header,
-            decode_branch(consensus_update.finalized_header_branch),
+            decode_branch(
+                "finalized_header_branch",
+                consensus_update.finalized_header_branch,
+            )?,
         ),
-        sync_aggregate: convert_proto_sync_aggregate(
-            consensus_update
-                .sync_aggregate
-                .ok_or(Error::proto_missing("sync_aggregate"))?,
+        sync_aggregate: decode_required(
+            "ConsensusUpdate",
+            "sync_aggregate",
+            consensus_update.sync_aggregate,
+            convert_proto_sync_aggregate,
         )?,
         signature_slot: consensus_update.signature_slot.into(),
-        finalized_execution_root: H256::from_slice(&consensus_update.finalized_execution_root),
+        finalized_execution_root: decode_h256(
+            "finalized_execution_root",
+            &consensus_update.finalized_execution_root,
+        )?,
         finalized_execution_branch,
     };
     Ok(consensus_update)
 }
 
-pub(crate) fn decode_branch(bz: Vec<Vec<u8>>) -> Vec<H256> {
-    bz.into_iter().map(|b| H256::from_slice(&b)).collect()
+pub(crate) fn convert_proto_to_sync_committee<const SYNC_COMMITTEE_SIZE: usize>(
+    sync_committee: ProtoSyncCommittee,
+) -> Result<SyncCommittee<SYNC_COMMITTEE_SIZE>, Error> {
+    // `Vector::from_iter` panics unless exactly `SYNC_COMMITTEE_SIZE` keys are given
+    if sync_committee.pubkeys.len() != SYNC_COMMITTEE_SIZE {
+        return Err(Error::decode_error(
+            "pubkeys",
+            Error::UnexpectedSyncCommitteeSize {
+                expected: SYNC_COMMITTEE_SIZE,
+                actual: sync_committee.pubkeys.len(),
+            },
+        ));
+    }
+    Ok(SyncCommittee {
+        pubkeys: Vector::<PublicKey, SYNC_COMMITTEE_SIZE>::from_iter(
+            sync_committee
+                .pubkeys
+                .into_iter()
+                .enumerate()
+                .map(|(i, pk)| {
+                    PublicKey::try_from(pk)
+                        .map_err(Error::from)
+                        .and_then(|pubkey| {
+                            validate_public_key(&pubkey)
+                                .map_err(|reason| Error::InvalidPublicKey { index: i, reason })?;
+                            Ok(pubkey)
+                        })
+                        .map_err(|e| Error::decode_error(&format!("pubkeys[{}]", i), e))
+                })
+                .collect::<Result<Vec<PublicKey>, _>>()?,
+        ),
+        aggregate_pubkey: PublicKey::try_from(sync_committee.aggregate_pubkey)
+            .map_err(Error::from)
+            .and_then(|pubkey| {
+                validate_public_key(&pubkey)
+                    .map_err(|reason| Error::InvalidAggregatePublicKey { reason })?;
+                Ok(pubkey)
+            })
+            .map_err(|e| Error::decode_error("aggregate_pubkey", e))?,
+    })
+}
+
+/// Decodes the required field `field` of the proto message `message` with `decode`
+///
+/// Returns a missing field error if the field is absent. The error of `decode` is wrapped with `field`.
+pub(crate) fn decode_required<T, U>(
+    message: &str,
+    field: &str,
+    value: Option<T>,
+    decode: impl FnOnce(T) -> Result<U, Error>,
+) -> Result<U, Error> {
+    decode(value.ok_or_else(|| Error::proto_missing(message, field))?)
+        .map_err(|e| Error::decode_error(field, e))
+}
+
+/// Decodes `bz` as a 32-byte hash. The error contains `field` if the length is invalid.
+pub(crate) fn decode_h256(field: &str, bz: &[u8]) -> Result<H256, Error> {
+    if bz.len() != 32 {
+        return Err(Error::decode_error(
+            field,
+            Error::InvalidBytesLength {
+                expected: 32,
+                actual: bz.len(),
+            },
+        ));
+    }
+    Ok(H256::from_slice(bz))
+}
+
+/// The maximum length of a merkle branch in a consensus update or an execution update
+///
+/// The branches of the beacon state and the beacon block body are shorter than 10.
+pub const MAX_BRANCH_LENGTH: usize = 32;
+
+/// Decode the branch, which is rejected with `Error::DecodeLimitExceeded` if it is longer than `MAX_BRANCH_LENGTH`
+pub(crate) fn decode_branch(field: &str, bz: Vec<Vec<u8>>) -> Result<Vec<H256>, Error> {
+    check_limit("MAX_BRANCH_LENGTH", MAX_BRANCH_LENGTH, bz.len())
+        .map_err(|e| Error::decode_error(field, e))?;
+    bz.into_iter()
+        .enumerate()
+        .map(|(i, b)| decode_h256(&format!("{}[{}]", field, i), &b))
+        .collect()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::bls::PointError;
+    use crate::{MAINNET_SYNC_COMMITTEE_SIZE, MINIMAL_SYNC_COMMITTEE_SIZE};
+    use ethereum_light_client_verifier::consensus::test_utils::MockSyncCommitteeManager;
+    use proptest::prelude::*;
+    use prost::Message;
+
+    /// Returns valid public keys to build the generated sync committees from
+    pub(crate) fn pubkey_pool() -> Vec<PublicKey> {
+        let scm = MockSyncCommitteeManager::<32>::new(1, 2);
+        (1..=2)
+            .flat_map(|period| {
+                let sync_committee = scm.get_committee(period).to_committee().clone();
+                sync_committee
+                    .pubkeys
+                    .iter()
+                    .cloned()
+                    .chain([sync_committee.aggregate_pubkey.clone()])
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Returns the signature at infinity, which is the shortest valid encoding of a signature
+    fn infinity_signature() -> Signature {
+        Signature::try_from([&[0xc0u8][..], &[0u8; 95][..]].concat()).unwrap()
+    }
+
+    /// Returns a consensus update of the default values with a valid signature
+    fn default_consensus_update<const SYNC_COMMITTEE_SIZE: usize>(
+    ) -> ConsensusUpdateInfo<SYNC_COMMITTEE_SIZE> {
+        let mut consensus_update = ConsensusUpdateInfo::<SYNC_COMMITTEE_SIZE>::default();
+        consensus_update.sync_aggregate.sync_committee_signature = infinity_signature();
+        consensus_update
+    }
+
+    /// Returns valid signatures to build the generated sync aggregates from
+    pub(crate) fn signature_pool() -> Vec<Signature> {
+        let (_, _, header, _) = crate::verify::tests::header_scenario();
+        vec![
+            infinity_signature(),
+            header
+                .consensus_update
+                .sync_aggregate
+                .sync_committee_signature,
+        ]
+    }
+
+    pub(crate) fn arb_h256() -> impl Strategy<Value = H256> {
+        any::<[u8; 32]>().prop_map(H256)
+    }
+
+    /// Branches may be empty, which is rejected by `validate_basic` but not by the conversions
+    pub(crate) fn arb_branch() -> impl Strategy<Value = Vec<H256>> {
+        prop::collection::vec(arb_h256(), 0..8)
+    }
+
+    pub(crate) fn arb_height() -> impl Strategy<Value = Height> {
+        (any::<u64>(), 1..=u64::MAX).prop_map(|(revision_number, revision_height)| {
+            Height::new(revision_number, revision_height).unwrap()
+        })
+    }
+
+    fn arb_beacon_block_header() -> impl Strategy<Value = BeaconBlockHeader> {
+        (
+            any::<u64>(),
+            any::<u64>(),
+            arb_h256(),
+            arb_h256(),
+            arb_h256(),
+        )
+            .prop_map(
+                |(slot, proposer_index, parent_root, state_root, body_root)| BeaconBlockHeader {
+                    slot: slot.into(),
+                    proposer_index: proposer_index.into(),
+                    parent_root,
+                    state_root,
+                    body_root,
+                },
+            )
+    }
+
+    pub(crate) fn arb_sync_committee<const SYNC_COMMITTEE_SIZE: usize>(
+        pubkeys: Vec<PublicKey>,
+    ) -> impl Strategy<Value = SyncCommittee<SYNC_COMMITTEE_SIZE>> {
+        (
+            prop::collection::vec(prop::sample::select(pubkeys.clone()), SYNC_COMMITTEE_SIZE),
+            prop::sample::select(pubkeys),
+        )
+            .prop_map(|(pubkeys, aggregate_pubkey)| SyncCommittee {
+                pubkeys: Vector::from_iter(pubkeys),
+                aggregate_pubkey,
+            })
+    }
+
+    fn arb_sync_aggregate<const SYNC_COMMITTEE_SIZE: usize>(
+        signatures: Vec<Signature>,
+    ) -> impl Strategy<Value = SyncAggregate<SYNC_COMMITTEE_SIZE>> {
+        (
+            prop::collection::vec(any::<bool>(), SYNC_COMMITTEE_SIZE),
+            prop::sample::select(signatures),
+        )
+            .prop_map(|(bits, sync_committee_signature)| {
+                let mut sync_committee_bits = Bitvector::<SYNC_COMMITTEE_SIZE>::default();
+                for (i, bit) in bits.into_iter().enumerate() {
+                    sync_committee_bits.set(i, bit);
+                }
+                SyncAggregate {
+                    sync_committee_bits,
+                    sync_committee_signature,
+                }
+            })
+    }
+
+    pub(crate) fn arb_consensus_update<const SYNC_COMMITTEE_SIZE: usize>(
+        pubkeys: Vec<PublicKey>,
+        signatures: Vec<Signature>,
+    ) -> impl Strategy<Value = ConsensusUpdateInfo<SYNC_COMMITTEE_SIZE>> {
+        (
+            arb_beacon_block_header(),
+            prop::option::of((arb_sync_committee(pubkeys), arb_branch())),
+            (arb_beacon_block_header(), arb_branch()),
+            arb_sync_aggregate(signatures),
+            any::<u64>(),
+            arb_h256(),
+            arb_branch(),
+        )
+            .prop_map(
+                |(
+                    attested_header,
+                    next_sync_committee,
+                    finalized_header,
+                    sync_aggregate,
+                    signature_slot,
+                    finalized_execution_root,
+                    finalized_execution_branch,
+                )| ConsensusUpdateInfo {
+                    attested_header,
+                    next_sync_committee,
+                    finalized_header,
+                    sync_aggregate,
+                    signature_slot: signature_slot.into(),
+                    finalized_execution_root,
+                    finalized_execution_branch,
+                },
+            )
+    }
+
+    pub(crate) fn arb_execution_update() -> impl Strategy<Value = ExecutionUpdateInfo> {
+        (
+            arb_h256(),
+            arb_branch(),
+            any::<u64>(),
+            arb_branch(),
+            prop::option::of(arb_h256()),
+            arb_branch(),
+        )
+            .prop_map(
+                |(
+                    state_root,
+                    state_root_branch,
+                    block_number,
+                    block_number_branch,
+                    block_hash,
+                    block_hash_branch,
+                )| {
+                    ExecutionUpdateInfo {
+                        state_root,
+                        state_root_branch,
+                        block_number: block_number.into(),
+                        block_number_branch,
+                        block_hash,
+                        block_hash_branch,
+                    }
+                },
+            )
+    }
+
+    /// The proof nodes are rlp lists as required by `decode_eip1186_rlp_proof`, and the proof is not empty
+    pub(crate) fn arb_account_update() -> impl Strategy<Value = AccountUpdateInfo> {
+        let node = prop::collection::vec(prop::collection::vec(any::<u8>(), 0..40), 1..17)
+            .prop_map(|items| {
+                let mut stream = rlp::RlpStream::new_list(items.len());
+                for item in items.iter() {
+                    stream.append(item);
+                }
+                stream.out().to_vec()
+            });
+        (prop::collection::vec(node, 1..8), arb_h256()).prop_map(
+            |(account_proof, account_storage_root)| AccountUpdateInfo {
+                account_proof,
+                account_storage_root,
+            },
+        )
+    }
+
+    pub(crate) fn arb_trusted_sync_committee<const SYNC_COMMITTEE_SIZE: usize>(
+        pubkeys: Vec<PublicKey>,
+    ) -> impl Strategy<Value = TrustedSyncCommittee<SYNC_COMMITTEE_SIZE>> {
+        (arb_height(), arb_sync_committee(pubkeys), any::<bool>()).prop_map(
+            |(height, sync_committee, is_next)| TrustedSyncCommittee {
+                height,
+                sync_committee,
+                is_next,
+            },
+        )
+    }
+
+    fn consensus_update_round_trip<const SYNC_COMMITTEE_SIZE: usize>(
+        consensus_update: ConsensusUpdateInfo<SYNC_COMMITTEE_SIZE>,
+    ) -> Result<(), TestCaseError> {
+        let bz = convert_consensus_update_to_proto(consensus_update.clone()).encode_to_vec();
+        let proto = ProtoConsensusUpdate::decode(bz.as_slice()).unwrap();
+        prop_assert_eq!(
+            convert_proto_to_consensus_update::<SYNC_COMMITTEE_SIZE>(proto).unwrap(),
+            consensus_update
+        );
+        Ok(())
+    }
+
+    fn trusted_sync_committee_round_trip<const SYNC_COMMITTEE_SIZE: usize>(
+        trusted_sync_committee: TrustedSyncCommittee<SYNC_COMMITTEE_SIZE>,
+    ) -> Result<(), TestCaseError> {
+        let bz = ProtoTrustedSyncCommittee::from(trusted_sync_committee.clone()).encode_to_vec();
+        let proto = ProtoTrustedSyncCommittee::decode(bz.as_slice()).unwrap();
+        prop_assert_eq!(
+            TrustedSyncCommittee::<SYNC_COMMITTEE_SIZE>::try_from(proto).unwrap(),
+            trusted_sync_committee
+        );
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn prop_execution_update_round_trip(execution_update in arb_execution_update()) {
+            let bz = convert_execution_update_to_proto(execution_update.clone()).encode_to_vec();
+            let proto = ProtoExecutionUpdate::decode(bz.as_slice()).unwrap();
+            prop_assert_eq!(convert_proto_to_execution_update(proto).unwrap(), execution_update);
+        }
+
+        #[test]
+        fn prop_account_update_round_trip(account_update in arb_account_update()) {
+            let bz = ProtoAccountUpdate::from(account_update.clone()).encode_to_vec();
+            let proto = ProtoAccountUpdate::decode(bz.as_slice()).unwrap();
+            prop_assert_eq!(AccountUpdateInfo::try_from(proto).unwrap(), account_update);
+        }
+
+        #[test]
+        fn prop_minimal_consensus_update_round_trip(
+            consensus_update in arb_consensus_update::<MINIMAL_SYNC_COMMITTEE_SIZE>(pubkey_pool(), signature_pool())
+        ) {
+            consensus_update_round_trip(consensus_update)?;
+        }
+
+        #[test]
+        fn prop_minimal_trusted_sync_committee_round_trip(
+            trusted_sync_committee in arb_trusted_sync_committee::<MINIMAL_SYNC_COMMITTEE_SIZE>(pubkey_pool())
+        ) {
+            trusted_sync_committee_round_trip(trusted_sync_committee)?;
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+
+        #[test]
+        fn prop_mainnet_consensus_update_round_trip(
+            consensus_update in arb_consensus_update::<MAINNET_SYNC_COMMITTEE_SIZE>(pubkey_pool(), signature_pool())
+        ) {
+            consensus_update_round_trip(consensus_update)?;
+        }
+
+        #[test]
+        fn prop_mainnet_trusted_sync_committee_round_trip(
+            trusted_sync_committee in arb_trusted_sync_committee::<MAINNET_SYNC_COMMITTEE_SIZE>(pubkey_pool())
+        ) {
+            trusted_sync_committee_round_trip(trusted_sync_committee)?;
+        }
+    }
+
+    #[test]
+    fn test_next_sync_committee_with_empty_branch_round_trip() {
+        // the next sync committee used to be dropped if its branch is empty, so that the decoded header passed `validate_basic`
+        let pubkeys = pubkey_pool();
+        let consensus_update = ConsensusUpdateInfo::<MINIMAL_SYNC_COMMITTEE_SIZE> {
+            next_sync_committee: Some((
+                SyncCommittee {
+                    pubkeys: Vector::from_iter(pubkeys[..MINIMAL_SYNC_COMMITTEE_SIZE].to_vec()),
+                    aggregate_pubkey: pubkeys[MINIMAL_SYNC_COMMITTEE_SIZE].clone(),
+                },
+                vec![],
+            )),
+            ..default_consensus_update()
+        };
+        let proto = convert_consensus_update_to_proto(consensus_update.clone());
+        assert!(proto.next_sync_committee_branch.is_empty());
+        let res = convert_proto_to_consensus_update::<MINIMAL_SYNC_COMMITTEE_SIZE>(proto);
+        assert_eq!(res.unwrap(), consensus_update);
+
+        // an absent next sync committee is still decoded as `None` with or without the empty message
+        let consensus_update = default_consensus_update::<MINIMAL_SYNC_COMMITTEE_SIZE>();
+        let mut proto = convert_consensus_update_to_proto(consensus_update.clone());
+        assert!(proto.next_sync_committee.is_none());
+        proto.next_sync_committee = Some(Default::default());
+        let res = convert_proto_to_consensus_update::<MINIMAL_SYNC_COMMITTEE_SIZE>(proto);
+        assert_eq!(res.unwrap(), consensus_update);
+    }
+
+    #[test]
+    fn test_decode_execution_update_without_block_hash() {
+        // an execution update encoded before `block_hash` was introduced, i.e. with the fields 1 to 4 only
+        let mut bz = vec![0x0a, 32];
+        bz.extend([1u8; 32]);
+        bz.extend([0x12, 32]);
+        bz.extend([2u8; 32]);
+        bz.extend([0x18, 100]);
+        bz.extend([0x22, 32]);
+        bz.extend([3u8; 32]);
+        let execution_update =
+            convert_proto_to_execution_update(ProtoExecutionUpdate::decode(bz.as_slice()).unwrap())
+                .unwrap();
+        assert_eq!(
+            execution_update,
+            ExecutionUpdateInfo {
+                state_root: H256([1u8; 32]),
+                state_root_branch: vec![H256([2u8; 32])],
+                block_number: U64(100),
+                block_number_branch: vec![H256([3u8; 32])],
+                block_hash: None,
+                block_hash_branch: vec![],
+            }
+        );
+        // it is encoded into the same bytes
+        assert_eq!(
+            convert_execution_update_to_proto(execution_update).encode_to_vec(),
+            bz
+        );
+
+        let mut proto = ProtoExecutionUpdate::decode(bz.as_slice()).unwrap();
+        proto.block_hash = vec![4u8; 31];
+        assert_eq!(
+            convert_proto_to_execution_update(proto)
+                .unwrap_err()
+                .to_string(),
+            "failed to decode `block_hash`: invalid bytes length: expected=32 actual=31"
+        );
+    }
+
+    #[test]
+    fn test_branch_length_limit() {
+        let branch = vec![vec![1u8; 32]; MAX_BRANCH_LENGTH];
+        assert_eq!(
+            decode_branch("branch", branch.clone()).unwrap().len(),
+            MAX_BRANCH_LENGTH
+        );
+        let too_long = vec![vec![1u8; 32]; MAX_BRANCH_LENGTH + 1];
+        let res = decode_branch("branch", too_long.clone());
+        assert!(
+            matches!(
+                &res,
+                Err(Error::DecodeError { field, source })
+                    if field == "branch"
+                        && matches!(
+                            **source,
+                            Error::DecodeLimitExceeded {
+                                limit: "MAX_BRANCH_LENGTH",
+                                max: MAX_BRANCH_LENGTH,
+                                actual,
+                            } if actual == MAX_BRANCH_LENGTH + 1
+                        )
+            ),
+            "{:?}",
+            res
+        );
+
+        // the limit is checked before the nodes of the branch are decoded
+        let mut malformed = too_long.clone();
+        malformed[0] = vec![];
+        assert!(matches!(
+            decode_branch("branch", malformed),
+            Err(Error::DecodeError { source, .. })
+                if matches!(*source, Error::DecodeLimitExceeded { .. })
+        ));
+
+        let mut proto = convert_consensus_update_to_proto(default_consensus_update::<
+            MINIMAL_SYNC_COMMITTEE_SIZE,
+        >());
+        proto.finalized_header_branch = branch;
+        assert!(
+            convert_proto_to_consensus_update::<MINIMAL_SYNC_COMMITTEE_SIZE>(proto.clone()).is_ok()
+        );
+        proto.finalized_header_branch = too_long;
+        let res = convert_proto_to_consensus_update::<MINIMAL_SYNC_COMMITTEE_SIZE>(proto);
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            format!(
+                "failed to decode `finalized_header_branch`: decode limit exceeded: limit=MAX_BRANCH_LENGTH max={} actual={}",
+                MAX_BRANCH_LENGTH,
+                MAX_BRANCH_LENGTH + 1
+            )
+        );
+    }
+
+    fn sync_aggregate_round_trip<const SYNC_COMMITTEE_SIZE: usize>() {
+        let mut sync_aggregate = SyncAggregate::<SYNC_COMMITTEE_SIZE> {
+            sync_committee_signature: infinity_signature(),
+            ..Default::default()
+        };
+        for i in (0..SYNC_COMMITTEE_SIZE).step_by(3) {
+            sync_aggregate.sync_committee_bits.set(i, true);
+        }
+        let proto = convert_sync_aggregate_to_proto(sync_aggregate.clone());
+        assert_eq!(proto.sync_committee_bits.len(), SYNC_COMMITTEE_SIZE / 8);
+        let res = convert_proto_sync_aggregate::<SYNC_COMMITTEE_SIZE>(proto.clone());
+        assert_eq!(res.unwrap(), sync_aggregate);
+
+        let expected_len = sync_committee_bits_len(SYNC_COMMITTEE_SIZE);
+        // a byte too short and a byte too long
+        for actual_len in [expected_len - 1, expected_len + 1] {
+            let mut invalid = proto.clone();
+            invalid.sync_committee_bits.resize(actual_len, 0);
+            let res = convert_proto_sync_aggregate::<SYNC_COMMITTEE_SIZE>(invalid);
+            assert!(
+                matches!(
+                    &res,
+                    Err(Error::DecodeError { field, source })
+                        if field == "sync_committee_bits"
+                            && matches!(
+                                **source,
+                                Error::UnexpectedSyncCommitteeBitsLength {
+                                    sync_committee_size: size,
+                                    expected_len: e,
+                                    actual_len: a,
+                                } if size == SYNC_COMMITTEE_SIZE && e == expected_len && a == actual_len
+                            )
+                ),
+                "{:?}",
+                res
+            );
+        }
+
+        // the most significant bit of the last byte is the last member, not a padding bit
+        let mut last_member = SyncAggregate::<SYNC_COMMITTEE_SIZE> {
+            sync_committee_signature: infinity_signature(),
+            ..Default::default()
+        };
+        last_member
+            .sync_committee_bits
+            .set(SYNC_COMMITTEE_SIZE - 1, true);
+        let proto = convert_sync_aggregate_to_proto(last_member.clone());
+        assert_eq!(proto.sync_committee_bits.last(), Some(&0x80));
+        let res = convert_proto_sync_aggregate::<SYNC_COMMITTEE_SIZE>(proto);
+        assert_eq!(res.unwrap(), last_member);
+    }
+
+    /// `SYNC_COMMITTEE_SIZE` must not be a multiple of 8 so that the encoding has padding bits
+    fn sync_committee_bits_padding<const SYNC_COMMITTEE_SIZE: usize>() {
+        let padding = SYNC_COMMITTEE_SIZE % 8;
+        assert_ne!(padding, 0);
+        let mut proto = convert_sync_aggregate_to_proto(SyncAggregate::<SYNC_COMMITTEE_SIZE> {
+            sync_committee_signature: infinity_signature(),
+            ..Default::default()
+        });
+        assert_eq!(
+            proto.sync_committee_bits.len(),
+            sync_committee_bits_len(SYNC_COMMITTEE_SIZE)
+        );
+        // the last member is accepted but the bit after it is a padding bit
+        *proto.sync_committee_bits.last_mut().unwrap() = 1 << (padding - 1);
+        assert!(convert_proto_sync_aggregate::<SYNC_COMMITTEE_SIZE>(proto.clone()).is_ok());
+        *proto.sync_committee_bits.last_mut().unwrap() = 1 << padding;
+        let res = convert_proto_sync_aggregate::<SYNC_COMMITTEE_SIZE>(proto);
+        assert!(
+            matches!(
+                &res,
+                Err(Error::DecodeError { field, source })
+                    if field == "sync_committee_bits"
+                        && matches!(
+                            **source,
+                            Error::NonZeroSyncCommitteeBitsPadding {
+                                sync_committee_size: size,
+                                last_byte,
+                            } if size == SYNC_COMMITTEE_SIZE && last_byte == 1 << padding
+                        )
+            ),
+            "{:?}",
+            res
+        );
+    }
+
+    #[test]
+    fn test_sync_aggregate_conversion() {
+        sync_aggregate_round_trip::<MINIMAL_SYNC_COMMITTEE_SIZE>();
+        sync_aggregate_round_trip::<MAINNET_SYNC_COMMITTEE_SIZE>();
+        sync_committee_bits_padding::<30>();
+        sync_committee_bits_padding::<510>();
+        assert_eq!(sync_committee_bits_len(MINIMAL_SYNC_COMMITTEE_SIZE), 4);
+        assert_eq!(sync_committee_bits_len(MAINNET_SYNC_COMMITTEE_SIZE), 64);
+        assert_eq!(sync_committee_bits_len(30), 4);
+
+        // the bits of a different preset must be rejected
+        let proto = convert_sync_aggregate_to_proto(
+            SyncAggregate::<MAINNET_SYNC_COMMITTEE_SIZE>::default(),
+        );
+        let res = convert_proto_sync_aggregate::<MINIMAL_SYNC_COMMITTEE_SIZE>(proto);
+        assert!(res.is_err(), "{:?}", res);
+        let proto = convert_sync_aggregate_to_proto(
+            SyncAggregate::<MINIMAL_SYNC_COMMITTEE_SIZE>::default(),
+        );
+        let res = convert_proto_sync_aggregate::<MAINNET_SYNC_COMMITTEE_SIZE>(proto);
+        assert!(res.is_err(), "{:?}", res);
+    }
+
+    #[test]
+    fn test_invalid_points_conversion() {
+        let mut infinity_pubkey = vec![0u8; 48];
+        infinity_pubkey[0] = 0xc0;
+        let pubkeys = pubkey_pool();
+        let proto = ProtoSyncCommittee {
+            pubkeys: pubkeys[..MINIMAL_SYNC_COMMITTEE_SIZE]
+                .iter()
+                .map(|pk| pk.to_vec())
+                .collect(),
+            aggregate_pubkey: pubkeys[MINIMAL_SYNC_COMMITTEE_SIZE].to_vec(),
+        };
+        assert!(
+            convert_proto_to_sync_committee::<MINIMAL_SYNC_COMMITTEE_SIZE>(proto.clone()).is_ok()
+        );
+
+        let mut invalid = proto.clone();
+        invalid.pubkeys[3] = infinity_pubkey.clone();
+        let res = convert_proto_to_sync_committee::<MINIMAL_SYNC_COMMITTEE_SIZE>(invalid);
+        assert!(
+            matches!(
+                &res,
+                Err(Error::DecodeError { field, source })
+                    if field == "pubkeys[3]"
+                        && matches!(
+                            **source,
+                            Error::InvalidPublicKey {
+                                index: 3,
+                                reason: PointError::Infinity
+                            }
+                        )
+            ),
+            "{:?}",
+            res
+        );
+        let mut invalid = proto;
+        invalid.aggregate_pubkey = infinity_pubkey;
+        let res = convert_proto_to_sync_committee::<MINIMAL_SYNC_COMMITTEE_SIZE>(invalid);
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "failed to decode `aggregate_pubkey`: invalid aggregate public key: the point at infinity"
+        );
+
+        // no point on the curve has the x coordinate 1
+        let mut off_curve = vec![0u8; 96];
+        off_curve[0] = 0x80;
+        off_curve[95] = 1;
+        let proto = ProtoSyncAggregate {
+            sync_committee_signature: off_curve,
+            ..convert_sync_aggregate_to_proto(SyncAggregate::<MINIMAL_SYNC_COMMITTEE_SIZE> {
+                sync_committee_signature: infinity_signature(),
+                ..Default::default()
+            })
+        };
+        let res = convert_proto_sync_aggregate::<MINIMAL_SYNC_COMMITTEE_SIZE>(proto);
+        assert!(
+            matches!(
+                &res,
+                Err(Error::DecodeError { field, source })
+                    if field == "sync_committee_signature"
+                        && matches!(
+                            **source,
+                            Error::InvalidSignature {
+                                reason: PointError::NotOnCurve
+                            }
+                        )
+            ),
+            "{:?}",
+            res
+        );
+    }
+
+    #[test]
+    fn test_sync_committee_size_mismatch() {
+        // a wrong number of public keys used to make `Vector::from_iter` panic
+        let pubkeys = pubkey_pool();
+        for len in [
+            0,
+            MINIMAL_SYNC_COMMITTEE_SIZE - 1,
+            MINIMAL_SYNC_COMMITTEE_SIZE + 1,
+        ] {
+            let proto = ProtoSyncCommittee {
+                pubkeys: pubkeys[..len].iter().map(|pk| pk.to_vec()).collect(),
+                aggregate_pubkey: pubkeys[0].to_vec(),
+            };
+            let res = convert_proto_to_sync_committee::<MINIMAL_SYNC_COMMITTEE_SIZE>(proto);
+            assert!(
+                matches!(
+                    &res,
+                    Err(Error::DecodeError { field, source })
+                        if field == "pubkeys"
+                            && matches!(
+                                **source,
+                                Error::UnexpectedSyncCommitteeSize {
+                                    expected: MINIMAL_SYNC_COMMITTEE_SIZE,
+                                    actual,
+                                } if actual == len
+                            )
+                ),
+                "{:?}",
+                res
+            );
+        }
+    }
+
+    #[test]
+    fn test_account_update_validate() {
+        let account_update = AccountUpdateInfo {
+            account_proof: vec![vec![0xc2, 0x80, 0x80]],
+            account_storage_root: H256([1u8; 32]),
+        };
+        assert!(account_update.validate().is_ok());
+
+        let res = AccountUpdateInfo::default().validate();
+        assert!(matches!(res, Err(Error::EmptyAccountProof)), "{:?}", res);
+        let res = AccountUpdateInfo {
+            account_proof: vec![],
+            ..account_update.clone()
+        }
+        .validate();
+        assert!(matches!(res, Err(Error::EmptyAccountProof)), "{:?}", res);
+        let res = AccountUpdateInfo {
+            account_proof: vec![vec![0xc2, 0x80, 0x80], vec![], vec![0xc0]],
+            ..account_update.clone()
+        }
+        .validate();
+        assert!(
+            matches!(res, Err(Error::EmptyAccountProofNode { index: 1 })),
+            "{:?}",
+            res
+        );
+        let res = AccountUpdateInfo {
+            account_storage_root: H256::default(),
+            ..account_update.clone()
+        }
+        .validate();
+        assert!(
+            matches!(res, Err(Error::ZeroAccountStorageRoot)),
+            "{:?}",
+            res
+        );
+
+        // the decoding reports the field of the failed condition
+        let proto = ProtoAccountUpdate::from(account_update.clone());
+        assert_eq!(
+            AccountUpdateInfo::try_from(proto.clone()).unwrap(),
+            account_update
+        );
+        let res = AccountUpdateInfo::try_from(ProtoAccountUpdate {
+            account_proof: vec![0xc0],
+            ..proto.clone()
+        });
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "failed to decode `account_proof`: empty account proof"
+        );
+        let res = AccountUpdateInfo::try_from(ProtoAccountUpdate {
+            account_storage_root: vec![0u8; 32],
+            ..proto
+        });
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "failed to decode `account_storage_root`: the account storage root is zero"
+        );
+    }
+
+    #[test]
+    fn test_sync_committee_periods() {
+        // the minimal preset has 64 slots per period, while the mainnet preset has 8192
+        let client_state = crate::client_state::tests::new_client_state();
+        let at = |slot: u64, is_next: bool| {
+            (
+                ConsensusState {
+                    slot: U64(slot),
+                    storage_root: H256::default().as_bytes().to_vec().into(),
+                    timestamp: ibc::timestamp::Timestamp::none(),
+                    current_sync_committee: PublicKey::default(),
+                    next_sync_committee: PublicKey::default(),
+                    current_block_hash: None,
+                },
+                TrustedSyncCommittee::<MINIMAL_SYNC_COMMITTEE_SIZE> {
+                    height: crate::misc::height_from_block_number(U64(1)).unwrap(),
+                    sync_committee: Default::default(),
+                    is_next,
+                },
+            )
+        };
+
+        for (slot, period) in [(63, 0), (64, 1), (130, 2), (8191, 127)] {
+            let update = ConsensusUpdateInfo::<MINIMAL_SYNC_COMMITTEE_SIZE> {
+                signature_slot: U64(slot),
+                ..Default::default()
+            };
+            assert_eq!(update.signature_period(&client_state), U64(period));
+
+            let (consensus_state, current) = at(slot, false);
+            assert!(current.covers_period(&client_state, &consensus_state, U64(period)));
+            assert!(!current.covers_period(&client_state, &consensus_state, U64(period + 1)));
+            let (consensus_state, next) = at(slot, true);
+            assert!(next.covers_period(&client_state, &consensus_state, U64(period + 1)));
+            assert!(!next.covers_period(&client_state, &consensus_state, U64(period)));
+        }
+    }
 }
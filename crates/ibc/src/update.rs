@@ -9,18 +9,35 @@ use ethereum_consensus::{
 };
 use ibc::timestamp::Timestamp;
 
+/// How an update is applied, which is decided by its finalized slot relative to the slot of the trusted consensus state, see `ClientState::route_update`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UpdateRoute {
+    /// The update finalizes a later slot, so it may advance the latest height
+    Advance,
+    /// The update finalizes the slot of the trusted consensus state with the same execution block and the same data, so it changes nothing
+    NoOp,
+    /// The update finalizes the slot of the trusted consensus state with a different execution block or different data, so the client must be frozen
+    Misbehaviour,
+    /// The update finalizes an earlier slot, so it does not change the client state and its consensus state would be below the latest height
+    Historical,
+}
+
 /// Apply the verified updates to the state and return the new state.
 ///
-/// CONTRACT: `apply_updates` must be called after `SyncProtocolVerifier::validate_updates()`
+/// CONTRACT: `apply_updates` must be called after `ClientState::verify_consensus_update()` and `ClientState::verify_execution_update()`
 /// The `update` satisfies the following conditions:
 /// - finalized_period <= attested_period <= signature_period
 /// - `consensus_update`'s signature period in (store_period, store_period + 1) == True
+///
+/// `block_hash` is the block hash verified by `ClientState::verify_execution_update()` if the execution update has it, which becomes `ConsensusState::current_block_hash`.
+#[allow(clippy::too_many_arguments)]
 pub fn apply_updates<const SYNC_COMMITTEE_SIZE: usize, C: ChainContext>(
     ctx: &C,
     client_state: &ClientState<SYNC_COMMITTEE_SIZE>,
     consensus_state: &ConsensusState,
     consensus_update: ConsensusUpdateInfo<SYNC_COMMITTEE_SIZE>,
     block_number: U64,
+    block_hash: Option<H256>,
     account_storage_root: H256,
     header_timestamp: Timestamp,
 ) -> Result<(ClientState<SYNC_COMMITTEE_SIZE>, ConsensusState), Error> {
@@ -40,6 +57,7 @@ pub fn apply_updates<const SYNC_COMMITTEE_SIZE: usize, C: ChainContext>(
             timestamp: header_timestamp,
             current_sync_committee: consensus_state.current_sync_committee.clone(),
             next_sync_committee: consensus_state.next_sync_committee.clone(),
+            current_block_hash: block_hash,
         }
     } else if store_period + 1 == update_finalized_period {
         // store_period + 1 == finalized_period == attested_period == signature_period
@@ -52,6 +70,7 @@ pub fn apply_updates<const SYNC_COMMITTEE_SIZE: usize, C: ChainContext>(
                 timestamp: header_timestamp,
                 current_sync_committee: consensus_state.next_sync_committee.clone(),
                 next_sync_committee: update_next_sync_committee.aggregate_pubkey,
+                current_block_hash: block_hash,
             }
         } else {
             // Relayers must submit an update that contains the next sync committee if the update period is `store_period + 1`.
@@ -69,8 +88,13 @@ pub fn apply_updates<const SYNC_COMMITTEE_SIZE: usize, C: ChainContext>(
         ));
     };
 
+    // If the update's block number is less than or equal to the latest one, the client state's latest execution block number is not changed,
+    // and `verify::simulate_update` rejects the update since its consensus state cannot be stored by the update handler of ibc-rs 0.29.
+    // An update that does not finalize a later slot than the consensus state never advances the latest height either, see `UpdateRoute`.
     let mut new_client_state = client_state.clone();
-    if client_state.latest_execution_block_number < block_number {
+    if update_finalized_slot > consensus_state.slot
+        && client_state.latest_execution_block_number < block_number
+    {
         new_client_state.latest_execution_block_number = block_number;
     }
     new_client_state.validate()?;
@@ -81,6 +105,8 @@ pub fn apply_updates<const SYNC_COMMITTEE_SIZE: usize, C: ChainContext>(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::address::Address;
+    use crate::commitment::CommitmentValueCodec;
     use core::time::Duration;
     use ethereum_consensus::beacon::Version;
     use ethereum_consensus::compute::compute_timestamp_at_slot;
@@ -91,7 +117,6 @@ mod tests {
     use ethereum_consensus::fork::deneb::DENEB_FORK_SPEC;
     use ethereum_consensus::fork::{ForkParameter, ForkParameters};
     use ethereum_consensus::preset::minimal::PRESET;
-    use ethereum_consensus::types::Address;
     use ethereum_consensus::{config, types::U64};
     use ethereum_light_client_verifier::updates::ConsensusUpdate;
     use ethereum_light_client_verifier::{
@@ -149,11 +174,15 @@ mod tests {
                 epochs_per_sync_committee_period: PRESET.EPOCHS_PER_SYNC_COMMITTEE_PERIOD,
                 ibc_address: Address(hex!("ff77D90D6aA12db33d3Ba50A34fB25401f6e4c4F")),
                 ibc_commitments_slot: keccak256("ibc_commitments_slot"),
+                commitment_value_codec: CommitmentValueCodec::Keccak256,
+                commitment_prefix: Default::default(),
                 trust_level: Fraction::new(2, 3).unwrap(),
                 trusting_period: Duration::from_secs(60 * 60 * 27),
                 max_clock_drift: Duration::from_secs(60),
+                max_intermediate_consensus_updates: U64(0),
                 latest_execution_block_number: 1.into(),
                 frozen_height: None,
+                misbehaviour_evidence_digest: None,
                 consensus_verifier: Default::default(),
                 execution_verifier: Default::default(),
             };
@@ -173,6 +202,7 @@ mod tests {
                 .get_committee(base_store_period + 1)
                 .to_committee()
                 .aggregate_pubkey,
+            current_block_hash: None,
         };
 
         {
@@ -197,6 +227,7 @@ mod tests {
                 &consensus_state,
                 update.clone(),
                 new_block_number,
+                Some(H256::from_slice(&[2u8; 32])),
                 H256::from_slice(&[1u8; 32]),
                 Timestamp::from_nanoseconds(
                     compute_timestamp_at_slot(&ctx, update.finalized_header.0.slot).0
@@ -211,6 +242,10 @@ mod tests {
                 new_block_number
             );
             assert_eq!(new_consensus_state.slot, update.finalized_header.0.slot);
+            assert_eq!(
+                new_consensus_state.current_block_hash,
+                Some(H256::from_slice(&[2u8; 32]))
+            );
             // sync committee info should be the same as the current consensus state
             assert_eq!(
                 new_consensus_state.current_sync_committee,
@@ -247,6 +282,7 @@ mod tests {
                 &consensus_state,
                 update.clone(),
                 new_block_number,
+                Some(H256::from_slice(&[2u8; 32])),
                 H256::from_slice(&[1u8; 32]),
                 Timestamp::from_nanoseconds(
                     compute_timestamp_at_slot(&ctx, update.finalized_header.0.slot).0
@@ -261,6 +297,10 @@ mod tests {
                 new_block_number
             );
             assert_eq!(new_consensus_state.slot, update.finalized_header.0.slot);
+            assert_eq!(
+                new_consensus_state.current_block_hash,
+                Some(H256::from_slice(&[2u8; 32]))
+            );
             // sync committee info should be the same as the current consensus state
             assert_eq!(
                 new_consensus_state.current_sync_committee,
@@ -298,6 +338,7 @@ mod tests {
                 &consensus_state,
                 update.clone(),
                 new_block_number,
+                None,
                 H256::from_slice(&[1u8; 32]),
                 Timestamp::from_nanoseconds(
                     compute_timestamp_at_slot(&ctx, update.finalized_header.0.slot).0
@@ -337,6 +378,7 @@ mod tests {
                 &consensus_state,
                 update.clone(),
                 new_block_number,
+                None,
                 H256::from_slice(&[1u8; 32]),
                 Timestamp::from_nanoseconds(
                     compute_timestamp_at_slot(&ctx, update.finalized_header.0.slot).0
@@ -359,6 +401,122 @@ mod tests {
         }
     }
 
+    #[test]
+    pub fn test_apply_updates_with_older_height() {
+        let scm = MockSyncCommitteeManager::<32>::new(1, 6);
+        let ctx = LightClientContext::new_with_config(
+            config::minimal::get_config(),
+            Default::default(),
+            Default::default(),
+            Fraction::new(2, 3).unwrap(),
+            SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap()
+                .as_secs()
+                .into(),
+        );
+
+        let slots_per_period = ctx.slots_per_epoch() * ctx.epochs_per_sync_committee_period();
+        let base_store_period = 3u64;
+        let base_store_slot = U64(base_store_period) * slots_per_period;
+        let base_finalized_epoch = base_store_slot / ctx.slots_per_epoch() + 1;
+        let base_attested_slot = (base_finalized_epoch + 2) * ctx.slots_per_epoch();
+        let base_signature_slot = base_attested_slot + 1;
+
+        let client_state = ClientState::<32> {
+            genesis_validators_root: keccak256("genesis_validators_root"),
+            min_sync_committee_participants: 1.into(),
+            genesis_time: 1.into(),
+            fork_parameters: ForkParameters::new(
+                Version([0, 0, 0, 1]),
+                vec![
+                    ForkParameter::new(Version([1, 0, 0, 1]), U64(0), ALTAIR_FORK_SPEC),
+                    ForkParameter::new(Version([2, 0, 0, 1]), U64(0), BELLATRIX_FORK_SPEC),
+                    ForkParameter::new(Version([3, 0, 0, 1]), U64(0), CAPELLA_FORK_SPEC),
+                    ForkParameter::new(Version([4, 0, 0, 1]), U64(0), DENEB_FORK_SPEC),
+                ],
+            )
+            .unwrap(),
+            seconds_per_slot: PRESET.SECONDS_PER_SLOT,
+            slots_per_epoch: PRESET.SLOTS_PER_EPOCH,
+            epochs_per_sync_committee_period: PRESET.EPOCHS_PER_SYNC_COMMITTEE_PERIOD,
+            ibc_address: Address(hex!("ff77D90D6aA12db33d3Ba50A34fB25401f6e4c4F")),
+            ibc_commitments_slot: keccak256("ibc_commitments_slot"),
+            commitment_value_codec: CommitmentValueCodec::Keccak256,
+            commitment_prefix: Default::default(),
+            trust_level: Fraction::new(2, 3).unwrap(),
+            trusting_period: Duration::from_secs(60 * 60 * 27),
+            max_clock_drift: Duration::from_secs(60),
+            max_intermediate_consensus_updates: U64(0),
+            latest_execution_block_number: 1.into(),
+            frozen_height: None,
+            misbehaviour_evidence_digest: None,
+            consensus_verifier: Default::default(),
+            execution_verifier: Default::default(),
+        };
+        let consensus_state = ConsensusState {
+            slot: base_store_slot,
+            storage_root: CommitmentRoot::from_bytes(keccak256("storage_root").as_bytes()),
+            timestamp: Timestamp::from_nanoseconds(
+                compute_timestamp_at_slot(&ctx, base_store_slot).0 * 1_000_000_000,
+            )
+            .unwrap(),
+            current_sync_committee: scm
+                .get_committee(base_store_period)
+                .to_committee()
+                .aggregate_pubkey,
+            next_sync_committee: scm
+                .get_committee(base_store_period + 1)
+                .to_committee()
+                .aggregate_pubkey,
+            current_block_hash: None,
+        };
+
+        let (update, _) = gen_light_client_update_with_params::<32, _>(
+            &ctx,
+            base_signature_slot,
+            base_attested_slot,
+            base_finalized_epoch,
+            [1u8; 32].into(),
+            1.into(),
+            scm.get_committee(base_store_period),
+            scm.get_committee(base_store_period + 1),
+            true,
+            32,
+        );
+        let update = to_consensus_update_info(update);
+        let header_timestamp = Timestamp::from_nanoseconds(
+            compute_timestamp_at_slot(&ctx, update.finalized_header.0.slot).0 * 1_000_000_000,
+        )
+        .unwrap();
+
+        let mut client_state = client_state;
+        for (block_number, expected_latest_block_number) in [(100, 100), (200, 200), (150, 200)] {
+            let (new_client_state, new_consensus_state) = apply_updates(
+                &ctx,
+                &client_state,
+                &consensus_state,
+                update.clone(),
+                U64(block_number),
+                None,
+                H256::from_slice(&[1u8; 32]),
+                header_timestamp,
+            )
+            .unwrap();
+            assert_eq!(
+                new_client_state.latest_execution_block_number,
+                U64(expected_latest_block_number)
+            );
+            // the consensus state for the older height is still produced
+            assert_eq!(new_consensus_state.slot, update.finalized_header.0.slot);
+            assert_eq!(
+                new_consensus_state.storage_root,
+                CommitmentRoot::from_bytes(&[1u8; 32])
+            );
+            client_state = new_client_state;
+        }
+    }
+
     fn keccak256(s: &str) -> H256 {
         use tiny_keccak::{Hasher, Keccak};
         let mut hasher = Keccak::v256();
@@ -0,0 +1,846 @@
+//! Verification of headers and misbehaviours without an ibc-rs host context
+//!
+//! The functions take the consensus states that the host would otherwise read from its store, and the current time of the host as a `HostTime`.
+//! The ibc-rs `ClientState` implementation reads them from the `ValidationContext` and delegates to these functions.
+use crate::cache::{NoCache, SyncCommitteeCache};
+use crate::client_state::{
+    validate_header_timestamp, validate_state_timestamp_within_trusting_period, ClientState,
+    UpdateResult,
+};
+use crate::consensus_state::{ConsensusState, TrustedConsensusState};
+use crate::errors::Error;
+use crate::header::Header;
+use crate::internal_prelude::*;
+use crate::misbehaviour::Misbehaviour;
+use crate::misc::HostTime;
+use crate::trace::{trace_span, traced};
+use crate::update::{apply_updates, UpdateRoute};
+use ibc::core::ics02_client::client_state::ClientState as Ics2ClientState;
+use ibc::Height;
+
+/// Verify the header against the trusted consensus state at the current time of `host` and return the new client state and consensus state
+///
+/// `trusted_consensus_state` is the consensus state stored at `Header::trusted_height`.
+/// The caller must not overwrite a consensus state already stored at `Header::height` with the returned consensus state, see `ClientState::check_for_misbehaviour`.
+pub fn verify_header<const SYNC_COMMITTEE_SIZE: usize>(
+    client_state: &ClientState<SYNC_COMMITTEE_SIZE>,
+    trusted_consensus_state: &ConsensusState,
+    header: Header<SYNC_COMMITTEE_SIZE>,
+    host: impl HostTime,
+) -> Result<(ClientState<SYNC_COMMITTEE_SIZE>, ConsensusState), Error> {
+    verify_header_with_cache(
+        client_state,
+        trusted_consensus_state,
+        header,
+        host,
+        &mut NoCache,
+    )
+}
+
+/// `verify_header` that skips the validation of the trusted sync committee of the header if it is in `cache`
+///
+/// The trusted sync committee is stored in `cache` once validated, so the following headers trusting the same sync committee are verified faster.
+pub fn verify_header_with_cache<C, const SYNC_COMMITTEE_SIZE: usize>(
+    client_state: &ClientState<SYNC_COMMITTEE_SIZE>,
+    trusted_consensus_state: &ConsensusState,
+    header: Header<SYNC_COMMITTEE_SIZE>,
+    host: impl HostTime,
+    cache: &mut C,
+) -> Result<(ClientState<SYNC_COMMITTEE_SIZE>, ConsensusState), Error>
+where
+    C: SyncCommitteeCache<SYNC_COMMITTEE_SIZE> + ?Sized,
+{
+    trace_span!(
+        "verify_header",
+        trusted_height = header.trusted_height().revision_height(),
+        block_number = header.execution_update.block_number.0,
+        signature_slot = header.consensus_update.signature_slot.0,
+    );
+    traced(client_state.verify_not_frozen())?;
+    let now = host.now();
+    let cc = client_state.build_context_at(now);
+    traced(header.validate_basic())?;
+
+    let trusted = {
+        trace_span!(
+            "validate_trusted_sync_committee",
+            trusted_height = header.trusted_sync_committee.height.revision_height(),
+            is_next = header.trusted_sync_committee.is_next,
+            period = client_state
+                .compute_period_at_slot(trusted_consensus_state.slot)
+                .0
+                + header.trusted_sync_committee.is_next as u64,
+        );
+        // this also validates the timestamp and the block number of the header
+        traced(header.validate_with_cache(&cc, cache))?;
+        let trusted_sync_committee = header.trusted_sync_committee;
+        traced(TrustedConsensusState::new_with_cache(
+            &cc,
+            trusted_consensus_state.clone(),
+            trusted_sync_committee.sync_committee,
+            trusted_sync_committee.is_next,
+            cache,
+        ))?
+    };
+    let trusted = traced(client_state.verify_intermediate_consensus_updates(
+        &cc,
+        trusted,
+        &header.intermediate_consensus_updates,
+    ))?;
+
+    let consensus_update = header.consensus_update;
+    let execution_update = header.execution_update;
+    {
+        trace_span!(
+            "verify_sync_aggregate",
+            slot = consensus_update.signature_slot.0,
+            period = consensus_update.signature_period(client_state).0,
+        );
+        traced(client_state.verify_slot_in_fork_schedule(consensus_update.signature_slot))?;
+        traced(client_state.verify_sync_committee_participants(&consensus_update))?;
+        traced(client_state.verify_consensus_update(&cc, &trusted, &consensus_update))?;
+    }
+    {
+        trace_span!(
+            "verify_execution_update",
+            slot = consensus_update.finalized_header.0.slot.0,
+            block_number = execution_update.block_number.0,
+        );
+        traced(client_state.verify_execution_update(&consensus_update, &execution_update))?;
+    }
+
+    let trusted = ConsensusState::from(trusted);
+    // the account proof must be verified against the state root verified in `verify_execution_update` above
+    {
+        trace_span!(
+            "verify_account_update",
+            block_number = execution_update.block_number.0,
+        );
+        traced(
+            client_state
+                .verify_account_storage(execution_update.state_root, &header.account_update),
+        )?;
+    }
+
+    // check if the current timestamp is within the trusting period
+    traced(validate_state_timestamp_within_trusting_period(
+        now,
+        client_state.trusting_period,
+        trusted_consensus_state.timestamp,
+    ))?;
+    // check if the header timestamp does not indicate a future time and is within the trusting period
+    traced(validate_header_timestamp(
+        now,
+        client_state.trusting_period,
+        client_state.max_clock_drift,
+        header.timestamp,
+    ))?;
+
+    trace_span!(
+        "apply_updates",
+        slot = consensus_update.finalized_header.0.slot.0,
+        block_number = execution_update.block_number.0,
+    );
+    traced(apply_updates(
+        &cc,
+        client_state,
+        &trusted,
+        consensus_update,
+        execution_update.block_number,
+        execution_update.block_hash,
+        header.account_update.account_storage_root,
+        header.timestamp,
+    ))
+}
+
+/// Verify the header and return the result of the update at the current time of `host` without writing anything
+///
+/// This is the update of `ClientState::update_state`, which reads the arguments from the host context and returns this result as is,
+/// so the host that writes the returned `UpdateResult` ends up with exactly the states of the dry run.
+/// `stored_consensus_state` is the consensus state already stored at `Header::height` if any; it is never overwritten, and the client is frozen if the header conflicts with it.
+/// The update is applied as `ClientState::route_update` returns: an update of the slot of the trusted consensus state either changes nothing or freezes the client at `Header::height`,
+/// and in both cases the returned consensus state is the trusted one at the trusted height, so nothing new is stored.
+/// A header that would store a new consensus state below the latest height fails with `Error::HistoricalConsensusStateNotSupported`,
+/// since the update handler of ibc-rs 0.29 writes the consensus state only at the latest height of the client state.
+/// `consensus_states` are the consensus states stored for the client, from which `UpdateResult::prunable_heights` is computed by `ClientState::expired_heights`.
+pub fn simulate_update<const SYNC_COMMITTEE_SIZE: usize>(
+    client_state: &ClientState<SYNC_COMMITTEE_SIZE>,
+    trusted_consensus_state: &ConsensusState,
+    stored_consensus_state: Option<&ConsensusState>,
+    consensus_states: &[(Height, ConsensusState)],
+    header: Header<SYNC_COMMITTEE_SIZE>,
+    host: impl HostTime,
+) -> Result<UpdateResult<SYNC_COMMITTEE_SIZE>, Error> {
+    let now = host.now();
+    // `Header::height` requires a valid header
+    header.validate_basic()?;
+    let header_height = header.height();
+    let trusted_height = header.trusted_height();
+    let header_timestamp = header.timestamp;
+    let (new_client_state, new_consensus_state) =
+        verify_header(client_state, trusted_consensus_state, header, now)?;
+
+    let route = client_state.route_update(
+        trusted_height,
+        trusted_consensus_state,
+        header_height,
+        &new_consensus_state,
+    );
+    let (height, new_client_state, new_consensus_state) = match (route, stored_consensus_state) {
+        (UpdateRoute::NoOp, _) => (
+            trusted_height,
+            client_state.clone(),
+            trusted_consensus_state.clone(),
+        ),
+        (UpdateRoute::Misbehaviour, _) => (
+            trusted_height,
+            client_state.clone().with_frozen_height(header_height),
+            trusted_consensus_state.clone(),
+        ),
+        // the stored consensus state is never overwritten
+        (_, Some(stored_consensus_state))
+            if client_state
+                .check_for_misbehaviour(&new_consensus_state, Some(stored_consensus_state)) =>
+        {
+            (
+                header_height,
+                client_state.clone().with_frozen_height(header_height),
+                stored_consensus_state.clone(),
+            )
+        }
+        (_, Some(stored_consensus_state)) => (
+            header_height,
+            new_client_state,
+            stored_consensus_state.clone(),
+        ),
+        // the update handler of ibc-rs 0.29 writes only at the latest height
+        (_, None) if new_client_state.latest_height() != header_height => {
+            return Err(Error::HistoricalConsensusStateNotSupported {
+                height: header_height,
+                latest_height: client_state.latest_height(),
+            });
+        }
+        (_, None) => (header_height, new_client_state, new_consensus_state),
+    };
+    let cc = client_state.build_context_at(now);
+    let committee_rotated =
+        new_consensus_state.current_period(&cc) > trusted_consensus_state.current_period(&cc);
+    // the consensus state at the height is the one written by this update
+    let mut prunable_heights =
+        new_client_state.expired_heights(consensus_states, header_timestamp, trusted_height);
+    prunable_heights.retain(|h| *h != height);
+    Ok(client_state.update_result(
+        new_client_state,
+        height,
+        new_consensus_state,
+        committee_rotated,
+        prunable_heights,
+    ))
+}
+
+/// Verify the misbehaviour against the trusted consensus states at the current time of `host`
+///
+/// `trusted_consensus_states` are the consensus states stored at the heights of `Misbehaviour::trusted_sync_committees`.
+/// If the misbehaviour is valid, the caller should freeze the client at `Misbehaviour::height`.
+pub fn verify_misbehaviour<const SYNC_COMMITTEE_SIZE: usize>(
+    client_state: &ClientState<SYNC_COMMITTEE_SIZE>,
+    trusted_consensus_states: (&ConsensusState, &ConsensusState),
+    misbehaviour: &Misbehaviour<SYNC_COMMITTEE_SIZE>,
+    host: impl HostTime,
+) -> Result<(), Error> {
+    client_state.verify_not_frozen()?;
+    misbehaviour.validate()?;
+    let now = host.now();
+    let cc = client_state.build_context_at(now);
+    let (trusted_sync_committee_1, trusted_sync_committee_2) =
+        misbehaviour.trusted_sync_committees();
+    let mut trusted = Vec::new();
+    for (consensus_state, trusted_sync_committee) in [
+        (trusted_consensus_states.0, trusted_sync_committee_1),
+        (trusted_consensus_states.1, trusted_sync_committee_2),
+    ] {
+        validate_state_timestamp_within_trusting_period(
+            now,
+            client_state.trusting_period,
+            consensus_state.timestamp,
+        )?;
+        trusted.push(TrustedConsensusState::new(
+            &cc,
+            consensus_state.clone(),
+            trusted_sync_committee.sync_committee.clone(),
+            trusted_sync_committee.is_next,
+        )?);
+    }
+    client_state.verify_misbehaviour(&cc, (&trusted[0], &trusted[1]), misbehaviour)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::address::Address;
+    use crate::cache::RecentSyncCommittees;
+    use crate::client_state::tests::{
+        get_membership_proof, new_client_state, single_account_update, to_consensus_update_info,
+    };
+    use crate::eth_client_type;
+    use crate::misc::{compute_timestamp_at_slot, height_from_block_number};
+    use crate::testing::MockContext;
+    use crate::types::{ConsensusUpdateInfo, ExecutionUpdateInfo, TrustedSyncCommittee};
+    use core::str::FromStr;
+    use ethereum_consensus::beacon::Slot;
+    use ethereum_consensus::context::ChainContext;
+    use ethereum_consensus::types::{H256, U64};
+    use ethereum_ibc_proto::ibc::lightclients::ethereum::v1::Header as RawHeader;
+    use ethereum_light_client_verifier::consensus::test_utils::{
+        gen_light_client_update_with_params, MockSyncCommitteeManager,
+    };
+    use ethereum_light_client_verifier::misbehaviour::{
+        FinalizedHeaderMisbehaviour, Misbehaviour as MisbehaviourData,
+    };
+    use ethereum_light_client_verifier::updates::ExecutionUpdate;
+    use hex_literal::hex;
+    use ibc::core::ics02_client::consensus_state::ConsensusState as Ics02ConsensusState;
+    use ibc::core::ics24_host::identifier::ClientId;
+    use ibc::core::ics24_host::Path;
+    use ibc::timestamp::Timestamp;
+    use ibc::Height;
+    use ibc_proto::google::protobuf::Any as IBCAny;
+    use prost::Message;
+
+    /// Returns the client state, the trusted consensus state at height 1, the header updating the client to height 2 and the current time
+    pub(crate) fn header_scenario() -> (ClientState<32>, ConsensusState, Header<32>, Timestamp) {
+        let scm = MockSyncCommitteeManager::<32>::new(1, 2);
+        let mut client_state = new_client_state();
+        client_state.ibc_address = Address(hex!("a7f733a4fEA1071f58114b203F57444969b86524"));
+        client_state.ibc_commitments_slot = H256(hex!(
+            "1ee222554989dda120e26ecacf756fe1235cd8d726706b57517715dde4f0c900"
+        ));
+        // the storage root of the IBC contract deployed with ibc-solidity
+        let storage_root = H256(hex!(
+            "27cd08827e6bf1e435832f4b2660107beb562314287b3fa534f3b189574c0cca"
+        ));
+        let (state_root, account_update) =
+            single_account_update(&client_state.ibc_address, storage_root);
+        let timestamp_at = |slot: Slot| {
+            compute_timestamp_at_slot(
+                client_state.genesis_time,
+                client_state.seconds_per_slot,
+                slot,
+            )
+            .unwrap()
+        };
+        let period_1 =
+            U64(1) * client_state.slots_per_epoch * client_state.epochs_per_sync_committee_period;
+        let trusted_consensus_state = ConsensusState {
+            slot: period_1 + 1,
+            storage_root: storage_root.as_bytes().to_vec().into(),
+            timestamp: timestamp_at(period_1 + 1),
+            current_sync_committee: scm.get_committee(1).to_committee().aggregate_pubkey.clone(),
+            next_sync_committee: scm.get_committee(2).to_committee().aggregate_pubkey.clone(),
+            current_block_hash: None,
+        };
+
+        let signature_slot = period_1 + 11;
+        let now = timestamp_at(signature_slot + 1);
+        // the updates are signed with the same chain parameters as the client state
+        let cc = client_state.build_context_at(now);
+        let attested_slot = signature_slot - 1;
+        let (update, execution_update) = gen_light_client_update_with_params::<32, _>(
+            &cc,
+            signature_slot,
+            attested_slot,
+            attested_slot / cc.slots_per_epoch(),
+            state_root,
+            2.into(),
+            scm.get_committee(1),
+            scm.get_committee(2),
+            true,
+            32,
+        );
+        let consensus_update = to_consensus_update_info(update);
+        let header = Header {
+            trusted_sync_committee: TrustedSyncCommittee {
+                height: height_from_block_number(U64(1)).unwrap(),
+                sync_committee: scm.get_committee(1).to_committee().clone(),
+                is_next: false,
+            },
+            intermediate_consensus_updates: vec![],
+            timestamp: timestamp_at(consensus_update.finalized_header.0.slot),
+            consensus_update,
+            execution_update: ExecutionUpdateInfo {
+                state_root: execution_update.state_root(),
+                state_root_branch: execution_update.state_root_branch(),
+                block_number: execution_update.block_number(),
+                block_number_branch: execution_update.block_number_branch(),
+                block_hash: None,
+                block_hash_branch: vec![],
+            },
+            account_update,
+        };
+
+        (client_state, trusted_consensus_state, header, now)
+    }
+
+    /// Returns the client state, the trusted consensus state at height 1, the misbehaviour of two finalized headers at the same slot and the current time
+    pub(crate) fn misbehaviour_scenario(
+    ) -> (ClientState<32>, ConsensusState, Misbehaviour<32>, Timestamp) {
+        let scm = MockSyncCommitteeManager::<32>::new(1, 2);
+        let client_state = new_client_state();
+        let period_1 =
+            U64(1) * client_state.slots_per_epoch * client_state.epochs_per_sync_committee_period;
+        let signature_slot = period_1 + 11;
+        let timestamp_at = |slot: Slot| {
+            compute_timestamp_at_slot(
+                client_state.genesis_time,
+                client_state.seconds_per_slot,
+                slot,
+            )
+            .unwrap()
+        };
+        let now = timestamp_at(signature_slot + 1);
+        let cc = client_state.build_context_at(now);
+        let gen_update = |execution_state_root: [u8; 32]| -> ConsensusUpdateInfo<32> {
+            let attested_slot = signature_slot - 1;
+            let (update, _) = gen_light_client_update_with_params::<32, _>(
+                &cc,
+                signature_slot,
+                attested_slot,
+                attested_slot / cc.slots_per_epoch(),
+                execution_state_root.into(),
+                1.into(),
+                scm.get_committee(1),
+                scm.get_committee(2),
+                true,
+                32,
+            );
+            to_consensus_update_info(update)
+        };
+        let trusted_consensus_state = ConsensusState {
+            slot: period_1 + 1,
+            storage_root: [1u8; 32].to_vec().into(),
+            timestamp: timestamp_at(period_1 + 1),
+            current_sync_committee: scm.get_committee(1).to_committee().aggregate_pubkey.clone(),
+            next_sync_committee: scm.get_committee(2).to_committee().aggregate_pubkey.clone(),
+            current_block_hash: None,
+        };
+        let trusted_sync_committee = TrustedSyncCommittee {
+            height: height_from_block_number(U64(1)).unwrap(),
+            sync_committee: scm.get_committee(1).to_committee().clone(),
+            is_next: false,
+        };
+        // the sync committee signs two different finalized headers at the same slot
+        let misbehaviour = Misbehaviour {
+            client_id: ClientId::new(eth_client_type(), 0).unwrap(),
+            trusted_sync_committee_1: trusted_sync_committee.clone(),
+            trusted_sync_committee_2: trusted_sync_committee,
+            data: MisbehaviourData::FinalizedHeader(FinalizedHeaderMisbehaviour {
+                consensus_update_1: gen_update([1u8; 32]),
+                consensus_update_2: gen_update([2u8; 32]),
+            }),
+        };
+        (client_state, trusted_consensus_state, misbehaviour, now)
+    }
+
+    #[test]
+    fn test_verify_header() {
+        let (client_state, trusted_consensus_state, header, now) = header_scenario();
+        let (new_client_state, new_consensus_state) =
+            verify_header(&client_state, &trusted_consensus_state, header.clone(), now).unwrap();
+        assert_eq!(new_client_state.latest_execution_block_number, U64(2));
+        assert_eq!(new_consensus_state.timestamp, header.timestamp);
+        assert_eq!(new_consensus_state.root(), trusted_consensus_state.root());
+        let (path, proof, value) = get_membership_proof();
+        let res = new_client_state.verify_membership(
+            header.height(),
+            &new_client_state.commitment_prefix(),
+            &proof.try_into().unwrap(),
+            new_consensus_state.root(),
+            Path::from_str(&path).unwrap(),
+            value,
+        );
+        assert!(res.is_ok(), "{:?}", res);
+
+        // the trusted consensus state has expired
+        let res = verify_header(
+            &client_state,
+            &trusted_consensus_state,
+            header.clone(),
+            (now + client_state.trusting_period).unwrap(),
+        );
+        assert!(
+            matches!(res, Err(Error::OutOfTrustingPeriod { .. })),
+            "{:?}",
+            res
+        );
+
+        // a frozen client cannot be updated
+        let frozen_client_state = client_state.clone().with_frozen_height(header.height());
+        let res = verify_header(&frozen_client_state, &trusted_consensus_state, header, now);
+        assert!(matches!(res, Err(Error::ClientFrozen { .. })), "{:?}", res);
+    }
+
+    #[test]
+    fn test_verify_header_storage_changed() {
+        let (client_state, trusted_consensus_state, header, now) = header_scenario();
+        // the storage of the IBC contract changed after the trusted consensus state
+        let stale_storage_root = H256([1u8; 32]);
+        let trusted_consensus_state = ConsensusState {
+            storage_root: stale_storage_root.as_bytes().to_vec().into(),
+            ..trusted_consensus_state
+        };
+        let (_, new_consensus_state) =
+            verify_header(&client_state, &trusted_consensus_state, header.clone(), now).unwrap();
+        assert_eq!(
+            new_consensus_state.storage_root.as_bytes(),
+            header.account_update.account_storage_root.as_bytes()
+        );
+
+        // a relayer cannot omit the account proof to carry the stale storage root forward
+        let mut raw = RawHeader::from(header.clone());
+        raw.account_update = None;
+        let res = Header::<32>::try_from(raw);
+        assert!(
+            matches!(res, Err(Error::ProtoMissingFieldError(ref field)) if field == "Header.account_update"),
+            "{:?}",
+            res
+        );
+
+        // nor claim the stale storage root with the proof of the new state
+        let mut stale_header = header;
+        stale_header.account_update.account_storage_root = stale_storage_root;
+        let res = verify_header(&client_state, &trusted_consensus_state, stale_header, now);
+        assert!(
+            matches!(res, Err(Error::AccountStorageRootMismatch(..))),
+            "{:?}",
+            res
+        );
+    }
+
+    #[test]
+    fn test_verify_header_host_time() {
+        let (client_state, trusted_consensus_state, header, now) = header_scenario();
+        let expected =
+            verify_header(&client_state, &trusted_consensus_state, header.clone(), now).unwrap();
+
+        // the host context is the time source
+        let ctx = MockContext::<32>::new(now, Height::new(0, 1).unwrap());
+        let res = verify_header(
+            &client_state,
+            &trusted_consensus_state,
+            header.clone(),
+            &ctx,
+        );
+        assert_eq!(res.unwrap(), expected);
+
+        // the host time in seconds taken as nanoseconds is long before the header
+        let seconds = Timestamp::from_nanoseconds(now.nanoseconds() / 1_000_000_000).unwrap();
+        let res = verify_header(&client_state, &trusted_consensus_state, header, seconds);
+        assert!(
+            matches!(res, Err(Error::HeaderFromFuture { .. })),
+            "{:?}",
+            res
+        );
+    }
+
+    #[test]
+    fn test_simulate_update_routes() {
+        let (client_state, trusted_consensus_state, header, now) = header_scenario();
+        let finalized_slot = header.consensus_update.finalized_header.0.slot;
+        let simulate = |trusted_consensus_state: &ConsensusState, header: Header<32>| {
+            simulate_update(
+                &client_state,
+                trusted_consensus_state,
+                None,
+                &[],
+                header,
+                now,
+            )
+            .unwrap()
+        };
+
+        // a later finalized slot advances the latest height
+        assert!(trusted_consensus_state.slot < finalized_slot);
+        let res = simulate(&trusted_consensus_state, header.clone());
+        assert_eq!(res.height, header.height());
+        assert_eq!(res.consensus_state.slot, finalized_slot);
+        assert_eq!(
+            res.client_state.unwrap().latest_execution_block_number,
+            U64(2)
+        );
+
+        // the same finalized slot and execution block with the same data changes nothing
+        let same_slot = ConsensusState {
+            slot: finalized_slot,
+            timestamp: header.timestamp,
+            ..trusted_consensus_state.clone()
+        };
+        let mut same_height = header.clone();
+        same_height.trusted_sync_committee.height = header.height();
+        assert_eq!(
+            client_state.route_update(
+                header.height(),
+                &same_slot,
+                header.height(),
+                &simulate(&trusted_consensus_state, header.clone()).consensus_state,
+            ),
+            UpdateRoute::NoOp
+        );
+        let res = simulate(&same_slot, same_height.clone());
+        assert_eq!(res.height, header.height());
+        assert_eq!(res.consensus_state, same_slot);
+        assert_eq!(res.client_state, None);
+        assert!(!res.committee_rotated);
+
+        // the same finalized slot with another execution block or different data is a misbehaviour
+        let res = simulate(&same_slot, header.clone());
+        assert_eq!(res.height, header.trusted_height());
+        assert_eq!(res.consensus_state, same_slot);
+        assert_eq!(
+            res.client_state.unwrap().frozen_height,
+            Some(header.height())
+        );
+        let different_timestamp = ConsensusState {
+            timestamp: trusted_consensus_state.timestamp,
+            ..same_slot.clone()
+        };
+        let res = simulate(&different_timestamp, same_height);
+        assert_eq!(res.consensus_state, different_timestamp);
+        assert_eq!(
+            res.client_state.unwrap().frozen_height,
+            Some(header.height())
+        );
+
+        // an earlier finalized slot does not advance the latest height, so its consensus state cannot be stored
+        let later_slot = ConsensusState {
+            slot: finalized_slot + 1,
+            ..trusted_consensus_state.clone()
+        };
+        let (new_client_state, new_consensus_state) =
+            verify_header(&client_state, &later_slot, header.clone(), now).unwrap();
+        assert_eq!(new_client_state, client_state);
+        assert_eq!(new_consensus_state.slot, finalized_slot);
+        let res = simulate_update(&client_state, &later_slot, None, &[], header.clone(), now);
+        assert!(
+            matches!(
+                res,
+                Err(Error::HistoricalConsensusStateNotSupported { height, latest_height })
+                    if height == header.height() && latest_height == client_state.latest_height()
+            ),
+            "{:?}",
+            res
+        );
+    }
+
+    #[test]
+    fn test_simulate_update_below_latest_height() {
+        let (mut client_state, trusted_consensus_state, header, now) = header_scenario();
+        // the client has advanced to 200 beyond the header at 2 since the trusted consensus state at 1
+        client_state.latest_execution_block_number = U64(200);
+        let res = simulate_update(
+            &client_state,
+            &trusted_consensus_state,
+            None,
+            &[],
+            header.clone(),
+            now,
+        );
+        assert!(
+            matches!(
+                res,
+                Err(Error::HistoricalConsensusStateNotSupported { height, latest_height })
+                    if height == header.height() && latest_height == client_state.latest_height()
+            ),
+            "{:?}",
+            res
+        );
+
+        // the header for the consensus state already stored at its height is accepted as it writes nothing new
+        let (_, new_consensus_state) =
+            verify_header(&client_state, &trusted_consensus_state, header.clone(), now).unwrap();
+        let res = simulate_update(
+            &client_state,
+            &trusted_consensus_state,
+            Some(&new_consensus_state),
+            &[],
+            header.clone(),
+            now,
+        )
+        .unwrap();
+        assert_eq!(res.height, header.height());
+        assert_eq!(res.consensus_state, new_consensus_state);
+        assert_eq!(res.client_state, None);
+    }
+
+    #[test]
+    fn test_simulate_update_zero_block_number() {
+        let (client_state, trusted_consensus_state, mut header, now) = header_scenario();
+        header.execution_update.block_number = U64(0);
+        let res = simulate_update(
+            &client_state,
+            &trusted_consensus_state,
+            None,
+            &[],
+            header,
+            now,
+        );
+        assert!(matches!(res, Err(Error::ZeroBlockNumberError)), "{:?}", res);
+    }
+
+    #[test]
+    fn test_verify_header_zero_participants() {
+        let (client_state, trusted_consensus_state, mut header, now) = header_scenario();
+        let signature_slot = header.consensus_update.signature_slot;
+        header.consensus_update.sync_aggregate.sync_committee_bits = Default::default();
+        // the signature is not even a point, so the BLS verification would fail with another error if it were performed
+        header
+            .consensus_update
+            .sync_aggregate
+            .sync_committee_signature = Default::default();
+        let res = verify_header(&client_state, &trusted_consensus_state, header, now);
+        assert!(
+            matches!(
+                res,
+                Err(Error::ZeroSyncCommitteeParticipants { signature_slot: slot }) if slot == signature_slot
+            ),
+            "{:?}",
+            res
+        );
+    }
+
+    #[test]
+    fn test_verify_header_with_cache() {
+        let (client_state, trusted_consensus_state, header, now) = header_scenario();
+        let expected =
+            verify_header(&client_state, &trusted_consensus_state, header.clone(), now).unwrap();
+
+        let mut cache = RecentSyncCommittees::<32>::new(2);
+        for _ in 0..2 {
+            let res = verify_header_with_cache(
+                &client_state,
+                &trusted_consensus_state,
+                header.clone(),
+                now,
+                &mut cache,
+            )
+            .unwrap();
+            assert_eq!(res, expected);
+            assert_eq!(
+                cache.get(
+                    &header
+                        .trusted_sync_committee
+                        .sync_committee
+                        .aggregate_pubkey
+                ),
+                Some(&header.trusted_sync_committee.sync_committee)
+            );
+        }
+
+        // a warm cache does not skip the verification of the consensus update
+        let mut invalid_header = header.clone();
+        invalid_header.consensus_update.finalized_header.1[0] = Default::default();
+        let res = verify_header_with_cache(
+            &client_state,
+            &trusted_consensus_state,
+            invalid_header,
+            now,
+            &mut cache,
+        );
+        assert!(res.is_err(), "{:?}", res);
+
+        // the trusted sync committee is still checked against the trusted consensus state
+        let mut other_consensus_state = trusted_consensus_state.clone();
+        other_consensus_state.current_sync_committee =
+            other_consensus_state.next_sync_committee.clone();
+        let res = verify_header_with_cache(
+            &client_state,
+            &other_consensus_state,
+            header,
+            now,
+            &mut cache,
+        );
+        assert!(
+            matches!(res, Err(Error::InvalidCurrentSyncCommitteeKeys { .. })),
+            "{:?}",
+            res
+        );
+    }
+
+    /// CI runs this test with and without the `std` feature, so the header must decode and verify to the same states in both builds
+    #[test]
+    fn test_decode_and_verify_header() {
+        let (client_state, trusted_consensus_state, header, now) = header_scenario();
+        let bz = IBCAny::from(header.clone()).encode_to_vec();
+        let decoded = Header::<32>::try_from(IBCAny::decode(bz.as_slice()).unwrap()).unwrap();
+        assert_eq!(decoded, header);
+        assert_eq!(IBCAny::from(decoded.clone()).encode_to_vec(), bz);
+
+        let (new_client_state, new_consensus_state) = verify_header(
+            &client_state,
+            &trusted_consensus_state,
+            decoded.clone(),
+            now,
+        )
+        .unwrap();
+        assert_eq!(new_client_state.latest_execution_block_number, U64(2));
+        assert_eq!(
+            new_consensus_state.slot,
+            header.consensus_update.finalized_header.0.slot
+        );
+        assert_eq!(new_consensus_state.timestamp, header.timestamp);
+        assert_eq!(
+            new_consensus_state.storage_root,
+            trusted_consensus_state.storage_root
+        );
+        assert_eq!(
+            ConsensusState::try_from(IBCAny::from(new_consensus_state.clone())).unwrap(),
+            new_consensus_state
+        );
+
+        // the decoded header is rejected in the same way as well
+        let res = verify_header(
+            &client_state,
+            &trusted_consensus_state,
+            decoded,
+            (now + client_state.trusting_period).unwrap(),
+        );
+        assert!(
+            matches!(res, Err(Error::OutOfTrustingPeriod { .. })),
+            "{:?}",
+            res
+        );
+    }
+
+    #[test]
+    fn test_verify_misbehaviour() {
+        let (client_state, trusted_consensus_state, misbehaviour, now) = misbehaviour_scenario();
+        let trusted_consensus_states = (&trusted_consensus_state, &trusted_consensus_state);
+
+        let res = verify_misbehaviour(&client_state, trusted_consensus_states, &misbehaviour, now);
+        assert!(res.is_ok(), "{:?}", res);
+
+        // the trusted consensus states have expired
+        let res = verify_misbehaviour(
+            &client_state,
+            trusted_consensus_states,
+            &misbehaviour,
+            (now + client_state.trusting_period).unwrap(),
+        );
+        assert!(
+            matches!(res, Err(Error::OutOfTrustingPeriod { .. })),
+            "{:?}",
+            res
+        );
+
+        // a frozen client cannot be frozen again
+        let frozen_client_state = client_state
+            .clone()
+            .with_frozen_height(misbehaviour.height());
+        let res = verify_misbehaviour(
+            &frozen_client_state,
+            trusted_consensus_states,
+            &misbehaviour,
+            now,
+        );
+        assert!(matches!(res, Err(Error::ClientFrozen { .. })), "{:?}", res);
+    }
+}
@@ -0,0 +1,681 @@
+//! Compatibility layer for running the client under the 08-wasm light client of ibc-go
+//!
+//! The 08-wasm module stores the client state and consensus states wrapped in the `ibc.lightclients.wasm.v1` envelopes,
+//! and calls the contract with the JSON messages defined here. A contract built on this module unwraps the stored states,
+//! deserializes the message and calls the dispatch functions, which only adapt the message shapes to the verification logic of this crate.
+use crate::client_state::{verify_delay_periods_passed, ClientState, UpdateResult};
+use crate::consensus_state::ConsensusState;
+use crate::errors::Error;
+use crate::internal_prelude::*;
+use core::str::FromStr;
+use core::time::Duration;
+use cosmwasm_std::Binary;
+use ethereum_ibc_proto::ibc::core::client::v1::Height as RawHeight;
+use ethereum_ibc_proto::ibc::lightclients::wasm::v1::{
+    ClientMessage as RawWasmClientMessage, ClientState as RawWasmClientState,
+    ConsensusState as RawWasmConsensusState,
+};
+use ibc::core::ics02_client::client_state::ClientState as Ics2ClientState;
+use ibc::core::ics02_client::error::ClientError;
+use ibc::core::ics23_commitment::commitment::{CommitmentPrefix, CommitmentProofBytes};
+use ibc::core::ics24_host::identifier::ClientId;
+use ibc::core::ics24_host::Path;
+use ibc::core::{ContextError, ValidationContext};
+use ibc::timestamp::Timestamp;
+use ibc_proto::google::protobuf::Any;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+
+pub const WASM_CLIENT_STATE_TYPE_URL: &str = "/ibc.lightclients.wasm.v1.ClientState";
+pub const WASM_CONSENSUS_STATE_TYPE_URL: &str = "/ibc.lightclients.wasm.v1.ConsensusState";
+pub const WASM_CLIENT_MESSAGE_TYPE_URL: &str = "/ibc.lightclients.wasm.v1.ClientMessage";
+
+/// Wraps the client state into the `Any` of the wasm client state with the checksum of the contract
+pub fn wrap_client_state<const SYNC_COMMITTEE_SIZE: usize>(
+    client_state: ClientState<SYNC_COMMITTEE_SIZE>,
+    checksum: Vec<u8>,
+) -> Any {
+    let latest_height = client_state.latest_height();
+    Any {
+        type_url: WASM_CLIENT_STATE_TYPE_URL.to_string(),
+        value: RawWasmClientState {
+            data: Any::from(client_state).encode_to_vec(),
+            checksum,
+            latest_height: Some(RawHeight {
+                revision_number: latest_height.revision_number(),
+                revision_height: latest_height.revision_height(),
+            }),
+        }
+        .encode_to_vec(),
+    }
+}
+
+/// Unwraps the `Any` of the wasm client state into the client state and the checksum of the contract
+pub fn unwrap_client_state<const SYNC_COMMITTEE_SIZE: usize>(
+    any: Any,
+) -> Result<(ClientState<SYNC_COMMITTEE_SIZE>, Vec<u8>), Error> {
+    let raw: RawWasmClientState = decode_envelope(&[WASM_CLIENT_STATE_TYPE_URL], any)?;
+    let client_state = Any::decode(raw.data.as_slice())
+        .map_err(|e| Error::decode_error("data", Error::Decode(e)))?
+        .try_into()
+        .map_err(|e| Error::decode_error("data", Error::ICS02(e)))?;
+    Ok((client_state, raw.checksum))
+}
+
+/// Wraps the consensus state into the `Any` of the wasm consensus state
+pub fn wrap_consensus_state(consensus_state: ConsensusState) -> Any {
+    Any {
+        type_url: WASM_CONSENSUS_STATE_TYPE_URL.to_string(),
+        value: RawWasmConsensusState {
+            data: Any::from(consensus_state).encode_to_vec(),
+        }
+        .encode_to_vec(),
+    }
+}
+
+/// Unwraps the `Any` of the wasm consensus state into the consensus state
+pub fn unwrap_consensus_state(any: Any) -> Result<ConsensusState, Error> {
+    let raw: RawWasmConsensusState = decode_envelope(&[WASM_CONSENSUS_STATE_TYPE_URL], any)?;
+    Any::decode(raw.data.as_slice())
+        .map_err(|e| Error::decode_error("data", Error::Decode(e)))?
+        .try_into()
+        .map_err(|e| Error::decode_error("data", Error::ICS02(e)))
+}
+
+/// Wraps the header or misbehaviour into the `Any` of the wasm client message
+pub fn wrap_client_message(client_message: Any) -> Any {
+    Any {
+        type_url: WASM_CLIENT_MESSAGE_TYPE_URL.to_string(),
+        value: RawWasmClientMessage {
+            data: client_message.encode_to_vec(),
+        }
+        .encode_to_vec(),
+    }
+}
+
+/// Unwraps the `Any` of the wasm client message into the `Any` of the header or misbehaviour
+pub fn unwrap_client_message(any: Any) -> Result<Any, Error> {
+    let raw: RawWasmClientMessage = decode_envelope(&[WASM_CLIENT_MESSAGE_TYPE_URL], any)?;
+    decode_client_message(&raw.data)
+}
+
+fn decode_envelope<T: Message + Default>(
+    expected: &'static [&'static str],
+    any: Any,
+) -> Result<T, Error> {
+    if !expected.contains(&any.type_url.as_str()) {
+        return Err(Error::UnexpectedTypeUrl {
+            expected,
+            got: any.type_url,
+        });
+    }
+    T::decode(any.value.as_slice()).map_err(Error::Decode)
+}
+
+fn decode_client_message(bz: &[u8]) -> Result<Any, Error> {
+    Any::decode(bz).map_err(|e| Error::decode_error("client_message", Error::Decode(e)))
+}
+
+/// The height in the JSON messages of 08-wasm, where zero fields are omitted
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WasmHeight {
+    #[serde(default)]
+    pub revision_number: u64,
+    #[serde(default)]
+    pub revision_height: u64,
+}
+
+impl From<ibc::Height> for WasmHeight {
+    fn from(value: ibc::Height) -> Self {
+        Self {
+            revision_number: value.revision_number(),
+            revision_height: value.revision_height(),
+        }
+    }
+}
+
+impl TryFrom<WasmHeight> for ibc::Height {
+    type Error = Error;
+
+    fn try_from(value: WasmHeight) -> Result<Self, Self::Error> {
+        Ok(ibc::Height::new(
+            value.revision_number,
+            value.revision_height,
+        )?)
+    }
+}
+
+/// The merkle path of a commitment, whose first key is the commitment prefix and second key is the path
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerklePath {
+    pub key_path: Vec<String>,
+}
+
+impl MerklePath {
+    /// Returns the commitment prefix and the path
+    pub fn split(&self) -> Result<(CommitmentPrefix, Path), Error> {
+        match self.key_path.as_slice() {
+            [prefix, path] => Ok((
+                CommitmentPrefix::try_from(prefix.as_bytes().to_vec()).map_err(|_| {
+                    Error::InvalidMerklePath("the commitment prefix must not be empty".into())
+                })?,
+                Path::from_str(path).map_err(|e| Error::InvalidMerklePath(e.to_string()))?,
+            )),
+            key_path => Err(Error::InvalidMerklePath(format!(
+                "the key path must consist of the prefix and the path: length={}",
+                key_path.len()
+            ))),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifyMembershipMsg {
+    pub height: WasmHeight,
+    pub delay_time_period: u64,
+    pub delay_block_period: u64,
+    pub proof: Binary,
+    pub path: MerklePath,
+    pub value: Binary,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifyNonMembershipMsg {
+    pub height: WasmHeight,
+    pub delay_time_period: u64,
+    pub delay_block_period: u64,
+    pub proof: Binary,
+    pub path: MerklePath,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpdateStateMsg {
+    pub client_message: Binary,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpdateStateOnMisbehaviourMsg {
+    pub client_message: Binary,
+}
+
+/// The messages of the `sudo` entry point
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SudoMsg {
+    UpdateState(UpdateStateMsg),
+    UpdateStateOnMisbehaviour(UpdateStateOnMisbehaviourMsg),
+    VerifyMembership(VerifyMembershipMsg),
+    VerifyNonMembership(VerifyNonMembershipMsg),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusMsg {}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimestampAtHeightMsg {
+    pub height: WasmHeight,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifyClientMessageMsg {
+    pub client_message: Binary,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckForMisbehaviourMsg {
+    pub client_message: Binary,
+}
+
+/// The messages of the `query` entry point
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    Status(StatusMsg),
+    TimestampAtHeight(TimestampAtHeightMsg),
+    VerifyClientMessage(VerifyClientMessageMsg),
+    CheckForMisbehaviour(CheckForMisbehaviourMsg),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusResult {
+    /// One of `Active`, `Frozen` and `Expired`
+    pub status: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimestampAtHeightResult {
+    /// The timestamp of the consensus state in nanoseconds
+    pub timestamp: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpdateStateResult {
+    /// The heights of the consensus states written by the update
+    pub heights: Vec<WasmHeight>,
+}
+
+impl<const SYNC_COMMITTEE_SIZE: usize> From<&UpdateResult<SYNC_COMMITTEE_SIZE>>
+    for UpdateStateResult
+{
+    fn from(value: &UpdateResult<SYNC_COMMITTEE_SIZE>) -> Self {
+        Self {
+            heights: core::iter::once(value.height.into()).collect(),
+        }
+    }
+}
+
+/// Verify the membership proof of `VerifyMembershipMsg` against the consensus state at `msg.height`
+///
+/// `ctx` reads the current time and height of the host, and the processed time and height of the consensus state at `msg.height` stored by the contract,
+/// and the delay periods of the message must have passed since the consensus state was processed.
+pub fn verify_membership<const SYNC_COMMITTEE_SIZE: usize>(
+    client_state: &ClientState<SYNC_COMMITTEE_SIZE>,
+    ctx: &dyn ValidationContext,
+    client_id: &ClientId,
+    consensus_state: &ConsensusState,
+    msg: VerifyMembershipMsg,
+) -> Result<(), Error> {
+    let height = msg.height.try_into()?;
+    verify_delay_periods_passed(
+        ctx,
+        client_id,
+        height,
+        Duration::from_nanos(msg.delay_time_period),
+        msg.delay_block_period,
+    )?;
+    let (prefix, path) = msg.path.split()?;
+    client_state
+        .verify_membership(
+            height,
+            &prefix,
+            &decode_proof(msg.proof)?,
+            &consensus_state.storage_root,
+            path,
+            msg.value.into(),
+        )
+        .map_err(Error::ICS02)
+}
+
+/// Verify the non-membership proof of `VerifyNonMembershipMsg` against the consensus state at `msg.height`
+///
+/// The delay periods are verified as `verify_membership` does.
+pub fn verify_non_membership<const SYNC_COMMITTEE_SIZE: usize>(
+    client_state: &ClientState<SYNC_COMMITTEE_SIZE>,
+    ctx: &dyn ValidationContext,
+    client_id: &ClientId,
+    consensus_state: &ConsensusState,
+    msg: VerifyNonMembershipMsg,
+) -> Result<(), Error> {
+    let height = msg.height.try_into()?;
+    verify_delay_periods_passed(
+        ctx,
+        client_id,
+        height,
+        Duration::from_nanos(msg.delay_time_period),
+        msg.delay_block_period,
+    )?;
+    let (prefix, path) = msg.path.split()?;
+    client_state
+        .verify_non_membership(
+            height,
+            &prefix,
+            &decode_proof(msg.proof)?,
+            &consensus_state.storage_root,
+            path,
+        )
+        .map_err(Error::ICS02)
+}
+
+fn decode_proof(proof: Binary) -> Result<CommitmentProofBytes, Error> {
+    CommitmentProofBytes::try_from(proof.to_vec())
+        .map_err(|_| Error::InvalidProofFormatError("proof must not be empty".into()))
+}
+
+/// Verify the header of `UpdateStateMsg` and return the result of the update, see `ClientState::update_state`
+///
+/// `ctx` reads the consensus states stored by the contract, and `stored_heights` are the heights of the stored consensus states considered for pruning.
+pub fn update_state<const SYNC_COMMITTEE_SIZE: usize>(
+    client_state: &ClientState<SYNC_COMMITTEE_SIZE>,
+    ctx: &dyn ValidationContext,
+    client_id: ClientId,
+    msg: UpdateStateMsg,
+    stored_heights: &[ibc::Height],
+) -> Result<UpdateResult<SYNC_COMMITTEE_SIZE>, ClientError> {
+    let header = decode_client_message(msg.client_message.as_slice())?;
+    client_state.update_state(ctx, client_id, header, stored_heights)
+}
+
+/// Verify the misbehaviour of `UpdateStateOnMisbehaviourMsg` and return the client state frozen at the misbehaviour height
+pub fn update_state_on_misbehaviour<const SYNC_COMMITTEE_SIZE: usize>(
+    client_state: &ClientState<SYNC_COMMITTEE_SIZE>,
+    ctx: &dyn ValidationContext,
+    client_id: ClientId,
+    msg: UpdateStateOnMisbehaviourMsg,
+) -> Result<ClientState<SYNC_COMMITTEE_SIZE>, ContextError> {
+    let any = decode_client_message(msg.client_message.as_slice())?;
+    Ok(client_state
+        .update_state_on_misbehaviour(ctx, client_id, any)?
+        .client_state)
+}
+
+/// Returns the status of the client with the consensus state at its latest height at `host_timestamp`
+pub fn status<const SYNC_COMMITTEE_SIZE: usize>(
+    client_state: &ClientState<SYNC_COMMITTEE_SIZE>,
+    latest_consensus_state: &ConsensusState,
+    host_timestamp: Timestamp,
+) -> StatusResult {
+    let status = if client_state.is_frozen() {
+        "Frozen"
+    } else if client_state
+        .diagnostics(latest_consensus_state, host_timestamp)
+        .trusting_period_remaining
+        .is_none()
+    {
+        "Expired"
+    } else {
+        "Active"
+    };
+    StatusResult {
+        status: status.to_string(),
+    }
+}
+
+/// Returns the timestamp of the consensus state
+pub fn timestamp_at_height(consensus_state: &ConsensusState) -> TimestampAtHeightResult {
+    TimestampAtHeightResult {
+        timestamp: consensus_state.timestamp.nanoseconds(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::address::Address;
+    use crate::client_state::ETHEREUM_CLIENT_REVISION_NUMBER;
+    use crate::eth_client_type;
+    use crate::header::ETHEREUM_HEADER_TYPE_URL;
+    use crate::misc::height_from_block_number;
+    use crate::preset::mainnet_fork_parameters;
+    use crate::testing::MockContext;
+    use crate::{MinimalClientState, MINIMAL_SYNC_COMMITTEE_SIZE};
+    use cosmwasm_std::{from_json, to_json_vec};
+    use ethereum_consensus::types::{H256, U64};
+    use ethereum_light_client_verifier::context::Fraction;
+    use hex_literal::hex;
+
+    fn client_state() -> MinimalClientState {
+        MinimalClientState {
+            fork_parameters: mainnet_fork_parameters(),
+            ibc_address: Address(hex!("a7f733a4fEA1071f58114b203F57444969b86524")),
+            ibc_commitments_slot: H256(hex!(
+                "1ee222554989dda120e26ecacf756fe1235cd8d726706b57517715dde4f0c900"
+            )),
+            trust_level: Fraction::new(2, 3).unwrap(),
+            trusting_period: Duration::from_secs(100),
+            latest_execution_block_number: 1.into(),
+            ..Default::default()
+        }
+    }
+
+    fn consensus_state() -> ConsensusState {
+        ConsensusState {
+            slot: 1.into(),
+            storage_root: hex!("27cd08827e6bf1e435832f4b2660107beb562314287b3fa534f3b189574c0cca")
+                .to_vec()
+                .into(),
+            timestamp: Timestamp::from_nanoseconds(1_000 * 1_000_000_000).unwrap(),
+            ..Default::default()
+        }
+    }
+
+    fn client_id() -> ClientId {
+        ClientId::new(eth_client_type(), 0).unwrap()
+    }
+
+    /// Returns the host where the consensus state at height 1 of `client_id` was processed at 1000 seconds and height 10
+    fn processed_context(client_id: &ClientId) -> MockContext<MINIMAL_SYNC_COMMITTEE_SIZE> {
+        let mut ctx = MockContext::new(
+            Timestamp::from_nanoseconds(1_000 * 1_000_000_000).unwrap(),
+            ibc::Height::new(1, 10).unwrap(),
+        );
+        ctx.store_consensus_state(
+            client_id.clone(),
+            height_from_block_number(U64(1)).unwrap(),
+            consensus_state(),
+        );
+        ctx
+    }
+
+    #[test]
+    fn test_wasm_envelopes() {
+        let any = wrap_client_state(client_state(), b"checksum".to_vec());
+        assert_eq!(any.type_url, WASM_CLIENT_STATE_TYPE_URL);
+        let raw = RawWasmClientState::decode(any.value.as_slice()).unwrap();
+        assert_eq!(
+            raw.latest_height,
+            Some(RawHeight {
+                revision_number: ETHEREUM_CLIENT_REVISION_NUMBER,
+                revision_height: 1,
+            })
+        );
+        let (unwrapped, checksum) = unwrap_client_state(any).unwrap();
+        assert_eq!(unwrapped, client_state());
+        assert_eq!(checksum, b"checksum".to_vec());
+
+        let any = wrap_consensus_state(consensus_state());
+        assert_eq!(any.type_url, WASM_CONSENSUS_STATE_TYPE_URL);
+        assert_eq!(unwrap_consensus_state(any).unwrap(), consensus_state());
+
+        // the envelope of the other type is rejected
+        let res = unwrap_consensus_state(wrap_client_state(client_state(), Vec::new()));
+        assert!(
+            matches!(res, Err(Error::UnexpectedTypeUrl { ref got, .. }) if got == WASM_CLIENT_STATE_TYPE_URL),
+            "{:?}",
+            res
+        );
+    }
+
+    #[test]
+    fn test_wasm_update_state_msg_round_trip() {
+        let header = Any {
+            type_url: ETHEREUM_HEADER_TYPE_URL.to_string(),
+            value: b"header".to_vec(),
+        };
+        let client_message = wrap_client_message(header.clone());
+        assert_eq!(client_message.type_url, WASM_CLIENT_MESSAGE_TYPE_URL);
+        let raw = RawWasmClientMessage::decode(client_message.value.as_slice()).unwrap();
+
+        // 08-wasm passes the data of the client message to the contract
+        let msg = SudoMsg::UpdateState(UpdateStateMsg {
+            client_message: raw.data.into(),
+        });
+        let json = to_json_vec(&msg).unwrap();
+        assert!(String::from_utf8(json.clone())
+            .unwrap()
+            .starts_with(r#"{"update_state":{"client_message":""#));
+        match from_json::<SudoMsg>(&json).unwrap() {
+            SudoMsg::UpdateState(msg) => {
+                assert_eq!(
+                    decode_client_message(msg.client_message.as_slice()).unwrap(),
+                    header
+                );
+            }
+            msg => panic!("unexpected message: {:?}", msg),
+        }
+        assert_eq!(unwrap_client_message(client_message).unwrap(), header);
+
+        let res = UpdateStateResult::from(&UpdateResult::<32> {
+            height: height_from_block_number(U64(10)).unwrap(),
+            consensus_state: consensus_state(),
+            client_state: None,
+            committee_rotated: false,
+            prunable_heights: Vec::new(),
+        });
+        // the zero revision number is omitted by ibc-go
+        assert_eq!(
+            from_json::<UpdateStateResult>(br#"{"heights":[{"revision_height":10}]}"#).unwrap(),
+            res
+        );
+    }
+
+    #[test]
+    fn test_wasm_verify_membership() {
+        let json = format!(
+            r#"{{"verify_membership":{{"height":{{"revision_height":1}},"delay_time_period":0,"delay_block_period":0,"proof":"{}","path":{{"key_path":["ibc","clients/lcp-client-0/clientState"]}},"value":"{}"}}}}"#,
+            Binary::from(hex!("f90159f901118080a0143145e818eeff83817419a6632ea193fd1acaa4f791eb17282f623f38117f56a0e6ee0a993a7254ee9253d766ea005aec74eb1e11656961f0fb11323f4f91075580808080a01efae04adc2e970b4af3517581f41ce2ba4ff60492d33696c1e2a5ab70cb55bba03bac3f5124774e41fb6efdd7219530846f9f6441045c4666d2855c6598cfca00a020d7122ffc86cb37228940b5a9441e9fd272a3450245c9130ca3ab00bc1cd6ef80a0047f255205a0f2b0e7d29d490abf02bfb62c3ed201c338bc7f0088fa9c5d77eda069fecc766fcb2df04eb3a834b1f4ba134df2be114479e251d9cc9b6ba493077b80a094c3ed6a7ef63a6a67e46cc9876b9b1882eeba3d28e6d61bb15cdfb207d077e180f843a03e077f3dfd0489e70c68282ced0126c62fcef50acdcb7f57aa4552b87b456b11a1a05dc044e92e82db28c96fd98edd502949612b06e8da6dd74664a43a5ed857b298").to_vec()).to_base64(),
+            Binary::from(hex!("0a242f6962632e6c69676874636c69656e74732e6c63702e76312e436c69656e74537461746512ed010a208083673c69fe3f098ea79a799d9dbb99c39b4b4f17a1a79ef58bdf8ae86299951080f524220310fb012a1353575f48415244454e494e475f4e45454445442a1147524f55505f4f55545f4f465f44415445320e494e54454c2d53412d3030323139320e494e54454c2d53412d3030323839320e494e54454c2d53412d3030333334320e494e54454c2d53412d3030343737320e494e54454c2d53412d3030363134320e494e54454c2d53412d3030363135320e494e54454c2d53412d3030363137320e494e54454c2d53412d30303832383a14cb96f8d6c2d543102184d679d7829b39434e4eec48015001").to_vec()).to_base64(),
+        );
+        let msg = match from_json::<SudoMsg>(json.as_bytes()).unwrap() {
+            SudoMsg::VerifyMembership(msg) => msg,
+            msg => panic!("unexpected message: {:?}", msg),
+        };
+        let client_id = client_id();
+        let ctx = processed_context(&client_id);
+        let res = verify_membership(
+            &client_state(),
+            &ctx,
+            &client_id,
+            &consensus_state(),
+            msg.clone(),
+        );
+        assert!(res.is_ok(), "{:?}", res);
+
+        // a different value is rejected
+        let res = verify_membership(
+            &client_state(),
+            &ctx,
+            &client_id,
+            &consensus_state(),
+            VerifyMembershipMsg {
+                value: b"value".to_vec().into(),
+                ..msg.clone()
+            },
+        );
+        assert!(res.is_err());
+
+        // the key path must consist of the prefix and the path
+        let res = verify_membership(
+            &client_state(),
+            &ctx,
+            &client_id,
+            &consensus_state(),
+            VerifyMembershipMsg {
+                path: MerklePath {
+                    key_path: vec!["clients/lcp-client-0/clientState".to_string()],
+                },
+                ..msg
+            },
+        );
+        assert!(matches!(res, Err(Error::InvalidMerklePath(_))), "{:?}", res);
+    }
+
+    #[test]
+    fn test_wasm_verify_delay_periods() {
+        let client_id = client_id();
+        let mut ctx = processed_context(&client_id);
+        let msg = VerifyNonMembershipMsg {
+            height: WasmHeight {
+                revision_number: 0,
+                revision_height: 1,
+            },
+            delay_time_period: Duration::from_secs(60).as_nanos() as u64,
+            delay_block_period: 5,
+            proof: b"proof".to_vec().into(),
+            path: MerklePath {
+                key_path: vec![
+                    "ibc".to_string(),
+                    "clients/client-0/clientState".to_string(),
+                ],
+            },
+        };
+
+        // neither delay period has passed
+        let res = verify_non_membership(
+            &client_state(),
+            &ctx,
+            &client_id,
+            &consensus_state(),
+            msg.clone(),
+        );
+        assert!(
+            matches!(res, Err(Error::NotEnoughTimeElapsed { .. })),
+            "{:?}",
+            res
+        );
+
+        // the delay time period has passed but the delay block period has not
+        ctx.set_host_timestamp(Timestamp::from_nanoseconds(1_060 * 1_000_000_000).unwrap());
+        let res = verify_non_membership(
+            &client_state(),
+            &ctx,
+            &client_id,
+            &consensus_state(),
+            msg.clone(),
+        );
+        assert!(
+            matches!(res, Err(Error::NotEnoughBlocksElapsed { .. })),
+            "{:?}",
+            res
+        );
+
+        // both delay periods have passed, so the proof is verified
+        ctx.set_host_height(ibc::Height::new(1, 15).unwrap());
+        let res = verify_non_membership(
+            &client_state(),
+            &ctx,
+            &client_id,
+            &consensus_state(),
+            msg.clone(),
+        );
+        assert!(matches!(res, Err(Error::ICS02(_))), "{:?}", res);
+
+        // the consensus state at the height must have been processed by the contract
+        let res = verify_non_membership(
+            &client_state(),
+            &ctx,
+            &ClientId::new(eth_client_type(), 1).unwrap(),
+            &consensus_state(),
+            msg,
+        );
+        assert!(
+            matches!(res, Err(Error::ProcessedTimeNotFound { .. })),
+            "{:?}",
+            res
+        );
+    }
+
+    #[test]
+    fn test_wasm_queries() {
+        let consensus_state = consensus_state();
+        assert_eq!(
+            timestamp_at_height(&consensus_state).timestamp,
+            1_000 * 1_000_000_000
+        );
+        let now = Timestamp::from_nanoseconds(1_050 * 1_000_000_000).unwrap();
+        assert_eq!(
+            status(&client_state(), &consensus_state, now).status,
+            "Active"
+        );
+        let now = Timestamp::from_nanoseconds(1_100 * 1_000_000_000).unwrap();
+        assert_eq!(
+            status(&client_state(), &consensus_state, now).status,
+            "Expired"
+        );
+        let frozen = client_state().with_frozen_height(height_from_block_number(U64(1)).unwrap());
+        assert_eq!(status(&frozen, &consensus_state, now).status, "Frozen");
+
+        let msg: QueryMsg = from_json(br#"{"status":{}}"#).unwrap();
+        assert_eq!(msg, QueryMsg::Status(StatusMsg {}));
+        let msg: QueryMsg =
+            from_json(br#"{"timestamp_at_height":{"height":{"revision_height":3}}}"#).unwrap();
+        assert_eq!(
+            msg,
+            QueryMsg::TimestampAtHeight(TimestampAtHeightMsg {
+                height: WasmHeight {
+                    revision_number: 0,
+                    revision_height: 3,
+                },
+            })
+        );
+    }
+}
@@ -0,0 +1,355 @@
+//! Golden `Any` encodings of the messages exchanged with the counterpart implementations, i.e. yui-relayer's ethereum prover and ibc-solidity's ethereum client
+//!
+//! The vectors in `testdata/interop` are the canonical proto3 encodings of `ethereum.proto` as produced by the Go marshaller:
+//! the fields are ordered by their numbers and the fields with default values are omitted.
+//! They were encoded from the proto definitions rather than captured from a running counterpart stack,
+//! so a vector must be replaced with the captured bytes if the two ever disagree.
+//!
+//! Every vector is decoded into the expected values, and re-encoding it must produce the same bytes.
+//! The accepted differences are the encodings that this crate decodes but normalizes on re-encoding:
+//! - a zero `frozen_height`, which non-nullable heights are encoded into, means the client is not frozen and is omitted;
+//!   it is only accepted by `ClientState::try_from_any_lenient`, since the strict `TryFrom<Any>` rejects every encoding that does not re-encode to itself
+//! - an empty `next_sync_committee` means the update has no next sync committee and is omitted
+//! - the deprecated `trusted_sync_committee` of a misbehaviour is re-encoded into `trusted_sync_committee_1` and `trusted_sync_committee_2`
+//!
+//! `header_without_account_update` is a header that older relayers could submit without proving the storage root, which is now rejected.
+use ethereum_ibc::address::Address;
+use ethereum_ibc::commitment::CommitmentValueCodec;
+use ethereum_ibc::consensus::beacon::{BeaconBlockHeader, Version};
+use ethereum_ibc::consensus::bls::PublicKey;
+use ethereum_ibc::consensus::types::{H256, U64};
+use ethereum_ibc::consensus_state::ConsensusState;
+use ethereum_ibc::light_client_verifier::context::Fraction;
+use ethereum_ibc::light_client_verifier::misbehaviour::Misbehaviour as MisbehaviourData;
+use ethereum_ibc::types::{ConsensusUpdateInfo, TrustedSyncCommittee};
+use ethereum_ibc::{
+    MinimalClientState, MinimalHeader, MinimalMisbehaviour, MINIMAL_SYNC_COMMITTEE_SIZE,
+};
+use hex_literal::hex;
+use ibc::timestamp::Timestamp;
+use ibc::Height;
+use ibc_proto::google::protobuf::Any;
+use prost::Message;
+use std::time::Duration;
+
+/// The compressed generator of G1
+const G1_GENERATOR: [u8; 48] = hex!("97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb");
+/// The compressed generator of G2
+const G2_GENERATOR: [u8; 96] = hex!("93e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8");
+
+fn load(name: &str) -> (Vec<u8>, Any) {
+    let path = format!(
+        "{}/testdata/interop/{}.hex",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    );
+    let bz = hex::decode(std::fs::read_to_string(&path).unwrap().trim()).unwrap();
+    let any = Any::decode(bz.as_slice()).unwrap();
+    // the `Any` itself must be canonical too
+    assert_eq!(any.encode_to_vec(), bz, "{}", name);
+    (bz, any)
+}
+
+/// The i-th public key of the vectors, a compressed point whose x coordinate is `i`
+fn pubkey(i: u8) -> PublicKey {
+    let mut pk = [0u8; 48];
+    pk[0] = 0x80;
+    pk[47] = i;
+    PublicKey::try_from(pk.to_vec()).unwrap()
+}
+
+fn filled(b: u8) -> H256 {
+    H256([b; 32])
+}
+
+fn branch(first: u8, len: u8) -> Vec<H256> {
+    (0..len).map(|i| filled(first + i)).collect()
+}
+
+fn beacon_header(slot: u64, proposer_index: u64, first: u8) -> BeaconBlockHeader {
+    BeaconBlockHeader {
+        slot: slot.into(),
+        proposer_index: proposer_index.into(),
+        parent_root: filled(first),
+        state_root: filled(first + 1),
+        body_root: filled(first + 2),
+    }
+}
+
+fn assert_sync_committee(
+    trusted: &TrustedSyncCommittee<MINIMAL_SYNC_COMMITTEE_SIZE>,
+    first_pubkey: u8,
+) {
+    let sync_committee = &trusted.sync_committee;
+    assert_eq!(sync_committee.pubkeys.len(), MINIMAL_SYNC_COMMITTEE_SIZE);
+    for (i, pk) in sync_committee.pubkeys.iter().enumerate() {
+        assert_eq!(*pk, pubkey(first_pubkey + i as u8));
+    }
+    assert_eq!(
+        sync_committee.aggregate_pubkey,
+        PublicKey::try_from(G1_GENERATOR.to_vec()).unwrap()
+    );
+}
+
+fn assert_consensus_update(
+    update: &ConsensusUpdateInfo<MINIMAL_SYNC_COMMITTEE_SIZE>,
+    finalized_header_first: u8,
+) {
+    assert_eq!(update.attested_header, beacon_header(200, 3, 0x11));
+    assert_eq!(
+        update.finalized_header,
+        (
+            beacon_header(192, 2, finalized_header_first),
+            branch(0x41, 6)
+        )
+    );
+    assert_eq!(update.finalized_execution_root, filled(0x51));
+    assert_eq!(update.finalized_execution_branch, branch(0x61, 4));
+    assert_eq!(
+        update.sync_aggregate.sync_committee_bits.count_ones(),
+        MINIMAL_SYNC_COMMITTEE_SIZE
+    );
+    assert_eq!(
+        update.sync_aggregate.sync_committee_signature.0.to_vec(),
+        G2_GENERATOR.to_vec()
+    );
+    assert_eq!(update.signature_slot, U64(201));
+}
+
+fn assert_account_update(header: &MinimalHeader) {
+    assert_eq!(header.account_update.account_proof.len(), 2);
+    assert_eq!(header.account_update.account_storage_root, filled(0xb1));
+}
+
+#[test]
+fn test_client_state() {
+    let (bz, any) = load("client_state");
+    let client_state = MinimalClientState::try_from(any).unwrap();
+    assert_eq!(
+        client_state.genesis_validators_root,
+        H256(hex!(
+            "4b363db94e286120d76eb905340fdd4e54bfe9f06bf33ff6cf5ad27f511bfe95"
+        ))
+    );
+    assert_eq!(client_state.min_sync_committee_participants, U64(1));
+    assert_eq!(client_state.genesis_time, U64(1606824023));
+    let fork_parameters = &client_state.fork_parameters;
+    assert_eq!(fork_parameters.genesis_version(), &Version([0, 0, 0, 0]));
+    let forks = fork_parameters.forks();
+    assert_eq!(forks.len(), 4);
+    for (i, (epoch, state_root_gindex, block_number_gindex)) in [
+        (74240, 0, 0),
+        (144896, 18, 22),
+        (194048, 18, 22),
+        (269568, 34, 38),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        assert_eq!(forks[i].version, Version([i as u8 + 1, 0, 0, 0]));
+        assert_eq!(forks[i].epoch, U64(epoch));
+        assert_eq!(forks[i].spec.finalized_root_gindex, 105);
+        assert_eq!(forks[i].spec.current_sync_committee_gindex, 54);
+        assert_eq!(forks[i].spec.next_sync_committee_gindex, 55);
+        assert_eq!(
+            forks[i].spec.execution_payload_state_root_gindex,
+            state_root_gindex
+        );
+        assert_eq!(
+            forks[i].spec.execution_payload_block_number_gindex,
+            block_number_gindex
+        );
+    }
+    assert_eq!(client_state.seconds_per_slot, U64(12));
+    assert_eq!(client_state.slots_per_epoch, U64(32));
+    assert_eq!(client_state.epochs_per_sync_committee_period, U64(256));
+    assert_eq!(
+        client_state.ibc_address,
+        Address(hex!("ff77D90D6aA12db33d3Ba50A34fB25401f6e4c4F"))
+    );
+    assert_eq!(
+        client_state.ibc_commitments_slot,
+        H256(hex!(
+            "1ee222554989dda120e26ecacf756fe1235cd8d726706b57517715dde4f0c900"
+        ))
+    );
+    assert_eq!(client_state.trust_level, Fraction::new(2, 3).unwrap());
+    assert_eq!(
+        client_state.trusting_period,
+        Duration::from_secs(60 * 60 * 27)
+    );
+    assert_eq!(client_state.max_clock_drift, Duration::from_secs(60));
+    assert_eq!(client_state.latest_execution_block_number, U64(1234));
+    assert_eq!(client_state.frozen_height, None);
+    assert_eq!(client_state.max_intermediate_consensus_updates, U64(4));
+    assert_eq!(
+        client_state.commitment_value_codec,
+        CommitmentValueCodec::Raw
+    );
+    assert_eq!(client_state.misbehaviour_evidence_digest, None);
+    assert_eq!(Any::from(client_state).encode_to_vec(), bz);
+}
+
+#[test]
+fn test_frozen_client_state() {
+    let (bz, any) = load("client_state_frozen");
+    let client_state = MinimalClientState::try_from(any).unwrap();
+    assert_eq!(
+        client_state.frozen_height,
+        Some(Height::new(0, 1234).unwrap())
+    );
+    assert_eq!(Any::from(client_state).encode_to_vec(), bz);
+}
+
+#[test]
+fn test_client_state_with_zero_frozen_height() {
+    // the zero height is omitted on re-encoding, so the strict decoding rejects it
+    let (_, any) = load("client_state_zero_frozen_height");
+    let err = MinimalClientState::try_from(any.clone()).unwrap_err();
+    assert!(
+        err.to_string().contains("not the canonical encoding"),
+        "{}",
+        err
+    );
+
+    // accepted difference: the lenient decoding reads the zero height as not frozen
+    let client_state = MinimalClientState::try_from_any_lenient(any).unwrap();
+    assert_eq!(client_state.frozen_height, None);
+    let (unfrozen, unfrozen_any) = load("client_state");
+    assert_eq!(
+        client_state,
+        MinimalClientState::try_from(unfrozen_any).unwrap()
+    );
+    assert_eq!(Any::from(client_state).encode_to_vec(), unfrozen);
+}
+
+#[test]
+fn test_consensus_state() {
+    let (bz, any) = load("consensus_state");
+    let consensus_state = ConsensusState::try_from(any).unwrap();
+    assert_eq!(consensus_state.slot, U64(6144));
+    assert_eq!(
+        consensus_state.storage_root.as_bytes(),
+        filled(0xb1).as_bytes()
+    );
+    assert_eq!(
+        consensus_state.timestamp,
+        Timestamp::from_nanoseconds(1_700_000_000 * 1_000_000_000).unwrap()
+    );
+    assert_eq!(
+        consensus_state.current_sync_committee,
+        PublicKey::try_from(G1_GENERATOR.to_vec()).unwrap()
+    );
+    assert_eq!(consensus_state.next_sync_committee, pubkey(1));
+    assert_eq!(consensus_state.current_block_hash, Some(filled(0xc1)));
+    assert_eq!(Any::from(consensus_state).encode_to_vec(), bz);
+}
+
+#[test]
+fn test_header_with_next_sync_committee() {
+    let (bz, any) = load("header_with_next_sync_committee");
+    let header = MinimalHeader::try_from(any).unwrap();
+    assert_eq!(
+        header.trusted_sync_committee.height,
+        Height::new(0, 100).unwrap()
+    );
+    assert!(!header.trusted_sync_committee.is_next);
+    assert_sync_committee(&header.trusted_sync_committee, 1);
+    assert!(header.intermediate_consensus_updates.is_empty());
+
+    let update = &header.consensus_update;
+    assert_consensus_update(update, 0x31);
+    let (next_sync_committee, next_sync_committee_branch) =
+        update.next_sync_committee.as_ref().unwrap();
+    for (i, pk) in next_sync_committee.pubkeys.iter().enumerate() {
+        assert_eq!(*pk, pubkey(33 + i as u8));
+    }
+    assert_eq!(*next_sync_committee_branch, branch(0x21, 5));
+
+    let execution_update = &header.execution_update;
+    assert_eq!(execution_update.state_root, filled(0x71));
+    assert_eq!(execution_update.state_root_branch, branch(0x81, 4));
+    assert_eq!(execution_update.block_number, U64(120));
+    assert_eq!(execution_update.block_number_branch, branch(0x91, 4));
+    // the vector predates the block hash proof
+    assert_eq!(execution_update.block_hash, None);
+    assert!(execution_update.block_hash_branch.is_empty());
+
+    assert_account_update(&header);
+    assert_eq!(
+        header.timestamp,
+        Timestamp::from_nanoseconds(1_700_000_000 * 1_000_000_000).unwrap()
+    );
+    assert_eq!(Any::from(header).encode_to_vec(), bz);
+}
+
+#[test]
+fn test_header_without_next_sync_committee() {
+    let (bz, any) = load("header_without_next_sync_committee");
+    let header = MinimalHeader::try_from(any).unwrap();
+    assert!(header.trusted_sync_committee.is_next);
+    assert_sync_committee(&header.trusted_sync_committee, 1);
+    assert_consensus_update(&header.consensus_update, 0x31);
+    assert!(header.consensus_update.next_sync_committee.is_none());
+    assert_account_update(&header);
+    assert_eq!(Any::from(header).encode_to_vec(), bz);
+}
+
+#[test]
+fn test_header_without_account_update() {
+    // the storage root of a header must always be proven, so a header without the account update is rejected
+    let (_, any) = load("header_without_account_update");
+    let err = MinimalHeader::try_from(any).unwrap_err();
+    assert!(
+        err.to_string().contains("`Header.account_update`"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn test_header_with_empty_next_sync_committee() {
+    // accepted difference: the empty next sync committee is decoded as absent and omitted on re-encoding
+    let (_, any) = load("header_empty_next_sync_committee");
+    let header = MinimalHeader::try_from(any).unwrap();
+    assert!(header.consensus_update.next_sync_committee.is_none());
+    let (without_next, without_next_any) = load("header_without_next_sync_committee");
+    assert_eq!(header, MinimalHeader::try_from(without_next_any).unwrap());
+    assert_eq!(Any::from(header).encode_to_vec(), without_next);
+}
+
+#[test]
+fn test_finalized_header_misbehaviour() {
+    let (bz, any) = load("finalized_header_misbehaviour");
+    let misbehaviour = MinimalMisbehaviour::try_from(any).unwrap();
+    assert_eq!(misbehaviour.client_id.as_str(), "ethereum-0");
+    for trusted in [
+        &misbehaviour.trusted_sync_committee_1,
+        &misbehaviour.trusted_sync_committee_2,
+    ] {
+        assert_eq!(trusted.height, Height::new(0, 100).unwrap());
+        assert!(!trusted.is_next);
+        assert_sync_committee(trusted, 1);
+    }
+    match &misbehaviour.data {
+        MisbehaviourData::FinalizedHeader(data) => {
+            assert_consensus_update(&data.consensus_update_1, 0x31);
+            assert_consensus_update(&data.consensus_update_2, 0x35);
+        }
+        data => panic!("unexpected misbehaviour: {:?}", data),
+    }
+    assert_eq!(Any::from(misbehaviour).encode_to_vec(), bz);
+}
+
+#[test]
+fn test_finalized_header_misbehaviour_with_deprecated_trusted_sync_committee() {
+    // accepted difference: the deprecated field is used for both updates and re-encoded into the per-update fields
+    let (_, any) = load("finalized_header_misbehaviour_deprecated_trusted_sync_committee");
+    let misbehaviour = MinimalMisbehaviour::try_from(any).unwrap();
+    let (current, current_any) = load("finalized_header_misbehaviour");
+    assert_eq!(
+        misbehaviour,
+        MinimalMisbehaviour::try_from(current_any).unwrap()
+    );
+    assert_eq!(Any::from(misbehaviour).encode_to_vec(), current);
+}
@@ -0,0 +1,22 @@
+//! Decodes the payload of a client state `Any` and checks that a decoded client state survives re-encoding
+#![no_main]
+use ethereum_ibc::{MainnetClientState, MinimalClientState, ETHEREUM_CLIENT_STATE_TYPE_URL};
+use ibc_proto::google::protobuf::Any;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let any = Any {
+        type_url: ETHEREUM_CLIENT_STATE_TYPE_URL.to_string(),
+        value: data.to_vec(),
+    };
+    if let Ok(client_state) = MinimalClientState::try_from(any.clone()) {
+        let decoded = MinimalClientState::try_from(Any::from(client_state.clone()))
+            .expect("a decoded client state must decode after re-encoding");
+        assert_eq!(decoded, client_state);
+    }
+    if let Ok(client_state) = MainnetClientState::try_from(any) {
+        let decoded = MainnetClientState::try_from(Any::from(client_state.clone()))
+            .expect("a decoded client state must decode after re-encoding");
+        assert_eq!(decoded, client_state);
+    }
+});
@@ -0,0 +1,18 @@
+//! Decodes the payload of a consensus state `Any` and checks that a decoded consensus state survives re-encoding
+#![no_main]
+use ethereum_ibc::consensus_state::ConsensusState;
+use ethereum_ibc::ETHEREUM_CONSENSUS_STATE_TYPE_URL;
+use ibc_proto::google::protobuf::Any;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let any = Any {
+        type_url: ETHEREUM_CONSENSUS_STATE_TYPE_URL.to_string(),
+        value: data.to_vec(),
+    };
+    if let Ok(consensus_state) = ConsensusState::try_from(any) {
+        let decoded = ConsensusState::try_from(Any::from(consensus_state.clone()))
+            .expect("a decoded consensus state must decode after re-encoding");
+        assert_eq!(decoded, consensus_state);
+    }
+});
@@ -0,0 +1,22 @@
+//! Decodes the payload of a header `Any` with the sync committee sizes of both presets
+#![no_main]
+use ethereum_ibc::{MainnetHeader, MinimalHeader, ETHEREUM_HEADER_TYPE_URL};
+use ibc_proto::google::protobuf::Any;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let any = Any {
+        type_url: ETHEREUM_HEADER_TYPE_URL.to_string(),
+        value: data.to_vec(),
+    };
+    if let Ok(header) = MinimalHeader::try_from(any.clone()) {
+        let decoded = MinimalHeader::try_from(Any::from(header.clone()))
+            .expect("a decoded header must decode after re-encoding");
+        assert_eq!(decoded, header);
+    }
+    if let Ok(header) = MainnetHeader::try_from(any) {
+        let decoded = MainnetHeader::try_from(Any::from(header.clone()))
+            .expect("a decoded header must decode after re-encoding");
+        assert_eq!(decoded, header);
+    }
+});
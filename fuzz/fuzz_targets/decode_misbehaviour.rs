@@ -0,0 +1,33 @@
+//! Decodes the payload of a misbehaviour `Any`, whose type URL is selected by the first byte of the input
+#![no_main]
+use ethereum_ibc::{
+    MainnetMisbehaviour, MinimalMisbehaviour, ETHEREUM_FINALIZED_HEADER_MISBEHAVIOUR_TYPE_URL,
+    ETHEREUM_NEXT_SYNC_COMMITTEE_MISBEHAVIOUR_TYPE_URL,
+};
+use ibc_proto::google::protobuf::Any;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Some((selector, value)) = data.split_first() else {
+        return;
+    };
+    let type_url = if selector % 2 == 0 {
+        ETHEREUM_FINALIZED_HEADER_MISBEHAVIOUR_TYPE_URL
+    } else {
+        ETHEREUM_NEXT_SYNC_COMMITTEE_MISBEHAVIOUR_TYPE_URL
+    };
+    let any = Any {
+        type_url: type_url.to_string(),
+        value: value.to_vec(),
+    };
+    if let Ok(misbehaviour) = MinimalMisbehaviour::try_from(any.clone()) {
+        let decoded = MinimalMisbehaviour::try_from(Any::from(misbehaviour.clone()))
+            .expect("a decoded misbehaviour must decode after re-encoding");
+        assert_eq!(decoded, misbehaviour);
+    }
+    if let Ok(misbehaviour) = MainnetMisbehaviour::try_from(any) {
+        let decoded = MainnetMisbehaviour::try_from(Any::from(misbehaviour.clone()))
+            .expect("a decoded misbehaviour must decode after re-encoding");
+        assert_eq!(decoded, misbehaviour);
+    }
+});
@@ -0,0 +1,11 @@
+//! Decodes an EIP-1186 proof, i.e. the RLP list of the trie nodes
+#![no_main]
+use ethereum_ibc::commitment::{decode_eip1186_rlp_proof, MAX_PROOF_NODES, MAX_PROOF_NODE_SIZE};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(nodes) = decode_eip1186_rlp_proof(data.to_vec()) {
+        assert!(nodes.len() <= MAX_PROOF_NODES);
+        assert!(nodes.iter().all(|node| node.len() <= MAX_PROOF_NODE_SIZE));
+    }
+});
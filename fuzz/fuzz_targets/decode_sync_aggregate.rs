@@ -0,0 +1,15 @@
+//! Decodes a proto-encoded sync aggregate with the sync committee sizes of both presets
+#![no_main]
+use ethereum_ibc::types::convert_proto_sync_aggregate;
+use ethereum_ibc::{MAINNET_SYNC_COMMITTEE_SIZE, MINIMAL_SYNC_COMMITTEE_SIZE};
+use ethereum_ibc_proto::ibc::lightclients::ethereum::v1::SyncAggregate;
+use libfuzzer_sys::fuzz_target;
+use prost::Message;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(sync_aggregate) = SyncAggregate::decode(data) else {
+        return;
+    };
+    let _ = convert_proto_sync_aggregate::<MINIMAL_SYNC_COMMITTEE_SIZE>(sync_aggregate.clone());
+    let _ = convert_proto_sync_aggregate::<MAINNET_SYNC_COMMITTEE_SIZE>(sync_aggregate);
+});
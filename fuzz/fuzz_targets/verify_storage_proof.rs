@@ -0,0 +1,37 @@
+//! Verifies the membership and the non-membership of a commitment with the input as the storage proof
+//!
+//! The storage root is the hash of the first node of the input if it decodes, so that the walk gets past the root node.
+#![no_main]
+use ethereum_ibc::commitment::{
+    decode_eip1186_rlp_proof, verify_membership, verify_non_membership, CommitmentValueCodec,
+};
+use ethereum_ibc::consensus::types::H256;
+use ibc::core::ics23_commitment::commitment::{CommitmentProofBytes, CommitmentRoot};
+use ibc::core::ics24_host::Path;
+use libfuzzer_sys::fuzz_target;
+use std::str::FromStr;
+use tiny_keccak::{Hasher, Keccak};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(proof) = CommitmentProofBytes::try_from(data.to_vec()) else {
+        return;
+    };
+    let root = match decode_eip1186_rlp_proof(data.to_vec()) {
+        Ok(nodes) if !nodes.is_empty() => {
+            let mut hasher = Keccak::v256();
+            hasher.update(&nodes[0]);
+            let mut output = [0u8; 32];
+            hasher.finalize(&mut output);
+            output
+        }
+        _ => [1u8; 32],
+    };
+    let root = CommitmentRoot::from_bytes(&root);
+    let slot = H256::default();
+    let path = Path::from_str("commitments/ports/transfer/channels/channel-0/sequences/1")
+        .expect("a valid path");
+    for codec in [CommitmentValueCodec::Keccak256, CommitmentValueCodec::Raw] {
+        let _ = verify_membership(&root, &slot, codec, &proof, path.clone(), &[1u8; 32]);
+    }
+    let _ = verify_non_membership(&root, &slot, &proof, path);
+});
@@ -1,5 +1,5 @@
 use argh::FromArgs;
-use std::fs::{create_dir_all, remove_dir_all};
+use std::fs::{self, create_dir_all, remove_dir_all};
 use std::path::{Path, PathBuf};
 use std::process;
 use walkdir::WalkDir;
@@ -80,25 +80,39 @@ impl CompileCmd {
         // List available paths for dependencies
         let includes: Vec<PathBuf> = proto_includes_paths.iter().map(PathBuf::from).collect();
         let attrs_serde = r#"#[derive(::serde::Serialize, ::serde::Deserialize)]"#;
+        // the types embedded in the messages with the proto3 JSON of pbjson get it in place of the derive
+        let attrs_serde_without_json = r#"#[cfg_attr(not(feature = "json"), derive(::serde::Serialize, ::serde::Deserialize))]"#;
         let attrs_jsonschema =
             r#"#[cfg_attr(feature = "json-schema", derive(::schemars::JsonSchema))]"#;
         let attrs_ord = "#[derive(Eq, PartialOrd, Ord)]";
-        let attrs_serde_default = r#"#[serde(default)]"#;
-        let compilation = tonic_build::configure()
+        let attrs_serde_default = r#"#[cfg_attr(not(feature = "json"), serde(default))]"#;
+        let mut config = tonic_build::configure()
             .build_client(true)
             .compile_well_known_types(true)
             .build_server(false)
             .out_dir(out_dir)
             .type_attribute(".google.protobuf.Any", attrs_serde)
-            .type_attribute(".google.protobuf.Timestamp", attrs_serde)
-            .type_attribute(".google.protobuf.Duration", attrs_serde)
-            .type_attribute(".ibc.core.client.v1", attrs_serde)
+            .type_attribute(".google.protobuf.Timestamp", attrs_serde_without_json)
+            .type_attribute(".google.protobuf.Duration", attrs_serde_without_json);
+        for message in [
+            "IdentifiedClientState",
+            "ConsensusStateWithHeight",
+            "ClientConsensusStates",
+            "ClientUpdateProposal",
+            "UpgradeProposal",
+            "Params",
+        ] {
+            config = config.type_attribute(format!(".ibc.core.client.v1.{}", message), attrs_serde);
+        }
+        let compilation = config
+            .type_attribute(".ibc.core.client.v1.Height", attrs_serde_without_json)
             .type_attribute(".ibc.core.client.v1.Height", attrs_ord)
             .type_attribute(".ibc.core.client.v1.Height", attrs_jsonschema)
             .field_attribute(".ibc.core.client.v1.Height", attrs_serde_default)
             .type_attribute(".cosmos.upgrade.v1beta1", attrs_serde)
             .file_descriptor_set_path(descriptor_path)
-            .compile(&protos, &includes);
+            .compile(&protos, &includes)
+            .and_then(|_| Self::compile_pbjson(out_dir, descriptor_path));
 
         match compilation {
             Ok(_) => {
@@ -110,4 +124,25 @@ impl CompileCmd {
             }
         }
     }
+
+    /// Generates the proto3 JSON (pbjson) serde impls of the ethereum messages and of
+    /// the `Height` embedded in them, which are enabled by the `json` feature of the proto crate
+    fn compile_pbjson(out_dir: &Path, descriptor_path: &Path) -> std::io::Result<()> {
+        let descriptor_set = fs::read(descriptor_path)?;
+        pbjson_build::Builder::new()
+            .register_descriptors(&descriptor_set)?
+            .out_dir(out_dir)
+            .build(&[
+                ".ibc.lightclients.ethereum.v1",
+                ".ibc.core.client.v1.Height",
+            ])?;
+
+        // the proto crate is no_std
+        for package in ["ibc.lightclients.ethereum.v1", "ibc.core.client.v1"] {
+            let path = out_dir.join(format!("{}.serde.rs", package));
+            let generated = fs::read_to_string(&path)?;
+            fs::write(&path, generated.replace("std::", "core::"))?;
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,170 @@
+//! Packing the ethereum light client messages into `google.protobuf.Any`
+
+use crate::google::protobuf::Any;
+use crate::ibc::lightclients::ethereum::v1::{
+    ClientState, ConsensusState, FinalizedHeaderMisbehaviour, Header, NextSyncCommitteeMisbehaviour,
+};
+use alloc::string::{String, ToString};
+use prost::Message;
+
+/// A message that is sent wrapped in `google.protobuf.Any`
+pub trait AnyMessage: Message + Default {
+    /// The type URL of the message in `Any`
+    const TYPE_URL: &'static str;
+
+    /// Wrap the message into `Any` with `TYPE_URL`
+    fn pack(&self) -> Any {
+        Any {
+            type_url: Self::TYPE_URL.to_string(),
+            value: self.encode_to_vec(),
+        }
+    }
+
+    /// Decode the message from `any`, which must have `TYPE_URL`
+    fn unpack(any: &Any) -> Result<Self, UnpackError> {
+        if any.type_url != Self::TYPE_URL {
+            return Err(UnpackError::UnexpectedTypeUrl {
+                expected: Self::TYPE_URL,
+                got: any.type_url.clone(),
+            });
+        }
+        Self::decode(any.value.as_slice()).map_err(UnpackError::Decode)
+    }
+}
+
+impl AnyMessage for ClientState {
+    const TYPE_URL: &'static str = "/ibc.lightclients.ethereum.v1.ClientState";
+}
+
+impl AnyMessage for ConsensusState {
+    const TYPE_URL: &'static str = "/ibc.lightclients.ethereum.v1.ConsensusState";
+}
+
+impl AnyMessage for Header {
+    const TYPE_URL: &'static str = "/ibc.lightclients.ethereum.v1.Header";
+}
+
+impl AnyMessage for FinalizedHeaderMisbehaviour {
+    const TYPE_URL: &'static str = "/ibc.lightclients.ethereum.v1.FinalizedHeaderMisbehaviour";
+}
+
+impl AnyMessage for NextSyncCommitteeMisbehaviour {
+    const TYPE_URL: &'static str = "/ibc.lightclients.ethereum.v1.NextSyncCommitteeMisbehaviour";
+}
+
+/// The error of `AnyMessage::unpack`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UnpackError {
+    /// `Any` has the type URL of another message
+    UnexpectedTypeUrl { expected: &'static str, got: String },
+    /// The value of `Any` cannot be decoded as the message
+    Decode(prost::DecodeError),
+}
+
+impl core::fmt::Display for UnpackError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::UnexpectedTypeUrl { expected, got } => {
+                write!(f, "unexpected type URL: expected={} got={}", expected, got)
+            }
+            Self::Decode(err) => write!(f, "failed to decode the value of Any: {}", err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnpackError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ibc::core::client::v1::Height;
+    use crate::ibc::lightclients::ethereum::v1::{ConsensusUpdate, TrustedSyncCommittee};
+    use alloc::vec;
+
+    fn assert_round_trip<T: AnyMessage + PartialEq + core::fmt::Debug>(message: T) {
+        let any = message.pack();
+        assert_eq!(any.type_url, T::TYPE_URL);
+        assert_eq!(any.value, message.encode_to_vec());
+        assert_eq!(T::unpack(&any).unwrap(), message);
+    }
+
+    #[test]
+    fn test_pack_unpack() {
+        let consensus_update = ConsensusUpdate {
+            signature_slot: 6465,
+            finalized_header_branch: vec![vec![1; 32]; 6],
+            ..Default::default()
+        };
+        let trusted_sync_committee = TrustedSyncCommittee {
+            trusted_height: Some(Height {
+                revision_number: 0,
+                revision_height: 6400,
+            }),
+            is_next: true,
+            ..Default::default()
+        };
+        assert_round_trip(ClientState {
+            seconds_per_slot: 6,
+            genesis_time: 1606824023,
+            ibc_address: vec![0xaa; 20],
+            ..Default::default()
+        });
+        assert_round_trip(ConsensusState {
+            slot: 6400,
+            storage_root: vec![2; 32],
+            ..Default::default()
+        });
+        assert_round_trip(Header {
+            trusted_sync_committee: Some(trusted_sync_committee.clone()),
+            consensus_update: Some(consensus_update.clone()),
+            ..Default::default()
+        });
+        assert_round_trip(FinalizedHeaderMisbehaviour {
+            client_id: "ethereum-0".into(),
+            trusted_sync_committee: Some(trusted_sync_committee.clone()),
+            consensus_update_1: Some(consensus_update.clone()),
+            consensus_update_2: Some(consensus_update.clone()),
+            trusted_sync_committee_1: None,
+            trusted_sync_committee_2: None,
+        });
+        assert_round_trip(NextSyncCommitteeMisbehaviour {
+            client_id: "ethereum-0".into(),
+            trusted_sync_committee: Some(trusted_sync_committee),
+            consensus_update_1: Some(consensus_update.clone()),
+            consensus_update_2: Some(consensus_update),
+            trusted_sync_committee_1: None,
+            trusted_sync_committee_2: None,
+        });
+        assert_round_trip(Header::default());
+    }
+
+    #[test]
+    fn test_unpack_unexpected_type_url() {
+        let any = ConsensusState::default().pack();
+        assert_eq!(
+            ClientState::unpack(&any),
+            Err(UnpackError::UnexpectedTypeUrl {
+                expected: ClientState::TYPE_URL,
+                got: ConsensusState::TYPE_URL.to_string(),
+            })
+        );
+        // the two misbehaviours have the same fields in part but different type URLs
+        let any = FinalizedHeaderMisbehaviour {
+            client_id: "ethereum-0".into(),
+            ..Default::default()
+        }
+        .pack();
+        assert!(matches!(
+            NextSyncCommitteeMisbehaviour::unpack(&any),
+            Err(UnpackError::UnexpectedTypeUrl { .. })
+        ));
+        assert!(FinalizedHeaderMisbehaviour::unpack(&any).is_ok());
+
+        let any = Any {
+            type_url: Header::TYPE_URL.to_string(),
+            value: vec![0xff],
+        };
+        assert!(matches!(Header::unpack(&any), Err(UnpackError::Decode(_))));
+    }
+}
@@ -233,4 +233,270 @@ pub mod protobuf {
             })
         }
     }
+
+    /// The proto3 JSON of `Duration` and `Timestamp`, which are the strings such as `"1.5s"`
+    /// and `"1970-01-01T00:00:00Z"`, for the messages with the pbjson impls
+    #[cfg(feature = "json")]
+    mod json {
+        use super::{Duration, Timestamp, NANOS_PER_SECOND};
+        use alloc::{format, string::String};
+        use core::fmt;
+        use serde::de::{self, Visitor};
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+        // the range of the seconds of `Duration`, i.e. about 10000 years
+        const MAX_DURATION_SECONDS: u64 = 315_576_000_000;
+        // the seconds of 0001-01-01T00:00:00Z and 9999-12-31T23:59:59Z
+        const MIN_TIMESTAMP_SECONDS: i64 = -62_135_596_800;
+        const MAX_TIMESTAMP_SECONDS: i64 = 253_402_300_799;
+        const SECONDS_PER_DAY: i64 = 86_400;
+
+        /// Formats the fractional seconds with 0, 3, 6 or 9 digits as the Go counterpart does
+        fn format_nanos(nanos: u32) -> String {
+            if nanos == 0 {
+                String::new()
+            } else if nanos % 1_000_000 == 0 {
+                format!(".{:03}", nanos / 1_000_000)
+            } else if nanos % 1_000 == 0 {
+                format!(".{:06}", nanos / 1_000)
+            } else {
+                format!(".{:09}", nanos)
+            }
+        }
+
+        /// Parses the digits after the decimal point of at most 9 digits into nanoseconds
+        fn parse_nanos(fraction: &str) -> Option<u32> {
+            if fraction.is_empty()
+                || fraction.len() > 9
+                || !fraction.bytes().all(|b| b.is_ascii_digit())
+            {
+                return None;
+            }
+            let nanos: u32 = fraction.parse().ok()?;
+            Some(nanos * 10u32.pow(9 - fraction.len() as u32))
+        }
+
+        fn parse_digits(s: &str) -> Option<u64> {
+            if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            s.parse().ok()
+        }
+
+        // http://howardhinnant.github.io/date_algorithms.html
+        fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+            let year = if month <= 2 { year - 1 } else { year };
+            let era = year.div_euclid(400);
+            let yoe = year - era * 400;
+            let doy = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+            let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+            era * 146_097 + doe - 719_468
+        }
+
+        fn civil_from_days(days: i64) -> (i64, i64, i64) {
+            let days = days + 719_468;
+            let era = days.div_euclid(146_097);
+            let doe = days - era * 146_097;
+            let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+            let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+            let mp = (5 * doy + 2) / 153;
+            let day = doy - (153 * mp + 2) / 5 + 1;
+            let month = if mp < 10 { mp + 3 } else { mp - 9 };
+            let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+            (year, month, day)
+        }
+
+        fn days_in_month(year: i64, month: i64) -> i64 {
+            match month {
+                2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+                2 => 28,
+                4 | 6 | 9 | 11 => 30,
+                _ => 31,
+            }
+        }
+
+        impl Serialize for Duration {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let mut duration = self.clone();
+                duration.normalize();
+                if duration.seconds.unsigned_abs() > MAX_DURATION_SECONDS {
+                    return Err(serde::ser::Error::custom(format!(
+                        "duration out of range: {:?}",
+                        self
+                    )));
+                }
+                let sign = if duration.seconds < 0 || duration.nanos < 0 {
+                    "-"
+                } else {
+                    ""
+                };
+                serializer.serialize_str(&format!(
+                    "{}{}{}s",
+                    sign,
+                    duration.seconds.unsigned_abs(),
+                    format_nanos(duration.nanos.unsigned_abs())
+                ))
+            }
+        }
+
+        impl<'de> Deserialize<'de> for Duration {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                struct DurationVisitor;
+
+                impl<'de> Visitor<'de> for DurationVisitor {
+                    type Value = Duration;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                        formatter.write_str("a duration string such as \"1.5s\"")
+                    }
+
+                    fn visit_str<E: de::Error>(self, value: &str) -> Result<Duration, E> {
+                        let invalid = || E::invalid_value(de::Unexpected::Str(value), &self);
+                        let s = value.strip_suffix('s').ok_or_else(invalid)?;
+                        let (negative, s) = match s.strip_prefix('-') {
+                            Some(s) => (true, s),
+                            None => (false, s),
+                        };
+                        let (seconds, nanos) = match s.split_once('.') {
+                            Some((seconds, fraction)) => (seconds, parse_nanos(fraction)),
+                            None => (s, Some(0)),
+                        };
+                        let seconds = parse_digits(seconds)
+                            .filter(|seconds| *seconds <= MAX_DURATION_SECONDS)
+                            .ok_or_else(invalid)?;
+                        let (seconds, nanos) = (seconds as i64, nanos.ok_or_else(invalid)? as i32);
+                        Ok(if negative {
+                            Duration {
+                                seconds: -seconds,
+                                nanos: -nanos,
+                            }
+                        } else {
+                            Duration { seconds, nanos }
+                        })
+                    }
+                }
+
+                deserializer.deserialize_str(DurationVisitor)
+            }
+        }
+
+        impl Serialize for Timestamp {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                if !(MIN_TIMESTAMP_SECONDS..=MAX_TIMESTAMP_SECONDS).contains(&self.seconds)
+                    || !(0..NANOS_PER_SECOND).contains(&self.nanos)
+                {
+                    return Err(serde::ser::Error::custom(format!(
+                        "timestamp out of range: {:?}",
+                        self
+                    )));
+                }
+                let days = self.seconds.div_euclid(SECONDS_PER_DAY);
+                let seconds_of_day = self.seconds.rem_euclid(SECONDS_PER_DAY);
+                let (year, month, day) = civil_from_days(days);
+                serializer.serialize_str(&format!(
+                    "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}{}Z",
+                    year,
+                    month,
+                    day,
+                    seconds_of_day / 3600,
+                    seconds_of_day / 60 % 60,
+                    seconds_of_day % 60,
+                    format_nanos(self.nanos as u32)
+                ))
+            }
+        }
+
+        impl<'de> Deserialize<'de> for Timestamp {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                struct TimestampVisitor;
+
+                impl<'de> Visitor<'de> for TimestampVisitor {
+                    type Value = Timestamp;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                        formatter
+                            .write_str("an RFC 3339 timestamp such as \"1970-01-01T00:00:00Z\"")
+                    }
+
+                    fn visit_str<E: de::Error>(self, value: &str) -> Result<Timestamp, E> {
+                        parse_timestamp(value)
+                            .ok_or_else(|| E::invalid_value(de::Unexpected::Str(value), &self))
+                    }
+                }
+
+                deserializer.deserialize_str(TimestampVisitor)
+            }
+        }
+
+        /// Parses `YYYY-MM-DDTHH:MM:SS[.fraction](Z|+HH:MM|-HH:MM)`
+        fn parse_timestamp(value: &str) -> Option<Timestamp> {
+            let field = |range: core::ops::Range<usize>| -> Option<i64> {
+                parse_digits(value.get(range)?).map(|v| v as i64)
+            };
+            let bytes = value.as_bytes();
+            if bytes.len() < 20
+                || bytes[4] != b'-'
+                || bytes[7] != b'-'
+                || !matches!(bytes[10], b'T' | b't')
+                || bytes[13] != b':'
+                || bytes[16] != b':'
+            {
+                return None;
+            }
+            let (year, month, day) = (field(0..4)?, field(5..7)?, field(8..10)?);
+            let (hour, minute, second) = (field(11..13)?, field(14..16)?, field(17..19)?);
+            if year == 0
+                || !(1..=12).contains(&month)
+                || !(1..=days_in_month(year, month)).contains(&day)
+                || hour > 23
+                || minute > 59
+                || second > 59
+            {
+                return None;
+            }
+
+            let rest = &value[19..];
+            let (nanos, offset) = match rest.strip_prefix('.') {
+                Some(rest) => {
+                    let end = rest
+                        .find(|c: char| !c.is_ascii_digit())
+                        .unwrap_or(rest.len());
+                    (parse_nanos(&rest[..end])?, &rest[end..])
+                }
+                None => (0, rest),
+            };
+            let offset_seconds = match offset {
+                "Z" | "z" => 0,
+                _ => {
+                    let offset_bytes = offset.as_bytes();
+                    if offset_bytes.len() != 6 || offset_bytes[3] != b':' {
+                        return None;
+                    }
+                    let hours = parse_digits(offset.get(1..3)?)? as i64;
+                    let minutes = parse_digits(offset.get(4..6)?)? as i64;
+                    if hours > 23 || minutes > 59 {
+                        return None;
+                    }
+                    match offset_bytes[0] {
+                        b'+' => hours * 3600 + minutes * 60,
+                        b'-' => -(hours * 3600 + minutes * 60),
+                        _ => return None,
+                    }
+                }
+            };
+
+            let seconds = days_from_civil(year, month, day) * SECONDS_PER_DAY
+                + hour * 3600
+                + minute * 60
+                + second
+                - offset_seconds;
+            if !(MIN_TIMESTAMP_SECONDS..=MAX_TIMESTAMP_SECONDS).contains(&seconds) {
+                return None;
+            }
+            Some(Timestamp {
+                seconds,
+                nanos: nanos as i32,
+            })
+        }
+    }
 }
@@ -10,6 +10,7 @@
 #![allow(rustdoc::bare_urls)]
 #![forbid(unsafe_code)]
 
+pub mod any;
 pub mod google;
 
 extern crate alloc;
@@ -37,6 +38,10 @@ pub mod ibc {
         pub mod client {
             pub mod v1 {
                 include_proto!("ibc.core.client.v1.rs");
+                #[cfg(feature = "json")]
+                use alloc::string::ToString;
+                #[cfg(feature = "json")]
+                include_proto!("ibc.core.client.v1.serde.rs");
             }
         }
     }
@@ -44,7 +49,244 @@ pub mod ibc {
         pub mod ethereum {
             pub mod v1 {
                 include_proto!("ibc.lightclients.ethereum.v1.rs");
+                #[cfg(feature = "json")]
+                use alloc::{format, string::ToString, vec::Vec};
+                #[cfg(feature = "json")]
+                include_proto!("ibc.lightclients.ethereum.v1.serde.rs");
             }
         }
+        pub mod wasm {
+            pub mod v1 {
+                include_proto!("ibc.lightclients.wasm.v1.rs");
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "json"))]
+mod tests {
+    use super::google::protobuf::{Duration, Timestamp};
+    use super::ibc::core::client::v1::Height;
+    use super::ibc::lightclients::ethereum::v1::*;
+    use serde::{de::DeserializeOwned, Serialize};
+    use serde_json::Value;
+
+    // the proto3 JSON of the messages in the form that `protojson.Marshal` of the Go counterpart emits
+    const PROTOJSON: &str = include_str!("../testdata/protojson.json");
+
+    fn fixture<T: DeserializeOwned>(name: &str) -> (T, Value) {
+        let value: Value = serde_json::from_str::<Value>(PROTOJSON).unwrap()[name].clone();
+        (serde_json::from_value(value.clone()).unwrap(), value)
+    }
+
+    fn assert_round_trip<T: Serialize + DeserializeOwned + PartialEq + core::fmt::Debug>(
+        message: T,
+    ) {
+        let json = serde_json::to_string(&message).unwrap();
+        assert_eq!(
+            message,
+            serde_json::from_str::<T>(&json).unwrap(),
+            "{}",
+            json
+        );
+    }
+
+    #[test]
+    fn test_go_protojson_fixture() {
+        let (client_state, json): (ClientState, _) = fixture("clientState");
+        assert_eq!(serde_json::to_value(&client_state).unwrap(), json);
+        assert_eq!(client_state.genesis_time, 1606824023);
+        assert_eq!(client_state.ibc_address.len(), 20);
+        assert_eq!(
+            client_state.trusting_period,
+            Some(Duration {
+                seconds: 1209600,
+                nanos: 0
+            })
+        );
+        assert_eq!(
+            client_state.max_clock_drift,
+            Some(Duration {
+                seconds: 10,
+                nanos: 500_000_000
+            })
+        );
+        assert_eq!(
+            client_state.frozen_height,
+            Some(Height {
+                revision_number: 0,
+                revision_height: 6400
+            })
+        );
+        assert_eq!(
+            client_state.commitment_value_codec,
+            CommitmentValueCodec::Raw as i32
+        );
+
+        let (consensus_state, json): (ConsensusState, _) = fixture("consensusState");
+        assert_eq!(serde_json::to_value(&consensus_state).unwrap(), json);
+        assert_eq!(
+            consensus_state.timestamp,
+            Some(Timestamp {
+                seconds: 1606900823,
+                nanos: 0
+            })
+        );
+        assert_eq!(consensus_state.current_sync_committee, [0xa1; 48]);
+
+        let (header, json): (Header, _) = fixture("header");
+        assert_eq!(serde_json::to_value(&header).unwrap(), json);
+        let consensus_update = header.consensus_update.unwrap();
+        assert_eq!(consensus_update.signature_slot, 6465);
+        assert_eq!(consensus_update.next_sync_committee_branch.len(), 2);
+        assert_eq!(
+            consensus_update.sync_aggregate.unwrap().sync_committee_bits,
+            [0xff]
+        );
+
+        let (misbehaviour, json): (FinalizedHeaderMisbehaviour, _) =
+            fixture("finalizedHeaderMisbehaviour");
+        assert_eq!(serde_json::to_value(&misbehaviour).unwrap(), json);
+        assert_eq!(misbehaviour.client_id, "ethereum-0");
+        assert!(misbehaviour.trusted_sync_committee.is_none());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let (client_state, _): (ClientState, _) = fixture("clientState");
+        let (consensus_state, _): (ConsensusState, _) = fixture("consensusState");
+        let (header, _): (Header, _) = fixture("header");
+        let (finalized_header_misbehaviour, _): (FinalizedHeaderMisbehaviour, _) =
+            fixture("finalizedHeaderMisbehaviour");
+        let fork_parameters = client_state.fork_parameters.clone().unwrap();
+        let fork = fork_parameters.forks[1].clone();
+        let trusted_sync_committee = header.trusted_sync_committee.clone().unwrap();
+        let consensus_update = header.consensus_update.clone().unwrap();
+
+        assert_round_trip(client_state.clone());
+        assert_round_trip(consensus_state);
+        assert_round_trip(Header {
+            intermediate_consensus_updates: vec![consensus_update.clone()],
+            ..header.clone()
+        });
+        assert_round_trip(trusted_sync_committee.clone());
+        assert_round_trip(fork_parameters);
+        assert_round_trip(client_state.trust_level.clone().unwrap());
+        assert_round_trip(fork.spec.clone().unwrap());
+        assert_round_trip(fork);
+        assert_round_trip(consensus_update.next_sync_committee.clone().unwrap());
+        assert_round_trip(consensus_update.sync_aggregate.clone().unwrap());
+        assert_round_trip(consensus_update.attested_header.clone().unwrap());
+        assert_round_trip(consensus_update.clone());
+        assert_round_trip(header.execution_update.clone().unwrap());
+        assert_round_trip(header.account_update.clone().unwrap());
+        assert_round_trip(finalized_header_misbehaviour.clone());
+        assert_round_trip(NextSyncCommitteeMisbehaviour {
+            client_id: finalized_header_misbehaviour.client_id,
+            trusted_sync_committee: Some(trusted_sync_committee),
+            consensus_update_1: Some(consensus_update.clone()),
+            consensus_update_2: Some(consensus_update),
+            trusted_sync_committee_1: None,
+            trusted_sync_committee_2: None,
+        });
+        assert_round_trip(client_state.frozen_height.unwrap());
+        assert_round_trip(CommitmentValueCodec::Keccak256);
+        assert_round_trip(CommitmentValueCodec::Raw);
+
+        // the default values are omitted
+        assert_eq!(
+            serde_json::to_string(&ClientState::default()).unwrap(),
+            "{}"
+        );
+        assert_eq!(serde_json::to_string(&Height::default()).unwrap(), "{}");
+    }
+
+    #[test]
+    fn test_proto_field_names() {
+        let height: Height =
+            serde_json::from_str(r#"{"revision_number":"1","revision_height":2}"#).unwrap();
+        assert_eq!(
+            height,
+            Height {
+                revision_number: 1,
+                revision_height: 2
+            }
+        );
+        assert_eq!(
+            serde_json::to_string(&height).unwrap(),
+            r#"{"revisionNumber":"1","revisionHeight":"2"}"#
+        );
+
+        let account_update: AccountUpdate =
+            serde_json::from_str(r#"{"account_storage_root":"AQI="}"#).unwrap();
+        assert_eq!(account_update.account_storage_root, [1, 2]);
+        assert!(serde_json::from_str::<AccountUpdate>(r#"{"unknown":"AQI="}"#).is_err());
+        assert!(serde_json::from_str::<ClientState>(
+            r#"{"commitmentValueCodec":"COMMITMENT_VALUE_CODEC_UNKNOWN"}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_well_known_types() {
+        for (duration, json) in [
+            ((0, 0), r#""0s""#),
+            ((3, 1), r#""3.000000001s""#),
+            ((3, 1_000), r#""3.000001s""#),
+            ((-1, -500_000_000), r#""-1.500s""#),
+            ((0, -1_000_000), r#""-0.001s""#),
+        ] {
+            let duration = Duration {
+                seconds: duration.0,
+                nanos: duration.1,
+            };
+            assert_eq!(serde_json::to_string(&duration).unwrap(), json);
+            assert_eq!(serde_json::from_str::<Duration>(json).unwrap(), duration);
+        }
+        assert_eq!(
+            serde_json::from_str::<Duration>(r#""1.5s""#).unwrap(),
+            Duration {
+                seconds: 1,
+                nanos: 500_000_000
+            }
+        );
+        for json in [r#""1""#, r#""1.s""#, r#""s""#, r#""1.0000000001s""#, "1"] {
+            assert!(serde_json::from_str::<Duration>(json).is_err(), "{}", json);
+        }
+
+        for (timestamp, json) in [
+            ((0, 0), r#""1970-01-01T00:00:00Z""#),
+            ((1606824023, 0), r#""2020-12-01T12:00:23Z""#),
+            ((951782400, 10_000_000), r#""2000-02-29T00:00:00.010Z""#),
+            ((-1, 999_999_999), r#""1969-12-31T23:59:59.999999999Z""#),
+            ((253402300799, 0), r#""9999-12-31T23:59:59Z""#),
+        ] {
+            let timestamp = Timestamp {
+                seconds: timestamp.0,
+                nanos: timestamp.1,
+            };
+            assert_eq!(serde_json::to_string(&timestamp).unwrap(), json);
+            assert_eq!(serde_json::from_str::<Timestamp>(json).unwrap(), timestamp);
+        }
+        assert_eq!(
+            serde_json::from_str::<Timestamp>(r#""2020-12-01T21:00:23.5+09:00""#).unwrap(),
+            Timestamp {
+                seconds: 1606824023,
+                nanos: 500_000_000
+            }
+        );
+        for json in [
+            r#""2020-12-01T12:00:23""#,
+            r#""2020-02-30T00:00:00Z""#,
+            r#""2020-12-01 12:00:23Z""#,
+            r#""2020-12-01T24:00:00Z""#,
+        ] {
+            assert!(serde_json::from_str::<Timestamp>(json).is_err(), "{}", json);
+        }
+        assert!(serde_json::to_string(&Timestamp {
+            seconds: 0,
+            nanos: -1
+        })
+        .is_err());
     }
 }
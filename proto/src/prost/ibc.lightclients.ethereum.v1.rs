@@ -35,6 +35,18 @@ pub struct ClientState {
     pub frozen_height: ::core::option::Option<
         super::super::super::core::client::v1::Height,
     >,
+    /// the maximum number of intermediate consensus updates in a header (0 disables them)
+    #[prost(uint64, tag = "15")]
+    pub max_intermediate_consensus_updates: u64,
+    /// how the storage value proven for a path is compared with the expected commitment value
+    #[prost(enumeration = "CommitmentValueCodec", tag = "16")]
+    pub commitment_value_codec: i32,
+    /// keccak256 digest of the evidence of the misbehaviour that froze the client (empty if the client is not frozen by a misbehaviour)
+    #[prost(bytes = "vec", tag = "17")]
+    pub misbehaviour_evidence_digest: ::prost::alloc::vec::Vec<u8>,
+    /// the commitment prefix that the counterparty must use for the client (empty means `ibc`, the prefix of ibc-solidity)
+    #[prost(bytes = "vec", tag = "18")]
+    pub commitment_prefix: ::prost::alloc::vec::Vec<u8>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -51,6 +63,9 @@ pub struct ConsensusState {
     pub current_sync_committee: ::prost::alloc::vec::Vec<u8>,
     #[prost(bytes = "vec", tag = "5")]
     pub next_sync_committee: ::prost::alloc::vec::Vec<u8>,
+    /// hash of the finalized execution block (optional)
+    #[prost(bytes = "vec", tag = "6")]
+    pub current_block_hash: ::prost::alloc::vec::Vec<u8>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -61,11 +76,15 @@ pub struct Header {
     pub consensus_update: ::core::option::Option<ConsensusUpdate>,
     #[prost(message, optional, tag = "3")]
     pub execution_update: ::core::option::Option<ExecutionUpdate>,
+    /// required, since the storage root of every consensus state must be proven against its state root
     #[prost(message, optional, tag = "4")]
     pub account_update: ::core::option::Option<AccountUpdate>,
     /// seconds from unix epoch
     #[prost(uint64, tag = "5")]
     pub timestamp: u64,
+    /// consensus updates applied in order before `consensus_update`
+    #[prost(message, repeated, tag = "6")]
+    pub intermediate_consensus_updates: ::prost::alloc::vec::Vec<ConsensusUpdate>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -174,6 +193,12 @@ pub struct ExecutionUpdate {
     pub block_number: u64,
     #[prost(bytes = "vec", repeated, tag = "4")]
     pub block_number_branch: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    /// hash of the execution block (optional)
+    /// if empty, the block hash is not proven and `block_hash_branch` must be empty
+    #[prost(bytes = "vec", tag = "5")]
+    pub block_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", repeated, tag = "6")]
+    pub block_hash_branch: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -202,22 +227,64 @@ pub struct BeaconBlockHeader {
 pub struct FinalizedHeaderMisbehaviour {
     #[prost(string, tag = "1")]
     pub client_id: ::prost::alloc::string::String,
+    /// Deprecated: the sync committee used to verify both updates. Set `trusted_sync_committee_1` and `trusted_sync_committee_2` instead.
     #[prost(message, optional, tag = "2")]
     pub trusted_sync_committee: ::core::option::Option<TrustedSyncCommittee>,
     #[prost(message, optional, tag = "3")]
     pub consensus_update_1: ::core::option::Option<ConsensusUpdate>,
     #[prost(message, optional, tag = "4")]
     pub consensus_update_2: ::core::option::Option<ConsensusUpdate>,
+    /// the sync committee used to verify `consensus_update_1`
+    #[prost(message, optional, tag = "5")]
+    pub trusted_sync_committee_1: ::core::option::Option<TrustedSyncCommittee>,
+    /// the sync committee used to verify `consensus_update_2`
+    #[prost(message, optional, tag = "6")]
+    pub trusted_sync_committee_2: ::core::option::Option<TrustedSyncCommittee>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct NextSyncCommitteeMisbehaviour {
     #[prost(string, tag = "1")]
     pub client_id: ::prost::alloc::string::String,
+    /// Deprecated: the sync committee used to verify both updates. Set `trusted_sync_committee_1` and `trusted_sync_committee_2` instead.
     #[prost(message, optional, tag = "2")]
     pub trusted_sync_committee: ::core::option::Option<TrustedSyncCommittee>,
     #[prost(message, optional, tag = "3")]
     pub consensus_update_1: ::core::option::Option<ConsensusUpdate>,
     #[prost(message, optional, tag = "4")]
     pub consensus_update_2: ::core::option::Option<ConsensusUpdate>,
+    /// the sync committee used to verify `consensus_update_1`
+    #[prost(message, optional, tag = "5")]
+    pub trusted_sync_committee_1: ::core::option::Option<TrustedSyncCommittee>,
+    /// the sync committee used to verify `consensus_update_2`
+    #[prost(message, optional, tag = "6")]
+    pub trusted_sync_committee_2: ::core::option::Option<TrustedSyncCommittee>,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum CommitmentValueCodec {
+    /// the storage value is `keccak256(value)`
+    Keccak256 = 0,
+    /// the storage value is `value` itself as a 32-byte word
+    Raw = 1,
+}
+impl CommitmentValueCodec {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            CommitmentValueCodec::Keccak256 => "COMMITMENT_VALUE_CODEC_KECCAK256",
+            CommitmentValueCodec::Raw => "COMMITMENT_VALUE_CODEC_RAW",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "COMMITMENT_VALUE_CODEC_KECCAK256" => Some(Self::Keccak256),
+            "COMMITMENT_VALUE_CODEC_RAW" => Some(Self::Raw),
+            _ => None,
+        }
+    }
 }
@@ -0,0 +1,2907 @@
+impl serde::Serialize for AccountUpdate {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.account_proof.is_empty() {
+            len += 1;
+        }
+        if !self.account_storage_root.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("ibc.lightclients.ethereum.v1.AccountUpdate", len)?;
+        if !self.account_proof.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("accountProof", pbjson::private::base64::encode(&self.account_proof).as_str())?;
+        }
+        if !self.account_storage_root.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("accountStorageRoot", pbjson::private::base64::encode(&self.account_storage_root).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for AccountUpdate {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "account_proof",
+            "accountProof",
+            "account_storage_root",
+            "accountStorageRoot",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            AccountProof,
+            AccountStorageRoot,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> core::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> core::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "accountProof" | "account_proof" => Ok(GeneratedField::AccountProof),
+                            "accountStorageRoot" | "account_storage_root" => Ok(GeneratedField::AccountStorageRoot),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = AccountUpdate;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                formatter.write_str("struct ibc.lightclients.ethereum.v1.AccountUpdate")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> core::result::Result<AccountUpdate, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut account_proof__ = None;
+                let mut account_storage_root__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::AccountProof => {
+                            if account_proof__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("accountProof"));
+                            }
+                            account_proof__ =
+                                Some(map.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::AccountStorageRoot => {
+                            if account_storage_root__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("accountStorageRoot"));
+                            }
+                            account_storage_root__ =
+                                Some(map.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                    }
+                }
+                Ok(AccountUpdate {
+                    account_proof: account_proof__.unwrap_or_default(),
+                    account_storage_root: account_storage_root__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("ibc.lightclients.ethereum.v1.AccountUpdate", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for BeaconBlockHeader {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.slot != 0 {
+            len += 1;
+        }
+        if self.proposer_index != 0 {
+            len += 1;
+        }
+        if !self.parent_root.is_empty() {
+            len += 1;
+        }
+        if !self.state_root.is_empty() {
+            len += 1;
+        }
+        if !self.body_root.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("ibc.lightclients.ethereum.v1.BeaconBlockHeader", len)?;
+        if self.slot != 0 {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("slot", ToString::to_string(&self.slot).as_str())?;
+        }
+        if self.proposer_index != 0 {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("proposerIndex", ToString::to_string(&self.proposer_index).as_str())?;
+        }
+        if !self.parent_root.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("parentRoot", pbjson::private::base64::encode(&self.parent_root).as_str())?;
+        }
+        if !self.state_root.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("stateRoot", pbjson::private::base64::encode(&self.state_root).as_str())?;
+        }
+        if !self.body_root.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("bodyRoot", pbjson::private::base64::encode(&self.body_root).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for BeaconBlockHeader {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "slot",
+            "proposer_index",
+            "proposerIndex",
+            "parent_root",
+            "parentRoot",
+            "state_root",
+            "stateRoot",
+            "body_root",
+            "bodyRoot",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Slot,
+            ProposerIndex,
+            ParentRoot,
+            StateRoot,
+            BodyRoot,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> core::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> core::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "slot" => Ok(GeneratedField::Slot),
+                            "proposerIndex" | "proposer_index" => Ok(GeneratedField::ProposerIndex),
+                            "parentRoot" | "parent_root" => Ok(GeneratedField::ParentRoot),
+                            "stateRoot" | "state_root" => Ok(GeneratedField::StateRoot),
+                            "bodyRoot" | "body_root" => Ok(GeneratedField::BodyRoot),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = BeaconBlockHeader;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                formatter.write_str("struct ibc.lightclients.ethereum.v1.BeaconBlockHeader")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> core::result::Result<BeaconBlockHeader, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut slot__ = None;
+                let mut proposer_index__ = None;
+                let mut parent_root__ = None;
+                let mut state_root__ = None;
+                let mut body_root__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Slot => {
+                            if slot__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("slot"));
+                            }
+                            slot__ =
+                                Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::ProposerIndex => {
+                            if proposer_index__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("proposerIndex"));
+                            }
+                            proposer_index__ =
+                                Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::ParentRoot => {
+                            if parent_root__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("parentRoot"));
+                            }
+                            parent_root__ =
+                                Some(map.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::StateRoot => {
+                            if state_root__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("stateRoot"));
+                            }
+                            state_root__ =
+                                Some(map.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::BodyRoot => {
+                            if body_root__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("bodyRoot"));
+                            }
+                            body_root__ =
+                                Some(map.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                    }
+                }
+                Ok(BeaconBlockHeader {
+                    slot: slot__.unwrap_or_default(),
+                    proposer_index: proposer_index__.unwrap_or_default(),
+                    parent_root: parent_root__.unwrap_or_default(),
+                    state_root: state_root__.unwrap_or_default(),
+                    body_root: body_root__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("ibc.lightclients.ethereum.v1.BeaconBlockHeader", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for ClientState {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.genesis_validators_root.is_empty() {
+            len += 1;
+        }
+        if self.min_sync_committee_participants != 0 {
+            len += 1;
+        }
+        if self.genesis_time != 0 {
+            len += 1;
+        }
+        if self.fork_parameters.is_some() {
+            len += 1;
+        }
+        if self.seconds_per_slot != 0 {
+            len += 1;
+        }
+        if self.slots_per_epoch != 0 {
+            len += 1;
+        }
+        if self.epochs_per_sync_committee_period != 0 {
+            len += 1;
+        }
+        if !self.ibc_address.is_empty() {
+            len += 1;
+        }
+        if !self.ibc_commitments_slot.is_empty() {
+            len += 1;
+        }
+        if self.trust_level.is_some() {
+            len += 1;
+        }
+        if self.trusting_period.is_some() {
+            len += 1;
+        }
+        if self.max_clock_drift.is_some() {
+            len += 1;
+        }
+        if self.latest_execution_block_number != 0 {
+            len += 1;
+        }
+        if self.frozen_height.is_some() {
+            len += 1;
+        }
+        if self.max_intermediate_consensus_updates != 0 {
+            len += 1;
+        }
+        if self.commitment_value_codec != 0 {
+            len += 1;
+        }
+        if !self.misbehaviour_evidence_digest.is_empty() {
+            len += 1;
+        }
+        if !self.commitment_prefix.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("ibc.lightclients.ethereum.v1.ClientState", len)?;
+        if !self.genesis_validators_root.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("genesisValidatorsRoot", pbjson::private::base64::encode(&self.genesis_validators_root).as_str())?;
+        }
+        if self.min_sync_committee_participants != 0 {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("minSyncCommitteeParticipants", ToString::to_string(&self.min_sync_committee_participants).as_str())?;
+        }
+        if self.genesis_time != 0 {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("genesisTime", ToString::to_string(&self.genesis_time).as_str())?;
+        }
+        if let Some(v) = self.fork_parameters.as_ref() {
+            struct_ser.serialize_field("forkParameters", v)?;
+        }
+        if self.seconds_per_slot != 0 {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("secondsPerSlot", ToString::to_string(&self.seconds_per_slot).as_str())?;
+        }
+        if self.slots_per_epoch != 0 {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("slotsPerEpoch", ToString::to_string(&self.slots_per_epoch).as_str())?;
+        }
+        if self.epochs_per_sync_committee_period != 0 {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("epochsPerSyncCommitteePeriod", ToString::to_string(&self.epochs_per_sync_committee_period).as_str())?;
+        }
+        if !self.ibc_address.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("ibcAddress", pbjson::private::base64::encode(&self.ibc_address).as_str())?;
+        }
+        if !self.ibc_commitments_slot.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("ibcCommitmentsSlot", pbjson::private::base64::encode(&self.ibc_commitments_slot).as_str())?;
+        }
+        if let Some(v) = self.trust_level.as_ref() {
+            struct_ser.serialize_field("trustLevel", v)?;
+        }
+        if let Some(v) = self.trusting_period.as_ref() {
+            struct_ser.serialize_field("trustingPeriod", v)?;
+        }
+        if let Some(v) = self.max_clock_drift.as_ref() {
+            struct_ser.serialize_field("maxClockDrift", v)?;
+        }
+        if self.latest_execution_block_number != 0 {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("latestExecutionBlockNumber", ToString::to_string(&self.latest_execution_block_number).as_str())?;
+        }
+        if let Some(v) = self.frozen_height.as_ref() {
+            struct_ser.serialize_field("frozenHeight", v)?;
+        }
+        if self.max_intermediate_consensus_updates != 0 {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("maxIntermediateConsensusUpdates", ToString::to_string(&self.max_intermediate_consensus_updates).as_str())?;
+        }
+        if self.commitment_value_codec != 0 {
+            let v = CommitmentValueCodec::from_i32(self.commitment_value_codec)
+                .ok_or_else(|| serde::ser::Error::custom(format!("Invalid variant {}", self.commitment_value_codec)))?;
+            struct_ser.serialize_field("commitmentValueCodec", &v)?;
+        }
+        if !self.misbehaviour_evidence_digest.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("misbehaviourEvidenceDigest", pbjson::private::base64::encode(&self.misbehaviour_evidence_digest).as_str())?;
+        }
+        if !self.commitment_prefix.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("commitmentPrefix", pbjson::private::base64::encode(&self.commitment_prefix).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for ClientState {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "genesis_validators_root",
+            "genesisValidatorsRoot",
+            "min_sync_committee_participants",
+            "minSyncCommitteeParticipants",
+            "genesis_time",
+            "genesisTime",
+            "fork_parameters",
+            "forkParameters",
+            "seconds_per_slot",
+            "secondsPerSlot",
+            "slots_per_epoch",
+            "slotsPerEpoch",
+            "epochs_per_sync_committee_period",
+            "epochsPerSyncCommitteePeriod",
+            "ibc_address",
+            "ibcAddress",
+            "ibc_commitments_slot",
+            "ibcCommitmentsSlot",
+            "trust_level",
+            "trustLevel",
+            "trusting_period",
+            "trustingPeriod",
+            "max_clock_drift",
+            "maxClockDrift",
+            "latest_execution_block_number",
+            "latestExecutionBlockNumber",
+            "frozen_height",
+            "frozenHeight",
+            "max_intermediate_consensus_updates",
+            "maxIntermediateConsensusUpdates",
+            "commitment_value_codec",
+            "commitmentValueCodec",
+            "misbehaviour_evidence_digest",
+            "misbehaviourEvidenceDigest",
+            "commitment_prefix",
+            "commitmentPrefix",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            GenesisValidatorsRoot,
+            MinSyncCommitteeParticipants,
+            GenesisTime,
+            ForkParameters,
+            SecondsPerSlot,
+            SlotsPerEpoch,
+            EpochsPerSyncCommitteePeriod,
+            IbcAddress,
+            IbcCommitmentsSlot,
+            TrustLevel,
+            TrustingPeriod,
+            MaxClockDrift,
+            LatestExecutionBlockNumber,
+            FrozenHeight,
+            MaxIntermediateConsensusUpdates,
+            CommitmentValueCodec,
+            MisbehaviourEvidenceDigest,
+            CommitmentPrefix,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> core::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> core::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "genesisValidatorsRoot" | "genesis_validators_root" => Ok(GeneratedField::GenesisValidatorsRoot),
+                            "minSyncCommitteeParticipants" | "min_sync_committee_participants" => Ok(GeneratedField::MinSyncCommitteeParticipants),
+                            "genesisTime" | "genesis_time" => Ok(GeneratedField::GenesisTime),
+                            "forkParameters" | "fork_parameters" => Ok(GeneratedField::ForkParameters),
+                            "secondsPerSlot" | "seconds_per_slot" => Ok(GeneratedField::SecondsPerSlot),
+                            "slotsPerEpoch" | "slots_per_epoch" => Ok(GeneratedField::SlotsPerEpoch),
+                            "epochsPerSyncCommitteePeriod" | "epochs_per_sync_committee_period" => Ok(GeneratedField::EpochsPerSyncCommitteePeriod),
+                            "ibcAddress" | "ibc_address" => Ok(GeneratedField::IbcAddress),
+                            "ibcCommitmentsSlot" | "ibc_commitments_slot" => Ok(GeneratedField::IbcCommitmentsSlot),
+                            "trustLevel" | "trust_level" => Ok(GeneratedField::TrustLevel),
+                            "trustingPeriod" | "trusting_period" => Ok(GeneratedField::TrustingPeriod),
+                            "maxClockDrift" | "max_clock_drift" => Ok(GeneratedField::MaxClockDrift),
+                            "latestExecutionBlockNumber" | "latest_execution_block_number" => Ok(GeneratedField::LatestExecutionBlockNumber),
+                            "frozenHeight" | "frozen_height" => Ok(GeneratedField::FrozenHeight),
+                            "maxIntermediateConsensusUpdates" | "max_intermediate_consensus_updates" => Ok(GeneratedField::MaxIntermediateConsensusUpdates),
+                            "commitmentValueCodec" | "commitment_value_codec" => Ok(GeneratedField::CommitmentValueCodec),
+                            "misbehaviourEvidenceDigest" | "misbehaviour_evidence_digest" => Ok(GeneratedField::MisbehaviourEvidenceDigest),
+                            "commitmentPrefix" | "commitment_prefix" => Ok(GeneratedField::CommitmentPrefix),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = ClientState;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                formatter.write_str("struct ibc.lightclients.ethereum.v1.ClientState")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> core::result::Result<ClientState, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut genesis_validators_root__ = None;
+                let mut min_sync_committee_participants__ = None;
+                let mut genesis_time__ = None;
+                let mut fork_parameters__ = None;
+                let mut seconds_per_slot__ = None;
+                let mut slots_per_epoch__ = None;
+                let mut epochs_per_sync_committee_period__ = None;
+                let mut ibc_address__ = None;
+                let mut ibc_commitments_slot__ = None;
+                let mut trust_level__ = None;
+                let mut trusting_period__ = None;
+                let mut max_clock_drift__ = None;
+                let mut latest_execution_block_number__ = None;
+                let mut frozen_height__ = None;
+                let mut max_intermediate_consensus_updates__ = None;
+                let mut commitment_value_codec__ = None;
+                let mut misbehaviour_evidence_digest__ = None;
+                let mut commitment_prefix__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::GenesisValidatorsRoot => {
+                            if genesis_validators_root__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("genesisValidatorsRoot"));
+                            }
+                            genesis_validators_root__ =
+                                Some(map.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::MinSyncCommitteeParticipants => {
+                            if min_sync_committee_participants__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("minSyncCommitteeParticipants"));
+                            }
+                            min_sync_committee_participants__ =
+                                Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::GenesisTime => {
+                            if genesis_time__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("genesisTime"));
+                            }
+                            genesis_time__ =
+                                Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::ForkParameters => {
+                            if fork_parameters__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("forkParameters"));
+                            }
+                            fork_parameters__ = map.next_value()?;
+                        }
+                        GeneratedField::SecondsPerSlot => {
+                            if seconds_per_slot__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("secondsPerSlot"));
+                            }
+                            seconds_per_slot__ =
+                                Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::SlotsPerEpoch => {
+                            if slots_per_epoch__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("slotsPerEpoch"));
+                            }
+                            slots_per_epoch__ =
+                                Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::EpochsPerSyncCommitteePeriod => {
+                            if epochs_per_sync_committee_period__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("epochsPerSyncCommitteePeriod"));
+                            }
+                            epochs_per_sync_committee_period__ =
+                                Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::IbcAddress => {
+                            if ibc_address__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("ibcAddress"));
+                            }
+                            ibc_address__ =
+                                Some(map.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::IbcCommitmentsSlot => {
+                            if ibc_commitments_slot__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("ibcCommitmentsSlot"));
+                            }
+                            ibc_commitments_slot__ =
+                                Some(map.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::TrustLevel => {
+                            if trust_level__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("trustLevel"));
+                            }
+                            trust_level__ = map.next_value()?;
+                        }
+                        GeneratedField::TrustingPeriod => {
+                            if trusting_period__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("trustingPeriod"));
+                            }
+                            trusting_period__ = map.next_value()?;
+                        }
+                        GeneratedField::MaxClockDrift => {
+                            if max_clock_drift__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("maxClockDrift"));
+                            }
+                            max_clock_drift__ = map.next_value()?;
+                        }
+                        GeneratedField::LatestExecutionBlockNumber => {
+                            if latest_execution_block_number__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("latestExecutionBlockNumber"));
+                            }
+                            latest_execution_block_number__ =
+                                Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::FrozenHeight => {
+                            if frozen_height__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("frozenHeight"));
+                            }
+                            frozen_height__ = map.next_value()?;
+                        }
+                        GeneratedField::MaxIntermediateConsensusUpdates => {
+                            if max_intermediate_consensus_updates__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("maxIntermediateConsensusUpdates"));
+                            }
+                            max_intermediate_consensus_updates__ =
+                                Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::CommitmentValueCodec => {
+                            if commitment_value_codec__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("commitmentValueCodec"));
+                            }
+                            commitment_value_codec__ = Some(map.next_value::<CommitmentValueCodec>()? as i32);
+                        }
+                        GeneratedField::MisbehaviourEvidenceDigest => {
+                            if misbehaviour_evidence_digest__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("misbehaviourEvidenceDigest"));
+                            }
+                            misbehaviour_evidence_digest__ =
+                                Some(map.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::CommitmentPrefix => {
+                            if commitment_prefix__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("commitmentPrefix"));
+                            }
+                            commitment_prefix__ =
+                                Some(map.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                    }
+                }
+                Ok(ClientState {
+                    genesis_validators_root: genesis_validators_root__.unwrap_or_default(),
+                    min_sync_committee_participants: min_sync_committee_participants__.unwrap_or_default(),
+                    genesis_time: genesis_time__.unwrap_or_default(),
+                    fork_parameters: fork_parameters__,
+                    seconds_per_slot: seconds_per_slot__.unwrap_or_default(),
+                    slots_per_epoch: slots_per_epoch__.unwrap_or_default(),
+                    epochs_per_sync_committee_period: epochs_per_sync_committee_period__.unwrap_or_default(),
+                    ibc_address: ibc_address__.unwrap_or_default(),
+                    ibc_commitments_slot: ibc_commitments_slot__.unwrap_or_default(),
+                    trust_level: trust_level__,
+                    trusting_period: trusting_period__,
+                    max_clock_drift: max_clock_drift__,
+                    latest_execution_block_number: latest_execution_block_number__.unwrap_or_default(),
+                    frozen_height: frozen_height__,
+                    max_intermediate_consensus_updates: max_intermediate_consensus_updates__.unwrap_or_default(),
+                    commitment_value_codec: commitment_value_codec__.unwrap_or_default(),
+                    misbehaviour_evidence_digest: misbehaviour_evidence_digest__.unwrap_or_default(),
+                    commitment_prefix: commitment_prefix__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("ibc.lightclients.ethereum.v1.ClientState", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for CommitmentValueCodec {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let variant = match self {
+            Self::Keccak256 => "COMMITMENT_VALUE_CODEC_KECCAK256",
+            Self::Raw => "COMMITMENT_VALUE_CODEC_RAW",
+        };
+        serializer.serialize_str(variant)
+    }
+}
+impl<'de> serde::Deserialize<'de> for CommitmentValueCodec {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "COMMITMENT_VALUE_CODEC_KECCAK256",
+            "COMMITMENT_VALUE_CODEC_RAW",
+        ];
+
+        struct GeneratedVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = CommitmentValueCodec;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(formatter, "expected one of: {:?}", &FIELDS)
+            }
+
+            fn visit_i64<E>(self, v: i64) -> core::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                use core::convert::TryFrom;
+                i32::try_from(v)
+                    .ok()
+                    .and_then(CommitmentValueCodec::from_i32)
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Signed(v), &self)
+                    })
+            }
+
+            fn visit_u64<E>(self, v: u64) -> core::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                use core::convert::TryFrom;
+                i32::try_from(v)
+                    .ok()
+                    .and_then(CommitmentValueCodec::from_i32)
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Unsigned(v), &self)
+                    })
+            }
+
+            fn visit_str<E>(self, value: &str) -> core::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                match value {
+                    "COMMITMENT_VALUE_CODEC_KECCAK256" => Ok(CommitmentValueCodec::Keccak256),
+                    "COMMITMENT_VALUE_CODEC_RAW" => Ok(CommitmentValueCodec::Raw),
+                    _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
+                }
+            }
+        }
+        deserializer.deserialize_any(GeneratedVisitor)
+    }
+}
+impl serde::Serialize for ConsensusState {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.slot != 0 {
+            len += 1;
+        }
+        if !self.storage_root.is_empty() {
+            len += 1;
+        }
+        if self.timestamp.is_some() {
+            len += 1;
+        }
+        if !self.current_sync_committee.is_empty() {
+            len += 1;
+        }
+        if !self.next_sync_committee.is_empty() {
+            len += 1;
+        }
+        if !self.current_block_hash.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("ibc.lightclients.ethereum.v1.ConsensusState", len)?;
+        if self.slot != 0 {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("slot", ToString::to_string(&self.slot).as_str())?;
+        }
+        if !self.storage_root.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("storageRoot", pbjson::private::base64::encode(&self.storage_root).as_str())?;
+        }
+        if let Some(v) = self.timestamp.as_ref() {
+            struct_ser.serialize_field("timestamp", v)?;
+        }
+        if !self.current_sync_committee.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("currentSyncCommittee", pbjson::private::base64::encode(&self.current_sync_committee).as_str())?;
+        }
+        if !self.next_sync_committee.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("nextSyncCommittee", pbjson::private::base64::encode(&self.next_sync_committee).as_str())?;
+        }
+        if !self.current_block_hash.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("currentBlockHash", pbjson::private::base64::encode(&self.current_block_hash).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for ConsensusState {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "slot",
+            "storage_root",
+            "storageRoot",
+            "timestamp",
+            "current_sync_committee",
+            "currentSyncCommittee",
+            "next_sync_committee",
+            "nextSyncCommittee",
+            "current_block_hash",
+            "currentBlockHash",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Slot,
+            StorageRoot,
+            Timestamp,
+            CurrentSyncCommittee,
+            NextSyncCommittee,
+            CurrentBlockHash,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> core::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> core::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "slot" => Ok(GeneratedField::Slot),
+                            "storageRoot" | "storage_root" => Ok(GeneratedField::StorageRoot),
+                            "timestamp" => Ok(GeneratedField::Timestamp),
+                            "currentSyncCommittee" | "current_sync_committee" => Ok(GeneratedField::CurrentSyncCommittee),
+                            "nextSyncCommittee" | "next_sync_committee" => Ok(GeneratedField::NextSyncCommittee),
+                            "currentBlockHash" | "current_block_hash" => Ok(GeneratedField::CurrentBlockHash),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = ConsensusState;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                formatter.write_str("struct ibc.lightclients.ethereum.v1.ConsensusState")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> core::result::Result<ConsensusState, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut slot__ = None;
+                let mut storage_root__ = None;
+                let mut timestamp__ = None;
+                let mut current_sync_committee__ = None;
+                let mut next_sync_committee__ = None;
+                let mut current_block_hash__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Slot => {
+                            if slot__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("slot"));
+                            }
+                            slot__ =
+                                Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::StorageRoot => {
+                            if storage_root__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("storageRoot"));
+                            }
+                            storage_root__ =
+                                Some(map.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::Timestamp => {
+                            if timestamp__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("timestamp"));
+                            }
+                            timestamp__ = map.next_value()?;
+                        }
+                        GeneratedField::CurrentSyncCommittee => {
+                            if current_sync_committee__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("currentSyncCommittee"));
+                            }
+                            current_sync_committee__ =
+                                Some(map.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::NextSyncCommittee => {
+                            if next_sync_committee__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("nextSyncCommittee"));
+                            }
+                            next_sync_committee__ =
+                                Some(map.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::CurrentBlockHash => {
+                            if current_block_hash__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("currentBlockHash"));
+                            }
+                            current_block_hash__ =
+                                Some(map.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                    }
+                }
+                Ok(ConsensusState {
+                    slot: slot__.unwrap_or_default(),
+                    storage_root: storage_root__.unwrap_or_default(),
+                    timestamp: timestamp__,
+                    current_sync_committee: current_sync_committee__.unwrap_or_default(),
+                    next_sync_committee: next_sync_committee__.unwrap_or_default(),
+                    current_block_hash: current_block_hash__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("ibc.lightclients.ethereum.v1.ConsensusState", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for ConsensusUpdate {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.attested_header.is_some() {
+            len += 1;
+        }
+        if self.next_sync_committee.is_some() {
+            len += 1;
+        }
+        if !self.next_sync_committee_branch.is_empty() {
+            len += 1;
+        }
+        if self.finalized_header.is_some() {
+            len += 1;
+        }
+        if !self.finalized_header_branch.is_empty() {
+            len += 1;
+        }
+        if !self.finalized_execution_root.is_empty() {
+            len += 1;
+        }
+        if !self.finalized_execution_branch.is_empty() {
+            len += 1;
+        }
+        if self.sync_aggregate.is_some() {
+            len += 1;
+        }
+        if self.signature_slot != 0 {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("ibc.lightclients.ethereum.v1.ConsensusUpdate", len)?;
+        if let Some(v) = self.attested_header.as_ref() {
+            struct_ser.serialize_field("attestedHeader", v)?;
+        }
+        if let Some(v) = self.next_sync_committee.as_ref() {
+            struct_ser.serialize_field("nextSyncCommittee", v)?;
+        }
+        if !self.next_sync_committee_branch.is_empty() {
+            struct_ser.serialize_field("nextSyncCommitteeBranch", &self.next_sync_committee_branch.iter().map(pbjson::private::base64::encode).collect::<Vec<_>>())?;
+        }
+        if let Some(v) = self.finalized_header.as_ref() {
+            struct_ser.serialize_field("finalizedHeader", v)?;
+        }
+        if !self.finalized_header_branch.is_empty() {
+            struct_ser.serialize_field("finalizedHeaderBranch", &self.finalized_header_branch.iter().map(pbjson::private::base64::encode).collect::<Vec<_>>())?;
+        }
+        if !self.finalized_execution_root.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("finalizedExecutionRoot", pbjson::private::base64::encode(&self.finalized_execution_root).as_str())?;
+        }
+        if !self.finalized_execution_branch.is_empty() {
+            struct_ser.serialize_field("finalizedExecutionBranch", &self.finalized_execution_branch.iter().map(pbjson::private::base64::encode).collect::<Vec<_>>())?;
+        }
+        if let Some(v) = self.sync_aggregate.as_ref() {
+            struct_ser.serialize_field("syncAggregate", v)?;
+        }
+        if self.signature_slot != 0 {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("signatureSlot", ToString::to_string(&self.signature_slot).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for ConsensusUpdate {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "attested_header",
+            "attestedHeader",
+            "next_sync_committee",
+            "nextSyncCommittee",
+            "next_sync_committee_branch",
+            "nextSyncCommitteeBranch",
+            "finalized_header",
+            "finalizedHeader",
+            "finalized_header_branch",
+            "finalizedHeaderBranch",
+            "finalized_execution_root",
+            "finalizedExecutionRoot",
+            "finalized_execution_branch",
+            "finalizedExecutionBranch",
+            "sync_aggregate",
+            "syncAggregate",
+            "signature_slot",
+            "signatureSlot",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            AttestedHeader,
+            NextSyncCommittee,
+            NextSyncCommitteeBranch,
+            FinalizedHeader,
+            FinalizedHeaderBranch,
+            FinalizedExecutionRoot,
+            FinalizedExecutionBranch,
+            SyncAggregate,
+            SignatureSlot,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> core::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> core::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "attestedHeader" | "attested_header" => Ok(GeneratedField::AttestedHeader),
+                            "nextSyncCommittee" | "next_sync_committee" => Ok(GeneratedField::NextSyncCommittee),
+                            "nextSyncCommitteeBranch" | "next_sync_committee_branch" => Ok(GeneratedField::NextSyncCommitteeBranch),
+                            "finalizedHeader" | "finalized_header" => Ok(GeneratedField::FinalizedHeader),
+                            "finalizedHeaderBranch" | "finalized_header_branch" => Ok(GeneratedField::FinalizedHeaderBranch),
+                            "finalizedExecutionRoot" | "finalized_execution_root" => Ok(GeneratedField::FinalizedExecutionRoot),
+                            "finalizedExecutionBranch" | "finalized_execution_branch" => Ok(GeneratedField::FinalizedExecutionBranch),
+                            "syncAggregate" | "sync_aggregate" => Ok(GeneratedField::SyncAggregate),
+                            "signatureSlot" | "signature_slot" => Ok(GeneratedField::SignatureSlot),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = ConsensusUpdate;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                formatter.write_str("struct ibc.lightclients.ethereum.v1.ConsensusUpdate")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> core::result::Result<ConsensusUpdate, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut attested_header__ = None;
+                let mut next_sync_committee__ = None;
+                let mut next_sync_committee_branch__ = None;
+                let mut finalized_header__ = None;
+                let mut finalized_header_branch__ = None;
+                let mut finalized_execution_root__ = None;
+                let mut finalized_execution_branch__ = None;
+                let mut sync_aggregate__ = None;
+                let mut signature_slot__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::AttestedHeader => {
+                            if attested_header__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("attestedHeader"));
+                            }
+                            attested_header__ = map.next_value()?;
+                        }
+                        GeneratedField::NextSyncCommittee => {
+                            if next_sync_committee__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("nextSyncCommittee"));
+                            }
+                            next_sync_committee__ = map.next_value()?;
+                        }
+                        GeneratedField::NextSyncCommitteeBranch => {
+                            if next_sync_committee_branch__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("nextSyncCommitteeBranch"));
+                            }
+                            next_sync_committee_branch__ =
+                                Some(map.next_value::<Vec<::pbjson::private::BytesDeserialize<_>>>()?
+                                    .into_iter().map(|x| x.0).collect())
+                            ;
+                        }
+                        GeneratedField::FinalizedHeader => {
+                            if finalized_header__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("finalizedHeader"));
+                            }
+                            finalized_header__ = map.next_value()?;
+                        }
+                        GeneratedField::FinalizedHeaderBranch => {
+                            if finalized_header_branch__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("finalizedHeaderBranch"));
+                            }
+                            finalized_header_branch__ =
+                                Some(map.next_value::<Vec<::pbjson::private::BytesDeserialize<_>>>()?
+                                    .into_iter().map(|x| x.0).collect())
+                            ;
+                        }
+                        GeneratedField::FinalizedExecutionRoot => {
+                            if finalized_execution_root__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("finalizedExecutionRoot"));
+                            }
+                            finalized_execution_root__ =
+                                Some(map.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::FinalizedExecutionBranch => {
+                            if finalized_execution_branch__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("finalizedExecutionBranch"));
+                            }
+                            finalized_execution_branch__ =
+                                Some(map.next_value::<Vec<::pbjson::private::BytesDeserialize<_>>>()?
+                                    .into_iter().map(|x| x.0).collect())
+                            ;
+                        }
+                        GeneratedField::SyncAggregate => {
+                            if sync_aggregate__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("syncAggregate"));
+                            }
+                            sync_aggregate__ = map.next_value()?;
+                        }
+                        GeneratedField::SignatureSlot => {
+                            if signature_slot__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("signatureSlot"));
+                            }
+                            signature_slot__ =
+                                Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                    }
+                }
+                Ok(ConsensusUpdate {
+                    attested_header: attested_header__,
+                    next_sync_committee: next_sync_committee__,
+                    next_sync_committee_branch: next_sync_committee_branch__.unwrap_or_default(),
+                    finalized_header: finalized_header__,
+                    finalized_header_branch: finalized_header_branch__.unwrap_or_default(),
+                    finalized_execution_root: finalized_execution_root__.unwrap_or_default(),
+                    finalized_execution_branch: finalized_execution_branch__.unwrap_or_default(),
+                    sync_aggregate: sync_aggregate__,
+                    signature_slot: signature_slot__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("ibc.lightclients.ethereum.v1.ConsensusUpdate", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for ExecutionUpdate {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.state_root.is_empty() {
+            len += 1;
+        }
+        if !self.state_root_branch.is_empty() {
+            len += 1;
+        }
+        if self.block_number != 0 {
+            len += 1;
+        }
+        if !self.block_number_branch.is_empty() {
+            len += 1;
+        }
+        if !self.block_hash.is_empty() {
+            len += 1;
+        }
+        if !self.block_hash_branch.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("ibc.lightclients.ethereum.v1.ExecutionUpdate", len)?;
+        if !self.state_root.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("stateRoot", pbjson::private::base64::encode(&self.state_root).as_str())?;
+        }
+        if !self.state_root_branch.is_empty() {
+            struct_ser.serialize_field("stateRootBranch", &self.state_root_branch.iter().map(pbjson::private::base64::encode).collect::<Vec<_>>())?;
+        }
+        if self.block_number != 0 {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("blockNumber", ToString::to_string(&self.block_number).as_str())?;
+        }
+        if !self.block_number_branch.is_empty() {
+            struct_ser.serialize_field("blockNumberBranch", &self.block_number_branch.iter().map(pbjson::private::base64::encode).collect::<Vec<_>>())?;
+        }
+        if !self.block_hash.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("blockHash", pbjson::private::base64::encode(&self.block_hash).as_str())?;
+        }
+        if !self.block_hash_branch.is_empty() {
+            struct_ser.serialize_field("blockHashBranch", &self.block_hash_branch.iter().map(pbjson::private::base64::encode).collect::<Vec<_>>())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for ExecutionUpdate {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "state_root",
+            "stateRoot",
+            "state_root_branch",
+            "stateRootBranch",
+            "block_number",
+            "blockNumber",
+            "block_number_branch",
+            "blockNumberBranch",
+            "block_hash",
+            "blockHash",
+            "block_hash_branch",
+            "blockHashBranch",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            StateRoot,
+            StateRootBranch,
+            BlockNumber,
+            BlockNumberBranch,
+            BlockHash,
+            BlockHashBranch,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> core::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> core::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "stateRoot" | "state_root" => Ok(GeneratedField::StateRoot),
+                            "stateRootBranch" | "state_root_branch" => Ok(GeneratedField::StateRootBranch),
+                            "blockNumber" | "block_number" => Ok(GeneratedField::BlockNumber),
+                            "blockNumberBranch" | "block_number_branch" => Ok(GeneratedField::BlockNumberBranch),
+                            "blockHash" | "block_hash" => Ok(GeneratedField::BlockHash),
+                            "blockHashBranch" | "block_hash_branch" => Ok(GeneratedField::BlockHashBranch),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = ExecutionUpdate;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                formatter.write_str("struct ibc.lightclients.ethereum.v1.ExecutionUpdate")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> core::result::Result<ExecutionUpdate, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut state_root__ = None;
+                let mut state_root_branch__ = None;
+                let mut block_number__ = None;
+                let mut block_number_branch__ = None;
+                let mut block_hash__ = None;
+                let mut block_hash_branch__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::StateRoot => {
+                            if state_root__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("stateRoot"));
+                            }
+                            state_root__ =
+                                Some(map.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::StateRootBranch => {
+                            if state_root_branch__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("stateRootBranch"));
+                            }
+                            state_root_branch__ =
+                                Some(map.next_value::<Vec<::pbjson::private::BytesDeserialize<_>>>()?
+                                    .into_iter().map(|x| x.0).collect())
+                            ;
+                        }
+                        GeneratedField::BlockNumber => {
+                            if block_number__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("blockNumber"));
+                            }
+                            block_number__ =
+                                Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::BlockNumberBranch => {
+                            if block_number_branch__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("blockNumberBranch"));
+                            }
+                            block_number_branch__ =
+                                Some(map.next_value::<Vec<::pbjson::private::BytesDeserialize<_>>>()?
+                                    .into_iter().map(|x| x.0).collect())
+                            ;
+                        }
+                        GeneratedField::BlockHash => {
+                            if block_hash__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("blockHash"));
+                            }
+                            block_hash__ =
+                                Some(map.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::BlockHashBranch => {
+                            if block_hash_branch__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("blockHashBranch"));
+                            }
+                            block_hash_branch__ =
+                                Some(map.next_value::<Vec<::pbjson::private::BytesDeserialize<_>>>()?
+                                    .into_iter().map(|x| x.0).collect())
+                            ;
+                        }
+                    }
+                }
+                Ok(ExecutionUpdate {
+                    state_root: state_root__.unwrap_or_default(),
+                    state_root_branch: state_root_branch__.unwrap_or_default(),
+                    block_number: block_number__.unwrap_or_default(),
+                    block_number_branch: block_number_branch__.unwrap_or_default(),
+                    block_hash: block_hash__.unwrap_or_default(),
+                    block_hash_branch: block_hash_branch__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("ibc.lightclients.ethereum.v1.ExecutionUpdate", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for FinalizedHeaderMisbehaviour {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.client_id.is_empty() {
+            len += 1;
+        }
+        if self.trusted_sync_committee.is_some() {
+            len += 1;
+        }
+        if self.consensus_update_1.is_some() {
+            len += 1;
+        }
+        if self.consensus_update_2.is_some() {
+            len += 1;
+        }
+        if self.trusted_sync_committee_1.is_some() {
+            len += 1;
+        }
+        if self.trusted_sync_committee_2.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("ibc.lightclients.ethereum.v1.FinalizedHeaderMisbehaviour", len)?;
+        if !self.client_id.is_empty() {
+            struct_ser.serialize_field("clientId", &self.client_id)?;
+        }
+        if let Some(v) = self.trusted_sync_committee.as_ref() {
+            struct_ser.serialize_field("trustedSyncCommittee", v)?;
+        }
+        if let Some(v) = self.consensus_update_1.as_ref() {
+            struct_ser.serialize_field("consensusUpdate1", v)?;
+        }
+        if let Some(v) = self.consensus_update_2.as_ref() {
+            struct_ser.serialize_field("consensusUpdate2", v)?;
+        }
+        if let Some(v) = self.trusted_sync_committee_1.as_ref() {
+            struct_ser.serialize_field("trustedSyncCommittee1", v)?;
+        }
+        if let Some(v) = self.trusted_sync_committee_2.as_ref() {
+            struct_ser.serialize_field("trustedSyncCommittee2", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for FinalizedHeaderMisbehaviour {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "client_id",
+            "clientId",
+            "trusted_sync_committee",
+            "trustedSyncCommittee",
+            "consensus_update_1",
+            "consensusUpdate1",
+            "consensus_update_2",
+            "consensusUpdate2",
+            "trusted_sync_committee_1",
+            "trustedSyncCommittee1",
+            "trusted_sync_committee_2",
+            "trustedSyncCommittee2",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            ClientId,
+            TrustedSyncCommittee,
+            ConsensusUpdate1,
+            ConsensusUpdate2,
+            TrustedSyncCommittee1,
+            TrustedSyncCommittee2,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> core::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> core::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "clientId" | "client_id" => Ok(GeneratedField::ClientId),
+                            "trustedSyncCommittee" | "trusted_sync_committee" => Ok(GeneratedField::TrustedSyncCommittee),
+                            "consensusUpdate1" | "consensus_update_1" => Ok(GeneratedField::ConsensusUpdate1),
+                            "consensusUpdate2" | "consensus_update_2" => Ok(GeneratedField::ConsensusUpdate2),
+                            "trustedSyncCommittee1" | "trusted_sync_committee_1" => Ok(GeneratedField::TrustedSyncCommittee1),
+                            "trustedSyncCommittee2" | "trusted_sync_committee_2" => Ok(GeneratedField::TrustedSyncCommittee2),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = FinalizedHeaderMisbehaviour;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                formatter.write_str("struct ibc.lightclients.ethereum.v1.FinalizedHeaderMisbehaviour")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> core::result::Result<FinalizedHeaderMisbehaviour, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut client_id__ = None;
+                let mut trusted_sync_committee__ = None;
+                let mut consensus_update_1__ = None;
+                let mut consensus_update_2__ = None;
+                let mut trusted_sync_committee_1__ = None;
+                let mut trusted_sync_committee_2__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::ClientId => {
+                            if client_id__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("clientId"));
+                            }
+                            client_id__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::TrustedSyncCommittee => {
+                            if trusted_sync_committee__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("trustedSyncCommittee"));
+                            }
+                            trusted_sync_committee__ = map.next_value()?;
+                        }
+                        GeneratedField::ConsensusUpdate1 => {
+                            if consensus_update_1__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("consensusUpdate1"));
+                            }
+                            consensus_update_1__ = map.next_value()?;
+                        }
+                        GeneratedField::ConsensusUpdate2 => {
+                            if consensus_update_2__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("consensusUpdate2"));
+                            }
+                            consensus_update_2__ = map.next_value()?;
+                        }
+                        GeneratedField::TrustedSyncCommittee1 => {
+                            if trusted_sync_committee_1__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("trustedSyncCommittee1"));
+                            }
+                            trusted_sync_committee_1__ = map.next_value()?;
+                        }
+                        GeneratedField::TrustedSyncCommittee2 => {
+                            if trusted_sync_committee_2__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("trustedSyncCommittee2"));
+                            }
+                            trusted_sync_committee_2__ = map.next_value()?;
+                        }
+                    }
+                }
+                Ok(FinalizedHeaderMisbehaviour {
+                    client_id: client_id__.unwrap_or_default(),
+                    trusted_sync_committee: trusted_sync_committee__,
+                    consensus_update_1: consensus_update_1__,
+                    consensus_update_2: consensus_update_2__,
+                    trusted_sync_committee_1: trusted_sync_committee_1__,
+                    trusted_sync_committee_2: trusted_sync_committee_2__,
+                })
+            }
+        }
+        deserializer.deserialize_struct("ibc.lightclients.ethereum.v1.FinalizedHeaderMisbehaviour", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for Fork {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.version.is_empty() {
+            len += 1;
+        }
+        if self.epoch != 0 {
+            len += 1;
+        }
+        if self.spec.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("ibc.lightclients.ethereum.v1.Fork", len)?;
+        if !self.version.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("version", pbjson::private::base64::encode(&self.version).as_str())?;
+        }
+        if self.epoch != 0 {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("epoch", ToString::to_string(&self.epoch).as_str())?;
+        }
+        if let Some(v) = self.spec.as_ref() {
+            struct_ser.serialize_field("spec", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for Fork {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "version",
+            "epoch",
+            "spec",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Version,
+            Epoch,
+            Spec,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> core::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> core::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "version" => Ok(GeneratedField::Version),
+                            "epoch" => Ok(GeneratedField::Epoch),
+                            "spec" => Ok(GeneratedField::Spec),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = Fork;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                formatter.write_str("struct ibc.lightclients.ethereum.v1.Fork")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> core::result::Result<Fork, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut version__ = None;
+                let mut epoch__ = None;
+                let mut spec__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Version => {
+                            if version__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("version"));
+                            }
+                            version__ =
+                                Some(map.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::Epoch => {
+                            if epoch__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("epoch"));
+                            }
+                            epoch__ =
+                                Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::Spec => {
+                            if spec__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("spec"));
+                            }
+                            spec__ = map.next_value()?;
+                        }
+                    }
+                }
+                Ok(Fork {
+                    version: version__.unwrap_or_default(),
+                    epoch: epoch__.unwrap_or_default(),
+                    spec: spec__,
+                })
+            }
+        }
+        deserializer.deserialize_struct("ibc.lightclients.ethereum.v1.Fork", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for ForkParameters {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.genesis_fork_version.is_empty() {
+            len += 1;
+        }
+        if !self.forks.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("ibc.lightclients.ethereum.v1.ForkParameters", len)?;
+        if !self.genesis_fork_version.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("genesisForkVersion", pbjson::private::base64::encode(&self.genesis_fork_version).as_str())?;
+        }
+        if !self.forks.is_empty() {
+            struct_ser.serialize_field("forks", &self.forks)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for ForkParameters {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "genesis_fork_version",
+            "genesisForkVersion",
+            "forks",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            GenesisForkVersion,
+            Forks,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> core::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> core::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "genesisForkVersion" | "genesis_fork_version" => Ok(GeneratedField::GenesisForkVersion),
+                            "forks" => Ok(GeneratedField::Forks),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = ForkParameters;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                formatter.write_str("struct ibc.lightclients.ethereum.v1.ForkParameters")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> core::result::Result<ForkParameters, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut genesis_fork_version__ = None;
+                let mut forks__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::GenesisForkVersion => {
+                            if genesis_fork_version__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("genesisForkVersion"));
+                            }
+                            genesis_fork_version__ =
+                                Some(map.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::Forks => {
+                            if forks__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("forks"));
+                            }
+                            forks__ = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(ForkParameters {
+                    genesis_fork_version: genesis_fork_version__.unwrap_or_default(),
+                    forks: forks__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("ibc.lightclients.ethereum.v1.ForkParameters", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for ForkSpec {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.finalized_root_gindex != 0 {
+            len += 1;
+        }
+        if self.current_sync_committee_gindex != 0 {
+            len += 1;
+        }
+        if self.next_sync_committee_gindex != 0 {
+            len += 1;
+        }
+        if self.execution_payload_gindex != 0 {
+            len += 1;
+        }
+        if self.execution_payload_state_root_gindex != 0 {
+            len += 1;
+        }
+        if self.execution_payload_block_number_gindex != 0 {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("ibc.lightclients.ethereum.v1.ForkSpec", len)?;
+        if self.finalized_root_gindex != 0 {
+            struct_ser.serialize_field("finalizedRootGindex", &self.finalized_root_gindex)?;
+        }
+        if self.current_sync_committee_gindex != 0 {
+            struct_ser.serialize_field("currentSyncCommitteeGindex", &self.current_sync_committee_gindex)?;
+        }
+        if self.next_sync_committee_gindex != 0 {
+            struct_ser.serialize_field("nextSyncCommitteeGindex", &self.next_sync_committee_gindex)?;
+        }
+        if self.execution_payload_gindex != 0 {
+            struct_ser.serialize_field("executionPayloadGindex", &self.execution_payload_gindex)?;
+        }
+        if self.execution_payload_state_root_gindex != 0 {
+            struct_ser.serialize_field("executionPayloadStateRootGindex", &self.execution_payload_state_root_gindex)?;
+        }
+        if self.execution_payload_block_number_gindex != 0 {
+            struct_ser.serialize_field("executionPayloadBlockNumberGindex", &self.execution_payload_block_number_gindex)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for ForkSpec {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "finalized_root_gindex",
+            "finalizedRootGindex",
+            "current_sync_committee_gindex",
+            "currentSyncCommitteeGindex",
+            "next_sync_committee_gindex",
+            "nextSyncCommitteeGindex",
+            "execution_payload_gindex",
+            "executionPayloadGindex",
+            "execution_payload_state_root_gindex",
+            "executionPayloadStateRootGindex",
+            "execution_payload_block_number_gindex",
+            "executionPayloadBlockNumberGindex",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            FinalizedRootGindex,
+            CurrentSyncCommitteeGindex,
+            NextSyncCommitteeGindex,
+            ExecutionPayloadGindex,
+            ExecutionPayloadStateRootGindex,
+            ExecutionPayloadBlockNumberGindex,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> core::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> core::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "finalizedRootGindex" | "finalized_root_gindex" => Ok(GeneratedField::FinalizedRootGindex),
+                            "currentSyncCommitteeGindex" | "current_sync_committee_gindex" => Ok(GeneratedField::CurrentSyncCommitteeGindex),
+                            "nextSyncCommitteeGindex" | "next_sync_committee_gindex" => Ok(GeneratedField::NextSyncCommitteeGindex),
+                            "executionPayloadGindex" | "execution_payload_gindex" => Ok(GeneratedField::ExecutionPayloadGindex),
+                            "executionPayloadStateRootGindex" | "execution_payload_state_root_gindex" => Ok(GeneratedField::ExecutionPayloadStateRootGindex),
+                            "executionPayloadBlockNumberGindex" | "execution_payload_block_number_gindex" => Ok(GeneratedField::ExecutionPayloadBlockNumberGindex),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = ForkSpec;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                formatter.write_str("struct ibc.lightclients.ethereum.v1.ForkSpec")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> core::result::Result<ForkSpec, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut finalized_root_gindex__ = None;
+                let mut current_sync_committee_gindex__ = None;
+                let mut next_sync_committee_gindex__ = None;
+                let mut execution_payload_gindex__ = None;
+                let mut execution_payload_state_root_gindex__ = None;
+                let mut execution_payload_block_number_gindex__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::FinalizedRootGindex => {
+                            if finalized_root_gindex__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("finalizedRootGindex"));
+                            }
+                            finalized_root_gindex__ =
+                                Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::CurrentSyncCommitteeGindex => {
+                            if current_sync_committee_gindex__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("currentSyncCommitteeGindex"));
+                            }
+                            current_sync_committee_gindex__ =
+                                Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::NextSyncCommitteeGindex => {
+                            if next_sync_committee_gindex__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("nextSyncCommitteeGindex"));
+                            }
+                            next_sync_committee_gindex__ =
+                                Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::ExecutionPayloadGindex => {
+                            if execution_payload_gindex__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("executionPayloadGindex"));
+                            }
+                            execution_payload_gindex__ =
+                                Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::ExecutionPayloadStateRootGindex => {
+                            if execution_payload_state_root_gindex__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("executionPayloadStateRootGindex"));
+                            }
+                            execution_payload_state_root_gindex__ =
+                                Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::ExecutionPayloadBlockNumberGindex => {
+                            if execution_payload_block_number_gindex__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("executionPayloadBlockNumberGindex"));
+                            }
+                            execution_payload_block_number_gindex__ =
+                                Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                    }
+                }
+                Ok(ForkSpec {
+                    finalized_root_gindex: finalized_root_gindex__.unwrap_or_default(),
+                    current_sync_committee_gindex: current_sync_committee_gindex__.unwrap_or_default(),
+                    next_sync_committee_gindex: next_sync_committee_gindex__.unwrap_or_default(),
+                    execution_payload_gindex: execution_payload_gindex__.unwrap_or_default(),
+                    execution_payload_state_root_gindex: execution_payload_state_root_gindex__.unwrap_or_default(),
+                    execution_payload_block_number_gindex: execution_payload_block_number_gindex__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("ibc.lightclients.ethereum.v1.ForkSpec", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for Fraction {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.numerator != 0 {
+            len += 1;
+        }
+        if self.denominator != 0 {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("ibc.lightclients.ethereum.v1.Fraction", len)?;
+        if self.numerator != 0 {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("numerator", ToString::to_string(&self.numerator).as_str())?;
+        }
+        if self.denominator != 0 {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("denominator", ToString::to_string(&self.denominator).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for Fraction {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "numerator",
+            "denominator",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Numerator,
+            Denominator,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> core::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> core::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "numerator" => Ok(GeneratedField::Numerator),
+                            "denominator" => Ok(GeneratedField::Denominator),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = Fraction;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                formatter.write_str("struct ibc.lightclients.ethereum.v1.Fraction")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> core::result::Result<Fraction, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut numerator__ = None;
+                let mut denominator__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Numerator => {
+                            if numerator__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("numerator"));
+                            }
+                            numerator__ =
+                                Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::Denominator => {
+                            if denominator__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("denominator"));
+                            }
+                            denominator__ =
+                                Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                    }
+                }
+                Ok(Fraction {
+                    numerator: numerator__.unwrap_or_default(),
+                    denominator: denominator__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("ibc.lightclients.ethereum.v1.Fraction", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for Header {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.trusted_sync_committee.is_some() {
+            len += 1;
+        }
+        if self.consensus_update.is_some() {
+            len += 1;
+        }
+        if self.execution_update.is_some() {
+            len += 1;
+        }
+        if self.account_update.is_some() {
+            len += 1;
+        }
+        if self.timestamp != 0 {
+            len += 1;
+        }
+        if !self.intermediate_consensus_updates.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("ibc.lightclients.ethereum.v1.Header", len)?;
+        if let Some(v) = self.trusted_sync_committee.as_ref() {
+            struct_ser.serialize_field("trustedSyncCommittee", v)?;
+        }
+        if let Some(v) = self.consensus_update.as_ref() {
+            struct_ser.serialize_field("consensusUpdate", v)?;
+        }
+        if let Some(v) = self.execution_update.as_ref() {
+            struct_ser.serialize_field("executionUpdate", v)?;
+        }
+        if let Some(v) = self.account_update.as_ref() {
+            struct_ser.serialize_field("accountUpdate", v)?;
+        }
+        if self.timestamp != 0 {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("timestamp", ToString::to_string(&self.timestamp).as_str())?;
+        }
+        if !self.intermediate_consensus_updates.is_empty() {
+            struct_ser.serialize_field("intermediateConsensusUpdates", &self.intermediate_consensus_updates)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for Header {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "trusted_sync_committee",
+            "trustedSyncCommittee",
+            "consensus_update",
+            "consensusUpdate",
+            "execution_update",
+            "executionUpdate",
+            "account_update",
+            "accountUpdate",
+            "timestamp",
+            "intermediate_consensus_updates",
+            "intermediateConsensusUpdates",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            TrustedSyncCommittee,
+            ConsensusUpdate,
+            ExecutionUpdate,
+            AccountUpdate,
+            Timestamp,
+            IntermediateConsensusUpdates,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> core::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> core::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "trustedSyncCommittee" | "trusted_sync_committee" => Ok(GeneratedField::TrustedSyncCommittee),
+                            "consensusUpdate" | "consensus_update" => Ok(GeneratedField::ConsensusUpdate),
+                            "executionUpdate" | "execution_update" => Ok(GeneratedField::ExecutionUpdate),
+                            "accountUpdate" | "account_update" => Ok(GeneratedField::AccountUpdate),
+                            "timestamp" => Ok(GeneratedField::Timestamp),
+                            "intermediateConsensusUpdates" | "intermediate_consensus_updates" => Ok(GeneratedField::IntermediateConsensusUpdates),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = Header;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                formatter.write_str("struct ibc.lightclients.ethereum.v1.Header")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> core::result::Result<Header, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut trusted_sync_committee__ = None;
+                let mut consensus_update__ = None;
+                let mut execution_update__ = None;
+                let mut account_update__ = None;
+                let mut timestamp__ = None;
+                let mut intermediate_consensus_updates__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::TrustedSyncCommittee => {
+                            if trusted_sync_committee__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("trustedSyncCommittee"));
+                            }
+                            trusted_sync_committee__ = map.next_value()?;
+                        }
+                        GeneratedField::ConsensusUpdate => {
+                            if consensus_update__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("consensusUpdate"));
+                            }
+                            consensus_update__ = map.next_value()?;
+                        }
+                        GeneratedField::ExecutionUpdate => {
+                            if execution_update__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("executionUpdate"));
+                            }
+                            execution_update__ = map.next_value()?;
+                        }
+                        GeneratedField::AccountUpdate => {
+                            if account_update__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("accountUpdate"));
+                            }
+                            account_update__ = map.next_value()?;
+                        }
+                        GeneratedField::Timestamp => {
+                            if timestamp__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("timestamp"));
+                            }
+                            timestamp__ =
+                                Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::IntermediateConsensusUpdates => {
+                            if intermediate_consensus_updates__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("intermediateConsensusUpdates"));
+                            }
+                            intermediate_consensus_updates__ = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(Header {
+                    trusted_sync_committee: trusted_sync_committee__,
+                    consensus_update: consensus_update__,
+                    execution_update: execution_update__,
+                    account_update: account_update__,
+                    timestamp: timestamp__.unwrap_or_default(),
+                    intermediate_consensus_updates: intermediate_consensus_updates__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("ibc.lightclients.ethereum.v1.Header", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for NextSyncCommitteeMisbehaviour {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.client_id.is_empty() {
+            len += 1;
+        }
+        if self.trusted_sync_committee.is_some() {
+            len += 1;
+        }
+        if self.consensus_update_1.is_some() {
+            len += 1;
+        }
+        if self.consensus_update_2.is_some() {
+            len += 1;
+        }
+        if self.trusted_sync_committee_1.is_some() {
+            len += 1;
+        }
+        if self.trusted_sync_committee_2.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("ibc.lightclients.ethereum.v1.NextSyncCommitteeMisbehaviour", len)?;
+        if !self.client_id.is_empty() {
+            struct_ser.serialize_field("clientId", &self.client_id)?;
+        }
+        if let Some(v) = self.trusted_sync_committee.as_ref() {
+            struct_ser.serialize_field("trustedSyncCommittee", v)?;
+        }
+        if let Some(v) = self.consensus_update_1.as_ref() {
+            struct_ser.serialize_field("consensusUpdate1", v)?;
+        }
+        if let Some(v) = self.consensus_update_2.as_ref() {
+            struct_ser.serialize_field("consensusUpdate2", v)?;
+        }
+        if let Some(v) = self.trusted_sync_committee_1.as_ref() {
+            struct_ser.serialize_field("trustedSyncCommittee1", v)?;
+        }
+        if let Some(v) = self.trusted_sync_committee_2.as_ref() {
+            struct_ser.serialize_field("trustedSyncCommittee2", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for NextSyncCommitteeMisbehaviour {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "client_id",
+            "clientId",
+            "trusted_sync_committee",
+            "trustedSyncCommittee",
+            "consensus_update_1",
+            "consensusUpdate1",
+            "consensus_update_2",
+            "consensusUpdate2",
+            "trusted_sync_committee_1",
+            "trustedSyncCommittee1",
+            "trusted_sync_committee_2",
+            "trustedSyncCommittee2",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            ClientId,
+            TrustedSyncCommittee,
+            ConsensusUpdate1,
+            ConsensusUpdate2,
+            TrustedSyncCommittee1,
+            TrustedSyncCommittee2,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> core::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> core::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "clientId" | "client_id" => Ok(GeneratedField::ClientId),
+                            "trustedSyncCommittee" | "trusted_sync_committee" => Ok(GeneratedField::TrustedSyncCommittee),
+                            "consensusUpdate1" | "consensus_update_1" => Ok(GeneratedField::ConsensusUpdate1),
+                            "consensusUpdate2" | "consensus_update_2" => Ok(GeneratedField::ConsensusUpdate2),
+                            "trustedSyncCommittee1" | "trusted_sync_committee_1" => Ok(GeneratedField::TrustedSyncCommittee1),
+                            "trustedSyncCommittee2" | "trusted_sync_committee_2" => Ok(GeneratedField::TrustedSyncCommittee2),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = NextSyncCommitteeMisbehaviour;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                formatter.write_str("struct ibc.lightclients.ethereum.v1.NextSyncCommitteeMisbehaviour")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> core::result::Result<NextSyncCommitteeMisbehaviour, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut client_id__ = None;
+                let mut trusted_sync_committee__ = None;
+                let mut consensus_update_1__ = None;
+                let mut consensus_update_2__ = None;
+                let mut trusted_sync_committee_1__ = None;
+                let mut trusted_sync_committee_2__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::ClientId => {
+                            if client_id__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("clientId"));
+                            }
+                            client_id__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::TrustedSyncCommittee => {
+                            if trusted_sync_committee__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("trustedSyncCommittee"));
+                            }
+                            trusted_sync_committee__ = map.next_value()?;
+                        }
+                        GeneratedField::ConsensusUpdate1 => {
+                            if consensus_update_1__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("consensusUpdate1"));
+                            }
+                            consensus_update_1__ = map.next_value()?;
+                        }
+                        GeneratedField::ConsensusUpdate2 => {
+                            if consensus_update_2__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("consensusUpdate2"));
+                            }
+                            consensus_update_2__ = map.next_value()?;
+                        }
+                        GeneratedField::TrustedSyncCommittee1 => {
+                            if trusted_sync_committee_1__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("trustedSyncCommittee1"));
+                            }
+                            trusted_sync_committee_1__ = map.next_value()?;
+                        }
+                        GeneratedField::TrustedSyncCommittee2 => {
+                            if trusted_sync_committee_2__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("trustedSyncCommittee2"));
+                            }
+                            trusted_sync_committee_2__ = map.next_value()?;
+                        }
+                    }
+                }
+                Ok(NextSyncCommitteeMisbehaviour {
+                    client_id: client_id__.unwrap_or_default(),
+                    trusted_sync_committee: trusted_sync_committee__,
+                    consensus_update_1: consensus_update_1__,
+                    consensus_update_2: consensus_update_2__,
+                    trusted_sync_committee_1: trusted_sync_committee_1__,
+                    trusted_sync_committee_2: trusted_sync_committee_2__,
+                })
+            }
+        }
+        deserializer.deserialize_struct("ibc.lightclients.ethereum.v1.NextSyncCommitteeMisbehaviour", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for SyncAggregate {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.sync_committee_bits.is_empty() {
+            len += 1;
+        }
+        if !self.sync_committee_signature.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("ibc.lightclients.ethereum.v1.SyncAggregate", len)?;
+        if !self.sync_committee_bits.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("syncCommitteeBits", pbjson::private::base64::encode(&self.sync_committee_bits).as_str())?;
+        }
+        if !self.sync_committee_signature.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("syncCommitteeSignature", pbjson::private::base64::encode(&self.sync_committee_signature).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for SyncAggregate {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "sync_committee_bits",
+            "syncCommitteeBits",
+            "sync_committee_signature",
+            "syncCommitteeSignature",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            SyncCommitteeBits,
+            SyncCommitteeSignature,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> core::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> core::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "syncCommitteeBits" | "sync_committee_bits" => Ok(GeneratedField::SyncCommitteeBits),
+                            "syncCommitteeSignature" | "sync_committee_signature" => Ok(GeneratedField::SyncCommitteeSignature),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = SyncAggregate;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                formatter.write_str("struct ibc.lightclients.ethereum.v1.SyncAggregate")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> core::result::Result<SyncAggregate, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut sync_committee_bits__ = None;
+                let mut sync_committee_signature__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::SyncCommitteeBits => {
+                            if sync_committee_bits__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("syncCommitteeBits"));
+                            }
+                            sync_committee_bits__ =
+                                Some(map.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::SyncCommitteeSignature => {
+                            if sync_committee_signature__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("syncCommitteeSignature"));
+                            }
+                            sync_committee_signature__ =
+                                Some(map.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                    }
+                }
+                Ok(SyncAggregate {
+                    sync_committee_bits: sync_committee_bits__.unwrap_or_default(),
+                    sync_committee_signature: sync_committee_signature__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("ibc.lightclients.ethereum.v1.SyncAggregate", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for SyncCommittee {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.pubkeys.is_empty() {
+            len += 1;
+        }
+        if !self.aggregate_pubkey.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("ibc.lightclients.ethereum.v1.SyncCommittee", len)?;
+        if !self.pubkeys.is_empty() {
+            struct_ser.serialize_field("pubkeys", &self.pubkeys.iter().map(pbjson::private::base64::encode).collect::<Vec<_>>())?;
+        }
+        if !self.aggregate_pubkey.is_empty() {
+            #[allow(clippy::needless_borrow)]
+            struct_ser.serialize_field("aggregatePubkey", pbjson::private::base64::encode(&self.aggregate_pubkey).as_str())?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for SyncCommittee {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "pubkeys",
+            "aggregate_pubkey",
+            "aggregatePubkey",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Pubkeys,
+            AggregatePubkey,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> core::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> core::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "pubkeys" => Ok(GeneratedField::Pubkeys),
+                            "aggregatePubkey" | "aggregate_pubkey" => Ok(GeneratedField::AggregatePubkey),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = SyncCommittee;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                formatter.write_str("struct ibc.lightclients.ethereum.v1.SyncCommittee")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> core::result::Result<SyncCommittee, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut pubkeys__ = None;
+                let mut aggregate_pubkey__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Pubkeys => {
+                            if pubkeys__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("pubkeys"));
+                            }
+                            pubkeys__ =
+                                Some(map.next_value::<Vec<::pbjson::private::BytesDeserialize<_>>>()?
+                                    .into_iter().map(|x| x.0).collect())
+                            ;
+                        }
+                        GeneratedField::AggregatePubkey => {
+                            if aggregate_pubkey__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("aggregatePubkey"));
+                            }
+                            aggregate_pubkey__ =
+                                Some(map.next_value::<::pbjson::private::BytesDeserialize<_>>()?.0)
+                            ;
+                        }
+                    }
+                }
+                Ok(SyncCommittee {
+                    pubkeys: pubkeys__.unwrap_or_default(),
+                    aggregate_pubkey: aggregate_pubkey__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("ibc.lightclients.ethereum.v1.SyncCommittee", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for TrustedSyncCommittee {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.trusted_height.is_some() {
+            len += 1;
+        }
+        if self.sync_committee.is_some() {
+            len += 1;
+        }
+        if self.is_next {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("ibc.lightclients.ethereum.v1.TrustedSyncCommittee", len)?;
+        if let Some(v) = self.trusted_height.as_ref() {
+            struct_ser.serialize_field("trustedHeight", v)?;
+        }
+        if let Some(v) = self.sync_committee.as_ref() {
+            struct_ser.serialize_field("syncCommittee", v)?;
+        }
+        if self.is_next {
+            struct_ser.serialize_field("isNext", &self.is_next)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for TrustedSyncCommittee {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> core::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "trusted_height",
+            "trustedHeight",
+            "sync_committee",
+            "syncCommittee",
+            "is_next",
+            "isNext",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            TrustedHeight,
+            SyncCommittee,
+            IsNext,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> core::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> core::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "trustedHeight" | "trusted_height" => Ok(GeneratedField::TrustedHeight),
+                            "syncCommittee" | "sync_committee" => Ok(GeneratedField::SyncCommittee),
+                            "isNext" | "is_next" => Ok(GeneratedField::IsNext),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = TrustedSyncCommittee;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                formatter.write_str("struct ibc.lightclients.ethereum.v1.TrustedSyncCommittee")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> core::result::Result<TrustedSyncCommittee, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut trusted_height__ = None;
+                let mut sync_committee__ = None;
+                let mut is_next__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::TrustedHeight => {
+                            if trusted_height__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("trustedHeight"));
+                            }
+                            trusted_height__ = map.next_value()?;
+                        }
+                        GeneratedField::SyncCommittee => {
+                            if sync_committee__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("syncCommittee"));
+                            }
+                            sync_committee__ = map.next_value()?;
+                        }
+                        GeneratedField::IsNext => {
+                            if is_next__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("isNext"));
+                            }
+                            is_next__ = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(TrustedSyncCommittee {
+                    trusted_height: trusted_height__,
+                    sync_committee: sync_committee__,
+                    is_next: is_next__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("ibc.lightclients.ethereum.v1.TrustedSyncCommittee", FIELDS, GeneratedVisitor)
+    }
+}